@@ -0,0 +1,95 @@
+//! Measures inputs where most insertions are outside the current hull, printing the results as CSV.
+//!
+//! Run with `cargo bench -p rita --bench hull_insertion`.
+use rita::{InsertOrder, Tetrahedralization, Triangulation};
+use rita_test_utils::{sample_vertices_2d, sample_vertices_3d};
+use std::time::Instant;
+
+/// Sort vertices along their first coordinate, so that every vertex is inserted beyond the current hull.
+fn sorted<const D: usize>(mut vertices: Vec<[f64; D]>) -> Vec<[f64; D]> {
+    vertices.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    vertices
+}
+
+/// Vertices in a thin annulus, so that many of them are hull vertices.
+///
+/// Vertices exactly on a circle are avoided, as the cocircular degeneracy dominates the runtime.
+fn in_annulus(n: usize) -> Vec<[f64; 2]> {
+    sample_vertices_2d(n, Some(-1.0..=1.0))
+        .into_iter()
+        .enumerate()
+        .map(|(i, [x, y])| {
+            let radius = 1.0 - 0.01 * (i % 100) as f64 / 100.0;
+            let angle = y.atan2(x);
+            [radius * angle.cos(), radius * angle.sin()]
+        })
+        .collect()
+}
+
+/// Vertices in a thin spherical shell, so that many of them are hull vertices.
+fn in_shell(n: usize) -> Vec<[f64; 3]> {
+    sample_vertices_3d(n, Some(-1.0..=1.0))
+        .into_iter()
+        .enumerate()
+        .map(|(i, [x, y, z])| {
+            let scale = (1.0 - 0.01 * (i % 100) as f64 / 100.0) / (x * x + y * y + z * z).sqrt();
+            [scale * x, scale * y, scale * z]
+        })
+        .collect()
+}
+
+fn main() {
+    println!("dim,num_vertices,input,order,millis,used_vertices,error");
+
+    for n in [10_000, 100_000] {
+        let inputs = [
+            ("uniform", sample_vertices_2d(n, None), InsertOrder::Hilbert),
+            (
+                "sorted",
+                sorted(sample_vertices_2d(n, None)),
+                InsertOrder::Input,
+            ),
+            ("annulus", in_annulus(n), InsertOrder::Hilbert),
+        ];
+        for (name, vertices, order) in inputs {
+            let now = Instant::now();
+            let mut triangulation = Triangulation::new(None);
+            let result = triangulation.insert_vertices_ordered(&vertices, None, order);
+            let millis = now.elapsed().as_millis();
+
+            match result {
+                Ok(_) => println!(
+                    "2,{n},{name},{order:?},{millis},{},",
+                    triangulation.num_used_vertices()
+                ),
+                Err(error) => println!("2,{n},{name},{order:?},{millis},,{error}"),
+            }
+        }
+    }
+
+    for n in [10_000, 50_000] {
+        let inputs = [
+            ("uniform", sample_vertices_3d(n, None), InsertOrder::Hilbert),
+            (
+                "sorted",
+                sorted(sample_vertices_3d(n, None)),
+                InsertOrder::Input,
+            ),
+            ("shell", in_shell(n), InsertOrder::Hilbert),
+        ];
+        for (name, vertices, order) in inputs {
+            let now = Instant::now();
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            let result = tetrahedralization.insert_vertices_ordered(&vertices, None, order);
+            let millis = now.elapsed().as_millis();
+
+            match result {
+                Ok(_) => println!(
+                    "3,{n},{name},{order:?},{millis},{},",
+                    tetrahedralization.num_used_vertices()
+                ),
+                Err(error) => println!("3,{n},{name},{order:?},{millis},,{error}"),
+            }
+        }
+    }
+}
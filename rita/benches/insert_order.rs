@@ -0,0 +1,71 @@
+//! Compares the insertion orders on weighted datasets, printing the results as CSV.
+//!
+//! Run with `cargo bench -p rita --bench insert_order`.
+use rita::{InsertOrder, Tetrahedralization, Triangulation};
+use rita_test_utils::{sample_vertices_2d, sample_vertices_3d, sample_weights};
+use std::time::Instant;
+
+const ORDERS: [(&str, InsertOrder); 3] = [
+    ("hilbert", InsertOrder::Hilbert),
+    ("weighted_hilbert", InsertOrder::WeightedHilbert),
+    ("random", InsertOrder::Random(42)),
+];
+
+/// Standard deviations of the weights, from barely to strongly weighted.
+const WEIGHT_STD_DEVS: [f64; 3] = [0.0005, 0.005, 0.05];
+
+/// Print a row, strongly weighted 3D inputs may fail to insert, which is reported instead of aborting.
+fn report(
+    dim: usize,
+    n: usize,
+    std_dev: f64,
+    order: &str,
+    millis: u128,
+    used: anyhow::Result<usize>,
+) {
+    match used {
+        Ok(used) => println!("{dim},{n},{std_dev},{order},{millis},{used},"),
+        Err(error) => println!("{dim},{n},{std_dev},{order},{millis},,{error}"),
+    }
+}
+
+fn main() {
+    println!("dim,num_vertices,weight_std_dev,order,millis,used_vertices,error");
+
+    for n in [10_000, 100_000] {
+        let vertices = sample_vertices_2d(n, None);
+        for std_dev in WEIGHT_STD_DEVS {
+            let weights = sample_weights(n, Some((0.0, std_dev)));
+            for (name, order) in ORDERS {
+                let now = Instant::now();
+                let mut triangulation = Triangulation::new(None);
+                let result =
+                    triangulation.insert_vertices_ordered(&vertices, Some(weights.clone()), order);
+                let millis = now.elapsed().as_millis();
+
+                let used = result.map(|_| triangulation.num_used_vertices());
+                report(2, n, std_dev, name, millis, used);
+            }
+        }
+    }
+
+    for n in [10_000, 50_000] {
+        let vertices = sample_vertices_3d(n, None);
+        for std_dev in WEIGHT_STD_DEVS {
+            let weights = sample_weights(n, Some((0.0, std_dev)));
+            for (name, order) in ORDERS {
+                let now = Instant::now();
+                let mut tetrahedralization = Tetrahedralization::new(None);
+                let result = tetrahedralization.insert_vertices_ordered(
+                    &vertices,
+                    Some(weights.clone()),
+                    order,
+                );
+                let millis = now.elapsed().as_millis();
+
+                let used = result.map(|_| tetrahedralization.num_used_vertices());
+                report(3, n, std_dev, name, millis, used);
+            }
+        }
+    }
+}
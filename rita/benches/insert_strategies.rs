@@ -0,0 +1,175 @@
+//! Compares the insertion strategies on the same datasets, printing the results as CSV.
+//!
+//! The 2D triangulation inserts by flipping, the 3D tetrahedralization by Bowyer-Watson.
+//! Each is measured for bulk insertion in the different [`InsertOrder`]s, and for single insertions in a spatially
+//! coherent order, once walking from the last inserted simplex and once from the first slot via a hint, i.e. without locality.
+//! Finally, the bulk insertion along the Hilbert curve is repeated with [`PredicateMode::FastInexact`].
+//!
+//! Run with `cargo bench -p rita --bench insert_strategies`.
+use rita::{InsertOrder, PredicateMode, Tetrahedralization, Triangulation};
+use rita_test_utils::{sample_vertices_2d, sample_vertices_3d};
+use std::time::Instant;
+
+const ORDERS: [(&str, InsertOrder); 3] = [
+    ("input", InsertOrder::Input),
+    ("hilbert", InsertOrder::Hilbert),
+    ("random", InsertOrder::Random(42)),
+];
+
+/// The predicates of the inexact runs, the tolerance is far above the rounding errors of the sampled vertices.
+const FAST_INEXACT: PredicateMode = PredicateMode::FastInexact { tolerance: 1e-12 };
+
+/// The number of vertices inserted in bulk, before the single insertions.
+const NUM_SEED_VERTICES: usize = 16;
+
+/// Order vertices in boustrophedon strips, which keeps consecutive vertices close without a space filling curve.
+///
+/// The strips are along the last axis, the previous axes are cut into `cells_per_axis` slabs each.
+fn snake_order<const D: usize>(mut vertices: Vec<[f64; D]>) -> Vec<[f64; D]> {
+    let cells_per_axis = (vertices.len() as f64).powf(1.0 / D as f64).ceil();
+    let cell = |v: &[f64; D]| -> Vec<i64> {
+        // the sampled coordinates are in [-0.5, 0.5]
+        v[..D - 1]
+            .iter()
+            .map(|&x| ((x + 0.5) * cells_per_axis) as i64)
+            .collect()
+    };
+    let key = |v: &[f64; D]| -> (Vec<i64>, f64) {
+        let mut cell = cell(v);
+        // flip every other slab and strip, such that each one starts where the previous ended
+        let mut flip = false;
+        for c in &mut cell {
+            if flip {
+                *c = -*c;
+            }
+            flip = *c % 2 != 0;
+        }
+        (cell, if flip { -v[D - 1] } else { v[D - 1] })
+    };
+
+    vertices.sort_by(|a, b| {
+        let (cell_a, x_a) = key(a);
+        let (cell_b, x_b) = key(b);
+        cell_a.cmp(&cell_b).then(x_a.total_cmp(&x_b))
+    });
+    vertices
+}
+
+/// Print a row, failed insertions are reported instead of aborting.
+fn report(
+    dim: usize,
+    n: usize,
+    algorithm: &str,
+    strategy: &str,
+    millis: u128,
+    used: anyhow::Result<usize>,
+) {
+    match used {
+        Ok(used) => println!("{dim},{n},{algorithm},{strategy},{millis},{used},"),
+        Err(error) => println!("{dim},{n},{algorithm},{strategy},{millis},,{error}"),
+    }
+}
+
+fn bench_2d(n: usize) {
+    let vertices = sample_vertices_2d(n, None);
+    for (name, order) in ORDERS {
+        let now = Instant::now();
+        let mut triangulation = Triangulation::new(None);
+        let result = triangulation.insert_vertices_ordered(&vertices, None, order);
+        let millis = now.elapsed().as_millis();
+
+        let used = result.map(|_| triangulation.num_used_vertices());
+        report(2, n, "flip", name, millis, used);
+    }
+
+    let now = Instant::now();
+    let mut triangulation = Triangulation::new(None);
+    triangulation.set_predicate_mode(FAST_INEXACT);
+    let result = triangulation.insert_vertices_ordered(&vertices, None, InsertOrder::Hilbert);
+    let millis = now.elapsed().as_millis();
+
+    let used = result.map(|_| triangulation.num_used_vertices());
+    report(2, n, "flip", "hilbert_fast_inexact", millis, used);
+
+    let vertices = snake_order(vertices);
+    for (name, from_last) in [("single_from_last", true), ("single_from_first", false)] {
+        let now = Instant::now();
+        let mut triangulation = Triangulation::new(None);
+        let result = triangulation
+            .insert_vertices(&vertices[..NUM_SEED_VERTICES], None, false)
+            .and_then(|_| {
+                for &v in &vertices[NUM_SEED_VERTICES..] {
+                    // without a hint the walk starts at the last inserted triangle, close in this order
+                    let near_to = if from_last {
+                        None
+                    } else {
+                        triangulation.tri_handle(0).ok()
+                    };
+                    triangulation.insert_vertex(v, None, near_to)?;
+                }
+                Ok(())
+            });
+        let millis = now.elapsed().as_millis();
+
+        let used = result.map(|()| triangulation.num_used_vertices());
+        report(2, n, "flip", name, millis, used);
+    }
+}
+
+fn bench_3d(n: usize) {
+    let vertices = sample_vertices_3d(n, None);
+    for (name, order) in ORDERS {
+        let now = Instant::now();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let result = tetrahedralization.insert_vertices_ordered(&vertices, None, order);
+        let millis = now.elapsed().as_millis();
+
+        let used = result.map(|_| tetrahedralization.num_used_vertices());
+        report(3, n, "bowyer_watson", name, millis, used);
+    }
+
+    let now = Instant::now();
+    let mut tetrahedralization = Tetrahedralization::new(None);
+    tetrahedralization.set_predicate_mode(FAST_INEXACT);
+    let result = tetrahedralization.insert_vertices_ordered(&vertices, None, InsertOrder::Hilbert);
+    let millis = now.elapsed().as_millis();
+
+    let used = result.map(|_| tetrahedralization.num_used_vertices());
+    report(3, n, "bowyer_watson", "hilbert_fast_inexact", millis, used);
+
+    let vertices = snake_order(vertices);
+    for (name, from_last) in [("single_from_last", true), ("single_from_first", false)] {
+        let now = Instant::now();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let result = tetrahedralization
+            .insert_vertices(&vertices[..NUM_SEED_VERTICES], None, false)
+            .and_then(|_| {
+                for &v in &vertices[NUM_SEED_VERTICES..] {
+                    // without a hint the walk starts at the last inserted tet, close in this order
+                    let near_to = if from_last {
+                        None
+                    } else {
+                        tetrahedralization.tet_handle(0).ok()
+                    };
+                    tetrahedralization.insert_vertex(v, None, near_to)?;
+                }
+                Ok(())
+            });
+        let millis = now.elapsed().as_millis();
+
+        let used = result.map(|()| tetrahedralization.num_used_vertices());
+        report(3, n, "bowyer_watson", name, millis, used);
+    }
+}
+
+fn main() {
+    println!("dim,num_vertices,algorithm,strategy,millis,used_vertices,error");
+
+    for n in [10_000, 100_000] {
+        bench_2d(n);
+    }
+
+    for n in [10_000, 50_000] {
+        bench_3d(n);
+    }
+}
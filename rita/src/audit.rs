@@ -0,0 +1,348 @@
+//! Validation of externally produced 2D meshes, and of the data structures of the triangulations.
+//!
+//! Re-uses the predicates of the triangulation to check, if a given triangle mesh fulfills the
+//! (weighted) Delaunay property, i.e. if all of its edges are locally regular.
+//! The [`SoundnessReport`] pinpoints broken links in the data structures themselves.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::RitaError;
+use crate::predicates;
+use crate::utils::types::{Vertex2, VertexIdx};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// An edge shared by two triangles, that violates the (weighted) Delaunay property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeViolation {
+    /// The vertex indices of the offending edge.
+    pub edge: [VertexIdx; 2],
+    /// The indices of the two triangles incident to the edge.
+    pub tris: [usize; 2],
+    /// The edge that replaces `edge` when flipping it, i.e. the two opposite vertices.
+    pub suggested_flip: [VertexIdx; 2],
+    /// Whether the flip can be performed, i.e. the quadrilateral formed by both triangles is convex.
+    ///
+    /// For weighted meshes a non-convex quadrilateral hints at a redundant vertex instead.
+    pub flippable: bool,
+}
+
+/// The result of [`check_delaunay`].
+#[derive(Debug, Clone, Default)]
+pub struct DelaunayReport {
+    /// The number of interior edges, i.e. edges shared by two triangles, that were checked.
+    pub num_edges_checked: usize,
+    /// Indices of triangles with three co-linear vertices.
+    pub flat_tris: Vec<usize>,
+    /// Interior edges that are not locally regular.
+    pub violations: Vec<EdgeViolation>,
+}
+
+impl DelaunayReport {
+    /// Check if the mesh is (weighted) Delaunay, i.e. there are no flat triangles and no violating edges.
+    pub fn is_delaunay(&self) -> bool {
+        self.flat_tris.is_empty() && self.violations.is_empty()
+    }
+}
+
+/// A broken link in the data structure of a triangulation or tetrahedralization.
+///
+/// In 2D hedges are given by their index. In 3D a hedge is given by `3 * half_tri_idx + i`,
+/// i.e. the `i`-th hedge of the half triangle, and a half triangle by `4 * tet_idx + i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundnessIssue {
+    /// The `next` hedge does not start where the hedge ends.
+    WrongNext(usize),
+    /// The `prev` hedge does not end where the hedge starts.
+    WrongPrev(usize),
+    /// 2D: the twin hedge does not connect the same nodes in reverse.
+    WrongTwin(usize),
+    /// 3D: the hedge on the opposite half triangle does not connect the same nodes in reverse.
+    WrongOpposite(usize),
+    /// 3D: the hedge on the neighboring half triangle of the same tet does not connect the same nodes in reverse.
+    WrongNeighbor(usize),
+    /// 3D: the opposite half triangle does not have the same nodes.
+    WrongOppositeTri(usize),
+    /// 3D: the tetrahedron has the same node more than once.
+    DuplicateNodes(usize),
+    /// 3D: the tetrahedron is still marked for deletion or keeping, i.e. an insertion was not finished.
+    Unfinished(usize),
+}
+
+impl core::fmt::Display for SoundnessIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongNext(hedge_idx) => write!(f, "Hedge {hedge_idx}: Wrong next hedge"),
+            Self::WrongPrev(hedge_idx) => write!(f, "Hedge {hedge_idx}: Wrong prev hedge"),
+            Self::WrongTwin(hedge_idx) => write!(f, "Hedge {hedge_idx}: Wrong twin hedge"),
+            Self::WrongOpposite(hedge_idx) => {
+                write!(f, "Hedge {hedge_idx}: Wrong opposite hedge")
+            }
+            Self::WrongNeighbor(hedge_idx) => {
+                write!(f, "Hedge {hedge_idx}: Wrong neighboring hedge")
+            }
+            Self::WrongOppositeTri(tri_idx) => {
+                write!(f, "Triangle {tri_idx}: Wrong opposite triangle")
+            }
+            Self::DuplicateNodes(tet_idx) => {
+                write!(f, "Tetrahedron {tet_idx}: tetrahedron with duplicate nodes")
+            }
+            Self::Unfinished(tet_idx) => write!(
+                f,
+                "Tetrahedron {tet_idx}: tetrahedron remaining after triangulation"
+            ),
+        }
+    }
+}
+
+/// The result of checking the data structure of a triangulation or tetrahedralization, e.g. `soundness_report`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoundnessReport {
+    /// The broken links, in the order of the checked simplices.
+    pub issues: Vec<SoundnessIssue>,
+}
+
+impl SoundnessReport {
+    /// Check if the data structure is sound, i.e. there are no issues.
+    pub fn is_sound(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// How thoroughly `check_regularity` of a triangulation or tetrahedralization checks the empty power circle property.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RegularityCheck {
+    /// Every vertex against every simplex, in `O(n·m)`, which counts each violated simplex.
+    #[default]
+    Full,
+    /// Every vertex against every simplex like [`Self::Full`], but stops at the first violated simplex.
+    EarlyExit,
+    /// Every vertex against `num_samples` random simplices, which estimates the degree of regularity.
+    Sampled { num_samples: usize, seed: u64 },
+    /// Every simplex against the vertices opposite to it in its neighbors, and every vertex that is not part of the
+    /// triangulation against the simplex containing it, found via locate, in `O(n log n)`.
+    ///
+    /// Locally regular simplices are regular, so this decides the regularity exactly, but counts violations per
+    /// simplex and vertex checked.
+    Local,
+}
+
+/// The result of `check_regularity` of a triangulation or tetrahedralization.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegularityReport {
+    /// The number of simplices checked, and for [`RegularityCheck::Local`] also of the vertices located.
+    pub num_checked: usize,
+    /// The number of checked simplices and vertices that violate the property, at most `1` for
+    /// [`RegularityCheck::EarlyExit`].
+    pub num_violated: usize,
+}
+
+impl RegularityReport {
+    /// Check if no violation was found.
+    pub const fn is_regular(&self) -> bool {
+        self.num_violated == 0
+    }
+
+    /// The share of the checks without violation, `1.0` if nothing was checked.
+    pub fn degree(&self) -> f64 {
+        if self.num_checked == 0 {
+            return 1.0;
+        }
+
+        1.0 - self.num_violated as f64 / self.num_checked as f64
+    }
+}
+
+/// Check an externally produced triangle mesh for the (weighted) Delaunay property.
+///
+/// The triangles may be given in any orientation. Every interior edge is tested for local
+/// regularity, which for a triangulation of a convex region is equivalent to the global property.
+/// An edge whose opposite vertex lies exactly on the power circle is legal, e.g. either diagonal of a grid cell.
+///
+/// ## Errors
+/// Returns an error if a triangle references a vertex out of bounds, the weights do not match
+/// the vertices, or an edge is shared by more than two triangles.
+pub fn check_delaunay(
+    vertices: &[Vertex2],
+    tri_indices: &[[VertexIdx; 3]],
+    weights: Option<&[f64]>,
+) -> HowResult<DelaunayReport> {
+    #[cfg(feature = "wasm")]
+    if weights.is_some() {
+        return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+    }
+
+    if let Some(weights) = weights {
+        if weights.len() != vertices.len() {
+            return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+                num_weights: weights.len(),
+                num_vertices: vertices.len(),
+            }));
+        }
+    }
+
+    let height = |v_idx: VertexIdx| {
+        vertices[v_idx][0].powi(2) + vertices[v_idx][1].powi(2)
+            - weights.map_or(0.0, |weights| weights[v_idx])
+    };
+
+    let mut report = DelaunayReport::default();
+
+    // bring all triangles in ccw order, such that the lifted orientation test has a consistent sign
+    let mut tris = Vec::with_capacity(tri_indices.len());
+    for (tri_idx, &[a, b, c]) in tri_indices.iter().enumerate() {
+        if let Some(&idx) = [a, b, c].iter().find(|&&idx| idx >= vertices.len()) {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "vertex",
+                idx,
+            }));
+        }
+
+        let orientation = predicates::orient_2d(&vertices[a], &vertices[b], &vertices[c]);
+        if orientation == 0.0 {
+            report.flat_tris.push(tri_idx);
+        }
+
+        tris.push(if orientation < 0.0 {
+            [a, c, b]
+        } else {
+            [a, b, c]
+        });
+    }
+
+    // collect the incident triangles of each undirected edge
+    let mut edge_tris: BTreeMap<[VertexIdx; 2], Vec<usize>> = BTreeMap::new();
+    for (tri_idx, tri) in tris.iter().enumerate() {
+        for i in 0..3 {
+            let (a, b) = (tri[i], tri[(i + 1) % 3]);
+            edge_tris
+                .entry([a.min(b), a.max(b)])
+                .or_default()
+                .push(tri_idx);
+        }
+    }
+
+    for (edge, incident) in &edge_tris {
+        match incident.len() {
+            1 => continue, // boundary edge
+            2 => (),
+            _ => {
+                return Err(anyhow::Error::new(RitaError::NonManifoldEdge {
+                    edge: *edge,
+                }));
+            }
+        }
+
+        report.num_edges_checked += 1;
+
+        let [t0, t1] = [incident[0], incident[1]];
+        let opposite = |tri: &[VertexIdx; 3]| *tri.iter().find(|v| !edge.contains(v)).unwrap();
+        let c = opposite(&tris[t0]);
+        let d = opposite(&tris[t1]);
+
+        let [a, b, e] = tris[t0].map(|v_idx| vertices[v_idx]);
+        let [h_a, h_b, h_e] = tris[t0].map(height);
+        // without symbolic perturbation, such that either diagonal of cocircular vertices is legal
+        let in_circle =
+            predicates::orient_2dlifted(&a, &b, &e, &vertices[d], h_a, h_b, h_e, height(d));
+
+        if in_circle > 0.0 {
+            let [p, q] = edge.map(|v_idx| vertices[v_idx]);
+            let (vc, vd) = (vertices[c], vertices[d]);
+            let flippable =
+                predicates::orient_2d(&vc, &vd, &p) * predicates::orient_2d(&vc, &vd, &q) < 0.0;
+
+            report.violations.push(EdgeViolation {
+                edge: *edge,
+                tris: [t0, t1],
+                suggested_flip: [c, d],
+                flippable,
+            });
+        }
+    }
+
+    HowOk(report)
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    // a convex quadrilateral, where the diagonal 1-3 is Delaunay and 0-2 is not
+    const QUAD: [Vertex2; 4] = [[0.0, 0.0], [2.0, -1.0], [4.0, 0.0], [2.0, 1.0]];
+
+    #[test]
+    fn test_check_delaunay() {
+        let report = check_delaunay(&QUAD, &[[0, 1, 3], [1, 2, 3]], None).unwrap();
+        assert!(report.is_delaunay());
+        assert_eq!(report.num_edges_checked, 1);
+
+        // clockwise triangles are fine too
+        let report = check_delaunay(&QUAD, &[[0, 3, 1], [1, 3, 2]], None).unwrap();
+        assert!(report.is_delaunay());
+
+        let report = check_delaunay(&QUAD, &[[0, 1, 2], [0, 2, 3]], None).unwrap();
+        assert!(!report.is_delaunay());
+        assert_eq!(
+            report.violations,
+            vec![EdgeViolation {
+                edge: [0, 2],
+                tris: [0, 1],
+                suggested_flip: [1, 3],
+                flippable: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_delaunay_cocircular() {
+        // both diagonals of a square are Delaunay
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        for tris in [[[0, 1, 2], [0, 2, 3]], [[0, 1, 3], [1, 2, 3]]] {
+            let report = check_delaunay(&square, &tris, None).unwrap();
+            assert!(report.is_delaunay());
+            assert_eq!(report.num_edges_checked, 1);
+        }
+
+        // a grid, with the cells split along alternating diagonals
+        let n = 4;
+        let grid: Vec<Vertex2> = (0..n * n)
+            .map(|i| [f64::from(i % n), f64::from(i / n)])
+            .collect();
+        let mut tris = Vec::new();
+        for y in 0..n - 1 {
+            for x in 0..n - 1 {
+                let [a, b, c, d] = [[x, y], [x + 1, y], [x + 1, y + 1], [x, y + 1]]
+                    .map(|[x, y]| (y * n + x) as VertexIdx);
+                if (x + y) % 2 == 0 {
+                    tris.extend([[a, b, c], [a, c, d]]);
+                } else {
+                    tris.extend([[a, b, d], [b, c, d]]);
+                }
+            }
+        }
+        let report = check_delaunay(&grid, &tris, None).unwrap();
+        assert!(report.is_delaunay());
+        assert_eq!(report.num_edges_checked, 21);
+    }
+
+    #[test]
+    fn test_check_delaunay_errors() {
+        let error = |tris: &[[usize; 3]]| {
+            *check_delaunay(&QUAD, tris, None)
+                .unwrap_err()
+                .downcast_ref::<RitaError>()
+                .unwrap()
+        };
+        assert_eq!(
+            error(&[[0, 1, 4]]),
+            RitaError::IndexOutOfBounds {
+                kind: "vertex",
+                idx: 4
+            }
+        );
+        assert!(matches!(
+            error(&[[0, 1, 2], [0, 2, 3], [0, 2, 1]]),
+            RitaError::NonManifoldEdge { .. }
+        ));
+    }
+}
@@ -0,0 +1,623 @@
+//! Tessellation of ball packings into Laguerre cells, e.g. for granular or foam simulations.
+//!
+//! The Laguerre cell of a ball is its power cell, with the squared radius as the weight. The cells of all balls
+//! partition the space, each cell contains the part of its ball, that is not shared with a neighboring ball.
+//! They are the dual of the regular triangulation or tetrahedralization of the centers, see [`power_cells`](crate::voronoi::power_cells).
+//!
+//! Here, each cell is clipped to a box shaped domain, and the faces shared by two cells give the contact areas of the balls.
+//! Balls whose cell vanishes, e.g. small balls inside larger ones, have empty cells.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    RitaError, Tetrahedralization, Triangulation,
+    decimation::vertex_neighbors,
+    geometry::{cross, dot, polygon_area, sub},
+    utils::types::{Vertex2, Vertex3, VertexIdx},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The Laguerre cell of a disk, clipped to the domain.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaguerreCell2 {
+    /// The corners of the cell, in counter-clockwise order.
+    pub polygon: Vec<Vertex2>,
+    /// For each edge from `polygon[i]` to `polygon[i + 1]`, the disk on the other side, `None` for the domain boundary.
+    pub neighbors: Vec<Option<usize>>,
+    /// The area of the cell.
+    pub area: f64,
+    /// The centroid of the cell, the center of the disk for empty cells.
+    pub centroid: Vertex2,
+}
+
+/// A face of the Laguerre cell of a ball.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellFace {
+    /// The ball on the other side of the face, `None` for the domain boundary.
+    pub neighbor: Option<usize>,
+    /// The corners of the face, in counter-clockwise order seen from outside the cell.
+    pub polygon: Vec<Vertex3>,
+    /// The area of the face.
+    pub area: f64,
+}
+
+/// The Laguerre cell of a ball, clipped to the domain.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaguerreCell3 {
+    /// The faces of the cell, i.e. a convex polyhedron.
+    pub faces: Vec<CellFace>,
+    /// The volume of the cell.
+    pub volume: f64,
+    /// The centroid of the cell, the center of the ball for empty cells.
+    pub centroid: Vertex3,
+}
+
+impl LaguerreCell2 {
+    /// Check if the cell is empty, e.g. the disk is redundant or outside of the domain.
+    pub fn is_empty(&self) -> bool {
+        self.polygon.is_empty()
+    }
+}
+
+impl LaguerreCell3 {
+    /// Check if the cell is empty, e.g. the ball is redundant or outside of the domain.
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+}
+
+/// Two balls, whose cells share a face.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// The two balls, the smaller index first.
+    pub balls: [usize; 2],
+    /// The area of the shared face, i.e. the length of the shared edge in 2D.
+    pub area: f64,
+}
+
+/// The cells of a ball packing, and the contacts between the balls.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BallTessellation<C> {
+    /// One cell per ball, in the order of the input.
+    pub cells: Vec<C>,
+    /// The pairs of balls, whose cells share a face of positive area.
+    pub contacts: Vec<Contact>,
+}
+
+/// Tessellate a packing of balls into their Laguerre cells, clipped to the box `domain`.
+///
+/// `domain` is `[min, max]` of the box. The balls are the weighted vertices of a tetrahedralization, with `r²` as weight.
+/// Each cell is the box, clipped by the power bisectors to the neighbors of its ball in the tetrahedralization.
+///
+/// ## Errors
+/// Returns an error if the number of radii does not match, a center or radius is not finite, a radius is negative,
+/// the domain is empty, or the centers do not span a tetrahedron. Weights are not supported with the `wasm` feature.
+pub fn tessellate(
+    centers: &[Vertex3],
+    radii: &[f64],
+    domain: [Vertex3; 2],
+) -> HowResult<BallTessellation<LaguerreCell3>> {
+    let weights = check_args(centers, radii, domain)?;
+
+    let mut tetrahedralization = Tetrahedralization::new(None);
+    tetrahedralization.insert_vertices(centers, Some(weights.clone()), true)?;
+
+    let mut neighbors = vec![Vec::new(); centers.len()];
+    for tet in tetrahedralization.tet_indices() {
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    neighbors[tet[i]].push(tet[j]);
+                }
+            }
+        }
+    }
+
+    let cells: Vec<LaguerreCell3> = centers
+        .iter()
+        .enumerate()
+        .map(|(v_idx, &v)| {
+            let ring = &mut neighbors[v_idx];
+            ring.sort_unstable();
+            ring.dedup();
+            if ring.is_empty() {
+                return LaguerreCell3 {
+                    centroid: v,
+                    ..LaguerreCell3::default()
+                };
+            }
+
+            let mut faces = box_faces(domain, v);
+            for &u_idx in ring.iter() {
+                let (normal, offset) = power_bisector(centers, &weights, v_idx, u_idx);
+                faces = clip_polyhedron(&faces, normal, offset, Some(u_idx));
+                if faces.is_empty() {
+                    break;
+                }
+            }
+
+            laguerre_cell_3d(&faces, v)
+        })
+        .collect();
+
+    let contacts = contacts(cells.iter().map(|cell| {
+        cell.faces
+            .iter()
+            .filter_map(|face| face.neighbor.map(|u_idx| (u_idx, face.area)))
+    }));
+
+    HowOk(BallTessellation { cells, contacts })
+}
+
+/// Tessellate a packing of disks into their Laguerre cells, clipped to the box `domain`, see [`tessellate`].
+///
+/// ## Errors
+/// Returns an error if the number of radii does not match, a center or radius is not finite, a radius is negative,
+/// the domain is empty, or the centers do not span a triangle. Weights are not supported with the `wasm` feature.
+pub fn tessellate_2d(
+    centers: &[Vertex2],
+    radii: &[f64],
+    domain: [Vertex2; 2],
+) -> HowResult<BallTessellation<LaguerreCell2>> {
+    let weights = check_args(centers, radii, domain)?;
+
+    let mut triangulation = Triangulation::new(None);
+    triangulation.insert_vertices(centers, Some(weights.clone()), true)?;
+
+    let [min, max] = domain;
+    let cells: Vec<LaguerreCell2> = vertex_neighbors(&triangulation)
+        .iter()
+        .enumerate()
+        .map(|(v_idx, ring)| {
+            let v = centers[v_idx];
+            if ring.is_empty() {
+                return LaguerreCell2 {
+                    centroid: v,
+                    ..LaguerreCell2::default()
+                };
+            }
+
+            // the box, in coordinates relative to the center
+            let mut polygon = [
+                [min[0], min[1]],
+                [max[0], min[1]],
+                [max[0], max[1]],
+                [min[0], max[1]],
+            ]
+            .map(|p| (sub(p, v), None))
+            .to_vec();
+            for &u_idx in ring {
+                let (normal, offset) = power_bisector(centers, &weights, v_idx, u_idx);
+                polygon = clip_polygon(&polygon, normal, offset, Some(u_idx));
+                if polygon.is_empty() {
+                    break;
+                }
+            }
+
+            laguerre_cell_2d(&polygon, v)
+        })
+        .collect();
+
+    let contacts = contacts(cells.iter().map(|cell| {
+        let len = cell.polygon.len();
+        (0..len).filter_map(move |i| {
+            let [p, q] = [cell.polygon[i], cell.polygon[(i + 1) % len]];
+            cell.neighbors[i].map(|u_idx| (u_idx, (q[0] - p[0]).hypot(q[1] - p[1])))
+        })
+    }));
+
+    HowOk(BallTessellation { cells, contacts })
+}
+
+/// Check the arguments and compute the weights of the balls.
+fn check_args<const D: usize>(
+    centers: &[[f64; D]],
+    radii: &[f64],
+    [min, max]: [[f64; D]; 2],
+) -> HowResult<Vec<f64>> {
+    if cfg!(feature = "wasm") {
+        return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+    }
+
+    if radii.len() != centers.len() {
+        return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+            num_weights: radii.len(),
+            num_vertices: centers.len(),
+        }));
+    }
+    if !centers
+        .iter()
+        .flatten()
+        .chain(radii)
+        .chain(min.iter().chain(&max))
+        .all(|x| x.is_finite())
+    {
+        return Err(anyhow::Error::new(RitaError::NonFinite));
+    }
+    if let Some(idx) = radii.iter().position(|&r| r < 0.0) {
+        return Err(anyhow::Error::new(RitaError::NegativeRadius { idx }));
+    }
+    if (0..D).any(|i| min[i] >= max[i]) {
+        return Err(anyhow::Error::new(RitaError::EmptyDomain));
+    }
+
+    HowOk(radii.iter().map(|r| r * r).collect())
+}
+
+/// The power bisector of `v` to `u`, as `normal · p <= offset` for `p` relative to `v`.
+///
+/// I.e. `|p|² - w_v <= |p - d|² - w_u` for the neighbor `u` at `d` relative to `v`.
+fn power_bisector<const D: usize>(
+    centers: &[[f64; D]],
+    weights: &[f64],
+    v_idx: VertexIdx,
+    u_idx: VertexIdx,
+) -> ([f64; D], f64) {
+    let d = sub(centers[u_idx], centers[v_idx]);
+    let d_sq: f64 = d.iter().map(|x| x * x).sum();
+
+    (d, (d_sq - weights[u_idx] + weights[v_idx]) / 2.0)
+}
+
+/// The pairs of cells sharing a face, from the neighbors and face areas of each cell.
+fn contacts<I: Iterator<Item = (usize, f64)>>(cells: impl Iterator<Item = I>) -> Vec<Contact> {
+    let mut contacts: Vec<Contact> = cells
+        .enumerate()
+        .flat_map(|(v_idx, faces)| {
+            faces
+                .filter(move |&(u_idx, area)| v_idx < u_idx && area > 0.0)
+                .map(move |(u_idx, area)| Contact {
+                    balls: [v_idx, u_idx],
+                    area,
+                })
+        })
+        .collect();
+    contacts.sort_by_key(|contact| contact.balls);
+
+    contacts
+}
+
+/// Clip a convex polygon to the half-space `normal · p <= offset`, in 3D the polygon is planar.
+///
+/// Each vertex carries the label of the edge to the next vertex, the new edge along the cut gets `label`.
+pub(crate) fn clip_polygon<const D: usize>(
+    polygon: &[([f64; D], Option<usize>)],
+    normal: [f64; D],
+    offset: f64,
+    label: Option<usize>,
+) -> Vec<([f64; D], Option<usize>)> {
+    let side = |p: [f64; D]| (0..D).map(|i| normal[i] * p[i]).sum::<f64>() - offset;
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &(p, p_label)) in polygon.iter().enumerate() {
+        let q = polygon[(i + 1) % polygon.len()].0;
+        let (side_p, side_q) = (side(p), side(q));
+        let crossing = || {
+            let t = side_p / (side_p - side_q);
+            core::array::from_fn(|i| p[i] + t * (q[i] - p[i]))
+        };
+
+        if side_p <= 0.0 {
+            // the edge leaves the half-plane, so the new edge starts at the crossing
+            if side_q > 0.0 {
+                clipped.push((p, p_label));
+                if side_p < 0.0 {
+                    clipped.push((crossing(), label));
+                } else {
+                    clipped.last_mut().unwrap().1 = label;
+                }
+            } else {
+                clipped.push((p, p_label));
+            }
+        } else if side_q < 0.0 {
+            // the edge enters the half-plane, the remaining part keeps its label
+            clipped.push((crossing(), p_label));
+        }
+    }
+
+    if clipped.len() < 3 {
+        clipped.clear();
+    }
+    clipped
+}
+
+/// The cell of a polygon relative to `v`.
+fn laguerre_cell_2d(polygon: &[(Vertex2, Option<usize>)], v: Vertex2) -> LaguerreCell2 {
+    let (points, neighbors): (Vec<Vertex2>, Vec<Option<usize>>) = polygon.iter().copied().unzip();
+    let area = polygon_area(&points);
+    if points.is_empty() || area <= 0.0 {
+        return LaguerreCell2 {
+            centroid: v,
+            ..LaguerreCell2::default()
+        };
+    }
+
+    let mut centroid = [0.0; 2];
+    for i in 1..points.len() - 1 {
+        let [a, b] = [sub(points[i], points[0]), sub(points[i + 1], points[0])];
+        let cross = a[0] * b[1] - a[1] * b[0];
+        centroid[0] += (a[0] + b[0]) * cross;
+        centroid[1] += (a[1] + b[1]) * cross;
+    }
+
+    LaguerreCell2 {
+        polygon: points.iter().map(|p| [p[0] + v[0], p[1] + v[1]]).collect(),
+        neighbors,
+        area,
+        centroid: core::array::from_fn(|i| v[i] + points[0][i] + centroid[i] / (6.0 * area)),
+    }
+}
+
+/// The six faces of the box, relative to `v`, each counter-clockwise seen from outside.
+fn box_faces([min, max]: [Vertex3; 2], v: Vertex3) -> Vec<(Option<usize>, Vec<Vertex3>)> {
+    let corner = |i: usize| {
+        let p = [
+            if i & 1 == 0 { min[0] } else { max[0] },
+            if i & 2 == 0 { min[1] } else { max[1] },
+            if i & 4 == 0 { min[2] } else { max[2] },
+        ];
+        sub(p, v)
+    };
+
+    [
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+    ]
+    .iter()
+    .map(|face| (None, face.iter().map(|&i| corner(i)).collect()))
+    .collect()
+}
+
+/// Clip a convex polyhedron to the half-space `normal · p <= offset`, closing it with a new face labeled `label`.
+fn clip_polyhedron(
+    faces: &[(Option<usize>, Vec<Vertex3>)],
+    normal: Vertex3,
+    offset: f64,
+    label: Option<usize>,
+) -> Vec<(Option<usize>, Vec<Vertex3>)> {
+    let side = |p: Vertex3| dot(normal, p) - offset;
+
+    let mut clipped = Vec::with_capacity(faces.len() + 1);
+    let mut cap = Vec::new();
+    for (face_label, polygon) in faces {
+        let mut face = Vec::with_capacity(polygon.len() + 1);
+        for (i, &p) in polygon.iter().enumerate() {
+            let q = polygon[(i + 1) % polygon.len()];
+            let (side_p, side_q) = (side(p), side(q));
+
+            if side_p <= 0.0 {
+                face.push(p);
+            }
+            if side_p == 0.0 {
+                cap.push(p);
+            }
+            if (side_p < 0.0 && side_q > 0.0) || (side_p > 0.0 && side_q < 0.0) {
+                let t = side_p / (side_p - side_q);
+                let crossing = core::array::from_fn(|j| p[j] + t * (q[j] - p[j]));
+                face.push(crossing);
+                cap.push(crossing);
+            }
+        }
+
+        if face.len() >= 3 {
+            clipped.push((*face_label, face));
+        }
+    }
+
+    if clipped.is_empty() {
+        return clipped;
+    }
+
+    // the cap is convex, so its corners are ordered by their angle around its center
+    let scale = faces
+        .iter()
+        .flat_map(|(_, polygon)| polygon.iter().flatten())
+        .fold(0.0_f64, |scale, x| scale.max(x.abs()));
+    let mut deduped: Vec<Vertex3> = Vec::with_capacity(cap.len());
+    for p in cap {
+        if !deduped
+            .iter()
+            .any(|q| sub(p, *q).iter().all(|x| x.abs() <= 1e-12 * scale))
+        {
+            deduped.push(p);
+        }
+    }
+    if deduped.len() >= 3 {
+        let center: Vertex3 = core::array::from_fn(|j| {
+            deduped.iter().map(|p| p[j]).sum::<f64>() / deduped.len() as f64
+        });
+        let u = sub(deduped[0], center);
+        let w = cross(normal, u);
+        deduped.sort_by(|a, b| {
+            let [da, db] = [sub(*a, center), sub(*b, center)];
+            let angle_a = dot(da, w).atan2(dot(da, u));
+            let angle_b = dot(db, w).atan2(dot(db, u));
+            angle_a.total_cmp(&angle_b)
+        });
+        clipped.push((label, deduped));
+    }
+
+    if clipped.len() < 4 {
+        clipped.clear();
+    }
+    clipped
+}
+
+/// The cell of a polyhedron relative to `v`.
+fn laguerre_cell_3d(faces: &[(Option<usize>, Vec<Vertex3>)], v: Vertex3) -> LaguerreCell3 {
+    // the tets from `v` to the fans of the faces
+    let mut volume = 0.0;
+    let mut centroid = [0.0; 3];
+    let faces: Vec<CellFace> = faces
+        .iter()
+        .map(|(neighbor, polygon)| {
+            let mut area_vector = [0.0; 3];
+            for i in 1..polygon.len() - 1 {
+                let [a, b, c] = [polygon[0], polygon[i], polygon[i + 1]];
+                let tet_volume = dot(a, cross(b, c)) / 6.0;
+                volume += tet_volume;
+                for j in 0..3 {
+                    centroid[j] += tet_volume * (a[j] + b[j] + c[j]) / 4.0;
+                }
+
+                let normal = cross(sub(b, a), sub(c, a));
+                for j in 0..3 {
+                    area_vector[j] += normal[j] / 2.0;
+                }
+            }
+
+            CellFace {
+                neighbor: *neighbor,
+                polygon: polygon
+                    .iter()
+                    .map(|p| core::array::from_fn(|j| p[j] + v[j]))
+                    .collect(),
+                area: dot(area_vector, area_vector).sqrt(),
+            }
+        })
+        .collect();
+
+    if faces.is_empty() || volume <= 0.0 {
+        return LaguerreCell3 {
+            centroid: v,
+            ..LaguerreCell3::default()
+        };
+    }
+
+    LaguerreCell3 {
+        faces,
+        volume,
+        centroid: core::array::from_fn(|j| v[j] + centroid[j] / volume),
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "wasm"))]
+    use rita_test_utils::{sample_vertices_2d, sample_vertices_3d};
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_tessellate_2d() {
+        // the bisector of the two disks is shifted towards the smaller one
+        let tessellation = tessellate_2d(
+            &[[0.25, 0.5], [0.75, 0.5], [0.5, 2.0]],
+            &[0.2, 0.1, 0.1],
+            [[0.0, 0.0], [1.0, 1.0]],
+        )
+        .unwrap();
+        let [cell0, cell1, cell2] = [0, 1, 2].map(|i| &tessellation.cells[i]);
+        assert!((cell0.area - 0.53).abs() < 1e-12);
+        assert!((cell1.area - 0.47).abs() < 1e-12);
+        assert!(cell2.is_empty());
+        assert_eq!(tessellation.contacts.len(), 1);
+        assert_eq!(tessellation.contacts[0].balls, [0, 1]);
+        assert!((tessellation.contacts[0].area - 1.0).abs() < 1e-12);
+        assert_eq!(cell0.neighbors.iter().flatten().collect::<Vec<_>>(), [&1]);
+
+        // the cells partition the domain
+        let centers = sample_vertices_2d(200, None);
+        let radii: Vec<f64> = (0..200)
+            .map(|i| 0.01 + 0.03 * (i % 7) as f64 / 7.0)
+            .collect();
+        let tessellation = tessellate_2d(&centers, &radii, [[-0.4, -0.4], [0.4, 0.4]]).unwrap();
+        let area: f64 = tessellation.cells.iter().map(|cell| cell.area).sum();
+        assert!((area - 0.64).abs() < 1e-9);
+        for cell in tessellation.cells.iter().filter(|cell| !cell.is_empty()) {
+            assert_eq!(cell.polygon.len(), cell.neighbors.len());
+            assert!(cell.centroid.iter().all(|x| x.abs() <= 0.4));
+        }
+        for contact in &tessellation.contacts {
+            let [v_idx, u_idx] = contact.balls;
+            assert!(tessellation.cells[u_idx].neighbors.contains(&Some(v_idx)));
+        }
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_tessellate() {
+        let centers = [
+            [0.25, 0.5, 0.5],
+            [0.75, 0.5, 0.5],
+            [0.5, 2.0, 0.5],
+            [0.5, 0.5, 2.0],
+            [0.26, 0.5, 0.5],
+        ];
+        let tessellation =
+            tessellate(&centers, &[0.2, 0.1, 0.1, 0.1, 0.01], [[0.0; 3], [1.0; 3]]).unwrap();
+        let cells = &tessellation.cells;
+        assert!((cells[0].volume - 0.53).abs() < 1e-12);
+        assert!((cells[1].volume - 0.47).abs() < 1e-12);
+        assert!((cells[0].centroid[0] - 0.265).abs() < 1e-12);
+        assert!(cells[2].is_empty() && cells[3].is_empty());
+        // inside of the large ball, so redundant
+        assert!(cells[4].is_empty());
+        assert_eq!(tessellation.contacts.len(), 1);
+        assert_eq!(tessellation.contacts[0].balls, [0, 1]);
+        assert!((tessellation.contacts[0].area - 1.0).abs() < 1e-12);
+
+        // the cells partition the domain, the faces of a contact match
+        let centers = sample_vertices_3d(200, None);
+        let radii: Vec<f64> = (0..200)
+            .map(|i| 0.01 + 0.03 * (i % 7) as f64 / 7.0)
+            .collect();
+        let tessellation = tessellate(&centers, &radii, [[-0.4; 3], [0.4; 3]]).unwrap();
+        let volume: f64 = tessellation.cells.iter().map(|cell| cell.volume).sum();
+        assert!((volume - 0.512).abs() < 1e-9);
+        for contact in &tessellation.contacts {
+            let [v_idx, u_idx] = contact.balls;
+            let face = tessellation.cells[u_idx]
+                .faces
+                .iter()
+                .find(|face| face.neighbor == Some(v_idx))
+                .unwrap();
+            assert!((face.area - contact.area).abs() < 1e-9);
+        }
+        let boundary_area: f64 = tessellation
+            .cells
+            .iter()
+            .flat_map(|cell| &cell.faces)
+            .filter(|face| face.neighbor.is_none())
+            .map(|face| face.area)
+            .sum();
+        assert!((boundary_area - 6.0 * 0.64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tessellate_errors() {
+        let centers = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+        let domain = [[0.0, 0.0], [1.0, 1.0]];
+        let error = |radii: &[f64], domain| {
+            *tessellate_2d(&centers, radii, domain)
+                .unwrap_err()
+                .downcast_ref::<RitaError>()
+                .unwrap()
+        };
+        #[cfg(not(feature = "wasm"))]
+        {
+            assert_eq!(
+                error(&[0.1, 0.1], domain),
+                RitaError::WeightsMismatch {
+                    num_weights: 2,
+                    num_vertices: 3
+                }
+            );
+            assert_eq!(
+                error(&[0.1, -0.1, 0.1], domain),
+                RitaError::NegativeRadius { idx: 1 }
+            );
+            assert_eq!(error(&[0.1, f64::NAN, 0.1], domain), RitaError::NonFinite);
+            assert_eq!(
+                error(&[0.1; 3], [[0.0, 0.0], [1.0, 0.0]]),
+                RitaError::EmptyDomain
+            );
+        }
+        #[cfg(feature = "wasm")]
+        assert_eq!(error(&[0.1; 3], domain), RitaError::WeightsNotSupported);
+    }
+}
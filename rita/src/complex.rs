@@ -0,0 +1,324 @@
+//! Export of triangulations as simplicial complexes, e.g. for topology or discrete exterior calculus.
+//!
+//! The boundary operators are given as sparse matrices, from which Hodge Laplacians can be built,
+//! together with the primal and dual measures for the Hodge stars.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::{
+    utils::types::{Vertex2, VertexIdx},
+    voronoi::orthocenter,
+};
+
+/// A sparse matrix in coordinate format, i.e. a list of `(row, column, value)` triplets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseMatrix {
+    /// The number of rows, i.e. of faces.
+    pub num_rows: usize,
+    /// The number of columns, i.e. of simplices.
+    pub num_cols: usize,
+    /// The non-zero entries, sorted by column.
+    pub entries: Vec<(usize, usize, i8)>,
+}
+
+impl SparseMatrix {
+    /// Convert into a dense, row-major matrix.
+    pub fn to_dense(&self) -> Vec<Vec<i8>> {
+        let mut dense = vec![vec![0; self.num_cols]; self.num_rows];
+        for &(row, col, value) in &self.entries {
+            dense[row][col] = value;
+        }
+        dense
+    }
+}
+
+/// The oriented simplices of a triangulation, together with the boundary operators between them.
+///
+/// Rows and columns of the boundary operators index into the simplex lists, e.g. `d1` has one row per
+/// entry of `vertices` and one column per entry of `edges`. All simplices except the top-dimensional
+/// ones are oriented by ascending vertex index, the latter keep the orientation of the triangulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoundaryMatrices {
+    /// The vertices that are part of a casual simplex, ascending.
+    pub vertices: Vec<VertexIdx>,
+    /// The edges, sorted lexicographically.
+    pub edges: Vec<[VertexIdx; 2]>,
+    /// The triangles, counter-clockwise for 2D triangulations, otherwise sorted lexicographically.
+    pub tris: Vec<[VertexIdx; 3]>,
+    /// The tetrahedra, empty for 2D triangulations.
+    pub tets: Vec<[VertexIdx; 4]>,
+    /// The boundary operator from edges to vertices.
+    pub d1: SparseMatrix,
+    /// The boundary operator from triangles to edges.
+    pub d2: SparseMatrix,
+    /// The boundary operator from tetrahedra to triangles, `None` for 2D triangulations.
+    pub d3: Option<SparseMatrix>,
+}
+
+impl BoundaryMatrices {
+    /// Build the complex spanned by the given triangles.
+    pub(crate) fn from_tris(tris: Vec<[VertexIdx; 3]>) -> Self {
+        let (edges, d2) = boundary(&tris);
+        let (vertices, d1) = boundary(&edges);
+
+        Self {
+            vertices: vertices.into_iter().map(|[v]| v).collect(),
+            edges,
+            tris,
+            tets: Vec::new(),
+            d1,
+            d2,
+            d3: None,
+        }
+    }
+
+    /// Build the complex spanned by the given tetrahedra.
+    pub(crate) fn from_tets(tets: Vec<[VertexIdx; 4]>) -> Self {
+        let (tris, d3) = boundary(&tets);
+        let Self {
+            vertices,
+            edges,
+            d1,
+            d2,
+            ..
+        } = Self::from_tris(tris.clone());
+
+        Self {
+            vertices,
+            edges,
+            tris,
+            tets,
+            d1,
+            d2,
+            d3: Some(d3),
+        }
+    }
+}
+
+/// Primal and dual measures of a 2D simplicial complex, for discrete exterior calculus.
+///
+/// Each array is aligned with the corresponding simplex list of [`BoundaryMatrices`].
+/// The dual complex is the power diagram, which for unweighted triangulations is the Voronoi diagram,
+/// clipped to the triangulated domain. The ratio of dual to primal measure gives the diagonal Hodge stars.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecMeasures {
+    /// The lengths of the edges.
+    pub edge_lengths: Vec<f64>,
+    /// The signed lengths of the dual edges, i.e. between the orthocenters of the incident triangles.
+    ///
+    /// Negative, if the orthocenters lie on the "wrong" side of the edge, e.g. for obtuse triangles.
+    pub dual_edge_lengths: Vec<f64>,
+    /// The areas of the triangles.
+    pub tri_areas: Vec<f64>,
+    /// The areas of the dual cells of the vertices, which sum up to the total area.
+    pub dual_areas: Vec<f64>,
+}
+
+impl DecMeasures {
+    /// Compute the measures of a complex built by [`BoundaryMatrices::from_tris`].
+    pub(crate) fn from_tris(
+        complex: &BoundaryMatrices,
+        vertices: &[Vertex2],
+        weight: impl Fn(VertexIdx) -> f64,
+    ) -> Self {
+        let distance = |a: Vertex2, b: Vertex2| (a[0] - b[0]).hypot(a[1] - b[1]);
+
+        let edge_lengths = complex
+            .edges
+            .iter()
+            .map(|&[a, b]| distance(vertices[a], vertices[b]))
+            .collect();
+        let mut dual_edge_lengths = vec![0.0; complex.edges.len()];
+        let mut tri_areas = Vec::with_capacity(complex.tris.len());
+        let mut dual_areas = vec![0.0; complex.vertices.len()];
+
+        for (tri_idx, &tri) in complex.tris.iter().enumerate() {
+            let coords = tri.map(|v_idx| vertices[v_idx]);
+            let center = orthocenter(coords, tri.map(&weight));
+
+            for i in 0..3 {
+                // the edge opposite to the i-th vertex, with the triangle on its left
+                let (p_idx, q_idx) = (tri[(i + 1) % 3], tri[(i + 2) % 3]);
+                let (p, q) = (vertices[p_idx], vertices[q_idx]);
+                let length = distance(p, q);
+
+                // signed distance of the orthocenter to the edge, positive towards the triangle
+                let height = ((q[0] - p[0]) * (center[1] - p[1])
+                    - (q[1] - p[1]) * (center[0] - p[0]))
+                    / length;
+
+                // the dual edge crosses the edge where the power distances to both end points are equal
+                let p_part = (length * length + weight(p_idx) - weight(q_idx)) / (2.0 * length);
+
+                let (edge_idx, _, _) = complex.d2.entries[3 * tri_idx + i];
+                dual_edge_lengths[edge_idx] += height;
+
+                for (v_idx, part) in [(p_idx, p_part), (q_idx, length - p_part)] {
+                    let pos = complex.vertices.binary_search(&v_idx).unwrap();
+                    dual_areas[pos] += part * height / 2.0;
+                }
+            }
+
+            let [a, b, c] = coords;
+            tri_areas.push(((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.0);
+        }
+
+        Self {
+            edge_lengths,
+            dual_edge_lengths,
+            tri_areas,
+            dual_areas,
+        }
+    }
+}
+
+/// The faces of a set of simplices, sorted lexicographically, and the boundary operator onto them.
+///
+/// The `i`-th face of a simplex, i.e. without its `i`-th vertex, has the sign `(-1)^i`,
+/// which flips with every swap needed to sort the face.
+fn boundary<const N: usize, const M: usize>(
+    simplices: &[[VertexIdx; N]],
+) -> (Vec<[VertexIdx; M]>, SparseMatrix) {
+    debug_assert_eq!(M + 1, N);
+
+    let sorted_face = |simplex: &[VertexIdx; N], i: usize| {
+        let mut face = [0; M];
+        let mut sign = if i % 2 == 0 { 1 } else { -1 };
+        for (face_v, &v) in face
+            .iter_mut()
+            .zip(simplex.iter().take(i).chain(simplex.iter().skip(i + 1)))
+        {
+            *face_v = v;
+        }
+
+        // insertion sort, to count the swaps
+        for j in 1..M {
+            let mut k = j;
+            while k > 0 && face[k - 1] > face[k] {
+                face.swap(k - 1, k);
+                sign = -sign;
+                k -= 1;
+            }
+        }
+
+        (face, sign)
+    };
+
+    let mut face_idxs = BTreeMap::new();
+    for simplex in simplices {
+        for i in 0..N {
+            face_idxs.insert(sorted_face(simplex, i).0, 0);
+        }
+    }
+    for (idx, face_idx) in face_idxs.values_mut().enumerate() {
+        *face_idx = idx;
+    }
+
+    let mut entries = Vec::with_capacity(simplices.len() * N);
+    for (col, simplex) in simplices.iter().enumerate() {
+        for i in 0..N {
+            let (face, sign) = sorted_face(simplex, i);
+            entries.push((face_idxs[&face], col, sign));
+        }
+    }
+
+    let matrix = SparseMatrix {
+        num_rows: face_idxs.len(),
+        num_cols: simplices.len(),
+        entries,
+    };
+
+    (face_idxs.into_keys().collect(), matrix)
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    /// Check that the product of two consecutive boundary operators vanishes.
+    fn assert_boundary_of_boundary_vanishes(d_k: &SparseMatrix, d_k1: &SparseMatrix) {
+        assert_eq!(d_k.num_cols, d_k1.num_rows);
+
+        let num_cols = d_k1.num_cols;
+        let (d_k, d_k1) = (d_k.to_dense(), d_k1.to_dense());
+        for row in &d_k {
+            for col in 0..num_cols {
+                let value: i32 = row
+                    .iter()
+                    .zip(&d_k1)
+                    .map(|(&a, d_k1_row)| i32::from(a) * i32::from(d_k1_row[col]))
+                    .sum();
+                assert_eq!(value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary_matrices_tris() {
+        // two counter-clockwise triangles sharing the edge 1-2
+        let complex = BoundaryMatrices::from_tris(vec![[0, 1, 2], [1, 3, 2]]);
+
+        assert_eq!(complex.vertices, vec![0, 1, 2, 3]);
+        assert_eq!(complex.edges, vec![[0, 1], [0, 2], [1, 2], [1, 3], [2, 3]]);
+        assert_eq!(complex.d3, None);
+
+        // the shared edge cancels out, as both triangles are oriented consistently
+        let d2 = complex.d2.to_dense();
+        assert_eq!(d2[2][0] + d2[2][1], 0);
+        assert_eq!(
+            d2.iter().map(|row| row[0]).collect::<Vec<_>>(),
+            vec![1, -1, 1, 0, 0]
+        );
+
+        assert_eq!(complex.d1.entries.len(), 2 * complex.edges.len());
+        assert_boundary_of_boundary_vanishes(&complex.d1, &complex.d2);
+    }
+
+    #[test]
+    fn test_dec_measures() {
+        // the unit square, split along the diagonal 1-3
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let complex = BoundaryMatrices::from_tris(vec![[0, 1, 3], [1, 2, 3]]);
+
+        let measures = DecMeasures::from_tris(&complex, &vertices, |_| 0.0);
+        assert_eq!(measures.tri_areas, vec![0.5, 0.5]);
+
+        // both circumcenters are the center of the square, so the diagonal has no dual length
+        let diagonal = complex.edges.iter().position(|&e| e == [1, 3]).unwrap();
+        assert!(measures.dual_edge_lengths[diagonal].abs() < 1e-12);
+        assert_eq!(measures.edge_lengths[diagonal], 2.0_f64.sqrt());
+
+        // the boundary edges are dual to half of the square's width
+        let bottom = complex.edges.iter().position(|&e| e == [0, 1]).unwrap();
+        assert!((measures.dual_edge_lengths[bottom] - 0.5).abs() < 1e-12);
+
+        // each vertex gets a quarter of the square
+        for area in &measures.dual_areas {
+            assert!((area - 0.25).abs() < 1e-12);
+        }
+
+        // a heavier vertex gets a larger dual cell, but the total area stays the same
+        let measures =
+            DecMeasures::from_tris(
+                &complex,
+                &vertices,
+                |v_idx| if v_idx == 0 { 0.2 } else { 0.0 },
+            );
+        assert!(measures.dual_areas[0] > 0.25);
+        assert!((measures.dual_areas.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_boundary_matrices_tets() {
+        let complex = BoundaryMatrices::from_tets(vec![[0, 1, 2, 3], [1, 2, 3, 4]]);
+
+        assert_eq!(complex.vertices.len(), 5);
+        assert_eq!(complex.edges.len(), 9);
+        assert_eq!(complex.tris.len(), 7);
+
+        let d3 = complex.d3.as_ref().unwrap();
+        assert_eq!((d3.num_rows, d3.num_cols), (7, 2));
+        assert_boundary_of_boundary_vanishes(&complex.d1, &complex.d2);
+        assert_boundary_of_boundary_vanishes(&complex.d2, d3);
+    }
+}
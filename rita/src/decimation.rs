@@ -0,0 +1,267 @@
+//! Coarsening of 2D triangulations by vertex decimation, e.g. to derive levels of detail of a terrain.
+//!
+//! Vertices are removed greedily by the error their removal introduces, i.e. the deviation of their value from the
+//! value interpolated in the triangulation without them. The vertices on the convex hull are kept, such that the
+//! covered area is preserved. After each removal the triangulation is the regular triangulation of the remaining vertices.
+//!
+//! Successive decimations build a chain of levels of detail, see [`LodChain`]. As the vertex sets of the levels are nested,
+//! each vertex of a finer level is either part of the next coarser level too, or lies in one of its triangles.
+//! Renderers can blend between two levels by moving each removed vertex from its position and value to its barycentric
+//! interpolation in the coarser level.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    RitaError, Triangulation,
+    utils::types::{Vertex2, VertexIdx},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The fraction of the removable vertices, that is removed at most per round.
+///
+/// Smaller fractions follow the greedy order more closely, but need more rounds, each of which rebuilds the triangulation.
+const BATCH_FRACTION: usize = 4;
+
+/// The values that the error of removing a vertex is measured on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorMetric<'a> {
+    /// Per vertex attribute values, e.g. the elevations of a terrain, indexed like the vertices.
+    Attribute(&'a [f64]),
+    /// The lifted heights of the vertices, see [`Triangulation::height`].
+    Height,
+}
+
+/// The result of [`Triangulation::decimate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Decimation {
+    /// The removed vertices, in the order of their removal.
+    pub removed_vertices: Vec<VertexIdx>,
+    /// The largest deviation of a removed vertex' value from the value interpolated in the decimated triangulation.
+    pub max_error: f64,
+}
+
+/// A vertex of a finer level, expressed in the triangle of the next coarser level that contains it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodParent {
+    /// The vertex, that is removed in the coarser level.
+    pub v_idx: VertexIdx,
+    /// The vertices of the containing triangle in the coarser level.
+    pub tri: [VertexIdx; 3],
+    /// The barycentric coordinates of the vertex in `tri`.
+    pub lambdas: [f64; 3],
+}
+
+/// A level of a [`LodChain`].
+#[derive(Debug)]
+pub struct LodLevel {
+    /// The triangulation of the vertices of this level, with the indices of the vertices of the full triangulation.
+    pub triangulation: Triangulation,
+    /// The vertices of the next finer level, that are removed in this level, empty for the finest level.
+    pub parents: Vec<LodParent>,
+    /// The largest deviation of any removed vertex' value from the value interpolated in this level.
+    pub max_error: f64,
+}
+
+/// Nested triangulations from the finest to the coarsest level of detail, see [`Triangulation::build_lod_chain`].
+#[derive(Debug)]
+pub struct LodChain {
+    /// The levels, the first one is the full triangulation.
+    pub levels: Vec<LodLevel>,
+    /// For each vertex the first level, that it is not part of anymore.
+    ///
+    /// `None` for vertices that are part of all levels, or of none, e.g. redundant ones.
+    pub removed_in_level: Vec<Option<usize>>,
+}
+
+/// The neighbors of each vertex, empty for vertices that are not part of the triangulation.
+pub(crate) fn vertex_neighbors(triangulation: &Triangulation) -> Vec<Vec<VertexIdx>> {
+    let mut neighbors = vec![Vec::new(); triangulation.vertices().len()];
+    for [a, b, c] in triangulation.tri_indices() {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            neighbors[from].push(to);
+            neighbors[to].push(from);
+        }
+    }
+
+    for ring in &mut neighbors {
+        ring.sort_unstable();
+        ring.dedup();
+    }
+
+    neighbors
+}
+
+/// The error of removing an inner vertex, `None` if it can not be determined.
+///
+/// Removing a vertex re-triangulates its star with triangles of the regular triangulation of its neighbors,
+/// so the value at the vertex is interpolated in the triangulation of its neighbors only.
+fn removal_error(
+    triangulation: &Triangulation,
+    v_idx: VertexIdx,
+    ring: &[VertexIdx],
+    values: &[f64],
+) -> Option<f64> {
+    let vertices: Vec<Vertex2> = ring
+        .iter()
+        .map(|&idx| triangulation.vertices()[idx])
+        .collect();
+    let weights = triangulation
+        .weights()
+        .as_ref()
+        .map(|weights| ring.iter().map(|&idx| weights[idx]).collect());
+
+    let mut link = Triangulation::new_with_vert_capacity(None, ring.len());
+    link.insert_vertices(&vertices, weights, false).ok()?;
+
+    let ring_values: Vec<f64> = ring.iter().map(|&idx| values[idx]).collect();
+    let value = link.interpolate(triangulation.vertices()[v_idx], &ring_values)?;
+
+    Some((value - values[v_idx]).abs())
+}
+
+/// Decimate a triangulation, see [`Triangulation::decimate`].
+pub(crate) fn decimate(
+    triangulation: &mut Triangulation,
+    target_vertex_count: usize,
+    error_metric: ErrorMetric,
+) -> HowResult<Decimation> {
+    let num_vertices = triangulation.vertices().len();
+    let values = metric_values(triangulation, error_metric)?;
+
+    let mut removed_vertices = Vec::new();
+    loop {
+        let neighbors = vertex_neighbors(triangulation);
+        let num_remaining = neighbors.iter().filter(|ring| !ring.is_empty()).count();
+        if num_remaining <= target_vertex_count {
+            break;
+        }
+
+        let mut on_hull = vec![false; num_vertices];
+        for v_idx in triangulation.hull_edges() {
+            on_hull[v_idx] = true;
+        }
+
+        let mut candidates: Vec<(f64, VertexIdx)> = (0..num_vertices)
+            .filter(|&v_idx| !neighbors[v_idx].is_empty() && !on_hull[v_idx])
+            .filter_map(|v_idx| {
+                removal_error(triangulation, v_idx, &neighbors[v_idx], &values)
+                    .map(|error| (error, v_idx))
+            })
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|(error_a, _), (error_b, _)| error_a.total_cmp(error_b));
+
+        // vertices that are not adjacent have disjoint stars, so their errors do not affect each other
+        let max_batch = (candidates.len() / BATCH_FRACTION)
+            .max(1)
+            .min(num_remaining - target_vertex_count);
+        let mut is_blocked = vec![false; num_vertices];
+        let mut batch = Vec::with_capacity(max_batch);
+        for (_, v_idx) in candidates {
+            if batch.len() == max_batch {
+                break;
+            }
+            if is_blocked[v_idx] {
+                continue;
+            }
+
+            batch.push(v_idx);
+            for &neighbor in &neighbors[v_idx] {
+                is_blocked[neighbor] = true;
+            }
+        }
+
+        triangulation.remove_vertices(&batch)?;
+        removed_vertices.extend(batch);
+    }
+
+    let max_error = max_error(triangulation, &removed_vertices, &values);
+
+    HowOk(Decimation {
+        removed_vertices,
+        max_error,
+    })
+}
+
+/// Build a chain of levels of detail, see [`Triangulation::build_lod_chain`].
+pub(crate) fn build_lod_chain(
+    triangulation: &Triangulation,
+    levels: &[usize],
+    error_metric: ErrorMetric,
+) -> HowResult<LodChain> {
+    let values = metric_values(triangulation, error_metric)?;
+
+    let mut removed_in_level = vec![None; triangulation.vertices().len()];
+    let mut removed_vertices = Vec::new();
+    let mut chain = vec![LodLevel {
+        triangulation: triangulation.rebuild_without(&[])?,
+        parents: Vec::new(),
+        max_error: 0.0,
+    }];
+
+    for (level, &target_vertex_count) in (1..).zip(levels) {
+        let mut coarser = chain[level - 1].triangulation.rebuild_without(&[])?;
+        let decimation = decimate(&mut coarser, target_vertex_count, error_metric)?;
+
+        let mut parents = Vec::with_capacity(decimation.removed_vertices.len());
+        for &v_idx in &decimation.removed_vertices {
+            // removed vertices are inner vertices, so they lie inside the hull of the coarser level
+            let (tri, lambdas) = coarser
+                .barycentric_coordinates(coarser.vertices()[v_idx])
+                .ok_or(RitaError::LocateFailed)?;
+            parents.push(LodParent {
+                v_idx,
+                tri,
+                lambdas,
+            });
+            removed_in_level[v_idx] = Some(level);
+        }
+
+        removed_vertices.extend(decimation.removed_vertices);
+        chain.push(LodLevel {
+            max_error: max_error(&coarser, &removed_vertices, &values),
+            triangulation: coarser,
+            parents,
+        });
+    }
+
+    HowOk(LodChain {
+        levels: chain,
+        removed_in_level,
+    })
+}
+
+/// The values of the `error_metric` for each vertex.
+fn metric_values(triangulation: &Triangulation, error_metric: ErrorMetric) -> HowResult<Vec<f64>> {
+    let num_vertices = triangulation.vertices().len();
+    match error_metric {
+        ErrorMetric::Attribute(values) => {
+            if values.len() < num_vertices {
+                return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                    kind: "vertex value",
+                    idx: values.len(),
+                }));
+            }
+            HowOk(values.to_vec())
+        }
+        ErrorMetric::Height => HowOk(
+            (0..num_vertices)
+                .map(|v_idx| triangulation.height(v_idx))
+                .collect(),
+        ),
+    }
+}
+
+/// The largest deviation of a removed vertex' value from the value interpolated in the triangulation.
+fn max_error(triangulation: &Triangulation, removed_vertices: &[VertexIdx], values: &[f64]) -> f64 {
+    removed_vertices
+        .iter()
+        .map(|&v_idx| {
+            triangulation
+                .interpolate(triangulation.vertices()[v_idx], values)
+                .map_or(f64::INFINITY, |value| (value - values[v_idx]).abs())
+        })
+        .fold(0.0, f64::max)
+}
@@ -0,0 +1,100 @@
+//! The lower-dimensional structures of inputs, that do not span a first simplex.
+//!
+//! If all vertices of a 2D input are collinear, or all vertices of a 3D input are coplanar, there is no first triangle
+//! or tetrahedron. Instead of failing, the `insert_vertices_or_degenerate` methods of [`Triangulation`] and
+//! [`Tetrahedralization`](crate::Tetrahedralization) return the triangulation of the flat the vertices span,
+//! i.e. a [`Chain`] along a line or a [`Triangulation`] in a plane.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    Triangulation,
+    geometry::Plane,
+    utils::{insert_status::InsertStatus, types::VertexIdx},
+};
+
+/// The result of inserting vertices, that may not span a first simplex.
+#[derive(Debug)]
+pub enum Insertion<D> {
+    /// The vertices are inserted, with the [`InsertStatus`] of each one in the order of the input.
+    Inserted(Vec<InsertStatus>),
+    /// The vertices span only a lower-dimensional flat, triangulated by `D`.
+    Degenerate(D),
+}
+
+/// The degenerate case of a 3D input, see
+/// [`Tetrahedralization::insert_vertices_or_degenerate`](crate::Tetrahedralization::insert_vertices_or_degenerate).
+#[derive(Debug)]
+pub enum Degenerate3 {
+    /// All vertices lie on a line.
+    Collinear(Chain),
+    /// All vertices lie on a plane, but not on a line.
+    Coplanar {
+        /// The plane, whose 2D coordinates the triangulation is in.
+        plane: Plane,
+        /// The triangulation of the vertices projected onto `plane`, with the same vertex indices as the input.
+        triangulation: Box<Triangulation>,
+    },
+}
+
+/// Collinear vertices in order along their line, the 1D counterpart of a triangulation.
+///
+/// Weights are not considered, i.e. every vertex is part of the chain, only duplicates are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chain {
+    /// The indices of the distinct vertices, in order along the line.
+    pub v_idxs: Vec<VertexIdx>,
+}
+
+impl Chain {
+    /// Order collinear vertices along their line, keeping the first of duplicate vertices.
+    ///
+    /// The line runs from the first vertex towards the vertex farthest from it.
+    #[must_use]
+    pub fn new<const D: usize>(vertices: &[[f64; D]]) -> Self {
+        let Some(&v0) = vertices.first() else {
+            return Self { v_idxs: Vec::new() };
+        };
+
+        let diff = |v: &[f64; D]| -> [f64; D] { core::array::from_fn(|i| v[i] - v0[i]) };
+        let dot = |a: [f64; D], b: [f64; D]| (0..D).map(|i| a[i] * b[i]).sum::<f64>();
+        let dir = vertices
+            .iter()
+            .map(diff)
+            .max_by(|a, b| dot(*a, *a).total_cmp(&dot(*b, *b)))
+            .unwrap_or([0.0; D]);
+
+        let mut v_idxs: Vec<VertexIdx> = (0..vertices.len()).collect();
+        // stable, such that the first of duplicate vertices comes first
+        v_idxs.sort_by(|&a, &b| {
+            dot(diff(&vertices[a]), dir).total_cmp(&dot(diff(&vertices[b]), dir))
+        });
+        v_idxs.dedup_by(|a, b| vertices[*a] == vertices[*b]);
+
+        Self { v_idxs }
+    }
+
+    /// The edges between consecutive vertices of the chain.
+    pub fn edges(&self) -> impl Iterator<Item = [VertexIdx; 2]> + '_ {
+        self.v_idxs.windows(2).map(|edge| [edge[0], edge[1]])
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let vertices = [[2.0, 2.0], [0.0, 0.0], [3.0, 3.0], [0.0, 0.0], [-1.0, -1.0]];
+        let chain = Chain::new(&vertices);
+
+        // from the first vertex towards the farthest one, i.e. `[-1.0, -1.0]`
+        assert_eq!(chain.v_idxs, vec![2, 0, 1, 4]);
+        assert_eq!(
+            chain.edges().collect::<Vec<_>>(),
+            vec![[2, 0], [0, 1], [1, 4]]
+        );
+        assert!(Chain::new::<3>(&[]).v_idxs.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+//! The reasons why building or querying a triangulation or tetrahedralization can fail.
+
+/// The error returned by [`Triangulation`](crate::Triangulation), [`Tetrahedralization`](crate::Tetrahedralization)
+/// and their data structures.
+///
+/// The functions return `anyhow` results, the failure reason can be retrieved via `anyhow::Error::downcast_ref`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RitaError {
+    /// Fewer vertices than needed for a first simplex, i.e. 3 in 2D and 4 in 3D.
+    NotEnoughVertices {
+        /// The number of vertices needed.
+        required: usize,
+    },
+    /// All vertices lie on a line, so there is no first triangle.
+    AllPointsCollinear,
+    /// All vertices lie on a plane, so there is no first tetrahedron.
+    AllPointsCoplanar,
+    /// A single vertex can only be inserted, once there is a first simplex.
+    EmptyTriangulation,
+    /// A first simplex can only be inserted into an empty data structure.
+    AlreadyInitialized,
+    /// An index into the data structure, e.g. of a triangle, is out of bounds.
+    IndexOutOfBounds {
+        /// The kind of element, e.g. `"triangle"`.
+        kind: &'static str,
+        /// The index, that is out of bounds.
+        idx: usize,
+    },
+    /// No simplex was found, whose power circle or sphere contains the vertex.
+    LocateFailed,
+    /// A vertex or weight is NaN or infinite.
+    NonFinite,
+    /// Weights are not supported, i.e. with the `wasm` feature.
+    WeightsNotSupported,
+    /// The operation is not defined for conceptual simplices, i.e. those including the point at infinity.
+    ConceptualSimplex,
+    /// The data structure is in a state, that the operation does not expect.
+    InvalidState(&'static str),
+    /// A handle was taken before the last change of the structure, so its simplex may have changed.
+    StaleHandle {
+        /// The generation of the handle.
+        handle: u64,
+        /// The current generation of the structure.
+        current: u64,
+    },
+    /// A vertex coincides with a vertex inserted before, which the [`DuplicatePolicy`](crate::DuplicatePolicy) does not allow.
+    DuplicateVertex {
+        /// The index of the duplicate.
+        v_idx: usize,
+        /// The index of the vertex inserted before.
+        canonical: usize,
+    },
+    /// Checking the epsilon power circle or sphere of a vertex needs an epsilon, which is not set.
+    EpsilonNotSet {
+        /// The index of the vertex, that was checked.
+        v_idx: usize,
+    },
+    /// Both vertices of an edge are reflex in the quadrilateral of its two triangles, which is geometrically impossible.
+    MultipleReflexVertices {
+        /// The vertices of the edge.
+        edge: [usize; 2],
+    },
+    /// An end of a segment is outside the convex hull, so the segment can not be walked along.
+    SegmentOutsideHull,
+    /// The number of weights does not match the number of vertices they belong to.
+    WeightsMismatch {
+        /// The number of weights.
+        num_weights: usize,
+        /// The number of vertices.
+        num_vertices: usize,
+    },
+    /// Inserting would exceed the vertices of a bounded triangulation, see
+    /// [`Triangulation::with_bounded_capacity`](crate::Triangulation::with_bounded_capacity).
+    CapacityExceeded {
+        /// The maximum number of vertices.
+        max_vertices: usize,
+        /// The number of vertices the insertion would have resulted in.
+        requested: usize,
+    },
+    /// A scratch buffer of a bounded triangulation is full, instead of growing, see
+    /// [`Triangulation::with_bounded_capacity`](crate::Triangulation::with_bounded_capacity).
+    BufferFull {
+        /// The name of the buffer, e.g. `"flip stack"`.
+        buffer: &'static str,
+        /// The capacity of the buffer.
+        capacity: usize,
+    },
+    /// The operation is only defined for an unweighted triangulation, e.g. the Delaunay graphs.
+    UnweightedRequired,
+    /// An edge is shared by more than two triangles, so they do not form a triangulation.
+    NonManifoldEdge {
+        /// The vertices of the edge.
+        edge: [usize; 2],
+    },
+    /// The normal of a plane has zero length.
+    ZeroNormal,
+    /// The radius of a ball is negative.
+    NegativeRadius {
+        /// The index of the ball.
+        idx: usize,
+    },
+    /// The domain, e.g. the box of a ball tessellation, is empty.
+    EmptyDomain,
+    /// A file can not be read, e.g. a PLY or a points file.
+    InvalidFile {
+        /// The format of the file, e.g. `"PLY"`.
+        format: &'static str,
+        /// The line of the file, if known.
+        line: Option<usize>,
+        /// Why the file is invalid.
+        reason: &'static str,
+    },
+    /// A snapshot can not be encoded or decoded.
+    InvalidSnapshot(&'static str),
+}
+
+impl core::fmt::Display for RitaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotEnoughVertices { required } => {
+                write!(f, "Needs at least {required} vertices!")
+            }
+            Self::AllPointsCollinear => write!(
+                f,
+                "All points are aligned, i.e. could not find 3 non-aligned points!"
+            ),
+            Self::AllPointsCoplanar => write!(
+                f,
+                "All points are coplanar, i.e. could not find 4 non-coplanar points!"
+            ),
+            Self::EmptyTriangulation => {
+                write!(f, "Needs at least 1 simplex to insert a single vertex!")
+            }
+            Self::AlreadyInitialized => write!(f, "Already contains simplices!"),
+            Self::IndexOutOfBounds { kind, idx } => {
+                write!(f, "The {kind} index {idx} is out of bounds!")
+            }
+            Self::LocateFailed => write!(f, "Could not find a simplex containing the vertex!"),
+            Self::NonFinite => write!(f, "Vertex or weight is not finite!"),
+            Self::WeightsNotSupported => write!(
+                f,
+                "Weighted Delaunay is not supported in wasm (robust predicates are unweighted). Use weights: None."
+            ),
+            Self::ConceptualSimplex => {
+                write!(f, "Operation not allowed for conceptual simplices yet!")
+            }
+            Self::InvalidState(reason) => write!(f, "Invalid state: {reason}"),
+            Self::StaleHandle { handle, current } => write!(
+                f,
+                "The handle of generation {handle} is stale, the structure is at generation {current}!"
+            ),
+            Self::DuplicateVertex { v_idx, canonical } => write!(
+                f,
+                "The vertex {v_idx} is at the position of the vertex {canonical} inserted before!"
+            ),
+            Self::EpsilonNotSet { v_idx } => write!(
+                f,
+                "Epsilon is not set, but needed to check the vertex {v_idx}!"
+            ),
+            Self::MultipleReflexVertices { edge: [a, b] } => write!(
+                f,
+                "Both vertices of the edge {a}-{b} are reflex, there cannot be more than 1 reflex vertex!"
+            ),
+            Self::SegmentOutsideHull => write!(f, "The segment must be inside the convex hull!"),
+            Self::WeightsMismatch {
+                num_weights,
+                num_vertices,
+            } => write!(
+                f,
+                "Number of weights {num_weights} does not match the number of vertices {num_vertices}!"
+            ),
+            Self::CapacityExceeded {
+                max_vertices,
+                requested,
+            } => write!(
+                f,
+                "Capacity of {max_vertices} vertices exceeded, {requested} requested!"
+            ),
+            Self::BufferFull { buffer, capacity } => {
+                write!(f, "The {buffer} is full at its capacity of {capacity}!")
+            }
+            Self::UnweightedRequired => write!(f, "Needs an unweighted triangulation!"),
+            Self::NonManifoldEdge { edge: [a, b] } => {
+                write!(f, "The edge {a}-{b} is shared by more than two triangles!")
+            }
+            Self::ZeroNormal => write!(f, "The normal of a plane must not be zero!"),
+            Self::NegativeRadius { idx } => write!(f, "The radius of the ball {idx} is negative!"),
+            Self::EmptyDomain => write!(f, "The domain must not be empty!"),
+            Self::InvalidFile {
+                format,
+                line: Some(line),
+                reason,
+            } => write!(f, "Invalid {format} file in line {line}: {reason}!"),
+            Self::InvalidFile {
+                format,
+                line: None,
+                reason,
+            } => write!(f, "Invalid {format} file: {reason}!"),
+            Self::InvalidSnapshot(reason) => write!(f, "Invalid snapshot: {reason}!"),
+        }
+    }
+}
+
+impl core::error::Error for RitaError {}
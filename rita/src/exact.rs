@@ -0,0 +1,319 @@
+//! Exact predicates over integer coordinates, to validate the results of the floating point predicates.
+//!
+//! The vertices are scaled to integers by a common power of two without rounding, and the predicates are evaluated
+//! exactly and without symbolic perturbation (SOS). So a degenerate configuration, e.g. four cocircular vertices,
+//! yields [`Ordering::Equal`] instead of being perturbed to one side. This is orders of magnitude slower than the fast
+//! path, and meant for tests, e.g. via [`Triangulation::is_regular_exact`](crate::Triangulation::is_regular_exact).
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+use crate::{RitaError, utils::types::VertexIdx};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// Vertices with integer coordinates and heights, i.e. lifted onto the paraboloid minus their weights.
+///
+/// All coordinates are scaled by the same power of two and the heights by its square, which keeps the signs of the
+/// predicates.
+#[derive(Debug, Clone)]
+pub struct ExactVertices<const D: usize> {
+    coords: Vec<[BigInt; D]>,
+    heights: Vec<BigInt>,
+}
+
+impl<const D: usize> ExactVertices<D> {
+    /// Convert vertices with optional weights into integers.
+    ///
+    /// ## Errors
+    /// Returns an error if the number of weights does not match the number of vertices, or a coordinate or weight is
+    /// not finite.
+    pub fn new(vertices: &[[f64; D]], weights: Option<&[f64]>) -> HowResult<Self> {
+        if let Some(num_weights) = weights.map(<[f64]>::len).filter(|&n| n != vertices.len()) {
+            return Err(RitaError::WeightsMismatch {
+                num_weights,
+                num_vertices: vertices.len(),
+            }
+            .into());
+        }
+
+        let finite = |x: f64| {
+            if x.is_finite() {
+                HowOk(decode(x))
+            } else {
+                Err(RitaError::NonFinite.into())
+            }
+        };
+
+        let coords = vertices
+            .iter()
+            .map(|v| {
+                let mut coord = [(0, 0); D];
+                for (c, &x) in coord.iter_mut().zip(v) {
+                    *c = finite(x)?;
+                }
+                HowOk(coord)
+            })
+            .collect::<HowResult<Vec<_>>>()?;
+        let weights = weights.map_or(HowOk(Vec::new()), |weights| {
+            weights.iter().map(|&w| finite(w)).collect()
+        })?;
+
+        // the smallest power of two, that makes all coordinates integers, and all weights at the squared scale
+        let nonzero_exponent =
+            |&(mantissa, exponent): &(i64, i32)| (mantissa != 0).then_some(exponent);
+        let shift = coords
+            .iter()
+            .flatten()
+            .filter_map(nonzero_exponent)
+            .map(|exponent| -exponent)
+            .chain(
+                weights
+                    .iter()
+                    .filter_map(nonzero_exponent)
+                    .map(|exponent| (1 - exponent) / 2),
+            )
+            .fold(0, i32::max);
+        let scaled = |(mantissa, exponent): (i64, i32), shift: i32| {
+            BigInt::from(mantissa) << (exponent + shift) as usize
+        };
+
+        let coords: Vec<[BigInt; D]> = coords
+            .into_iter()
+            .map(|coord| coord.map(|c| scaled(c, shift)))
+            .collect();
+        let heights = coords
+            .iter()
+            .enumerate()
+            .map(|(v_idx, coord)| {
+                let weight = weights
+                    .get(v_idx)
+                    .map_or(BigInt::zero(), |&w| scaled(w, 2 * shift));
+                coord.iter().map(|c| c * c).sum::<BigInt>() - weight
+            })
+            .collect();
+
+        HowOk(Self { coords, heights })
+    }
+
+    /// The number of vertices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.coords.len()
+    }
+
+    /// Whether there are no vertices.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.coords.is_empty()
+    }
+
+    /// The orientation of a simplex of `D + 1` vertices, i.e. the sign of the determinant of its edge vectors from
+    /// the first vertex.
+    ///
+    /// In 2D [`Ordering::Greater`] means counter-clockwise, [`Ordering::Equal`] means the simplex is flat.
+    #[must_use]
+    pub fn orient(&self, simplex: &[VertexIdx]) -> Ordering {
+        debug_assert_eq!(simplex.len(), D + 1);
+        let first = &self.coords[simplex[0]];
+
+        let rows = simplex[1..]
+            .iter()
+            .map(|&v_idx| (0..D).map(|i| &self.coords[v_idx][i] - &first[i]).collect())
+            .collect();
+
+        sign(&det(rows))
+    }
+
+    /// Whether the vertex `v_idx` lies in the power circle or sphere of a simplex of `D + 1` vertices.
+    ///
+    /// [`Ordering::Greater`] if inside, [`Ordering::Equal`] if on and [`Ordering::Less`] if outside of it,
+    /// regardless of the orientation of the simplex. A flat simplex yields [`Ordering::Equal`].
+    #[must_use]
+    pub fn power_test(&self, simplex: &[VertexIdx], v_idx: VertexIdx) -> Ordering {
+        debug_assert_eq!(simplex.len(), D + 1);
+        let p = &self.coords[v_idx];
+        let h_p = &self.heights[v_idx];
+
+        // the lifted vertex is below the lifted simplex, iff it is in the power sphere
+        let rows = simplex
+            .iter()
+            .map(|&s_idx| {
+                (0..D)
+                    .map(|i| &self.coords[s_idx][i] - &p[i])
+                    .chain([&self.heights[s_idx] - h_p])
+                    .collect()
+            })
+            .collect();
+        let lifted = sign(&det(rows));
+
+        // the determinant has the sign of `orient` for a lifted vertex below, the (D+1)-th column adds (-1)^D
+        let orient = self.orient(simplex);
+        let below = if D % 2 == 0 { lifted } else { lifted.reverse() };
+        match orient {
+            Ordering::Greater => below,
+            Ordering::Less => below.reverse(),
+            Ordering::Equal => Ordering::Equal,
+        }
+    }
+
+    /// Whether the simplices are regular w.r.t. the vertices `v_idxs`.
+    ///
+    /// That is, none of the simplices is flat, all of them have the same orientation, and none of the vertices lies
+    /// strictly inside the power circle or sphere of one of them. Takes `O(simplices * vertices)`.
+    #[must_use]
+    pub fn is_regular(&self, simplices: &[Vec<VertexIdx>], v_idxs: &[VertexIdx]) -> bool {
+        let Some(first) = simplices.first() else {
+            return true;
+        };
+        let orientation = self.orient(first);
+
+        orientation != Ordering::Equal
+            && simplices.iter().all(|simplex| {
+                self.orient(simplex) == orientation
+                    && v_idxs.iter().all(|&v_idx| {
+                        simplex.contains(&v_idx)
+                            || self.power_test(simplex, v_idx) != Ordering::Greater
+                    })
+            })
+    }
+}
+
+fn sign(x: &BigInt) -> Ordering {
+    if x.is_positive() {
+        Ordering::Greater
+    } else if x.is_negative() {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// The mantissa and exponent of a finite `x`, such that `x = mantissa * 2^exponent` with an odd or zero mantissa.
+const fn decode(x: f64) -> (i64, i32) {
+    let bits = x.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    // subnormals lack the implicit leading bit, and have the exponent of the smallest normal
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    if mantissa == 0 {
+        return (0, 0);
+    }
+
+    let trailing_zeros = mantissa.trailing_zeros();
+    let mantissa = (mantissa >> trailing_zeros) as i64;
+    let sign = if bits >> 63 == 0 { 1 } else { -1 };
+    (sign * mantissa, exponent + trailing_zeros as i32)
+}
+
+/// The determinant of a square matrix via Bareiss' fraction-free elimination.
+///
+/// Each entry after step `k` is a minor of the matrix, so the divisions by the previous pivot are exact and the
+/// integers stay as small as the determinant.
+fn det(mut rows: Vec<Vec<BigInt>>) -> BigInt {
+    let n = rows.len();
+    let mut negated = false;
+    let mut prev_pivot = BigInt::one();
+
+    for k in 0..n {
+        if rows[k][k].is_zero() {
+            let Some(pivot) = (k + 1..n).find(|&r| !rows[r][k].is_zero()) else {
+                return BigInt::zero();
+            };
+            rows.swap(k, pivot);
+            negated = !negated;
+        }
+
+        let (top, bottom) = rows.split_at_mut(k + 1);
+        let pivot_row = &top[k];
+        for row in bottom {
+            for j in k + 1..n {
+                row[j] = (&row[j] * &pivot_row[k] - &row[k] * &pivot_row[j]) / &prev_pivot;
+            }
+        }
+        prev_pivot = rows[k][k].clone();
+    }
+
+    if negated { -prev_pivot } else { prev_pivot }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient_and_power_test_2d() {
+        let vertices = [
+            [1.0, 0.0],
+            [0.0, 1.0],
+            [-1.0, 0.0],
+            [0.0, 0.0],
+            [0.0, -1.0],
+            [2.0, 0.0],
+        ];
+        let exact = ExactVertices::new(&vertices, None).unwrap();
+
+        assert_eq!(exact.orient(&[0, 1, 2]), Ordering::Greater);
+        assert_eq!(exact.orient(&[0, 2, 1]), Ordering::Less);
+        assert_eq!(exact.orient(&[0, 2, 3]), Ordering::Equal);
+
+        for simplex in [[0, 1, 2], [0, 2, 1]] {
+            assert_eq!(exact.power_test(&simplex, 3), Ordering::Greater);
+            // cocircular, where the fast path perturbs to one side
+            assert_eq!(exact.power_test(&simplex, 4), Ordering::Equal);
+            assert_eq!(exact.power_test(&simplex, 5), Ordering::Less);
+        }
+
+        // a negative weight lifts the vertex out of the power circle, at any scale of the vertices and weights
+        for scale in [1.0, 0.375, 2f64.powi(-60), 2f64.powi(40)] {
+            let vertices = vertices.map(|v| v.map(|x| x * scale));
+            let weights = [0.0, 0.0, 0.0, -2.0, 0.0, 0.0].map(|w| w * scale * scale);
+            let exact = ExactVertices::new(&vertices, Some(&weights)).unwrap();
+            assert_eq!(exact.power_test(&[0, 1, 2], 3), Ordering::Less);
+            assert_eq!(exact.power_test(&[0, 1, 2], 4), Ordering::Equal);
+        }
+
+        assert!(ExactVertices::new(&[[f64::NAN, 0.0]], None).is_err());
+        for weights in [&[0.0; 5][..], &[0.0; 7]] {
+            let error = ExactVertices::new(&vertices, Some(weights)).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<RitaError>(),
+                Some(RitaError::WeightsMismatch {
+                    num_vertices: 6,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_orient_and_power_test_3d() {
+        let vertices = [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [-1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 0.0, 2.0],
+        ];
+        let exact = ExactVertices::new(&vertices, None).unwrap();
+
+        assert_ne!(exact.orient(&[0, 1, 2, 3]), Ordering::Equal);
+        assert_eq!(
+            exact.orient(&[0, 1, 2, 3]),
+            exact.orient(&[1, 0, 2, 3]).reverse()
+        );
+        for simplex in [[0, 1, 2, 3], [1, 0, 2, 3]] {
+            assert_eq!(exact.power_test(&simplex, 4), Ordering::Greater);
+            assert_eq!(exact.power_test(&simplex, 5), Ordering::Equal);
+            assert_eq!(exact.power_test(&simplex, 6), Ordering::Less);
+        }
+    }
+}
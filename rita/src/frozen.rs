@@ -0,0 +1,580 @@
+//! Frozen, read-only triangulations, optimized for queries after building.
+//!
+//! [`Triangulation::freeze`](crate::Triangulation::freeze) and
+//! [`Tetrahedralization::freeze`](crate::Tetrahedralization::freeze) copy the casual simplices into flat arrays,
+//! ordered along a Hilbert curve so nearby simplices are close in memory. The neighbors of each simplex and the
+//! simplices incident to each vertex are precomputed, the latter in compressed sparse row (CSR) layout.
+//! Unlike the build structures, there are no conceptual, deleted or reusable simplices to skip.
+//! [`Triangulation::snapshot`](crate::Triangulation::snapshot) shares such a view with other threads during a build.
+//!
+//! ```
+//! use rita::Triangulation;
+//!
+//! let mut triangulation = Triangulation::new(None);
+//! let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+//! triangulation.insert_vertices(&vertices, None, false).unwrap();
+//!
+//! let compact = triangulation.freeze();
+//! assert_eq!(compact.num_tris(), 2);
+//! let values = [0.0, 1.0, 1.0, 2.0];
+//! assert_eq!(compact.interpolate([0.5, 0.25], &values), Some(0.75));
+//! assert_eq!(compact.locate([2.0, 0.0]), None);
+//! ```
+
+use alloc::{vec, vec::Vec};
+use anyhow::Result as HowResult;
+
+use crate::{
+    RitaError,
+    geometry::{barycentric_2d, barycentric_3d},
+    locate_grid::LocateGrid,
+    predicates,
+    utils::{
+        compact::IdxVec,
+        point_order::{sort_along_hilbert_curve_2d, sort_along_hilbert_curve_3d},
+        types::{Vertex2, Vertex3, VertexIdx},
+    },
+};
+
+/// The connectivity of `N`-node simplices, shared by the 2D and 3D frozen triangulations.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Topology<const N: usize> {
+    /// The nodes of each simplex, `N` per simplex.
+    nodes: IdxVec,
+    /// The neighbor opposite to each node of each simplex, `usize::MAX` across the hull.
+    neighbors: IdxVec,
+    /// The simplices incident to vertex `v_idx` are `incident[incident_offsets[v_idx]..incident_offsets[v_idx + 1]]`.
+    incident_offsets: IdxVec,
+    /// The simplices incident to each vertex, sorted.
+    incident: IdxVec,
+}
+
+impl<const N: usize> Topology<N> {
+    fn new(num_vertices: usize, simplices: &[[VertexIdx; N]]) -> Self {
+        let mut nodes = IdxVec::with_capacity(N * simplices.len());
+        nodes.extend(simplices.iter().flatten().copied());
+
+        // the facet opposite to node `i` is keyed by the other nodes, sorted, so both of its simplices get the same key
+        let mut facets = Vec::with_capacity(N * simplices.len());
+        for (simplex_idx, simplex) in simplices.iter().enumerate() {
+            for i in 0..N {
+                let mut key = *simplex;
+                key[i] = usize::MAX;
+                key.sort_unstable();
+                facets.push((key, N * simplex_idx + i));
+            }
+        }
+        facets.sort_unstable();
+
+        let mut neighbors = IdxVec::with_capacity(N * simplices.len());
+        neighbors.extend(core::iter::repeat_n(usize::MAX, N * simplices.len()));
+        for pair in facets.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                neighbors.set(pair[0].1, pair[1].1 / N);
+                neighbors.set(pair[1].1, pair[0].1 / N);
+            }
+        }
+
+        let mut counts = vec![0; num_vertices + 1];
+        for &v_idx in simplices.iter().flatten() {
+            counts[v_idx + 1] += 1;
+        }
+        let mut incident_offsets = IdxVec::with_capacity(num_vertices + 1);
+        incident_offsets.extend(counts.iter().scan(0, |offset, &count| {
+            *offset += count;
+            Some(*offset)
+        }));
+
+        let mut next = (0..num_vertices)
+            .map(|v_idx| incident_offsets.get(v_idx))
+            .collect::<Vec<_>>();
+        let mut incident = IdxVec::with_capacity(N * simplices.len());
+        incident.extend(core::iter::repeat_n(0, N * simplices.len()));
+        for (simplex_idx, simplex) in simplices.iter().enumerate() {
+            for &v_idx in simplex {
+                incident.set(next[v_idx], simplex_idx);
+                next[v_idx] += 1;
+            }
+        }
+
+        Self {
+            nodes,
+            neighbors,
+            incident_offsets,
+            incident,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len() / N
+    }
+
+    fn check(&self, simplex_idx: usize, kind: &'static str) -> HowResult<()> {
+        if simplex_idx >= self.len() {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind,
+                idx: simplex_idx,
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn nodes(&self, simplex_idx: usize) -> [VertexIdx; N] {
+        core::array::from_fn(|i| self.nodes.get(N * simplex_idx + i))
+    }
+
+    /// The neighbor opposite to node `i` of the simplex, `None` across the hull.
+    fn neighbor(&self, simplex_idx: usize, i: usize) -> Option<usize> {
+        let neighbor = self.neighbors.get(N * simplex_idx + i);
+        (neighbor != usize::MAX).then_some(neighbor)
+    }
+
+    /// The simplices incident to the vertex, empty for vertices that are not part of the triangulation.
+    fn incident(&self, v_idx: VertexIdx) -> impl Iterator<Item = usize> + '_ {
+        let range = if v_idx + 1 < self.incident_offsets.len() {
+            self.incident_offsets.get(v_idx)..self.incident_offsets.get(v_idx + 1)
+        } else {
+            0..0
+        };
+
+        range.map(|pos| self.incident.get(pos))
+    }
+
+    /// Walks from the starting simplex towards a point, until no facet separates them.
+    ///
+    /// `beyond(nodes, i)` tells whether the point lies beyond the facet opposite to node `i`.
+    /// Returns `None` if the point is beyond a hull facet, i.e. outside the convex hull. The first facet checked rotates
+    /// with each step, so the walk does not cycle. Falls back to checking all simplices after visiting all of them.
+    fn walk(
+        &self,
+        starting_idx: usize,
+        beyond: impl Fn([VertexIdx; N], usize) -> bool,
+    ) -> Option<usize> {
+        let mut simplex_idx = starting_idx;
+        for step in 0..self.len() {
+            let nodes = self.nodes(simplex_idx);
+            let Some(i) = (0..N).map(|i| (i + step) % N).find(|&i| beyond(nodes, i)) else {
+                return Some(simplex_idx);
+            };
+            simplex_idx = self.neighbor(simplex_idx, i)?;
+        }
+
+        (0..self.len()).find(|&simplex_idx| {
+            let nodes = self.nodes(simplex_idx);
+            (0..N).all(|i| !beyond(nodes, i))
+        })
+    }
+}
+
+/// The centroid of the points.
+fn centroid<const D: usize, const N: usize>(points: [[f64; D]; N]) -> [f64; D] {
+    core::array::from_fn(|i| points.iter().map(|point| point[i]).sum::<f64>() / N as f64)
+}
+
+/// A grid over the centroids of the simplices, to start the walks from a nearby simplex.
+fn locate_grid<const D: usize>(centroids: &[[f64; D]]) -> LocateGrid<D> {
+    let anchors = centroids.iter().copied().zip(0..).collect::<Vec<_>>();
+    let mut grid = LocateGrid::new();
+    grid.rebuild(centroids.len(), &anchors);
+
+    grid
+}
+
+/// The value at a point, from the `values` of the nodes of the simplex containing it, weighted by `lambdas`.
+///
+/// A point at a node takes its value, even if it is not finite.
+fn interpolate_in<const D: usize, const N: usize>(
+    nodes: [VertexIdx; N],
+    points: [[f64; D]; N],
+    lambdas: impl FnOnce() -> [f64; N],
+    point: [f64; D],
+    values: &[f64],
+) -> Option<f64> {
+    if let Some(i) = points.iter().position(|&node_point| node_point == point) {
+        return values.get(nodes[i]).copied();
+    }
+
+    nodes
+        .into_iter()
+        .zip(lambdas())
+        .map(|(v_idx, lambda)| values.get(v_idx).map(|value| lambda * value))
+        .sum()
+}
+
+/// A read-only 2D triangulation, built by [`Triangulation::freeze`](crate::Triangulation::freeze).
+///
+/// Holds the casual triangles only, counter-clockwise and indexed from `0` to `num_tris()`. The vertices are indexed
+/// like in the triangulation, including those that are not part of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactTriangulation {
+    vertices: Vec<Vertex2>,
+    topology: Topology<3>,
+    locate_grid: LocateGrid<2>,
+}
+
+impl CompactTriangulation {
+    pub(crate) fn new(vertices: Vec<Vertex2>, tris: &[[VertexIdx; 3]]) -> Self {
+        let centroids = tris
+            .iter()
+            .map(|tri| centroid(tri.map(|v_idx| vertices[v_idx])))
+            .collect::<Vec<_>>();
+        let idxs = (0..tris.len()).collect::<Vec<_>>();
+        let order = sort_along_hilbert_curve_2d(&centroids, &idxs);
+
+        let tris = order
+            .iter()
+            .map(|&tri_idx| {
+                let [v0, v1, v2] = tris[tri_idx];
+                let [p0, p1, p2] = [v0, v1, v2].map(|v_idx| vertices[v_idx]);
+                if predicates::orient_2d(&p0, &p1, &p2) < 0.0 {
+                    [v0, v2, v1]
+                } else {
+                    [v0, v1, v2]
+                }
+            })
+            .collect::<Vec<_>>();
+        let centroids = order
+            .iter()
+            .map(|&tri_idx| centroids[tri_idx])
+            .collect::<Vec<_>>();
+
+        Self {
+            topology: Topology::new(vertices.len(), &tris),
+            locate_grid: locate_grid(&centroids),
+            vertices,
+        }
+    }
+
+    /// Get the vertices, indexed like in the triangulation.
+    pub fn vertices(&self) -> &[Vertex2] {
+        &self.vertices
+    }
+
+    /// Get the number of triangles.
+    pub fn num_tris(&self) -> usize {
+        self.topology.len()
+    }
+
+    /// Get the vertices of a triangle, counter-clockwise.
+    ///
+    /// ## Errors
+    /// Returns an error if the index is out of bounds.
+    pub fn tri(&self, tri_idx: usize) -> HowResult<[VertexIdx; 3]> {
+        self.topology.check(tri_idx, "triangle")?;
+
+        Ok(self.topology.nodes(tri_idx))
+    }
+
+    /// Get the triangles adjacent to a triangle, `None` across the hull.
+    ///
+    /// The neighbor `i` shares the edge from the node `i` of the triangle to its node `i + 1`, like in
+    /// [`Triangulation::tri_neighbors`](crate::Triangulation::tri_neighbors).
+    ///
+    /// ## Errors
+    /// Returns an error if the index is out of bounds.
+    pub fn tri_neighbors(&self, tri_idx: usize) -> HowResult<[Option<usize>; 3]> {
+        self.topology.check(tri_idx, "triangle")?;
+
+        // the edge from node `i` to node `i + 1` is opposite to node `i + 2`
+        Ok(core::array::from_fn(|i| {
+            self.topology.neighbor(tri_idx, (i + 2) % 3)
+        }))
+    }
+
+    /// Get the triangles incident to a vertex, sorted, empty if the vertex is not part of the triangulation.
+    pub fn incident_tris(&self, v_idx: VertexIdx) -> impl Iterator<Item = usize> + '_ {
+        self.topology.incident(v_idx)
+    }
+
+    /// Locate the triangle that contains a point, `None` if it is outside the convex hull.
+    ///
+    /// A point on an edge or a vertex is contained by any of their triangles.
+    pub fn locate(&self, point: Vertex2) -> Option<usize> {
+        let starting_idx = self.locate_grid.hint(&point).unwrap_or(0);
+
+        self.topology.walk(starting_idx, |nodes, i| {
+            let mut points = nodes.map(|v_idx| self.vertices[v_idx]);
+            points[i] = point;
+            predicates::orient_2d(&points[0], &points[1], &points[2]) < 0.0
+        })
+    }
+
+    /// Interpolate per vertex `values` at a point, via its barycentric coordinates in the containing triangle.
+    ///
+    /// The `values` are indexed like the vertices.
+    /// Returns `None` for points outside the convex hull, or if a vertex of the containing triangle has no value.
+    pub fn interpolate(&self, point: Vertex2, values: &[f64]) -> Option<f64> {
+        let nodes = self.topology.nodes(self.locate(point)?);
+        let points = nodes.map(|v_idx| self.vertices[v_idx]);
+
+        interpolate_in(
+            nodes,
+            points,
+            || barycentric_2d(points, point),
+            point,
+            values,
+        )
+    }
+}
+
+/// A read-only 3D tetrahedralization, built by [`Tetrahedralization::freeze`](crate::Tetrahedralization::freeze).
+///
+/// Holds the casual tetrahedra only, positively oriented and indexed from `0` to `num_tets()`. The vertices are
+/// indexed like in the tetrahedralization, including those that are not part of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactTetrahedralization {
+    vertices: Vec<Vertex3>,
+    topology: Topology<4>,
+    locate_grid: LocateGrid<3>,
+}
+
+impl CompactTetrahedralization {
+    pub(crate) fn new(vertices: Vec<Vertex3>, tets: &[[VertexIdx; 4]]) -> Self {
+        let centroids = tets
+            .iter()
+            .map(|tet| centroid(tet.map(|v_idx| vertices[v_idx])))
+            .collect::<Vec<_>>();
+        let order = sort_along_hilbert_curve_3d(&centroids, (0..tets.len()).collect());
+
+        let tets = order
+            .iter()
+            .map(|&tet_idx| {
+                let [v0, v1, v2, v3] = tets[tet_idx];
+                let [p0, p1, p2, p3] = [v0, v1, v2, v3].map(|v_idx| vertices[v_idx]);
+                if predicates::orient_3d(&p0, &p1, &p2, &p3) < 0.0 {
+                    [v1, v0, v2, v3]
+                } else {
+                    [v0, v1, v2, v3]
+                }
+            })
+            .collect::<Vec<_>>();
+        let centroids = order
+            .iter()
+            .map(|&tet_idx| centroids[tet_idx])
+            .collect::<Vec<_>>();
+
+        Self {
+            topology: Topology::new(vertices.len(), &tets),
+            locate_grid: locate_grid(&centroids),
+            vertices,
+        }
+    }
+
+    /// Get the vertices, indexed like in the tetrahedralization.
+    pub fn vertices(&self) -> &[Vertex3] {
+        &self.vertices
+    }
+
+    /// Get the number of tetrahedra.
+    pub fn num_tets(&self) -> usize {
+        self.topology.len()
+    }
+
+    /// Get the vertices of a tetrahedron.
+    ///
+    /// ## Errors
+    /// Returns an error if the index is out of bounds.
+    pub fn tet(&self, tet_idx: usize) -> HowResult<[VertexIdx; 4]> {
+        self.topology.check(tet_idx, "tetrahedron")?;
+
+        Ok(self.topology.nodes(tet_idx))
+    }
+
+    /// Get the tetrahedra adjacent to a tetrahedron, `None` across the hull.
+    ///
+    /// The neighbor `i` shares the facet opposite to the node `i` of the tetrahedron.
+    ///
+    /// ## Errors
+    /// Returns an error if the index is out of bounds.
+    pub fn tet_neighbors(&self, tet_idx: usize) -> HowResult<[Option<usize>; 4]> {
+        self.topology.check(tet_idx, "tetrahedron")?;
+
+        Ok(core::array::from_fn(|i| self.topology.neighbor(tet_idx, i)))
+    }
+
+    /// Get the tetrahedra incident to a vertex, sorted, empty if the vertex is not part of the tetrahedralization.
+    pub fn incident_tets(&self, v_idx: VertexIdx) -> impl Iterator<Item = usize> + '_ {
+        self.topology.incident(v_idx)
+    }
+
+    /// Locate the tetrahedron that contains a point, `None` if it is outside the convex hull.
+    ///
+    /// A point on a facet, an edge or a vertex is contained by any of their tetrahedra.
+    pub fn locate(&self, point: Vertex3) -> Option<usize> {
+        let starting_idx = self.locate_grid.hint(&point).unwrap_or(0);
+
+        self.topology.walk(starting_idx, |nodes, i| {
+            let mut points = nodes.map(|v_idx| self.vertices[v_idx]);
+            points[i] = point;
+            predicates::orient_3d(&points[0], &points[1], &points[2], &points[3]) < 0.0
+        })
+    }
+
+    /// Interpolate per vertex `values` at a point, via its barycentric coordinates in the containing tetrahedron.
+    ///
+    /// The `values` are indexed like the vertices.
+    /// Returns `None` for points outside the convex hull, or if a vertex of the containing tetrahedron has no value.
+    pub fn interpolate(&self, point: Vertex3, values: &[f64]) -> Option<f64> {
+        let nodes = self.topology.nodes(self.locate(point)?);
+        let points = nodes.map(|v_idx| self.vertices[v_idx]);
+
+        interpolate_in(
+            nodes,
+            points,
+            || barycentric_3d(points, point),
+            point,
+            values,
+        )
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::{Triangulation, triangulation::Location};
+    use rita_test_utils::sample_vertices_2d;
+
+    #[test]
+    fn test_freeze_triangulation() {
+        let vertices = sample_vertices_2d(300, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let compact = triangulation.freeze();
+
+        let mut tris = (0..compact.num_tris())
+            .map(|tri_idx| compact.tri(tri_idx).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(tris.len(), triangulation.tri_indices().len());
+        assert!(compact.tri(compact.num_tris()).is_err());
+
+        for (tri_idx, tri) in tris.iter().enumerate() {
+            let points = tri.map(|v_idx| vertices[v_idx]);
+            assert!(predicates::orient_2d(&points[0], &points[1], &points[2]) > 0.0);
+
+            // the neighbor `i` shares the edge from node `i` to node `i + 1`, reversed
+            for (i, neighbor) in compact
+                .tri_neighbors(tri_idx)
+                .unwrap()
+                .into_iter()
+                .enumerate()
+            {
+                let Some(neighbor) = neighbor else {
+                    continue;
+                };
+                assert!(
+                    compact
+                        .tri_neighbors(neighbor)
+                        .unwrap()
+                        .contains(&Some(tri_idx))
+                );
+                let edge = [tri[i], tri[(i + 1) % 3]];
+                assert!(tris[neighbor].contains(&edge[0]) && tris[neighbor].contains(&edge[1]));
+            }
+        }
+
+        for v_idx in 0..vertices.len() {
+            let incident = compact.incident_tris(v_idx).collect::<Vec<_>>();
+            assert_eq!(incident.len(), triangulation.incident_tris(v_idx).len());
+            assert!(
+                incident
+                    .iter()
+                    .all(|&tri_idx| tris[tri_idx].contains(&v_idx))
+            );
+        }
+        assert_eq!(compact.incident_tris(vertices.len()).count(), 0);
+
+        let f = |[x, y]: Vertex2| 2.0 * x - y + 1.0;
+        let values = vertices.iter().map(|&v| f(v)).collect::<Vec<_>>();
+        for point in sample_vertices_2d(200, Some(-1.5..=1.5)) {
+            let located = compact.locate(point);
+            let is_outside = matches!(
+                triangulation.locate(point).unwrap(),
+                Location::OutsideHull(_)
+            );
+            assert_eq!(located.is_none(), is_outside);
+            if let Some(value) = compact.interpolate(point, &values) {
+                assert!((value - f(point)).abs() < 1e-9);
+            }
+        }
+        assert_eq!(compact.interpolate(vertices[0], &values), Some(values[0]));
+
+        // the triangles are the same, only renumbered
+        tris.iter_mut().for_each(|tri| tri.sort_unstable());
+        tris.sort_unstable();
+        let mut expected = triangulation.tri_indices();
+        expected.iter_mut().for_each(|tri| tri.sort_unstable());
+        expected.sort_unstable();
+        assert_eq!(tris, expected);
+
+        let empty = Triangulation::new(None).freeze();
+        assert_eq!((empty.num_tris(), empty.locate([0.0, 0.0])), (0, None));
+    }
+
+    // the insertion of tets is not robust with the predicates of wasm
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_freeze_tetrahedralization() {
+        use crate::{Tetrahedralization, tetrahedralization::Location3};
+        use rita_test_utils::sample_vertices_3d;
+
+        let vertices = sample_vertices_3d(200, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let compact = tetrahedralization.freeze();
+        assert_eq!(compact.num_tets(), tetrahedralization.tet_indices().len());
+
+        for tet_idx in 0..compact.num_tets() {
+            let tet = compact.tet(tet_idx).unwrap();
+            let points = tet.map(|v_idx| vertices[v_idx]);
+            assert!(predicates::orient_3d(&points[0], &points[1], &points[2], &points[3]) > 0.0);
+
+            for (i, neighbor) in compact
+                .tet_neighbors(tet_idx)
+                .unwrap()
+                .into_iter()
+                .enumerate()
+            {
+                let Some(neighbor) = neighbor else {
+                    continue;
+                };
+                assert!(
+                    compact
+                        .tet_neighbors(neighbor)
+                        .unwrap()
+                        .contains(&Some(tet_idx))
+                );
+                let neighbor_tet = compact.tet(neighbor).unwrap();
+                assert!(
+                    (0..4)
+                        .filter(|&j| j != i)
+                        .all(|j| neighbor_tet.contains(&tet[j]))
+                );
+            }
+        }
+
+        for v_idx in 0..vertices.len() {
+            assert_eq!(
+                compact.incident_tets(v_idx).count(),
+                tetrahedralization.incident_tets(v_idx).len()
+            );
+        }
+
+        let f = |[x, y, z]: Vertex3| 2.0 * x - y + 0.5 * z + 1.0;
+        let values = vertices.iter().map(|&v| f(v)).collect::<Vec<_>>();
+        for point in sample_vertices_3d(200, Some(-1.5..=1.5)) {
+            let is_outside = matches!(
+                tetrahedralization.locate(point).unwrap(),
+                Location3::OutsideHull(_)
+            );
+            assert_eq!(compact.locate(point).is_none(), is_outside);
+            if let Some(value) = compact.interpolate(point, &values) {
+                assert!((value - f(point)).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//! Small geometry helpers, consistent with the predicates used by the triangulations.
+
+use crate::RitaError;
+use crate::predicates;
+use crate::utils::types::{Vertex2, Vertex3};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+pub use crate::utils::convexity::is_convex;
+
+/// The orientation of a closed polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The vertices are ordered counter-clockwise.
+    CounterClockwise,
+    /// The vertices are ordered clockwise.
+    Clockwise,
+    /// The polygon has less than three distinct vertices or all of them are aligned.
+    Degenerate,
+}
+
+/// The signed area of a closed polygon, computed via the shoelace formula.
+///
+/// Positive for counter-clockwise, negative for clockwise polygons.
+/// The polygon is implicitly closed, i.e. the last vertex connects to the first one.
+pub fn polygon_area(polygon: &[Vertex2]) -> f64 {
+    if polygon.len() < 3 {
+        return 0.0;
+    }
+
+    // translate to the first vertex to reduce cancellation for polygons far away from the origin
+    let o = polygon[0];
+    let mut area = 0.0;
+    for i in 1..polygon.len() - 1 {
+        let a = [polygon[i][0] - o[0], polygon[i][1] - o[1]];
+        let b = [polygon[i + 1][0] - o[0], polygon[i + 1][1] - o[1]];
+        area += a[0] * b[1] - a[1] * b[0];
+    }
+
+    area / 2.0
+}
+
+/// The orientation of a simple, closed polygon.
+///
+/// Decided by an exact orientation test at the lowest, leftmost vertex (which is always convex),
+/// so the result does not suffer from the rounding errors of [`polygon_area`].
+pub fn polygon_orientation(polygon: &[Vertex2]) -> Orientation {
+    let n = polygon.len();
+    if n < 3 {
+        return Orientation::Degenerate;
+    }
+
+    let extreme = (0..n)
+        .min_by(|&i, &j| {
+            let (a, b) = (polygon[i], polygon[j]);
+            a[1].total_cmp(&b[1]).then(a[0].total_cmp(&b[0]))
+        })
+        .unwrap();
+
+    let v = polygon[extreme];
+    // skip duplicates of the extreme vertex, they do not contribute to the orientation
+    let prev = (1..n)
+        .map(|k| polygon[(extreme + n - k) % n])
+        .find(|p| *p != v);
+    let next = (1..n).map(|k| polygon[(extreme + k) % n]).find(|p| *p != v);
+
+    let (Some(prev), Some(next)) = (prev, next) else {
+        return Orientation::Degenerate;
+    };
+
+    let orientation = predicates::orient_2d(&prev, &v, &next);
+    if orientation > 0.0 {
+        Orientation::CounterClockwise
+    } else if orientation < 0.0 {
+        Orientation::Clockwise
+    } else {
+        // the extreme vertex is flat, fall back to the area for the remaining polygon
+        let area = polygon_area(polygon);
+        if area > 0.0 {
+            Orientation::CounterClockwise
+        } else if area < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Degenerate
+        }
+    }
+}
+
+pub(crate) fn dot(a: Vertex3, b: Vertex3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross(a: Vertex3, b: Vertex3) -> Vertex3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vertex3) -> Option<Vertex3> {
+    let len = dot(a, a).sqrt();
+    (len > 0.0 && len.is_finite()).then(|| a.map(|x| x / len))
+}
+
+/// The eigenvector of the smallest eigenvalue of a symmetric 3x3 matrix, via cyclic Jacobi rotations.
+fn smallest_eigenvector(mut a: [[f64; 3]; 3]) -> Vertex3 {
+    let mut eigenvectors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..32 {
+        let off_diagonal = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        let diagonal = a[0][0].powi(2) + a[1][1].powi(2) + a[2][2].powi(2);
+        if off_diagonal <= f64::EPSILON * f64::EPSILON * diagonal {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+
+            // the rotation that annihilates a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+
+            for row in a.iter_mut().chain(eigenvectors.iter_mut()) {
+                let (x_p, x_q) = (row[p], row[q]);
+                row[p] = c * x_p - s * x_q;
+                row[q] = s * x_p + c * x_q;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            for k in 0..3 {
+                a[p][k] = c * row_p[k] - s * row_q[k];
+                a[q][k] = s * row_p[k] + c * row_q[k];
+            }
+        }
+    }
+
+    let smallest = (0..3).min_by(|&i, &j| a[i][i].total_cmp(&a[j][j])).unwrap();
+    eigenvectors.map(|row| row[smallest])
+}
+
+/// A plane in 3D, with an orthonormal 2D coordinate frame to project points into.
+///
+/// The frame is right-handed w.r.t. the normal, i.e. counter-clockwise triangles in the plane
+/// are counter-clockwise when looking against the normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    origin: Vertex3,
+    normal: Vertex3,
+    u: Vertex3,
+    v: Vertex3,
+}
+
+impl Plane {
+    /// Create a plane through `origin` with the given `normal`, which does not need to be normalized.
+    ///
+    /// ## Errors
+    /// Returns an error if the normal has zero length.
+    pub fn new(origin: Vertex3, normal: Vertex3) -> HowResult<Self> {
+        let normal = normalize(normal).ok_or(RitaError::ZeroNormal)?;
+
+        // pick the coordinate axis that is the least aligned with the normal to span the frame
+        let axis = if normal[0].abs() <= normal[1].abs() && normal[0].abs() <= normal[2].abs() {
+            [1.0, 0.0, 0.0]
+        } else if normal[1].abs() <= normal[2].abs() {
+            [0.0, 1.0, 0.0]
+        } else {
+            [0.0, 0.0, 1.0]
+        };
+        let u = normalize(cross(axis, normal)).unwrap();
+        let v = cross(normal, u);
+
+        HowOk(Self {
+            origin,
+            normal,
+            u,
+            v,
+        })
+    }
+
+    /// The least-squares plane through a set of points.
+    ///
+    /// It passes through the centroid, and its normal is the direction of the smallest variance.
+    ///
+    /// ## Errors
+    /// Returns an error if there are less than three points.
+    pub fn best_fit(points: &[Vertex3]) -> HowResult<Self> {
+        if points.len() < 3 {
+            return Err(anyhow::Error::new(RitaError::NotEnoughVertices {
+                required: 3,
+            }));
+        }
+
+        let n = points.len() as f64;
+        let mut centroid = [0.0; 3];
+        for p in points {
+            for i in 0..3 {
+                centroid[i] += p[i] / n;
+            }
+        }
+
+        let mut covariance = [[0.0; 3]; 3];
+        for p in points {
+            let d = [p[0] - centroid[0], p[1] - centroid[1], p[2] - centroid[2]];
+            for i in 0..3 {
+                for j in 0..3 {
+                    covariance[i][j] += d[i] * d[j];
+                }
+            }
+        }
+
+        Self::new(centroid, smallest_eigenvector(covariance))
+    }
+
+    /// The point the 2D frame is centered at.
+    #[must_use]
+    pub const fn origin(&self) -> Vertex3 {
+        self.origin
+    }
+
+    /// The normalized normal of the plane.
+    #[must_use]
+    pub const fn normal(&self) -> Vertex3 {
+        self.normal
+    }
+
+    /// The coordinate frame of the plane, mapping its 2D coordinates (with zero height) back into 3D.
+    #[cfg(feature = "nalgebra")]
+    #[must_use]
+    pub fn frame(&self) -> nalgebra::Isometry3<f64> {
+        let rotation = nalgebra::Rotation3::from_basis_unchecked(
+            &[self.u, self.v, self.normal].map(nalgebra::Vector3::from),
+        );
+
+        nalgebra::Isometry3::from_parts(
+            nalgebra::Translation3::from(self.origin),
+            nalgebra::UnitQuaternion::from_rotation_matrix(&rotation),
+        )
+    }
+
+    /// Project a point orthogonally onto the plane, in the plane's 2D coordinates.
+    #[must_use]
+    pub fn project(&self, p: Vertex3) -> Vertex2 {
+        let d = [
+            p[0] - self.origin[0],
+            p[1] - self.origin[1],
+            p[2] - self.origin[2],
+        ];
+        [dot(d, self.u), dot(d, self.v)]
+    }
+}
+
+pub(crate) fn sub<const D: usize>(a: [f64; D], b: [f64; D]) -> [f64; D] {
+    core::array::from_fn(|i| a[i] - b[i])
+}
+
+/// The barycentric coordinates of `p` w.r.t. the triangle `[a, b, c]`, which may be oriented either way.
+///
+/// Computed with floating point arithmetic, so the coordinates of a point on an edge may be slightly off zero.
+/// They are not finite for a degenerate triangle.
+pub fn barycentric_2d([a, b, c]: [Vertex2; 3], p: Vertex2) -> [f64; 3] {
+    let det = |u: Vertex2, v: Vertex2| u[0] * v[1] - u[1] * v[0];
+    let [ab, ac, ap] = [sub(b, a), sub(c, a), sub(p, a)];
+    let area = det(ab, ac);
+
+    let lambda_b = det(ap, ac) / area;
+    let lambda_c = det(ab, ap) / area;
+
+    [1.0 - lambda_b - lambda_c, lambda_b, lambda_c]
+}
+
+/// The barycentric coordinates of `p` w.r.t. the tetrahedron `[a, b, c, d]`, which may be oriented either way.
+///
+/// Computed with floating point arithmetic, see [`barycentric_2d`].
+pub fn barycentric_3d([a, b, c, d]: [Vertex3; 4], p: Vertex3) -> [f64; 4] {
+    let det = |u: Vertex3, v: Vertex3, w: Vertex3| dot(u, cross(v, w));
+    let [ab, ac, ad, ap] = [sub(b, a), sub(c, a), sub(d, a), sub(p, a)];
+    let volume = det(ab, ac, ad);
+
+    let lambda_b = det(ap, ac, ad) / volume;
+    let lambda_c = det(ab, ap, ad) / volume;
+    let lambda_d = det(ab, ac, ap) / volume;
+
+    [
+        1.0 - lambda_b - lambda_c - lambda_d,
+        lambda_b,
+        lambda_c,
+        lambda_d,
+    ]
+}
+
+/// The barycentric coordinates of the point on the segment `[a, b]` closest to `p`.
+pub(crate) fn closest_on_segment<const D: usize>([a, b]: [[f64; D]; 2], p: [f64; D]) -> [f64; 2] {
+    let [ab, ap] = [sub(b, a), sub(p, a)];
+    let len_sq: f64 = ab.iter().map(|x| x * x).sum();
+    if len_sq == 0.0 {
+        return [1.0, 0.0];
+    }
+
+    let t = (ab.iter().zip(&ap).map(|(x, y)| x * y).sum::<f64>() / len_sq).clamp(0.0, 1.0);
+    [1.0 - t, t]
+}
+
+/// The barycentric coordinates of the point on the triangle `[a, b, c]` closest to `p`.
+///
+/// Checks the voronoi regions of the corners and edges first, see Ericson, Real-Time Collision Detection, 5.1.5.
+pub(crate) fn closest_on_triangle([a, b, c]: [Vertex3; 3], p: Vertex3) -> [f64; 3] {
+    let [ab, ac, ap] = [sub(b, a), sub(c, a), sub(p, a)];
+    let [d1, d2] = [dot(ab, ap), dot(ac, ap)];
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+
+    let bp = sub(p, b);
+    let [d3, d4] = [dot(ab, bp), dot(ac, bp)];
+    if d3 >= 0.0 && d4 <= d3 {
+        return [0.0, 1.0, 0.0];
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let t = d1 / (d1 - d3);
+        return [1.0 - t, t, 0.0];
+    }
+
+    let cp = sub(p, c);
+    let [d5, d6] = [dot(ab, cp), dot(ac, cp)];
+    if d6 >= 0.0 && d5 <= d6 {
+        return [0.0, 0.0, 1.0];
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let t = d2 / (d2 - d6);
+        return [1.0 - t, 0.0, t];
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return [0.0, 1.0 - t, t];
+    }
+
+    let denom = va + vb + vc;
+    let [v, w] = [vb / denom, vc / denom];
+    [1.0 - v - w, v, w]
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Vertex2; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+    #[test]
+    fn test_closest_on_simplex() {
+        assert_eq!(
+            closest_on_segment([[0.0, 0.0], [2.0, 0.0]], [0.5, 1.0]),
+            [0.75, 0.25]
+        );
+        assert_eq!(
+            closest_on_segment([[0.0, 0.0], [2.0, 0.0]], [-1.0, 1.0]),
+            [1.0, 0.0]
+        );
+        assert_eq!(
+            closest_on_segment([[1.0, 1.0], [1.0, 1.0]], [0.0, 0.0]),
+            [1.0, 0.0]
+        );
+
+        let tri = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        assert_eq!(closest_on_triangle(tri, [-1.0, -1.0, 1.0]), [1.0, 0.0, 0.0]);
+        assert_eq!(closest_on_triangle(tri, [2.0, 0.0, -1.0]), [0.0, 1.0, 0.0]);
+        assert_eq!(closest_on_triangle(tri, [0.5, -1.0, 0.0]), [0.5, 0.5, 0.0]);
+        assert_eq!(closest_on_triangle(tri, [1.0, 1.0, 0.0]), [0.0, 0.5, 0.5]);
+        assert_eq!(
+            closest_on_triangle(tri, [0.25, 0.5, 3.0]),
+            [0.25, 0.25, 0.5]
+        );
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        assert_eq!(polygon_area(&SQUARE), 1.0);
+
+        let mut reversed = SQUARE;
+        reversed.reverse();
+        assert_eq!(polygon_area(&reversed), -1.0);
+
+        assert_eq!(polygon_area(&SQUARE[..2]), 0.0);
+    }
+
+    #[test]
+    fn test_polygon_orientation() {
+        assert_eq!(polygon_orientation(&SQUARE), Orientation::CounterClockwise);
+
+        let mut reversed = SQUARE;
+        reversed.reverse();
+        assert_eq!(polygon_orientation(&reversed), Orientation::Clockwise);
+
+        let aligned = [[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]];
+        assert_eq!(polygon_orientation(&aligned), Orientation::Degenerate);
+    }
+
+    #[test]
+    fn test_is_convex() {
+        assert!(is_convex([0.0, 1.0], [0.0, 0.0], [1.0, 0.0]));
+        assert!(!is_convex([1.0, 0.0], [0.0, 0.0], [0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let tri = [[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]];
+        assert_eq!(barycentric_2d(tri, [0.5, 0.5]), [0.5, 0.25, 0.25]);
+        assert_eq!(barycentric_2d(tri, [0.0, 2.0]), [0.0, 0.0, 1.0]);
+
+        let tet = [[0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_eq!(barycentric_3d(tet, [0.25, 0.25, 0.25]), [0.25; 4]);
+        let [.., lambda_d] = barycentric_3d(tet, [0.0, 0.0, -1.0]);
+        assert!(lambda_d < 0.0);
+    }
+
+    #[test]
+    fn test_plane() {
+        let plane = Plane::new([0.0, 0.0, 1.0], [0.0, 0.0, 2.0]).unwrap();
+        assert_eq!(plane.normal(), [0.0, 0.0, 1.0]);
+
+        // a ccw triangle seen from above stays ccw in the plane
+        let tri = [[0.0, 0.0, 5.0], [1.0, 0.0, 5.0], [0.0, 1.0, 5.0]].map(|p| plane.project(p));
+        assert!(predicates::orient_2d(&tri[0], &tri[1], &tri[2]) > 0.0);
+
+        assert!(Plane::new([0.0; 3], [0.0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_plane_best_fit() {
+        let points = [
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 2.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 2.0],
+        ];
+
+        let plane = Plane::best_fit(&points).unwrap();
+
+        // the points lie on the plane z = 1 + x
+        let expected = 0.5_f64.sqrt();
+        assert!((plane.normal()[0].abs() - expected).abs() < 1e-9);
+        assert!(plane.normal()[1].abs() < 1e-9);
+        assert!((plane.normal()[2].abs() - expected).abs() < 1e-9);
+        assert_eq!(plane.origin(), [0.5, 0.5, 1.5]);
+
+        assert!(Plane::best_fit(&points[..2]).is_err());
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_plane_frame() {
+        let plane = Plane::new([1.0, 2.0, 3.0], [1.0, 1.0, 1.0]).unwrap();
+        let frame = plane.frame();
+
+        // points on the plane are recovered from their projection
+        for p in [[1.0, 2.0, 3.0], [3.0, 2.0, 1.0], [0.0, 0.0, 6.0]] {
+            let [x, y] = plane.project(p);
+            let q = frame * nalgebra::Point3::new(x, y, 0.0);
+            assert!((0..3).all(|i| (q[i] - p[i]).abs() < 1e-12));
+        }
+    }
+}
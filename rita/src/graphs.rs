@@ -0,0 +1,262 @@
+//! Proximity graphs of a 2D Delaunay triangulation, i.e. its subgraphs.
+//!
+//! The Euclidean minimum spanning tree is contained in the relative neighborhood graph, which is contained in the
+//! Gabriel graph, which is contained in the Delaunay triangulation. So each of them is extracted from the edges of
+//! a finished [`Triangulation`] instead of from all pairs of vertices.
+//!
+//! The graphs are returned as edge lists of vertex index pairs, with the smaller index first.
+//! Only the vertices of the triangulation are part of the graphs, i.e. not the ignored or duplicate ones.
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+use crate::{
+    RitaError, Triangulation,
+    decimation::vertex_neighbors,
+    utils::types::{Vertex2, VertexIdx},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// An undirected edge as vertex indices, the smaller index first.
+pub type Edge = (VertexIdx, VertexIdx);
+
+/// The squared distance of two vertices.
+fn dist_sq(a: Vertex2, b: Vertex2) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+}
+
+/// The graphs are subgraphs of the Delaunay triangulation only, not of a regular one.
+fn check_unweighted(triangulation: &Triangulation) -> HowResult<()> {
+    if triangulation.weights().is_some() {
+        return Err(anyhow::Error::new(RitaError::UnweightedRequired));
+    }
+
+    HowOk(())
+}
+
+/// The root of the set of `v_idx`, halving the paths on the way.
+fn find(parents: &mut [VertexIdx], mut v_idx: VertexIdx) -> VertexIdx {
+    while parents[v_idx] != v_idx {
+        parents[v_idx] = parents[parents[v_idx]];
+        v_idx = parents[v_idx];
+    }
+
+    v_idx
+}
+
+/// Compute the Euclidean minimum spanning tree of the vertices of a triangulation, via Kruskal's algorithm.
+///
+/// The edges are sorted by increasing length, ties are broken by the vertex indices.
+///
+/// ## Errors
+/// Returns an error if the triangulation is weighted.
+pub fn euclidean_mst(triangulation: &Triangulation) -> HowResult<Vec<Edge>> {
+    check_unweighted(triangulation)?;
+    let vertices = triangulation.vertices();
+
+    let mut edges: Vec<(f64, VertexIdx, VertexIdx)> = triangulation
+        .edges()
+        .map(|(v0, v1)| (dist_sq(vertices[v0], vertices[v1]), v0, v1))
+        .collect();
+    edges.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+
+    let mut parents: Vec<VertexIdx> = (0..vertices.len()).collect();
+    let mut tree = Vec::new();
+    for (_, v0, v1) in edges {
+        let (root0, root1) = (find(&mut parents, v0), find(&mut parents, v1));
+        if root0 != root1 {
+            parents[root0] = root1;
+            tree.push((v0, v1));
+        }
+    }
+
+    HowOk(tree)
+}
+
+/// Compute the Gabriel graph of the vertices of a triangulation.
+///
+/// An edge is part of it, if the closed disk with the edge as its diameter contains no other vertex.
+/// For a Delaunay edge, only the opposite vertices of its two triangles need to be checked,
+/// i.e. both angles opposite to the edge are acute.
+///
+/// ## Errors
+/// Returns an error if the triangulation is weighted.
+pub fn gabriel_graph(triangulation: &Triangulation) -> HowResult<Vec<Edge>> {
+    check_unweighted(triangulation)?;
+    let vertices = triangulation.vertices();
+
+    let mut non_gabriel = BTreeSet::new();
+    for [a, b, c] in triangulation.tri_indices() {
+        for (v0, v1, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+            let [p, q, r] = [vertices[v0], vertices[v1], vertices[opposite]];
+            let dot = (p[0] - r[0]) * (q[0] - r[0]) + (p[1] - r[1]) * (q[1] - r[1]);
+            if dot <= 0.0 {
+                non_gabriel.insert((v0.min(v1), v0.max(v1)));
+            }
+        }
+    }
+
+    HowOk(
+        triangulation
+            .edges()
+            .filter(|edge| !non_gabriel.contains(edge))
+            .collect(),
+    )
+}
+
+/// Compute the relative neighborhood graph of the vertices of a triangulation.
+///
+/// An edge is part of it, if no other vertex is closer to both of its vertices than they are to each other,
+/// i.e. the open lune of the edge is empty.
+///
+/// The lune is searched from the Gabriel edges, walking the neighbors within the disk around the edge's midpoint
+/// that contains the lune, as the vertices of a Delaunay triangulation in any disk are connected.
+///
+/// ## Errors
+/// Returns an error if the triangulation is weighted.
+pub fn relative_neighborhood_graph(triangulation: &Triangulation) -> HowResult<Vec<Edge>> {
+    let gabriel = gabriel_graph(triangulation)?;
+    let vertices = triangulation.vertices();
+    let neighbors = vertex_neighbors(triangulation);
+    let mut visited = vec![false; vertices.len()];
+
+    let is_lune_empty = |visited: &mut [bool], v0: VertexIdx, v1: VertexIdx| {
+        let [a, b] = [vertices[v0], vertices[v1]];
+        let len_sq = dist_sq(a, b);
+        let mid = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+        // the lune is within 3/4 of the edge's length of its midpoint
+        let in_disk = |v: Vertex2| dist_sq(v, mid) < 0.75 * len_sq;
+        let in_lune = |v: Vertex2| dist_sq(v, a) < len_sq && dist_sq(v, b) < len_sq;
+
+        let mut to_visit = vec![v0];
+        let mut seen = vec![v0];
+        visited[v0] = true;
+        let mut is_empty = true;
+        while let Some(v_idx) = to_visit.pop() {
+            for &neighbor in &neighbors[v_idx] {
+                if visited[neighbor] || !in_disk(vertices[neighbor]) {
+                    continue;
+                }
+                if in_lune(vertices[neighbor]) {
+                    is_empty = false;
+                    to_visit.clear();
+                    break;
+                }
+                visited[neighbor] = true;
+                seen.push(neighbor);
+                to_visit.push(neighbor);
+            }
+        }
+
+        for v_idx in seen {
+            visited[v_idx] = false;
+        }
+        is_empty
+    };
+
+    HowOk(
+        gabriel
+            .into_iter()
+            .filter(|&(v0, v1)| is_lune_empty(&mut visited, v0, v1))
+            .collect(),
+    )
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use rita_test_utils::sample_vertices_2d;
+
+    /// The graphs by their definitions, checking all pairs of vertices.
+    fn brute_force(vertices: &[Vertex2]) -> (Vec<Edge>, Vec<Edge>) {
+        let mut gabriel = Vec::new();
+        let mut rng = Vec::new();
+        for v0 in 0..vertices.len() {
+            for v1 in v0 + 1..vertices.len() {
+                let [a, b] = [vertices[v0], vertices[v1]];
+                let len_sq = dist_sq(a, b);
+                let mid = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+                let others = (0..vertices.len()).filter(|&v_idx| v_idx != v0 && v_idx != v1);
+
+                if others
+                    .clone()
+                    .all(|v_idx| dist_sq(vertices[v_idx], mid) > len_sq / 4.0)
+                {
+                    gabriel.push((v0, v1));
+                }
+                if others.clone().all(|v_idx| {
+                    dist_sq(vertices[v_idx], a) >= len_sq || dist_sq(vertices[v_idx], b) >= len_sq
+                }) {
+                    rng.push((v0, v1));
+                }
+            }
+        }
+
+        (gabriel, rng)
+    }
+
+    #[test]
+    fn test_proximity_graphs() {
+        let vertices = sample_vertices_2d(300, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let (expected_gabriel, expected_rng) = brute_force(&vertices);
+
+        let mut gabriel = gabriel_graph(&triangulation).unwrap();
+        gabriel.sort_unstable();
+        assert_eq!(gabriel, expected_gabriel);
+
+        let mut rng = relative_neighborhood_graph(&triangulation).unwrap();
+        rng.sort_unstable();
+        assert_eq!(rng, expected_rng);
+
+        // a spanning tree, whose edges are in the relative neighborhood graph
+        let mst = euclidean_mst(&triangulation).unwrap();
+        assert_eq!(mst.len(), vertices.len() - 1);
+        assert!(mst.iter().all(|edge| rng.binary_search(edge).is_ok()));
+        let mst_len: f64 = mst
+            .iter()
+            .map(|&(v0, v1)| dist_sq(vertices[v0], vertices[v1]).sqrt())
+            .sum();
+
+        // Prim's algorithm on all pairs gives the same length
+        let mut dists = vec![f64::INFINITY; vertices.len()];
+        let mut in_tree = vec![false; vertices.len()];
+        dists[0] = 0.0;
+        let mut expected_len = 0.0;
+        for _ in 0..vertices.len() {
+            let next = (0..vertices.len())
+                .filter(|&v_idx| !in_tree[v_idx])
+                .min_by(|&a, &b| dists[a].total_cmp(&dists[b]))
+                .unwrap();
+            in_tree[next] = true;
+            expected_len += dists[next];
+            for v_idx in 0..vertices.len() {
+                let dist = dist_sq(vertices[next], vertices[v_idx]).sqrt();
+                dists[v_idx] = dists[v_idx].min(dist);
+            }
+        }
+        assert!((mst_len - expected_len).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_square() {
+        // cocircular, so neither diagonal is a Gabriel edge, whichever the triangulation contains
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let mut gabriel = gabriel_graph(&triangulation).unwrap();
+        gabriel.sort_unstable();
+        assert_eq!(gabriel, vec![(0, 1), (0, 3), (1, 2), (2, 3)]);
+        assert_eq!(
+            relative_neighborhood_graph(&triangulation).unwrap().len(),
+            4
+        );
+        assert_eq!(euclidean_mst(&triangulation).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,56 @@
+//! Handles to the simplices of a triangulation or tetrahedralization, e.g. as hints where to start locating.
+//!
+//! A raw simplex index can be invalidated by the flips or cavities of later insertions, i.e. it may then refer to a
+//! different simplex, or to a deleted slot. A handle additionally records the generation of the structure it was taken
+//! from, which is advanced on every change of the structure. Resolving a handle checks the generation, such that a stale
+//! handle is detected instead of silently referring to another simplex.
+//!
+//! As insertion hints, stale handles are still fine: any existing simplex is a valid starting point of the walk,
+//! it may only be further away. Hints to simplices that do not exist anymore are ignored.
+
+use core::marker::PhantomData;
+
+/// The kind of a [`Handle`] to a triangle of a [`Triangulation`](crate::Triangulation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tri {}
+
+/// The kind of a [`Handle`] to a tetrahedron of a [`Tetrahedralization`](crate::Tetrahedralization).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tet {}
+
+/// An opaque handle to a simplex, checked against the generation of the structure it was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle<K> {
+    idx: usize,
+    generation: u64,
+    kind: PhantomData<K>,
+}
+
+/// A handle to a triangle of a [`Triangulation`](crate::Triangulation).
+pub type TriHandle = Handle<Tri>;
+
+/// A handle to a tetrahedron of a [`Tetrahedralization`](crate::Tetrahedralization).
+pub type TetHandle = Handle<Tet>;
+
+impl<K> Handle<K> {
+    pub(crate) const fn new(idx: usize, generation: u64) -> Self {
+        Self {
+            idx,
+            generation,
+            kind: PhantomData,
+        }
+    }
+
+    /// The index of the simplex at the time the handle was taken.
+    ///
+    /// Only valid as long as the structure did not change, see [`Triangulation::resolve_tri`](crate::Triangulation::resolve_tri)
+    /// and [`Tetrahedralization::resolve_tet`](crate::Tetrahedralization::resolve_tet) for checked access.
+    pub const fn idx(&self) -> usize {
+        self.idx
+    }
+
+    /// The generation of the structure, that the handle was taken from.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+}
@@ -0,0 +1,1291 @@
+//! Import and export of triangulations, e.g. to inspect them in Blender or MeshLab.
+//!
+//! OBJ meshes are written into any [`core::fmt::Write`], e.g. a `String` that is then saved to a file:
+//!
+//! ```
+//! use rita::{Triangulation, io::ObjZ};
+//!
+//! let mut triangulation = Triangulation::new(None);
+//! triangulation.insert_vertices([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], None, false).unwrap();
+//!
+//! let mut obj = String::new();
+//! triangulation.write_obj(&mut obj, ObjZ::Zero).unwrap();
+//! // three vertices and one face
+//! assert_eq!(obj.lines().count(), 4);
+//! ```
+//!
+//! PLY files are read from and written to bytes, in ASCII or binary, see [`read_ply`] and [`write_ply`].
+//! Besides the positions and triangles, they store the weights of the vertices as a `weight` property,
+//! so weighted inputs and outputs round-trip.
+//!
+//! The `.node`, `.ele` and `.poly` files of the reference tools Triangle and TetGen are read from and written to text,
+//! see [`read_node_ele`], [`write_node_ele`], [`read_poly`] and [`write_poly`], e.g. to compare results.
+//!
+//! 2D triangulations are rendered as SVG figures, see [`Triangulation::to_svg`] and [`SvgOptions`].
+//! With the `geojson` feature, their triangles and power cells are exported as GeoJSON for GIS tools,
+//! see `Triangulation::to_geojson` and `Triangulation::cells_to_geojson`.
+//!
+//! Points with optional weights are loaded from CSV or XYZ text, see [`points`].
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use crate::{
+    RitaError, Triangulation,
+    utils::types::{Vertex2, Vertex3, VertexIdx},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+pub mod points;
+
+/// The z coordinate of the vertices of a 2D triangulation in an OBJ mesh, see [`Triangulation::write_obj`](crate::Triangulation::write_obj).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ObjZ {
+    /// All vertices at `z = 0`, i.e. the flat triangulation.
+    #[default]
+    Zero,
+    /// The height of the vertices, i.e. their lifting to the paraboloid, see [`Triangulation::height`](crate::Triangulation::height).
+    Height,
+    /// The weights of the vertices, `0` for unweighted triangulations.
+    Weight,
+}
+
+/// Write counter-clockwise triangles as an OBJ mesh.
+///
+/// Only the vertices referenced by a triangle are written, in the order of their indices,
+/// as OBJ indices start at `1` and every written vertex should be part of the mesh.
+pub(crate) fn write_obj(
+    writer: &mut impl Write,
+    num_vertices: usize,
+    position: impl Fn(VertexIdx) -> Vertex3,
+    tris: &[[VertexIdx; 3]],
+) -> HowResult<()> {
+    const NOT_WRITTEN: usize = 0;
+    let mut obj_idxs = vec![NOT_WRITTEN; num_vertices];
+    for &v_idx in tris.iter().flatten() {
+        obj_idxs[v_idx] = 1;
+    }
+
+    let mut num_written = 0;
+    for (v_idx, obj_idx) in obj_idxs.iter_mut().enumerate() {
+        if *obj_idx == NOT_WRITTEN {
+            continue;
+        }
+
+        let [x, y, z] = position(v_idx);
+        writeln!(writer, "v {x} {y} {z}")?;
+        num_written += 1;
+        *obj_idx = num_written;
+    }
+
+    for tri in tris {
+        let [a, b, c] = tri.map(|v_idx| obj_idxs[v_idx]);
+        writeln!(writer, "f {a} {b} {c}")?;
+    }
+
+    HowOk(())
+}
+
+/// The encoding of the data of a PLY file, after its ASCII header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// Whitespace separated numbers, one element per line.
+    #[default]
+    Ascii,
+    /// Binary numbers, least significant byte first.
+    BinaryLittleEndian,
+    /// Binary numbers, most significant byte first.
+    BinaryBigEndian,
+}
+
+/// The vertices, weights and triangles of a PLY file.
+///
+/// 2D data is stored with `z = 0`, see [`PlyData::vertices_2d`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlyData {
+    /// The positions of the vertices.
+    pub vertices: Vec<Vertex3>,
+    /// The weights of the vertices, `Some` if the vertices have a `weight` property.
+    pub weights: Option<Vec<f64>>,
+    /// The triangles, as indices into the vertices.
+    pub faces: Vec<[VertexIdx; 3]>,
+}
+
+impl PlyData {
+    /// The positions of the vertices without their z coordinate, e.g. to insert them into a [`Triangulation`](crate::Triangulation).
+    pub fn vertices_2d(&self) -> Vec<Vertex2> {
+        self.vertices.iter().map(|&[x, y, _]| [x, y]).collect()
+    }
+}
+
+/// The type of a PLY property, or of the count and the items of a list property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyScalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyScalar {
+    fn parse(name: &str) -> HowResult<Self> {
+        HowOk(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return Err(ply_error("unknown property type")),
+        })
+    }
+}
+
+/// A property of a PLY element, a list if it has a count type.
+#[derive(Debug)]
+struct PlyProperty {
+    name: String,
+    count: Option<PlyScalar>,
+    scalar: PlyScalar,
+}
+
+/// An element of a PLY file, e.g. the vertices, and the properties of each of its items.
+#[derive(Debug)]
+struct PlyElement {
+    name: String,
+    len: usize,
+    properties: Vec<PlyProperty>,
+}
+
+fn ply_error(reason: &'static str) -> anyhow::Error {
+    anyhow::Error::new(RitaError::InvalidFile {
+        format: "PLY",
+        line: None,
+        reason,
+    })
+}
+
+/// The data of a PLY file after its header, read number by number.
+enum PlyBody<'a> {
+    Ascii(core::str::SplitAsciiWhitespace<'a>),
+    Binary { bytes: &'a [u8], big_endian: bool },
+}
+
+impl PlyBody<'_> {
+    fn next(&mut self, scalar: PlyScalar) -> HowResult<f64> {
+        match self {
+            Self::Ascii(tokens) => tokens
+                .next()
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| ply_error("missing or invalid number")),
+            Self::Binary { bytes, big_endian } => {
+                macro_rules! read {
+                    ($t:ty) => {{
+                        const SIZE: usize = size_of::<$t>();
+                        if bytes.len() < SIZE {
+                            return Err(ply_error("unexpected end of data"));
+                        }
+                        let (number, rest) = bytes.split_at(SIZE);
+                        *bytes = rest;
+                        let number = number.try_into().unwrap();
+                        if *big_endian {
+                            <$t>::from_be_bytes(number) as f64
+                        } else {
+                            <$t>::from_le_bytes(number) as f64
+                        }
+                    }};
+                }
+
+                HowOk(match scalar {
+                    PlyScalar::I8 => read!(i8),
+                    PlyScalar::U8 => read!(u8),
+                    PlyScalar::I16 => read!(i16),
+                    PlyScalar::U16 => read!(u16),
+                    PlyScalar::I32 => read!(i32),
+                    PlyScalar::U32 => read!(u32),
+                    PlyScalar::F32 => read!(f32),
+                    PlyScalar::F64 => read!(f64),
+                })
+            }
+        }
+    }
+}
+
+/// Read the vertices, weights and triangles of a PLY file.
+///
+/// Reads the `x`, `y`, `z` and `weight` properties of the `vertex` element, a missing `z` is `0`, and
+/// the `vertex_indices` (or `vertex_index`) lists of the `face` element. Other elements and properties are skipped.
+///
+/// ## Errors
+/// Returns an error if the file is not a valid PLY file, a face is not a triangle or references a missing vertex.
+pub fn read_ply(bytes: &[u8]) -> HowResult<PlyData> {
+    const END_HEADER: &[u8] = b"end_header";
+    let header_len = bytes
+        .windows(END_HEADER.len())
+        .position(|window| window == END_HEADER)
+        .ok_or_else(|| ply_error("missing end_header"))?;
+    let body_start = bytes[header_len..]
+        .iter()
+        .position(|&byte| byte == b'\n')
+        .map_or(bytes.len(), |newline| header_len + newline + 1);
+    let header =
+        core::str::from_utf8(&bytes[..header_len]).map_err(|_| ply_error("header is not ASCII"))?;
+
+    let mut lines = header.lines().map(str::trim);
+    if lines.next() != Some("ply") {
+        return Err(ply_error("missing magic number"));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for line in lines {
+        let tokens: Vec<&str> = line.split_ascii_whitespace().collect();
+        match tokens.as_slice() {
+            ["format", name, _] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(ply_error("unknown format")),
+                });
+            }
+            ["element", name, len] => elements.push(PlyElement {
+                name: name.to_string(),
+                len: len
+                    .parse()
+                    .map_err(|_| ply_error("invalid element count"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, scalar, name] => elements
+                .last_mut()
+                .ok_or_else(|| ply_error("property before element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    count: Some(PlyScalar::parse(count)?),
+                    scalar: PlyScalar::parse(scalar)?,
+                }),
+            ["property", scalar, name] => elements
+                .last_mut()
+                .ok_or_else(|| ply_error("property before element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    count: None,
+                    scalar: PlyScalar::parse(scalar)?,
+                }),
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            _ => return Err(ply_error("invalid header line")),
+        }
+    }
+
+    let mut body = match format.ok_or_else(|| ply_error("missing format"))? {
+        PlyFormat::Ascii => PlyBody::Ascii(
+            core::str::from_utf8(&bytes[body_start..])
+                .map_err(|_| ply_error("data is not ASCII"))?
+                .split_ascii_whitespace(),
+        ),
+        PlyFormat::BinaryLittleEndian => PlyBody::Binary {
+            bytes: &bytes[body_start..],
+            big_endian: false,
+        },
+        PlyFormat::BinaryBigEndian => PlyBody::Binary {
+            bytes: &bytes[body_start..],
+            big_endian: true,
+        },
+    };
+
+    let mut ply = PlyData::default();
+    for element in &elements {
+        let is_vertex = element.name == "vertex";
+        let is_face = element.name == "face";
+        let has_weights = is_vertex && element.properties.iter().any(|p| p.name == "weight");
+        let mut weights = Vec::new();
+
+        for _ in 0..element.len {
+            let mut vertex = [0.0; 3];
+            for property in &element.properties {
+                let Some(count) = property.count else {
+                    let value = body.next(property.scalar)?;
+                    match (is_vertex, property.name.as_str()) {
+                        (true, "x") => vertex[0] = value,
+                        (true, "y") => vertex[1] = value,
+                        (true, "z") => vertex[2] = value,
+                        (true, "weight") => weights.push(value),
+                        _ => {}
+                    }
+                    continue;
+                };
+
+                let len = body.next(count)? as usize;
+                let is_face_list = is_face
+                    && (property.name == "vertex_indices" || property.name == "vertex_index");
+                if is_face_list && len != 3 {
+                    return Err(ply_error("only triangle faces are supported"));
+                }
+
+                let values = (0..len)
+                    .map(|_| body.next(property.scalar))
+                    .collect::<HowResult<Vec<f64>>>()?;
+                if is_face_list {
+                    if values.iter().any(|&value| value < 0.0) {
+                        return Err(ply_error("face with a negative vertex index"));
+                    }
+                    ply.faces
+                        .push([values[0], values[1], values[2]].map(|value| value as VertexIdx));
+                }
+            }
+
+            if is_vertex {
+                ply.vertices.push(vertex);
+            }
+        }
+
+        if has_weights {
+            ply.weights = Some(weights);
+        }
+    }
+
+    if ply
+        .faces
+        .iter()
+        .flatten()
+        .any(|&v_idx| v_idx >= ply.vertices.len())
+    {
+        return Err(ply_error("face references a missing vertex"));
+    }
+
+    HowOk(ply)
+}
+
+/// Write vertices, weights and triangles as a PLY file.
+///
+/// The positions and weights are written as doubles, the weights as a `weight` property of the vertices if `Some`.
+pub fn write_ply(ply: &PlyData, format: PlyFormat) -> Vec<u8> {
+    let format_name = match format {
+        PlyFormat::Ascii => "ascii",
+        PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        PlyFormat::BinaryBigEndian => "binary_big_endian",
+    };
+
+    let mut header = String::new();
+    // writing into a string can not fail
+    let _ = write!(
+        header,
+        "ply\nformat {format_name} 1.0\nelement vertex {}\n",
+        ply.vertices.len()
+    );
+    header.push_str("property double x\nproperty double y\nproperty double z\n");
+    if ply.weights.is_some() {
+        header.push_str("property double weight\n");
+    }
+    let _ = write!(
+        header,
+        "element face {}\nproperty list uchar int vertex_indices\nend_header\n",
+        ply.faces.len()
+    );
+
+    let weight = |v_idx: VertexIdx| ply.weights.as_ref().map(|weights| weights[v_idx]);
+    let mut bytes = header.into_bytes();
+    match format {
+        PlyFormat::Ascii => {
+            let mut body = String::new();
+            for (v_idx, [x, y, z]) in ply.vertices.iter().enumerate() {
+                let _ = match weight(v_idx) {
+                    Some(w) => writeln!(body, "{x} {y} {z} {w}"),
+                    None => writeln!(body, "{x} {y} {z}"),
+                };
+            }
+            for [a, b, c] in &ply.faces {
+                let _ = writeln!(body, "3 {a} {b} {c}");
+            }
+            bytes.extend_from_slice(body.as_bytes());
+        }
+        PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => {
+            let big_endian = format == PlyFormat::BinaryBigEndian;
+            let push_f64 = |bytes: &mut Vec<u8>, value: f64| {
+                bytes.extend_from_slice(&if big_endian {
+                    value.to_be_bytes()
+                } else {
+                    value.to_le_bytes()
+                });
+            };
+            for (v_idx, &vertex) in ply.vertices.iter().enumerate() {
+                for value in vertex.into_iter().chain(weight(v_idx)) {
+                    push_f64(&mut bytes, value);
+                }
+            }
+            for face in &ply.faces {
+                bytes.push(3);
+                for v_idx in face.map(|v_idx| v_idx as i32) {
+                    bytes.extend_from_slice(&if big_endian {
+                        v_idx.to_be_bytes()
+                    } else {
+                        v_idx.to_le_bytes()
+                    });
+                }
+            }
+        }
+    }
+
+    bytes
+}
+
+/// The vertices, weights and elements of the `.node` and `.ele` files of
+/// [Triangle](https://www.cs.cmu.edu/~quake/triangle.html), with `D = 2` and `N = 3`,
+/// or [TetGen](https://wias-berlin.de/software/tetgen/), with `D = 3` and `N = 4`.
+///
+/// The weights are the first attribute of the vertices, which is what TetGen reads as weights with its `-w` switch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeEleData<const D: usize, const N: usize> {
+    /// The positions of the vertices.
+    pub vertices: Vec<[f64; D]>,
+    /// The weights of the vertices, `Some` if the vertices have attributes.
+    pub weights: Option<Vec<f64>>,
+    /// The triangles or tetrahedra, as indices into the vertices.
+    pub elements: Vec<[VertexIdx; N]>,
+}
+
+/// The vertices, weights, segments and holes of a `.poly` file of [Triangle](https://www.cs.cmu.edu/~quake/triangle.html).
+///
+/// The weights are the first attribute of the vertices, as for [`NodeEleData`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolyData {
+    /// The positions of the vertices.
+    pub vertices: Vec<Vertex2>,
+    /// The weights of the vertices, `Some` if the vertices have attributes.
+    pub weights: Option<Vec<f64>>,
+    /// The segments, as indices into the vertices.
+    pub segments: Vec<[VertexIdx; 2]>,
+    /// A point inside each hole.
+    pub holes: Vec<Vertex2>,
+}
+
+fn shewchuk_error(reason: &'static str) -> anyhow::Error {
+    anyhow::Error::new(RitaError::InvalidFile {
+        format: "Triangle or TetGen",
+        line: None,
+        reason,
+    })
+}
+
+/// The lines of a Triangle or TetGen file as numbers, without comments and empty lines.
+struct ShewchukLines<'a>(core::str::Lines<'a>);
+
+impl<'a> ShewchukLines<'a> {
+    fn new(text: &'a str) -> Self {
+        Self(text.lines())
+    }
+
+    /// The numbers of the next line, which needs at least `min_len` of them.
+    fn next(&mut self, min_len: usize) -> HowResult<Vec<f64>> {
+        for line in self.0.by_ref() {
+            let line = line.split('#').next().unwrap_or_default();
+            let numbers = line
+                .split_ascii_whitespace()
+                .map(|token| token.parse())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| shewchuk_error("invalid number"))?;
+            if numbers.is_empty() {
+                continue;
+            }
+            if numbers.len() < min_len {
+                return Err(shewchuk_error("missing number"));
+            }
+            return HowOk(numbers);
+        }
+
+        Err(shewchuk_error("unexpected end of file"))
+    }
+}
+
+/// A number of a Triangle or TetGen file that is used as a count or an index.
+fn shewchuk_idx(number: f64) -> HowResult<usize> {
+    if number < 0.0 || number.fract() != 0.0 {
+        return Err(shewchuk_error("invalid count or index"));
+    }
+    HowOk(number as usize)
+}
+
+/// Read the vertex list of a `.node` or `.poly` file, returning the vertices, their weights and the number of the first vertex.
+#[allow(clippy::type_complexity)]
+fn read_shewchuk_vertices<const D: usize>(
+    lines: &mut ShewchukLines,
+) -> HowResult<(Vec<[f64; D]>, Option<Vec<f64>>, usize)> {
+    let header = lines.next(1)?;
+    let num_vertices = shewchuk_idx(header[0])?;
+    let count = |i: usize| header.get(i).copied().map_or(HowOk(0), shewchuk_idx);
+    let (num_attributes, num_markers) = (count(2)?, count(3)?);
+    if num_vertices > 0 && header.get(1).is_some_and(|&dim| dim != D as f64) {
+        return Err(shewchuk_error("wrong dimension"));
+    }
+
+    let mut vertices = Vec::with_capacity(num_vertices);
+    let mut weights = Vec::new();
+    let mut first = 0;
+    for i in 0..num_vertices {
+        let numbers = lines.next(1 + D + num_attributes + num_markers)?;
+        let number = shewchuk_idx(numbers[0])?;
+        if i == 0 {
+            first = number;
+        }
+        if first > 1 || number != first + i {
+            return Err(shewchuk_error(
+                "vertices must be numbered consecutively from 0 or 1",
+            ));
+        }
+
+        vertices.push(core::array::from_fn(|d| numbers[1 + d]));
+        if num_attributes > 0 {
+            weights.push(numbers[1 + D]);
+        }
+    }
+
+    HowOk((vertices, (num_attributes > 0).then_some(weights), first))
+}
+
+/// Read the vertex indices of an element, numbered from `first`.
+fn read_shewchuk_element<const N: usize>(
+    numbers: &[f64],
+    first: usize,
+    num_vertices: usize,
+) -> HowResult<[VertexIdx; N]> {
+    let mut element = [0; N];
+    for (v_idx, &number) in element.iter_mut().zip(numbers) {
+        *v_idx = shewchuk_idx(number)?
+            .checked_sub(first)
+            .filter(|&v_idx| v_idx < num_vertices)
+            .ok_or_else(|| shewchuk_error("element references a missing vertex"))?;
+    }
+    HowOk(element)
+}
+
+/// Read the `.node` file and, if given, the `.ele` file of Triangle or TetGen, see [`NodeEleData`].
+///
+/// The vertices may be numbered from `0` or `1`. Further attributes, boundary markers and
+/// the extra nodes of second order elements are skipped.
+///
+/// ## Errors
+/// Returns an error if a file is not valid, has the wrong dimension or an element references a missing vertex.
+pub fn read_node_ele<const D: usize, const N: usize>(
+    node: &str,
+    ele: Option<&str>,
+) -> HowResult<NodeEleData<D, N>> {
+    let (vertices, weights, first) = read_shewchuk_vertices(&mut ShewchukLines::new(node))?;
+
+    let mut elements = Vec::new();
+    if let Some(ele) = ele {
+        let mut lines = ShewchukLines::new(ele);
+        let header = lines.next(2)?;
+        let num_elements = shewchuk_idx(header[0])?;
+        let num_nodes = shewchuk_idx(header[1])?;
+        if num_nodes < N {
+            return Err(shewchuk_error("too few nodes per element"));
+        }
+
+        elements.reserve(num_elements);
+        for _ in 0..num_elements {
+            let numbers = lines.next(1 + num_nodes)?;
+            elements.push(read_shewchuk_element(&numbers[1..], first, vertices.len())?);
+        }
+    }
+
+    HowOk(NodeEleData {
+        vertices,
+        weights,
+        elements,
+    })
+}
+
+/// Write the vertex list of a `.node` or `.poly` file, numbered from `1`.
+fn write_shewchuk_vertices<const D: usize>(
+    writer: &mut impl Write,
+    vertices: &[[f64; D]],
+    weights: Option<&[f64]>,
+) -> HowResult<()> {
+    writeln!(
+        writer,
+        "{} {D} {} 0",
+        vertices.len(),
+        usize::from(weights.is_some())
+    )?;
+    for (v_idx, vertex) in vertices.iter().enumerate() {
+        write!(writer, "{}", v_idx + 1)?;
+        for coord in vertex.iter().chain(weights.map(|weights| &weights[v_idx])) {
+            write!(writer, " {coord}")?;
+        }
+        writeln!(writer)?;
+    }
+
+    HowOk(())
+}
+
+/// Write the `.node` and `.ele` files of Triangle or TetGen, see [`NodeEleData`].
+///
+/// The vertices are numbered from `1`, like the tools do by default.
+///
+/// ## Errors
+/// Returns an error if a writer fails.
+pub fn write_node_ele<const D: usize, const N: usize>(
+    data: &NodeEleData<D, N>,
+    node: &mut impl Write,
+    ele: &mut impl Write,
+) -> HowResult<()> {
+    write_shewchuk_vertices(node, &data.vertices, data.weights.as_deref())?;
+
+    writeln!(ele, "{} {N} 0", data.elements.len())?;
+    for (element_idx, element) in data.elements.iter().enumerate() {
+        write!(ele, "{}", element_idx + 1)?;
+        for v_idx in element {
+            write!(ele, " {}", v_idx + 1)?;
+        }
+        writeln!(ele)?;
+    }
+
+    HowOk(())
+}
+
+/// Read a `.poly` file of Triangle, see [`PolyData`].
+///
+/// If the `.poly` file has no vertices, they are read from the `.node` file, as Triangle does.
+/// Further attributes, boundary markers and regional attributes are skipped.
+///
+/// ## Errors
+/// Returns an error if a file is not valid, the vertices are missing or a segment references a missing vertex.
+pub fn read_poly(poly: &str, node: Option<&str>) -> HowResult<PolyData> {
+    let mut lines = ShewchukLines::new(poly);
+    let (mut vertices, mut weights, mut first) = read_shewchuk_vertices(&mut lines)?;
+    if vertices.is_empty() {
+        let node = node.ok_or_else(|| shewchuk_error("missing vertices"))?;
+        (vertices, weights, first) = read_shewchuk_vertices(&mut ShewchukLines::new(node))?;
+    }
+
+    let num_segments = shewchuk_idx(lines.next(1)?[0])?;
+    let segments = (0..num_segments)
+        .map(|_| read_shewchuk_element(&lines.next(3)?[1..], first, vertices.len()))
+        .collect::<HowResult<Vec<_>>>()?;
+
+    let num_holes = shewchuk_idx(lines.next(1)?[0])?;
+    let holes = (0..num_holes)
+        .map(|_| lines.next(3).map(|numbers| [numbers[1], numbers[2]]))
+        .collect::<HowResult<Vec<_>>>()?;
+
+    HowOk(PolyData {
+        vertices,
+        weights,
+        segments,
+        holes,
+    })
+}
+
+/// Write a `.poly` file of Triangle, see [`PolyData`], with the vertices numbered from `1`.
+///
+/// ## Errors
+/// Returns an error if the writer fails.
+pub fn write_poly(poly: &PolyData, writer: &mut impl Write) -> HowResult<()> {
+    write_shewchuk_vertices(writer, &poly.vertices, poly.weights.as_deref())?;
+
+    writeln!(writer, "{} 0", poly.segments.len())?;
+    for (segment_idx, [a, b]) in poly.segments.iter().enumerate() {
+        writeln!(writer, "{} {} {}", segment_idx + 1, a + 1, b + 1)?;
+    }
+
+    writeln!(writer, "{}", poly.holes.len())?;
+    for (hole_idx, [x, y]) in poly.holes.iter().enumerate() {
+        writeln!(writer, "{} {x} {y}", hole_idx + 1)?;
+    }
+
+    HowOk(())
+}
+
+/// The layout and content of an SVG figure of a triangulation, see [`Triangulation::to_svg`].
+///
+/// Every layer is a group with a `class`, i.e. `tris`, `hull`, `voronoi`, `vertices`, `redundant` and `ignored`,
+/// so the default colors can be restyled with CSS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgOptions {
+    /// The width of the figure in pixels, the height follows from the aspect ratio of the vertices.
+    pub width: f64,
+    /// The margin around the vertices in pixels.
+    pub margin: f64,
+    /// The width of the lines in pixels.
+    pub stroke_width: f64,
+    /// The radius of the vertices in pixels, `0` to skip them.
+    pub vertex_radius: f64,
+    /// Whether to draw the convex hull.
+    pub hull: bool,
+    /// Whether to overlay the Voronoi diagram, or the power diagram of a weighted triangulation.
+    pub voronoi: bool,
+    /// Whether to highlight the redundant and ignored vertices.
+    pub highlight_skipped: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            margin: 20.0,
+            stroke_width: 1.0,
+            vertex_radius: 2.5,
+            hull: true,
+            voronoi: false,
+            highlight_skipped: true,
+        }
+    }
+}
+
+/// Render a triangulation as an SVG figure, see [`Triangulation::to_svg`].
+pub(crate) fn write_svg(triangulation: &Triangulation, options: &SvgOptions) -> String {
+    let vertices = triangulation.vertices();
+    let SvgOptions {
+        width,
+        margin,
+        stroke_width,
+        vertex_radius,
+        ..
+    } = *options;
+
+    // the bounding box of all vertices is scaled to the width, with y pointing up
+    let mut min = [f64::INFINITY; 2];
+    let mut max = [f64::NEG_INFINITY; 2];
+    for v in vertices {
+        for d in 0..2 {
+            min[d] = min[d].min(v[d]);
+            max[d] = max[d].max(v[d]);
+        }
+    }
+    if vertices.is_empty() {
+        (min, max) = ([0.0; 2], [1.0; 2]);
+    }
+    let [dx, dy] = [max[0] - min[0], max[1] - min[1]];
+    let extent = if dx > 0.0 { dx } else { dy.max(1.0) };
+    let scale = (width - 2.0 * margin) / extent;
+    let height = dy * scale + 2.0 * margin;
+    let to_svg = |[x, y]: Vertex2| [margin + (x - min[0]) * scale, margin + (max[1] - y) * scale];
+
+    let mut svg = String::new();
+    // writing into a string can not fail
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.2}\" height=\"{height:.2}\" viewBox=\"0 0 {width:.2} {height:.2}\">"
+    );
+
+    let mut path = |class: &str,
+                    color: &str,
+                    polylines: &mut dyn Iterator<Item = (Vec<Vertex2>, bool)>| {
+        let _ = write!(
+            svg,
+            "<path class=\"{class}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"{stroke_width}\" stroke-linejoin=\"round\" d=\""
+        );
+        for (polyline, closed) in polylines {
+            for (i, [x, y]) in polyline.into_iter().map(to_svg).enumerate() {
+                let command = if i == 0 { 'M' } else { 'L' };
+                let _ = write!(svg, "{command}{x:.2} {y:.2}");
+            }
+            if closed {
+                svg.push('Z');
+            }
+        }
+        svg.push_str("\"/>\n");
+    };
+
+    path(
+        "tris",
+        "#4a4a4a",
+        &mut triangulation
+            .edges()
+            .map(|(a, b)| (vec![vertices[a], vertices[b]], false)),
+    );
+
+    if options.voronoi {
+        // the rays of unbounded cells leave the figure
+        let far = 2.0 * (dx + dy) + 1.0;
+        let cells = crate::voronoi::power_cells(triangulation).unwrap_or_default();
+        path(
+            "voronoi",
+            "#d62728",
+            &mut cells
+                .into_iter()
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| {
+                    let Some([ray_in, ray_out]) = cell.rays else {
+                        return (cell.vertices, true);
+                    };
+                    let (first, last) = (cell.vertices[0], cell.vertices[cell.vertices.len() - 1]);
+                    let mut polyline =
+                        vec![[first[0] + far * ray_in[0], first[1] + far * ray_in[1]]];
+                    polyline.extend(cell.vertices);
+                    polyline.push([last[0] + far * ray_out[0], last[1] + far * ray_out[1]]);
+                    (polyline, false)
+                }),
+        );
+    }
+
+    if options.hull {
+        let hull = triangulation.hull_edges();
+        path(
+            "hull",
+            "#1f77b4",
+            &mut (!hull.is_empty())
+                .then(|| (hull.iter().map(|&v_idx| vertices[v_idx]).collect(), true))
+                .into_iter(),
+        );
+    }
+
+    if vertex_radius > 0.0 {
+        let mut used = vec![false; vertices.len()];
+        for &v_idx in triangulation.tri_indices().iter().flatten() {
+            used[v_idx] = true;
+        }
+        let used: Vec<VertexIdx> = (0..vertices.len()).filter(|&v_idx| used[v_idx]).collect();
+
+        let mut circles = |class: &str, color: &str, v_idxs: &[VertexIdx]| {
+            let _ = writeln!(svg, "<g class=\"{class}\" fill=\"{color}\">");
+            for &v_idx in v_idxs {
+                let [x, y] = to_svg(vertices[v_idx]);
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"{vertex_radius}\"/>"
+                );
+            }
+            svg.push_str("</g>\n");
+        };
+
+        circles("vertices", "#000000", &used);
+        if options.highlight_skipped {
+            circles("redundant", "#ff7f0e", triangulation.redundant_vertices());
+            circles("ignored", "#9467bd", triangulation.ignored_vertices());
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A GeoJSON feature of a counter-clockwise polygon, with its ring closed as GeoJSON requires.
+#[cfg(feature = "geojson")]
+pub(crate) fn geojson_polygon(
+    polygon: &[Vertex2],
+    properties: geojson::JsonObject,
+) -> geojson::Feature {
+    let ring = polygon
+        .iter()
+        .chain(polygon.first())
+        .map(|v| v.to_vec())
+        .collect();
+
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(geojson::Value::Polygon(vec![ring]))),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::{Tetrahedralization, Triangulation};
+    use alloc::{string::String, vec::Vec};
+
+    /// The positions and 0-based triangles of an OBJ mesh.
+    fn parse_obj(obj: &str) -> (Vec<Vertex3>, Vec<[usize; 3]>) {
+        let mut positions = Vec::new();
+        let mut tris = Vec::new();
+        for line in obj.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let coords: Vec<f64> = tokens.map(|token| token.parse().unwrap()).collect();
+                    positions.push([coords[0], coords[1], coords[2]]);
+                }
+                Some("f") => {
+                    let idxs: Vec<usize> = tokens.map(|token| token.parse().unwrap()).collect();
+                    tris.push([idxs[0] - 1, idxs[1] - 1, idxs[2] - 1]);
+                }
+                _ => panic!("unexpected line {line}"),
+            }
+        }
+
+        (positions, tris)
+    }
+
+    #[test]
+    fn test_write_obj() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let mut obj = String::new();
+        triangulation.write_obj(&mut obj, ObjZ::Zero).unwrap();
+        let (positions, tris) = parse_obj(&obj);
+        assert_eq!(positions, vertices.map(|[x, y]| [x, y, 0.0]));
+        assert_eq!(tris.len(), 4);
+
+        // counter-clockwise, i.e. facing up
+        for [a, b, c] in tris {
+            let [a, b, c] = [positions[a], positions[b], positions[c]];
+            assert!((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]) > 0.0);
+        }
+
+        let mut obj = String::new();
+        triangulation.write_obj(&mut obj, ObjZ::Height).unwrap();
+        let (positions, _) = parse_obj(&obj);
+        for (v_idx, [_, _, z]) in positions.into_iter().enumerate() {
+            assert_eq!(z, triangulation.height(v_idx));
+        }
+
+        let mut obj = String::new();
+        triangulation.write_obj(&mut obj, ObjZ::Weight).unwrap();
+        let (positions, _) = parse_obj(&obj);
+        assert!(positions.iter().all(|[_, _, z]| *z == 0.0));
+
+        let mut obj = String::new();
+        Triangulation::new(None)
+            .write_obj(&mut obj, ObjZ::Zero)
+            .unwrap();
+        assert!(obj.is_empty());
+    }
+
+    #[test]
+    fn test_write_obj_skips_unused_vertices() {
+        // the center is inserted twice, the duplicate is not part of the mesh
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [0.5, 0.5], [0.5, 0.5], [0.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let mut obj = String::new();
+        triangulation.write_obj(&mut obj, ObjZ::Zero).unwrap();
+        let (positions, tris) = parse_obj(&obj);
+        assert_eq!(positions.len(), 4);
+        assert!(tris.iter().flatten().all(|&idx| idx < 4));
+    }
+
+    #[test]
+    fn test_write_hull_obj() {
+        let cube: Vec<Vertex3> = (0..8)
+            .map(|i| [i & 1, (i >> 1) & 1, i >> 2].map(f64::from))
+            .chain([[0.5, 0.5, 0.5]])
+            .collect();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&cube, None, false)
+            .unwrap();
+
+        let mut obj = String::new();
+        tetrahedralization.write_hull_obj(&mut obj).unwrap();
+        let (positions, tris) = parse_obj(&obj);
+
+        // the inner vertex is not on the hull
+        assert_eq!(positions, cube[..8].to_vec());
+        assert_eq!(tris.len(), tetrahedralization.num_hull_facets());
+
+        // facing outwards, i.e. the signed volume is positive
+        let volume: f64 = tris
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [positions[a], positions[b], positions[c]];
+                a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0])
+            })
+            .sum::<f64>()
+            / 6.0;
+        assert!((volume - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ply_round_trip() {
+        let ply = PlyData {
+            vertices: vec![
+                [0.0, 0.0, 0.0],
+                [1.5, -0.25, 0.0],
+                [0.1, 1e-20, 2.0],
+                [-3.0, 4.0, 5.0],
+            ],
+            weights: Some(vec![0.0, 0.5, -1.0, 1e10]),
+            faces: vec![[0, 1, 2], [1, 3, 2]],
+        };
+        let unweighted = PlyData {
+            weights: None,
+            ..ply.clone()
+        };
+
+        for format in [
+            PlyFormat::Ascii,
+            PlyFormat::BinaryLittleEndian,
+            PlyFormat::BinaryBigEndian,
+        ] {
+            assert_eq!(read_ply(&write_ply(&ply, format)).unwrap(), ply);
+            assert_eq!(
+                read_ply(&write_ply(&unweighted, format)).unwrap(),
+                unweighted
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_ply() {
+        // other elements and properties are skipped, a missing z is zero
+        let ply = b"ply\r
+format ascii 1.0\r
+comment from another tool\r
+element vertex 3\r
+property float x\r
+property float y\r
+property uchar red\r
+property list uchar int tags\r
+element face 1\r
+property list uchar uint vertex_index\r
+element edge 1\r
+property int vertex1\r
+property int vertex2\r
+end_header\r
+0 0 255 0\r
+1 0 0 2 7 8\r
+0 1.5 0 1 9\r
+3 0 1 2\r
+0 1\r
+";
+        let ply = read_ply(ply).unwrap();
+        assert_eq!(
+            ply.vertices,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.5, 0.0]]
+        );
+        assert_eq!(ply.vertices_2d(), vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.5]]);
+        assert_eq!(ply.weights, None);
+        assert_eq!(ply.faces, vec![[0, 1, 2]]);
+
+        let header =
+            "ply\nformat ascii 1.0\nelement vertex 3\nproperty double x\nproperty double y\n";
+        let quad = [
+            header,
+            "element face 1\nproperty list uchar int vertex_indices\nend_header\n",
+        ];
+        let vertices = "0 0\n1 0\n0 1\n";
+        assert!(
+            read_ply(alloc::format!("{}{}4 0 1 2 0\n", quad.concat(), vertices).as_bytes())
+                .is_err()
+        );
+        assert!(
+            read_ply(alloc::format!("{}{}3 0 1 3\n", quad.concat(), vertices).as_bytes()).is_err()
+        );
+        assert!(
+            read_ply(alloc::format!("{}{}3 0 1\n", quad.concat(), vertices).as_bytes()).is_err()
+        );
+        assert!(read_ply(header.as_bytes()).is_err());
+        assert!(read_ply(b"obj\nend_header\n").is_err());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_ply_triangulation() {
+        use rita_test_utils::{sample_vertices_2d, sample_weights};
+
+        let vertices = sample_vertices_2d(100, None);
+        let weights = sample_weights(100, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+
+        // the input is restored, and reinserting it gives the same triangulation
+        let ply = read_ply(&write_ply(
+            &triangulation.to_ply(),
+            PlyFormat::BinaryLittleEndian,
+        ))
+        .unwrap();
+        assert_eq!(ply.vertices_2d(), vertices);
+        assert_eq!(ply.weights.as_ref(), Some(&weights));
+        assert_eq!(ply.faces, triangulation.tri_indices());
+
+        let mut reinserted = Triangulation::new(None);
+        reinserted
+            .insert_vertices(ply.vertices_2d(), ply.weights, true)
+            .unwrap();
+        let mut expected = triangulation.tri_indices();
+        let mut tris = reinserted.tri_indices();
+        for tris in [&mut expected, &mut tris] {
+            for tri in tris.iter_mut() {
+                let min = (0..3).min_by_key(|&i| tri[i]).unwrap();
+                tri.rotate_left(min);
+            }
+            tris.sort_unstable();
+        }
+        assert_eq!(tris, expected);
+
+        // the hull of a tetrahedralization
+        let cube: Vec<Vertex3> = (0..8)
+            .map(|i| [i & 1, (i >> 1) & 1, i >> 2].map(f64::from))
+            .collect();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&cube, None, false)
+            .unwrap();
+        let ply = read_ply(&write_ply(&tetrahedralization.to_ply(), PlyFormat::Ascii)).unwrap();
+        assert_eq!(ply.vertices, cube);
+        assert_eq!(ply.faces.len(), tetrahedralization.num_hull_facets());
+    }
+
+    #[test]
+    fn test_node_ele_round_trip() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 0.5]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let mut data = triangulation.to_node_ele();
+        for weights in [None, Some(vec![0.0, -1.5, 2.0, 1e-10, 3.0])] {
+            data.weights = weights;
+            let (mut node, mut ele) = (String::new(), String::new());
+            write_node_ele(&data, &mut node, &mut ele).unwrap();
+            assert_eq!(read_node_ele(&node, Some(&ele)).unwrap(), data);
+        }
+
+        // numbered from 1, like Triangle
+        let (mut node, mut ele) = (String::new(), String::new());
+        write_node_ele(&data, &mut node, &mut ele).unwrap();
+        assert!(node.starts_with("5 2 1 0\n1 0 0 0\n"));
+        assert!(ele.starts_with("4 3 0\n1 "));
+
+        let cube: Vec<Vertex3> = (0..8)
+            .map(|i| [i & 1, (i >> 1) & 1, i >> 2].map(f64::from))
+            .collect();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&cube, None, false)
+            .unwrap();
+        let data = tetrahedralization.to_node_ele();
+        let (mut node, mut ele) = (String::new(), String::new());
+        write_node_ele(&data, &mut node, &mut ele).unwrap();
+        assert_eq!(read_node_ele(&node, Some(&ele)).unwrap(), data);
+        assert_eq!(data.elements.len(), tetrahedralization.num_casual_tets());
+    }
+
+    #[test]
+    fn test_read_node_ele() {
+        // numbered from 0, with comments, markers, two attributes and second order triangles
+        let node = "# vertices\n4 2 2 1\n0 0 0 0.5 7 1\n\n1 1 0 0.25 7 1\n2 1 1 0 7 1 # comment\n3 0 1 1 7 0\n";
+        let ele = "2 6 1\n0 0 1 2 4 5 6 1\n1 0 2 3 7 8 9 1\n";
+        let data = read_node_ele::<2, 3>(node, Some(ele)).unwrap();
+        assert_eq!(
+            data.vertices,
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+        );
+        assert_eq!(data.weights, Some(vec![0.5, 0.25, 0.0, 1.0]));
+        assert_eq!(data.elements, vec![[0, 1, 2], [0, 2, 3]]);
+        assert!(
+            read_node_ele::<2, 3>(node, None)
+                .unwrap()
+                .elements
+                .is_empty()
+        );
+
+        // a TetGen tetrahedron
+        let node = "4 3 0 0\n1 0 0 0\n2 1 0 0\n3 0 1 0\n4 0 0 1\n";
+        let data = read_node_ele::<3, 4>(node, Some("1 4 0\n1 1 2 3 4\n")).unwrap();
+        assert_eq!(data.weights, None);
+        assert_eq!(data.elements, vec![[0, 1, 2, 3]]);
+
+        assert!(read_node_ele::<2, 3>(node, None).is_err());
+        assert!(read_node_ele::<3, 4>(node, Some("1 4 0\n1 1 2 3 5\n")).is_err());
+        assert!(read_node_ele::<3, 4>(node, Some("1 4 0\n1 0 1 2 3\n")).is_err());
+        assert!(read_node_ele::<3, 4>(node, Some("1 3 0\n1 1 2 3\n")).is_err());
+        assert!(read_node_ele::<3, 4>(node, Some("2 4 0\n1 1 2 3 4\n")).is_err());
+        assert!(read_node_ele::<3, 4>("2 3 0 0\n2 0 0 0\n3 1 0 0\n", None).is_err());
+        assert!(read_node_ele::<3, 4>("1 3 0 0\n1 0 x 0\n", None).is_err());
+    }
+
+    #[test]
+    fn test_poly() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 0.5]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let mut poly = triangulation.to_poly();
+        assert_eq!(poly.segments.len(), 4);
+        poly.holes.push([1.0, 1.5]);
+        let mut text = String::new();
+        write_poly(&poly, &mut text).unwrap();
+        assert_eq!(read_poly(&text, None).unwrap(), poly);
+
+        // the vertices in a separate node file, with segment markers and regional attributes
+        let node = "3 2 0 1\n1 0 0 1\n2 1 0 1\n3 0 1 1\n";
+        let poly = "0 2 0 1\n3 1\n1 1 2 5\n2 2 3 5\n3 3 1 5\n0\n1\n1 0.1 0.1 3 0.5\n";
+        let data = read_poly(poly, Some(node)).unwrap();
+        assert_eq!(data.vertices.len(), 3);
+        assert_eq!(data.segments, vec![[0, 1], [1, 2], [2, 0]]);
+        assert!(data.holes.is_empty());
+
+        assert!(read_poly(poly, None).is_err());
+        assert!(read_poly("0 2 0 1\n1 0\n1 1 4\n0\n", Some(node)).is_err());
+        assert!(read_poly("0 2 0 1\n0 0\n", Some(node)).is_err());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_to_svg() {
+        // one of the two vertices at the bottom left corner is ignored
+        let vertices = [
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [0.0, 1.0],
+            [1.0, 0.5],
+            [5e-4, 2e-4],
+        ];
+        let mut triangulation = Triangulation::new(Some(1e-3));
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+        assert_eq!(triangulation.num_ignored_vertices(), 1);
+
+        let options = SvgOptions::default();
+        let svg = triangulation.to_svg(&options);
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800.00\" height=\"420.00\""
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle").count(), 6);
+        assert!(svg.contains("class=\"hull\""));
+        assert!(!svg.contains("class=\"voronoi\""));
+
+        // y points up, i.e. the vertex at the top right corner is at the top right of the figure
+        assert!(svg.contains("<circle cx=\"780.00\" cy=\"20.00\""));
+        let ignored = svg.split("class=\"ignored\"").nth(1).unwrap();
+        assert_eq!(ignored.matches("<circle").count(), 1);
+
+        let options = SvgOptions {
+            hull: false,
+            voronoi: true,
+            highlight_skipped: false,
+            vertex_radius: 0.0,
+            ..options
+        };
+        let svg = triangulation.to_svg(&options);
+        assert!(svg.contains("class=\"voronoi\""));
+        assert!(!svg.contains("class=\"hull\""));
+        assert!(!svg.contains("<circle"));
+
+        // without vertices, the figure is empty
+        let svg = Triangulation::new(None).to_svg(&options);
+        assert!(svg.contains("class=\"voronoi\" fill=\"none\" stroke=\"#d62728\" stroke-width=\"1\" stroke-linejoin=\"round\" d=\"\"/>"));
+    }
+}
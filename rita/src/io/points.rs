@@ -0,0 +1,216 @@
+//! Loading of points with optional weights from CSV or XYZ text, e.g. to triangulate measured data.
+//!
+//! Each line holds one point, its coordinates optionally followed by its weight, i.e. the columns are `x,y[,w]` in 2D
+//! and `x,y,z[,w]` in 3D, separated by commas, semicolons or whitespace. Empty lines and lines starting with `#` are skipped.
+//!
+//! A first line that is not numeric is a header, naming the columns `x`, `y`, `z` and `w` (or `weight`) in any order.
+//! Other columns are skipped, so with a header 3D points can also be loaded as 2D points.
+//!
+//! ```
+//! use rita::{Triangulation, io::points::parse_points};
+//!
+//! let points = parse_points::<2>("id,x,y\n1,0.0,0.0\n2,1.0,0.0\n3,0.0,1.0\n").unwrap();
+//! assert_eq!(points.vertices, vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+//! assert_eq!(points.weights, None);
+//!
+//! let mut triangulation = Triangulation::new(None);
+//! triangulation.insert_vertices(&points.vertices, points.weights, false).unwrap();
+//! assert_eq!(triangulation.num_casual_tris(), 1);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::RitaError;
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The points of a CSV or XYZ file, with `D` coordinates each.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Points<const D: usize> {
+    /// The positions of the points.
+    pub vertices: Vec<[f64; D]>,
+    /// The weights of the points, `Some` if the file has a weight column.
+    pub weights: Option<Vec<f64>>,
+}
+
+fn points_error(line_nr: usize, reason: &'static str) -> anyhow::Error {
+    anyhow::Error::new(RitaError::InvalidFile {
+        format: "points",
+        line: Some(line_nr),
+        reason,
+    })
+}
+
+/// Parses the lines of a points file one by one, so they can be streamed.
+#[derive(Debug, Default)]
+struct PointsParser<const D: usize> {
+    /// The column of each coordinate and of the weight, known after the first line.
+    columns: Option<([usize; D], Option<usize>)>,
+    points: Points<D>,
+    line_nr: usize,
+}
+
+impl<const D: usize> PointsParser<D> {
+    fn parse_line(&mut self, line: &str) -> HowResult<()> {
+        self.line_nr += 1;
+        if line.trim_start().starts_with('#') {
+            return HowOk(());
+        }
+        let tokens: Vec<&str> = line
+            .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .collect();
+        if tokens.is_empty() {
+            return HowOk(());
+        }
+
+        let (coord_columns, weight_column) = match self.columns {
+            Some(columns) => columns,
+            None => {
+                let is_header = tokens.iter().any(|token| token.parse::<f64>().is_err());
+                let columns = if is_header {
+                    self.header_columns(&tokens)?
+                } else if tokens.len() == D || tokens.len() == D + 1 {
+                    (
+                        core::array::from_fn(|d| d),
+                        (tokens.len() == D + 1).then_some(D),
+                    )
+                } else {
+                    return Err(points_error(
+                        self.line_nr,
+                        "wrong number of columns without a header",
+                    ));
+                };
+
+                self.columns = Some(columns);
+                self.points.weights = columns.1.map(|_| Vec::new());
+                if is_header {
+                    return HowOk(());
+                }
+                columns
+            }
+        };
+
+        let number = |column: usize| {
+            tokens
+                .get(column)
+                .ok_or_else(|| points_error(self.line_nr, "missing column"))?
+                .parse::<f64>()
+                .map_err(|_| points_error(self.line_nr, "invalid number"))
+        };
+        let mut vertex = [0.0; D];
+        for (coord, &column) in vertex.iter_mut().zip(&coord_columns) {
+            *coord = number(column)?;
+        }
+        let weight = weight_column.map(number).transpose()?;
+
+        self.points.vertices.push(vertex);
+        if let (Some(weights), Some(weight)) = (&mut self.points.weights, weight) {
+            weights.push(weight);
+        }
+
+        HowOk(())
+    }
+
+    /// The columns of the coordinates and of the weight, named by a header.
+    fn header_columns(&self, tokens: &[&str]) -> HowResult<([usize; D], Option<usize>)> {
+        let names: Vec<_> = tokens
+            .iter()
+            .map(|token| token.trim_matches('"').to_ascii_lowercase())
+            .collect();
+        let column = |name: &str| names.iter().position(|n| n == name);
+
+        let mut coord_columns = [0; D];
+        for (coord_column, name) in coord_columns.iter_mut().zip(["x", "y", "z"]) {
+            *coord_column = column(name)
+                .ok_or_else(|| points_error(self.line_nr, "header misses a coordinate"))?;
+        }
+
+        HowOk((coord_columns, column("w").or_else(|| column("weight"))))
+    }
+}
+
+/// Parse points with `D` coordinates and optional weights from CSV or XYZ text, see [`points`](self).
+///
+/// ## Errors
+/// Returns an error if a line has too few columns or an invalid number, or the header misses a coordinate.
+pub fn parse_points<const D: usize>(text: &str) -> HowResult<Points<D>> {
+    let mut parser = PointsParser::default();
+    for line in text.lines() {
+        parser.parse_line(line)?;
+    }
+
+    HowOk(parser.points)
+}
+
+/// Read points with `D` coordinates and optional weights from CSV or XYZ text line by line, see [`points`](self).
+///
+/// Reads from any [`BufRead`](std::io::BufRead), e.g. a buffered file, without loading the whole text at once.
+///
+/// ## Errors
+/// Returns an error if reading fails, a line has too few columns or an invalid number, or the header misses a coordinate.
+#[cfg(feature = "std")]
+pub fn read_points<const D: usize>(reader: impl std::io::BufRead) -> HowResult<Points<D>> {
+    let mut parser = PointsParser::default();
+    for line in reader.lines() {
+        parser.parse_line(&line?)?;
+    }
+
+    HowOk(parser.points)
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_parse_points() {
+        // positional columns, with and without weights
+        let points = parse_points::<2>("0 0\n1.5 -2\n\n# comment\n3e-1\t4\n").unwrap();
+        assert_eq!(points.vertices, vec![[0.0, 0.0], [1.5, -2.0], [0.3, 4.0]]);
+        assert_eq!(points.weights, None);
+
+        let points = parse_points::<2>("0;0;0.5\n1;0;-1\n").unwrap();
+        assert_eq!(points.vertices, vec![[0.0, 0.0], [1.0, 0.0]]);
+        assert_eq!(points.weights, Some(vec![0.5, -1.0]));
+
+        let points = parse_points::<3>("0,0,0\n1,2,3\n").unwrap();
+        assert_eq!(points.vertices, vec![[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]]);
+        assert_eq!(points.weights, None);
+
+        // named columns in any order, other columns are skipped
+        let text = "\"ID\",Weight,Z,Y,X\n7,0.25,3,2,1\n8,0.5,6,5,4\n";
+        let points = parse_points::<3>(text).unwrap();
+        assert_eq!(points.vertices, vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(points.weights, Some(vec![0.25, 0.5]));
+        let points = parse_points::<2>(text).unwrap();
+        assert_eq!(points.vertices, vec![[1.0, 2.0], [4.0, 5.0]]);
+
+        assert_eq!(parse_points::<2>("").unwrap(), Points::default());
+        assert_eq!(parse_points::<2>("x,y\n").unwrap(), Points::default());
+    }
+
+    #[test]
+    fn test_parse_points_errors() {
+        assert!(parse_points::<2>("0\n").is_err());
+        assert!(parse_points::<2>("0,0,0,0\n").is_err());
+        assert!(parse_points::<2>("0,0\n1\n").is_err());
+        assert!(parse_points::<2>("x,y\n0,zero\n").is_err());
+        assert!(parse_points::<3>("x,y\n0,0\n").is_err());
+
+        let error = parse_points::<2>("x,y\n0,0\n\n1,\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid points file in line 4: missing column!"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_points() {
+        let text = "x y w\n0 0 1\n1 0 2\n0 1 3\n";
+        let points = read_points::<2>(std::io::Cursor::new(text)).unwrap();
+        assert_eq!(points, parse_points::<2>(text).unwrap());
+        assert_eq!(points.weights, Some(vec![1.0, 2.0, 3.0]));
+    }
+}
@@ -3,12 +3,24 @@
 //! An implementation of 2D and 3D weighted delaunay triangulation via incremental algorithms.
 //!
 //! ## Features
-//! - `std` (default) - enables anyhow and nalgebra's std features
+//! - `std` (default) - enables anyhow's (and nalgebra's) std features
 //! - `geogram` (default) - uses [geogram_predicates] for robust predicates (FFI to C++); supports weighted Delaunay
+//! - `parallel` (default) - runs e.g. `Triangulation::par_is_regular()` and `Triangulation::par_insert_vertices()` on multiple threads via [rayon], bounded by `set_max_threads()`; they run on a single thread without it
 //! - `wasm` - uses pure-Rust [robust] predicates for wasm32 builds; **no weighted Delaunay** (use `weights: None`). Build with: `--no-default-features --features "std,wasm"`
-//! - `timing` - enables timing of function run time, this requires std
+//! - `timing` - enables timing of function run time, recorded in `stats()`, this requires std and implies `metrics`
 //! - `logging` - uses `log` to record errors and warnings, along with some extra information
 //! - `log_timing` - enables logging and timing, to record timing info
+//! - `nalgebra` - enables conversions into [nalgebra] types, e.g. `Plane::frame()`, and from its points, see [`IntoVertex2`]
+//! - `glam` - enables inserting [glam] points as vertices, see [`IntoVertex2`] and [`IntoVertex3`]
+//! - `geo-types` - enables inserting [geo_types] coordinates and points as vertices, see [`IntoVertex2`]
+//! - `petgraph` - enables exporting the adjacency graph as a [petgraph] graph, e.g. `Triangulation::to_graph()`
+//! - `geojson` - enables exporting triangles and power cells as [geojson] feature collections, e.g. `Triangulation::to_geojson()`; requires std
+//! - `u32-indices` - stores the indices of the data structures as `u32` instead of `usize`, roughly halving their memory; limits them to `u32::MAX - 1`, see [`StoredIdx`]
+//! - `serde` - derives `Serialize` and `Deserialize` for the triangulations, to persist and reload them
+//! - `snapshot` - enables fast binary snapshots via [bincode], e.g. `Triangulation::save_snapshot()`, to checkpoint and resume long builds
+//! - `trace` - enables recording the insertions and flips of a triangulation into a serializable trace and replaying them, see `trace`
+//! - `metrics` - records peak sizes of internal buffers, counts of flips and walk lengths during construction, see `stats()`
+//! - `exact` - enables exact predicates over integer coordinates without SOS via [num_bigint], to validate results in tests, e.g. `Triangulation::is_regular_exact()`
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(unused, clippy::incompatible_msrv)]
@@ -16,17 +28,58 @@
 
 extern crate alloc;
 
+pub use error::RitaError;
+pub use frozen::{CompactTetrahedralization, CompactTriangulation};
+pub use handle::{TetHandle, TriHandle};
 pub use node::VertexNode;
+pub use predicates::PredicateMode;
+pub use tetds::half_tri_iterator::HalfTriIterator;
 pub use tetrahedralization::Tetrahedralization;
-pub use triangulation::Triangulation;
+pub use triangulation::{DedupPolicy, InsertionStrategy, Triangulation, TriangulationConfig};
+pub use utils::{
+    compact::{IdxVec, NodeVec, StoredIdx},
+    insert_status::{DuplicatePolicy, InsertProgress, InsertStatus, SimplexChanges},
+    into_vertex::{IntoVertex2, IntoVertex3},
+    point_order::InsertOrder,
+};
+pub use walk::{WalkCap, WalkFallback};
 
+pub mod audit;
+pub mod balls;
+pub mod complex;
+pub mod decimation;
+pub mod degenerate;
+pub mod error;
+#[cfg(feature = "exact")]
+pub mod exact;
+pub mod frozen;
+pub mod geometry;
+pub mod graphs;
+pub mod handle;
+pub mod io;
+mod locate_grid;
+pub mod mesh;
+pub mod nav;
 pub mod node;
+pub mod observer;
 mod predicates;
+pub mod quality;
+pub mod redundancy;
+pub mod refinement;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+pub mod spatial_sort;
+#[cfg(feature = "metrics")]
+pub mod stats;
 mod tetds;
 pub mod tetrahedralization;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod triangulation;
 mod trids;
 mod utils;
+pub mod voronoi;
+pub mod walk;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
@@ -0,0 +1,159 @@
+//! A uniform grid over the vertices, remembering a simplex in each cell, to start visibility walks close to their target.
+//!
+//! The grid only provides hints, a remembered simplex may be deleted or reused since, so hints are checked before use.
+
+use alloc::{vec, vec::Vec};
+
+/// The mean number of vertices per cell the grid is built for.
+const VERTICES_PER_CELL: usize = 4;
+
+/// A uniform grid over the bounding box of the simplices, with the simplex last recorded in each cell.
+///
+/// Rebuilt finer whenever the number of vertices doubled, so its cost stays amortized constant per insertion.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LocateGrid<const D: usize> {
+    /// The lower corner of the grid.
+    min: [f64; D],
+    /// The inverse size of a cell along each axis.
+    inv_cell_size: [f64; D],
+    /// The number of cells along each axis.
+    resolution: usize,
+    /// The simplex last recorded in each cell, if any.
+    cells: Vec<Option<usize>>,
+    /// The number of vertices the grid was built for.
+    num_vertices: usize,
+}
+
+impl<const D: usize> LocateGrid<D> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            min: [0.0; D],
+            inv_cell_size: [0.0; D],
+            resolution: 0,
+            cells: Vec::new(),
+            num_vertices: 0,
+        }
+    }
+
+    /// Whether the grid is not built yet, or too coarse for `num_vertices`.
+    pub(crate) const fn needs_rebuild(&self, num_vertices: usize) -> bool {
+        self.resolution == 0 || num_vertices > 2 * self.num_vertices
+    }
+
+    /// Forget all recorded simplices, the grid is built again on the next insertion.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Build the grid for `num_vertices` over the bounding box of the `anchors`, recording each simplex at its anchor.
+    pub(crate) fn rebuild(&mut self, num_vertices: usize, anchors: &[([f64; D], usize)]) {
+        let mut resolution = 1;
+        while resolution_cells::<D>(resolution) * VERTICES_PER_CELL < num_vertices {
+            resolution += 1;
+        }
+
+        let mut min = [f64::INFINITY; D];
+        let mut max = [f64::NEG_INFINITY; D];
+        for (anchor, _) in anchors {
+            for i in 0..D {
+                min[i] = min[i].min(anchor[i]);
+                max[i] = max[i].max(anchor[i]);
+            }
+        }
+
+        *self = Self {
+            min,
+            inv_cell_size: core::array::from_fn(|i| {
+                let extent = max[i] - min[i];
+                if extent > 0.0 {
+                    resolution as f64 / extent
+                } else {
+                    0.0
+                }
+            }),
+            resolution,
+            cells: vec![None; resolution_cells::<D>(resolution)],
+            num_vertices,
+        };
+        for (anchor, simplex_idx) in anchors {
+            self.record(anchor, *simplex_idx);
+        }
+    }
+
+    /// Remember the simplex in the cell of the point.
+    pub(crate) fn record(&mut self, point: &[f64; D], simplex_idx: usize) {
+        if let Some(cell_idx) = self.cell(point) {
+            self.cells[cell_idx] = Some(simplex_idx);
+        }
+    }
+
+    /// The simplex last recorded in the cell of the point, points beyond the grid use the closest cell.
+    pub(crate) fn hint(&self, point: &[f64; D]) -> Option<usize> {
+        self.cell(point).and_then(|cell_idx| self.cells[cell_idx])
+    }
+
+    /// The cell of the point, `None` if the grid is not built.
+    fn cell(&self, point: &[f64; D]) -> Option<usize> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        // the float to int cast saturates, i.e. points below the grid and NaNs end up in the first cell
+        let cell_idx = (0..D).rev().fold(0, |cell_idx, i| {
+            let coord = ((point[i] - self.min[i]) * self.inv_cell_size[i]) as usize;
+            cell_idx * self.resolution + coord.min(self.resolution - 1)
+        });
+
+        Some(cell_idx)
+    }
+}
+
+impl<const D: usize> Default for LocateGrid<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The number of cells of a grid with `resolution` cells along each of the `D` axes.
+fn resolution_cells<const D: usize>(resolution: usize) -> usize {
+    (0..D).fold(1, |num_cells: usize, _| {
+        num_cells.saturating_mul(resolution)
+    })
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_grid() {
+        let mut grid = LocateGrid::<2>::new();
+        assert!(grid.needs_rebuild(0));
+        assert_eq!(grid.hint(&[0.5, 0.5]), None);
+
+        let anchors = [
+            ([0.0, 0.0], 0),
+            ([1.0, 0.0], 1),
+            ([0.0, 1.0], 2),
+            ([1.0, 1.0], 3),
+        ];
+        grid.rebuild(64, &anchors);
+        assert_eq!(grid.resolution, 4);
+        assert!(!grid.needs_rebuild(128));
+        assert!(grid.needs_rebuild(129));
+
+        assert_eq!(grid.hint(&[0.1, 0.1]), Some(0));
+        assert_eq!(grid.hint(&[0.9, 0.9]), Some(3));
+        assert_eq!(grid.hint(&[0.5, 0.5]), None);
+
+        // points beyond the grid use the closest cell
+        assert_eq!(grid.hint(&[-5.0, 7.0]), Some(2));
+        assert_eq!(grid.hint(&[f64::NAN, 0.0]), Some(0));
+
+        grid.record(&[0.5, 0.5], 4);
+        assert_eq!(grid.hint(&[0.55, 0.6]), Some(4));
+
+        grid.clear();
+        assert!(grid.needs_rebuild(0));
+    }
+}
@@ -0,0 +1,20 @@
+//! Streaming export of triangulations into external mesh types.
+
+use crate::utils::types::Vertex2;
+
+/// A consumer of triangle meshes, e.g. a render mesh of a game engine or a mesh processing crate.
+///
+/// Vertices are referenced by triangles via the order in which they were added,
+/// i.e. the first call to [`MeshSink::add_vertex`] defines vertex `0`.
+pub trait MeshSink {
+    /// Called once before any vertex or triangle is added, with upper bounds for both counts.
+    ///
+    /// Does nothing by default.
+    fn reserve(&mut self, _num_vertices: usize, _num_triangles: usize) {}
+
+    /// Add a vertex to the mesh.
+    fn add_vertex(&mut self, position: Vertex2);
+
+    /// Add a counter-clockwise triangle to the mesh, referencing previously added vertices.
+    fn add_triangle(&mut self, tri: [usize; 3]);
+}
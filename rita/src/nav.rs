@@ -0,0 +1,307 @@
+//! Path finding over the triangles of a 2D triangulation, e.g. for navigation meshes.
+//!
+//! The path is searched with A* over the adjacency of walkable triangles, and the resulting
+//! corridor is smoothed with the funnel algorithm ("simple stupid funnel algorithm").
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+    RitaError, Triangulation, predicates,
+    utils::types::{Triangle2, Vertex2},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// An entry in the open set of the A* search, ordered by lowest estimated total cost.
+struct OpenTri {
+    estimate: f64,
+    tri_idx: usize,
+}
+
+impl PartialEq for OpenTri {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenTri {}
+
+impl PartialOrd for OpenTri {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenTri {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, such that the max-heap pops the lowest estimate first
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn distance(a: Vertex2, b: Vertex2) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+fn centroid([a, b, c]: Triangle2) -> Vertex2 {
+    [(a[0] + b[0] + c[0]) / 3.0, (a[1] + b[1] + c[1]) / 3.0]
+}
+
+/// Find a path from `start` to `goal` that only crosses walkable triangles.
+///
+/// `walkable` is called with the index and coordinates of a casual triangle.
+///
+/// Returns `None` if `start` or `goal` is outside the convex hull or in a non-walkable triangle,
+/// or if there is no connection between them. Otherwise the returned path starts with `start`,
+/// ends with `goal` and only turns at vertices of the triangulation.
+///
+/// ## Errors
+/// Returns [`RitaError::EmptyTriangulation`] if the triangulation has no triangles.
+pub fn find_path(
+    triangulation: &Triangulation,
+    start: Vertex2,
+    goal: Vertex2,
+    walkable: impl Fn(usize, Triangle2) -> bool,
+) -> HowResult<Option<Vec<Vertex2>>> {
+    if triangulation.num_tris() == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    let tds = triangulation.tds();
+    let vertices = triangulation.vertices();
+
+    let coords = |tri_idx: usize| -> Option<Triangle2> {
+        let tri = tds.get_tri(tri_idx).ok()?;
+        if tri.is_conceptual() || tri.is_deleted() {
+            return None;
+        }
+        Some(tri.nodes().map(|node| vertices[node.idx().unwrap()]))
+    };
+    let is_walkable = |tri_idx: usize| coords(tri_idx).is_some_and(|tri| walkable(tri_idx, tri));
+
+    let start_tri = triangulation.locate_point_vis_walk(start, triangulation.walk_start(start))?;
+    let goal_tri = triangulation.locate_point_vis_walk(goal, start_tri)?;
+
+    if !is_walkable(start_tri) || !is_walkable(goal_tri) {
+        return HowOk(None);
+    }
+
+    // A* over the triangle adjacency, the cost is the distance between triangle centroids
+    let num_all_tris = triangulation.num_all_tris();
+    let mut cost = vec![f64::INFINITY; num_all_tris];
+    // the hedge through which a triangle was entered on the cheapest known path
+    let mut entered_via: Vec<Option<usize>> = vec![None; num_all_tris];
+    let mut open = BinaryHeap::new();
+
+    cost[start_tri] = 0.0;
+    open.push(OpenTri {
+        estimate: distance(start, goal),
+        tri_idx: start_tri,
+    });
+
+    let mut found = false;
+    while let Some(OpenTri { tri_idx, .. }) = open.pop() {
+        if tri_idx == goal_tri {
+            found = true;
+            break;
+        }
+
+        let center = if tri_idx == start_tri {
+            start
+        } else {
+            centroid(coords(tri_idx).unwrap())
+        };
+
+        for hedge in tds.get_tri(tri_idx)?.hedges() {
+            let twin = hedge.twin();
+            let next_idx = twin.tri().idx;
+
+            if !is_walkable(next_idx) {
+                continue;
+            }
+
+            let next_center = if next_idx == goal_tri {
+                goal
+            } else {
+                centroid(coords(next_idx).unwrap())
+            };
+            let next_cost = cost[tri_idx] + distance(center, next_center);
+
+            if next_cost < cost[next_idx] {
+                cost[next_idx] = next_cost;
+                entered_via[next_idx] = Some(twin.idx);
+                open.push(OpenTri {
+                    estimate: next_cost + distance(next_center, goal),
+                    tri_idx: next_idx,
+                });
+            }
+        }
+    }
+
+    if !found {
+        return HowOk(None);
+    }
+
+    // collect the portals, i.e. the shared edges along the corridor, from goal back to start
+    let mut portals = vec![(goal, goal)];
+    let mut tri_idx = goal_tri;
+    while let Some(hedge_idx) = entered_via[tri_idx] {
+        let hedge = tds.get_hedge(hedge_idx)?;
+        // the hedge belongs to the entered triangle, which lies to its left,
+        // so when walking into that triangle the starting node is on the left
+        let left = vertices[hedge.starting_node().idx().unwrap()];
+        let right = vertices[hedge.end_node().idx().unwrap()];
+        portals.push((left, right));
+        tri_idx = hedge.twin().tri().idx;
+    }
+    portals.push((start, start));
+    portals.reverse();
+
+    HowOk(Some(string_pull(&portals)))
+}
+
+/// Shortest path through a sequence of `(left, right)` portals, via the funnel algorithm.
+///
+/// The first and last portal are expected to be degenerate, i.e. the start and the goal.
+fn string_pull(portals: &[(Vertex2, Vertex2)]) -> Vec<Vertex2> {
+    let mut path = Vec::new();
+
+    let (mut apex, _) = portals[0];
+    let (mut funnel_left, mut funnel_right) = portals[0];
+    let (mut left_idx, mut right_idx) = (0, 0);
+
+    path.push(apex);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (left, right) = portals[i];
+
+        // try to narrow the funnel from the right
+        if predicates::orient_2d(&apex, &funnel_right, &right) >= 0.0 {
+            if apex == funnel_right || predicates::orient_2d(&apex, &funnel_left, &right) < 0.0 {
+                funnel_right = right;
+                right_idx = i;
+            } else {
+                // the right side crosses over the left one, the left point becomes a corner
+                path.push(funnel_left);
+                apex = funnel_left;
+                let apex_idx = left_idx;
+                (funnel_left, funnel_right) = (apex, apex);
+                (left_idx, right_idx) = (apex_idx, apex_idx);
+                i = apex_idx + 1;
+                continue;
+            }
+        }
+
+        // try to narrow the funnel from the left
+        if predicates::orient_2d(&apex, &funnel_left, &left) <= 0.0 {
+            if apex == funnel_left || predicates::orient_2d(&apex, &funnel_right, &left) > 0.0 {
+                funnel_left = left;
+                left_idx = i;
+            } else {
+                // the left side crosses over the right one, the right point becomes a corner
+                path.push(funnel_right);
+                apex = funnel_right;
+                let apex_idx = right_idx;
+                (funnel_left, funnel_right) = (apex, apex);
+                (left_idx, right_idx) = (apex_idx, apex_idx);
+                i = apex_idx + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    let (goal, _) = portals[portals.len() - 1];
+    if path.last() != Some(&goal) {
+        path.push(goal);
+    }
+
+    path
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    // A 4x3 grid of vertices, with spacing 1
+    fn grid() -> Triangulation {
+        let mut vertices = Vec::new();
+        for y in 0..3 {
+            for x in 0..4 {
+                vertices.push([x as f64, y as f64 + 0.001 * x as f64]);
+            }
+        }
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        triangulation
+    }
+
+    #[test]
+    fn test_straight_path() {
+        let triangulation = grid();
+
+        let path = find_path(&triangulation, [0.2, 1.0], [2.8, 1.0], |_, _| true)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path, vec![[0.2, 1.0], [2.8, 1.0]]);
+    }
+
+    #[test]
+    fn test_path_around_obstacle() {
+        let triangulation = grid();
+
+        // block every triangle touching the vertical line x = 1.5 below y = 1.5
+        let walkable = |_, tri: Triangle2| {
+            let [x_min, x_max, y_min] = [
+                tri.iter().map(|v| v[0]).fold(f64::INFINITY, f64::min),
+                tri.iter().map(|v| v[0]).fold(f64::NEG_INFINITY, f64::max),
+                tri.iter().map(|v| v[1]).fold(f64::INFINITY, f64::min),
+            ];
+            !(x_min < 1.5 && x_max > 1.5 && y_min < 1.0)
+        };
+
+        let path = find_path(&triangulation, [0.5, 0.2], [2.5, 0.2], walkable)
+            .unwrap()
+            .unwrap();
+
+        // the path has to go over the top of the obstacle, turning at grid vertices
+        assert!(path.len() > 2);
+        assert_eq!(path.first(), Some(&[0.5, 0.2]));
+        assert_eq!(path.last(), Some(&[2.5, 0.2]));
+        assert!(path[1..path.len() - 1].iter().all(|v| v[1] >= 1.0));
+    }
+
+    #[test]
+    fn test_no_path() {
+        let triangulation = grid();
+
+        // outside of the convex hull
+        assert!(
+            find_path(&triangulation, [0.5, 0.5], [5.0, 0.5], |_, _| true)
+                .unwrap()
+                .is_none()
+        );
+
+        // nothing is walkable
+        assert!(
+            find_path(&triangulation, [0.5, 0.5], [2.5, 0.5], |_, _| false)
+                .unwrap()
+                .is_none()
+        );
+
+        assert_eq!(
+            find_path(&Triangulation::new(None), [0.5, 0.5], [2.5, 0.5], |_, _| {
+                true
+            })
+            .unwrap_err()
+            .downcast_ref::<RitaError>(),
+            Some(&RitaError::EmptyTriangulation)
+        );
+    }
+}
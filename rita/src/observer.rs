@@ -0,0 +1,233 @@
+//! Hooks into the insertion of vertices, e.g. to visualize it step by step or to instrument it for research.
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::utils::types::VertexIdx;
+
+/// An observer of the insertions and flips of a [`Triangulation`](crate::Triangulation) or
+/// [`Tetrahedralization`](crate::Tetrahedralization), registered via their `set_observer`.
+///
+/// Simplices are referenced by their index in the data structure at the time of the call. Flips and cavities reuse
+/// the slots of the simplices they remove, see [`SimplexChanges`](crate::SimplexChanges).
+/// To read the events after the insertion, share the state of the observer, e.g. via an `Arc`.
+///
+/// All methods do nothing by default.
+pub trait InsertObserver: Send + Sync {
+    /// A vertex is inserted into the simplex containing it, before any flips.
+    ///
+    /// Only called for vertices that become part of the triangulation, not e.g. for duplicate or redundant ones.
+    fn on_insert(&mut self, _v_idx: VertexIdx, _containing_idx: usize) {}
+
+    /// The two triangles at an edge are replaced by the two at the other diagonal, in the same slots.
+    fn on_flip_2_2(&mut self, _tri_idxs: [usize; 2]) {}
+
+    /// The three triangles around the vertex `v_idx` of degree 3 are replaced by one in the first slot.
+    fn on_flip_3_1(&mut self, _tri_idxs: [usize; 3], _v_idx: VertexIdx) {}
+
+    /// The tets `removed` are replaced by the tets `added`, e.g. by a 1-4, 2-3, 3-2 or 4-1 flip.
+    fn on_flip_tets(&mut self, _removed: &[usize], _added: &[usize]) {}
+
+    /// The cavity of a Bowyer Watson insertion of the vertex `v_idx` is replaced by the star around it.
+    fn on_bw_cavity(&mut self, _v_idx: VertexIdx, _removed: &[usize], _added: &[usize]) {}
+}
+
+/// The observer registered at a triangulation, if any, forwarding the events to it.
+#[derive(Default)]
+pub(crate) struct Observer(Option<Box<dyn InsertObserver>>);
+
+impl Observer {
+    pub(crate) const NONE: Self = Self(None);
+
+    pub(crate) fn set(&mut self, observer: Box<dyn InsertObserver>) {
+        self.0 = Some(observer);
+    }
+
+    pub(crate) const fn is_registered(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub(crate) fn take(&mut self) -> Option<Box<dyn InsertObserver>> {
+        self.0.take()
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "Observer(Some(..))"
+        } else {
+            "Observer(None)"
+        })
+    }
+}
+
+impl InsertObserver for Observer {
+    fn on_insert(&mut self, v_idx: VertexIdx, containing_idx: usize) {
+        if let Some(observer) = &mut self.0 {
+            observer.on_insert(v_idx, containing_idx);
+        }
+    }
+
+    fn on_flip_2_2(&mut self, tri_idxs: [usize; 2]) {
+        if let Some(observer) = &mut self.0 {
+            observer.on_flip_2_2(tri_idxs);
+        }
+    }
+
+    fn on_flip_3_1(&mut self, tri_idxs: [usize; 3], v_idx: VertexIdx) {
+        if let Some(observer) = &mut self.0 {
+            observer.on_flip_3_1(tri_idxs, v_idx);
+        }
+    }
+
+    fn on_flip_tets(&mut self, removed: &[usize], added: &[usize]) {
+        if let Some(observer) = &mut self.0 {
+            observer.on_flip_tets(removed, added);
+        }
+    }
+
+    fn on_bw_cavity(&mut self, v_idx: VertexIdx, removed: &[usize], added: &[usize]) {
+        if let Some(observer) = &mut self.0 {
+            observer.on_bw_cavity(v_idx, removed, added);
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::{InsertionStrategy, Triangulation, TriangulationConfig, decimation::ErrorMetric};
+    use alloc::{sync::Arc, vec::Vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use rita_test_utils::sample_vertices_2d;
+
+    /// Counts the events, in the order of the methods of [`InsertObserver`].
+    #[derive(Default)]
+    struct Counter(Arc<[AtomicUsize; 5]>);
+
+    impl Counter {
+        fn counts(&self) -> Vec<usize> {
+            self.0
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .collect()
+        }
+
+        fn count(&self, event: usize) {
+            self.0[event].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    impl InsertObserver for Counter {
+        fn on_insert(&mut self, _v_idx: VertexIdx, _containing_idx: usize) {
+            self.count(0);
+        }
+
+        fn on_flip_2_2(&mut self, _tri_idxs: [usize; 2]) {
+            self.count(1);
+        }
+
+        fn on_flip_3_1(&mut self, _tri_idxs: [usize; 3], _v_idx: VertexIdx) {
+            self.count(2);
+        }
+
+        fn on_flip_tets(&mut self, removed: &[usize], added: &[usize]) {
+            assert!(!removed.is_empty() && !added.is_empty());
+            self.count(3);
+        }
+
+        fn on_bw_cavity(&mut self, _v_idx: VertexIdx, removed: &[usize], added: &[usize]) {
+            assert!(!removed.is_empty() && !added.is_empty());
+            self.count(4);
+        }
+    }
+
+    #[test]
+    fn test_observer_triangulation() {
+        let vertices = sample_vertices_2d(200, None);
+
+        let counter = Counter::default();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_observer(Counter(counter.0.clone()));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let counts = counter.counts();
+        // the initial triangle is not inserted vertex by vertex
+        assert_eq!(counts[0], 197);
+        assert!(counts[1] > 0);
+        assert_eq!(counts[3..], [0, 0]);
+
+        // an unregistered observer is not called anymore
+        assert!(triangulation.take_observer().is_some());
+        triangulation.insert_vertex([0.5, 0.5], None, None).unwrap();
+        assert_eq!(counter.counts(), counts);
+
+        let counter = Counter::default();
+        let config = TriangulationConfig::new().insertion_strategy(InsertionStrategy::BowyerWatson);
+        let mut triangulation = Triangulation::with_config(config);
+        triangulation.set_observer(Counter(counter.0.clone()));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert_eq!(counter.counts(), [197, 0, 0, 0, 197]);
+    }
+
+    #[test]
+    fn test_observer_survives_rebuild() {
+        let vertices = sample_vertices_2d(100, None);
+
+        let counter = Counter::default();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_observer(Counter(counter.0.clone()));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let counts = counter.counts();
+
+        // the insertions of the rebuild are not reported
+        triangulation.remove_vertices(&[0, 1]).unwrap();
+        let heights: Vec<f64> = vertices.iter().map(|[x, y]| x * y).collect();
+        triangulation
+            .decimate(50, ErrorMetric::Attribute(&heights))
+            .unwrap();
+        assert_eq!(counter.counts(), counts);
+
+        triangulation.insert_vertex([0.5, 0.5], None, None).unwrap();
+        assert_eq!(counter.counts()[0], counts[0] + 1);
+        assert!(triangulation.take_observer().is_some());
+    }
+
+    // the insertion of tets is not robust with the predicates of wasm
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_observer_tetrahedralization() {
+        use crate::Tetrahedralization;
+        use rita_test_utils::sample_vertices_3d;
+
+        let vertices = sample_vertices_3d(100, None);
+
+        for strategy in [InsertionStrategy::Flips, InsertionStrategy::BowyerWatson] {
+            let counter = Counter::default();
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization.set_insertion_strategy(strategy);
+            tetrahedralization.set_observer(Counter(counter.0.clone()));
+            tetrahedralization
+                .insert_vertices(&vertices, None, true)
+                .unwrap();
+
+            let counts = counter.counts();
+            assert_eq!(counts[0], 96);
+            assert_eq!(counts[1..3], [0, 0]);
+            // vertices beyond the hull are inserted via Bowyer Watson by both strategies
+            assert!(counts[4] > 0);
+            if strategy == InsertionStrategy::Flips {
+                assert!(counts[3] > 0);
+            } else {
+                assert_eq!(counts[3], 0);
+                assert_eq!(counts[4], 96);
+            }
+        }
+    }
+}
@@ -4,6 +4,8 @@
 //! weighted 2D/3D (power circle/sphere via `orient_*lifted_SOS`).
 //!
 //! With feature `wasm`: uses pure-Rust [robust] — unweighted only; weighted APIs are unavailable.
+//!
+//! With `geogram`, static floating point filters decide the well-separated cases, before falling back to geogram.
 
 #![allow(dead_code)]
 #![allow(non_snake_case)] // match geogram_predicates API (in_sphere_3d_SOS, orient_*lifted_SOS)
@@ -21,6 +23,17 @@ compile_error!(
     "Features 'geogram' and 'wasm' are mutually exclusive. For WASM use --no-default-features --features 'std,wasm'."
 );
 
+/// How a power circle or sphere test was decided, such that each structure can count the outcomes of its filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Decided {
+    /// By the floating point filter.
+    Filter,
+    /// By the exact predicate, as the filter failed.
+    Fallback,
+    /// Without a filter, i.e. by the robust predicates or by [`PredicateMode::FastInexact`].
+    Unfiltered,
+}
+
 /// Normalize predicate result to sign: -1.0, 0.0, or 1.0 so that `==` compares signs.
 #[inline]
 fn sign_f64(x: f64) -> f64 {
@@ -36,10 +49,50 @@ fn sign_f64(x: f64) -> f64 {
 #[cfg(all(feature = "geogram", not(feature = "wasm")))]
 mod imp {
     use super::*;
+    use core::sync::atomic::{AtomicU8, Ordering};
     use geogram_predicates as gp;
 
+    const UNINITIALIZED: u8 = 0;
+    const INITIALIZING: u8 = 1;
+    const INITIALIZED: u8 = 2;
+
+    static STATE: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+
+    /// Initialize geogram's arithmetic constants once, before the first predicate is evaluated.
+    ///
+    /// Without them the exact fallback of the filtered predicates is wrong, which gives inconsistent signs
+    /// for (nearly) degenerate configurations. Other threads wait, until the initialization is done.
+    #[inline]
+    fn ensure_initialized() {
+        if STATE.load(Ordering::Acquire) == INITIALIZED {
+            return;
+        }
+
+        if STATE
+            .compare_exchange(
+                UNINITIALIZED,
+                INITIALIZING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            )
+            .is_ok()
+        {
+            gp::initialize();
+            STATE.store(INITIALIZED, Ordering::Release);
+        } else {
+            while STATE.load(Ordering::Acquire) != INITIALIZED {
+                core::hint::spin_loop();
+            }
+        }
+    }
+
     #[inline]
     pub fn orient_2d(a: &Vertex2, b: &Vertex2, c: &Vertex2) -> f64 {
+        if let Some(sign) = filter::orient_2d(a, b, c) {
+            return sign;
+        }
+
+        ensure_initialized();
         let r = gp::orient_2d(a, b, c);
         if r > 0i16 {
             1.0
@@ -52,6 +105,11 @@ mod imp {
 
     #[inline]
     pub fn orient_3d(a: &Vertex3, b: &Vertex3, c: &Vertex3, d: &Vertex3) -> f64 {
+        if let Some(sign) = filter::orient_3d(a, b, c, d) {
+            return sign;
+        }
+
+        ensure_initialized();
         let r = gp::orient_3d(a, b, c, d);
         if r > 0i16 {
             1.0
@@ -70,6 +128,7 @@ mod imp {
         d: &Vertex3,
         p: &Vertex3,
     ) -> f64 {
+        ensure_initialized();
         let r = gp::in_sphere_3d_SOS(a, b, c, d, p);
         if r > 0i16 {
             1.0
@@ -92,14 +151,57 @@ mod imp {
         h_c: f64,
         h_p: f64,
     ) -> f64 {
+        orient_2dlifted_SOS_decided(a, b, c, p, h_a, h_b, h_c, h_p).0
+    }
+
+    /// [`orient_2dlifted_SOS`], along with whether the filter decided it.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_2dlifted_SOS_decided(
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        // a certain sign does not need the symbolic perturbation
+        if let Some(sign) = filter::orient_2dlifted(a, b, c, p, h_a, h_b, h_c, h_p) {
+            return (sign, Decided::Filter);
+        }
+
+        ensure_initialized();
         let r = gp::orient_2dlifted_SOS(a, b, c, p, h_a, h_b, h_c, h_p);
-        if r > 0i16 {
+        let sign = if r > 0i16 {
             1.0
         } else if r < 0i16 {
             -1.0
         } else {
             0.0
-        }
+        };
+        (sign, Decided::Fallback)
+    }
+
+    /// The power circle test without symbolic perturbation, i.e. `0.0` for a vertex on the power circle.
+    ///
+    /// Exact for the given heights, as it is the orientation of the lifted vertices. Has the signs of
+    /// [`orient_2dlifted_SOS`] for a positively oriented triangle, where that is not perturbed.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn orient_2dlifted(
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> f64 {
+        let [a, b, c, p] = [(a, h_a), (b, h_b), (c, h_c), (p, h_p)].map(|(v, h)| [v[0], v[1], h]);
+        super::inexact::LIFTED_SIGN * orient_3d(&a, &b, &c, &p)
     }
 
     #[inline]
@@ -116,14 +218,39 @@ mod imp {
         h_d: f64,
         h_p: f64,
     ) -> f64 {
+        orient_3dlifted_SOS_decided(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p).0
+    }
+
+    /// [`orient_3dlifted_SOS`], along with whether the filter decided it.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_3dlifted_SOS_decided(
+        a: &Vertex3,
+        b: &Vertex3,
+        c: &Vertex3,
+        d: &Vertex3,
+        p: &Vertex3,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_d: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        // a certain sign does not need the symbolic perturbation
+        if let Some(sign) = filter::orient_3dlifted(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p) {
+            return (sign, Decided::Filter);
+        }
+
+        ensure_initialized();
         let r = gp::orient_3dlifted_SOS(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p);
-        if r > 0i16 {
+        let sign = if r > 0i16 {
             1.0
         } else if r < 0i16 {
             -1.0
         } else {
             0.0
-        }
+        };
+        (sign, Decided::Fallback)
     }
 }
 
@@ -173,6 +300,30 @@ mod imp {
         sign_f64(incircle(coord2(a), coord2(b), coord2(c), coord2(p)))
     }
 
+    /// The power circle test without symbolic perturbation, i.e. `0.0` for a vertex on the power circle.
+    ///
+    /// Exact for the given heights, as it is the orientation of the lifted vertices. Has the signs of
+    /// [`orient_2dlifted_SOS`] for a positively oriented triangle.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn orient_2dlifted(
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> f64 {
+        let [a, b, c, p] = [(a, h_a), (b, h_b), (c, h_c), (p, h_p)].map(|(v, h)| Coord3D {
+            x: v[0],
+            y: v[1],
+            z: h,
+        });
+        sign_f64(orient3d(a, b, c, p))
+    }
+
     /// Unweighted insphere (same as in_sphere_3d_SOS). Used when `wasm` feature is on.
     #[inline]
     pub fn in_sphere_3d_SOS(
@@ -214,7 +365,496 @@ mod imp {
             coord3(p),
         ))
     }
+
+    /// [`orient_2dlifted_SOS`], the robust predicates have no filter to count.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_2dlifted_SOS_decided(
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        let sign = orient_2dlifted_SOS(a, b, c, p, h_a, h_b, h_c, h_p);
+        (sign, Decided::Unfiltered)
+    }
+
+    /// [`orient_3dlifted_SOS`], the robust predicates have no filter to count.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_3dlifted_SOS_decided(
+        a: &Vertex3,
+        b: &Vertex3,
+        c: &Vertex3,
+        d: &Vertex3,
+        p: &Vertex3,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_d: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        let sign = orient_3dlifted_SOS(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p);
+        (sign, Decided::Unfiltered)
+    }
 }
 
 // Re-export so call sites can use crate::predicates::orient_2d etc.
-pub use imp::{in_sphere_3d_SOS, orient_2d, orient_2dlifted_SOS, orient_3d, orient_3dlifted_SOS};
+pub use imp::{
+    in_sphere_3d_SOS, orient_2d, orient_2dlifted, orient_2dlifted_SOS, orient_3d,
+    orient_3dlifted_SOS,
+};
+use imp::{orient_2dlifted_SOS_decided, orient_3dlifted_SOS_decided};
+
+/// How the power circle and sphere tests of the insertion are decided.
+///
+/// The orientation tests, e.g. of the walks and of the hull, are always exact.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PredicateMode {
+    /// Exact predicates, such that the result is guaranteed to be regular.
+    #[default]
+    Exact,
+    /// Floating point power circle and sphere tests, only determinants within the relative `tolerance` of zero are
+    /// decided exactly.
+    ///
+    /// Faster, e.g. for visualizing particles, but rounding errors beyond the tolerance can leave locally non-regular
+    /// simplices, i.e. the regularity is **not** guaranteed. The structure itself stays valid.
+    FastInexact {
+        /// The tolerance relative to the magnitude of the coordinates, e.g. `1e-12`.
+        tolerance: f64,
+    },
+}
+
+impl PredicateMode {
+    /// Whether the regularity of the result is guaranteed.
+    #[must_use]
+    pub const fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact)
+    }
+
+    /// The power circle test, with the signs of [`orient_2dlifted_SOS`].
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_2dlifted(
+        self,
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> f64 {
+        self.orient_2dlifted_decided(a, b, c, p, h_a, h_b, h_c, h_p)
+            .0
+    }
+
+    /// [`Self::orient_2dlifted`], along with how it was decided.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_2dlifted_decided(
+        self,
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        if let Self::FastInexact { tolerance } = self {
+            let rows = [(b, h_b), (c, h_c), (p, h_p)]
+                .map(|(q, h_q)| [q[0] - a[0], q[1] - a[1], h_q - h_a]);
+            let base = [rows[0], rows[1]].map(|row| [row[0], row[1]]);
+            let lifted = inexact::filtered_sign(inexact::det_3(&rows), &rows, tolerance);
+            let orientation = inexact::filtered_sign(inexact::det_2(&base), &base, tolerance);
+            if let (Some(lifted), Some(orientation)) = (lifted, orientation) {
+                return (
+                    inexact::LIFTED_SIGN * lifted * orientation,
+                    Decided::Unfiltered,
+                );
+            }
+        }
+
+        orient_2dlifted_SOS_decided(a, b, c, p, h_a, h_b, h_c, h_p)
+    }
+
+    /// The power sphere test, with the signs of [`orient_3dlifted_SOS`].
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_3dlifted(
+        self,
+        a: &Vertex3,
+        b: &Vertex3,
+        c: &Vertex3,
+        d: &Vertex3,
+        p: &Vertex3,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_d: f64,
+        h_p: f64,
+    ) -> f64 {
+        self.orient_3dlifted_decided(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p)
+            .0
+    }
+
+    /// [`Self::orient_3dlifted`], along with how it was decided.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn orient_3dlifted_decided(
+        self,
+        a: &Vertex3,
+        b: &Vertex3,
+        c: &Vertex3,
+        d: &Vertex3,
+        p: &Vertex3,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_d: f64,
+        h_p: f64,
+    ) -> (f64, Decided) {
+        if let Self::FastInexact { tolerance } = self {
+            let rows = [(b, h_b), (c, h_c), (d, h_d), (p, h_p)]
+                .map(|(q, h_q)| [q[0] - a[0], q[1] - a[1], q[2] - a[2], h_q - h_a]);
+            let base = [rows[0], rows[1], rows[2]].map(|row| [row[0], row[1], row[2]]);
+            let lifted = inexact::filtered_sign(inexact::det_4(&rows), &rows, tolerance);
+            let orientation = inexact::filtered_sign(inexact::det_3(&base), &base, tolerance);
+            if let (Some(lifted), Some(orientation)) = (lifted, orientation) {
+                return (
+                    inexact::LIFTED_SIGN * lifted * orientation,
+                    Decided::Unfiltered,
+                );
+            }
+        }
+
+        orient_3dlifted_SOS_decided(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p)
+    }
+}
+
+/// Plain floating point determinants, for [`PredicateMode::FastInexact`].
+mod inexact {
+    use super::sign_f64;
+
+    /// The sign of the lifted determinants times the orientation, relative to the exact lifted predicates,
+    /// which do not depend on the orientation of the simplex.
+    pub(super) const LIFTED_SIGN: f64 = -1.0;
+
+    pub(super) fn det_2(rows: &[[f64; 2]; 2]) -> f64 {
+        rows[0][0] * rows[1][1] - rows[0][1] * rows[1][0]
+    }
+
+    pub(super) fn det_3(rows: &[[f64; 3]; 3]) -> f64 {
+        let [r0, r1, r2] = rows;
+        r0[0] * (r1[1] * r2[2] - r1[2] * r2[1]) - r0[1] * (r1[0] * r2[2] - r1[2] * r2[0])
+            + r0[2] * (r1[0] * r2[1] - r1[1] * r2[0])
+    }
+
+    pub(super) fn det_4(rows: &[[f64; 4]; 4]) -> f64 {
+        // Laplace expansion along the last column
+        (0..4)
+            .map(|i| {
+                let mut minor = [[0.0; 3]; 3];
+                let other_rows = rows.iter().enumerate().filter(|&(j, _)| j != i);
+                for ((_, row), minor_row) in other_rows.zip(&mut minor) {
+                    *minor_row = [row[0], row[1], row[2]];
+                }
+                let sign = if (i + 3) % 2 == 0 { 1.0 } else { -1.0 };
+                sign * rows[i][3] * det_3(&minor)
+            })
+            .sum()
+    }
+
+    /// The sign of the determinant, `None` if it is within the tolerance of zero.
+    ///
+    /// The tolerance is relative to the product of the largest entry of each column, i.e. the scale of the determinant.
+    pub(super) fn filtered_sign<const N: usize>(
+        det: f64,
+        rows: &[[f64; N]; N],
+        tolerance: f64,
+    ) -> Option<f64> {
+        let scale: f64 = (0..N)
+            .map(|col| rows.iter().map(|row| row[col].abs()).fold(0.0, f64::max))
+            .product();
+
+        (det.abs() > tolerance * scale).then(|| sign_f64(det))
+    }
+}
+
+/// Static floating point filters in front of the exact geogram predicates.
+///
+/// The determinants are evaluated in floating point, along with a bound of their rounding error as in Shewchuk's
+/// "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates". If a determinant exceeds its
+/// bound, its sign is certain and the exact evaluation is skipped, otherwise the filter fails with `None`.
+#[cfg(all(feature = "geogram", not(feature = "wasm")))]
+mod filter {
+    use super::{inexact, sign_f64};
+    use crate::utils::types::{Vertex2, Vertex3};
+
+    /// The unit roundoff of `f64`.
+    const EPS: f64 = f64::EPSILON / 2.0;
+    /// The relative error bound of a 2x2 determinant of differences, `ccwerrboundA` of Shewchuk.
+    const DET_2_BOUND: f64 = (3.0 + 16.0 * EPS) * EPS;
+    /// The relative error bound of a 3x3 determinant of differences, `o3derrboundA` of Shewchuk.
+    const DET_3_BOUND: f64 = (7.0 + 56.0 * EPS) * EPS;
+    /// The relative error bound of a 4x4 determinant of differences, expanded into 3x3 minors.
+    ///
+    /// Each minor is off by [`DET_3_BOUND`], the product with the entry and the sum of the four terms add `5 * EPS`,
+    /// rounded up to cover the higher order terms.
+    const DET_4_BOUND: f64 = 16.0 * EPS;
+
+    /// The sign of `det`, if it exceeds the relative error `bound` of the `permanent`, i.e. the determinant with the
+    /// absolute values of the products.
+    #[inline]
+    fn certain_sign(det: f64, permanent: f64, bound: f64) -> Option<f64> {
+        (det.abs() > bound * permanent).then(|| sign_f64(det))
+    }
+
+    fn det_2(rows: &[[f64; 2]; 2]) -> Option<f64> {
+        let permanent = (rows[0][0] * rows[1][1]).abs() + (rows[0][1] * rows[1][0]).abs();
+        certain_sign(inexact::det_2(rows), permanent, DET_2_BOUND)
+    }
+
+    fn permanent_3(rows: &[[f64; 3]; 3]) -> f64 {
+        let [r0, r1, r2] = rows;
+        r0[0].abs() * ((r1[1] * r2[2]).abs() + (r1[2] * r2[1]).abs())
+            + r0[1].abs() * ((r1[0] * r2[2]).abs() + (r1[2] * r2[0]).abs())
+            + r0[2].abs() * ((r1[0] * r2[1]).abs() + (r1[1] * r2[0]).abs())
+    }
+
+    fn det_3(rows: &[[f64; 3]; 3]) -> Option<f64> {
+        certain_sign(inexact::det_3(rows), permanent_3(rows), DET_3_BOUND)
+    }
+
+    fn det_4(rows: &[[f64; 4]; 4]) -> Option<f64> {
+        // the same Laplace expansion along the last column as `inexact::det_4`
+        let permanent: f64 = (0..4)
+            .map(|i| {
+                let mut minor = [[0.0; 3]; 3];
+                let other_rows = rows.iter().enumerate().filter(|&(j, _)| j != i);
+                for ((_, row), minor_row) in other_rows.zip(&mut minor) {
+                    *minor_row = [row[0], row[1], row[2]];
+                }
+                rows[i][3].abs() * permanent_3(&minor)
+            })
+            .sum();
+
+        certain_sign(inexact::det_4(rows), permanent, DET_4_BOUND)
+    }
+
+    #[inline]
+    pub(super) fn orient_2d(a: &Vertex2, b: &Vertex2, c: &Vertex2) -> Option<f64> {
+        det_2(&[b, c].map(|q| [q[0] - a[0], q[1] - a[1]]))
+    }
+
+    #[inline]
+    pub(super) fn orient_3d(a: &Vertex3, b: &Vertex3, c: &Vertex3, d: &Vertex3) -> Option<f64> {
+        det_3(&[b, c, d].map(|q| [q[0] - a[0], q[1] - a[1], q[2] - a[2]]))
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn orient_2dlifted(
+        a: &Vertex2,
+        b: &Vertex2,
+        c: &Vertex2,
+        p: &Vertex2,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_p: f64,
+    ) -> Option<f64> {
+        let rows =
+            [(b, h_b), (c, h_c), (p, h_p)].map(|(q, h_q)| [q[0] - a[0], q[1] - a[1], h_q - h_a]);
+        let base = [rows[0], rows[1]].map(|row| [row[0], row[1]]);
+
+        Some(inexact::LIFTED_SIGN * det_3(&rows)? * det_2(&base)?)
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn orient_3dlifted(
+        a: &Vertex3,
+        b: &Vertex3,
+        c: &Vertex3,
+        d: &Vertex3,
+        p: &Vertex3,
+        h_a: f64,
+        h_b: f64,
+        h_c: f64,
+        h_d: f64,
+        h_p: f64,
+    ) -> Option<f64> {
+        let rows = [(b, h_b), (c, h_c), (d, h_d), (p, h_p)]
+            .map(|(q, h_q)| [q[0] - a[0], q[1] - a[1], q[2] - a[2], h_q - h_a]);
+        let base = [rows[0], rows[1], rows[2]].map(|row| [row[0], row[1], row[2]]);
+
+        Some(inexact::LIFTED_SIGN * det_4(&rows)? * det_3(&base)?)
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use rita_test_utils::{sample_vertices_2d, sample_vertices_3d, sample_weights};
+
+    /// Heights of the vertices, unweighted with `wasm` where the weights are ignored.
+    fn heights<const D: usize>(vertices: &[[f64; D]]) -> Vec<f64> {
+        let weights = sample_weights(vertices.len(), None);
+        #[cfg(feature = "wasm")]
+        let weights = vec![0.0; weights.len()];
+
+        vertices
+            .iter()
+            .zip(weights)
+            .map(|(v, w)| v.iter().map(|x| x * x).sum::<f64>() - w)
+            .collect()
+    }
+
+    #[test]
+    fn test_nearly_degenerate_signs() {
+        // points close to a line or plane, that the floating point evaluation can not decide
+        let [a, b, c] = [
+            [0.10957860598549463, 0.26538529591773785],
+            [0.8856239926684798, 0.8357374096797802],
+            [0.36228308919305985, 0.4511096602756394],
+        ];
+        assert_eq!(orient_2d(&a, &b, &c), 1.0);
+        assert_eq!(orient_2d(&b, &a, &c), -1.0);
+
+        let [a, b, c, d] = [
+            [0.7843811699880133, 0.10583105896179168, 0.6803987606905156],
+            [0.5573254083570716, 0.8880257118879713, 0.9350945013825874],
+            [0.04909834714396033, 0.7168587627240527, 0.5570040411851611],
+            [0.07523209891061533, 0.8306000303454704, 0.6282166155060178],
+        ];
+        assert_ne!(orient_3d(&a, &b, &c, &d), 0.0);
+        assert_eq!(orient_3d(&a, &b, &c, &d), -orient_3d(&b, &a, &c, &d));
+    }
+
+    #[test]
+    fn test_orient_2dlifted() {
+        for _ in 0..1000 {
+            let mut v = sample_vertices_2d(4, None);
+            if orient_2d(&v[0], &v[1], &v[2]) < 0.0 {
+                v.swap(0, 1);
+            }
+            let h = heights(&v);
+            assert_eq!(
+                orient_2dlifted(&v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3]),
+                orient_2dlifted_SOS(&v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3])
+            );
+        }
+
+        // cocircular vertices are not perturbed
+        let [a, b, c, p] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        assert_eq!(orient_2dlifted(&a, &b, &c, &p, 0.0, 1.0, 2.0, 1.0), 0.0);
+        assert_eq!(orient_2dlifted(&a, &b, &c, &p, 0.0, 1.0, 2.0, 0.5), 1.0);
+    }
+
+    #[test]
+    fn test_fast_inexact_signs() {
+        let fast = PredicateMode::FastInexact { tolerance: 1e-12 };
+
+        for _ in 0..1000 {
+            // positively oriented, as the exact predicates of `wasm` depend on the orientation
+            let mut v = sample_vertices_2d(4, None);
+            if orient_2d(&v[0], &v[1], &v[2]) < 0.0 {
+                v.swap(0, 1);
+            }
+            let h = heights(&v);
+            assert_eq!(
+                fast.orient_2dlifted(&v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3]),
+                orient_2dlifted_SOS(&v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3])
+            );
+
+            let mut v = sample_vertices_3d(5, None);
+            if orient_3d(&v[0], &v[1], &v[2], &v[3]) < 0.0 {
+                v.swap(0, 1);
+            }
+            let h = heights(&v);
+            assert_eq!(
+                fast.orient_3dlifted(
+                    &v[0], &v[1], &v[2], &v[3], &v[4], h[0], h[1], h[2], h[3], h[4]
+                ),
+                orient_3dlifted_SOS(
+                    &v[0], &v[1], &v[2], &v[3], &v[4], h[0], h[1], h[2], h[3], h[4]
+                )
+            );
+        }
+    }
+
+    #[cfg(feature = "geogram")]
+    #[test]
+    fn test_filter_signs() {
+        use geogram_predicates as gp;
+        gp::initialize();
+        let sign = |r: i16| f64::from(r.signum());
+
+        for _ in 0..1000 {
+            let v = sample_vertices_2d(4, None);
+            let h = heights(&v);
+            if let Some(filtered) = filter::orient_2d(&v[0], &v[1], &v[2]) {
+                assert_eq!(filtered, sign(gp::orient_2d(&v[0], &v[1], &v[2])));
+            }
+            if let Some(filtered) =
+                filter::orient_2dlifted(&v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3])
+            {
+                assert_eq!(
+                    filtered,
+                    sign(gp::orient_2dlifted_SOS(
+                        &v[0], &v[1], &v[2], &v[3], h[0], h[1], h[2], h[3]
+                    ))
+                );
+            }
+
+            let v = sample_vertices_3d(5, None);
+            let h = heights(&v);
+            if let Some(filtered) = filter::orient_3d(&v[0], &v[1], &v[2], &v[3]) {
+                assert_eq!(filtered, sign(gp::orient_3d(&v[0], &v[1], &v[2], &v[3])));
+            }
+            if let Some(filtered) = filter::orient_3dlifted(
+                &v[0], &v[1], &v[2], &v[3], &v[4], h[0], h[1], h[2], h[3], h[4],
+            ) {
+                assert_eq!(
+                    filtered,
+                    sign(gp::orient_3dlifted_SOS(
+                        &v[0], &v[1], &v[2], &v[3], &v[4], h[0], h[1], h[2], h[3], h[4]
+                    ))
+                );
+            }
+        }
+
+        // degenerate and nearly degenerate configurations are left to geogram
+        let a = [0.1, 0.1];
+        let b = [0.3, 0.3];
+        assert_eq!(filter::orient_2d(&a, &b, &[0.7, 0.7]), None);
+        assert_eq!(filter::orient_2d(&a, &b, &[0.7, 0.7 + 1e-17]), None);
+        assert_eq!(filter::orient_2d(&a, &b, &[0.7, 0.8]), Some(1.0));
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        assert_eq!(
+            filter::orient_2dlifted(
+                &square[0], &square[1], &square[2], &square[3], 0.0, 1.0, 2.0, 1.0
+            ),
+            None
+        );
+        assert_eq!(
+            filter::orient_3d(
+                &[0.0; 3],
+                &[1.0, 0.0, 0.0],
+                &[0.0, 1.0, 0.0],
+                &[0.5, 0.5, 0.0]
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Quality metrics of triangles and tetrahedra, e.g. to evaluate the effect of the epsilon-approximation on a mesh.
+//!
+//! The ratios are normalized, such that they are `1` for the equilateral triangle and the regular tetrahedron,
+//! and grow for badly shaped simplices. Degenerate simplices have a minimal angle of zero and infinite ratios.
+
+use alloc::{vec, vec::Vec};
+
+use crate::utils::types::{Tetrahedron3, Triangle2, Vertex3};
+
+/// The quality metrics of a single triangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriQuality {
+    /// The smallest interior angle, in degrees.
+    pub min_angle: f64,
+    /// The circumradius divided by twice the inradius.
+    pub aspect_ratio: f64,
+    /// The circumradius divided by the shortest edge, normalized by that of the equilateral triangle.
+    pub radius_edge_ratio: f64,
+    /// The unsigned area.
+    pub area: f64,
+}
+
+/// The quality metrics of a single tetrahedron.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TetQuality {
+    /// The smallest dihedral angle, in degrees.
+    pub min_dihedral_angle: f64,
+    /// The circumradius divided by three times the inradius.
+    pub aspect_ratio: f64,
+    /// The circumradius divided by the shortest edge, normalized by that of the regular tetrahedron.
+    pub radius_edge_ratio: f64,
+    /// The unsigned volume.
+    pub volume: f64,
+}
+
+/// The distribution of a metric over all simplices, in bins of equal width between its minimum and maximum.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Histogram {
+    /// The smallest finite value.
+    pub min: f64,
+    /// The largest finite value.
+    pub max: f64,
+    /// The number of values per bin, the last bin includes the maximum.
+    pub counts: Vec<usize>,
+    /// The number of values, that are not finite, e.g. the ratios of degenerate simplices.
+    pub num_non_finite: usize,
+}
+
+impl Histogram {
+    /// Count the values in `num_bins` bins, spanning the range of the finite values.
+    pub fn new(values: impl IntoIterator<Item = f64>, num_bins: usize) -> Self {
+        let (finite, non_finite): (Vec<f64>, Vec<f64>) =
+            values.into_iter().partition(|value| value.is_finite());
+
+        let mut histogram = Self {
+            min: finite.iter().copied().fold(f64::INFINITY, f64::min),
+            max: finite.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            counts: vec![0; num_bins],
+            num_non_finite: non_finite.len(),
+        };
+        if finite.is_empty() || num_bins == 0 {
+            return histogram;
+        }
+
+        let width = (histogram.max - histogram.min) / num_bins as f64;
+        for value in finite {
+            let bin = if width > 0.0 {
+                ((value - histogram.min) / width) as usize
+            } else {
+                0
+            };
+            histogram.counts[bin.min(num_bins - 1)] += 1;
+        }
+
+        histogram
+    }
+
+    /// The number of values counted, including the non-finite ones.
+    pub fn num_values(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.num_non_finite
+    }
+}
+
+/// The histograms of the quality metrics of all casual triangles or tetrahedra.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QualityHistograms {
+    /// The smallest interior angles of triangles, or dihedral angles of tetrahedra.
+    pub min_angle: Histogram,
+    pub aspect_ratio: Histogram,
+    pub radius_edge_ratio: Histogram,
+    /// The areas of triangles, or volumes of tetrahedra.
+    pub size: Histogram,
+}
+
+impl QualityHistograms {
+    pub(crate) fn from_tris(qualities: &[TriQuality], num_bins: usize) -> Self {
+        Self {
+            min_angle: Histogram::new(qualities.iter().map(|q| q.min_angle), num_bins),
+            aspect_ratio: Histogram::new(qualities.iter().map(|q| q.aspect_ratio), num_bins),
+            radius_edge_ratio: Histogram::new(
+                qualities.iter().map(|q| q.radius_edge_ratio),
+                num_bins,
+            ),
+            size: Histogram::new(qualities.iter().map(|q| q.area), num_bins),
+        }
+    }
+
+    pub(crate) fn from_tets(qualities: &[TetQuality], num_bins: usize) -> Self {
+        Self {
+            min_angle: Histogram::new(qualities.iter().map(|q| q.min_dihedral_angle), num_bins),
+            aspect_ratio: Histogram::new(qualities.iter().map(|q| q.aspect_ratio), num_bins),
+            radius_edge_ratio: Histogram::new(
+                qualities.iter().map(|q| q.radius_edge_ratio),
+                num_bins,
+            ),
+            size: Histogram::new(qualities.iter().map(|q| q.volume), num_bins),
+        }
+    }
+}
+
+fn sub(a: Vertex3, b: Vertex3) -> Vertex3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vertex3, b: Vertex3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vertex3, b: Vertex3) -> Vertex3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: Vertex3) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// The angle between two vectors, in degrees.
+fn angle(a: Vertex3, b: Vertex3) -> f64 {
+    norm(cross(a, b)).atan2(dot(a, b)).to_degrees()
+}
+
+/// Compute the quality metrics of a triangle.
+pub fn tri_quality([a, b, c]: Triangle2) -> TriQuality {
+    let [a, b, c] = [a, b, c].map(|[x, y]| [x, y, 0.0]);
+    let [ab, bc, ca] = [sub(b, a), sub(c, b), sub(a, c)];
+    let lengths = [norm(ab), norm(bc), norm(ca)];
+
+    let area = norm(cross(ab, ca)) / 2.0;
+    let circumradius = lengths[0] * lengths[1] * lengths[2] / (4.0 * area);
+    let inradius = 2.0 * area / (lengths[0] + lengths[1] + lengths[2]);
+    let min_length = lengths.into_iter().fold(f64::INFINITY, f64::min);
+
+    let angles = [
+        angle(ab, sub(c, a)),
+        angle(bc, sub(a, b)),
+        angle(ca, sub(b, c)),
+    ];
+
+    TriQuality {
+        min_angle: angles.into_iter().fold(f64::INFINITY, f64::min),
+        aspect_ratio: circumradius / (2.0 * inradius),
+        radius_edge_ratio: circumradius / min_length * 3f64.sqrt(),
+        area,
+    }
+}
+
+/// Compute the quality metrics of a tetrahedron.
+pub fn tet_quality([a, b, c, d]: Tetrahedron3) -> TetQuality {
+    let [ab, ac, ad] = [sub(b, a), sub(c, a), sub(d, a)];
+    let six_volume = dot(ab, cross(ac, ad));
+    let volume = six_volume.abs() / 6.0;
+
+    // the circumcenter relative to `a`, solving `2 * [ab, ac, ad]^T * x = [|ab|^2, |ac|^2, |ad|^2]`
+    let circumcenter = {
+        let [l_ab, l_ac, l_ad] = [dot(ab, ab), dot(ac, ac), dot(ad, ad)];
+        let [x_bc, x_db, x_cd] = [cross(ac, ad), cross(ad, ab), cross(ab, ac)];
+        core::array::from_fn(|i| {
+            (l_ab * x_bc[i] + l_ac * x_db[i] + l_ad * x_cd[i]) / (2.0 * six_volume)
+        })
+    };
+    let circumradius = norm(circumcenter);
+
+    let faces = [[b, c, d], [a, d, c], [a, b, d], [a, c, b]];
+    let normals = faces.map(|[p, q, r]| cross(sub(q, p), sub(r, p)));
+    let surface = normals
+        .iter()
+        .map(|&normal| norm(normal) / 2.0)
+        .sum::<f64>();
+    let inradius = 3.0 * volume / surface;
+
+    let edges = [ab, ac, ad, sub(c, b), sub(d, b), sub(d, c)];
+    let min_length = edges.into_iter().map(norm).fold(f64::INFINITY, f64::min);
+
+    // the dihedral angle at an edge is the supplement of the angle between the outward normals of its faces
+    let mut min_dihedral_angle = f64::INFINITY;
+    for i in 0..4 {
+        for j in i + 1..4 {
+            min_dihedral_angle = min_dihedral_angle.min(180.0 - angle(normals[i], normals[j]));
+        }
+    }
+
+    TetQuality {
+        min_dihedral_angle,
+        aspect_ratio: circumradius / (3.0 * inradius),
+        radius_edge_ratio: circumradius / min_length * 8f64.sqrt() / 3f64.sqrt(),
+        volume,
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_tri_quality() {
+        let equilateral = tri_quality([[0.0, 0.0], [1.0, 0.0], [0.5, 3f64.sqrt() / 2.0]]);
+        assert_close(equilateral.min_angle, 60.0);
+        assert_close(equilateral.aspect_ratio, 1.0);
+        assert_close(equilateral.radius_edge_ratio, 1.0);
+        assert_close(equilateral.area, 3f64.sqrt() / 4.0);
+
+        let right = tri_quality([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+        assert_close(right.min_angle, 45.0);
+        assert!(right.aspect_ratio > 1.0);
+
+        let flat = tri_quality([[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]);
+        assert_eq!(flat.min_angle, 0.0);
+        assert!(!flat.aspect_ratio.is_finite());
+    }
+
+    #[test]
+    fn test_tet_quality() {
+        let regular = tet_quality([
+            [1.0, 1.0, 1.0],
+            [1.0, -1.0, -1.0],
+            [-1.0, 1.0, -1.0],
+            [-1.0, -1.0, 1.0],
+        ]);
+        assert_close(
+            regular.min_dihedral_angle,
+            (1.0f64 / 3.0).acos().to_degrees(),
+        );
+        assert_close(regular.aspect_ratio, 1.0);
+        assert_close(regular.radius_edge_ratio, 1.0);
+        assert_close(regular.volume, 8.0 / 3.0);
+
+        let corner = tet_quality([[0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_close(
+            corner.min_dihedral_angle,
+            (1.0f64 / 3.0).sqrt().acos().to_degrees(),
+        );
+        assert_close(corner.volume, 1.0 / 6.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Histogram::new([0.0, 0.5, 1.0, 2.0, f64::INFINITY], 2);
+        assert_eq!(histogram.min, 0.0);
+        assert_eq!(histogram.max, 2.0);
+        assert_eq!(histogram.counts, vec![2, 2]);
+        assert_eq!(histogram.num_non_finite, 1);
+        assert_eq!(histogram.num_values(), 5);
+
+        assert_eq!(Histogram::new([3.0; 3], 4).counts, vec![3, 0, 0, 0]);
+        assert_eq!(Histogram::new([], 4).num_values(), 0);
+    }
+}
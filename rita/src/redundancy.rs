@@ -0,0 +1,432 @@
+//! Pre-filtering of weighted point sets, discarding points that are provably redundant.
+//!
+//! A weighted point is redundant, i.e. not part of the regular triangulation, if its lifted point
+//! lies above the lower convex hull of all lifted points. This is certified locally: if a point is contained in a
+//! simplex of nearby points and its lifted point lies above the lifted simplex, it is redundant.
+//! Checking this for a few nearby points is much cheaper than inserting and later flipping out the point.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+    RitaError, predicates,
+    utils::{
+        translation::{local_origin, to_local},
+        types::{Vertex2, Vertex3},
+    },
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The number of nearby points, that are combined into simplices to certify redundancy.
+const NUM_NEIGHBORS: usize = 8;
+
+/// A uniform grid over a point set, to gather nearby points.
+struct Grid<const D: usize> {
+    min: [f64; D],
+    cell_size: f64,
+    /// The largest cell index per dimension, i.e. the grid spans `0..=max_cell[i]`.
+    max_cell: [i64; D],
+    cells: BTreeMap<[i64; D], Vec<usize>>,
+}
+
+impl<const D: usize> Grid<D> {
+    /// Build a grid with about two points per cell.
+    fn new(points: &[[f64; D]]) -> Self {
+        let mut min = [f64::INFINITY; D];
+        let mut max = [f64::NEG_INFINITY; D];
+        for p in points {
+            for i in 0..D {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+
+        // size the cells by the dimensions the points actually span, flat ones hold a single layer of cells
+        let extents: Vec<f64> = (0..D)
+            .map(|i| max[i] - min[i])
+            .filter(|&extent| extent > 0.0)
+            .collect();
+        let max_extent = extents.iter().copied().fold(0.0, f64::max);
+        let cell_size = if extents.is_empty() {
+            1.0
+        } else {
+            let volume: f64 = extents.iter().product();
+            let cell_size = (2.0 * volume / points.len() as f64).powf(1.0 / extents.len() as f64);
+            // at most one cell per point along each dimension, e.g. when one extent is tiny
+            cell_size.max(max_extent / points.len() as f64)
+        };
+
+        let mut grid = Self {
+            min,
+            cell_size,
+            max_cell: [0; D],
+            cells: BTreeMap::new(),
+        };
+        grid.max_cell = grid.cell(&max);
+        for (idx, p) in points.iter().enumerate() {
+            grid.cells.entry(grid.cell(p)).or_default().push(idx);
+        }
+        grid
+    }
+
+    fn cell(&self, p: &[f64; D]) -> [i64; D] {
+        core::array::from_fn(|i| ((p[i] - self.min[i]) / self.cell_size) as i64)
+    }
+
+    /// The (approximately) nearest `k` points to the point at `idx`, excluding itself.
+    fn nearest(&self, points: &[[f64; D]], idx: usize, k: usize) -> Vec<usize> {
+        let center = self.cell(&points[idx]);
+        let dist_sq = |other: usize| -> f64 {
+            (0..D)
+                .map(|i| (points[other][i] - points[idx][i]).powi(2))
+                .sum()
+        };
+        // beyond this radius the searched block covers the whole grid
+        let max_radius = self.max_cell.iter().copied().max().unwrap_or(0);
+
+        let mut found = Vec::new();
+        // grow the searched block of cells, until it holds enough points, then search one more ring
+        let mut extra_rings = 1;
+        for radius in 0..=max_radius {
+            found.clear();
+            // the block, clipped to the cells of the grid
+            let lo: [i64; D] = core::array::from_fn(|i| center[i].saturating_sub(radius).max(0));
+            let hi: [i64; D] =
+                core::array::from_fn(|i| center[i].saturating_add(radius).min(self.max_cell[i]));
+            let mut cell = lo;
+            'cells: loop {
+                if let Some(idxs) = self.cells.get(&cell) {
+                    found.extend(idxs.iter().filter(|&&other| other != idx));
+                }
+
+                // next cell in the block, like an odometer
+                for i in 0..D {
+                    if cell[i] < hi[i] {
+                        cell[i] += 1;
+                        continue 'cells;
+                    }
+                    cell[i] = lo[i];
+                }
+                break;
+            }
+
+            if found.len() >= k || found.len() + 1 == points.len() {
+                if extra_rings == 0 {
+                    break;
+                }
+                extra_rings -= 1;
+            }
+        }
+
+        found.sort_by(|&a, &b| dist_sq(a).total_cmp(&dist_sq(b)));
+        found.truncate(k);
+        found
+    }
+}
+
+/// Calls `f` with all `N`-subsets of `0..n`, until it returns `true`.
+fn any_subset<const N: usize>(n: usize, mut f: impl FnMut([usize; N]) -> bool) -> bool {
+    if n < N {
+        return false;
+    }
+
+    let mut subset: [usize; N] = core::array::from_fn(|i| i);
+    loop {
+        if f(subset) {
+            return true;
+        }
+
+        // advance to the next subset in lexicographic order
+        let Some(i) = (0..N).rev().find(|&i| subset[i] < n - N + i) else {
+            return false;
+        };
+        subset[i] += 1;
+        for j in i + 1..N {
+            subset[j] = subset[j - 1] + 1;
+        }
+    }
+}
+
+/// Check the arguments and compute the heights of the lifted points.
+fn lifted_heights<const D: usize>(points: &[[f64; D]], weights: &[f64]) -> HowResult<Vec<f64>> {
+    if cfg!(feature = "wasm") {
+        return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+    }
+
+    if weights.len() != points.len() {
+        return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+            num_weights: weights.len(),
+            num_vertices: points.len(),
+        }));
+    }
+    if !points
+        .iter()
+        .flatten()
+        .chain(weights)
+        .all(|x| x.is_finite())
+    {
+        return Err(anyhow::Error::new(RitaError::NonFinite));
+    }
+
+    // relative to a local origin, which does not change which lifted points are above each other
+    let origin = local_origin(points);
+    HowOk(
+        points
+            .iter()
+            .zip(weights)
+            .map(|(p, w)| to_local(p, &origin).iter().map(|x| x * x).sum::<f64>() - w)
+            .collect(),
+    )
+}
+
+/// Discard 2D weighted points, that are provably redundant, before building a triangulation.
+///
+/// Returns the indices of the remaining points, ascending. A point is discarded, if it lies strictly inside
+/// a triangle of nearby points and its lifted point strictly above the lifted triangle. Triangulating the
+/// remaining points yields the same regular triangulation, but not every redundant point is found.
+///
+/// ## Errors
+/// Returns an error if the number of weights does not match, a coordinate or weight is not finite,
+/// or with the `wasm` feature, which does not support weights.
+pub fn filter_redundant_candidates_2d(
+    points: &[Vertex2],
+    weights: &[f64],
+) -> HowResult<Vec<usize>> {
+    let heights = lifted_heights(points, weights)?;
+    if points.len() < 4 {
+        return HowOk((0..points.len()).collect());
+    }
+
+    let grid = Grid::new(points);
+    let is_redundant = |p_idx: usize| {
+        let p = &points[p_idx];
+        let neighbors = grid.nearest(points, p_idx, NUM_NEIGHBORS);
+
+        any_subset(neighbors.len(), |[i, j, k]| {
+            let [a, b, c] = [neighbors[i], neighbors[j], neighbors[k]];
+            let (pa, pb, pc) = (&points[a], &points[b], &points[c]);
+
+            let orientation = predicates::orient_2d(pa, pb, pc);
+            let inside = orientation != 0.0
+                && predicates::orient_2d(pa, pb, p) == orientation
+                && predicates::orient_2d(pb, pc, p) == orientation
+                && predicates::orient_2d(pc, pa, p) == orientation;
+
+            // the lifted predicate does not depend on the orientation of the triangle
+            inside
+                && predicates::orient_2dlifted_SOS(
+                    pa,
+                    pb,
+                    pc,
+                    p,
+                    heights[a],
+                    heights[b],
+                    heights[c],
+                    heights[p_idx],
+                ) < 0.0
+        })
+    };
+
+    HowOk(
+        (0..points.len())
+            .filter(|&idx| !is_redundant(idx))
+            .collect(),
+    )
+}
+
+/// Discard 3D weighted points, that are provably redundant, before building a tetrahedralization.
+///
+/// Returns the indices of the remaining points, ascending. A point is discarded, if it lies strictly inside
+/// a tetrahedron of nearby points and its lifted point strictly above the lifted tetrahedron. Tetrahedralizing the
+/// remaining points yields the same regular tetrahedralization, but not every redundant point is found.
+///
+/// ## Errors
+/// Returns an error if the number of weights does not match, a coordinate or weight is not finite,
+/// or with the `wasm` feature, which does not support weights.
+pub fn filter_redundant_candidates_3d(
+    points: &[Vertex3],
+    weights: &[f64],
+) -> HowResult<Vec<usize>> {
+    let heights = lifted_heights(points, weights)?;
+    if points.len() < 5 {
+        return HowOk((0..points.len()).collect());
+    }
+
+    let grid = Grid::new(points);
+    let is_redundant = |p_idx: usize| {
+        let p = &points[p_idx];
+        let neighbors = grid.nearest(points, p_idx, NUM_NEIGHBORS);
+
+        any_subset(neighbors.len(), |[i, j, k, l]| {
+            let [a, b, c, d] = [neighbors[i], neighbors[j], neighbors[k], neighbors[l]];
+            let (pa, pb, pc, pd) = (&points[a], &points[b], &points[c], &points[d]);
+
+            let orientation = predicates::orient_3d(pa, pb, pc, pd);
+            let inside = orientation != 0.0
+                && predicates::orient_3d(p, pb, pc, pd) == orientation
+                && predicates::orient_3d(pa, p, pc, pd) == orientation
+                && predicates::orient_3d(pa, pb, p, pd) == orientation
+                && predicates::orient_3d(pa, pb, pc, p) == orientation;
+
+            inside
+                && predicates::orient_3dlifted_SOS(
+                    pa,
+                    pb,
+                    pc,
+                    pd,
+                    p,
+                    heights[a],
+                    heights[b],
+                    heights[c],
+                    heights[d],
+                    heights[p_idx],
+                ) < 0.0
+        })
+    };
+
+    HowOk(
+        (0..points.len())
+            .filter(|&idx| !is_redundant(idx))
+            .collect(),
+    )
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod tests {
+    use super::*;
+    use crate::{Tetrahedralization, Triangulation};
+    use rita_test_utils::{sample_vertices_2d, sample_vertices_3d, sample_weights};
+
+    /// Sort the vertices of each simplex and the simplices, to compare them regardless of order.
+    fn normalize<const N: usize>(mut simplices: Vec<[usize; N]>) -> Vec<[usize; N]> {
+        for simplex in &mut simplices {
+            simplex.sort_unstable();
+        }
+        simplices.sort_unstable();
+        simplices
+    }
+
+    #[test]
+    fn test_filter_redundant_candidates_2d() {
+        // a light point in the middle of a square of heavy points is redundant, a heavy one is not
+        let points = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.4, 0.3]];
+        let mut weights = vec![1.0, 1.0, 1.0, 1.0, 0.0];
+        assert_eq!(
+            filter_redundant_candidates_2d(&points, &weights).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+
+        weights[4] = 2.0;
+        assert_eq!(
+            filter_redundant_candidates_2d(&points, &weights).unwrap(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        assert!(filter_redundant_candidates_2d(&points, &weights[..4]).is_err());
+    }
+
+    #[test]
+    fn test_filter_redundant_candidates_3d() {
+        let points = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.2, 0.2, 0.2],
+        ];
+        let mut weights = vec![0.5, 0.5, 0.5, 0.5, 0.0];
+        assert_eq!(
+            filter_redundant_candidates_3d(&points, &weights).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+
+        weights[4] = 1.0;
+        assert_eq!(
+            filter_redundant_candidates_3d(&points, &weights).unwrap(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_filter_flat_input() {
+        // no point lies strictly inside a simplex, so all are kept
+        let points: Vec<Vertex2> = (0..20).map(|i| [f64::from(i), 0.0]).collect();
+        let weights = vec![0.1; points.len()];
+        assert_eq!(
+            filter_redundant_candidates_2d(&points, &weights).unwrap(),
+            (0..20).collect::<Vec<_>>()
+        );
+
+        let points: Vec<Vertex3> = (0..100)
+            .map(|i| [f64::from(i % 10), f64::from(i / 10), 0.5])
+            .collect();
+        let weights = vec![0.1; points.len()];
+        assert_eq!(
+            filter_redundant_candidates_3d(&points, &weights).unwrap(),
+            (0..100).collect::<Vec<_>>()
+        );
+
+        let points = vec![[1.0, 2.0, 3.0]; 6];
+        let weights = vec![0.1; points.len()];
+        assert_eq!(
+            filter_redundant_candidates_3d(&points, &weights).unwrap(),
+            (0..6).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_filter_keeps_triangulation() {
+        let n = 2000;
+        let points = sample_vertices_2d(n, None);
+        let weights = sample_weights(n, Some((0.0, 0.01)));
+
+        let kept = filter_redundant_candidates_2d(&points, &weights).unwrap();
+        assert!(kept.len() < n);
+
+        let mut full = Triangulation::new(None);
+        full.insert_vertices(&points, Some(weights.clone()), true)
+            .unwrap();
+
+        let kept_points: Vec<Vertex2> = kept.iter().map(|&idx| points[idx]).collect();
+        let kept_weights = kept.iter().map(|&idx| weights[idx]).collect();
+        let mut filtered = Triangulation::new(None);
+        filtered
+            .insert_vertices(&kept_points, Some(kept_weights), true)
+            .unwrap();
+
+        // the same triangles, in terms of the original indices
+        let filtered_tris = filtered
+            .tri_indices()
+            .into_iter()
+            .map(|tri| tri.map(|idx| kept[idx]))
+            .collect();
+        assert_eq!(normalize(full.tri_indices()), normalize(filtered_tris));
+    }
+
+    #[test]
+    fn test_filter_keeps_tetrahedralization() {
+        let n = 1000;
+        let points = sample_vertices_3d(n, None);
+        let weights = sample_weights(n, Some((0.0, 0.005)));
+
+        let kept = filter_redundant_candidates_3d(&points, &weights).unwrap();
+        assert!(kept.len() < n);
+
+        let kept_points: Vec<Vertex3> = kept.iter().map(|&idx| points[idx]).collect();
+        let kept_weights = kept.iter().map(|&idx| weights[idx]).collect();
+        let mut filtered = Tetrahedralization::new(None);
+        filtered
+            .insert_vertices(&kept_points, Some(kept_weights), true)
+            .unwrap();
+
+        let mut full = Tetrahedralization::new(None);
+        full.insert_vertices(&points, Some(weights.clone()), true)
+            .unwrap();
+
+        let filtered_tets = filtered
+            .tet_indices()
+            .into_iter()
+            .map(|tet| tet.map(|idx| kept[idx]))
+            .collect();
+        assert_eq!(normalize(full.tet_indices()), normalize(filtered_tets));
+    }
+}
@@ -0,0 +1,339 @@
+//! Delaunay refinement of 2D triangulations (Ruppert's algorithm), to build quality meshes.
+//!
+//! Steiner points are inserted at the circumcenters of bad triangles, i.e. those with a too small angle or a too large area.
+//! The edges of the convex hull act as segments: a hull edge, whose diametral circle contains a vertex, is encroached
+//! and split instead, such that no vertex is inserted outside of the hull. Hull edges at the corners of the initial hull
+//! are split at a power of two distance from the corner (concentric shells), which keeps small corner angles from causing
+//! endless splits.
+//!
+//! Without constrained edges, a split point on a hull edge that is not axis-aligned is collinear only up to rounding,
+//! so a flat sliver triangle along the hull can remain. Such slivers, like the angles of the hull corners, are left as they are.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    RitaError, Triangulation,
+    quality::tri_quality,
+    triangulation::Location,
+    utils::types::{Vertex2, VertexIdx},
+    voronoi::orthocenter,
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The largest minimal angle, in degrees, that refinement is guaranteed to terminate for.
+pub const MAX_MIN_ANGLE: f64 = 20.7;
+
+/// Segments and triangle edges shorter than this fraction of the bounding box diagonal are not refined any further.
+const MIN_RELATIVE_LENGTH: f64 = 1e-9;
+
+fn sub(a: Vertex2, b: Vertex2) -> Vertex2 {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn dot(a: Vertex2, b: Vertex2) -> f64 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn distance(a: Vertex2, b: Vertex2) -> f64 {
+    dot(sub(a, b), sub(a, b)).sqrt()
+}
+
+/// Check if `p` lies strictly inside the diametral circle of the segment `ab`.
+fn encroaches(p: Vertex2, [a, b]: [Vertex2; 2]) -> bool {
+    dot(sub(a, p), sub(b, p)) < 0.0
+}
+
+/// The point to split the segment `ab` at.
+///
+/// Segments with exactly one corner are split at the power of two distance from the corner, that is closest to the midpoint.
+fn split_point([a, b]: [Vertex2; 2], [a_is_corner, b_is_corner]: [bool; 2]) -> Vertex2 {
+    let len = distance(a, b);
+    let shell = |len: f64| 2f64.powi((len / 2.0).log2().round() as i32) / len;
+
+    let t = match (a_is_corner, b_is_corner) {
+        (true, false) => shell(len),
+        (false, true) => 1.0 - shell(len),
+        _ => 0.5,
+    };
+
+    [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+}
+
+/// The apexes of the casual triangles on each edge, by the sorted vertices of the edge.
+fn edge_apexes(triangulation: &Triangulation) -> BTreeMap<[VertexIdx; 2], Vec<VertexIdx>> {
+    let mut apexes: BTreeMap<[VertexIdx; 2], Vec<VertexIdx>> = BTreeMap::new();
+    for [a, b, c] in triangulation.tri_indices() {
+        for (from, to, apex) in [(a, b, c), (b, c, a), (c, a, b)] {
+            apexes
+                .entry([from.min(to), from.max(to)])
+                .or_default()
+                .push(apex);
+        }
+    }
+
+    apexes
+}
+
+/// Check if the triangle with the sorted vertices `tri` is still part of the triangulation.
+fn contains_tri(triangulation: &Triangulation, tri: [VertexIdx; 3]) -> bool {
+    let [a, b, c] = tri.map(|v_idx| triangulation.vertices()[v_idx]);
+    let centroid = [(a[0] + b[0] + c[0]) / 3.0, (a[1] + b[1] + c[1]) / 3.0];
+
+    let Ok(Location::InsideTriangle(handle)) = triangulation.locate(centroid) else {
+        return false;
+    };
+    triangulation
+        .tds()
+        .get_tri(handle.idx())
+        .is_ok_and(|found| {
+            let mut nodes = found.nodes().map(|node| node.idx().unwrap_or(usize::MAX));
+            nodes.sort_unstable();
+            nodes == tri
+        })
+}
+
+/// Check if the triangle has an angle smaller than `min_angle` at a hull corner, i.e. between two of its edges on segments.
+///
+/// Such an angle is part of the domain and can not be improved.
+fn has_small_corner_angle(
+    triangulation: &Triangulation,
+    tri: [VertexIdx; 3],
+    segments: &BTreeSet<[VertexIdx; 2]>,
+    min_angle: f64,
+) -> bool {
+    let on_hull = |a: VertexIdx, b: VertexIdx| segments.contains(&[a.min(b), a.max(b)]);
+
+    (0..3).any(|i| {
+        let [corner, a, b] = [tri[i], tri[(i + 1) % 3], tri[(i + 2) % 3]];
+        if !on_hull(corner, a) || !on_hull(corner, b) {
+            return false;
+        }
+
+        let [v, v_a, v_b] = [corner, a, b].map(|v_idx| triangulation.vertices()[v_idx]);
+        let [u, w] = [sub(v_a, v), sub(v_b, v)];
+        let angle = (dot(u, w) / (dot(u, u) * dot(w, w)).sqrt())
+            .clamp(-1.0, 1.0)
+            .acos()
+            .to_degrees();
+
+        angle < min_angle
+    })
+}
+
+/// Insert a Steiner point, returns `false` if it did not become part of the triangulation, e.g. due to epsilon.
+fn insert_steiner_point(triangulation: &mut Triangulation, v: Vertex2) -> HowResult<bool> {
+    let v_idx = triangulation.vertices().len();
+    triangulation.insert_vertex(v, None, None)?;
+
+    HowOk(triangulation.used_vertices().last() == Some(&v_idx))
+}
+
+/// Refine a triangulation, see [`Triangulation::refine`].
+pub(crate) fn refine(
+    triangulation: &mut Triangulation,
+    max_area: Option<f64>,
+    min_angle: f64,
+) -> HowResult<usize> {
+    if triangulation.weights().is_some() {
+        return Err(anyhow::Error::new(RitaError::InvalidState(
+            "refinement needs an unweighted triangulation",
+        )));
+    }
+    if triangulation.num_casual_tris() == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    let min_angle = min_angle.min(MAX_MIN_ANGLE);
+    let is_bad = |min: f64, area: f64| min < min_angle || max_area.is_some_and(|max| area > max);
+
+    let mut is_corner = vec![false; triangulation.vertices().len()];
+    for v_idx in triangulation.hull_edges() {
+        is_corner[v_idx] = true;
+    }
+
+    let [v_min, v_max] = triangulation.used_vertices().iter().fold(
+        [[f64::INFINITY; 2], [f64::NEG_INFINITY; 2]],
+        |[v_min, v_max], &v_idx| {
+            let v = triangulation.vertices()[v_idx];
+            [
+                [v_min[0].min(v[0]), v_min[1].min(v[1])],
+                [v_max[0].max(v[0]), v_max[1].max(v[1])],
+            ]
+        },
+    );
+    let min_length = MIN_RELATIVE_LENGTH * distance(v_min, v_max);
+
+    // the hull edges, that are split into subsegments along the way
+    let mut segments: BTreeSet<[VertexIdx; 2]> = BTreeSet::new();
+    let hull = triangulation.hull_edges();
+    for (&a, &b) in hull.iter().zip(hull.iter().cycle().skip(1)) {
+        segments.insert([a.min(b), a.max(b)]);
+    }
+
+    // triangles and segments, that can not be improved
+    let mut skipped_tris = BTreeSet::new();
+    let mut skipped_segments = BTreeSet::new();
+    let mut num_inserted = 0;
+
+    'refine: loop {
+        // split the encroached segments first, the circumcenters of bad triangles next to them might lie outside the hull
+        // a segment that is not an edge, e.g. due to a vertex on it, is split as well
+        let apexes = edge_apexes(triangulation);
+        let encroached: Vec<[VertexIdx; 2]> = segments
+            .iter()
+            .filter(|&segment| {
+                !skipped_segments.contains(segment)
+                    && apexes.get(segment).is_none_or(|apexes| {
+                        apexes.iter().any(|&apex| {
+                            encroaches(
+                                triangulation.vertices()[apex],
+                                segment.map(|v_idx| triangulation.vertices()[v_idx]),
+                            )
+                        })
+                    })
+            })
+            .copied()
+            .collect();
+
+        if !encroached.is_empty() {
+            for segment in encroached {
+                num_inserted += split_segment(
+                    triangulation,
+                    segment,
+                    &is_corner,
+                    min_length,
+                    &mut segments,
+                    &mut skipped_segments,
+                )?;
+            }
+            continue;
+        }
+
+        let mut bad_tris: Vec<(f64, [VertexIdx; 3])> = triangulation
+            .tri_indices()
+            .into_iter()
+            .filter_map(|mut tri| {
+                tri.sort_unstable();
+                let quality = tri_quality(tri.map(|v_idx| triangulation.vertices()[v_idx]));
+                (is_bad(quality.min_angle, quality.area) && !skipped_tris.contains(&tri))
+                    .then_some((quality.min_angle, tri))
+            })
+            .collect();
+        if bad_tris.is_empty() {
+            break;
+        }
+        bad_tris.sort_by(|(angle_a, _), (angle_b, _)| angle_a.total_cmp(angle_b));
+
+        // the triangles of the scan might be gone after the first insertion of this round
+        let num_inserted_before = num_inserted;
+        for (_, tri) in bad_tris {
+            if num_inserted > num_inserted_before && !contains_tri(triangulation, tri) {
+                continue;
+            }
+
+            let vertices = tri.map(|v_idx| triangulation.vertices()[v_idx]);
+            let quality = tri_quality(vertices);
+            let shortest = (0..3)
+                .map(|i| distance(vertices[i], vertices[(i + 1) % 3]))
+                .fold(f64::INFINITY, f64::min);
+
+            // triangles that are only bad by an angle of the domain are kept
+            let is_too_large = max_area.is_some_and(|max| quality.area > max);
+            if shortest < min_length
+                || (!is_too_large
+                    && has_small_corner_angle(triangulation, tri, &segments, min_angle))
+            {
+                skipped_tris.insert(tri);
+                continue;
+            }
+
+            // slivers along the hull, that are flat up to rounding, have no circumcenter
+            let circumcenter = orthocenter(vertices, [0.0; 3]);
+            if !circumcenter.iter().all(|x| x.is_finite()) {
+                skipped_tris.insert(tri);
+                continue;
+            }
+
+            // instead of inserting a circumcenter that encroaches segments, split those
+            let encroached: Vec<[VertexIdx; 2]> = segments
+                .iter()
+                .filter(|&segment| {
+                    !skipped_segments.contains(segment)
+                        && encroaches(
+                            circumcenter,
+                            segment.map(|v_idx| triangulation.vertices()[v_idx]),
+                        )
+                })
+                .copied()
+                .collect();
+
+            if !encroached.is_empty() {
+                for segment in encroached {
+                    num_inserted += split_segment(
+                        triangulation,
+                        segment,
+                        &is_corner,
+                        min_length,
+                        &mut segments,
+                        &mut skipped_segments,
+                    )?;
+                }
+                continue 'refine;
+            }
+
+            // a circumcenter outside the hull means, that vertices inserted in this round encroach segments
+            if !matches!(
+                triangulation.locate(circumcenter)?,
+                Location::InsideTriangle(_) | Location::OnEdge(_)
+            ) {
+                if num_inserted > num_inserted_before {
+                    continue 'refine;
+                }
+                skipped_tris.insert(tri);
+                continue;
+            }
+
+            if insert_steiner_point(triangulation, circumcenter)? {
+                num_inserted += 1;
+            } else {
+                skipped_tris.insert(tri);
+            }
+        }
+    }
+
+    HowOk(num_inserted)
+}
+
+/// Split a segment into two subsegments, returns the number of inserted vertices.
+fn split_segment(
+    triangulation: &mut Triangulation,
+    segment: [VertexIdx; 2],
+    is_corner: &[bool],
+    min_length: f64,
+    segments: &mut BTreeSet<[VertexIdx; 2]>,
+    skipped_segments: &mut BTreeSet<[VertexIdx; 2]>,
+) -> HowResult<usize> {
+    let vertices = segment.map(|v_idx| triangulation.vertices()[v_idx]);
+    if distance(vertices[0], vertices[1]) < min_length {
+        skipped_segments.insert(segment);
+        return HowOk(0);
+    }
+
+    let corners = segment.map(|v_idx| is_corner.get(v_idx).copied().unwrap_or(false));
+    let v_idx = triangulation.vertices().len();
+    if !insert_steiner_point(triangulation, split_point(vertices, corners))? {
+        skipped_segments.insert(segment);
+        return HowOk(0);
+    }
+
+    let [a, b] = segment;
+    segments.remove(&segment);
+    segments.insert([a.min(v_idx), a.max(v_idx)]);
+    segments.insert([b.min(v_idx), b.max(v_idx)]);
+
+    HowOk(1)
+}
@@ -0,0 +1,143 @@
+//! Fast binary snapshots of the triangulations and their data structures, e.g. to checkpoint long builds and resume them.
+//!
+//! A snapshot starts with a short header, naming the kind of structure it holds, followed by the structure encoded by
+//! [bincode]. The scratch buffers of the insertion are not part of a snapshot, they are allocated again when needed.
+//!
+//! Snapshots are only compatible between builds of rita with the same version and features,
+//! and they are not validated beyond decoding, see e.g. [`Triangulation::is_sound`](crate::Triangulation::is_sound).
+
+use alloc::vec::Vec;
+
+use crate::RitaError;
+use anyhow::{Ok as HowOk, Result as HowResult};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// The magic bytes at the start of each snapshot.
+const MAGIC: &[u8; 4] = b"rita";
+
+/// The version of the snapshot format, incremented on incompatible changes of the header.
+const VERSION: u8 = 1;
+
+/// The structure held by a snapshot, stored in its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum SnapshotKind {
+    TriDataStructure = 1,
+    TetDataStructure = 2,
+    Triangulation = 3,
+    Tetrahedralization = 4,
+}
+
+fn snapshot_error(reason: &'static str) -> anyhow::Error {
+    anyhow::Error::new(RitaError::InvalidSnapshot(reason))
+}
+
+/// Encode a structure as a snapshot of the given kind.
+pub(crate) fn save<T: Serialize>(kind: SnapshotKind, value: &T) -> HowResult<Vec<u8>> {
+    let mut bytes = Vec::from(*MAGIC);
+    bytes.extend([VERSION, kind as u8]);
+
+    let body = bincode::serde::encode_to_vec(value, bincode::config::standard())
+        .map_err(|_| snapshot_error("could not encode"))?;
+    bytes.extend(body);
+
+    HowOk(bytes)
+}
+
+/// Decode a structure from a snapshot, which has to be of the given kind.
+pub(crate) fn load<T: DeserializeOwned>(kind: SnapshotKind, bytes: &[u8]) -> HowResult<T> {
+    let Some((header, body)) = bytes.split_at_checked(MAGIC.len() + 2) else {
+        return Err(snapshot_error("missing header"));
+    };
+    if &header[..MAGIC.len()] != MAGIC {
+        return Err(snapshot_error("missing magic bytes"));
+    }
+    if header[MAGIC.len()] != VERSION {
+        return Err(snapshot_error("unsupported version"));
+    }
+    if header[MAGIC.len() + 1] != kind as u8 {
+        return Err(snapshot_error("holds another kind of structure"));
+    }
+
+    let (value, len) = bincode::serde::decode_from_slice(body, bincode::config::standard())
+        .map_err(|_| snapshot_error("could not decode"))?;
+    if len != body.len() {
+        return Err(snapshot_error("trailing bytes"));
+    }
+
+    HowOk(value)
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use crate::{
+        Tetrahedralization, Triangulation, tetds::tet_data_structure::TetDataStructure,
+        trids::tri_data_structure::TriDataStructure,
+    };
+    use rita_test_utils::{sample_vertices_2d, sample_vertices_3d};
+
+    #[test]
+    fn test_snapshot_triangulation() {
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices[..100], None, true)
+            .unwrap();
+
+        let bytes = triangulation.save_snapshot().unwrap();
+        let mut resumed = Triangulation::load_snapshot(&bytes).unwrap();
+        assert_eq!(resumed.tri_indices(), triangulation.tri_indices());
+        assert_eq!(resumed.vertices(), triangulation.vertices());
+
+        // resuming the build gives the same triangulation as building it at once
+        let remaining = vertices[100..].iter().copied();
+        triangulation
+            .insert_vertices(remaining.clone(), None, true)
+            .unwrap();
+        resumed.insert_vertices(remaining, None, true).unwrap();
+        assert_eq!(resumed.tri_indices(), triangulation.tri_indices());
+        assert!(resumed.is_sound().unwrap());
+
+        let tds = TriDataStructure::load_snapshot(&resumed.tds().save_snapshot().unwrap()).unwrap();
+        assert_eq!(tds.num_tris(), resumed.tds().num_tris());
+        assert!(tds.is_sound());
+    }
+
+    #[test]
+    fn test_snapshot_tetrahedralization() {
+        let vertices = sample_vertices_3d(100, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let bytes = tetrahedralization.save_snapshot().unwrap();
+        let resumed = Tetrahedralization::load_snapshot(&bytes).unwrap();
+        assert_eq!(resumed.tet_indices(), tetrahedralization.tet_indices());
+
+        let tds = TetDataStructure::load_snapshot(&resumed.tds().save_snapshot().unwrap()).unwrap();
+        assert_eq!(tds.num_tets(), resumed.tds().num_tets());
+    }
+
+    #[test]
+    fn test_snapshot_errors() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], None, false)
+            .unwrap();
+        let bytes = triangulation.save_snapshot().unwrap();
+
+        // another kind, a truncated body, trailing bytes and a missing header
+        assert!(Tetrahedralization::load_snapshot(&bytes).is_err());
+        assert!(Triangulation::load_snapshot(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(Triangulation::load_snapshot(&trailing).is_err());
+        assert!(Triangulation::load_snapshot(&bytes[..4]).is_err());
+
+        let mut other_version = bytes;
+        other_version[4] += 1;
+        let error = Triangulation::load_snapshot(&other_version).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid snapshot: unsupported version!");
+    }
+}
@@ -0,0 +1,25 @@
+//! Spatial orders of vertex batches, e.g. to pre-order batches that are inserted one by one in a custom loop.
+//!
+//! The functions return the given indices of `vertices` in their new order. Nearby vertices are close in the order,
+//! so consecutive insertions locate each vertex with a short walk from the last one.
+//! The batch insertions, e.g. [`Triangulation::insert_vertices`](crate::Triangulation::insert_vertices), apply them
+//! by an [`InsertOrder`] already.
+//!
+//! ```
+//! use rita::{Triangulation, spatial_sort::brio_order_2d};
+//!
+//! let mut triangulation = Triangulation::new(None);
+//! triangulation.insert_vertices(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], None, false).unwrap();
+//!
+//! let batch = [[0.5, 0.1], [0.1, 0.5], [0.4, 0.4]];
+//! let idxs: Vec<usize> = (0..batch.len()).collect();
+//! for idx in brio_order_2d(&batch, &idxs, 7) {
+//!     triangulation.insert_vertex(batch[idx], None, None).unwrap();
+//! }
+//! assert_eq!(triangulation.num_used_vertices(), 6);
+//! ```
+
+pub use crate::utils::point_order::{
+    InsertOrder, brio_order_2d, brio_order_3d, sort_along_hilbert_curve_2d,
+    sort_along_hilbert_curve_3d, sort_along_morton_curve_2d, sort_along_morton_curve_3d,
+};
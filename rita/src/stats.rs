@@ -0,0 +1,190 @@
+//! Statistics recorded while building a triangulation or tetrahedralization.
+
+#[cfg(feature = "metrics")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "metrics")]
+use crate::predicates::Decided;
+
+/// Peak sizes of internal buffers and counts of the insertion steps, recorded with the `metrics` feature since construction.
+///
+/// Guides choosing capacities up front, and reveals pathological cavity sizes or walks on real datasets.
+/// The times are recorded with the `timing` feature only, which implies `metrics`.
+/// Fields that do not apply to a dimension stay zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildStats {
+    /// 2D: the most hedges on the stack of hedges to check for flips.
+    pub peak_hedge_stack: usize,
+    /// 3D: the most tetrahedra in the Bowyer-Watson queue of tetrahedra to check.
+    pub peak_bw_tets_to_check: usize,
+    /// 3D: the most tetrahedra marked for deletion, i.e. cavities not yet cleaned up.
+    pub peak_bw_tets_to_del: usize,
+    /// 3D: the most tetrahedra kept at the border of a single cavity.
+    pub peak_bw_tets_to_keep: usize,
+    /// 3D: the most boundary triangles of a single cavity, i.e. the size of the insertion scratch buffers.
+    pub peak_bw_boundary_tris: usize,
+    /// 3D: how often the insertion scratch buffers had to grow, as they are reused across insertions.
+    ///
+    /// Stays at a few dozen even for millions of vertices, [`Tetrahedralization::reserve_insertion_scratch`](crate::Tetrahedralization::reserve_insertion_scratch)
+    /// avoids it.
+    pub bw_scratch_growths: usize,
+
+    /// The time spent sorting the vertices along a Hilbert curve in μs.
+    pub time_hilbert: u128,
+    /// The time spent locating the vertices in μs.
+    pub time_walking: u128,
+    /// The time spent inserting the located vertices in μs, in 2D without the flips after the 1->3 flip.
+    pub time_inserting: u128,
+    /// 2D: the time spent flipping edges after the 1->3 flips in μs.
+    pub time_flipping: u128,
+
+    /// 2D: the number of 1->3 flips, i.e. of vertices inserted by flips.
+    pub num_flips_1_3: usize,
+    /// 2D: the number of 2->2 flips.
+    pub num_flips_2_2: usize,
+    /// 2D: the number of 3->1 flips, i.e. of vertices flipped out as redundant or by epsilon.
+    pub num_flips_3_1: usize,
+    /// 3D: the number of 1->4 flips, i.e. of vertices inserted by flips.
+    pub num_flips_1_4: usize,
+    /// 3D: the number of 2->3 flips.
+    pub num_flips_2_3: usize,
+    /// 3D: the number of 3->2 flips.
+    pub num_flips_3_2: usize,
+    /// 3D: the number of 4->1 flips, i.e. of vertices flipped out as redundant.
+    pub num_flips_4_1: usize,
+    /// The number of Bowyer-Watson cavities, i.e. of vertices inserted by Bowyer-Watson.
+    pub num_bw_cavities: usize,
+
+    /// The number of visibility walks of the insertions.
+    pub num_walks: usize,
+    /// The number of simplices visited by all walks, along with [`Self::num_walks`] the mean length of a walk.
+    pub num_walk_steps: usize,
+    /// The most simplices visited by a single walk.
+    pub peak_walk_steps: usize,
+
+    /// The number of simplices created by the flips and cavities of the insertions, including reused slots.
+    pub num_simplices_created: usize,
+    /// The number of simplices deleted by the flips and cavities of the insertions, including reused slots.
+    pub num_simplices_deleted: usize,
+
+    /// The number of power circle and sphere tests of the insertions decided by the floating point filters, without
+    /// geogram.
+    pub num_filtered_predicates: usize,
+    /// The number of power circle and sphere tests of the insertions, that the floating point filters failed on and
+    /// geogram decided.
+    ///
+    /// Always zero with the `wasm` predicates, and for the tests of [`PredicateMode::FastInexact`](crate::PredicateMode::FastInexact)
+    /// decided in floating point.
+    pub num_filter_failures: usize,
+}
+
+impl BuildStats {
+    pub(crate) const fn new() -> Self {
+        Self {
+            peak_hedge_stack: 0,
+            peak_bw_tets_to_check: 0,
+            peak_bw_tets_to_del: 0,
+            peak_bw_tets_to_keep: 0,
+            peak_bw_boundary_tris: 0,
+            bw_scratch_growths: 0,
+            time_hilbert: 0,
+            time_walking: 0,
+            time_inserting: 0,
+            time_flipping: 0,
+            num_flips_1_3: 0,
+            num_flips_2_2: 0,
+            num_flips_3_1: 0,
+            num_flips_1_4: 0,
+            num_flips_2_3: 0,
+            num_flips_3_2: 0,
+            num_flips_4_1: 0,
+            num_bw_cavities: 0,
+            num_walks: 0,
+            num_walk_steps: 0,
+            peak_walk_steps: 0,
+            num_simplices_created: 0,
+            num_simplices_deleted: 0,
+            num_filtered_predicates: 0,
+            num_filter_failures: 0,
+        }
+    }
+
+    /// The mean number of simplices visited by a walk, zero without walks.
+    pub fn mean_walk_steps(&self) -> f64 {
+        if self.num_walks == 0 {
+            0.0
+        } else {
+            self.num_walk_steps as f64 / self.num_walks as f64
+        }
+    }
+
+    /// Record a walk, that visited `num_steps` simplices.
+    pub(crate) const fn record_walk(&mut self, num_steps: usize) {
+        self.num_walks += 1;
+        self.num_walk_steps += num_steps;
+        Self::record(&mut self.peak_walk_steps, num_steps);
+    }
+
+    /// Record a flip or cavity, that replaced `num_deleted` simplices by `num_created` ones.
+    pub(crate) const fn record_changes(&mut self, num_deleted: usize, num_created: usize) {
+        self.num_simplices_deleted += num_deleted;
+        self.num_simplices_created += num_created;
+    }
+
+    /// The share of predicates, that the floating point filters failed on, zero without predicates.
+    pub fn filter_failure_rate(&self) -> f64 {
+        let num_predicates = self.num_filtered_predicates + self.num_filter_failures;
+        if num_predicates == 0 {
+            0.0
+        } else {
+            self.num_filter_failures as f64 / num_predicates as f64
+        }
+    }
+
+    /// Record the power tests of an insertion, that the filters decided and failed on.
+    pub(crate) const fn record_filter_counts(&mut self, [num_filtered, num_failures]: [usize; 2]) {
+        self.num_filtered_predicates += num_filtered;
+        self.num_filter_failures += num_failures;
+    }
+
+    /// Raise a peak to `len`, if it is exceeded.
+    pub(crate) const fn record(peak: &mut usize, len: usize) {
+        if len > *peak {
+            *peak = len;
+        }
+    }
+}
+
+/// The power tests of one structure, that the floating point filters decided and failed on.
+///
+/// The power tests only borrow the structure, so the counts are atomics. As only the insertions are counted, which
+/// borrow it mutably, a load and a store suffice, no other thread evaluates predicates of the structure meanwhile.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default)]
+pub(crate) struct FilterCounts([AtomicUsize; 2]);
+
+#[cfg(feature = "metrics")]
+impl FilterCounts {
+    pub(crate) const fn new() -> Self {
+        Self([AtomicUsize::new(0), AtomicUsize::new(0)])
+    }
+
+    /// Count a power test, that was `decided` as given.
+    #[inline]
+    pub(crate) fn record(&self, decided: Decided) {
+        let count = match decided {
+            Decided::Filter => &self.0[0],
+            Decided::Fallback => &self.0[1],
+            Decided::Unfiltered => return,
+        };
+        count.store(count.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+    }
+
+    /// The counts since the last call, i.e. the ones to record in the [`BuildStats`].
+    pub(crate) fn take(&mut self) -> [usize; 2] {
+        self.0
+            .each_mut()
+            .map(|count| core::mem::take(count.get_mut()))
+    }
+}
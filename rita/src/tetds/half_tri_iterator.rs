@@ -1,4 +1,5 @@
-use crate::VertexNode;
+use crate::{VertexNode, audit::SoundnessIssue};
+use alloc::vec::Vec;
 
 use super::{
     hedge_iterator::HedgeIterator,
@@ -6,10 +7,14 @@ use super::{
     tet_iterator::TetIterator,
 };
 
+/// A half triangle, i.e. a face of a tetrahedron as seen from that tetrahedron.
+///
+/// Each face of the tetrahedralization is represented by two half triangles, one per adjacent tet, see [`Self::opposite`].
+/// The half triangle `4 * tet_idx + i` lies opposite to the node `i` of its tet.
 #[derive(Clone)]
 pub struct HalfTriIterator<'a> {
-    pub tds: &'a TetDataStructure,
-    pub half_tri_idx: usize,
+    pub(crate) tds: &'a TetDataStructure,
+    pub(crate) half_tri_idx: usize,
 }
 
 impl<'a> HalfTriIterator<'a> {
@@ -38,6 +43,18 @@ impl<'a> HalfTriIterator<'a> {
     }
 
     pub fn is_sound(&self) -> bool {
+        let mut issues = Vec::new();
+        self.check_soundness(&mut issues);
+        issues.is_empty()
+    }
+
+    /// Collect an issue, if the opposite half triangle does not have the same nodes.
+    pub(crate) fn check_soundness(&self, issues: &mut Vec<SoundnessIssue>) {
+        if self.tds.half_tri_opposite.get(self.half_tri_idx) >= self.tds.half_tri_opposite.len() {
+            issues.push(SoundnessIssue::WrongOppositeTri(self.half_tri_idx));
+            return;
+        }
+
         let [n0, n1, n2] = self.nodes();
 
         let [n_opposite0, n_opposite1, n_opposite2] = self.opposite().nodes();
@@ -46,12 +63,8 @@ impl<'a> HalfTriIterator<'a> {
             || (n0 == n_opposite2 && n1 == n_opposite1 && n2 == n_opposite0)
             || (n0 == n_opposite1 && n1 == n_opposite0 && n2 == n_opposite2))
         {
-            #[cfg(feature = "logging")]
-            log::error!("{}: Wrong opposite triangle: {}", self, self.opposite());
-            return false;
+            issues.push(SoundnessIssue::WrongOppositeTri(self.half_tri_idx));
         }
-
-        true
     }
 
     /// A triangle is considered conceptual if one of its nodes are conceptual
@@ -59,27 +72,54 @@ impl<'a> HalfTriIterator<'a> {
         self.nodes().iter().any(VertexNode::is_conceptual)
     }
 
+    /// The index of the half triangle within its tet, which is also the index of the opposite node within the tet.
+    pub const fn local_index(&self) -> usize {
+        self.half_tri_idx % 4
+    }
+
+    /// The indices within the tet of the nodes of the half triangle, in the order of [`Self::nodes`].
+    pub const fn local_node_indices(&self) -> [usize; 3] {
+        TRIANGLE_SUBINDICES[self.local_index()]
+    }
+
+    /// The nodes of the half triangle, in counter-clockwise order seen from outside the tet.
+    ///
+    /// I.e. the normal `(n1 - n0) × (n2 - n0)` points away from the opposite node.
     pub fn nodes(&self) -> [VertexNode; 3] {
-        let mod4 = self.half_tri_idx % 4;
-        let sub_idx = TRIANGLE_SUBINDICES[mod4];
+        [self.node(0), self.node(1), self.node(2)]
+    }
 
-        [
-            self.tds.tet_nodes[self.half_tri_idx - mod4 + sub_idx[0]],
-            self.tds.tet_nodes[self.half_tri_idx - mod4 + sub_idx[1]],
-            self.tds.tet_nodes[self.half_tri_idx - mod4 + sub_idx[2]],
-        ]
+    /// The `i`-th node of the half triangle, in the order of [`Self::nodes`].
+    pub(crate) fn node(&self, i: usize) -> VertexNode {
+        self.tds
+            .tet_nodes
+            .get(self.half_tri_idx - self.local_index() + self.local_node_indices()[i])
+    }
+
+    /// The nodes of the half triangle in reverse order, i.e. counter-clockwise seen from the opposite node.
+    ///
+    /// This is the order of the nodes of the [opposite](Self::opposite) half triangle up to rotation,
+    /// so the tet beyond the face with the apex `p` has the nodes `[n0, n1, n2, p]`.
+    pub fn oriented_nodes(&self) -> [VertexNode; 3] {
+        let [n0, n1, n2] = self.nodes();
+        [n0, n2, n1]
     }
 
     /// Get the opposite node on the same tet, i.e the node that is not part of the triangle
     pub fn opposite_node(&self) -> VertexNode {
-        self.tds.tet_nodes[self.idx()]
+        self.tds.tet_nodes.get(self.idx())
+    }
+
+    /// The index of the opposite vertex on the same tet, `None` if it is the conceptual node.
+    pub fn opposite_vertex(&self) -> Option<usize> {
+        self.opposite_node().idx()
     }
 
     /// Opposite half triangle on the neighboring tet
     pub fn opposite(&self) -> HalfTriIterator<'a> {
         HalfTriIterator {
             tds: self.tds,
-            half_tri_idx: self.tds.half_tri_opposite[self.idx()],
+            half_tri_idx: self.tds.half_tri_opposite.get(self.idx()),
         }
     }
 
@@ -1,8 +1,9 @@
 use super::{
     half_tri_iterator::HalfTriIterator,
-    tet_data_structure::{NEIGHBOR_HALFEDGE, TRIANGLE_SUBINDICES, TetDataStructure},
+    tet_data_structure::{NEIGHBOR_HALFEDGE, TetDataStructure},
 };
-use crate::VertexNode;
+use crate::{VertexNode, audit::SoundnessIssue};
+use alloc::vec::Vec;
 
 pub struct HedgeIterator<'a> {
     pub tds: &'a TetDataStructure,
@@ -12,12 +13,7 @@ pub struct HedgeIterator<'a> {
 
 impl<'a> HedgeIterator<'a> {
     pub fn first_node(&self) -> VertexNode {
-        // TODO: refactor the first two lines
-        let mod4 = self.half_tri_idx % 4;
-
-        let sub_idx = TRIANGLE_SUBINDICES[mod4];
-
-        self.tds.tet_nodes[self.half_tri_idx - mod4 + sub_idx[self.hedge_idx]]
+        self.tri().node(self.hedge_idx)
     }
 
     pub const fn idx(&self) -> usize {
@@ -25,53 +21,42 @@ impl<'a> HedgeIterator<'a> {
     }
 
     pub fn is_sound(&self) -> bool {
+        let mut issues = Vec::new();
+        self.check_soundness(&mut issues);
+        issues.is_empty()
+    }
+
+    /// Collect the broken links of this hedge, see [`SoundnessIssue`] for its index.
+    pub(crate) fn check_soundness(&self, issues: &mut Vec<SoundnessIssue>) {
         let first_node = self.first_node();
         let last_node = self.last_node();
+        let idx = 3 * self.half_tri_idx + self.hedge_idx;
 
-        let hedge_next = self.next();
-        let hedge_prev = self.prev();
-        let hedge_opposite = self.opposite();
         let hedge_neighbor = self.neighbor();
 
-        let mut valid = true;
-
-        if !(hedge_next.first_node() == last_node) {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: Wrong next hedge");
-            valid = false;
+        if self.next().first_node() != last_node {
+            issues.push(SoundnessIssue::WrongNext(idx));
         }
-        if !(hedge_prev.last_node() == first_node) {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: Wrong prev hedge");
-            valid = false;
+        if self.prev().last_node() != first_node {
+            issues.push(SoundnessIssue::WrongPrev(idx));
         }
-        if !(hedge_opposite.first_node() == last_node)
-            || !(hedge_opposite.last_node() == first_node)
+        // a stale link may point beyond the half triangles, e.g. after an interrupted insertion
+        if self.tds.half_tri_opposite.get(self.half_tri_idx) >= self.tds.half_tri_opposite.len()
+            || self.opposite().first_node() != last_node
+            || self.opposite().last_node() != first_node
         {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: Wrong opposite hedge");
-            valid = false;
+            issues.push(SoundnessIssue::WrongOpposite(idx));
         }
-        if !(hedge_neighbor.first_node() == last_node)
-            || !(hedge_neighbor.last_node() == first_node)
-        {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: Wrong neighboring hedge");
-            valid = false;
+        if hedge_neighbor.first_node() != last_node || hedge_neighbor.last_node() != first_node {
+            issues.push(SoundnessIssue::WrongNeighbor(idx));
         }
-
-        valid
     }
 
     pub fn last_node(&self) -> VertexNode {
-        // TODO: refactor the first two lines
-        let mod4 = self.half_tri_idx % 4;
-
-        let sub_idx = TRIANGLE_SUBINDICES[mod4];
-
-        self.tds.tet_nodes[self.half_tri_idx - mod4 + sub_idx[(self.hedge_idx + 1) % 3]]
+        self.tri().node((self.hedge_idx + 1) % 3)
     }
 
+    /// The halfedge of the other half triangle of the same tet, that runs along the same edge in the reverse direction.
     pub const fn neighbor(&self) -> HedgeIterator<'a> {
         let mod4 = self.half_tri_idx % 4;
 
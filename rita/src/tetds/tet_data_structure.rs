@@ -1,18 +1,38 @@
 use super::{
     half_tri_iterator::HalfTriIterator, hedge_iterator::HedgeIterator, tet_iterator::TetIterator,
 };
-use crate::VertexNode;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{self, SnapshotKind};
+#[cfg(feature = "metrics")]
+use crate::stats::BuildStats;
+use crate::{
+    RitaError, VertexNode,
+    audit::SoundnessReport,
+    utils::compact::{IdxVec, NodeVec},
+};
 
-use alloc::{vec, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
 use anyhow::{Ok as HowOk, Result as HowResult};
 
-// For each tri idx within a tet, associate list of vertex idx triples, i.e. the face indices
 /// For each triangle index within tetrahedron, associate list of vertices within tetrahedron
+///
+/// The half triangle `4 * tet_idx + i` lies opposite to the node `i` of the tet, its nodes are the tet's nodes at
+/// `TRIANGLE_SUBINDICES[i]`, in counter-clockwise order seen from outside the tet, i.e. away from node `i`.
+/// Use [`HalfTriIterator::local_index`], [`HalfTriIterator::local_node_indices`] and
+/// [`HalfTriIterator::oriented_nodes`] instead of indexing this directly.
 pub(crate) const TRIANGLE_SUBINDICES: [[usize; 3]; 4] =
     [[1, 3, 2], [0, 2, 3], [0, 3, 1], [0, 1, 2]];
 
 /// For each triangle index, for each halfedge index, associate triangle and halfedge index within
 /// tetrahedron
+///
+/// The halfedge `j` of a half triangle runs from its node `j` to its node `j + 1 (mod 3)`. `NEIGHBOR_HALFEDGE[i][j]`
+/// is the halfedge of the other half triangle of the same tet, that runs along the same edge in the reverse direction.
+/// Use [`HedgeIterator::neighbor`] instead of indexing this directly.
 pub(crate) const NEIGHBOR_HALFEDGE: [[(usize, usize); 3]; 4] = [
     [(2, 1), (1, 1), (3, 1)],
     [(3, 2), (0, 1), (2, 0)],
@@ -20,12 +40,6 @@ pub(crate) const NEIGHBOR_HALFEDGE: [[(usize, usize); 3]; 4] = [
     [(2, 2), (0, 2), (1, 0)],
 ];
 
-// Flips in 3D
-// OneToFour,
-// FourToOne,
-// TwoToThree,
-// ThreeToTwo,
-
 /// A 3D triangulation data structure.
 ///
 /// The edges are stored in a doubly-connected edge list (DCEL) manner.
@@ -50,13 +64,27 @@ pub(crate) const NEIGHBOR_HALFEDGE: [[(usize, usize); 3]; 4] = [
 // `tri3 = (i, i+1, i+2)`
 #[derive(Debug)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TetDataStructure {
-    pub tet_nodes: Vec<VertexNode>,
+    pub tet_nodes: NodeVec,
     /// Opposite half triangle index of this tet
-    pub(crate) half_tri_opposite: Vec<usize>,
+    pub(crate) half_tri_opposite: IdxVec,
 
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     num_tets: usize,
+    /// The number of tetrahedra connected to the conceptual node, maintained on every mutation.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    num_conceptual_tets: usize,
+    /// A tetrahedron incident to each vertex, set whenever a tetrahedron with the vertex is written.
+    ///
+    /// Stale for vertices that are not part of the tetrahedralization anymore, see [`Self::vertex_tet`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    vertex_tets: Vec<usize>,
+    /// A tetrahedron incident to the conceptual node, set like the ones of [`Self::vertex_tets`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    conceptual_tet: usize,
 
     // structures to speed up tetrahedra insertion with Bowyer Watson algorithm
     pub(crate) should_del_tet: Vec<bool>,
@@ -64,6 +92,24 @@ pub struct TetDataStructure {
     tets_to_del: Vec<usize>,
     tets_to_keep: Vec<usize>,
     tets_to_check: Vec<usize>,
+
+    // scratch buffers of the Bowyer Watson insertion, reused such that inserting a vertex does not allocate
+    /// The boundary triangles of the cavity.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_boundary_tris: Vec<usize>,
+    /// For each boundary triangle, the indices of the boundary triangles adjacent to its hedges.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_boundary_neighbors: Vec<[Option<usize>; 3]>,
+    /// The tetrahedra created by the last insertion, one per boundary triangle.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_added_tets: Vec<usize>,
+
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub(crate) stats: BuildStats,
 }
 
 impl Default for TetDataStructure {
@@ -76,15 +122,66 @@ impl TetDataStructure {
     /// Simplicial structure initialisation
     pub const fn new() -> Self {
         Self {
-            tet_nodes: Vec::new(),
-            half_tri_opposite: Vec::new(),
+            tet_nodes: NodeVec::new(),
+            half_tri_opposite: IdxVec::new(),
             num_tets: 0,
+            num_conceptual_tets: 0,
+            vertex_tets: Vec::new(),
+            conceptual_tet: 0,
             should_del_tet: Vec::new(),
             should_keep_tet: Vec::new(),
             tets_to_del: Vec::new(),
             tets_to_keep: Vec::new(),
             tets_to_check: Vec::new(),
+            bw_boundary_tris: Vec::new(),
+            bw_boundary_neighbors: Vec::new(),
+            bw_added_tets: Vec::new(),
+            #[cfg(feature = "metrics")]
+            stats: BuildStats::new(),
+        }
+    }
+
+    /// Encode the data structure as a compact binary snapshot, e.g. to checkpoint a long build.
+    ///
+    /// The snapshot can only be loaded by a build of rita with the same version and features, see [`load_snapshot`](Self::load_snapshot).
+    ///
+    /// ## Errors
+    /// Returns an error if the encoding fails.
+    #[cfg(feature = "snapshot")]
+    pub fn save_snapshot(&self) -> HowResult<Vec<u8>> {
+        snapshot::save(SnapshotKind::TetDataStructure, self)
+    }
+
+    /// Decode a data structure of tetrahedra from a snapshot of [`save_snapshot`](Self::save_snapshot).
+    ///
+    /// The decoded structure is not validated, a snapshot of a sound structure stays sound.
+    ///
+    /// ## Errors
+    /// Returns an error if the bytes are not a snapshot of a data structure of tetrahedra, or it can not be decoded.
+    #[cfg(feature = "snapshot")]
+    pub fn load_snapshot(bytes: &[u8]) -> HowResult<Self> {
+        snapshot::load(SnapshotKind::TetDataStructure, bytes)
+    }
+
+    /// Reserve the scratch buffers of the insertion for cavities with up to `num_boundary_tris` boundary triangles.
+    ///
+    /// The buffers are reused and grow on demand anyway, this only avoids growing them during the first insertions.
+    pub fn reserve_bw_scratch(&mut self, num_boundary_tris: usize) {
+        for buffer in [&mut self.bw_boundary_tris, &mut self.bw_added_tets] {
+            buffer.reserve(num_boundary_tris.saturating_sub(buffer.len()));
         }
+        self.bw_boundary_neighbors
+            .reserve(num_boundary_tris.saturating_sub(self.bw_boundary_neighbors.len()));
+    }
+
+    /// The tetrahedra removed since the last [`Self::clean_to_del`], i.e. the cavity of the current insertion in a clean structure.
+    pub(crate) fn bw_removed_tets(&self) -> &[usize] {
+        &self.tets_to_del
+    }
+
+    /// The tetrahedra created by the last [`Self::bw_insert_node`].
+    pub(crate) fn bw_added_tets(&self) -> &[usize] {
+        &self.bw_added_tets
     }
 
     const fn hedge(&self, ind_halftriangle: usize, ind_halfedge: usize) -> HedgeIterator<'_> {
@@ -109,29 +206,21 @@ impl TetDataStructure {
         if half_tri_idx < self.half_tri_opposite.len() {
             HowOk(self.half_triangle(half_tri_idx))
         } else {
-            Err(anyhow::Error::msg(
-                "Halftriangle value not in tetrahedron data structure",
-            ))
+            Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "half-triangle",
+                idx: half_tri_idx,
+            }))
         }
     }
 
-    /// Get the number of triangles in the triangulation, without the ones connected to the dummy point.
-    pub fn num_casual_tets(&self) -> usize {
-        let mut num_casual_tets = 0;
-        for i in 0..self.num_tets() {
-            let tri = self.get_tet(i).unwrap();
-            let [n0, n1, n2, n3] = tri.nodes();
+    /// Get the number of tetrahedra in the tetrahedralization, without the ones connected to the dummy point.
+    pub const fn num_casual_tets(&self) -> usize {
+        self.num_tets - self.num_conceptual_tets
+    }
 
-            if !tri.is_conceptual()
-                && n0.idx().is_some()
-                && n1.idx().is_some()
-                && n2.idx().is_some()
-                && n3.idx().is_some()
-            {
-                num_casual_tets += 1;
-            }
-        }
-        num_casual_tets
+    /// Get the number of tetrahedra connected to the dummy point, i.e. one per convex hull triangle.
+    pub const fn num_conceptual_tets(&self) -> usize {
+        self.num_conceptual_tets
     }
 
     const fn tet(&self, ind_tetrahedron: usize) -> TetIterator<'_> {
@@ -147,7 +236,10 @@ impl TetDataStructure {
         if ind_tetrahedron < self.num_tets {
             HowOk(self.tet(ind_tetrahedron))
         } else {
-            Err(anyhow::Error::msg("Tetrahedron value not in simplicial"))
+            Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "tetrahedron",
+                idx: ind_tetrahedron,
+            }))
         }
     }
 
@@ -157,6 +249,8 @@ impl TetDataStructure {
     }
 
     /// Gets halfedges containing a pair of nodes
+    ///
+    /// Only the star of `node0` is searched, see [`Self::get_tet_containing`].
     pub fn get_hedge_containing(
         &self,
         node0: &VertexNode,
@@ -164,15 +258,15 @@ impl TetDataStructure {
     ) -> Vec<HedgeIterator<'_>> {
         let mut hedges = Vec::new();
 
-        for i in 0..self.num_tets() {
+        for i in self.star(node0) {
             let first_node = i << 2;
             let mut sub_ind_v0 = 4;
             let mut sub_ind_v1 = 4;
 
             for j in 0..4 {
-                if self.tet_nodes[first_node + j] == *node0 {
+                if self.tet_nodes.get(first_node + j) == *node0 {
                     sub_ind_v0 = j;
-                } else if self.tet_nodes[first_node + j] == *node1 {
+                } else if self.tet_nodes.get(first_node + j) == *node1 {
                     sub_ind_v1 = j;
                 }
             }
@@ -199,24 +293,26 @@ impl TetDataStructure {
     }
 
     /// Gets halftriangle containing a triple of nodes
+    ///
+    /// Only the star of `node1` is searched, see [`Self::get_tet_containing`].
     pub fn get_half_tri_containing(
         &self,
         node1: &VertexNode,
         node2: &VertexNode,
         node3: &VertexNode,
     ) -> Option<HalfTriIterator<'_>> {
-        for i in 0..self.num_tets {
+        for i in self.star(node1) {
             let first_node = i << 2;
             let mut sub_ind_v0 = 4;
             let mut sub_ind_v1 = 4;
             let mut sub_ind_v2 = 4;
 
             for j in 0..4 {
-                if self.tet_nodes[first_node + j] == *node1 {
+                if self.tet_nodes.get(first_node + j) == *node1 {
                     sub_ind_v0 = j;
-                } else if self.tet_nodes[first_node + j] == *node2 {
+                } else if self.tet_nodes.get(first_node + j) == *node2 {
                     sub_ind_v1 = j;
-                } else if self.tet_nodes[first_node + j] == *node3 {
+                } else if self.tet_nodes.get(first_node + j) == *node3 {
                     sub_ind_v2 = j;
                 }
             }
@@ -246,30 +342,100 @@ impl TetDataStructure {
         None
     }
 
-    /// Gets tetrahedra containing a specific node
+    /// Gets tetrahedra containing a specific node, ordered by their index
+    ///
+    /// Walks across the triangles around the node, starting at its incident tetrahedron, so the cost is proportional
+    /// to the size of its star. Tetrahedra removed by an unfinished insertion are not part of any star.
     pub fn get_tet_containing(&self, node: &VertexNode) -> Vec<TetIterator<'_>> {
-        let mut tets = Vec::new();
+        self.star(node)
+            .into_iter()
+            .map(|tet_idx| self.tet(tet_idx))
+            .collect()
+    }
 
-        for i in 0..self.num_tets {
-            let first_node = i << 2;
+    /// The indices of the tetrahedra containing the node, sorted.
+    fn star(&self, node: &VertexNode) -> Vec<usize> {
+        let start = match node {
+            VertexNode::Casual(v_idx) => self.vertex_tet(*v_idx),
+            VertexNode::Conceptual => self.conceptual_tet(),
+            VertexNode::Deleted => None,
+        };
+        let Some(start) = start else {
+            return Vec::new();
+        };
 
-            for j in 0..4 {
-                if self.tet_nodes[first_node + j] == *node {
-                    tets.push(self.tet(i));
-                    break;
+        // a sorted set, as the star of the conceptual node holds all hull tetrahedra
+        let mut star = BTreeSet::from([start]);
+        let mut to_visit = vec![start];
+        while let Some(tet_idx) = to_visit.pop() {
+            for half_tri in self.tet(tet_idx).half_triangles() {
+                let neighbor_idx = half_tri.opposite().tet().idx();
+                if half_tri.opposite_node() != *node && star.insert(neighbor_idx) {
+                    to_visit.push(neighbor_idx);
+                }
+            }
+        }
+
+        star.into_iter().collect()
+    }
+
+    /// Record the tetrahedron as the incident tetrahedron of its nodes.
+    fn set_vertex_tets(&mut self, nodes: &[VertexNode], tet_idx: usize) {
+        for node in nodes {
+            match *node {
+                VertexNode::Casual(v_idx) => {
+                    if v_idx >= self.vertex_tets.len() {
+                        self.vertex_tets.resize(v_idx + 1, usize::MAX);
+                    }
+                    self.vertex_tets[v_idx] = tet_idx;
                 }
+                VertexNode::Conceptual => self.conceptual_tet = tet_idx,
+                VertexNode::Deleted => {}
             }
         }
+    }
+
+    /// A tetrahedron incident to the vertex, `None` if the vertex is in no tetrahedron.
+    ///
+    /// Falls back to scanning all tetrahedra, if the recorded one does not contain the vertex,
+    /// e.g. for a data structure deserialized without incident tetrahedra.
+    pub fn vertex_tet(&self, v_idx: usize) -> Option<usize> {
+        let contains_v = |tet_idx: &usize| {
+            *tet_idx < self.num_tets
+                && !self.should_del_tet[*tet_idx]
+                && self
+                    .tet_nodes
+                    .contains(4 * tet_idx..4 * tet_idx + 4, VertexNode::Casual(v_idx))
+        };
+
+        self.vertex_tets
+            .get(v_idx)
+            .copied()
+            .filter(contains_v)
+            .or_else(|| (0..self.num_tets).find(contains_v))
+    }
+
+    /// A tetrahedron incident to the conceptual node, `None` if there is none, falling back like [`Self::vertex_tet`].
+    fn conceptual_tet(&self) -> Option<usize> {
+        let is_conceptual = |tet_idx: &usize| {
+            *tet_idx < self.num_tets
+                && !self.should_del_tet[*tet_idx]
+                && self
+                    .tet_nodes
+                    .contains(4 * tet_idx..4 * tet_idx + 4, VertexNode::Conceptual)
+        };
 
-        tets
+        Some(self.conceptual_tet)
+            .filter(is_conceptual)
+            .or_else(|| (0..self.num_tets).find(is_conceptual))
     }
 
     /// Starts BW insertion, setting a first tetrahedron to remove
     pub fn bw_start(&mut self, first_tet_idx: usize) -> HowResult<()> {
         if !self.tets_to_check.is_empty() || !self.tets_to_keep.is_empty() {
-            return Err(anyhow::Error::msg(
+            return Err(anyhow::Error::new(RitaError::InvalidState(
                 "Bowyer Watson algorithm already started",
-            ));
+            )));
         }
 
         self.bw_rem_tet(first_tet_idx);
@@ -294,10 +460,10 @@ impl TetDataStructure {
         let tri2 = tri0 + 2;
         let tri3 = tri0 + 3;
 
-        let opp_tri0 = self.half_tri_opposite[tri0];
-        let opp_tri1 = self.half_tri_opposite[tri1];
-        let opp_tri2 = self.half_tri_opposite[tri2];
-        let opp_tri3 = self.half_tri_opposite[tri3];
+        let opp_tri0 = self.half_tri_opposite.get(tri0);
+        let opp_tri1 = self.half_tri_opposite.get(tri1);
+        let opp_tri2 = self.half_tri_opposite.get(tri2);
+        let opp_tri3 = self.half_tri_opposite.get(tri3);
 
         self.tets_to_check.push(opp_tri0 >> 2);
         self.tets_to_check.push(opp_tri1 >> 2);
@@ -306,6 +472,15 @@ impl TetDataStructure {
 
         self.should_del_tet[tet_idx] = true;
         self.tets_to_del.push(tet_idx);
+
+        #[cfg(feature = "metrics")]
+        {
+            BuildStats::record(
+                &mut self.stats.peak_bw_tets_to_check,
+                self.tets_to_check.len(),
+            );
+            BuildStats::record(&mut self.stats.peak_bw_tets_to_del, self.tets_to_del.len());
+        }
     }
 
     /// Sets tetrahedron to keep
@@ -313,15 +488,40 @@ impl TetDataStructure {
         self.should_keep_tet[tet_idx] = true;
         self.tets_to_keep.push(tet_idx);
 
+        #[cfg(feature = "metrics")]
+        BuildStats::record(
+            &mut self.stats.peak_bw_tets_to_keep,
+            self.tets_to_keep.len(),
+        );
+
         HowOk(())
     }
 
+    /// Sets a kept tetrahedron to remove after all, e.g. to grow the cavity.
+    pub fn bw_grow(&mut self, tet_idx: usize) {
+        if self.should_del_tet[tet_idx] {
+            return;
+        }
+
+        self.should_keep_tet[tet_idx] = false;
+        self.tets_to_keep.retain(|&idx| idx != tet_idx);
+        self.bw_rem_tet(tet_idx);
+    }
+
+    /// The half triangles of the kept tetrahedra, that face a removed one, i.e. the boundary of the cavity.
+    pub fn bw_cavity_boundary(&self) -> impl Iterator<Item = HalfTriIterator<'_>> {
+        self.tets_to_keep
+            .iter()
+            .flat_map(|&tet_idx| self.tet(tet_idx).half_triangles())
+            .filter(|tri| tri.opposite().tet().should_del())
+    }
+
     /// BW insertion algorithm
-    pub fn bw_insert_node(&mut self, nod: VertexNode) -> HowResult<Vec<usize>> {
+    pub fn bw_insert_node(&mut self, nod: VertexNode) -> HowResult<()> {
         if !self.tets_to_check.is_empty() {
-            return Err(anyhow::Error::msg(
-                "Cannot insert node if all tetrahedra are not checked",
-            ));
+            return Err(anyhow::Error::new(RitaError::InvalidState(
+                "cannot insert node if all tetrahedra are not checked",
+            )));
         }
 
         // 1 - find boundary triangle
@@ -337,15 +537,33 @@ impl TetDataStructure {
             } else if tris[3].opposite().tet().should_del() {
                 tris[3].idx()
             } else {
-                return Err(anyhow::Error::msg("Isolated kept tetrahedron"));
+                return Err(anyhow::Error::new(RitaError::InvalidState(
+                    "isolated kept tetrahedron",
+                )));
             }
         } else {
-            return Err(anyhow::Error::msg("No kept tetrahedron"));
+            return Err(anyhow::Error::new(RitaError::InvalidState(
+                "no kept tetrahedron",
+            )));
         };
 
+        // the scratch buffers are taken out of `self` while it is borrowed by the iterators
+        let mut vec_tri = core::mem::take(&mut self.bw_boundary_tris);
+        let mut vec_nei = core::mem::take(&mut self.bw_boundary_neighbors);
+        let mut added_tets = core::mem::take(&mut self.bw_added_tets);
+        #[cfg(feature = "metrics")]
+        let capacities = [
+            vec_tri.capacity(),
+            vec_nei.capacity(),
+            added_tets.capacity(),
+        ];
+        vec_tri.clear();
+        vec_nei.clear();
+        added_tets.clear();
+
         // 2 - build boundary triangles graph
-        let mut vec_tri = vec![ind_tri_first];
-        let mut vec_nei: Vec<[Option<usize>; 3]> = vec![[None; 3]];
+        vec_tri.push(ind_tri_first);
+        vec_nei.push([None; 3]);
         let mut ind_cur = 0;
         loop {
             let cur_tri = HalfTriIterator {
@@ -390,7 +608,9 @@ impl TetDataStructure {
             }
         }
 
-        let mut added_tets = Vec::with_capacity(vec_tri.len());
+        #[cfg(feature = "metrics")]
+        BuildStats::record(&mut self.stats.peak_bw_boundary_tris, vec_tri.len());
+
         // 3 - create tetrahedra
         for i in &vec_tri {
             let cur_tri = HalfTriIterator {
@@ -398,18 +618,18 @@ impl TetDataStructure {
                 half_tri_idx: *i,
             };
 
-            let [nod0, nod1, nod2] = cur_tri.nodes();
+            let [nod0, nod1, nod2] = cur_tri.oriented_nodes();
 
             if let Some(ind_add) = self.tets_to_del.pop() {
                 added_tets.push(ind_add);
-                self.replace_tet(ind_add, nod0, nod2, nod1, nod);
+                self.replace_tet(ind_add, nod0, nod1, nod2, nod);
             } else {
                 added_tets.push(self.num_tets());
                 self.half_tri_opposite.push(0);
                 self.half_tri_opposite.push(0);
                 self.half_tri_opposite.push(0);
                 self.half_tri_opposite.push(0);
-                self.insert_tet(nod0, nod2, nod1, nod);
+                self.insert_tet(nod0, nod1, nod2, nod);
             };
         }
 
@@ -454,26 +674,202 @@ impl TetDataStructure {
                 ind_tet_nei2 * 4 + 2
             };
 
-            self.half_tri_opposite[tri0] = ind_tri0_nei;
-            self.half_tri_opposite[tri1] = ind_tri1_nei;
-            self.half_tri_opposite[tri2] = ind_tri2_nei;
-            self.half_tri_opposite[tri3] = ind_tri_nei;
-            self.half_tri_opposite[ind_tri_nei] = tri3;
+            self.half_tri_opposite.set(tri0, ind_tri0_nei);
+            self.half_tri_opposite.set(tri1, ind_tri1_nei);
+            self.half_tri_opposite.set(tri2, ind_tri2_nei);
+            self.half_tri_opposite.set(tri3, ind_tri_nei);
+            self.half_tri_opposite.set(ind_tri_nei, tri3);
         }
 
         while let Some(ind_tetra_keep) = self.tets_to_keep.pop() {
             self.should_keep_tet[ind_tetra_keep] = false;
         }
 
-        HowOk(added_tets)
+        #[cfg(debug_assertions)]
+        self.debug_check_tets("Bowyer-Watson insertion", &added_tets);
+
+        #[cfg(feature = "metrics")]
+        if capacities
+            != [
+                vec_tri.capacity(),
+                vec_nei.capacity(),
+                added_tets.capacity(),
+            ]
+        {
+            self.stats.bw_scratch_growths += 1;
+        }
+
+        self.bw_boundary_tris = vec_tri;
+        self.bw_boundary_neighbors = vec_nei;
+        self.bw_added_tets = added_tets;
+
+        HowOk(())
+    }
+
+    /// Replaces the tetrahedra `old_tets` by new tetrahedra with the nodes `new_tets`, that fill the same region.
+    ///
+    /// This is the common step of the flips (1-4, 2-3, 3-2 and 4-1): the facets between the new tetrahedra are linked
+    /// to each other, the ones on the boundary of the region to the tetrahedra outside of it.
+    /// The slots of the old tetrahedra and of removed ones are reused, left over slots are removed.
+    /// Returns the indices of the new tetrahedra, in the order of `new_tets`.
+    pub fn flip(
+        &mut self,
+        old_tets: &[usize],
+        new_tets: &[[VertexNode; 4]],
+    ) -> HowResult<Vec<usize>> {
+        let key = |nodes: [VertexNode; 3]| {
+            let mut key = nodes.map(|node| node.idx().unwrap_or(usize::MAX));
+            key.sort_unstable();
+            key
+        };
+
+        // the half triangles outside of the region, facing its boundary
+        let mut outside_tris: Vec<([usize; 3], usize)> = Vec::new();
+        for &tet_idx in old_tets {
+            for tri in self.tet(tet_idx).half_triangles() {
+                let opposite = tri.opposite();
+                if !old_tets.contains(&opposite.tet().idx()) {
+                    outside_tris.push((key(tri.nodes()), opposite.idx()));
+                }
+            }
+        }
+
+        let mut tet_idxs = Vec::with_capacity(new_tets.len());
+        for (i, &[node0, node1, node2, node3]) in new_tets.iter().enumerate() {
+            let tet_idx = if let Some(&tet_idx) = old_tets.get(i).or(self.tets_to_del.last()) {
+                if i >= old_tets.len() {
+                    self.tets_to_del.pop();
+                }
+                self.replace_tet(tet_idx, node0, node1, node2, node3);
+                tet_idx
+            } else {
+                self.insert_tet(node0, node1, node2, node3);
+                self.half_tri_opposite.extend([usize::MAX; 4]);
+                self.num_tets - 1
+            };
+            tet_idxs.push(tet_idx);
+        }
+
+        for &tet_idx in old_tets.iter().skip(new_tets.len()) {
+            self.should_del_tet[tet_idx] = true;
+            self.tets_to_del.push(tet_idx);
+        }
+
+        // the half triangles between the new tetrahedra, that are not linked yet
+        let mut inside_tris: Vec<([usize; 3], usize)> = Vec::new();
+        let new_tris: Vec<([usize; 3], usize)> = tet_idxs
+            .iter()
+            .flat_map(|&tet_idx| self.tet(tet_idx).half_triangles())
+            .map(|tri| (key(tri.nodes()), tri.idx()))
+            .collect();
+        for (tri_key, tri_idx) in new_tris {
+            let opposite_idx = if let Some(pos) = outside_tris
+                .iter()
+                .position(|&(other_key, _)| other_key == tri_key)
+            {
+                outside_tris.swap_remove(pos).1
+            } else if let Some(pos) = inside_tris
+                .iter()
+                .position(|&(other_key, _)| other_key == tri_key)
+            {
+                inside_tris.swap_remove(pos).1
+            } else {
+                inside_tris.push((tri_key, tri_idx));
+                continue;
+            };
+
+            self.half_tri_opposite.set(tri_idx, opposite_idx);
+            self.half_tri_opposite.set(opposite_idx, tri_idx);
+        }
+
+        if !outside_tris.is_empty() || !inside_tris.is_empty() {
+            return Err(anyhow::Error::new(RitaError::InvalidState(
+                "flipped tetrahedra do not fill the same region",
+            )));
+        }
+
+        #[cfg(debug_assertions)]
+        self.debug_check_tets("flip", &tet_idxs);
+
+        HowOk(tet_idxs)
+    }
+
+    /// Check the structural invariants of the given tetrahedra, right after they were modified.
+    ///
+    /// Catches corruption at the mutation site, instead of much later in [`Self::is_sound`].
+    /// Panics with the offending indices, only compiled with `debug_assertions`.
+    #[cfg(debug_assertions)]
+    fn debug_check_tets(&self, operation: &str, tet_idxs: &[usize]) {
+        for &tet_idx in tet_idxs {
+            let tet = self.tet(tet_idx);
+            let [n0, n1, n2, n3] = tet.nodes();
+
+            assert!(
+                n0 != n1 && n0 != n2 && n0 != n3 && n1 != n2 && n1 != n3 && n2 != n3,
+                "{operation}: tetrahedron {tet_idx} has duplicate nodes [{n0}, {n1}, {n2}, {n3}]"
+            );
+
+            for half_tri in tet.half_triangles() {
+                let half_tri_idx = half_tri.idx();
+
+                let opposite_idx = self.half_tri_opposite.get(half_tri_idx);
+                assert!(
+                    opposite_idx < self.half_tri_opposite.len(),
+                    "{operation}: half triangle {half_tri_idx} of tetrahedron {tet_idx} has no opposite ({opposite_idx})"
+                );
+                assert_eq!(
+                    self.half_tri_opposite.get(opposite_idx),
+                    half_tri_idx,
+                    "{operation}: opposite of the opposite {opposite_idx} of half triangle {half_tri_idx} in tetrahedron {tet_idx} is not the half triangle itself"
+                );
+                assert!(
+                    !self.should_del_tet[opposite_idx >> 2],
+                    "{operation}: half triangle {half_tri_idx} of tetrahedron {tet_idx} is linked to the deleted tetrahedron {}",
+                    opposite_idx >> 2
+                );
+
+                let [m0, m1, m2] = half_tri.nodes();
+                let opposite_nodes = half_tri.opposite().nodes();
+                assert!(
+                    [[m0, m2, m1], [m1, m0, m2], [m2, m1, m0]].contains(&opposite_nodes),
+                    "{operation}: nodes of half triangle {half_tri_idx} and its opposite {opposite_idx} in tetrahedron {tet_idx} do not match"
+                );
+
+                for hedge in half_tri.hedges() {
+                    assert!(
+                        hedge.next().next().next().idx() == hedge.idx()
+                            && hedge.next().prev().idx() == hedge.idx(),
+                        "{operation}: next/prev cycle of hedge {} in half triangle {half_tri_idx} of tetrahedron {tet_idx} is broken",
+                        hedge.idx()
+                    );
+                    assert!(
+                        hedge.neighbor().first_node() == hedge.last_node()
+                            && hedge.neighbor().last_node() == hedge.first_node(),
+                        "{operation}: neighbor of hedge {} in half triangle {half_tri_idx} of tetrahedron {tet_idx} has the wrong nodes",
+                        hedge.idx()
+                    );
+                }
+            }
+        }
     }
 
     /// Clean removed tetrahedra
     pub fn clean_to_del(&mut self) -> HowResult<()> {
+        self.clean_to_del_with(|_, _| ())
+    }
+
+    /// Clean removed tetrahedra, calling `on_move` with the old and new index of each tetrahedron moved into a removed one.
+    pub(crate) fn clean_to_del_with(
+        &mut self,
+        mut on_move: impl FnMut(usize, usize),
+    ) -> HowResult<()> {
         self.tets_to_del.sort_unstable();
 
         while let Some(tet_to_del_idx) = self.tets_to_del.pop() {
             self.should_del_tet[tet_to_del_idx] = false;
+            if tet_to_del_idx != self.num_tets - 1 {
+                on_move(self.num_tets - 1, tet_to_del_idx);
+            }
             self.mov_end_tet(tet_to_del_idx)?;
         }
 
@@ -493,11 +889,18 @@ impl TetDataStructure {
         self.tet_nodes.push(nod2);
         self.tet_nodes.push(nod3);
         self.tet_nodes.push(nod4);
+        self.set_vertex_tets(&[nod1, nod2, nod3, nod4], idx0 / 4);
 
         self.should_del_tet.push(false);
         self.should_keep_tet.push(false);
 
         self.num_tets += 1;
+        if [nod1, nod2, nod3, nod4]
+            .iter()
+            .any(VertexNode::is_conceptual)
+        {
+            self.num_conceptual_tets += 1;
+        }
 
         (idx0, idx0 + 1, idx0 + 2, idx0 + 3)
     }
@@ -512,10 +915,21 @@ impl TetDataStructure {
     ) -> (usize, usize, usize, usize) {
         let idx0 = tet_idx * 4;
 
-        self.tet_nodes[idx0] = nod1;
-        self.tet_nodes[idx0 + 1] = nod2;
-        self.tet_nodes[idx0 + 2] = nod3;
-        self.tet_nodes[idx0 + 3] = nod4;
+        if self.tet(tet_idx).is_conceptual() {
+            self.num_conceptual_tets -= 1;
+        }
+        if [nod1, nod2, nod3, nod4]
+            .iter()
+            .any(VertexNode::is_conceptual)
+        {
+            self.num_conceptual_tets += 1;
+        }
+
+        self.tet_nodes.set(idx0, nod1);
+        self.tet_nodes.set(idx0 + 1, nod2);
+        self.tet_nodes.set(idx0 + 2, nod3);
+        self.tet_nodes.set(idx0 + 3, nod4);
+        self.set_vertex_tets(&[nod1, nod2, nod3, nod4], tet_idx);
 
         self.should_del_tet[tet_idx] = false;
         self.should_keep_tet[tet_idx] = false;
@@ -525,25 +939,29 @@ impl TetDataStructure {
 
     fn mov_end_tet(&mut self, tet_idx: usize) -> HowResult<()> {
         if tet_idx != self.num_tets - 1 {
-            let opp_tri_idx0 = self.half_tri_opposite[self.half_tri_opposite.len() - 4];
-            let opp_tri_idx1 = self.half_tri_opposite[self.half_tri_opposite.len() - 3];
-            let opp_tri_idx2 = self.half_tri_opposite[self.half_tri_opposite.len() - 2];
-            let opp_tri_idx3 = self.half_tri_opposite[self.half_tri_opposite.len() - 1];
+            let opp_tri_idx0 = self.half_tri_opposite.get(self.half_tri_opposite.len() - 4);
+            let opp_tri_idx1 = self.half_tri_opposite.get(self.half_tri_opposite.len() - 3);
+            let opp_tri_idx2 = self.half_tri_opposite.get(self.half_tri_opposite.len() - 2);
+            let opp_tri_idx3 = self.half_tri_opposite.get(self.half_tri_opposite.len() - 1);
 
             let [node0, node1, node2, node3] = self.tet(self.num_tets - 1).nodes();
 
             let (tri_idx0, tri_idx1, tri_idx2, tri_idx3) =
                 self.replace_tet(tet_idx, node0, node1, node2, node3);
 
-            self.half_tri_opposite[tri_idx0] = opp_tri_idx0;
-            self.half_tri_opposite[tri_idx1] = opp_tri_idx1;
-            self.half_tri_opposite[tri_idx2] = opp_tri_idx2;
-            self.half_tri_opposite[tri_idx3] = opp_tri_idx3;
+            self.half_tri_opposite.set(tri_idx0, opp_tri_idx0);
+            self.half_tri_opposite.set(tri_idx1, opp_tri_idx1);
+            self.half_tri_opposite.set(tri_idx2, opp_tri_idx2);
+            self.half_tri_opposite.set(tri_idx3, opp_tri_idx3);
 
-            self.half_tri_opposite[opp_tri_idx0] = tri_idx0;
-            self.half_tri_opposite[opp_tri_idx1] = tri_idx1;
-            self.half_tri_opposite[opp_tri_idx2] = tri_idx2;
-            self.half_tri_opposite[opp_tri_idx3] = tri_idx3;
+            self.half_tri_opposite.set(opp_tri_idx0, tri_idx0);
+            self.half_tri_opposite.set(opp_tri_idx1, tri_idx1);
+            self.half_tri_opposite.set(opp_tri_idx2, tri_idx2);
+            self.half_tri_opposite.set(opp_tri_idx3, tri_idx3);
+        }
+
+        if self.tet(self.num_tets - 1).is_conceptual() {
+            self.num_conceptual_tets -= 1;
         }
 
         self.tet_nodes.pop();
@@ -567,7 +985,7 @@ impl TetDataStructure {
     /// Inserts a first tetrahedron in the structure
     pub fn insert_first_tet(&mut self, nodes: [usize; 4]) -> HowResult<[TetIterator<'_>; 4]> {
         if self.num_tets != 0 {
-            return Err(anyhow::Error::msg("Already tetrahedra in simplicial"));
+            return Err(anyhow::Error::new(RitaError::AlreadyInitialized));
         }
 
         let node0 = VertexNode::Casual(nodes[0]);
@@ -627,22 +1045,80 @@ impl TetDataStructure {
 
     /// Checks soundness of tetrahedral graph
     pub fn is_sound(&self) -> HowResult<bool> {
-        let mut sound = true;
+        let report = self.soundness_report();
 
-        for tet_idx in 0..self.num_tets() {
-            let tet = self.get_tet(tet_idx)?;
+        #[cfg(feature = "logging")]
+        for issue in &report.issues {
+            log::error!("{issue}");
+        }
 
-            sound = sound && tet.is_sound();
+        HowOk(report.is_sound())
+    }
+
+    /// Try to repair recoverable inconsistencies, e.g. after an interrupted insertion, and report the remaining issues.
+    ///
+    /// Abandons a pending Bowyer Watson insertion, i.e. unmarks the tetrahedra to delete or keep,
+    /// and re-links every half triangle, whose opposite does not share its nodes, to the unique half triangle that does.
+    pub fn try_repair(&mut self) -> SoundnessReport {
+        self.tets_to_check.clear();
+        self.tets_to_keep.clear();
+        self.tets_to_del.clear();
+        self.should_del_tet.fill(false);
+        self.should_keep_tet.fill(false);
+
+        let key = |nodes: [VertexNode; 3]| {
+            let mut key = nodes.map(|node| node.idx().unwrap_or(usize::MAX));
+            key.sort_unstable();
+            key
+        };
+        let num_half_tris = 4 * self.num_tets;
+
+        let mut half_tris: BTreeMap<[usize; 3], Vec<usize>> = BTreeMap::new();
+        for half_tri_idx in 0..num_half_tris {
+            half_tris
+                .entry(key(self.half_triangle(half_tri_idx).nodes()))
+                .or_default()
+                .push(half_tri_idx);
+        }
+
+        for half_tri_idx in 0..num_half_tris {
+            let nodes = key(self.half_triangle(half_tri_idx).nodes());
+            let opposite_idx = self.half_tri_opposite.get(half_tri_idx);
+
+            let synced = opposite_idx < num_half_tris
+                && self.half_tri_opposite.get(opposite_idx) == half_tri_idx
+                && key(self.half_triangle(opposite_idx).nodes()) == nodes;
+            if synced {
+                continue;
+            }
+
+            if let Some(&[idx0, idx1]) = half_tris.get(&nodes).map(Vec::as_slice) {
+                let opposite_idx = if idx0 == half_tri_idx { idx1 } else { idx0 };
+                self.half_tri_opposite.set(half_tri_idx, opposite_idx);
+                self.half_tri_opposite.set(opposite_idx, half_tri_idx);
+            }
+        }
+
+        self.soundness_report()
+    }
+
+    /// Check all tetrahedra, their half triangles and hedges, for broken links.
+    pub fn soundness_report(&self) -> SoundnessReport {
+        let mut report = SoundnessReport::default();
+
+        for tet_idx in 0..self.num_tets() {
+            let tet = self.tet(tet_idx);
+            tet.check_soundness(&mut report.issues);
 
             for tri in tet.half_triangles() {
-                sound = sound && tri.is_sound();
+                tri.check_soundness(&mut report.issues);
                 for he in tri.hedges() {
-                    sound = sound && he.is_sound();
+                    he.check_soundness(&mut report.issues);
                 }
             }
         }
 
-        HowOk(sound)
+        report
     }
 }
 
@@ -1,5 +1,6 @@
 use super::{half_tri_iterator::HalfTriIterator, tet_data_structure::TetDataStructure};
-use crate::{VertexNode, utils::types::TetIteratorIdx};
+use crate::{VertexNode, audit::SoundnessIssue, utils::types::TetIteratorIdx};
+use alloc::vec::Vec;
 
 pub struct TetIterator<'a> {
     pub tds: &'a TetDataStructure,
@@ -45,32 +46,32 @@ impl<'a> TetIterator<'a> {
     }
 
     pub fn is_sound(&self) -> bool {
+        let mut issues = Vec::new();
+        self.check_soundness(&mut issues);
+        issues.is_empty()
+    }
+
+    /// Collect the issues of this tetrahedron, i.e. whether it is unfinished or has duplicate nodes.
+    pub(crate) fn check_soundness(&self, issues: &mut Vec<SoundnessIssue>) {
         if self.should_del() || self.should_keep() {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: tetrahedron remaining after triangulation.");
-            return false;
+            issues.push(SoundnessIssue::Unfinished(self.idx()));
+            return;
         }
 
         let [n0, n1, n2, n3] = self.nodes();
 
-        let mut sound = true;
-
         if n0 == n1 || n0 == n2 || n0 == n3 || n1 == n2 || n1 == n3 || n2 == n3 {
-            #[cfg(feature = "logging")]
-            log::error!("{self}: tetrahedron with duplicate nodes.");
-            sound = false;
+            issues.push(SoundnessIssue::DuplicateNodes(self.idx()));
         }
-
-        sound
     }
 
     pub fn nodes(&self) -> [VertexNode; 4] {
         let idx_first = self.idx() << 2; // this is equivalent to self.tet_idx * 4 (rounding down to nearest integer), but faster
         [
-            self.tds.tet_nodes[idx_first],
-            self.tds.tet_nodes[idx_first + 1],
-            self.tds.tet_nodes[idx_first + 2],
-            self.tds.tet_nodes[idx_first + 3],
+            self.tds.tet_nodes.get(idx_first),
+            self.tds.tet_nodes.get(idx_first + 1),
+            self.tds.tet_nodes.get(idx_first + 2),
+            self.tds.tet_nodes.get(idx_first + 3),
         ]
     }
 
@@ -1,18 +1,45 @@
-use alloc::{vec, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{fmt::Write, ops::ControlFlow};
 
+#[cfg(feature = "exact")]
+use crate::exact::ExactVertices;
 use crate::predicates;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{self, SnapshotKind};
+#[cfg(feature = "metrics")]
+use crate::stats::{BuildStats, FilterCounts};
 use crate::{
-    VertexNode,
+    IntoVertex3, PredicateMode, RitaError, Triangulation, VertexNode,
+    audit::{RegularityCheck, RegularityReport, SoundnessReport},
+    complex::BoundaryMatrices,
+    degenerate::{Chain, Degenerate3, Insertion},
+    frozen::CompactTetrahedralization,
+    geometry::{Plane, barycentric_3d, closest_on_triangle, cross, dot, sub},
+    handle::TetHandle,
+    io::{self, NodeEleData, PlyData},
+    locate_grid::LocateGrid,
+    observer::{InsertObserver, Observer},
+    quality::{QualityHistograms, TetQuality, tet_quality},
     tetds::{half_tri_iterator::HalfTriIterator, tet_data_structure::TetDataStructure},
+    triangulation::InsertionStrategy,
     utils::{
-        point_order::sort_along_hilbert_curve_3d,
-        types::{Tetrahedron3, Triangle3, Vertex3, VertexIdx},
+        insert_status::{
+            DuplicatePolicy, InsertProgress, InsertStatus, SimplexChanges, canonical_vertices,
+            insert_statuses, merge_duplicate_weights,
+        },
+        parallel::{self, prelude::*},
+        point_order::{
+            InsertOrder, SplitMix64, brio_order_3d, shuffle, sort_along_hilbert_curve_3d,
+            sort_along_morton_curve_3d, sort_buckets_by_weight,
+        },
+        translation::{bbox_origin, is_exact_translation, local_origin, to_local},
+        types::{Tetrahedron3, Triangle3, Vertex2, Vertex3, VertexIdx},
     },
+    walk::{WalkCap, WalkFallback, WalkStats},
 };
 use anyhow::Result as HowResult;
 #[cfg(feature = "logging")]
 use log::error;
-use rayon::prelude::*;
 
 /// Extended tetrahedron, including point at infinity
 pub enum ExtendedTetrahedron {
@@ -22,6 +49,21 @@ pub enum ExtendedTetrahedron {
     Triangle(Triangle3),
 }
 
+/// Where a point lies in a [`Tetrahedralization`], see [`Tetrahedralization::locate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location3 {
+    /// Strictly inside this casual tetrahedron.
+    InsideTet(TetHandle),
+    /// On the facet of a casual tetrahedron, given by the index of its half triangle.
+    OnFacet(usize),
+    /// On the edge between these two vertices.
+    OnEdge([VertexIdx; 2]),
+    /// At the position of the vertex with this index.
+    OnVertex(VertexIdx),
+    /// Outside the convex hull, beyond the hull facet of this conceptual tetrahedron.
+    OutsideHull(TetHandle),
+}
+
 /// A weighted 3D Delaunay Tetrahedralization with eps-approximation.
 ///
 /// ```
@@ -46,6 +88,7 @@ pub enum ExtendedTetrahedron {
 /// ```
 #[derive(Debug)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tetrahedralization {
     /// An artificial weight to make points be considered as regular (ie. not lying in a tetrahedrons circumsphere).
     ///
@@ -55,13 +98,49 @@ pub struct Tetrahedralization {
     vertices: Vec<Vertex3>,
     /// The weights of the vertices, `Some` if the vertices are weighted
     weights: Option<Vec<f64>>,
-
-    #[cfg(feature = "timing")]
-    pub(crate) time_hilbert: u128,
-    #[cfg(feature = "timing")]
-    time_walking: u128,
-    #[cfg(feature = "timing")]
-    time_inserting: u128,
+    /// The local origin, that heights are computed relative to.
+    ///
+    /// Chosen close to the centroid of the first inserted vertices, such that each vertex is translated exactly.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    origin: Vertex3,
+    /// A conceptual tetrahedron next to the last vertex inserted beyond the hull, to locate the next such vertex directly.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    hull_tet: Option<usize>,
+    /// The maximum number of tets a visibility walk visits, before falling back.
+    walk_cap: WalkCap,
+    /// What a locate does, when the visibility walk hits the cap or fails.
+    #[cfg_attr(feature = "serde", serde(default))]
+    walk_fallback: WalkFallback,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    walk_stats: WalkStats,
+    /// Whether the visibility walks start from the [`LocateGrid`], which is maintained during insertion.
+    #[cfg_attr(feature = "serde", serde(default))]
+    use_locate_grid: bool,
+    /// A tet near each cell of a uniform grid, built again on the next insertion after loading.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    locate_grid: LocateGrid<3>,
+    /// How the power sphere tests of the insertion are decided.
+    #[cfg_attr(feature = "serde", serde(default))]
+    predicate_mode: PredicateMode,
+    /// How the vertices are inserted.
+    #[cfg_attr(feature = "serde", serde(default = "bowyer_watson"))]
+    insertion_strategy: InsertionStrategy,
+    /// The most threads of the parallel methods, `None` for the current [rayon] pool.
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_threads: Option<usize>,
+    /// The pool of [`Self::max_threads`], built on the first parallel call.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    thread_pool: parallel::ThreadPool,
+    /// What happens to vertices at the position of an inserted vertex.
+    #[cfg_attr(feature = "serde", serde(default))]
+    duplicate_policy: DuplicatePolicy,
+    /// The power tests of the current insertion, that the floating point filters decided and failed on.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    filter_counts: FilterCounts,
 
     /// Indices of vertices that are inserted, i.e. not skipped due to epsilon
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
@@ -69,6 +148,28 @@ pub struct Tetrahedralization {
     /// Indices of vertices that are ignored, i.e. skipped due to epsilon
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     ignored_vertices: Vec<VertexIdx>,
+    /// Indices of vertices that are redundant, i.e. skipped due to their weight
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    redundant_vertices: Vec<VertexIdx>,
+    /// Indices of vertices that are duplicates, i.e. skipped as they coincide with an inserted vertex
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    duplicate_vertices: Vec<VertexIdx>,
+    /// The inserted vertex of each duplicate vertex
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    duplicate_of: BTreeMap<VertexIdx, VertexIdx>,
+    /// Advanced on every change of the tets, to detect stale [`TetHandle`]s.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    generation: u64,
+    /// The tets destroyed and created by the current insertion, `Some` only during [`Tetrahedralization::insert_vertex_tracked`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tracked_changes: Option<SimplexChanges>,
+    /// The observer of the insertions and flips, see [`Tetrahedralization::set_observer`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observer: Observer,
 }
 
 impl Default for Tetrahedralization {
@@ -77,6 +178,12 @@ impl Default for Tetrahedralization {
     }
 }
 
+/// The insertion strategy of tetrahedralizations serialized before it was configurable.
+#[cfg(feature = "serde")]
+const fn bowyer_watson() -> InsertionStrategy {
+    InsertionStrategy::BowyerWatson
+}
+
 /// Create a new [`Tetrahedralization`] from vertices with optional weights and epsilon.
 ///
 /// ## Example
@@ -126,14 +233,28 @@ impl Tetrahedralization {
             tds: TetDataStructure::new(),
             vertices: Vec::new(),
             weights: None,
-            #[cfg(feature = "timing")]
-            time_hilbert: 0,
-            #[cfg(feature = "timing")]
-            time_walking: 0,
-            #[cfg(feature = "timing")]
-            time_inserting: 0,
+            origin: [0.0; 3],
+            hull_tet: None,
+            walk_cap: WalkCap::Quarter,
+            walk_fallback: WalkFallback::LinearScan,
+            walk_stats: WalkStats::new(),
+            use_locate_grid: false,
+            locate_grid: LocateGrid::new(),
+            predicate_mode: PredicateMode::Exact,
+            insertion_strategy: InsertionStrategy::BowyerWatson,
+            max_threads: None,
+            thread_pool: parallel::ThreadPool::new(),
+            duplicate_policy: DuplicatePolicy::SkipAndMap,
+            #[cfg(feature = "metrics")]
+            filter_counts: FilterCounts::new(),
             used_vertices: Vec::new(),
             ignored_vertices: Vec::new(),
+            redundant_vertices: Vec::new(),
+            duplicate_vertices: Vec::new(),
+            duplicate_of: BTreeMap::new(),
+            generation: 0,
+            tracked_changes: None,
+            observer: Observer::NONE,
         }
     }
 
@@ -144,14 +265,28 @@ impl Tetrahedralization {
             tds: TetDataStructure::new(),
             vertices: Vec::with_capacity(capacity),
             weights: None,
-            #[cfg(feature = "timing")]
-            time_hilbert: 0,
-            #[cfg(feature = "timing")]
-            time_walking: 0,
-            #[cfg(feature = "timing")]
-            time_inserting: 0,
+            origin: [0.0; 3],
+            hull_tet: None,
+            walk_cap: WalkCap::Quarter,
+            walk_fallback: WalkFallback::LinearScan,
+            walk_stats: WalkStats::new(),
+            use_locate_grid: false,
+            locate_grid: LocateGrid::new(),
+            predicate_mode: PredicateMode::Exact,
+            insertion_strategy: InsertionStrategy::BowyerWatson,
+            max_threads: None,
+            thread_pool: parallel::ThreadPool::new(),
+            duplicate_policy: DuplicatePolicy::SkipAndMap,
+            #[cfg(feature = "metrics")]
+            filter_counts: FilterCounts::new(),
             used_vertices: Vec::new(),
             ignored_vertices: Vec::new(),
+            redundant_vertices: Vec::new(),
+            duplicate_vertices: Vec::new(),
+            duplicate_of: BTreeMap::new(),
+            generation: 0,
+            tracked_changes: None,
+            observer: Observer::NONE,
         }
     }
 
@@ -160,22 +295,59 @@ impl Tetrahedralization {
     }
 
     /// Gets the height for a vertex
+    ///
+    /// The height is computed relative to the local [`origin`](Self::origin),
+    /// which keeps it accurate for large coordinates.
     pub fn height(&self, v_idx: usize) -> f64 {
-        self.vertices[v_idx][0].powi(2)
-            + self.vertices[v_idx][1].powi(2)
-            + self.vertices[v_idx][2].powi(2)
+        let [x, y, z] = to_local(&self.vertices[v_idx], &self.origin);
+        x.powi(2) + y.powi(2) + z.powi(2)
             - self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
     }
 
-    /// The number of triangles, without the ones that have an connection to the dummy point.
-    pub fn num_casual_tets(&self) -> usize {
+    /// Update the local origin for the vertices starting at `first_new_idx`.
+    ///
+    /// Before the first tetrahedron exists, the origin is moved to the centroid of all vertices,
+    /// or to the center of their bounding box `bbox` if known.
+    /// Afterwards it falls back to zero, if a new vertex can not be translated exactly.
+    fn update_origin(&mut self, first_new_idx: VertexIdx, bbox: Option<[Vertex3; 2]>) {
+        if self.tds.num_tets() == 0 {
+            self.origin = bbox.map_or_else(
+                || local_origin(&self.vertices),
+                |bbox| bbox_origin(&self.vertices, bbox),
+            );
+        } else if !self.vertices[first_new_idx..]
+            .iter()
+            .all(|v| is_exact_translation(v, &self.origin))
+        {
+            #[cfg(feature = "logging")]
+            log::warn!("Vertex can not be translated exactly, falling back to the global origin");
+            self.origin = [0.0; 3];
+        }
+    }
+
+    /// The number of tetrahedra, without the ones that have an connection to the dummy point.
+    pub const fn num_casual_tets(&self) -> usize {
         self.tds().num_casual_tets()
     }
 
+    /// The number of tetrahedra, that have a connection to the dummy point.
+    pub const fn num_conceptual_tets(&self) -> usize {
+        self.tds().num_conceptual_tets()
+    }
+
+    /// The number of triangles on the convex hull, each of them is part of exactly one conceptual tet.
+    pub const fn num_hull_facets(&self) -> usize {
+        self.num_conceptual_tets()
+    }
+
     pub fn num_ignored_vertices(&self) -> usize {
         self.ignored_vertices.len()
     }
 
+    pub fn num_redundant_vertices(&self) -> usize {
+        self.redundant_vertices.len()
+    }
+
     pub const fn num_tets(&self) -> usize {
         self.tds.num_tets()
     }
@@ -188,6 +360,28 @@ impl Tetrahedralization {
         &self.tds
     }
 
+    /// Encode the tetrahedralization as a compact binary snapshot, e.g. to checkpoint a long build.
+    ///
+    /// The snapshot can only be loaded by a build of rita with the same version and features, see [`load_snapshot`](Self::load_snapshot).
+    ///
+    /// ## Errors
+    /// Returns an error if the encoding fails.
+    #[cfg(feature = "snapshot")]
+    pub fn save_snapshot(&self) -> HowResult<Vec<u8>> {
+        snapshot::save(SnapshotKind::Tetrahedralization, self)
+    }
+
+    /// Decode a tetrahedralization from a snapshot of [`save_snapshot`](Self::save_snapshot).
+    ///
+    /// The decoded structure is not validated, a snapshot of a sound structure stays sound.
+    ///
+    /// ## Errors
+    /// Returns an error if the bytes are not a snapshot of a tetrahedralization, or it can not be decoded.
+    #[cfg(feature = "snapshot")]
+    pub fn load_snapshot(bytes: &[u8]) -> HowResult<Self> {
+        snapshot::load(SnapshotKind::Tetrahedralization, bytes)
+    }
+
     /// Get the tetrahedra of the tetrahedralization as `Tetrahedron3`, i.e `[[f64; 3]; 4]`.
     ///
     /// Does not include conceptual tetrahedra, i.e. the convex hull faces
@@ -213,10 +407,298 @@ impl Tetrahedralization {
             .collect()
     }
 
+    /// Get the tetrahedra of the tetrahedralization as vertex indices.
+    ///
+    /// Same order as [`tets`](Self::tets), i.e. conceptual tetrahedra are skipped.
+    pub fn tet_indices(&self) -> Vec<[VertexIdx; 4]> {
+        (0..self.tds().num_tets())
+            .filter_map(|tet_idx| {
+                let tet = self.tds().get_tet(tet_idx).ok()?;
+                if tet.is_conceptual() {
+                    return None;
+                }
+                Some(tet.nodes().map(|node| node.idx().unwrap()))
+            })
+            .collect()
+    }
+
+    /// Get the edges of the casual tetrahedra as vertex indices, each undirected edge once with the smaller index first.
+    ///
+    /// The edges are sorted, as they are collected from all tets around them.
+    pub fn edges(&self) -> impl Iterator<Item = (VertexIdx, VertexIdx)> {
+        let mut edges = Vec::with_capacity(6 * self.tds().num_tets());
+        for tet_idx in 0..self.tds().num_tets() {
+            let Ok(tet) = self.tds().get_tet(tet_idx) else {
+                continue;
+            };
+            if tet.is_conceptual() || tet.should_del() {
+                continue;
+            }
+
+            let v_idxs = tet.nodes().map(|node| node.idx().unwrap());
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    edges.push((v_idxs[i].min(v_idxs[j]), v_idxs[i].max(v_idxs[j])));
+                }
+            }
+        }
+
+        edges.sort_unstable();
+        edges.dedup();
+        edges.into_iter()
+    }
+
+    /// Get the adjacency graph of the tetrahedralization, with the [`edges`](Self::edges) weighted by their length.
+    ///
+    /// There is a node per vertex, at its index and with its position as weight,
+    /// so vertices that are not part of the tetrahedralization are isolated nodes.
+    #[cfg(feature = "petgraph")]
+    pub fn to_graph(&self) -> petgraph::graph::UnGraph<Vertex3, f64> {
+        use petgraph::graph::{NodeIndex, UnGraph};
+
+        let mut graph = UnGraph::with_capacity(self.vertices.len(), 0);
+        for &v in &self.vertices {
+            graph.add_node(v);
+        }
+
+        for (v0, v1) in self.edges() {
+            let [a, b] = [self.vertices[v0], self.vertices[v1]];
+            let length =
+                ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt();
+            graph.add_edge(NodeIndex::new(v0), NodeIndex::new(v1), length);
+        }
+
+        graph
+    }
+
+    /// Get the triangles on the convex hull as vertex indices, i.e. the casual facets of the conceptual tetrahedra.
+    ///
+    /// The triangles are oriented consistently, as the facets of their conceptual tetrahedra.
+    /// Whether that is clockwise or counter-clockwise seen from outside depends on the sign convention of the predicates.
+    pub fn hull_facets(&self) -> Vec<[VertexIdx; 3]> {
+        (0..self.tds().num_tets())
+            .filter_map(|tet_idx| {
+                let tet = self.tds().get_tet(tet_idx).ok()?;
+                let tri = tet
+                    .half_triangles()
+                    .into_iter()
+                    .find(|tri| tri.opposite_node().is_conceptual())?;
+                let [node0, node1, node2] = tri.nodes();
+                Some([node0.idx()?, node1.idx()?, node2.idx()?])
+            })
+            .collect()
+    }
+
+    /// Get the casual tetrahedra as a simplicial complex, together with its boundary operators.
+    pub fn boundary_matrices(&self) -> BoundaryMatrices {
+        BoundaryMatrices::from_tets(self.tet_indices())
+    }
+
+    /// The hull facets, oriented counter-clockwise when seen from outside.
+    fn outward_hull_facets(&self) -> Vec<[VertexIdx; 3]> {
+        // the orientation of the tets depends on the predicates, so orient each facet away from its inner vertex
+        (0..self.tds().num_tets())
+            .filter_map(|tet_idx| {
+                let tet = self.tds().get_tet(tet_idx).ok()?;
+                let tri = tet
+                    .half_triangles()
+                    .into_iter()
+                    .find(|tri| tri.opposite_node().is_conceptual())?;
+                let [node0, node1, node2] = tri.nodes();
+                let [a, b, c] = [node0.idx()?, node1.idx()?, node2.idx()?];
+                let inner = self.vertices[tri.opposite().opposite_node().idx()?];
+
+                let [ab, ac, a_inner] =
+                    [self.vertices[b], self.vertices[c], inner].map(|v| sub(v, self.vertices[a]));
+                let faces_inwards = dot(cross(ab, ac), a_inner) > 0.0;
+                Some(if faces_inwards { [a, c, b] } else { [a, b, c] })
+            })
+            .collect()
+    }
+
+    /// Write the convex hull as an OBJ mesh, with its facets facing outwards, see [`io`](crate::io).
+    ///
+    /// Only the vertices on the hull are written.
+    ///
+    /// ## Errors
+    /// Returns an error if the writer fails.
+    pub fn write_hull_obj(&self, writer: &mut impl core::fmt::Write) -> HowResult<()> {
+        io::write_obj(
+            writer,
+            self.vertices.len(),
+            |v_idx| self.vertices[v_idx],
+            &self.outward_hull_facets(),
+        )
+    }
+
+    /// Get all vertices with their weights, and the convex hull facing outwards, to be written as a PLY file.
+    ///
+    /// See [`io::write_ply`], the vertices keep their indices, so they can be inserted again with their weights.
+    pub fn to_ply(&self) -> PlyData {
+        PlyData {
+            vertices: self.vertices.clone(),
+            weights: self.weights.clone(),
+            faces: self.outward_hull_facets(),
+        }
+    }
+
+    /// Get all vertices with their weights, and the casual tetrahedra, to be written as the `.node` and `.ele` files of TetGen.
+    ///
+    /// See [`io::write_node_ele`], the vertices keep their indices.
+    pub fn to_node_ele(&self) -> NodeEleData<3, 4> {
+        NodeEleData {
+            vertices: self.vertices.clone(),
+            weights: self.weights.clone(),
+            elements: self.tet_indices(),
+        }
+    }
+
+    /// Get the quality metrics of the casual tetrahedra, in the same order as [`tets`](Self::tets).
+    pub fn tet_qualities(&self) -> Vec<TetQuality> {
+        self.tets().into_iter().map(tet_quality).collect()
+    }
+
+    /// Get the histograms of the quality metrics of the casual tetrahedra, each with `num_bins` bins.
+    pub fn quality_histograms(&self, num_bins: usize) -> QualityHistograms {
+        QualityHistograms::from_tets(&self.tet_qualities(), num_bins)
+    }
+
     pub const fn vertices(&self) -> &Vec<Vertex3> {
         &self.vertices
     }
 
+    /// Get the weights.
+    #[must_use]
+    pub const fn weights(&self) -> &Option<Vec<f64>> {
+        &self.weights
+    }
+
+    /// Get the local origin, that heights are computed relative to.
+    ///
+    /// The vertices themselves are kept in their input coordinates.
+    #[must_use]
+    pub const fn origin(&self) -> Vertex3 {
+        self.origin
+    }
+
+    /// Get the maximum number of tets a visibility walk visits, before falling back, see [`WalkFallback`].
+    #[must_use]
+    pub const fn walk_cap(&self) -> WalkCap {
+        self.walk_cap
+    }
+
+    /// Set the maximum number of tets a visibility walk visits, before falling back, see [`WalkFallback`].
+    pub const fn set_walk_cap(&mut self, walk_cap: WalkCap) {
+        self.walk_cap = walk_cap;
+    }
+
+    /// Get what a locate does, when the visibility walk hits the cap or fails.
+    #[must_use]
+    pub const fn walk_fallback(&self) -> WalkFallback {
+        self.walk_fallback
+    }
+
+    /// Set what a locate does, when the visibility walk hits the cap or fails.
+    pub const fn set_walk_fallback(&mut self, walk_fallback: WalkFallback) {
+        self.walk_fallback = walk_fallback;
+    }
+
+    /// Get the most threads of the parallel methods, see [`Self::set_max_threads`].
+    #[must_use]
+    pub const fn max_threads(&self) -> Option<usize> {
+        self.max_threads
+    }
+
+    /// Set the most threads of the parallel methods, e.g. [`Self::par_is_regular`], to bound their CPU usage.
+    ///
+    /// With `Some`, the calls run on a thread pool with that many threads, built on the first call and kept until the
+    /// limit is set again. With `None`, the default, they run on the global pool of [rayon], or on the pool of a
+    /// surrounding `ThreadPool::install`, to share one pool.
+    /// Without the `parallel` feature, they run on the calling thread either way.
+    #[cfg_attr(not(feature = "parallel"), allow(clippy::missing_const_for_fn))]
+    pub fn set_max_threads(&mut self, max_threads: Option<usize>) {
+        self.max_threads = max_threads;
+        self.thread_pool = parallel::ThreadPool::new();
+    }
+
+    /// Get whether the visibility walks start from a uniform grid of recently inserted tets.
+    #[must_use]
+    pub const fn uses_locate_grid(&self) -> bool {
+        self.use_locate_grid
+    }
+
+    /// Set whether the visibility walks start from a uniform grid of recently inserted tets.
+    ///
+    /// The grid remembers the tet last inserted in each of its cells, so walks start close to their target even
+    /// without a spatially sorted insertion order, e.g. for single insertions in random order or many [`Self::locate`]
+    /// queries. It has about a cell per four vertices and is built again whenever the number of vertices doubled.
+    pub fn set_locate_grid(&mut self, use_locate_grid: bool) {
+        self.use_locate_grid = use_locate_grid;
+        if !use_locate_grid {
+            self.locate_grid.clear();
+        }
+    }
+
+    /// Get how the power sphere tests of the insertion are decided.
+    #[must_use]
+    pub const fn predicate_mode(&self) -> PredicateMode {
+        self.predicate_mode
+    }
+
+    /// Set how the power sphere tests of the insertion are decided.
+    ///
+    /// With [`PredicateMode::FastInexact`], the tetrahedralization built afterwards is not guaranteed to be regular.
+    pub const fn set_predicate_mode(&mut self, predicate_mode: PredicateMode) {
+        self.predicate_mode = predicate_mode;
+    }
+
+    /// Get how the vertices are inserted.
+    #[must_use]
+    pub const fn insertion_strategy(&self) -> InsertionStrategy {
+        self.insertion_strategy
+    }
+
+    /// Set how the vertices are inserted, both give the same regular tetrahedralization.
+    ///
+    /// Defaults to [`InsertionStrategy::BowyerWatson`]. With [`InsertionStrategy::Flips`], the containing tet is split
+    /// and the facets around the vertex are flipped (2-3, 3-2 and, for weighted vertices, 4-1) until they are regular.
+    /// Vertices beyond the hull, and facets no flip can make regular, are finished by a Bowyer-Watson insertion.
+    pub const fn set_insertion_strategy(&mut self, insertion_strategy: InsertionStrategy) {
+        self.insertion_strategy = insertion_strategy;
+    }
+
+    /// Get what happens to vertices at the position of an inserted vertex.
+    #[must_use]
+    pub const fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Set what happens to vertices at the position of an inserted vertex, only affects later insertions.
+    pub const fn set_duplicate_policy(&mut self, duplicate_policy: DuplicatePolicy) {
+        self.duplicate_policy = duplicate_policy;
+    }
+
+    /// Get the statistics of the visibility walks, recorded since construction.
+    #[must_use]
+    pub const fn walk_stats(&self) -> &WalkStats {
+        &self.walk_stats
+    }
+
+    /// Get the statistics of the build, e.g. peak buffer sizes and counts of flips, recorded since construction, see [`BuildStats`].
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub const fn stats(&self) -> BuildStats {
+        self.tds.stats
+    }
+
+    /// Reserve the scratch buffers of the insertion, for cavities with up to `num_boundary_tris` boundary triangles.
+    ///
+    /// The buffers are reused across insertions and grow on demand, so this is only a hint, e.g. from the
+    /// `peak_bw_boundary_tris` of a previous build of similar data.
+    pub fn reserve_insertion_scratch(&mut self, num_boundary_tris: usize) {
+        self.tds.reserve_bw_scratch(num_boundary_tris);
+    }
+
     /// Gets extended tetrahedron from index
     pub fn get_tet_as_extended(&self, tet_idx: usize) -> HowResult<ExtendedTetrahedron> {
         let [node0, node1, node2, node3] = self.tds().get_tet(tet_idx)?.nodes();
@@ -279,13 +761,70 @@ impl Tetrahedralization {
                 ExtendedTetrahedron::Tetrahedron([v0, v1, v2, v3])
             }
             (_, _, _, _) => {
-                return Err(anyhow::Error::msg("Case should not happen"));
+                return Err(anyhow::Error::new(RitaError::InvalidState(
+                    "unexpected tetrahedron case",
+                )));
             }
         };
 
         Ok(ext_tri)
     }
 
+    /// The vertex indices of a casual tet, to pass its vertices to the SOS predicates by reference into `self.vertices`.
+    ///
+    /// Geogram breaks ties by the addresses of the vertices, so only references into one buffer perturb the vertices
+    /// consistently across tets, copies would be ordered by their place on the stack.
+    fn casual_tet_v_idxs(&self, tet_idx: usize) -> HowResult<[VertexIdx; 4]> {
+        match self.tds().get_tet(tet_idx)?.nodes() {
+            [
+                VertexNode::Casual(v_idx0),
+                VertexNode::Casual(v_idx1),
+                VertexNode::Casual(v_idx2),
+                VertexNode::Casual(v_idx3),
+            ] => Ok([v_idx0, v_idx1, v_idx2, v_idx3]),
+            _ => Err(anyhow::Error::new(RitaError::ConceptualSimplex)),
+        }
+    }
+
+    /// Describe a tetrahedron for humans, e.g. for logs and bug reports.
+    ///
+    /// Lists the nodes with their coordinates and weights, the neighboring tetrahedra and the flags.
+    pub fn describe_tet(&self, tet_idx: usize) -> HowResult<String> {
+        let tet = self.tds().get_tet(tet_idx)?;
+        let kind = if tet.is_conceptual() {
+            "conceptual"
+        } else {
+            "casual"
+        };
+
+        let mut description = String::new();
+        write!(description, "Tetrahedron {tet_idx} ({kind}")?;
+        if tet.should_del() {
+            write!(description, ", to delete")?;
+        }
+        if tet.should_keep() {
+            write!(description, ", to keep")?;
+        }
+        writeln!(description, ")")?;
+
+        for node in tet.nodes() {
+            write!(description, "  {node}")?;
+            if let VertexNode::Casual(v_idx) = node {
+                let [x, y, z] = self.vertices[v_idx];
+                write!(description, " at [{x}, {y}, {z}]")?;
+                if let Some(weights) = &self.weights {
+                    write!(description, " with weight {}", weights[v_idx])?;
+                }
+            }
+            writeln!(description)?;
+        }
+
+        let [n0, n1, n2, n3] = tet.half_triangles().map(|tri| tri.opposite().tet().idx());
+        write!(description, "  neighbors: {n0}, {n1}, {n2}, {n3}")?;
+
+        Ok(description)
+    }
+
     pub fn is_v_in_sphere(&self, v_idx: usize, tet_idx: usize, strict: bool) -> HowResult<bool> {
         let p = self.vertices[v_idx];
 
@@ -293,8 +832,11 @@ impl Tetrahedralization {
 
         let in_sphere = match ext_tet {
             // TODO: why do we need to invert gp's in sphere, compared to robust's, they should have the same signs for the same cases
-            ExtendedTetrahedron::Tetrahedron([a, b, c, d]) => {
-                -predicates::in_sphere_3d_SOS(&a, &b, &c, &d, &p)
+            ExtendedTetrahedron::Tetrahedron(_) => {
+                let [a, b, c, d] = self
+                    .casual_tet_v_idxs(tet_idx)?
+                    .map(|idx| &self.vertices[idx]);
+                -predicates::in_sphere_3d_SOS(a, b, c, d, &self.vertices[v_idx])
             }
             ExtendedTetrahedron::Triangle([a, b, c]) => -predicates::orient_3d(&a, &b, &c, &p),
         };
@@ -306,7 +848,31 @@ impl Tetrahedralization {
         }
     }
 
-    fn is_v_in_powersphere(&self, v_idx: usize, tet_idx: usize, strict: bool) -> HowResult<bool> {
+    /// Whether the vertex is at the position of one of the tet's nodes, which the predicates do not decide consistently.
+    fn is_v_on_node(&self, v_idx: usize, tet_idx: usize) -> HowResult<bool> {
+        Ok(self.node_at_v(v_idx, tet_idx)?.is_some())
+    }
+
+    /// The vertex of the tet's nodes at the position of the vertex, if any.
+    fn node_at_v(&self, v_idx: usize, tet_idx: usize) -> HowResult<Option<VertexIdx>> {
+        let v = self.vertices[v_idx];
+        Ok(self
+            .tds()
+            .get_tet(tet_idx)?
+            .nodes()
+            .iter()
+            .filter_map(VertexNode::idx)
+            .find(|&idx| self.vertices[idx] == v))
+    }
+
+    /// Check for a vertex, if it lies inside the power sphere of a tet, decided with the given predicates.
+    fn is_v_in_powersphere(
+        &self,
+        v_idx: usize,
+        tet_idx: usize,
+        strict: bool,
+        predicate_mode: PredicateMode,
+    ) -> HowResult<bool> {
         let p = self.vertices[v_idx];
         let h_p = self.height(v_idx);
 
@@ -314,17 +880,50 @@ impl Tetrahedralization {
 
         let in_sphere = match ext_tet {
             // TODO: why do we need to invert gp's in sphere, compared to robust's, they should have the same signs for the same cases
-            ExtendedTetrahedron::Tetrahedron([a, b, c, d]) => {
-                let [h_a, h_b, h_c, h_d] = self
-                    .tds()
-                    .get_tet(tet_idx)?
-                    .nodes()
-                    .map(|n| self.height(n.idx().unwrap()));
-
-                predicates::orient_3dlifted_SOS(&a, &b, &c, &d, &p, h_a, h_b, h_c, h_d, h_p)
+            ExtendedTetrahedron::Tetrahedron(_) => {
+                let v_idxs = self.casual_tet_v_idxs(tet_idx)?;
+                let [a, b, c, d] = v_idxs.map(|idx| &self.vertices[idx]);
+                let [h_a, h_b, h_c, h_d] = v_idxs.map(|idx| self.height(idx));
+
+                let (sign, _decided) = predicate_mode.orient_3dlifted_decided(
+                    a,
+                    b,
+                    c,
+                    d,
+                    &self.vertices[v_idx],
+                    h_a,
+                    h_b,
+                    h_c,
+                    h_d,
+                    h_p,
+                );
+                #[cfg(feature = "metrics")]
+                self.filter_counts.record(_decided);
+                sign
             }
             // if the triangle is a line segment, then the power sphere is a sphere with infinite radius and we can use a orientation test
-            ExtendedTetrahedron::Triangle([a, b, c]) => -predicates::orient_3d(&a, &b, &c, &p),
+            ExtendedTetrahedron::Triangle([a, b, c]) => {
+                let orientation = -predicates::orient_3d(&a, &b, &c, &p);
+                if orientation == 0.0 {
+                    // in the plane of the hull facet, the power sphere degenerates to the power circle of the facet,
+                    // which is shared by the casual tet on the other side of the facet
+                    let hull_tri = self
+                        .tds()
+                        .get_tet(tet_idx)?
+                        .half_triangles()
+                        .into_iter()
+                        .find(|tri| tri.opposite_node().is_conceptual())
+                        .ok_or(RitaError::InvalidState("conceptual tet without hull facet"))?;
+
+                    return self.is_v_in_powersphere(
+                        v_idx,
+                        hull_tri.opposite().tet().idx(),
+                        strict,
+                        predicate_mode,
+                    );
+                }
+                orientation
+            }
         };
 
         if strict {
@@ -339,43 +938,91 @@ impl Tetrahedralization {
         let _ = (v_idx, tet_idx);
 
         #[cfg(feature = "wasm")]
-        return Err(anyhow::Error::msg(
-            "Epsilon power sphere is not supported in wasm (robust predicates are unweighted).",
-        ));
+        return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
 
         #[cfg(not(feature = "wasm"))]
         {
-            let p = self.vertices[v_idx];
-
-            let h_p = if let Some(epsilon) = self.epsilon {
-                self.height(v_idx) + epsilon
-            } else {
-                panic!("Epsilon not set!");
-            };
+            let epsilon = self.epsilon.ok_or(RitaError::EpsilonNotSet { v_idx })?;
+            let h_p = self.height(v_idx) + epsilon;
 
             let ext_tet = self.get_tet_as_extended(tet_idx)?;
 
             match ext_tet {
-                ExtendedTetrahedron::Tetrahedron([a, b, c, d]) => {
-                    let [h_a, h_b, h_c, h_d] = self
-                        .tds()
-                        .get_tet(tet_idx)?
-                        .nodes()
-                        .map(|n| self.height(n.idx().unwrap()));
+                ExtendedTetrahedron::Tetrahedron(_) => {
+                    let v_idxs = self.casual_tet_v_idxs(tet_idx)?;
+                    let [a, b, c, d] = v_idxs.map(|idx| &self.vertices[idx]);
+                    let [h_a, h_b, h_c, h_d] = v_idxs.map(|idx| self.height(idx));
 
                     let in_eps_circle = predicates::orient_3dlifted_SOS(
-                        &a, &b, &c, &d, &p, h_a, h_b, h_c, h_d, h_p,
+                        a,
+                        b,
+                        c,
+                        d,
+                        &self.vertices[v_idx],
+                        h_a,
+                        h_b,
+                        h_c,
+                        h_d,
+                        h_p,
                     );
 
                     Ok(in_eps_circle > 0.0)
                 }
-                ExtendedTetrahedron::Triangle(_) => Err(anyhow::Error::msg(
-                    "Epsilon power circle test not allowed for conceptual triangles yet!",
-                )),
+                ExtendedTetrahedron::Triangle(_) => {
+                    Err(anyhow::Error::new(RitaError::ConceptualSimplex))
+                }
             }
         }
     }
 
+    /// Skip vertices that are not in power sphere by epsilon (i.e. above the hyperplane) inside the current convex hull,
+    /// and vertices that are closer than epsilon to the lifted hull beyond it.
+    fn is_v_ignored_by_eps(&self, v_idx: usize, tet_idx: usize) -> HowResult<bool> {
+        if self.tds().get_tet(tet_idx)?.is_casual() {
+            Ok(!self.is_v_in_eps_powersphere(v_idx, tet_idx)?)
+        } else {
+            self.is_v_near_lifted_hull(v_idx, tet_idx)
+        }
+    }
+
+    /// Check if a vertex beyond the hull is closer than `self.epsilon` to the lifted tetrahedralization, the counterpart
+    /// of [`Self::is_v_in_eps_powersphere`] for the conceptual tet `tet_idx`.
+    ///
+    /// The lifted hull continues upwards from the lifted hull facet of the tet, towards the point at infinity.
+    /// So the distance is measured from the lifted vertex to the point of the lifted facet next to it, only horizontally
+    /// if the vertex is above that point. Like inside the hull, this skips vertices close to a hull vertex, but not the
+    /// ones close to the middle of a large facet, as the lifted facet is far above them.
+    /// Returns an error if `self.epsilon` is not set.
+    fn is_v_near_lifted_hull(&self, v_idx: usize, tet_idx: usize) -> HowResult<bool> {
+        let epsilon = self.epsilon.ok_or(RitaError::EpsilonNotSet { v_idx })?;
+
+        let hull_facet = self
+            .tds()
+            .get_tet(tet_idx)?
+            .half_triangles()
+            .into_iter()
+            .find(|tri| tri.opposite_node().is_conceptual())
+            .ok_or(RitaError::InvalidState("Conceptual tet without hull facet"))?;
+        let facet = hull_facet
+            .nodes()
+            .map(|node| node.idx().ok_or(RitaError::ConceptualSimplex));
+        let facet = [facet[0]?, facet[1]?, facet[2]?];
+
+        let p = self.vertices[v_idx];
+        let lambdas = closest_on_triangle(facet.map(|idx| self.vertices[idx]), p);
+        let q: [f64; 3] = core::array::from_fn(|i| {
+            (0..3)
+                .map(|j| lambdas[j] * self.vertices[facet[j]][i])
+                .sum()
+        });
+        let h_q: f64 = (0..3).map(|j| lambdas[j] * self.height(facet[j])).sum();
+
+        let below = (h_q - self.height(v_idx)).max(0.0);
+        let dist_sq = (0..3).map(|i| (p[i] - q[i]).powi(2)).sum::<f64>() + below * below;
+
+        Ok(dist_sq < epsilon * epsilon)
+    }
+
     fn is_tet_flat(&self, tet_idx: usize) -> HowResult<bool> {
         let ext_tri = self.get_tet_as_extended(tet_idx)?;
 
@@ -428,31 +1075,116 @@ impl Tetrahedralization {
                 continue;
             }
 
-            if self.is_v_in_powersphere(v_idx, curr_tet_idx, false)? {
+            // contained geometrically, as a redundant vertex is in no power sphere
+            if self.is_v_on_node(v_idx, curr_tet_idx)?
+                || self
+                    .classify_point(curr_tet_idx, &self.vertices[v_idx])?
+                    .is_some()
+            {
                 return Ok(curr_tet_idx);
             }
         }
 
-        Err(anyhow::Error::msg("Could not find sphere containing point"))
+        Err(anyhow::Error::new(RitaError::LocateFailed))
     }
 
-    fn locate_vis_walk(&self, v_idx: usize, starting_tet_idx: usize) -> HowResult<usize> {
+    /// Locates the vertex after the visibility walk failed, as the [`WalkFallback`] says.
+    fn locate_fallback(&mut self, v_idx: usize) -> HowResult<usize> {
+        if let WalkFallback::Restarts {
+            num_restarts,
+            num_samples,
+        } = self.walk_fallback
+        {
+            let mut rng = SplitMix64::new(v_idx as u64);
+            for _ in 0..num_restarts {
+                self.walk_stats.num_restarts += 1;
+                let starting_tet_idx = self.nearest_sampled_tet(v_idx, num_samples, &mut rng)?;
+                if let Ok(tet_idx) = self.locate_vis_walk(v_idx, starting_tet_idx) {
+                    return Ok(tet_idx);
+                }
+            }
+        }
+
+        self.walk_stats.num_fallbacks += 1;
+        self.walk_check_all(v_idx)
+    }
+
+    /// The tet nearest to the vertex among `num_samples` random ones, by the distance to their closest casual node.
+    fn nearest_sampled_tet(
+        &self,
+        v_idx: usize,
+        num_samples: usize,
+        rng: &mut SplitMix64,
+    ) -> HowResult<usize> {
         let v = self.vertices[v_idx];
+        let mut nearest = (f64::INFINITY, 0);
+
+        for _ in 0..num_samples.max(1) {
+            let tet_idx = rng.below(self.tds().num_tets());
+            let dist_sq = self
+                .tds()
+                .get_tet(tet_idx)?
+                .nodes()
+                .iter()
+                .filter_map(|node| node.idx())
+                .map(|idx| {
+                    (0..3)
+                        .map(|i| (self.vertices[idx][i] - v[i]).powi(2))
+                        .sum::<f64>()
+                })
+                .fold(f64::INFINITY, f64::min);
+            if dist_sq < nearest.0 {
+                nearest = (dist_sq, tet_idx);
+            }
+        }
+
+        Ok(nearest.1)
+    }
+
+    /// Walks towards the vertex, visiting at most as many tets as the [`WalkCap`] allows, and records the walk.
+    fn locate_vis_walk(&mut self, v_idx: usize, starting_tet_idx: usize) -> HowResult<usize> {
+        let tets_visitable = self
+            .walk_cap
+            .max_visits(self.tds().num_tets(), &self.walk_stats);
+        let (reached, num_visited) =
+            self.walk_to_point(&self.vertices[v_idx], starting_tet_idx, tets_visitable)?;
+        self.walk_stats.record(num_visited, reached.is_none());
+        #[cfg(feature = "metrics")]
+        self.tds.stats.record_walk(num_visited);
+
+        match reached {
+            Some(tet_idx)
+                if self.is_v_on_node(v_idx, tet_idx)?
+                    || self.is_v_in_sphere(v_idx, tet_idx, false)? =>
+            {
+                Ok(tet_idx)
+            }
+            _ => Err(anyhow::Error::new(RitaError::LocateFailed)),
+        }
+    }
 
+    /// Walks from the starting tet towards the point, until no facet of the current tet separates them.
+    ///
+    /// Returns the reached tet, or `None` if more than `tets_visitable` tets were visited, and the number of visited tets.
+    fn walk_to_point(
+        &self,
+        v: &Vertex3,
+        starting_tet_idx: usize,
+        tets_visitable: usize,
+    ) -> HowResult<(Option<usize>, usize)> {
         let mut curr_tet_idx = starting_tet_idx;
         let starting_tet = self.tds().get_tet(curr_tet_idx)?;
         let mut tris = starting_tet.half_triangles().to_vec();
 
         let mut side = 0;
         let mut num_visited = 0;
-        let tets_visitable = self.tds().num_tets() >> 2;
 
         loop {
             if num_visited > tets_visitable {
-                break Err(anyhow::Error::msg("Could not find sphere containing point"));
+                break Ok((None, num_visited));
             }
 
-            if let Some(tri) = self.choose_tri(&tris, &v) {
+            if let Some(tri) = self.choose_tri(&tris, v) {
                 num_visited += 1;
 
                 let opp_tri = tri.opposite();
@@ -466,78 +1198,584 @@ impl Tetrahedralization {
                 tris.push(hedges[(2 + side) % 3].neighbor().tri());
 
                 side = (side + 1) % 3;
-            } else if self.is_v_in_sphere(v_idx, curr_tet_idx, false)? {
-                break Ok(curr_tet_idx);
             } else {
-                break Err(anyhow::Error::msg("Could not find sphere containing point"));
+                break Ok((Some(curr_tet_idx), num_visited));
             }
         }
     }
 
-    /// Inserts point using Bowyer Watson method
-    fn insert_bw(&mut self, v_idx: usize, first_tet_idx: usize) -> HowResult<Vec<usize>> {
-        self.tds.bw_start(first_tet_idx)?;
+    /// Locate an arbitrary point, e.g. for point-in-mesh queries after building.
+    ///
+    /// Classifies whether the point is inside a tet, on a facet, an edge or a vertex, or outside the convex hull.
+    /// Falls back to checking all tets, if the visibility walk does not reach the point.
+    pub fn locate(&self, point: Vertex3) -> HowResult<Location3> {
+        let num_tets = self.tds().num_tets();
+        if num_tets == 0 {
+            return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+        }
 
-        while let Some(tet_idx) = self.tds.bw_tets_to_check() {
-            if self.is_v_in_powersphere(v_idx, tet_idx, false)? {
-                self.tds.bw_rem_tet(tet_idx);
-            } else {
-                self.tds.bw_keep_tetra(tet_idx)?;
+        let starting_tet_idx = self.walk_start(&point, num_tets - 1);
+        if let (Some(tet_idx), _) = self.walk_to_point(&point, starting_tet_idx, num_tets)? {
+            if let Some(location) = self.classify_point(tet_idx, &point)? {
+                return Ok(location);
             }
         }
 
-        let node = VertexNode::Casual(v_idx);
-        self.tds.bw_insert_node(node)
-    }
-
+        for tet_idx in 0..num_tets {
+            if let Some(location) = self.classify_point(tet_idx, &point)? {
+                return Ok(location);
+            }
+        }
+
+        Err(anyhow::Error::new(RitaError::LocateFailed))
+    }
+
+    /// Interpolate per vertex `values` at an arbitrary point, via its barycentric coordinates in the containing tet.
+    ///
+    /// The `values` are indexed like the vertices.
+    /// Returns `None` for points outside the convex hull, or if a vertex of the containing tet has no value.
+    pub fn interpolate(&self, point: Vertex3, values: &[f64]) -> Option<f64> {
+        let tet_idx = match self.locate(point).ok()? {
+            Location3::InsideTet(handle) => handle.idx(),
+            Location3::OnFacet(half_tri_idx) => half_tri_idx / 4,
+            Location3::OnEdge([v_idx0, v_idx1]) => {
+                // linear along the edge, as any tet of the edge would weigh its other vertices with zero
+                let [a, b] = [self.vertices[v_idx0], self.vertices[v_idx1]];
+                let ab: Vertex3 = core::array::from_fn(|i| b[i] - a[i]);
+                let ap: Vertex3 = core::array::from_fn(|i| point[i] - a[i]);
+                let t = (0..3).map(|i| ab[i] * ap[i]).sum::<f64>()
+                    / (0..3).map(|i| ab[i] * ab[i]).sum::<f64>();
+
+                return Some((1.0 - t) * values.get(v_idx0)? + t * values.get(v_idx1)?);
+            }
+            Location3::OnVertex(v_idx) => return values.get(v_idx).copied(),
+            Location3::OutsideHull(_) => return None,
+        };
+
+        let [n0, n1, n2, n3] = self.tds().get_tet(tet_idx).ok()?.nodes();
+        let v_idxs = [n0.idx()?, n1.idx()?, n2.idx()?, n3.idx()?];
+        let lambdas = barycentric_3d(v_idxs.map(|v_idx| self.vertices[v_idx]), point);
+
+        v_idxs
+            .into_iter()
+            .zip(lambdas)
+            .map(|(v_idx, lambda)| values.get(v_idx).map(|value| lambda * value))
+            .sum()
+    }
+
+    /// Freeze the tetrahedralization into a read-only [`CompactTetrahedralization`], optimized for queries.
+    ///
+    /// The vertices are copied and keep their indices, the tets are renumbered, see [`crate::frozen`].
+    #[must_use]
+    pub fn freeze(&self) -> CompactTetrahedralization {
+        CompactTetrahedralization::new(self.vertices.clone(), &self.tet_indices())
+    }
+
+    /// Classify where the point lies in the tet, `None` if it is not contained.
+    fn classify_point(&self, tet_idx: usize, point: &Vertex3) -> HowResult<Option<Location3>> {
+        let tet = self.tds().get_tet(tet_idx)?;
+
+        if let Some(v_idx) = tet
+            .nodes()
+            .into_iter()
+            .filter_map(|node| node.idx())
+            .find(|&v_idx| self.vertices[v_idx] == *point)
+        {
+            return Ok(Some(Location3::OnVertex(v_idx)));
+        }
+
+        let mut on_tris = Vec::new();
+        for tri in tet.half_triangles() {
+            let [node0, node1, node2] = tri.nodes();
+            if let (
+                VertexNode::Casual(v_idx0),
+                VertexNode::Casual(v_idx1),
+                VertexNode::Casual(v_idx2),
+            ) = (node0, node1, node2)
+            {
+                let orientation = -predicates::orient_3d(
+                    &self.vertices[v_idx0],
+                    &self.vertices[v_idx1],
+                    &self.vertices[v_idx2],
+                    point,
+                );
+
+                // a conceptual tet only contains the points strictly beyond its hull facet
+                if tet.is_conceptual() {
+                    return Ok(
+                        (orientation > 0.0).then_some(Location3::OutsideHull(self.handle(tet_idx)))
+                    );
+                }
+
+                if orientation < 0.0 {
+                    return Ok(None);
+                } else if orientation == 0.0 {
+                    on_tris.push(tri);
+                }
+            }
+        }
+
+        let location = match on_tris.as_slice() {
+            [] => Location3::InsideTet(self.handle(tet_idx)),
+            [tri] => Location3::OnFacet(tri.idx()),
+            [tri0, tri1, ..] => {
+                let nodes1 = tri1.nodes();
+                let mut edge = tri0
+                    .nodes()
+                    .into_iter()
+                    .filter(|node| nodes1.contains(node))
+                    .filter_map(|node| node.idx());
+                Location3::OnEdge([edge.next().unwrap(), edge.next().unwrap()])
+            }
+        };
+
+        Ok(Some(location))
+    }
+
+    /// Fast path to locate a vertex beyond the hull, e.g. for inputs sorted along an axis.
+    ///
+    /// Checks whether the vertex sees the hull face of the last hull tetrahedron or one of its neighbors along the hull,
+    /// if so, the insertion starts there directly instead of walking through the tetrahedralization.
+    fn locate_beyond_hull(&self, v_idx: usize) -> Option<usize> {
+        let tet = self.tds().get_tet(self.hull_tet?).ok()?;
+        if !tet.is_conceptual() || tet.should_del() {
+            return None;
+        }
+
+        let neighbors = tet
+            .half_triangles()
+            .into_iter()
+            .filter(HalfTriIterator::is_conceptual)
+            .map(|tri| tri.opposite().tet().idx());
+
+        core::iter::once(tet.idx())
+            .chain(neighbors)
+            .find(|&tet_idx| {
+                self.is_v_in_powersphere(v_idx, tet_idx, true, self.predicate_mode)
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Inserts point using Bowyer Watson method, the new tets are in [`TetDataStructure::bw_added_tets`].
+    ///
+    /// Tets that already contain the vertex, left by an incomplete [flip insertion](Self::insert_flips), are removed as well.
+    fn insert_bw(&mut self, v_idx: usize, first_tet_idx: usize) -> HowResult<()> {
+        let node = VertexNode::Casual(v_idx);
+        let num_removed_before = self.tds.bw_removed_tets().len();
+        self.tds.bw_start(first_tet_idx)?;
+
+        loop {
+            while let Some(tet_idx) = self.tds.bw_tets_to_check() {
+                if self.tds().get_tet(tet_idx)?.nodes().contains(&node)
+                    || self.is_v_in_powersphere(v_idx, tet_idx, false, self.predicate_mode)?
+                {
+                    self.tds.bw_rem_tet(tet_idx);
+                } else {
+                    self.tds.bw_keep_tetra(tet_idx)?;
+                }
+            }
+
+            if self.predicate_mode.is_exact() {
+                break;
+            }
+
+            // inexact power sphere tests may give a cavity, that is not star-shaped, i.e. some new tets would be
+            // flat or inverted, so the tets behind the facets the vertex does not see are removed as well
+            let hidden_tets: Vec<usize> = self.hidden_cavity_tets(v_idx).collect();
+            if hidden_tets.is_empty() {
+                break;
+            }
+            for tet_idx in hidden_tets {
+                self.tds.bw_grow(tet_idx);
+            }
+        }
+
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tet_idx in &self.tds.bw_removed_tets()[num_removed_before..] {
+                changes.destroy(tet_idx);
+            }
+        }
+
+        // the slots of the removed tets are taken by the insertion
+        let removed_tets = if self.observer.is_registered() {
+            self.tds.bw_removed_tets()[num_removed_before..].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        #[cfg(feature = "metrics")]
+        let num_removed = self.tds.bw_removed_tets().len() - num_removed_before;
+
+        self.tds.bw_insert_node(node)?;
+        self.observer
+            .on_bw_cavity(v_idx, &removed_tets, self.tds.bw_added_tets());
+        #[cfg(feature = "metrics")]
+        {
+            let num_added = self.tds.bw_added_tets().len();
+            self.tds.stats.num_bw_cavities += 1;
+            self.tds.stats.record_changes(num_removed, num_added);
+        }
+
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tet_idx in self.tds.bw_added_tets() {
+                changes.create(tet_idx);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The kept tets, whose casual facet on the boundary of the cavity the vertex does not see strictly from inside.
+    fn hidden_cavity_tets(&self, v_idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let p = self.vertices[v_idx];
+        self.tds
+            .bw_cavity_boundary()
+            .filter(move |tri| match tri.nodes() {
+                [
+                    VertexNode::Casual(v_idx0),
+                    VertexNode::Casual(v_idx1),
+                    VertexNode::Casual(v_idx2),
+                ] => {
+                    predicates::orient_3d(
+                        &self.vertices[v_idx0],
+                        &self.vertices[v_idx1],
+                        &self.vertices[v_idx2],
+                        &p,
+                    ) <= 0.0
+                }
+                _ => false,
+            })
+            .map(|tri| tri.tet().idx())
+    }
+
+    /// Inserts point by flips, returns a tet containing it.
+    ///
+    /// The containing tet is split 1-4, then the facets opposite to the vertex are flipped until they are regular.
+    /// A vertex on a facet of the containing tet, and facets that no flip can make regular, e.g. degenerate ones,
+    /// are left to [`Self::insert_bw`], which finishes the star of the vertex.
+    fn insert_flips(&mut self, v_idx: usize, containing_tet_idx: usize) -> HowResult<usize> {
+        let node = VertexNode::Casual(v_idx);
+        let containing_nodes = self.tds().get_tet(containing_tet_idx)?.nodes();
+
+        let split_tets = [0, 1, 2, 3].map(|i| {
+            let mut nodes = containing_nodes;
+            nodes[i] = node;
+            nodes
+        });
+        let orientation = self.orientation(containing_nodes);
+        if orientation == 0.0
+            || split_tets
+                .iter()
+                .any(|&nodes| self.orientation(nodes) * orientation <= 0.0)
+        {
+            self.insert_bw(v_idx, containing_tet_idx)?;
+            return Ok(self.tds.bw_added_tets()[0]);
+        }
+
+        let mut tets_to_check = self.flip_tets(&[containing_tet_idx], &split_tets)?;
+        let mut star_tet_idx = tets_to_check[0];
+        // facets that could not be flipped when checked, most are flipped away by later flips
+        let mut unflipped_tets = Vec::new();
+
+        while let Some(tet_idx) = tets_to_check.pop() {
+            let Some(local_idx) = self.non_regular_link_facet(v_idx, tet_idx)? else {
+                continue;
+            };
+
+            if let Some(new_tets) = self.flip_facet(tet_idx, local_idx)? {
+                star_tet_idx = new_tets[0];
+                tets_to_check.extend(new_tets);
+            } else {
+                unflipped_tets.push(tet_idx);
+            }
+        }
+
+        for tet_idx in unflipped_tets {
+            if self.non_regular_link_facet(v_idx, tet_idx)?.is_some() {
+                self.insert_bw(v_idx, star_tet_idx)?;
+                return Ok(self.tds.bw_added_tets()[0]);
+            }
+        }
+
+        Ok(star_tet_idx)
+    }
+
+    /// The local index of the vertex in a live tet containing it, if the facet opposite to it is not regular.
+    fn non_regular_link_facet(&self, v_idx: usize, tet_idx: usize) -> HowResult<Option<usize>> {
+        let tet = self.tds().get_tet(tet_idx)?;
+        if tet.should_del() {
+            return Ok(None);
+        }
+        let Some(local_idx) = tet
+            .nodes()
+            .iter()
+            .position(|&node| node == VertexNode::Casual(v_idx))
+        else {
+            return Ok(None);
+        };
+
+        let neighbor_idx = tet.half_triangles()[local_idx].opposite().tet().idx();
+        Ok(self
+            .is_v_in_powersphere(v_idx, neighbor_idx, false, self.predicate_mode)?
+            .then_some(local_idx))
+    }
+
+    /// Flips the facet of a tet opposite to its node `local_idx`, which is the inserted vertex `p`, returns the new tets.
+    ///
+    /// With the facet `abc` and the node `d` beyond it, the flip depends on where the line `pd` crosses the plane of
+    /// the facet: inside the facet 2-3, beyond the edge `ab` 3-2, if `pabd` is a tet, and beyond the node `a` 4-1,
+    /// if the four tets around `a` fill `pbcd` and the vertices are weighted. `None` if none of them applies.
+    fn flip_facet(&mut self, tet_idx: usize, local_idx: usize) -> HowResult<Option<Vec<usize>>> {
+        let tet = self.tds().get_tet(tet_idx)?;
+        let nodes = tet.nodes();
+        let tris = tet.half_triangles();
+        let facet = &tris[local_idx];
+        let [a, b, c] = facet.nodes();
+        let d = facet.opposite().opposite_node();
+        let neighbor_idx = facet.opposite().tet().idx();
+
+        // the tet with the facet before the apex has the same orientation as the tet itself
+        let pabc = [a, b, c, nodes[local_idx]];
+        let orientation = self.orientation(pabc);
+        if d.is_conceptual() || tet.is_conceptual() || orientation == 0.0 {
+            return Ok(None);
+        }
+
+        // the tet with the node `i` of the facet replaced by `d` is positive, iff `pd` crosses the facet on its side
+        let replaced = |i: usize| {
+            let mut nodes = pabc;
+            nodes[i] = d;
+            nodes
+        };
+        let sides = [0, 1, 2].map(|i| self.orientation(replaced(i)) * orientation);
+        if sides.contains(&0.0) {
+            return Ok(None);
+        }
+
+        // the tet beyond the facet of `tet` opposite to the node `i` of the facet, if its apex is `d`
+        let tet_beyond = |i: usize| {
+            let tri = &tris[nodes.iter().position(|&n| n == pabc[i])?];
+            (tri.opposite().opposite_node() == d).then(|| tri.opposite().tet().idx())
+        };
+
+        let behind: Vec<usize> = (0..3).filter(|&i| sides[i] < 0.0).collect();
+        let (old_tets, new_tets) = match behind[..] {
+            [] => (
+                vec![tet_idx, neighbor_idx],
+                vec![replaced(0), replaced(1), replaced(2)],
+            ),
+            [i] => {
+                let Some(third_idx) = tet_beyond(i) else {
+                    return Ok(None);
+                };
+                let [j, k] = [(i + 1) % 3, (i + 2) % 3];
+                (
+                    vec![tet_idx, neighbor_idx, third_idx],
+                    vec![replaced(j), replaced(k)],
+                )
+            }
+            [i, j] if self.weighted() => {
+                let (Some(third_idx), Some(fourth_idx)) = (tet_beyond(i), tet_beyond(j)) else {
+                    return Ok(None);
+                };
+                (
+                    vec![tet_idx, neighbor_idx, third_idx, fourth_idx],
+                    vec![replaced(3 - i - j)],
+                )
+            }
+            _ => return Ok(None),
+        };
+
+        self.flip_tets(&old_tets, &new_tets).map(Some)
+    }
+
+    /// Replaces tets by new ones filling the same region, see [`TetDataStructure::flip`].
+    fn flip_tets(
+        &mut self,
+        old_tets: &[usize],
+        new_tets: &[[VertexNode; 4]],
+    ) -> HowResult<Vec<usize>> {
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tet_idx in old_tets {
+                changes.destroy(tet_idx);
+            }
+        }
+
+        let tet_idxs = self.tds.flip(old_tets, new_tets)?;
+        self.observer.on_flip_tets(old_tets, &tet_idxs);
+        #[cfg(feature = "metrics")]
+        {
+            let stats = &mut self.tds.stats;
+            match (old_tets.len(), tet_idxs.len()) {
+                (1, 4) => stats.num_flips_1_4 += 1,
+                (2, 3) => stats.num_flips_2_3 += 1,
+                (3, 2) => stats.num_flips_3_2 += 1,
+                (4, 1) => stats.num_flips_4_1 += 1,
+                _ => (),
+            }
+            stats.record_changes(old_tets.len(), tet_idxs.len());
+        }
+
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tet_idx in &tet_idxs {
+                changes.create(tet_idx);
+            }
+        }
+
+        Ok(tet_idxs)
+    }
+
+    /// The sign of the orientation of casual nodes, i.e. `-1.0`, `0.0` or `1.0`, zero if one of them is conceptual.
+    ///
+    /// Only the signs are compared, as the product of two tiny orientations might underflow.
+    fn orientation(&self, nodes: [VertexNode; 4]) -> f64 {
+        let orientation = match nodes {
+            [
+                VertexNode::Casual(v_idx0),
+                VertexNode::Casual(v_idx1),
+                VertexNode::Casual(v_idx2),
+                VertexNode::Casual(v_idx3),
+            ] => -predicates::orient_3d(
+                &self.vertices[v_idx0],
+                &self.vertices[v_idx1],
+                &self.vertices[v_idx2],
+                &self.vertices[v_idx3],
+            ),
+            _ => 0.0,
+        };
+
+        if orientation == 0.0 {
+            0.0
+        } else {
+            orientation.signum()
+        }
+    }
+
     fn insert_vertex_helper(&mut self, v_idx: usize, near_to_idx: usize) -> HowResult<usize> {
+        // the power tests of queries since the last insertion are not counted
+        #[cfg(feature = "metrics")]
+        self.filter_counts.take();
+
+        let result = self.insert_vertex_helper_inner(v_idx, near_to_idx);
+
+        #[cfg(feature = "metrics")]
+        self.tds
+            .stats
+            .record_filter_counts(self.filter_counts.take());
+        result
+    }
+
+    fn insert_vertex_helper_inner(&mut self, v_idx: usize, near_to_idx: usize) -> HowResult<usize> {
+        self.generation += 1;
+
         // Locating vertex via vis walk
         #[cfg(feature = "timing")]
         let now = std::time::Instant::now();
 
-        let containing_tet_idx = if let Ok(idx) = self.locate_vis_walk(v_idx, near_to_idx) {
+        let containing_tet_idx = if let Some(idx) = self.locate_beyond_hull(v_idx) {
+            idx
+        } else if let Ok(idx) = self.locate_vis_walk(v_idx, near_to_idx) {
             idx
         } else {
             self.tds.clean_to_del()?;
-            self.walk_check_all(v_idx)?
+            self.locate_fallback(v_idx)?
         };
 
         #[cfg(feature = "timing")]
         {
-            self.time_walking += now.elapsed().as_micros();
+            self.tds.stats.time_walking += now.elapsed().as_micros();
         }
 
-        if self.epsilon.is_some()
-            && self.tds().get_tet(containing_tet_idx)?.is_casual()
-            && !self.is_v_in_eps_powersphere(v_idx, containing_tet_idx)?
-        {
-            // Skip vertices that are not in power sphere by epsilon (i.e. above the hyperplane)
-            // but only if the containing tet is casual (for now), i.e. the vertex is inside the current convex hull
+        // Skip vertices at the position of a vertex of the containing tet, the first one inserted is kept
+        if let Some(canonical) = self.node_at_v(v_idx, containing_tet_idx)? {
+            self.duplicate_policy
+                .check(v_idx, canonical, self.weights.as_deref())?;
+            self.duplicate_vertices.push(v_idx);
+            self.duplicate_of.insert(v_idx, canonical);
+            return Ok(containing_tet_idx);
+        }
+
+        if self.epsilon.is_some() && self.is_v_ignored_by_eps(v_idx, containing_tet_idx)? {
             self.ignored_vertices.push(v_idx);
-            return Ok(0); // TODO return correct last added idx
+            return Ok(containing_tet_idx);
         } else if self.weighted()
-            && self.tds().get_tet(containing_tet_idx)?.is_casual()
-            && !self.is_v_in_powersphere(v_idx, containing_tet_idx, false)?
+            && !self.is_v_in_powersphere(v_idx, containing_tet_idx, false, self.predicate_mode)?
         {
-            // Skip redundant vertices
-            self.ignored_vertices.push(v_idx);
-            return Ok(0); // TODO return correct last added idx
+            // Skip redundant vertices, a vertex beyond the hull is never redundant,
+            // but one on a hull facet is decided by the power circle of the facet
+            self.redundant_vertices.push(v_idx);
+            return Ok(containing_tet_idx);
         }
 
         // Inserting vertex
         self.used_vertices.push(v_idx);
+        self.observer.on_insert(v_idx, containing_tet_idx);
 
         #[cfg(feature = "timing")]
         let now = std::time::Instant::now();
 
-        let new_tets = self.insert_bw(v_idx, containing_tet_idx)?;
+        let is_beyond_hull = self.tds().get_tet(containing_tet_idx)?.is_conceptual();
+        let new_tet_idx =
+            if is_beyond_hull || self.insertion_strategy == InsertionStrategy::BowyerWatson {
+                self.insert_bw(v_idx, containing_tet_idx)?;
+                self.tds.bw_added_tets()[0]
+            } else {
+                self.insert_flips(v_idx, containing_tet_idx)?
+            };
+        if is_beyond_hull {
+            self.hull_tet = self.tds.bw_added_tets().iter().copied().find(|&tet_idx| {
+                self.tds()
+                    .get_tet(tet_idx)
+                    .is_ok_and(|tet| tet.is_conceptual())
+            });
+        }
 
         #[cfg(feature = "timing")]
         {
-            self.time_inserting += now.elapsed().as_micros();
+            self.tds.stats.time_inserting += now.elapsed().as_micros();
+        }
+
+        if self.use_locate_grid {
+            self.update_locate_grid(v_idx, new_tet_idx)?;
+        }
+
+        Ok(new_tet_idx)
+    }
+
+    /// Record the tet incident to the inserted vertex in the grid, or build the grid again if it is too coarse.
+    fn update_locate_grid(&mut self, v_idx: usize, tet_idx: usize) -> HowResult<()> {
+        if !self.locate_grid.needs_rebuild(self.used_vertices.len()) {
+            self.locate_grid.record(&self.vertices[v_idx], tet_idx);
+            return Ok(());
+        }
+
+        let mut anchors = Vec::with_capacity(self.tds.num_tets());
+        for tet_idx in 0..self.tds.num_tets() {
+            let tet = self.tds().get_tet(tet_idx)?;
+            if tet.should_del() || tet.is_conceptual() {
+                continue;
+            }
+            let corners = tet
+                .nodes()
+                .map(|node| node.idx().map(|idx| self.vertices[idx]));
+            if let [Some(a), Some(b), Some(c), Some(d)] = corners {
+                let centroid = core::array::from_fn(|i| (a[i] + b[i] + c[i] + d[i]) / 4.0);
+                anchors.push((centroid, tet_idx));
+            }
         }
+        self.locate_grid.rebuild(self.used_vertices.len(), &anchors);
+
+        Ok(())
+    }
 
-        Ok(new_tets[0])
+    /// The tet to start a visibility walk towards the point from.
+    ///
+    /// The tet of the [`LocateGrid`] cell of the point, if it is used and the tet still exists, otherwise `fallback`.
+    fn walk_start(&self, point: &Vertex3, fallback: usize) -> usize {
+        self.locate_grid
+            .hint(point)
+            .filter(|&tet_idx| self.is_live_tet(tet_idx))
+            .unwrap_or(fallback)
     }
 
     fn insert_first_tet(
@@ -548,30 +1786,30 @@ impl Tetrahedralization {
         #[cfg(feature = "log_timing")]
         let now = std::time::Instant::now();
 
+        self.generation += 1;
+
         // first tetrahedron insertion
         if self.vertices.len() == idxs_to_insert.len() {
             let idx0 = idxs_to_insert.pop().unwrap();
-            let idx1 = idxs_to_insert.pop().unwrap();
-
             let v0 = self.vertices[idx0];
-            let v1 = self.vertices[idx1];
 
+            // duplicates of the first vertex are inserted later, and skipped then
             let mut aligned = Vec::new();
-            let v01 = [v1[0] - v0[0], v1[1] - v0[1], v1[2] - v0[2]];
+            let idx1 = loop {
+                let idx = idxs_to_insert.pop().ok_or(RitaError::AllPointsCoplanar)?;
+                if self.vertices[idx] == v0 {
+                    aligned.push(idx);
+                } else {
+                    break idx;
+                }
+            };
+            let v1 = self.vertices[idx1];
 
+            // the last vertex not collinear with the first two, exactly, as a nearly collinear one is still fine
             let i2 = idxs_to_insert
                 .iter()
-                .rev()
-                .enumerate()
-                .map(|(e, &idx)| (e, self.vertices[idx]))
-                .map(|(e, v)| (e, [v[0] - v0[0], v[1] - v0[1], v[2] - v0[2]]))
-                .map(|(e, vec)| (e, vec[0] * v01[0] + vec[1] * v01[1] + vec[2] * v01[2]))
-                .map(|(e, scal)| if scal < 0.0 { (e, -scal) } else { (e, scal) })
-                .max_by(|(_, val1), (_, val2)| val1.partial_cmp(val2).unwrap())
-                .map(|(e, _)| e)
-                .unwrap();
-
-            // todo this needs a double check
+                .rposition(|&idx| !is_collinear(&v0, &v1, &self.vertices[idx]))
+                .ok_or(RitaError::AllPointsCoplanar)?;
             let idx2 = if spatial_sorting {
                 idxs_to_insert.remove(i2)
             } else {
@@ -585,7 +1823,11 @@ impl Tetrahedralization {
 
                     let orientation = -predicates::orient_3d(&v0, &v1, &v2, &v3);
 
-                    if orientation > 0.0 {
+                    if [v0, v1, v2].contains(&v3) {
+                        // the orientation of a duplicate is not reliably zero
+                        aligned.push(idx3);
+                        continue;
+                    } else if orientation > 0.0 {
                         self.tds.insert_first_tet([idx0, idx1, idx2, idx3])?;
                     } else if orientation < 0.0 {
                         self.tds.insert_first_tet([idx0, idx2, idx1, idx3])?;
@@ -596,7 +1838,7 @@ impl Tetrahedralization {
 
                     self.used_vertices.append(&mut vec![idx0, idx1, idx2, idx3]);
                 } else {
-                    return Err(anyhow::Error::msg("Could not find four non aligned points"));
+                    return Err(anyhow::Error::new(RitaError::AllPointsCoplanar));
                 }
 
                 break;
@@ -615,94 +1857,345 @@ impl Tetrahedralization {
 
     /// Insert a single vertex in the structure
     ///
+    /// The walk to locate the vertex starts at the tet `near_to`, e.g. from [`Self::locate`] of a nearby point.
+    /// A stale hint is still used, if its tet exists, otherwise the walk starts at the last inserted tet.
+    /// The `weight` defaults to zero in a weighted tetrahedralization, and is ignored in an unweighted one.
+    ///
     /// ## Errors
     /// Returns an error if `self` does not have any triangles in it.
-    pub fn insert_vertex(&mut self, v: [f64; 3], near_to_idx: Option<usize>) -> HowResult<()> {
+    pub fn insert_vertex(
+        &mut self,
+        v: [f64; 3],
+        weight: Option<f64>,
+        near_to: Option<TetHandle>,
+    ) -> HowResult<()> {
         if self.tds.num_tets() == 0 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 1 tetrahedron to insert a single point",
-            ));
+            return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
         }
 
         let idxs_to_insert = self.vertices.len();
         self.vertices.push(v);
+        if let Some(weights) = &mut self.weights {
+            weights.push(weight.unwrap_or(0.0));
+        }
+        self.update_origin(idxs_to_insert, None);
 
-        self.insert_vertex_helper(
-            idxs_to_insert,
-            near_to_idx.unwrap_or(self.tds.num_tets() - 1),
-        )?;
+        let near_to_idx = near_to
+            .map(|handle| handle.idx())
+            .filter(|&tet_idx| self.is_live_tet(tet_idx))
+            .unwrap_or_else(|| self.walk_start(&v, self.tds.num_tets() - 1));
 
-        self.tds.clean_to_del()?;
+        self.insert_vertex_helper(idxs_to_insert, near_to_idx)?;
+
+        // compacting moves tets from the end into the slots of removed ones
+        match &mut self.tracked_changes {
+            Some(changes) => self
+                .tds
+                .clean_to_del_with(|from, to| changes.relocate(from, to))?,
+            None => self.tds.clean_to_del()?,
+        }
 
         #[cfg(feature = "log_timing")]
         {
-            log::trace!("Walks computed in {} μs", self.time_walking);
-            log::trace!("Insertions computed in {} μs", self.time_inserting);
+            log::trace!("Walks computed in {} μs", self.tds.stats.time_walking);
+            log::trace!(
+                "Insertions computed in {} μs",
+                self.tds.stats.time_inserting
+            );
         }
 
         Ok(())
     }
 
+    /// Insert a single vertex in the structure, like [`Self::insert_vertex`], and return the tets it changed.
+    ///
+    /// The cavity of the vertex is replaced by new tets, which reuse the slots of the removed ones, see [`SimplexChanges`].
+    /// Afterwards the tets at the end are moved into the remaining slots, such that these are in both lists as well.
+    /// The indices of all other tets are unchanged, so per-tet data of them stays valid. A vertex that is not
+    /// inserted, e.g. a redundant one, changes no tets.
+    ///
+    /// ## Errors
+    /// Returns an error if `self` does not have any triangles in it.
+    pub fn insert_vertex_tracked(
+        &mut self,
+        v: [f64; 3],
+        weight: Option<f64>,
+        near_to: Option<TetHandle>,
+    ) -> HowResult<SimplexChanges> {
+        self.tracked_changes = Some(SimplexChanges::default());
+        let result = self.insert_vertex(v, weight, near_to);
+        let changes = self.tracked_changes.take().unwrap_or_default();
+
+        result.map(|()| changes)
+    }
+
+    /// Register an observer, that is called on each insertion and flip from now on, replacing the one before.
+    pub fn set_observer(&mut self, observer: impl InsertObserver + 'static) {
+        self.observer.set(Box::new(observer));
+    }
+
+    /// Unregister the observer, returning it.
+    pub fn take_observer(&mut self) -> Option<Box<dyn InsertObserver>> {
+        self.observer.take()
+    }
+
     /// Updates delaunay graph, including newly inserted vertices
+    ///
+    /// With `spatial_sorting` the vertices are sorted spatially, `true` along a Hilbert curve,
+    /// or in any [`InsertOrder`], like [`insert_vertices_ordered`](Self::insert_vertices_ordered).
+    ///
+    /// The vertices can be arrays, or points of other crates behind their features, see [`IntoVertex3`].
+    ///
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
     pub fn insert_vertices(
+        &mut self,
+        vertices: impl IntoIterator<Item = impl IntoVertex3>,
+        weights: Option<Vec<f64>>,
+        spatial_sorting: impl Into<InsertOrder>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        let vertices: Vec<Vertex3> = vertices
+            .into_iter()
+            .map(IntoVertex3::into_vertex3)
+            .collect();
+        self.insert_vertices_ordered(&vertices, weights, spatial_sorting.into())
+    }
+
+    /// Updates delaunay graph, including newly inserted vertices in the given order
+    ///
+    /// The construction is deterministic, also for [`InsertOrder::Random`] given the same seed.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn insert_vertices_ordered(
         &mut self,
         vertices: &[[f64; 3]],
         weights: Option<Vec<f64>>,
-        spatial_sorting: bool,
-    ) -> HowResult<()> {
-        #[cfg(feature = "wasm")]
-        if weights.is_some() {
-            return Err(anyhow::Error::msg(
-                "Weighted Delaunay is not supported in wasm (robust predicates are unweighted). Use weights: None.",
-            ));
-        }
+        order: InsertOrder,
+    ) -> HowResult<Vec<InsertStatus>> {
+        self.insert_vertices_with(
+            vertices,
+            weights,
+            order,
+            None,
+            |_| ControlFlow::Continue(()),
+        )
+    }
 
-        let mut idxs_to_insert = Vec::with_capacity(vertices.len());
+    /// Insert a set of vertices like [`Self::insert_vertices_ordered`], returning the triangulation of their plane or
+    /// their [`Chain`] if all of them are coplanar, instead of the error [`RitaError::AllPointsCoplanar`].
+    ///
+    /// The vertex indices of the [`Degenerate3`] are into `vertices`, the tetrahedralization stays without tets then.
+    ///
+    /// ## Errors
+    /// Returns the errors of [`Self::insert_vertices_ordered`] except for coplanar vertices, or the ones of
+    /// triangulating the projected vertices.
+    pub fn insert_vertices_or_degenerate(
+        &mut self,
+        vertices: &[[f64; 3]],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+    ) -> HowResult<Insertion<Degenerate3>> {
+        let err = match self.insert_vertices_ordered(vertices, weights.clone(), order) {
+            Ok(statuses) => return Ok(Insertion::Inserted(statuses)),
+            Err(err) if err.downcast_ref() == Some(&RitaError::AllPointsCoplanar) => err,
+            Err(err) => return Err(err),
+        };
 
-        for &v in vertices {
-            idxs_to_insert.push(self.vertices.len());
-            self.vertices.push(v);
+        let v0 = vertices[0];
+        let Some(&v1) = vertices.iter().find(|&&v| v != v0) else {
+            return Ok(Insertion::Degenerate(Degenerate3::Collinear(Chain::new(
+                vertices,
+            ))));
+        };
+        let Some(&v2) = vertices.iter().find(|v| !is_collinear(&v0, &v1, v)) else {
+            return Ok(Insertion::Degenerate(Degenerate3::Collinear(Chain::new(
+                vertices,
+            ))));
+        };
+        // the first tetrahedron may fail on other grounds, e.g. a non-finite vertex
+        if vertices
+            .iter()
+            .any(|v| predicates::orient_3d(&v0, &v1, &v2, v) != 0.0)
+        {
+            return Err(err);
         }
 
-        self.weights = weights;
-
-        if self.vertices.len() < 4 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 4 vertices to compute Delaunay",
-            ));
-        }
+        let plane = Plane::best_fit(vertices).map_err(|_| err)?;
+        let projected: Vec<Vertex2> = vertices.iter().map(|&v| plane.project(v)).collect();
+        let mut triangulation = Triangulation::new_with_vert_capacity(None, projected.len());
+        triangulation.insert_vertices_ordered(&projected, weights, order)?;
 
-        if spatial_sorting {
-            #[cfg(feature = "timing")]
-            let now = std::time::Instant::now();
+        Ok(Insertion::Degenerate(Degenerate3::Coplanar {
+            plane,
+            triangulation: Box::new(triangulation),
+        }))
+    }
 
-            idxs_to_insert = sort_along_hilbert_curve_3d(&self.vertices, idxs_to_insert);
+    /// Insert a set of vertices into the tetrahedralization in the given order, reporting the progress to `progress`.
+    ///
+    /// The callback can cancel the insertion by returning [`ControlFlow::Break`], e.g. for huge inputs in a GUI.
+    /// The vertices processed before stay inserted and the tetrahedralization stays sound, see [`InsertProgress`].
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`, [`InsertStatus::Cancelled`] for the
+    /// ones not processed.
+    pub fn insert_vertices_with_progress(
+        &mut self,
+        vertices: &[[f64; 3]],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+        progress: impl FnMut(InsertProgress) -> ControlFlow<()>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        self.insert_vertices_with(vertices, weights, order, None, progress)
+    }
 
-            #[cfg(feature = "timing")]
-            {
-                self.time_hilbert = now.elapsed().as_micros();
-            }
-            #[cfg(feature = "log_timing")]
-            log::trace!("Hilbert curve computed in {} μs", now.elapsed().as_micros());
+    /// Insert a set of vertices, that are already in a spatial order, e.g. along a Hilbert or Morton curve.
+    ///
+    /// Skips the spatial sort and the scans over all vertices for their bounds, e.g. for huge inputs that come from
+    /// a prior tiling step. The local origin is derived from the bounding box `bbox`, i.e. `[min, max]` of the vertices.
+    /// Wrong bounds do not affect the correctness, only the accuracy of the origin.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn insert_vertices_presorted(
+        &mut self,
+        vertices: &[[f64; 3]],
+        weights: Option<Vec<f64>>,
+        bbox: [Vertex3; 2],
+    ) -> HowResult<Vec<InsertStatus>> {
+        self.insert_vertices_with(vertices, weights, InsertOrder::Input, Some(bbox), |_| {
+            ControlFlow::Continue(())
+        })
+    }
+
+    /// Insert the vertices in the given order, presorted ones if their bounding box `bbox` is known.
+    ///
+    /// Stops early if `progress` breaks, the remaining vertices are cancelled then.
+    fn insert_vertices_with(
+        &mut self,
+        vertices: &[[f64; 3]],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+        bbox: Option<[Vertex3; 2]>,
+        mut progress: impl FnMut(InsertProgress) -> ControlFlow<()>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        #[cfg(feature = "wasm")]
+        if weights.is_some() {
+            return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+        }
+        // the weights are those of all vertices, the inserted ones and the ones before
+        let num_vertices = self.vertices.len() + vertices.len();
+        if let Some(num_weights) = weights
+            .as_ref()
+            .map(Vec::len)
+            .filter(|&n| n != num_vertices)
+        {
+            return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+                num_weights,
+                num_vertices,
+            }));
+        }
+
+        let mut idxs_to_insert = Vec::with_capacity(vertices.len());
+        let first_new_idx = self.vertices.len();
+
+        for &v in vertices {
+            idxs_to_insert.push(self.vertices.len());
+            self.vertices.push(v);
+        }
+
+        self.weights = weights;
+        if let (DuplicatePolicy::MergeWeights, Some(weights)) =
+            (self.duplicate_policy, &mut self.weights)
+        {
+            merge_duplicate_weights(&self.vertices, weights, &idxs_to_insert);
+        }
+        self.update_origin(first_new_idx, bbox);
+
+        if self.vertices.len() < 4 {
+            return Err(anyhow::Error::new(RitaError::NotEnoughVertices {
+                required: 4,
+            }));
+        }
+
+        match order {
+            InsertOrder::Input => (),
+            InsertOrder::Hilbert | InsertOrder::WeightedHilbert => {
+                #[cfg(feature = "timing")]
+                let now = std::time::Instant::now();
+
+                idxs_to_insert = sort_along_hilbert_curve_3d(&self.vertices, idxs_to_insert);
+
+                #[cfg(feature = "timing")]
+                {
+                    self.tds.stats.time_hilbert += now.elapsed().as_micros();
+                }
+                #[cfg(feature = "log_timing")]
+                log::trace!("Hilbert curve computed in {} μs", now.elapsed().as_micros());
+            }
+            InsertOrder::Random(seed) => shuffle(&mut idxs_to_insert, seed),
+            InsertOrder::Morton => {
+                idxs_to_insert = sort_along_morton_curve_3d(&self.vertices, &idxs_to_insert);
+            }
+            InsertOrder::Brio(seed) => {
+                // popped from the end, such that the first round is inserted first
+                idxs_to_insert = brio_order_3d(&self.vertices, &idxs_to_insert, seed);
+                idxs_to_insert.reverse();
+            }
+        }
+
+        if order == InsertOrder::WeightedHilbert {
+            if let Some(weights) = &self.weights {
+                sort_buckets_by_weight(&mut idxs_to_insert, weights);
+            }
         }
 
         if self.tds.num_tets() == 0 {
-            self.insert_first_tet(&mut idxs_to_insert, spatial_sorting)?;
+            // keep the order of the remaining vertices, unless they are in an arbitrary input order
+            let keep_order = order != InsertOrder::Input || bbox.is_some();
+            self.insert_first_tet(&mut idxs_to_insert, keep_order)?;
         }
 
+        let num_total = vertices.len();
         let mut last_added_idx = self.tds.num_tets() - 1;
         while let Some(v_idx) = idxs_to_insert.pop() {
-            last_added_idx = self.insert_vertex_helper(v_idx, last_added_idx)?;
+            let near_to_idx = self.walk_start(&self.vertices[v_idx], last_added_idx);
+            last_added_idx = self.insert_vertex_helper(v_idx, near_to_idx)?;
+
+            let num_processed = num_total - idxs_to_insert.len();
+            if InsertProgress::is_due(num_processed, idxs_to_insert.len())
+                && progress(InsertProgress {
+                    num_processed,
+                    num_total,
+                })
+                .is_break()
+            {
+                #[cfg(feature = "logging")]
+                log::info!("Insertion cancelled after {num_processed} of {num_total} vertices");
+                break;
+            }
         }
 
         self.tds.clean_to_del()?;
         #[cfg(feature = "log_timing")]
         {
-            log::trace!("Walks computed in {} μs", self.time_walking);
-            log::trace!("Insertions computed in {} μs", self.time_inserting);
+            log::trace!("Walks computed in {} μs", self.tds.stats.time_walking);
+            log::trace!(
+                "Insertions computed in {} μs",
+                self.tds.stats.time_inserting
+            );
         }
 
-        Ok(())
+        let mut statuses = insert_statuses(
+            first_new_idx,
+            self.vertices.len(),
+            [
+                (&self.used_vertices, InsertStatus::Redundant),
+                (&self.redundant_vertices, InsertStatus::Redundant),
+                (&self.ignored_vertices, InsertStatus::IgnoredByEpsilon),
+                (&self.duplicate_vertices, InsertStatus::Duplicate),
+            ],
+            self.tds.tet_nodes.iter(),
+        );
+        for v_idx in idxs_to_insert {
+            statuses[v_idx - first_new_idx] = InsertStatus::Cancelled;
+        }
+
+        Ok(statuses)
     }
 
     /// Check if the tetrahedralization is valid, i.e. no vertices are inside the circumsphere of any tetrahedron
@@ -713,7 +2206,7 @@ impl Tetrahedralization {
         for tet_idx in 0..self.tds().num_tets() {
             if self.is_tet_flat(tet_idx)? {
                 #[cfg(feature = "logging")]
-                error!("Flat tetrahedron: {}", self.tds().get_tet(tet_idx)?);
+                error!("Flat tetrahedron: {}", self.describe_tet(tet_idx)?);
                 regular = false;
                 num_violated_tets += 1;
                 continue;
@@ -731,12 +2224,26 @@ impl Tetrahedralization {
                     continue;
                 }
 
-                if self.is_v_in_powersphere(v_idx, tet_idx, false)? {
+                if self.is_v_in_powersphere(v_idx, tet_idx, false, PredicateMode::Exact)? {
                     #[cfg(feature = "logging")]
                     // FIXME: should this not be an error?
-                    log::error!("Non Delaunay tetrahedron: {}", self.tds().get_tet(tet_idx)?);
+                    log::error!("Non Delaunay tetrahedron: {}", self.describe_tet(tet_idx)?);
+                    regular = false;
+                    num_violated_tets += 1;
+                }
+            }
+
+            // Check the redundant vertices, they stay outside of every power sphere
+            for &v_idx in &self.redundant_vertices {
+                if self.is_v_in_powersphere(v_idx, tet_idx, false, PredicateMode::Exact)? {
+                    #[cfg(feature = "logging")]
+                    log::error!(
+                        "Redundant vertex {v_idx} in power sphere: {}",
+                        self.describe_tet(tet_idx)?
+                    );
                     regular = false;
                     num_violated_tets += 1;
+                    break;
                 }
             }
         }
@@ -747,56 +2254,149 @@ impl Tetrahedralization {
         ))
     }
 
-    /// Checks regularity in parallel using [`rayon`]s.
+    /// Checks regularity in parallel using `rayon`, on a single thread without the `parallel` feature.
     ///
     /// This can significantly reduce the runtime of this predicate.
+    /// Returns the share of tets without violation, see [`RegularityCheck::Full`].
     #[must_use]
     pub fn par_is_regular(&self, with_ignored_vertices: bool) -> f64 {
-        let num_tets = self.tds().num_tets();
+        self.check_regularity(RegularityCheck::Full, with_ignored_vertices)
+            .degree()
+    }
 
-        let num_violated_tets: f64 = (0..num_tets)
-            .into_par_iter()
-            .map(|tet_idx| {
-                if self.is_tet_flat(tet_idx).unwrap() {
-                    1.0
-                } else {
-                    // Check the used vertices, for this any computed tetrahedralization should always be regular
-                    let used_violation = self.used_vertices.iter().find(|&&v_idx| {
-                        // Skip vertices that are part of the current tetrahedron
-                        if self
-                            .tds()
-                            .get_tet(tet_idx)
-                            .unwrap()
-                            .nodes()
-                            .contains(&VertexNode::Casual(v_idx))
-                        {
-                            return false;
-                        }
+    /// Former name of [`Self::par_is_regular`].
+    #[deprecated(since = "0.3.0", note = "use `par_is_regular` instead")]
+    #[must_use]
+    pub fn is_regular_p(&self, with_ignored_vertices: bool) -> f64 {
+        self.par_is_regular(with_ignored_vertices)
+    }
 
-                        self.is_v_in_powersphere(v_idx, tet_idx, false).unwrap()
-                    });
+    /// Check in parallel, how far the tetrahedralization is regular w.r.t. the empty power sphere property.
+    ///
+    /// The used and redundant vertices are checked, and the ignored vertices as well if `with_ignored_vertices`.
+    /// A tet or vertex, whose check fails, e.g. for a broken data structure, counts as a violation.
+    #[must_use]
+    pub fn check_regularity(
+        &self,
+        check: RegularityCheck,
+        with_ignored_vertices: bool,
+    ) -> RegularityReport {
+        let mut tet_idxs: Vec<usize> = (0..self.tds().num_tets()).collect();
+        if let RegularityCheck::Sampled { num_samples, seed } = check {
+            shuffle(&mut tet_idxs, seed);
+            tet_idxs.truncate(num_samples);
+        }
+        let num_checked = tet_idxs.len();
+
+        self.thread_pool.install(self.max_threads, || match check {
+            RegularityCheck::Full | RegularityCheck::Sampled { .. } => RegularityReport {
+                num_checked,
+                num_violated: tet_idxs
+                    .into_par_iter()
+                    .filter(|&tet_idx| {
+                        self.is_tet_violated(tet_idx, with_ignored_vertices)
+                            .unwrap_or(true)
+                    })
+                    .count(),
+            },
+            RegularityCheck::EarlyExit => RegularityReport {
+                num_checked,
+                num_violated: usize::from(tet_idxs.into_par_iter().any(|tet_idx| {
+                    self.is_tet_violated(tet_idx, with_ignored_vertices)
+                        .unwrap_or(true)
+                })),
+            },
+            RegularityCheck::Local => {
+                let mut v_idxs = self.redundant_vertices.clone();
+                if with_ignored_vertices {
+                    v_idxs.extend_from_slice(&self.ignored_vertices);
+                }
 
-                    if used_violation.is_some() {
-                        return 1.0;
-                    }
+                RegularityReport {
+                    num_checked: num_checked + v_idxs.len(),
+                    num_violated: tet_idxs
+                        .into_par_iter()
+                        .filter(|&tet_idx| self.is_tet_locally_violated(tet_idx).unwrap_or(true))
+                        .count()
+                        + v_idxs
+                            .into_par_iter()
+                            .filter(|&v_idx| {
+                                self.is_v_in_containing_powersphere(v_idx).unwrap_or(true)
+                            })
+                            .count(),
+                }
+            }
+        })
+    }
 
-                    // Check the ignored vertices, here we can account for the degree of irregularity the epsilon filter introduced
-                    if with_ignored_vertices {
-                        let ignored_violation = self.ignored_vertices.iter().find(|&&v_idx| {
-                            self.is_v_in_powersphere(v_idx, tet_idx, false).unwrap()
-                        });
+    /// Whether a tet is flat, or one of the checked vertices lies in its power sphere.
+    fn is_tet_violated(&self, tet_idx: usize, with_ignored_vertices: bool) -> HowResult<bool> {
+        if self.is_tet_flat(tet_idx)? {
+            return Ok(true);
+        }
 
-                        if ignored_violation.is_some() {
-                            return 1.0;
-                        }
-                    }
+        let nodes = self.tds().get_tet(tet_idx)?.nodes();
+        let ignored_vertices = if with_ignored_vertices {
+            self.ignored_vertices.as_slice()
+        } else {
+            &[]
+        };
+        for &v_idx in self
+            .used_vertices
+            .iter()
+            .chain(&self.redundant_vertices)
+            .chain(ignored_vertices)
+        {
+            // Skip vertices that are part of the current tet
+            if !nodes.contains(&VertexNode::Casual(v_idx))
+                && self.is_v_in_powersphere(v_idx, tet_idx, false, PredicateMode::Exact)?
+            {
+                return Ok(true);
+            }
+        }
 
-                    0.0
-                }
-            })
-            .sum();
+        Ok(false)
+    }
+
+    /// Whether a tet is flat, or the vertex opposite to one of its facets in the neighbor lies in its power sphere.
+    fn is_tet_locally_violated(&self, tet_idx: usize) -> HowResult<bool> {
+        if self.is_tet_flat(tet_idx)? {
+            return Ok(true);
+        }
+
+        for half_tri in self.tds().get_tet(tet_idx)?.half_triangles() {
+            // the neighbor beyond a hull facet has no opposite vertex
+            let Some(v_idx) = half_tri.opposite().opposite_vertex() else {
+                continue;
+            };
+            if self.is_v_in_powersphere(v_idx, tet_idx, false, PredicateMode::Exact)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Whether a vertex lies in the power sphere of the tet containing it, i.e. below the lifted tetrahedralization.
+    fn is_v_in_containing_powersphere(&self, v_idx: usize) -> HowResult<bool> {
+        let v = self.vertices[v_idx];
+        let num_tets = self.tds().num_tets();
+        let tet_idx = match self.walk_to_point(
+            &v,
+            self.walk_start(&v, num_tets.saturating_sub(1)),
+            num_tets,
+        )? {
+            (Some(tet_idx), _) => tet_idx,
+            // the walk may not reach the vertex, if the tetrahedralization is not regular
+            (None, _) => (0..num_tets)
+                .find(|&tet_idx| {
+                    self.classify_point(tet_idx, &v)
+                        .is_ok_and(|loc| loc.is_some())
+                })
+                .ok_or(RitaError::LocateFailed)?,
+        };
 
-        1.0 - num_violated_tets / self.tds().num_tets() as f64
+        self.is_v_in_powersphere(v_idx, tet_idx, false, PredicateMode::Exact)
     }
 
     pub fn is_regular_for_point_set(
@@ -826,41 +2426,15 @@ impl Tetrahedralization {
 
             if self.is_tet_flat(tet_idx)? {
                 #[cfg(feature = "logging")]
-                error!("Flat tetrahedron: {}", self.tds().get_tet(tet_idx)?);
+                error!("Flat tetrahedron: {}", self.describe_tet(tet_idx)?);
                 regular = false;
                 num_violated_tets += 1;
                 continue;
             }
 
-            // Check the used vertices, for this any computed triangulation should always be regular
-            for (idx, v) in vertices.iter().enumerate() {
-                // TODO: skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
-
-                let h_v = v[0].powi(2) + v[1].powi(2) + v[2].powi(2) - weights[idx];
-
-                let ext_tet = self.get_tet_as_extended(tet_idx)?;
-
-                let in_sphere = match ext_tet {
-                    ExtendedTetrahedron::Tetrahedron([a, b, c, d]) => {
-                        let [h_a, h_b, h_c, h_d] = self
-                            .tds()
-                            .get_tet(tet_idx)?
-                            .nodes()
-                            .map(|n| self.height(n.idx().unwrap()));
-
-                        predicates::orient_3dlifted_SOS(&a, &b, &c, &d, v, h_a, h_b, h_c, h_d, h_v)
-                    }
-                    // if the triangle is a line segment, then the power sphere is a sphere with infinite radius and we can use a orientation test
-                    ExtendedTetrahedron::Triangle([a, b, c]) => {
-                        -predicates::orient_3d(&a, &b, &c, v)
-                    }
-                };
-
-                if in_sphere > 0.0 {
-                    regular = false;
-                    num_violated_tets += 1;
-                    break; // each triangle can be violated once
-                }
+            if self.is_point_in_powersphere(tet_idx, vertices, &weights)? {
+                regular = false;
+                num_violated_tets += 1; // each tet can be violated once
             }
         }
 
@@ -870,6 +2444,91 @@ impl Tetrahedralization {
         ))
     }
 
+    /// Checks regularity exactly, with rational coordinates and without SOS, see [`ExactVertices`].
+    ///
+    /// Cospherical vertices do not count as a violation, so on degenerate inputs e.g. grids, this validates that the
+    /// tetrahedralization of the fast path is one of the regular ones. Checks each tet against all used vertices,
+    /// so this is only feasible for small inputs.
+    ///
+    /// ## Errors
+    /// Returns an error if a vertex or weight is not finite.
+    #[cfg(feature = "exact")]
+    pub fn is_regular_exact(&self) -> HowResult<bool> {
+        let exact = ExactVertices::new(&self.vertices, self.weights.as_deref())?;
+        let tets: Vec<Vec<VertexIdx>> = self.tet_indices().into_iter().map(Vec::from).collect();
+
+        Ok(exact.is_regular(&tets, &self.used_vertices))
+    }
+
+    /// Checks regularity against a point set in parallel, like [`Self::par_is_regular`] does for the own vertices.
+    ///
+    /// Returns the share of tets that are not flat, and have none of the points in their power sphere.
+    /// A tet, whose check fails, e.g. for a broken data structure, counts as violated.
+    ///
+    /// ## Errors
+    /// Returns [`RitaError::WeightsMismatch`], if the number of weights does not match the number of vertices.
+    pub fn par_is_regular_for_point_set(
+        &self,
+        vertices: &[[f64; 3]],
+        weights: Option<Vec<f64>>,
+    ) -> HowResult<f64> {
+        let weights = weights.unwrap_or_else(|| vec![0.0; vertices.len()]);
+        if weights.len() != vertices.len() {
+            return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+                num_weights: weights.len(),
+                num_vertices: vertices.len(),
+            }));
+        }
+
+        let num_violated_tets = self.thread_pool.install(self.max_threads, || {
+            (0..self.tds().num_tets())
+                .into_par_iter()
+                .filter(|&tet_idx| {
+                    let violated = || -> HowResult<bool> {
+                        Ok(self.is_tet_flat(tet_idx)?
+                            || self.is_point_in_powersphere(tet_idx, vertices, &weights)?)
+                    };
+
+                    violated().unwrap_or(true)
+                })
+                .count()
+        });
+
+        Ok(1.0 - num_violated_tets as f64 / self.tds().num_tets() as f64)
+    }
+
+    /// Whether one of the weighted points lies in the power sphere of a tet.
+    fn is_point_in_powersphere(
+        &self,
+        tet_idx: usize,
+        vertices: &[[f64; 3]],
+        weights: &[f64],
+    ) -> HowResult<bool> {
+        // TODO: skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
+        let mut points = vertices.iter().zip(weights);
+
+        Ok(match self.get_tet_as_extended(tet_idx)? {
+            ExtendedTetrahedron::Tetrahedron([a, b, c, d]) => {
+                let [h_a, h_b, h_c, h_d] = self
+                    .tds()
+                    .get_tet(tet_idx)?
+                    .nodes()
+                    .map(|n| self.height(n.idx().unwrap()));
+
+                points.any(|(v, weight)| {
+                    let [x, y, z] = to_local(v, &self.origin);
+                    let h_v = x.powi(2) + y.powi(2) + z.powi(2) - weight;
+                    predicates::orient_3dlifted_SOS(&a, &b, &c, &d, v, h_a, h_b, h_c, h_d, h_v)
+                        > 0.0
+                })
+            }
+            // if the triangle is a line segment, then the power sphere is a sphere with infinite radius and we can use a orientation test
+            ExtendedTetrahedron::Triangle([a, b, c]) => {
+                points.any(|(v, _)| -predicates::orient_3d(&a, &b, &c, v) > 0.0)
+            }
+        })
+    }
+
     pub fn is_sound(&self) -> HowResult<bool> {
         match self.tds().is_sound() {
             Ok(true) => Ok(true),
@@ -887,9 +2546,203 @@ impl Tetrahedralization {
         }
     }
 
+    /// Check the data structure for broken links, listing each of them instead of a single `bool` like [`Self::is_sound`].
+    pub fn soundness_report(&self) -> SoundnessReport {
+        self.tds().soundness_report()
+    }
+
+    /// Try to repair recoverable inconsistencies of the data structure, e.g. stale links after an interrupted insertion.
+    ///
+    /// Returns the issues that could not be repaired, i.e. the structure is consistent if the report is sound.
+    pub fn try_repair(&mut self) -> SoundnessReport {
+        self.generation += 1;
+        self.tds.try_repair()
+    }
+
+    /// The current generation of the tetrahedralization, advanced on every change of its tets.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Get a handle to the tet with this index, e.g. as a hint for [`Self::insert_vertex`].
+    ///
+    /// ## Errors
+    /// Returns an error if there is no such tet, i.e. the index is out of bounds or the tet is deleted.
+    pub fn tet_handle(&self, tet_idx: usize) -> HowResult<TetHandle> {
+        if !self.is_live_tet(tet_idx) {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "tetrahedron",
+                idx: tet_idx,
+            }));
+        }
+
+        Ok(self.handle(tet_idx))
+    }
+
+    /// The casual tets adjacent to a tet, `None` across the hull.
+    ///
+    /// The neighbor `i` shares the facet opposite to the node `i` of the tet.
+    ///
+    /// ## Errors
+    /// Returns an error if there is no such tet, i.e. the index is out of bounds or the tet is deleted.
+    pub fn tet_neighbors(&self, tet_idx: usize) -> HowResult<[Option<usize>; 4]> {
+        if !self.is_live_tet(tet_idx) {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "tetrahedron",
+                idx: tet_idx,
+            }));
+        }
+
+        Ok(self.tds.get_tet(tet_idx)?.half_triangles().map(|half_tri| {
+            let neighbor = half_tri.opposite().tet();
+            neighbor.is_casual().then_some(neighbor.idx())
+        }))
+    }
+
+    /// The index of the tet of a handle, checked to be taken from the current generation.
+    ///
+    /// ## Errors
+    /// Returns an error if the tetrahedralization changed since the handle was taken.
+    pub fn resolve_tet(&self, handle: TetHandle) -> HowResult<usize> {
+        if handle.generation() != self.generation {
+            return Err(anyhow::Error::new(RitaError::StaleHandle {
+                handle: handle.generation(),
+                current: self.generation,
+            }));
+        }
+
+        Ok(handle.idx())
+    }
+
+    /// A handle to an existing tet, of the current generation.
+    const fn handle(&self, tet_idx: usize) -> TetHandle {
+        TetHandle::new(tet_idx, self.generation)
+    }
+
+    /// Check if the index refers to an existing, i.e. not deleted, tet.
+    fn is_live_tet(&self, tet_idx: usize) -> bool {
+        self.tds()
+            .get_tet(tet_idx)
+            .is_ok_and(|tet| !tet.should_del())
+    }
+
+    /// The casual tets incident to the vertex, i.e. its star, sorted.
+    ///
+    /// Walks across the facets around the vertex starting at its incident tet, empty if the vertex is not part
+    /// of the tetrahedralization.
+    #[must_use]
+    pub fn incident_tets(&self, v_idx: VertexIdx) -> Vec<usize> {
+        self.tds
+            .get_tet_containing(&VertexNode::Casual(v_idx))
+            .into_iter()
+            .filter(|tet| tet.is_casual())
+            .map(|tet| tet.idx())
+            .collect()
+    }
+
+    /// The vertex of the tetrahedralization closest to the point, `None` if there are no tets.
+    ///
+    /// Locates the point and descends to closer neighbors from there, as in a Delaunay tetrahedralization every
+    /// vertex but the closest one has a closer neighbor. Weighted tetrahedralizations are not Delaunay, so all of
+    /// their vertices are scanned instead, see [`Self::nearest_power_vertex`] for the query they answer by descending.
+    #[must_use]
+    pub fn nearest_vertex(&self, point: Vertex3) -> Option<VertexIdx> {
+        let dist_sq = |v_idx: VertexIdx| {
+            let v = self.vertices[v_idx];
+            (0..3).map(|i| (v[i] - point[i]).powi(2)).sum::<f64>()
+        };
+
+        if self.weighted() {
+            return (0..self.tds.num_tets())
+                .filter(|&tet_idx| self.is_live_tet(tet_idx))
+                .flat_map(|tet_idx| self.tds.get_tet(tet_idx).unwrap().nodes())
+                .filter_map(|node| node.idx())
+                .min_by(|&a, &b| dist_sq(a).total_cmp(&dist_sq(b)));
+        }
+
+        self.descend_to_nearest(point, dist_sq)
+    }
+
+    /// The vertex, whose power cell contains the point, `None` if there are no tets.
+    ///
+    /// That is the vertex with the smallest power distance `|p - v|² - w` to the point,
+    /// found by locating the point and descending to neighbors of smaller power distance.
+    /// Without weights, this is the [`nearest_vertex`](Self::nearest_vertex).
+    #[must_use]
+    pub fn nearest_power_vertex(&self, point: Vertex3) -> Option<VertexIdx> {
+        self.descend_to_nearest(point, |v_idx| {
+            let v = self.vertices[v_idx];
+            (0..3).map(|i| (v[i] - point[i]).powi(2)).sum::<f64>()
+                - self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
+        })
+    }
+
+    /// Descend from a vertex of the tet containing the point to neighbors of smaller distance.
+    fn descend_to_nearest(
+        &self,
+        point: Vertex3,
+        dist: impl Fn(VertexIdx) -> f64,
+    ) -> Option<VertexIdx> {
+        let closest = |v_idxs: &mut dyn Iterator<Item = VertexIdx>| {
+            v_idxs.min_by(|&a, &b| dist(a).total_cmp(&dist(b)))
+        };
+
+        let start_tet = match self.locate(point) {
+            Ok(Location3::InsideTet(handle) | Location3::OutsideHull(handle)) => Some(handle.idx()),
+            Ok(Location3::OnFacet(half_tri_idx)) => Some(half_tri_idx / 4),
+            Ok(Location3::OnEdge([v_idx, _]) | Location3::OnVertex(v_idx)) => {
+                self.tds.vertex_tet(v_idx)
+            }
+            Err(_) => None,
+        }
+        .or_else(|| (0..self.tds.num_tets()).find(|&tet_idx| self.is_live_tet(tet_idx)))?;
+        let mut v_idx = closest(
+            &mut self
+                .tds
+                .get_tet(start_tet)
+                .ok()?
+                .nodes()
+                .into_iter()
+                .filter_map(|node| node.idx()),
+        )?;
+
+        loop {
+            let neighbor = closest(
+                &mut self
+                    .incident_tets(v_idx)
+                    .into_iter()
+                    .flat_map(|tet_idx| self.tds.get_tet(tet_idx).unwrap().nodes())
+                    .filter_map(|node| node.idx()),
+            )?;
+            if dist(neighbor) >= dist(v_idx) {
+                return Some(v_idx);
+            }
+            v_idx = neighbor;
+        }
+    }
+
     pub const fn used_vertices(&self) -> &Vec<usize> {
         &self.used_vertices
     }
+
+    /// The vertices that are not part of the tetrahedralization, due to their weight.
+    pub fn redundant_vertices(&self) -> &[usize] {
+        &self.redundant_vertices
+    }
+
+    /// The vertices that are not part of the tetrahedralization, due to epsilon.
+    pub fn ignored_vertices(&self) -> &[usize] {
+        &self.ignored_vertices
+    }
+
+    /// The canonical vertex of each vertex, i.e. the vertex itself,
+    /// or for a duplicate the vertex at its position that was inserted before.
+    ///
+    /// The canonical vertex might not be part of the tetrahedralization, e.g. when it becomes redundant later.
+    #[must_use]
+    pub fn canonical_vertices(&self) -> Vec<VertexIdx> {
+        canonical_vertices(self.vertices.len(), &self.duplicate_of)
+    }
 }
 
 impl core::fmt::Display for Tetrahedralization {
@@ -903,6 +2756,14 @@ impl core::fmt::Display for Tetrahedralization {
     }
 }
 
+/// Whether `c` lies on the line through `a` and `b`, exactly, i.e. it does in all three coordinate projections.
+fn is_collinear(a: &Vertex3, b: &Vertex3, c: &Vertex3) -> bool {
+    [[0, 1], [1, 2], [2, 0]].iter().all(|&[i, j]| {
+        let [a, b, c]: [Vertex2; 3] = [a, b, c].map(|p| [p[i], p[j]]);
+        predicates::orient_2d(&a, &b, &c) == 0.0
+    })
+}
+
 #[cfg(all(test, feature = "logging"))]
 mod pre_test {
     #[test]
@@ -914,6 +2775,7 @@ mod pre_test {
 #[cfg(all(test, feature = "logging"))]
 mod tests {
     use super::*;
+    use crate::audit::SoundnessIssue;
     use rita_test_utils::{sample_vertices_3d, sample_weights};
 
     fn verify_tetrahedralization(tetrahedralization: &Tetrahedralization) {
@@ -921,6 +2783,30 @@ mod tests {
         let sound = tetrahedralization.is_sound().unwrap();
         assert_eq!(regularity, 1.0);
         assert!(sound);
+        assert!(
+            tetrahedralization
+                .check_regularity(RegularityCheck::Local, false)
+                .is_regular()
+        );
+
+        // the maintained counters match a full scan
+        let num_conceptual_tets = (0..tetrahedralization.num_tets())
+            .filter(|&tet_idx| {
+                tetrahedralization
+                    .tds()
+                    .get_tet(tet_idx)
+                    .unwrap()
+                    .is_conceptual()
+            })
+            .count();
+        assert_eq!(
+            tetrahedralization.num_conceptual_tets(),
+            num_conceptual_tets
+        );
+        assert_eq!(
+            tetrahedralization.num_casual_tets(),
+            tetrahedralization.tets().len()
+        );
     }
 
     const NUM_VERTICES_LIST: [usize; 7] = [4, 5, 10, 50, 100, 500, 1000];
@@ -944,41 +2830,1356 @@ mod tests {
     }
 
     #[test]
-    fn test_delaunay_3d() {
-        for n in NUM_VERTICES_LIST {
-            let vertices = sample_vertices_3d(n, None);
+    fn test_half_triangle_conventions() {
+        let vertices = sample_vertices_3d(50, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
 
-            let mut tetrahedralization = Tetrahedralization::new(None);
-            let result = tetrahedralization.insert_vertices(&vertices, None, true);
+        let sub = |a: Vertex3, b: Vertex3| -> Vertex3 { core::array::from_fn(|i| a[i] - b[i]) };
+        for tet_idx in 0..tetrahedralization.num_tets() {
+            let tet = tetrahedralization.tds().get_tet(tet_idx).unwrap();
+            let tet_nodes = tet.nodes();
+
+            for (i, tri) in tet.half_triangles().into_iter().enumerate() {
+                assert_eq!(tri.local_index(), i);
+                assert_eq!(tri.opposite_node(), tet_nodes[i]);
+                assert_eq!(tri.opposite_vertex(), tet_nodes[i].idx());
+                assert_eq!(tri.nodes(), tri.local_node_indices().map(|j| tet_nodes[j]));
+
+                // the oriented nodes are those of the opposite half triangle, up to rotation
+                let [n0, n1, n2] = tri.oriented_nodes();
+                let opposite = tri.opposite().nodes();
+                assert!([[n0, n1, n2], [n1, n2, n0], [n2, n0, n1]].contains(&opposite));
+
+                // the hedges of a half triangle share their edge with a reversed hedge in the same tet
+                for hedge in tri.hedges() {
+                    let neighbor = hedge.neighbor();
+                    assert_eq!(neighbor.tri().tet().idx(), tet_idx);
+                    assert_eq!(neighbor.first_node(), hedge.last_node());
+                    assert_eq!(neighbor.last_node(), hedge.first_node());
+                }
 
-            match result {
-                Ok(_) => (),
-                Err(e) => {
-                    log::error!("Error: {}", e);
+                // the normal of the nodes points away from the opposite vertex
+                if let ([Some(a), Some(b), Some(c)], Some(d)) =
+                    (tri.nodes().map(|n| n.idx()), tri.opposite_vertex())
+                {
+                    let [a, b, c, d] = [a, b, c, d].map(|v_idx| vertices[v_idx]);
+                    let [u, v, w] = [sub(b, a), sub(c, a), sub(d, a)];
+                    let normal = [
+                        u[1] * v[2] - u[2] * v[1],
+                        u[2] * v[0] - u[0] * v[2],
+                        u[0] * v[1] - u[1] * v[0],
+                    ];
+                    assert!(normal[0] * w[0] + normal[1] * w[1] + normal[2] * w[2] < 0.0);
                 }
             }
-
-            verify_tetrahedralization(&tetrahedralization);
         }
     }
 
     #[test]
-    fn test_weighted_delaunay_3d() {
-        for n in NUM_VERTICES_LIST {
-            let vertices = sample_vertices_3d(n, None);
-            let weights = sample_weights(n, None);
+    fn test_errors() {
+        fn reason<T: core::fmt::Debug>(result: HowResult<T>) -> RitaError {
+            *result.unwrap_err().downcast_ref::<RitaError>().unwrap()
+        }
 
-            let mut tetrahedralization = Tetrahedralization::new(None);
-            let result = tetrahedralization.insert_vertices(&vertices, Some(weights), true);
+        let vertices = sample_vertices_3d(3, None);
+        assert_eq!(
+            reason(Tetrahedralization::new(None).insert_vertices(&vertices, None, true)),
+            RitaError::NotEnoughVertices { required: 4 }
+        );
 
-            match result {
-                Ok(_) => (),
-                Err(e) => {
-                    log::error!("Error: {}", e);
-                }
-            }
+        let coplanar = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ];
+        assert_eq!(
+            reason(Tetrahedralization::new(None).insert_vertices(coplanar, None, true)),
+            RitaError::AllPointsCoplanar
+        );
+        assert_eq!(
+            reason(Tetrahedralization::new(None).insert_vertex([0.0; 3], None, None)),
+            RitaError::EmptyTriangulation
+        );
+        #[cfg(not(feature = "wasm"))]
+        assert_eq!(
+            reason(Tetrahedralization::new(None).insert_vertices(
+                &vertices,
+                Some(vec![0.0; 4]),
+                true
+            )),
+            RitaError::WeightsMismatch {
+                num_weights: 4,
+                num_vertices: 3
+            }
+        );
+    }
+
+    #[cfg(feature = "exact")]
+    #[test]
+    fn test_is_regular_exact() {
+        let vertices = sample_vertices_3d(50, None);
+
+        for order in [InsertOrder::Hilbert, InsertOrder::Random(7)] {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization
+                .insert_vertices_ordered(&vertices, None, order)
+                .unwrap();
+            assert!(tetrahedralization.is_regular_exact().unwrap());
+        }
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, Some(sample_weights(50, None)), true)
+            .unwrap();
+        assert!(tetrahedralization.is_regular_exact().unwrap());
+
+        // a grid, where every eight vertices of a cell are cospherical
+        let grid: Vec<Vertex3> = (0..64)
+            .map(|i| [(i % 4) as f64, (i / 4 % 4) as f64, (i / 16) as f64])
+            .collect();
+        for order in [
+            InsertOrder::Input,
+            InsertOrder::Hilbert,
+            InsertOrder::Random(7),
+        ] {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization
+                .insert_vertices_ordered(&grid, None, order)
+                .unwrap();
+            assert_eq!(tetrahedralization.num_used_vertices(), 64);
+            assert!(tetrahedralization.is_regular_exact().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_insert_vertices_or_degenerate() {
+        let coplanar = [
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.5, 0.5, 1.0],
+        ];
+        let Insertion::Degenerate(Degenerate3::Coplanar {
+            plane,
+            triangulation,
+        }) = Tetrahedralization::new(None)
+            .insert_vertices_or_degenerate(&coplanar, None, InsertOrder::Hilbert)
+            .unwrap()
+        else {
+            panic!("expected coplanar vertices");
+        };
+        assert_eq!(plane.normal()[2].abs(), 1.0);
+        assert_eq!(triangulation.num_used_vertices(), 5);
+        assert!(triangulation.is_sound().unwrap());
+
+        let collinear = [
+            [0.0, 0.0, 0.0],
+            [0.3, 0.1, 0.7],
+            [0.6, 0.2, 1.4],
+            [-0.3, -0.1, -0.7],
+        ];
+        let Insertion::Degenerate(Degenerate3::Collinear(chain)) = Tetrahedralization::new(None)
+            .insert_vertices_or_degenerate(&collinear, None, InsertOrder::Hilbert)
+            .unwrap()
+        else {
+            panic!("expected collinear vertices");
+        };
+        assert_eq!(chain.v_idxs, vec![3, 0, 1, 2]);
+
+        let vertices = sample_vertices_3d(20, None);
+        let Insertion::Inserted(statuses) = Tetrahedralization::new(None)
+            .insert_vertices_or_degenerate(&vertices, None, InsertOrder::Hilbert)
+            .unwrap()
+        else {
+            panic!("expected a tetrahedralization");
+        };
+        assert_eq!(statuses.len(), 20);
+
+        // the first vertices of a grid are collinear, which must not make it coplanar
+        for n in [3, 4, 5] {
+            let grid: Vec<Vertex3> = (0..n * n * n)
+                .map(|i| [i % n, i / n % n, i / (n * n)].map(f64::from))
+                .collect();
+            for order in [InsertOrder::Input, InsertOrder::Hilbert] {
+                let mut tetrahedralization = Tetrahedralization::new(None);
+                let Insertion::Inserted(_) = tetrahedralization
+                    .insert_vertices_or_degenerate(&grid, None, order)
+                    .unwrap()
+                else {
+                    panic!("expected a tetrahedralization of the {n}x{n}x{n} grid");
+                };
+                assert!(tetrahedralization.is_sound().unwrap());
+                assert_eq!(tetrahedralization.num_used_vertices(), grid.len());
+                // the symbolic perturbation breaks the ties of the cospherical vertices consistently
+                assert!(
+                    (0..tetrahedralization.num_tets())
+                        .all(|tet_idx| !tetrahedralization.is_tet_flat(tet_idx).unwrap())
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(50, None), None, true)
+            .unwrap();
+
+        let json = serde_json::to_string(&tetrahedralization).unwrap();
+        let mut reloaded: Tetrahedralization = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.tet_indices(), tetrahedralization.tet_indices());
+
+        reloaded.insert_vertex([0.5, 0.5, 0.5], None, None).unwrap();
+        verify_tetrahedralization(&reloaded);
+    }
+
+    #[test]
+    fn test_hull_facets() {
+        let vertices = sample_vertices_3d(50, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let hull = tetrahedralization.hull_facets();
+        assert_eq!(hull.len(), tetrahedralization.num_hull_facets());
+
+        // every other vertex is below, or on, every outward facing hull facet
+        for facet in hull {
+            let [a, b, c] = facet.map(|v_idx| vertices[v_idx]);
+            assert!(
+                (0..vertices.len())
+                    .filter(|v_idx| !facet.contains(v_idx))
+                    .all(|v_idx| predicates::orient_3d(&a, &b, &c, &vertices[v_idx]) >= 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_boundary_matrices() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(50, None), None, true)
+            .unwrap();
+
+        let complex = tetrahedralization.boundary_matrices();
+
+        // Euler characteristic of a ball
+        assert_eq!(
+            (complex.vertices.len() + complex.tris.len()) as isize
+                - (complex.edges.len() + complex.tets.len()) as isize,
+            1
+        );
+
+        // interior triangles cancel out, only the hull triangles remain on the boundary of the whole ball
+        let d3 = complex.d3.unwrap();
+        let mut tri_sums = vec![0; complex.tris.len()];
+        for &(row, _, value) in &d3.entries {
+            tri_sums[row] += value;
+        }
+        assert_eq!(
+            tri_sums.iter().filter(|&&sum| sum != 0).count(),
+            tetrahedralization.num_hull_facets()
+        );
+    }
+
+    #[test]
+    fn test_quality_histograms() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(50, None), None, true)
+            .unwrap();
+
+        let qualities = tetrahedralization.tet_qualities();
+        assert_eq!(qualities.len(), tetrahedralization.tets().len());
+        assert!(qualities.iter().all(|q| q.volume > 0.0));
+        assert!(qualities.iter().all(|q| q.aspect_ratio >= 1.0 - 1e-9));
+
+        let histograms = tetrahedralization.quality_histograms(8);
+        assert_eq!(histograms.aspect_ratio.counts.len(), 8);
+        assert_eq!(histograms.min_angle.num_values(), qualities.len());
+    }
+
+    #[test]
+    fn test_insert_order() {
+        let vertices = sample_vertices_3d(500, None);
+
+        for order in [
+            InsertOrder::Input,
+            InsertOrder::Hilbert,
+            InsertOrder::Random(42),
+            InsertOrder::WeightedHilbert,
+            InsertOrder::Morton,
+            InsertOrder::Brio(42),
+        ] {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization
+                .insert_vertices_ordered(&vertices, None, order)
+                .unwrap();
+            verify_tetrahedralization(&tetrahedralization);
+        }
+
+        // the same seed results in the same construction
+        let tets = [7, 7].map(|seed| {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization
+                .insert_vertices_ordered(&vertices, None, InsertOrder::Random(seed))
+                .unwrap();
+            tetrahedralization.tets()
+        });
+        assert_eq!(tets[0], tets[1]);
+    }
+
+    #[test]
+    fn test_sorted_insertion() {
+        // sorted along x, such that every vertex is inserted beyond the current hull
+        let mut vertices = sample_vertices_3d(500, None);
+        vertices.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, false)
+            .unwrap();
+
+        assert_eq!(tetrahedralization.num_used_vertices(), 500);
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_soundness_report() {
+        let vertices = sample_vertices_3d(20, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert!(tetrahedralization.soundness_report().is_sound());
+
+        let tet_idx = tetrahedralization.num_tets() - 1;
+        tetrahedralization.tds.should_del_tet[tet_idx] = true;
+
+        let report = tetrahedralization.soundness_report();
+        assert_eq!(report.issues, vec![SoundnessIssue::Unfinished(tet_idx)]);
+        assert!(!tetrahedralization.is_sound().unwrap());
+    }
+
+    #[test]
+    fn test_try_repair() {
+        let vertices = sample_vertices_3d(20, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert!(tetrahedralization.try_repair().is_sound());
+
+        // an insertion interrupted after marking its cavity, and a stale opposite link
+        tetrahedralization.tds.bw_start(0).unwrap();
+        tetrahedralization.tds.half_tri_opposite.set(1, 4);
+        assert!(!tetrahedralization.soundness_report().is_sound());
+
+        assert!(tetrahedralization.try_repair().is_sound());
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_describe_tet() {
+        let vertices = sample_vertices_3d(20, None);
+        let weights = sample_weights(20, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+
+        let tet_idx = (0..tetrahedralization.num_tets())
+            .find(|&tet_idx| {
+                tetrahedralization
+                    .tds()
+                    .get_tet(tet_idx)
+                    .unwrap()
+                    .is_conceptual()
+            })
+            .unwrap();
+        let description = tetrahedralization.describe_tet(tet_idx).unwrap();
+        assert!(description.starts_with(&format!("Tetrahedron {tet_idx} (conceptual)")));
+        assert!(description.contains("  Conceptual\n"));
+        assert_eq!(description.matches(" with weight ").count(), 3);
+        assert!(description.contains("neighbors: "));
+
+        assert!(tetrahedralization.describe_tet(usize::MAX / 4).is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // the query points are built from vertices well inside the hull, as points on a hull edge or facet
+        // may round to the outside
+        let mut vertices = sample_vertices_3d(3, Some(0.4..=0.6));
+        vertices.extend(sample_vertices_3d(47, Some(0.0..=1.0)));
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        // a linear function is reproduced exactly, up to rounding
+        let f = |[x, y, z]: Vertex3| 2.0 * x - y + 0.5 * z + 1.0;
+        let values: Vec<f64> = vertices.iter().map(|&v| f(v)).collect();
+
+        let [a, b, c] = [vertices[0], vertices[1], vertices[2]];
+        let centroid = core::array::from_fn(|i| (a[i] + b[i] + c[i]) / 3.0);
+        let midpoint = core::array::from_fn(|i| (a[i] + b[i]) / 2.0);
+        for point in [a, centroid, midpoint] {
+            let value = tetrahedralization.interpolate(point, &values).unwrap();
+            assert!((value - f(point)).abs() < 1e-9);
+        }
+
+        assert_eq!(tetrahedralization.interpolate([2.0; 3], &values), None);
+        assert_eq!(tetrahedralization.interpolate(centroid, &[]), None);
+    }
+
+    #[test]
+    fn test_locate() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        assert!(tetrahedralization.locate([0.0; 3]).is_err());
+
+        let vertices = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 1.0, 2.0],
+        ];
+        tetrahedralization
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        for (v_idx, &v) in vertices.iter().enumerate() {
+            assert_eq!(
+                tetrahedralization.locate(v).unwrap(),
+                Location3::OnVertex(v_idx)
+            );
+        }
+
+        assert_eq!(
+            tetrahedralization.locate([0.5, 0.0, 0.0]).unwrap(),
+            Location3::OnEdge([0, 1])
+        );
+
+        let Ok(Location3::OnFacet(tri_idx)) = tetrahedralization.locate([0.25, 0.25, 0.0]) else {
+            unreachable!("expected the point on a facet");
+        };
+        let tri = tetrahedralization.tds().get_half_tri(tri_idx).unwrap();
+        let mut facet = tri.nodes().map(|node| node.idx().unwrap());
+        facet.sort_unstable();
+        assert_eq!(facet, [0, 1, 2]);
+
+        let Ok(Location3::InsideTet(handle)) = tetrahedralization.locate([0.1, 0.1, 0.1]) else {
+            unreachable!("expected the point inside a tet");
+        };
+        let tet_idx = tetrahedralization.resolve_tet(handle).unwrap();
+        assert!(
+            tetrahedralization
+                .tds()
+                .get_tet(tet_idx)
+                .unwrap()
+                .is_casual()
+        );
+
+        for point in [[5.0, 5.0, 5.0], [-1.0, -1.0, -1.0]] {
+            let Ok(Location3::OutsideHull(handle)) = tetrahedralization.locate(point) else {
+                unreachable!("expected the point outside the hull");
+            };
+            let tet_idx = tetrahedralization.resolve_tet(handle).unwrap();
+            assert!(
+                tetrahedralization
+                    .tds()
+                    .get_tet(tet_idx)
+                    .unwrap()
+                    .is_conceptual()
+            );
+        }
+
+        // every vertex is found, also after walking through many tets
+        let vertices = sample_vertices_3d(200, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        for (v_idx, &v) in vertices.iter().enumerate() {
+            assert_eq!(
+                tetrahedralization.locate(v).unwrap(),
+                Location3::OnVertex(v_idx)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tet_handles() {
+        // the enclosing tet contains the queries
+        let mut vertices = sample_vertices_3d(50, None);
+        vertices.extend([
+            [-1.0, -1.0, -1.0],
+            [5.0, -1.0, -1.0],
+            [-1.0, 5.0, -1.0],
+            [-1.0, -1.0, 5.0],
+        ]);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert!(tetrahedralization.tet_handle(usize::MAX).is_err());
+
+        let handle = match tetrahedralization.locate([0.01, 0.02, 0.03]).unwrap() {
+            Location3::InsideTet(handle) => handle,
+            location => unreachable!("expected the point inside a tet, got {location:?}"),
+        };
+        let tet_idx = tetrahedralization.resolve_tet(handle).unwrap();
+        assert_eq!(tetrahedralization.tet_handle(tet_idx).unwrap(), handle);
+
+        // a handle is stale after an insertion, but still works as a hint
+        tetrahedralization
+            .insert_vertex([0.03, 0.02, 0.01], None, Some(handle))
+            .unwrap();
+        let error = tetrahedralization.resolve_tet(handle).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RitaError>(),
+            Some(RitaError::StaleHandle { .. })
+        ));
+        tetrahedralization
+            .insert_vertex([0.02, 0.03, 0.01], None, Some(handle))
+            .unwrap();
+
+        // a hint to a tet that does not exist falls back to the default walk
+        tetrahedralization
+            .insert_vertex(
+                [0.01, 0.03, 0.02],
+                None,
+                Some(TetHandle::new(usize::MAX, 0)),
+            )
+            .unwrap();
+        assert_eq!(tetrahedralization.num_used_vertices(), vertices.len() + 3);
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_insert_vertices_presorted() {
+        let vertices = sample_vertices_3d(100, Some(10.0..=20.0));
+        let order = sort_along_hilbert_curve_3d(&vertices, (0..vertices.len()).collect());
+        let sorted: Vec<Vertex3> = order.iter().map(|&v_idx| vertices[v_idx]).collect();
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let statuses = tetrahedralization
+            .insert_vertices_presorted(&sorted, None, [[10.0; 3], [20.0; 3]])
+            .unwrap();
+        verify_tetrahedralization(&tetrahedralization);
+        assert!(
+            statuses
+                .iter()
+                .all(|&status| status == InsertStatus::Inserted)
+        );
+        assert_eq!(tetrahedralization.origin(), [15.0; 3]);
+
+        let mut reference = Tetrahedralization::new(None);
+        reference.insert_vertices(&vertices, None, true).unwrap();
+        assert_eq!(tetrahedralization.num_tets(), reference.num_tets());
+    }
+
+    #[test]
+    fn test_weighted_redundancy_near_hull() {
+        let vertices = [
+            [0.0, 0.0, 0.0],
+            [4.0, 0.0, 0.0],
+            [0.0, 4.0, 0.0],
+            [0.0, 0.0, 4.0],
+            [1.0, 1.0, 1.0],
+        ];
+
+        // the power circle of the hull facet at z = 0 lifts [1, 1, 0] to a height of 8, i.e. it is redundant below a weight of -6
+        let cases = [
+            ([1.0, 1.0, 0.0], -10.0, InsertStatus::Redundant),
+            ([1.0, 1.0, 0.0], -1.0, InsertStatus::Inserted),
+            ([1.0, 1.0, 1e-6], -10.0, InsertStatus::Redundant),
+            // beyond the hull a vertex is never redundant, no matter its weight
+            ([1.0, 1.0, -1e-6], -100.0, InsertStatus::Inserted),
+        ];
+
+        for (v, weight, expected) in cases {
+            // inserted last and first, as the vertices are popped from the back
+            for at_front in [true, false] {
+                let mut vertices = vertices.to_vec();
+                let mut weights = vec![0.0; vertices.len()];
+                let v_idx = if at_front { 0 } else { vertices.len() };
+                vertices.insert(v_idx, v);
+                weights.insert(v_idx, weight);
+
+                let mut tetrahedralization = Tetrahedralization::new(None);
+                let statuses = tetrahedralization
+                    .insert_vertices(&vertices, Some(weights), false)
+                    .unwrap();
+
+                assert_eq!(statuses[v_idx], expected, "{v:?} with weight {weight}");
+                verify_tetrahedralization(&tetrahedralization);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_vertices_on_hull_facets() {
+        let mut vertices = vec![
+            [0.0, 0.0, 0.0],
+            [4.0, 0.0, 0.0],
+            [0.0, 4.0, 0.0],
+            [0.0, 0.0, 4.0],
+        ];
+        vertices.extend(sample_vertices_3d(20, Some(0.5..=1.0)));
+        // on the hull facet at z = 0
+        vertices.extend(
+            sample_vertices_3d(40, Some(0.1..=1.9))
+                .into_iter()
+                .map(|[x, y, _]| [x, y, 0.0]),
+        );
+        let weights = sample_weights(vertices.len(), Some((-1.0, 1.0)));
+
+        let mut inserted = Vec::new();
+        for order in [
+            InsertOrder::Input,
+            InsertOrder::Hilbert,
+            InsertOrder::Random(7),
+        ] {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            let statuses = tetrahedralization
+                .insert_vertices_ordered(&vertices, Some(weights.clone()), order)
+                .unwrap();
+            verify_tetrahedralization(&tetrahedralization);
+            inserted.push(statuses);
+        }
+
+        // the regular tetrahedralization, and so its vertices, do not depend on the insertion order
+        assert_eq!(inserted[0], inserted[1]);
+        assert_eq!(inserted[0], inserted[2]);
+    }
+
+    #[test]
+    fn test_insert_vertices_with_progress() {
+        let vertices = sample_vertices_3d(1500, None);
+
+        let mut num_processed = Vec::new();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices_with_progress(&vertices, None, InsertOrder::Hilbert, |progress| {
+                num_processed.push(progress.num_processed);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        assert_eq!(num_processed, [1024, 1500]);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let statuses = tetrahedralization
+            .insert_vertices_with_progress(&vertices, None, InsertOrder::Hilbert, |_| {
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|&&s| s == InsertStatus::Cancelled)
+                .count(),
+            1500 - 1024
+        );
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_insert_statuses() {
+        let mut vertices = sample_vertices_3d(100, None);
+        vertices.push(vertices[42]);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let statuses = tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        assert_eq!(statuses.len(), 101);
+        let mut duplicates = [statuses[42], statuses[100]];
+        duplicates.sort_by_key(|&s| s == InsertStatus::Duplicate);
+        assert_eq!(
+            duplicates,
+            [InsertStatus::Inserted, InsertStatus::Duplicate]
+        );
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|&&s| s == InsertStatus::Inserted)
+                .count(),
+            100
+        );
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_incident_tets() {
+        let vertices = sample_vertices_3d(200, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let tds = tetrahedralization.tds();
+        let scan = |node: VertexNode| -> Vec<usize> {
+            (0..tds.num_tets())
+                .filter(|&tet_idx| tds.get_tet(tet_idx).unwrap().nodes().contains(&node))
+                .collect()
+        };
+        for v_idx in 0..vertices.len() {
+            let expected: Vec<usize> = scan(VertexNode::Casual(v_idx))
+                .into_iter()
+                .filter(|&tet_idx| tds.get_tet(tet_idx).unwrap().is_casual())
+                .collect();
+            assert_eq!(tetrahedralization.incident_tets(v_idx), expected);
+        }
+        assert!(tetrahedralization.incident_tets(200).is_empty());
+
+        // the star of the conceptual node are the tets at the hull
+        let conceptual: Vec<usize> = tds
+            .get_tet_containing(&VertexNode::Conceptual)
+            .iter()
+            .map(|tet| tet.idx())
+            .collect();
+        assert_eq!(conceptual, scan(VertexNode::Conceptual));
+        assert_eq!(conceptual.len(), tds.num_conceptual_tets());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_tds_lookups() {
+        let vertices = sample_vertices_3d(100, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let tds = tetrahedralization.tds();
+
+        for tet_idx in 0..tds.num_tets() {
+            for half_tri in tds.get_tet(tet_idx).unwrap().half_triangles() {
+                let [node0, node1, node2] = half_tri.nodes();
+                let found = tds.get_half_tri_containing(&node0, &node1, &node2).unwrap();
+                let found_nodes = found.nodes();
+                assert!(
+                    [node0, node1, node2]
+                        .iter()
+                        .all(|node| found_nodes.contains(node))
+                );
+
+                // one hedge from node0 to node1 in each tet around the edge
+                let hedges = tds.get_hedge_containing(&node0, &node1);
+                assert!(hedges.len() >= 3);
+                for hedge in hedges {
+                    assert_eq!([hedge.first_node(), hedge.last_node()], [node0, node1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(100, None), None, true)
+            .unwrap();
+
+        let edges: Vec<(VertexIdx, VertexIdx)> = tetrahedralization.edges().collect();
+        assert!(edges.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let complex = tetrahedralization.boundary_matrices();
+        assert_eq!(edges.len(), complex.edges.len());
+        assert!(
+            complex
+                .edges
+                .iter()
+                .all(|&[v0, v1]| edges.binary_search(&(v0, v1)).is_ok())
+        );
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_to_graph() {
+        let vertices = sample_vertices_3d(100, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let graph = tetrahedralization.to_graph();
+        assert_eq!(graph.node_count(), vertices.len());
+        assert_eq!(graph.edge_count(), tetrahedralization.edges().count());
+        assert_eq!(petgraph::algo::connected_components(&graph), 1);
+        assert_eq!(graph[petgraph::graph::NodeIndex::new(42)], vertices[42]);
+    }
+
+    #[test]
+    fn test_nearest_vertex() {
+        let vertices = sample_vertices_3d(300, None);
+        let weights = sample_weights(300, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        assert_eq!(tetrahedralization.nearest_vertex([0.0; 3]), None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let mut weighted = Tetrahedralization::new(None);
+        weighted
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+
+        // also beyond the hull
+        for query in sample_vertices_3d(100, Some(-1.0..=1.0)) {
+            let dist_sq = |v_idx: VertexIdx| {
+                (0..3)
+                    .map(|i| (vertices[v_idx][i] - query[i]).powi(2))
+                    .sum::<f64>()
+            };
+            let power = |v_idx: VertexIdx| dist_sq(v_idx) - weights[v_idx];
+            let min = |f: &dyn Fn(VertexIdx) -> f64| {
+                (0..vertices.len()).map(f).fold(f64::INFINITY, f64::min)
+            };
+
+            let nearest = tetrahedralization.nearest_vertex(query).unwrap();
+            assert_eq!(dist_sq(nearest), min(&dist_sq));
+            assert_eq!(
+                tetrahedralization.nearest_power_vertex(query),
+                Some(nearest)
+            );
+
+            // the power cells of redundant vertices are empty
+            assert_eq!(
+                power(weighted.nearest_power_vertex(query).unwrap()),
+                min(&power)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tet_neighbors() {
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(100, None), None, true)
+            .unwrap();
+
+        let mut num_across_hull = 0;
+        for tet_idx in 0..tetrahedralization.tds().num_tets() {
+            let tet = tetrahedralization.tds().get_tet(tet_idx).unwrap();
+            if tet.should_del() || tet.is_conceptual() {
+                continue;
+            }
+            let neighbors = tetrahedralization.tet_neighbors(tet_idx).unwrap();
+            for (node, neighbor) in tet.nodes().into_iter().zip(neighbors) {
+                match neighbor {
+                    Some(neighbor_idx) => {
+                        let neighbor = tetrahedralization.tds().get_tet(neighbor_idx).unwrap();
+                        assert!(!neighbor.nodes().contains(&node));
+                        assert!(
+                            tetrahedralization
+                                .tet_neighbors(neighbor_idx)
+                                .unwrap()
+                                .contains(&Some(tet_idx))
+                        );
+                    }
+                    None => num_across_hull += 1,
+                }
+            }
+        }
+
+        // one casual tet per hull facet, which is adjacent to a conceptual tet
+        assert_eq!(
+            num_across_hull,
+            tetrahedralization.tds().num_conceptual_tets()
+        );
+        assert!(tetrahedralization.tet_neighbors(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        let mut vertices = sample_vertices_3d(100, None);
+        vertices.push(vertices[42]);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        let statuses = tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        // the duplicate maps to the one of both inserted first, all other vertices to themselves
+        let (kept, skipped) = if statuses[42] == InsertStatus::Inserted {
+            (42, 100)
+        } else {
+            (100, 42)
+        };
+        let mut expected: Vec<VertexIdx> = (0..vertices.len()).collect();
+        expected[skipped] = kept;
+        assert_eq!(tetrahedralization.canonical_vertices(), expected);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.set_duplicate_policy(DuplicatePolicy::Error);
+        let err = tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RitaError>(),
+            Some(&RitaError::DuplicateVertex {
+                v_idx: skipped,
+                canonical: kept
+            })
+        );
+
+        // the weights of duplicates are merged, such that the kept vertex is the heaviest
+        let mut weights = vec![0.0; vertices.len()];
+        weights[skipped] = 0.5;
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.set_duplicate_policy(DuplicatePolicy::MergeWeights);
+        tetrahedralization
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+        verify_tetrahedralization(&tetrahedralization);
+        assert_eq!(tetrahedralization.canonical_vertices(), expected);
+        assert_eq!(tetrahedralization.weights.as_ref().unwrap()[kept], 0.5);
+    }
+
+    #[test]
+    fn test_fast_inexact() {
+        let vertices = sample_vertices_3d(500, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.set_predicate_mode(PredicateMode::FastInexact { tolerance: 1e-12 });
+        assert!(!tetrahedralization.predicate_mode().is_exact());
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        // the tolerance is far above the rounding errors, so the result is regular nonetheless
+        verify_tetrahedralization(&tetrahedralization);
+
+        // without a tolerance, the cospherical vertices of a grid are decided by rounding errors, the structure stays valid
+        let grid: Vec<Vertex3> = (0..512)
+            .map(|i| [i % 8, i / 8 % 8, i / 64].map(|x| f64::from(x) * 0.1))
+            .collect();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.set_predicate_mode(PredicateMode::FastInexact { tolerance: 0.0 });
+        tetrahedralization
+            .insert_vertices(&grid, None, true)
+            .unwrap();
+        assert!(tetrahedralization.is_sound().unwrap());
+        assert_eq!(tetrahedralization.num_used_vertices(), grid.len());
+        assert!(
+            (0..tetrahedralization.num_tets())
+                .all(|tet_idx| !tetrahedralization.is_tet_flat(tet_idx).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_walk_cap() {
+        // an elongated domain, with long walks between consecutive vertices
+        let vertices: Vec<_> = sample_vertices_3d(500, None)
+            .into_iter()
+            .map(|[x, y, z]| [100.0 * x, y, z])
+            .collect();
+
+        for walk_cap in [WalkCap::Quarter, WalkCap::Fixed(0), WalkCap::Auto] {
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            tetrahedralization.set_walk_cap(walk_cap);
+            tetrahedralization
+                .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+                .unwrap();
+
+            assert_eq!(tetrahedralization.num_used_vertices(), 500);
+            verify_tetrahedralization(&tetrahedralization);
+
+            let stats = tetrahedralization.walk_stats();
+            assert!(stats.num_walks() > 0);
+            if walk_cap == WalkCap::Fixed(0) {
+                // every walk that takes a step falls back
+                assert_eq!(stats.num_capped, stats.num_walks() - stats.histogram[0]);
+                assert!(stats.num_fallbacks >= stats.num_capped);
+            }
+            assert!(stats.mean_len() <= stats.max_len as f64);
+            assert_eq!(stats.num_bisector_cases, 0);
+        }
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_walk_fallback() {
+        let vertices: Vec<_> = sample_vertices_3d(500, None)
+            .into_iter()
+            .map(|[x, y, z]| [100.0 * x, y, z])
+            .collect();
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        assert_eq!(tetrahedralization.walk_fallback(), WalkFallback::LinearScan);
+        tetrahedralization.set_walk_cap(WalkCap::Fixed(8));
+        tetrahedralization.set_walk_fallback(WalkFallback::Restarts {
+            num_restarts: 4,
+            num_samples: 32,
+        });
+        tetrahedralization
+            .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+            .unwrap();
+
+        assert_eq!(tetrahedralization.num_used_vertices(), 500);
+        verify_tetrahedralization(&tetrahedralization);
+
+        // each linear scan follows all restarts of a locate
+        let stats = tetrahedralization.walk_stats();
+        assert!(stats.num_restarts > 0);
+        assert!(4 * stats.num_fallbacks <= stats.num_restarts);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_locate_grid() {
+        let vertices = sample_vertices_3d(1000, None);
+
+        let mut plain = Tetrahedralization::new(None);
+        plain
+            .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+            .unwrap();
+        let mut gridded = Tetrahedralization::new(None);
+        gridded.set_locate_grid(true);
+        assert!(gridded.uses_locate_grid());
+        gridded
+            .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+            .unwrap();
+
+        // the same tetrahedralization, with shorter walks in a random order
+        verify_tetrahedralization(&gridded);
+        assert_eq!(gridded.tds().num_tets(), plain.tds().num_tets());
+        assert!(gridded.walk_stats().mean_len() < plain.walk_stats().mean_len());
+        for v in &vertices[..100] {
+            assert!(matches!(
+                gridded.locate(*v).unwrap(),
+                Location3::OnVertex(_)
+            ));
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_stats() {
+        let vertices = sample_vertices_3d(100, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let stats = tetrahedralization.stats();
+        // every cavity has at least the four boundary triangles of a single tetrahedron
+        assert!(stats.peak_bw_boundary_tris >= 4);
+        assert!(stats.peak_bw_tets_to_check >= 4);
+        assert!(stats.peak_bw_tets_to_del >= 1);
+        assert!(stats.peak_bw_tets_to_keep >= 1);
+        assert_eq!(stats.peak_hedge_stack, 0);
+
+        // all but the vertices of the initial tet are inserted by Bowyer-Watson
+        assert_eq!(stats.num_bw_cavities, 96);
+        assert_eq!(stats.num_flips_1_4 + stats.num_flips_2_3, 0);
+        assert!(stats.num_simplices_created > stats.num_simplices_deleted);
+        assert_eq!(stats.num_walks, tetrahedralization.walk_stats().num_walks());
+        assert_eq!(
+            stats.peak_walk_steps,
+            tetrahedralization.walk_stats().max_len
+        );
+        #[cfg(feature = "geogram")]
+        assert!(stats.num_filtered_predicates > 0);
+
+        // the scratch buffers only grow with the peak cavity, and not at all when reserved for it
+        assert!(stats.bw_scratch_growths >= 1);
+        assert!(stats.bw_scratch_growths <= stats.peak_bw_boundary_tris);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.reserve_insertion_scratch(stats.peak_bw_boundary_tris);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert_eq!(tetrahedralization.stats().bw_scratch_growths, 0);
+    }
+
+    #[test]
+    fn test_delaunay_3d() {
+        for n in NUM_VERTICES_LIST {
+            let vertices = sample_vertices_3d(n, None);
+
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            let result = tetrahedralization.insert_vertices(&vertices, None, true);
+
+            match result {
+                Ok(_) => (),
+                Err(e) => {
+                    log::error!("Error: {}", e);
+                }
+            }
+
+            verify_tetrahedralization(&tetrahedralization);
+        }
+    }
+
+    #[test]
+    fn test_weighted_delaunay_3d() {
+        for n in NUM_VERTICES_LIST {
+            let vertices = sample_vertices_3d(n, None);
+            let weights = sample_weights(n, None);
+
+            let mut tetrahedralization = Tetrahedralization::new(None);
+            let result = tetrahedralization.insert_vertices(&vertices, Some(weights), true);
+
+            match result {
+                Ok(_) => (),
+                Err(e) => {
+                    log::error!("Error: {}", e);
+                }
+            }
+
+            verify_tetrahedralization(&tetrahedralization);
+        }
+    }
+
+    #[test]
+    fn test_flip_insertion() {
+        fn sorted_tets(tetrahedralization: &Tetrahedralization) -> Vec<[VertexIdx; 4]> {
+            let mut tets: Vec<[VertexIdx; 4]> = tetrahedralization
+                .tet_indices()
+                .into_iter()
+                .map(|mut tet| {
+                    tet.sort_unstable();
+                    tet
+                })
+                .collect();
+            tets.sort_unstable();
+            tets
+        }
+
+        let grid: Vec<Vertex3> = (0..512)
+            .map(|i| [i % 8, i / 8 % 8, i / 64].map(|x| f64::from(x) * 0.1))
+            .collect();
+        // the cospherical vertices of a grid lie on the power spheres of the tets, so only its soundness is checked
+        let inputs = [
+            (sample_vertices_3d(500, None), None, true),
+            (
+                sample_vertices_3d(500, None),
+                Some(sample_weights(500, None)),
+                true,
+            ),
+            (grid, None, false),
+        ];
+
+        for (vertices, weights, is_general) in inputs {
+            let [flips, bowyer_watson] =
+                [InsertionStrategy::Flips, InsertionStrategy::BowyerWatson].map(
+                    |insertion_strategy| {
+                        let mut tetrahedralization = Tetrahedralization::new(None);
+                        tetrahedralization.set_insertion_strategy(insertion_strategy);
+                        tetrahedralization
+                            .insert_vertices(&vertices, weights.clone(), true)
+                            .unwrap();
+                        if is_general {
+                            verify_tetrahedralization(&tetrahedralization);
+                        } else {
+                            assert!(tetrahedralization.is_sound().unwrap());
+                        }
+                        tetrahedralization
+                    },
+                );
+
+            assert_eq!(flips.insertion_strategy(), InsertionStrategy::Flips);
+            assert_eq!(sorted_tets(&flips), sorted_tets(&bowyer_watson));
+            assert_eq!(
+                flips.redundant_vertices(),
+                bowyer_watson.redundant_vertices()
+            );
+        }
+
+        // single insertions into the interior are tracked as well
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization.set_insertion_strategy(InsertionStrategy::Flips);
+        tetrahedralization
+            .insert_vertices(sample_vertices_3d(100, None), None, true)
+            .unwrap();
+        for v in sample_vertices_3d(20, Some(-0.3..=0.3)) {
+            let changes = tetrahedralization
+                .insert_vertex_tracked(v, None, None)
+                .unwrap();
+            assert!(!changes.created.is_empty());
+            assert!(
+                changes
+                    .created
+                    .iter()
+                    .all(|&tet_idx| tet_idx < tetrahedralization.num_tets())
+            );
+        }
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_eps_beyond_hull() {
+        let mut tetrahedralization = Tetrahedralization::new(Some(1e-3));
+        tetrahedralization
+            .insert_vertices(
+                [
+                    [0.0, 0.0, 0.0],
+                    [1.0, 0.0, 0.0],
+                    [0.0, 1.0, 0.0],
+                    [0.0, 0.0, 1.0],
+                ],
+                None,
+                false,
+            )
+            .unwrap();
+
+        // close to hull vertices, also in the lifted space
+        for v in [[-5e-4, -2e-4, -2e-4], [1.0 + 5e-4, 0.0, 0.0]] {
+            tetrahedralization.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(tetrahedralization.ignored_vertices(), &[4, 5]);
+
+        // the lifted vertex is far below the lifted hull facet, like inside the hull next to the facet
+        for v in [[0.2, 0.2, -1e-4], [2.0, 2.0, 2.0]] {
+            tetrahedralization.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(tetrahedralization.num_ignored_vertices(), 2);
+        assert_eq!(tetrahedralization.num_used_vertices(), 6);
+        verify_tetrahedralization(&tetrahedralization);
+    }
+
+    #[test]
+    fn test_insert_vertex_tracked() {
+        // the sorted vertices of each tet, by index
+        fn tet_keys(tetrahedralization: &Tetrahedralization) -> Vec<Vec<Option<usize>>> {
+            (0..tetrahedralization.tds().num_tets())
+                .map(|tet_idx| {
+                    let tet = tetrahedralization.tds().get_tet(tet_idx).unwrap();
+                    let mut key = tet.nodes().map(|node| node.idx()).to_vec();
+                    key.sort_unstable();
+                    key
+                })
+                .collect()
+        }
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(
+                sample_vertices_3d(100, None),
+                Some(sample_weights(100, None)),
+                true,
+            )
+            .unwrap();
+
+        // also beyond the hull
+        let vertices = sample_vertices_3d(100, Some(-0.7..=0.7));
+        let weights = sample_weights(100, None);
+        for (&v, &weight) in vertices.iter().zip(&weights) {
+            let before = tet_keys(&tetrahedralization);
+            let num_used = tetrahedralization.num_used_vertices();
+            let changes = tetrahedralization
+                .insert_vertex_tracked(v, Some(weight), None)
+                .unwrap();
+            let after = tet_keys(&tetrahedralization);
+
+            if tetrahedralization.num_used_vertices() == num_used {
+                assert_eq!(changes, SimplexChanges::default());
+            }
+            for tet_idx in 0..before.len().max(after.len()) {
+                let was_destroyed = changes.destroyed.contains(&tet_idx);
+                let is_created = changes.created.contains(&tet_idx);
+                let [key_before, key_after] = [&before, &after].map(|keys| keys.get(tet_idx));
+                // tets moved by the compaction are at a new index, i.e. destroyed and created as well
+                assert_eq!(
+                    was_destroyed,
+                    key_before.is_some() && key_before != key_after
+                );
+                assert_eq!(is_created, key_after.is_some() && key_before != key_after);
+            }
+        }
+        verify_tetrahedralization(&tetrahedralization);
+
+        // a light vertex next to a heavy one is redundant
+        tetrahedralization
+            .insert_vertex_tracked([0.1, 0.1, 0.1], Some(1.0), None)
+            .unwrap();
+        let changes = tetrahedralization
+            .insert_vertex_tracked([0.11, 0.1, 0.1], Some(0.0), None)
+            .unwrap();
+        assert_eq!(changes, SimplexChanges::default());
+        assert_eq!(tetrahedralization.redundant_vertices().last(), Some(&201));
+    }
+
+    #[test]
+    fn test_insert_vertex_weighted() {
+        let vertices = sample_vertices_3d(50, None);
+        let weights = sample_weights(50, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+
+        let single_vertices = sample_vertices_3d(20, Some(-0.4..=0.4));
+        let single_weights = sample_weights(20, None);
+        for (&v, &weight) in single_vertices.iter().zip(&single_weights) {
+            tetrahedralization
+                .insert_vertex(v, Some(weight), None)
+                .unwrap();
+        }
+        // without a weight, the vertex is inserted with weight zero
+        tetrahedralization
+            .insert_vertex([0.01, 0.02, 0.03], None, None)
+            .unwrap();
+
+        assert_eq!(tetrahedralization.weights.as_ref().unwrap().len(), 71);
+        assert_eq!(
+            tetrahedralization.num_used_vertices() + tetrahedralization.num_redundant_vertices(),
+            71
+        );
+        verify_tetrahedralization(&tetrahedralization);
+
+        // a heavy vertex is inserted, also among light vertices close by
+        tetrahedralization
+            .insert_vertex([0.0; 3], Some(1.0), None)
+            .unwrap();
+        assert!(tetrahedralization.used_vertices().contains(&71));
+        verify_tetrahedralization(&tetrahedralization);
+    }
 
-            verify_tetrahedralization(&tetrahedralization);
+    #[test]
+    fn test_redundant_vertices() {
+        // a heavy vertex in the center makes the light vertices close to it redundant
+        let mut vertices = sample_vertices_3d(100, None);
+        vertices.push([0.0; 3]);
+        let mut weights = vec![0.0; 100];
+        weights.push(0.3);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+        // the sampled vertices might all be far enough from the center, this one is not
+        tetrahedralization
+            .insert_vertex([0.01, 0.0, 0.0], Some(0.0), None)
+            .unwrap();
+        assert!(tetrahedralization.ignored_vertices().is_empty());
+        assert!(!tetrahedralization.redundant_vertices().is_empty());
+        assert_eq!(
+            tetrahedralization.num_used_vertices() + tetrahedralization.num_redundant_vertices(),
+            vertices.len() + 1
+        );
+        verify_tetrahedralization(&tetrahedralization);
+
+        // a redundant vertex, that gets heavy, violates the regularity
+        let v_idx = tetrahedralization.redundant_vertices()[0];
+        tetrahedralization.weights.as_mut().unwrap()[v_idx] = 1.0;
+        assert!(!tetrahedralization.is_regular().unwrap().0);
+        assert!(tetrahedralization.par_is_regular(false) < 1.0);
+        for check in [RegularityCheck::EarlyExit, RegularityCheck::Local] {
+            assert!(
+                !tetrahedralization
+                    .check_regularity(check, false)
+                    .is_regular()
+            );
         }
     }
 
@@ -1000,7 +4201,9 @@ mod tests {
             verify_tetrahedralization(&tetrahedralization);
 
             assert!(
-                tetrahedralization.num_used_vertices() + tetrahedralization.num_ignored_vertices()
+                tetrahedralization.num_used_vertices()
+                    + tetrahedralization.num_ignored_vertices()
+                    + tetrahedralization.num_redundant_vertices()
                     == n
             );
         }
@@ -1025,7 +4228,9 @@ mod tests {
             verify_tetrahedralization(&tetrahedralization);
 
             assert!(
-                tetrahedralization.num_used_vertices() + tetrahedralization.num_ignored_vertices()
+                tetrahedralization.num_used_vertices()
+                    + tetrahedralization.num_ignored_vertices()
+                    + tetrahedralization.num_redundant_vertices()
                     == n
             );
         }
@@ -1052,6 +4257,45 @@ mod tests {
         assert!(elapsed_p < elapsed)
     }
 
+    #[test]
+    fn test_par_is_regular_for_point_set() {
+        let vertices = sample_vertices_3d(200, None);
+        let (inserted, others) = vertices.split_at(100);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(inserted, None, true)
+            .unwrap();
+
+        assert_eq!(
+            tetrahedralization
+                .par_is_regular_for_point_set(&[], None)
+                .unwrap(),
+            1.0
+        );
+
+        let (_, degree) = tetrahedralization
+            .is_regular_for_point_set(others, None)
+            .unwrap();
+        assert!(degree < 1.0);
+        assert_eq!(
+            tetrahedralization
+                .par_is_regular_for_point_set(others, None)
+                .unwrap(),
+            degree
+        );
+
+        assert_eq!(
+            tetrahedralization
+                .par_is_regular_for_point_set(others, Some(vec![0.0; 3]))
+                .unwrap_err()
+                .downcast_ref::<RitaError>(),
+            Some(&RitaError::WeightsMismatch {
+                num_weights: 3,
+                num_vertices: others.len(),
+            })
+        );
+    }
+
     #[test]
     fn results_same_3d() {
         let vertices = &[
@@ -1072,27 +4316,33 @@ mod tests {
             vec![
                 [
                     [-41.65, 6.3, 2.69],
-                    [-7.1, -91.7, 8.5],
+                    [-2.91, 4.7, 60.85],
                     [6.49, -5.9, 96.9],
-                    [-2.91, 4.7, 60.85]
+                    [9.8, 49.0, 42.9]
                 ],
                 [
-                    [-41.65, 6.3, 2.69],
-                    [-2.91, 4.7, 60.85],
+                    [7.28, 4.9, -1.81],
                     [7.62, 5.3, -1.57],
-                    [5.3, -3.2, 2.68]
+                    [4.105, -1.8, -9.71],
+                    [8.7, -4.5, -6.4]
                 ],
                 [
-                    [-7.1, -91.7, 8.5],
-                    [6.49, -5.9, 96.9],
-                    [-2.91, 4.7, 60.85],
-                    [5.3, -3.2, 2.68]
+                    [7.62, 5.3, -1.57],
+                    [7.28, 4.9, -1.81],
+                    [5.3, -3.2, 2.68],
+                    [8.7, -4.5, -6.4]
+                ],
+                [
+                    [4.105, -1.8, -9.71],
+                    [5.3, -3.2, 2.68],
+                    [8.7, -4.5, -6.4],
+                    [-7.1, -91.7, 8.5]
                 ],
                 [
-                    [-41.65, 6.3, 2.69],
-                    [-2.91, 4.7, 60.85],
                     [6.49, -5.9, 96.9],
-                    [9.8, 49.0, 42.9]
+                    [7.62, 5.3, -1.57],
+                    [5.3, -3.2, 2.68],
+                    [8.7, -4.5, -6.4]
                 ],
                 [
                     [-41.65, 6.3, 2.69],
@@ -1102,20 +4352,20 @@ mod tests {
                 ],
                 [
                     [-41.65, 6.3, 2.69],
-                    [4.105, -1.8, -9.71],
-                    [-7.1, -91.7, 8.5],
+                    [7.62, 5.3, -1.57],
+                    [7.28, 4.9, -1.81],
                     [5.3, -3.2, 2.68]
                 ],
                 [
-                    [6.49, -5.9, 96.9],
+                    [-41.65, 6.3, 2.69],
+                    [7.28, 4.9, -1.81],
                     [7.62, 5.3, -1.57],
-                    [5.3, -3.2, 2.68],
-                    [8.7, -4.5, -6.4]
+                    [4.105, -1.8, -9.71]
                 ],
                 [
                     [-41.65, 6.3, 2.69],
-                    [-7.1, -91.7, 8.5],
                     [-2.91, 4.7, 60.85],
+                    [7.62, 5.3, -1.57],
                     [5.3, -3.2, 2.68]
                 ],
                 [
@@ -1125,16 +4375,16 @@ mod tests {
                     [5.3, -3.2, 2.68]
                 ],
                 [
-                    [6.49, -5.9, 96.9],
                     [9.8, 49.0, 42.9],
                     [7.62, 5.3, -1.57],
+                    [6.49, -5.9, 96.9],
                     [8.7, -4.5, -6.4]
                 ],
                 [
-                    [-41.65, 6.3, 2.69],
                     [7.28, 4.9, -1.81],
-                    [7.62, 5.3, -1.57],
-                    [4.105, -1.8, -9.71]
+                    [4.105, -1.8, -9.71],
+                    [5.3, -3.2, 2.68],
+                    [8.7, -4.5, -6.4]
                 ],
                 [
                     [-41.65, 6.3, 2.69],
@@ -1143,9 +4393,9 @@ mod tests {
                     [4.105, -1.8, -9.71]
                 ],
                 [
-                    [-41.65, 6.3, 2.69],
+                    [9.8, 49.0, 42.9],
+                    [6.49, -5.9, 96.9],
                     [7.62, 5.3, -1.57],
-                    [7.28, 4.9, -1.81],
                     [5.3, -3.2, 2.68]
                 ],
                 [
@@ -1162,40 +4412,34 @@ mod tests {
                 ],
                 [
                     [6.49, -5.9, 96.9],
-                    [7.62, 5.3, -1.57],
-                    [9.8, 49.0, 42.9],
-                    [5.3, -3.2, 2.68]
-                ],
-                [
-                    [-7.1, -91.7, 8.5],
-                    [6.49, -5.9, 96.9],
+                    [8.7, -4.5, -6.4],
                     [5.3, -3.2, 2.68],
-                    [8.7, -4.5, -6.4]
+                    [-7.1, -91.7, 8.5]
                 ],
                 [
-                    [7.62, 5.3, -1.57],
-                    [7.28, 4.9, -1.81],
+                    [-41.65, 6.3, 2.69],
                     [5.3, -3.2, 2.68],
-                    [8.7, -4.5, -6.4]
-                ],
-                [
-                    [7.28, 4.9, -1.81],
-                    [7.62, 5.3, -1.57],
                     [4.105, -1.8, -9.71],
-                    [8.7, -4.5, -6.4]
+                    [-7.1, -91.7, 8.5]
                 ],
                 [
-                    [4.105, -1.8, -9.71],
-                    [-7.1, -91.7, 8.5],
+                    [-2.91, 4.7, 60.85],
+                    [6.49, -5.9, 96.9],
                     [5.3, -3.2, 2.68],
-                    [8.7, -4.5, -6.4]
+                    [-7.1, -91.7, 8.5]
                 ],
                 [
-                    [7.28, 4.9, -1.81],
-                    [4.105, -1.8, -9.71],
-                    [5.3, -3.2, 2.68],
-                    [8.7, -4.5, -6.4]
+                    [-41.65, 6.3, 2.69],
+                    [6.49, -5.9, 96.9],
+                    [-2.91, 4.7, 60.85],
+                    [-7.1, -91.7, 8.5]
                 ],
+                [
+                    [-41.65, 6.3, 2.69],
+                    [-2.91, 4.7, 60.85],
+                    [5.3, -3.2, 2.68],
+                    [-7.1, -91.7, 8.5]
+                ]
             ]
         );
 
@@ -1257,27 +4501,37 @@ mod tests {
             vec![
                 [
                     [
-                        -0.07082940540173965,
-                        -0.21955363061383965,
-                        0.412806916526937
+                        -0.12150571763445661,
+                        -0.03990107532727405,
+                        -0.08537975686394306
                     ],
                     [
-                        0.22292364004203769,
-                        -0.09745743275599683,
-                        0.05550159697839596
+                        -0.3192238770476341,
+                        -0.0067495248588208545,
+                        -0.45779316426328687
                     ],
                     [
-                        0.26555392349136553,
-                        -0.32992168321175064,
-                        0.22636353961636158
+                        -0.04725968862914487,
+                        0.3516462125678388,
+                        -0.12313760895205272
                     ],
                     [
-                        0.2730786166118322,
-                        0.06453656113465944,
-                        -0.01530615283103176
+                        -0.07998418694311427,
+                        0.19729937490029037,
+                        0.06739429707395683
                     ]
                 ],
                 [
+                    [
+                        -0.12150571763445661,
+                        -0.03990107532727405,
+                        -0.08537975686394306
+                    ],
+                    [
+                        -0.07082940540173965,
+                        -0.21955363061383965,
+                        0.412806916526937
+                    ],
                     [
                         -0.07998418694311427,
                         0.19729937490029037,
@@ -1287,16 +4541,6 @@ mod tests {
                         0.22292364004203769,
                         -0.09745743275599683,
                         0.05550159697839596
-                    ],
-                    [
-                        -0.07082940540173965,
-                        -0.21955363061383965,
-                        0.412806916526937
-                    ],
-                    [
-                        0.2730786166118322,
-                        0.06453656113465944,
-                        -0.01530615283103176
                     ]
                 ],
                 [
@@ -1321,28 +4565,6 @@ mod tests {
                         -0.09889301224830771
                     ]
                 ],
-                [
-                    [
-                        -0.07082940540173965,
-                        -0.21955363061383965,
-                        0.412806916526937
-                    ],
-                    [
-                        0.26555392349136553,
-                        -0.32992168321175064,
-                        0.22636353961636158
-                    ],
-                    [
-                        0.22292364004203769,
-                        -0.09745743275599683,
-                        0.05550159697839596
-                    ],
-                    [
-                        0.038053334853741405,
-                        -0.45937873618870206,
-                        -0.09889301224830771
-                    ]
-                ],
                 [
                     [
                         -0.12150571763445661,
@@ -1354,22 +4576,22 @@ mod tests {
                         -0.0067495248588208545,
                         -0.45779316426328687
                     ],
-                    [
-                        -0.04725968862914487,
-                        0.3516462125678388,
-                        -0.12313760895205272
-                    ],
                     [
                         -0.07998418694311427,
                         0.19729937490029037,
                         0.06739429707395683
+                    ],
+                    [
+                        -0.07082940540173965,
+                        -0.21955363061383965,
+                        0.412806916526937
                     ]
                 ],
                 [
                     [
-                        -0.12150571763445661,
-                        -0.03990107532727405,
-                        -0.08537975686394306
+                        -0.07082940540173965,
+                        -0.21955363061383965,
+                        0.412806916526937
                     ],
                     [
                         -0.07998418694311427,
@@ -1382,9 +4604,9 @@ mod tests {
                         0.05550159697839596
                     ],
                     [
-                        -0.07082940540173965,
-                        -0.21955363061383965,
-                        0.412806916526937
+                        0.2730786166118322,
+                        0.06453656113465944,
+                        -0.01530615283103176
                     ]
                 ],
                 [
@@ -1393,11 +4615,33 @@ mod tests {
                         -0.21955363061383965,
                         0.412806916526937
                     ],
+                    [
+                        0.22292364004203769,
+                        -0.09745743275599683,
+                        0.05550159697839596
+                    ],
                     [
                         0.26555392349136553,
                         -0.32992168321175064,
                         0.22636353961636158
                     ],
+                    [
+                        0.2730786166118322,
+                        0.06453656113465944,
+                        -0.01530615283103176
+                    ]
+                ],
+                [
+                    [
+                        -0.3192238770476341,
+                        -0.0067495248588208545,
+                        -0.45779316426328687
+                    ],
+                    [
+                        -0.04725968862914487,
+                        0.3516462125678388,
+                        -0.12313760895205272
+                    ],
                     [
                         0.04798679923829818,
                         0.4761807498607096,
@@ -1411,36 +4655,36 @@ mod tests {
                 ],
                 [
                     [
-                        -0.12150571763445661,
-                        -0.03990107532727405,
-                        -0.08537975686394306
+                        -0.07082940540173965,
+                        -0.21955363061383965,
+                        0.412806916526937
                     ],
                     [
-                        -0.3192238770476341,
-                        -0.0067495248588208545,
-                        -0.45779316426328687
+                        0.26555392349136553,
+                        -0.32992168321175064,
+                        0.22636353961636158
                     ],
                     [
-                        -0.07998418694311427,
-                        0.19729937490029037,
-                        0.06739429707395683
+                        0.22292364004203769,
+                        -0.09745743275599683,
+                        0.05550159697839596
                     ],
                     [
-                        -0.07082940540173965,
-                        -0.21955363061383965,
-                        0.412806916526937
+                        0.038053334853741405,
+                        -0.45937873618870206,
+                        -0.09889301224830771
                     ]
                 ],
                 [
                     [
-                        -0.3192238770476341,
-                        -0.0067495248588208545,
-                        -0.45779316426328687
+                        -0.07082940540173965,
+                        -0.21955363061383965,
+                        0.412806916526937
                     ],
                     [
-                        -0.04725968862914487,
-                        0.3516462125678388,
-                        -0.12313760895205272
+                        0.26555392349136553,
+                        -0.32992168321175064,
+                        0.22636353961636158
                     ],
                     [
                         0.04798679923829818,
@@ -1650,7 +4894,7 @@ mod tests {
                         -0.45937873618870206,
                         -0.09889301224830771
                     ]
-                ],
+                ]
             ]
         );
     }
@@ -0,0 +1,256 @@
+//! Recording of the insertions and flips of a triangulation, to replay them step by step, e.g. to debug the flip logic.
+//!
+//! ```
+//! use rita::Triangulation;
+//!
+//! let mut triangulation = Triangulation::new(None);
+//! triangulation.start_trace();
+//! triangulation
+//!     .insert_vertices(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]], None, false)
+//!     .unwrap();
+//! let trace = triangulation.take_trace().unwrap();
+//!
+//! let mut replayer = trace.replayer();
+//! while replayer.step().is_some() {
+//!     println!("{} triangles", replayer.casual_tris().len());
+//! }
+//! assert_eq!(replayer.casual_tris().len(), 2);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    VertexNode,
+    trids::{tri_data_structure::TriDataStructure, tri_iterator::TriIterator},
+    utils::types::{Vertex2, VertexIdx},
+};
+
+/// A step of a [`TriangulationTrace`], named like the hooks of an [`InsertObserver`](crate::observer::InsertObserver).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TraceEvent {
+    /// The initial triangle of the vertices is created, along with the conceptual ones around it.
+    Init { v_idxs: [VertexIdx; 3] },
+    /// A vertex is inserted into the triangle containing it.
+    Insert {
+        v_idx: VertexIdx,
+        containing_idx: usize,
+    },
+    /// The triangle containing the vertex is split into three, the first one in its slot.
+    Flip13 { tri_idxs: [usize; 3] },
+    /// The two triangles at an edge are replaced by the two at the other diagonal, in the same slots.
+    Flip22 { tri_idxs: [usize; 2] },
+    /// The three triangles around the vertex `v_idx` of degree 3 are replaced by one in the first slot.
+    Flip31 {
+        tri_idxs: [usize; 3],
+        v_idx: VertexIdx,
+    },
+    /// The cavity of a Bowyer Watson insertion of the vertex `v_idx` is replaced by the star around it.
+    ///
+    /// The triangles of the cavity are the removed ones of the step, the ones of the star the written ones.
+    BwCavity { v_idx: VertexIdx },
+}
+
+/// A recorded [`TraceEvent`], along with its changes of the triangles.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TraceStep {
+    pub event: TraceEvent,
+    /// The slots of the triangles removed by the step, before the written ones are set.
+    pub removed: Vec<usize>,
+    /// The triangles written by the step, by their slot.
+    pub written: Vec<(usize, [VertexNode; 3])>,
+}
+
+/// The insertions and flips of a triangulation, recorded between `start_trace` and `take_trace` of a
+/// [`Triangulation`](crate::Triangulation).
+///
+/// Only insertions are recorded, other changes of the triangles, e.g. by decimation, are not part of the trace.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TriangulationTrace {
+    /// The vertices of the triangulation, when the recording stopped.
+    pub vertices: Vec<Vertex2>,
+    /// The triangles by their slot, when the recording started, `None` for inactive slots.
+    pub initial: Vec<Option<[VertexNode; 3]>>,
+    /// The recorded steps, in their order.
+    pub steps: Vec<TraceStep>,
+}
+
+impl TriangulationTrace {
+    /// A replayer starting at the initial triangles.
+    pub fn replayer(&self) -> TraceReplayer<'_> {
+        TraceReplayer {
+            trace: self,
+            tris: self.initial.clone(),
+            num_steps: 0,
+        }
+    }
+
+    /// The triangles by their slot, after the first `num_steps` steps.
+    pub fn state_at(&self, num_steps: usize) -> Vec<Option<[VertexNode; 3]>> {
+        let mut replayer = self.replayer();
+        replayer.seek(num_steps);
+
+        replayer.tris
+    }
+
+    /// The triangles of `tds` by their slot.
+    pub(crate) fn slots(tds: &TriDataStructure) -> Vec<Option<[VertexNode; 3]>> {
+        (0..tds.num_tris() + tds.num_deleted_tris)
+            .map(|tri_idx| {
+                let tri = TriIterator::new(tds, tri_idx);
+                (!tri.is_deleted()).then(|| tri.nodes())
+            })
+            .collect()
+    }
+
+    /// Record a step, that removed the triangles `removed` and wrote the ones `written` of `tds`.
+    pub(crate) fn record(
+        &mut self,
+        tds: &TriDataStructure,
+        event: TraceEvent,
+        removed: &[usize],
+        written: &[usize],
+    ) {
+        self.steps.push(TraceStep {
+            event,
+            removed: removed.to_vec(),
+            written: written
+                .iter()
+                .map(|&tri_idx| (tri_idx, TriIterator::new(tds, tri_idx).nodes()))
+                .collect(),
+        });
+    }
+}
+
+/// Reconstructs the intermediate states of a [`TriangulationTrace`], one step at a time.
+#[derive(Debug, Clone)]
+pub struct TraceReplayer<'a> {
+    trace: &'a TriangulationTrace,
+    tris: Vec<Option<[VertexNode; 3]>>,
+    num_steps: usize,
+}
+
+impl TraceReplayer<'_> {
+    /// Apply the next step, returning its event, or `None` at the end of the trace.
+    pub fn step(&mut self) -> Option<&TraceEvent> {
+        let step = self.trace.steps.get(self.num_steps)?;
+        for &tri_idx in &step.removed {
+            self.tris[tri_idx] = None;
+        }
+        for &(tri_idx, nodes) in &step.written {
+            if tri_idx >= self.tris.len() {
+                self.tris.resize(tri_idx + 1, None);
+            }
+            self.tris[tri_idx] = Some(nodes);
+        }
+        self.num_steps += 1;
+
+        Some(&step.event)
+    }
+
+    /// Go to the state after the first `num_steps` steps, or the end of the trace, replaying from the start if needed.
+    pub fn seek(&mut self, num_steps: usize) {
+        if num_steps < self.num_steps {
+            self.tris.clone_from(&self.trace.initial);
+            self.num_steps = 0;
+        }
+        while self.num_steps < num_steps && self.step().is_some() {}
+    }
+
+    /// The number of steps applied.
+    pub const fn num_steps(&self) -> usize {
+        self.num_steps
+    }
+
+    /// The triangles by their slot, `None` for inactive slots.
+    pub fn tris(&self) -> &[Option<[VertexNode; 3]>] {
+        &self.tris
+    }
+
+    /// The vertex indices of the casual triangles, i.e. the ones without the conceptual node.
+    pub fn casual_tris(&self) -> Vec<[VertexIdx; 3]> {
+        self.tris
+            .iter()
+            .flatten()
+            .filter_map(|nodes| {
+                let [a, b, c] = nodes.map(|node| node.idx());
+                Some([a?, b?, c?])
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use crate::{
+        InsertionStrategy, Triangulation, TriangulationConfig, predicates, trace::TraceEvent,
+    };
+    use alloc::vec::Vec;
+    use rita_test_utils::sample_vertices_2d;
+
+    fn sorted(mut tris: Vec<[usize; 3]>) -> Vec<[usize; 3]> {
+        for tri in &mut tris {
+            let min_pos = (0..3).min_by_key(|&i| tri[i]).unwrap();
+            tri.rotate_left(min_pos);
+        }
+        tris.sort_unstable();
+        tris
+    }
+
+    #[test]
+    fn test_trace_replay() {
+        let vertices = sample_vertices_2d(100, None);
+
+        for strategy in [InsertionStrategy::Flips, InsertionStrategy::BowyerWatson] {
+            let config = TriangulationConfig::new().insertion_strategy(strategy);
+            let mut triangulation = Triangulation::with_config(config);
+            triangulation.start_trace();
+            triangulation
+                .insert_vertices(&vertices, None, true)
+                .unwrap();
+            let trace = triangulation.take_trace().unwrap();
+            assert!(triangulation.take_trace().is_none());
+
+            let num_inserts = trace
+                .steps
+                .iter()
+                .filter(|step| matches!(step.event, TraceEvent::Insert { .. }))
+                .count();
+            assert_eq!(num_inserts, 97);
+
+            // each intermediate state after an insertion is a valid triangulation
+            let mut replayer = trace.replayer();
+            while let Some(event) = replayer.step() {
+                if matches!(
+                    event,
+                    TraceEvent::Flip13 { .. } | TraceEvent::BwCavity { .. }
+                ) {
+                    for [a, b, c] in replayer.casual_tris() {
+                        let [a, b, c] = [a, b, c].map(|v_idx| trace.vertices[v_idx]);
+                        assert!(predicates::orient_2d(&a, &b, &c) > 0.0);
+                    }
+                }
+            }
+
+            assert_eq!(
+                sorted(replayer.casual_tris()),
+                sorted(triangulation.tri_indices())
+            );
+            assert_eq!(trace.state_at(trace.steps.len()), replayer.tris());
+
+            // seeking backwards replays from the start
+            replayer.seek(1);
+            assert_eq!(replayer.num_steps(), 1);
+            assert_eq!(replayer.casual_tris().len(), 1);
+        }
+
+        // the recording continues after removing vertices, without the insertions of the rebuild
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        triangulation.start_trace();
+        triangulation.remove_vertices(&[0, 1]).unwrap();
+        let trace = triangulation.take_trace().unwrap();
+        assert!(trace.steps.is_empty());
+    }
+}
@@ -1,26 +1,65 @@
-use alloc::{vec, vec::Vec};
-use core::panic;
-
-// TODO: we could allow the epsilon filter on insertion also allow to happen, when the inserted vertex is in a casual triangle, i.e. outside the c-hull
-// TODO: we could also incorporate that in the 3->1 flip, as to remove points in a later stage of the algo (not just at insertion)
+use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec, vec::Vec};
+use core::{fmt::Write, ops::ControlFlow};
 
+#[cfg(feature = "exact")]
+use crate::exact::ExactVertices;
 use crate::predicates;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{self, SnapshotKind};
+#[cfg(feature = "metrics")]
+use crate::stats::{BuildStats, FilterCounts};
+#[cfg(feature = "trace")]
+use crate::trace::{TraceEvent, TriangulationTrace};
 use crate::{
-    VertexNode,
+    IntoVertex2, PredicateMode, RitaError, VertexNode,
+    audit::{RegularityCheck, RegularityReport, SoundnessReport},
+    complex::{BoundaryMatrices, DecMeasures},
+    decimation::{Decimation, ErrorMetric, LodChain, build_lod_chain, decimate},
+    degenerate::{Chain, Insertion},
+    frozen::CompactTriangulation,
+    geometry::{Plane, barycentric_2d, closest_on_segment},
+    handle::TriHandle,
+    io::{self, NodeEleData, ObjZ, PlyData, PolyData, SvgOptions},
+    locate_grid::LocateGrid,
+    mesh::MeshSink,
+    observer::{InsertObserver, Observer},
+    quality::{QualityHistograms, TriQuality, tri_quality},
+    refinement::refine,
     trids::{
         hedge_iterator::HedgeIterator, tri_data_structure::TriDataStructure,
         tri_iterator::TriIterator,
     },
     utils::{
+        bounded::push_bounded,
         convexity::is_convex,
-        point_order::sort_along_hilbert_curve_2d,
-        types::{Edge2, Triangle2, Vertex2, VertexIdx},
+        insert_status::{
+            DuplicatePolicy, InsertProgress, InsertStatus, SimplexChanges, canonical_vertices,
+            insert_statuses, merge_duplicate_weights,
+        },
+        parallel::{self, prelude::*},
+        point_grid::PointGrid,
+        point_order::{
+            InsertOrder, brio_order_2d, shuffle, sort_along_hilbert_curve_2d,
+            sort_along_morton_curve_2d, sort_buckets_by_weight,
+        },
+        translation::{bbox_origin, is_exact_translation, local_origin, to_local},
+        types::{Edge2, Triangle2, Vertex2, Vertex3, VertexIdx},
     },
+    voronoi::{lloyd_step, orthocenter},
+    walk::WalkStats,
 };
 use anyhow::{Ok as HowOk, Result as HowResult};
 #[cfg(feature = "logging")]
 use log::error;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// The minimum number of vertices per chunk of [`Triangulation::par_insert_vertices`], fewer are inserted sequentially.
+const PAR_MIN_CHUNK_SIZE: usize = 4096;
+/// The relative slack on the radius of the power circles of the merge, to cover most of their rounding errors.
+///
+/// The merged triangulation is checked for regularity, so a vertex missed due to a larger error is not kept.
+const PAR_RADIUS_SLACK: f64 = 1e-6;
+/// The most cells of the [`PointGrid`] searched for a power circle of the merge, larger ones are not final.
+const PAR_MAX_CELLS: usize = 64;
 
 /// Triangle, including point at infinity
 pub enum TriangleExtended {
@@ -30,6 +69,171 @@ pub enum TriangleExtended {
     ConceptualTriangle(Edge2),
 }
 
+/// Where a point lies in a [`Triangulation`], see [`Triangulation::locate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    /// Strictly inside this casual triangle.
+    InsideTriangle(TriHandle),
+    /// On the edge of a casual triangle, given by the index of its hedge.
+    OnEdge(usize),
+    /// At the position of the vertex with this index.
+    OnVertex(VertexIdx),
+    /// Outside the convex hull, beyond the hull edge of this conceptual triangle.
+    OutsideHull(TriHandle),
+}
+
+/// A vertex that was skipped by [`Triangulation::insert_vertices_lenient`].
+#[derive(Debug)]
+pub struct InsertIssue {
+    /// The index of the vertex, i.e. into [`Triangulation::vertices`].
+    pub v_idx: VertexIdx,
+    /// Why the vertex could not be inserted.
+    pub error: anyhow::Error,
+}
+
+/// How a vertex is inserted into a [`Triangulation`] or a [`Tetrahedralization`](crate::Tetrahedralization),
+/// see [`Triangulation::set_insertion_strategy`] and
+/// [`Tetrahedralization::set_insertion_strategy`](crate::Tetrahedralization::set_insertion_strategy).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertionStrategy {
+    /// Split the containing simplex, and flip the facets around the vertex, until they are regular.
+    ///
+    /// The default of a [`Triangulation`].
+    #[default]
+    Flips,
+    /// Remove the simplices, whose power sphere contains the vertex, and connect the boundary of the cavity to it.
+    ///
+    /// The default of a [`Tetrahedralization`](crate::Tetrahedralization).
+    BowyerWatson,
+}
+
+/// How vertices at the position of an inserted vertex are skipped, see [`Triangulation::set_dedup`].
+///
+/// Skipped vertices get the status [`InsertStatus::Duplicate`], the first one inserted is kept.
+/// What else happens to them is decided by the [`DuplicatePolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DedupPolicy {
+    /// Skip vertices at exactly the position of a vertex of their containing triangle.
+    #[default]
+    Exact,
+    /// Skip vertices within this distance of a vertex of their containing triangle, e.g. to merge noisy duplicates.
+    ///
+    /// Only the vertices of the containing triangle are compared, which are the closest ones for small distances.
+    Within(f64),
+}
+
+/// The settings of a [`Triangulation`], see [`Triangulation::with_config`].
+///
+/// ```
+/// use rita::{InsertOrder, Triangulation, TriangulationConfig};
+///
+/// let config = TriangulationConfig::new()
+///     .epsilon(1e-9)
+///     .insert_order(InsertOrder::Random(7))
+///     .capacity(4);
+///
+/// let mut triangulation = Triangulation::with_config(config);
+/// triangulation.insert(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]], None).unwrap();
+/// assert_eq!(triangulation.num_casual_tris(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TriangulationConfig {
+    /// The epsilon, that vertices close to being regular are skipped by, `None` to insert all vertices.
+    pub epsilon: Option<f64>,
+    /// The order of the vertices inserted by [`Triangulation::insert`].
+    pub insert_order: InsertOrder,
+    /// How vertices at the position of an inserted vertex are skipped.
+    pub dedup: DedupPolicy,
+    /// What happens to vertices at the position of an inserted vertex.
+    pub duplicate_policy: DuplicatePolicy,
+    /// See [`Triangulation::set_insertion_strategy`].
+    pub insertion_strategy: InsertionStrategy,
+    /// The number of vertices to pre-allocate for.
+    pub capacity: usize,
+    /// See [`Triangulation::set_locate_grid`].
+    pub locate_grid: bool,
+    /// See [`Triangulation::set_max_threads`].
+    pub max_threads: Option<usize>,
+}
+
+impl TriangulationConfig {
+    /// The default settings, i.e. no epsilon, Hilbert order, exact deduplication, flips, no pre-allocation, no locate grid
+    /// and the current thread pool.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            epsilon: None,
+            insert_order: InsertOrder::Hilbert,
+            dedup: DedupPolicy::Exact,
+            duplicate_policy: DuplicatePolicy::SkipAndMap,
+            insertion_strategy: InsertionStrategy::Flips,
+            capacity: 0,
+            locate_grid: false,
+            max_threads: None,
+        }
+    }
+
+    /// Set the epsilon.
+    #[must_use]
+    pub const fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    /// Set the order of the vertices inserted by [`Triangulation::insert`].
+    #[must_use]
+    pub const fn insert_order(mut self, insert_order: InsertOrder) -> Self {
+        self.insert_order = insert_order;
+        self
+    }
+
+    /// Set how vertices at the position of an inserted vertex are skipped.
+    #[must_use]
+    pub const fn dedup(mut self, dedup: DedupPolicy) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set what happens to vertices at the position of an inserted vertex.
+    #[must_use]
+    pub const fn duplicate_policy(mut self, duplicate_policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = duplicate_policy;
+        self
+    }
+
+    /// Set how the vertices are inserted.
+    #[must_use]
+    pub const fn insertion_strategy(mut self, insertion_strategy: InsertionStrategy) -> Self {
+        self.insertion_strategy = insertion_strategy;
+        self
+    }
+
+    /// Set the number of vertices to pre-allocate for.
+    #[must_use]
+    pub const fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set whether the visibility walks start from a uniform grid of recently inserted triangles.
+    #[must_use]
+    pub const fn locate_grid(mut self, locate_grid: bool) -> Self {
+        self.locate_grid = locate_grid;
+        self
+    }
+
+    /// Set the most threads of the parallel methods.
+    #[must_use]
+    pub const fn max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum Flip {
     #[allow(unused)]
@@ -63,6 +267,7 @@ pub(crate) enum Flip {
 /// ```
 #[derive(Debug)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Triangulation {
     /// An artificial inverse weight to make points be considered as regular (ie. not lying in a triangles circumcircle).
     ///
@@ -72,14 +277,66 @@ pub struct Triangulation {
     pub vertices: Vec<Vertex2>,
     /// The weights of the vertices, `Some` if the vertices are weighted
     pub weights: Option<Vec<f64>>,
+    /// The local origin, that heights are computed relative to.
+    ///
+    /// Chosen close to the centroid of the first inserted vertices, such that each vertex is translated exactly.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    origin: Vertex2,
+    /// The maximum number of vertices, `Some` if the buffers are bounded.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    max_vertices: Option<usize>,
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    stats: BuildStats,
+    /// The power tests of the current insertion, that the floating point filters decided and failed on.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    filter_counts: FilterCounts,
     last_inserted_triangle: Option<usize>,
-
-    #[cfg(feature = "timing")]
-    time_flipping: u128,
-    #[cfg(feature = "timing")]
-    time_inserting: u128,
-    #[cfg(feature = "timing")]
-    time_walking: u128,
+    /// The hedges to check for flips, reused by the insertions.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hedges_to_verify: Vec<usize>,
+    /// A conceptual triangle next to the last vertex inserted beyond the hull, to locate the next such vertex directly.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    hull_triangle: Option<usize>,
+    /// The statistics of the visibility walks of the insertions.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    walk_stats: WalkStats,
+    /// Whether the visibility walks start from the [`LocateGrid`], which is maintained during insertion.
+    #[cfg_attr(feature = "serde", serde(default))]
+    use_locate_grid: bool,
+    /// A triangle near each cell of a uniform grid, built again on the next insertion after loading.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    locate_grid: LocateGrid<2>,
+    /// How the power circle tests of the insertion are decided.
+    #[cfg_attr(feature = "serde", serde(default))]
+    predicate_mode: PredicateMode,
+    /// Whether vertices are also skipped by epsilon after their insertion, in the flips of later insertions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    eps_in_flips: bool,
+    /// How the vertices are inserted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    insertion_strategy: InsertionStrategy,
+    /// The most threads of the parallel methods, `None` for the current [rayon] pool.
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_threads: Option<usize>,
+    /// The pool of [`Self::max_threads`], built on the first parallel call.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    thread_pool: parallel::ThreadPool,
+    /// The order of the vertices inserted by [`Triangulation::insert`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    insert_order: InsertOrder,
+    /// How vertices at the position of an inserted vertex are skipped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    dedup: DedupPolicy,
+    /// What happens to vertices at the position of an inserted vertex.
+    #[cfg_attr(feature = "serde", serde(default))]
+    duplicate_policy: DuplicatePolicy,
 
     /// Vertices that are part of the triangulation
     /// (i.e. the input point set without redundant and ignored vertices).
@@ -91,6 +348,37 @@ pub struct Triangulation {
     /// Vertices that are not part of the triangulation, due to epsilon.
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     ignored_vertices: Vec<usize>,
+    /// Vertices that are not part of the triangulation, as they coincide with an inserted vertex.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    duplicate_vertices: Vec<usize>,
+    /// The inserted vertex of each duplicate vertex.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    duplicate_of: BTreeMap<VertexIdx, VertexIdx>,
+    /// Vertices that are not part of the triangulation anymore, as they have been removed, e.g. by decimation.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    removed_vertices: Vec<usize>,
+    /// Advanced on every change of the triangles, to detect stale [`TriHandle`]s.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    generation: u64,
+    /// The triangles destroyed and created by the current insertion, `Some` only during [`Triangulation::insert_vertex_tracked`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tracked_changes: Option<SimplexChanges>,
+    /// The observer of the insertions and flips, see [`Triangulation::set_observer`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observer: Observer,
+    /// The insertions and flips recorded since [`Triangulation::start_trace`].
+    #[cfg(feature = "trace")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trace: Option<TriangulationTrace>,
+    /// The last [`Triangulation::snapshot`], with the generation and the number of vertices it was taken at.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_snapshot: Option<(u64, usize, Arc<CompactTriangulation>)>,
 }
 
 impl Default for Triangulation {
@@ -147,17 +435,39 @@ impl Triangulation {
             tds: TriDataStructure::new(),
             vertices: Vec::new(),
             weights: None,
-            #[cfg(feature = "timing")]
-            time_flipping: 0,
-            #[cfg(feature = "timing")]
-            time_inserting: 0,
-            #[cfg(feature = "timing")]
-            time_walking: 0,
+            origin: [0.0; 2],
+            max_vertices: None,
+            #[cfg(feature = "metrics")]
+            stats: BuildStats::new(),
+            #[cfg(feature = "metrics")]
+            filter_counts: FilterCounts::new(),
             last_inserted_triangle: None,
+            hedges_to_verify: Vec::new(),
+            hull_triangle: None,
+            walk_stats: WalkStats::new(),
+            use_locate_grid: false,
+            locate_grid: LocateGrid::new(),
             epsilon,
             used_vertices: Vec::new(),
             ignored_vertices: Vec::new(),
             redundant_vertices: Vec::new(),
+            duplicate_vertices: Vec::new(),
+            duplicate_of: BTreeMap::new(),
+            removed_vertices: Vec::new(),
+            predicate_mode: PredicateMode::Exact,
+            eps_in_flips: false,
+            insertion_strategy: InsertionStrategy::Flips,
+            max_threads: None,
+            thread_pool: parallel::ThreadPool::new(),
+            insert_order: InsertOrder::Hilbert,
+            dedup: DedupPolicy::Exact,
+            duplicate_policy: DuplicatePolicy::SkipAndMap,
+            generation: 0,
+            tracked_changes: None,
+            observer: Observer::NONE,
+            #[cfg(feature = "trace")]
+            trace: None,
+            last_snapshot: None,
         }
     }
 
@@ -167,18 +477,121 @@ impl Triangulation {
             tds: TriDataStructure::new(),
             vertices: Vec::with_capacity(capacity),
             weights: None,
-            #[cfg(feature = "timing")]
-            time_flipping: 0,
-            #[cfg(feature = "timing")]
-            time_inserting: 0,
-            #[cfg(feature = "timing")]
-            time_walking: 0,
+            origin: [0.0; 2],
+            max_vertices: None,
+            #[cfg(feature = "metrics")]
+            stats: BuildStats::new(),
+            #[cfg(feature = "metrics")]
+            filter_counts: FilterCounts::new(),
             last_inserted_triangle: None,
+            hedges_to_verify: Vec::new(),
+            hull_triangle: None,
+            walk_stats: WalkStats::new(),
+            use_locate_grid: false,
+            locate_grid: LocateGrid::new(),
             epsilon,
             used_vertices: Vec::new(),
             ignored_vertices: Vec::new(),
             redundant_vertices: Vec::new(),
+            duplicate_vertices: Vec::new(),
+            duplicate_of: BTreeMap::new(),
+            removed_vertices: Vec::new(),
+            predicate_mode: PredicateMode::Exact,
+            eps_in_flips: false,
+            insertion_strategy: InsertionStrategy::Flips,
+            max_threads: None,
+            thread_pool: parallel::ThreadPool::new(),
+            insert_order: InsertOrder::Hilbert,
+            dedup: DedupPolicy::Exact,
+            duplicate_policy: DuplicatePolicy::SkipAndMap,
+            generation: 0,
+            tracked_changes: None,
+            observer: Observer::NONE,
+            #[cfg(feature = "trace")]
+            trace: None,
+            last_snapshot: None,
+        }
+    }
+
+    /// Create a new `Triangulation` with the given settings.
+    ///
+    /// Use [`Self::insert`] to insert vertices in the [`InsertOrder`] of the config.
+    pub fn with_config(config: TriangulationConfig) -> Self {
+        Self {
+            insertion_strategy: config.insertion_strategy,
+            insert_order: config.insert_order,
+            dedup: config.dedup,
+            duplicate_policy: config.duplicate_policy,
+            use_locate_grid: config.locate_grid,
+            max_threads: config.max_threads,
+            ..Self::new_with_vert_capacity(config.epsilon, config.capacity)
+        }
+    }
+
+    /// Create a new `Triangulation` that holds at most `max_vertices` vertices.
+    ///
+    /// The vertex and triangle buffers, and the scratch buffers of the insertions, e.g. the stack of hedges to check
+    /// for flips and the cavity of a Bowyer Watson insertion, are allocated once, here, and never grow afterwards,
+    /// e.g. for targets with a small, fixed heap. Each inserted vertex creates at most two triangles,
+    /// so space for `2 * max_vertices - 2` triangles is reserved. The locate grid is not used, as it grows with the vertices.
+    /// Insertions that would exceed the capacity fail with [`RitaError::CapacityExceeded`], before modifying `self`.
+    /// An insertion that would grow a full scratch buffer fails with [`RitaError::BufferFull`], the vertex is taken out
+    /// again by rebuilding the triangulation without it then, see [`Self::remove_vertices`].
+    ///
+    /// So [`Self::insert_vertex`] does not allocate, except to map a duplicate to the vertex inserted before.
+    /// Inserting several vertices at once allocates their order and their returned statuses, once per call.
+    pub fn with_bounded_capacity(epsilon: Option<f64>, max_vertices: usize) -> Self {
+        let max_tris = (2 * max_vertices).saturating_sub(2);
+
+        Self {
+            tds: TriDataStructure::with_bounded_capacity(max_tris),
+            vertices: Vec::with_capacity(max_vertices),
+            max_vertices: Some(max_vertices),
+            // a triangulation has at most `3 * max_vertices` edges, including the conceptual ones
+            hedges_to_verify: Vec::with_capacity(3 * max_vertices),
+            used_vertices: Vec::with_capacity(max_vertices),
+            ignored_vertices: Vec::with_capacity(max_vertices),
+            redundant_vertices: Vec::with_capacity(max_vertices),
+            duplicate_vertices: Vec::with_capacity(max_vertices),
+            ..Self::new(epsilon)
+        }
+    }
+
+    /// Check if `num_new` more vertices fit into a bounded triangulation.
+    fn check_capacity(&self, num_new: usize) -> HowResult<()> {
+        if let Some(max_vertices) = self.max_vertices {
+            let requested = self.vertices.len() + num_new;
+            if requested > max_vertices {
+                return Err(anyhow::Error::new(RitaError::CapacityExceeded {
+                    max_vertices,
+                    requested,
+                }));
+            }
         }
+
+        HowOk(())
+    }
+
+    /// Triangulate nearly planar 3D points, by projecting them onto a plane.
+    ///
+    /// If no plane is given, the least-squares plane of the points is used.
+    /// The vertex indices of the triangulation match the indices of `points`,
+    /// so [`tri_indices`](Self::tri_indices) can be used to index the original 3D points.
+    ///
+    /// ## Errors
+    /// Returns an error if the plane can not be fitted, or the projected points can not be triangulated.
+    pub fn from_projected_3d(points: &[Vertex3], plane: Option<Plane>) -> HowResult<Self> {
+        let plane = match plane {
+            Some(plane) => plane,
+            None => Plane::best_fit(points)?,
+        };
+
+        let projected: Vec<Vertex2> = points.iter().map(|&p| plane.project(p)).collect();
+
+        let mut triangulation = Self::new_with_vert_capacity(None, projected.len());
+        triangulation.insert_vertices(&projected, None, true)?;
+
+        HowOk(triangulation)
     }
 
     pub(crate) const fn weighted(&self) -> bool {
@@ -191,7 +604,7 @@ impl Triangulation {
     #[must_use]
     fn choose_hedge<'a>(
         &self,
-        v_hedges: &Vec<HedgeIterator<'a>>,
+        v_hedges: &[HedgeIterator<'a>],
         v: &[f64; 2],
     ) -> Option<HedgeIterator<'a>> {
         for hedge in v_hedges {
@@ -219,6 +632,42 @@ impl Triangulation {
         None
     }
 
+    /// Describe a triangle for humans, e.g. for logs and bug reports.
+    ///
+    /// Lists the nodes with their coordinates and weights, the neighboring triangles and the flags.
+    pub fn describe_tri(&self, tri_idx: usize) -> HowResult<String> {
+        let tri = self.tds().get_tri(tri_idx)?;
+        let kind = if tri.is_conceptual() {
+            "conceptual"
+        } else {
+            "casual"
+        };
+
+        let mut description = String::new();
+        write!(description, "Triangle {tri_idx} ({kind}")?;
+        if tri.is_deleted() {
+            write!(description, ", deleted")?;
+        }
+        writeln!(description, ")")?;
+
+        for node in tri.nodes() {
+            write!(description, "  {node}")?;
+            if let VertexNode::Casual(v_idx) = node {
+                let [x, y] = self.vertices[v_idx];
+                write!(description, " at [{x}, {y}]")?;
+                if let Some(weights) = &self.weights {
+                    write!(description, " with weight {}", weights[v_idx])?;
+                }
+            }
+            writeln!(description)?;
+        }
+
+        let [n0, n1, n2] = tri.hedges().map(|hedge| hedge.twin().tri().idx);
+        write!(description, "  neighbors: {n0}, {n1}, {n2}")?;
+
+        HowOk(description)
+    }
+
     /// For a tri idx get the triangle variant, i.e. a normal triangle, or a line with one of its three indices at infinity
     pub fn get_tri_type(&self, tri_idx: usize) -> HowResult<TriangleExtended> {
         let [node0, node1, node2] = self.tds.get_tri(tri_idx)?.nodes();
@@ -245,58 +694,103 @@ impl Triangulation {
                 let v2 = self.vertices[idx2];
                 TriangleExtended::Triangle([v0, v1, v2])
             }
-            (_, _, _) => return Err(anyhow::Error::msg("An unexpected triangle case occurred")),
+            (_, _, _) => {
+                return Err(anyhow::Error::new(RitaError::InvalidState(
+                    "unexpected triangle case",
+                )));
+            }
         };
 
         HowOk(tri_extended)
     }
 
     /// Gets the height for a vertex, this is affected by weights
+    ///
+    /// The height is computed relative to the local [`origin`](Self::origin),
+    /// which keeps it accurate for large coordinates.
     pub fn height(&self, v_idx: VertexIdx) -> f64 {
-        self.vertices[v_idx][0].powi(2) + self.vertices[v_idx][1].powi(2)
-            - self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
+        let [x, y] = to_local(&self.vertices[v_idx], &self.origin);
+        x.powi(2) + y.powi(2) - self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
+    }
+
+    /// Update the local origin for the vertices starting at `first_new_idx`.
+    ///
+    /// Before the first triangle exists, the origin is moved to the centroid of all vertices,
+    /// or to the center of their bounding box `bbox` if known.
+    /// Afterwards it falls back to zero, if a new vertex can not be translated exactly.
+    fn update_origin(&mut self, first_new_idx: VertexIdx, bbox: Option<[Vertex2; 2]>) {
+        if self.tds.num_tris() == 0 {
+            self.origin = bbox.map_or_else(
+                || local_origin(&self.vertices),
+                |bbox| bbox_origin(&self.vertices, bbox),
+            );
+        } else if !self.vertices[first_new_idx..]
+            .iter()
+            .all(|v| is_exact_translation(v, &self.origin))
+        {
+            #[cfg(feature = "logging")]
+            log::warn!("Vertex can not be translated exactly, falling back to the global origin");
+            self.origin = [0.0; 2];
+        }
     }
 
     pub fn insert_init_tri(&mut self, v_idxs: &mut Vec<VertexIdx>) -> HowResult<()> {
         #[cfg(feature = "log_timing")]
         let now = std::time::Instant::now();
+        self.generation += 1;
 
-        if self.vertices().len() == v_idxs.len() {
-            let idx0 = v_idxs.pop().unwrap();
-            let idx1 = v_idxs.pop().unwrap();
+        if self.tds.num_tris() == 0 {
+            if v_idxs.len() < 3 {
+                return Err(anyhow::Error::new(RitaError::NotEnoughVertices {
+                    required: 3,
+                }));
+            }
 
+            let idx0 = v_idxs.pop().unwrap();
             let v0 = self.vertices()[idx0];
-            let v1 = self.vertices()[idx1];
 
+            // duplicates of the first vertex are inserted later, and skipped then
             let mut aligned = Vec::new();
+            let idx1 = loop {
+                let idx = v_idxs.pop().ok_or(RitaError::AllPointsCollinear)?;
+                if self.vertices()[idx] == v0 {
+                    aligned.push(idx);
+                } else {
+                    break idx;
+                }
+            };
+            let v1 = self.vertices()[idx1];
 
-            // TODO: simplify the control flow here, the break and continue can be aligned more understandably
-            loop {
-                if let Some(idx2) = v_idxs.pop() {
-                    let v2 = self.vertices()[idx2];
-
-                    let orientation = predicates::orient_2d(&v0, &v1, &v2);
-
-                    // insert the triangle in ccw order, or if aligned, find another point to build the starting triangle
-                    if orientation > 0.0 {
-                        self.tds_mut().add_init_tri([idx0, idx1, idx2])?;
-                    } else if orientation < 0.0 {
-                        self.tds_mut().add_init_tri([idx0, idx2, idx1])?;
-                    } else {
-                        aligned.push(idx2);
-                        continue;
-                    }
-
-                    self.used_vertices.append(&mut vec![idx0, idx1, idx2]);
+            let (idx2, orientation) = loop {
+                let idx = v_idxs.pop().ok_or(RitaError::AllPointsCollinear)?;
+                let orientation = predicates::orient_2d(&v0, &v1, &self.vertices()[idx]);
+                if orientation == 0.0 {
+                    aligned.push(idx);
                 } else {
-                    return Err(anyhow::Error::msg(
-                        "All points are aligned, i.e. could not find 3 non-aligned points !",
-                    ));
+                    break (idx, orientation);
                 }
-                break;
+            };
+
+            // insert the triangle in ccw order
+            let tri_v_idxs = if orientation > 0.0 {
+                [idx0, idx1, idx2]
+            } else {
+                [idx0, idx2, idx1]
+            };
+            self.tds_mut().add_init_tri(tri_v_idxs)?;
+            #[cfg(feature = "trace")]
+            if let Some(trace) = &mut self.trace {
+                trace.record(
+                    &self.tds,
+                    TraceEvent::Init { v_idxs: tri_v_idxs },
+                    &[],
+                    &[0, 1, 2, 3],
+                );
             }
+            self.used_vertices.append(&mut vec![idx0, idx1, idx2]);
 
-            v_idxs.append(&mut aligned); // re-add the aligned points
+            self.order_aligned(&mut aligned, v0, v1);
+            v_idxs.append(&mut aligned); // re-add the aligned points, such that they are inserted next
         }
 
         self.last_inserted_triangle = Some(0); // here the first triangle is the last inserted, as it is the initial casual triangle
@@ -309,37 +803,65 @@ impl Triangulation {
         HowOk(())
     }
 
+    /// Order the points aligned with the first edge `v0`, `v1` for their insertion, i.e. in reverse as they are popped.
+    ///
+    /// Inputs like rasterized outlines contain long collinear chains on the hull. The points beyond `v1` are inserted
+    /// first, then the ones beyond `v0`, each by increasing distance, such that every one of them extends the hull chain
+    /// next to the previous one and is found by [`Self::locate_beyond_hull`]. The points between `v0` and `v1` come last,
+    /// they split the hull edge of the first triangle. The order only affects the walks, not the result.
+    fn order_aligned(&self, aligned: &mut [VertexIdx], v0: Vertex2, v1: Vertex2) {
+        let dir = [v1[0] - v0[0], v1[1] - v0[1]];
+        let len_sq = dir[0] * dir[0] + dir[1] * dir[1];
+
+        // the side of the first edge, and the distance beyond it
+        let key = |idx: &VertexIdx| {
+            let v = self.vertices()[*idx];
+            let t = (v[0] - v0[0]) * dir[0] + (v[1] - v0[1]) * dir[1];
+            if t > len_sq {
+                (2, t - len_sq)
+            } else if t < 0.0 {
+                (1, -t)
+            } else {
+                (0, 0.0)
+            }
+        };
+        aligned.sort_by(|a, b| {
+            let (side_a, dist_a) = key(a);
+            let (side_b, dist_b) = key(b);
+            side_a.cmp(&side_b).then(dist_b.total_cmp(&dist_a))
+        });
+    }
+
     /// Insert a vertex into the triangulation.
     ///
+    /// The walk to locate the vertex starts at the triangle `near_to`, e.g. from [`Self::locate`] of a nearby point.
+    /// A stale hint is still used, if its triangle exists, otherwise the walk starts at the last inserted triangle.
+    /// The `weight` defaults to zero in a weighted triangulation, and is ignored in an unweighted one.
+    ///
     /// ## Errors
-    /// Returns an error if `self` does not have any triangles in it.
+    /// Returns an error if `self` does not have any triangles in it, or its capacity is exceeded.
     pub fn insert_vertex(
         &mut self,
         v: [f64; 2],
         weight: Option<f64>,
-        near_to: Option<usize>,
+        near_to: Option<TriHandle>,
     ) -> HowResult<()> {
         if self.tds.num_tris() == 0 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 1 triangle in the triangulation to insert a vertex!",
-            ));
+            return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
         }
+        self.check_capacity(1)?;
 
         let idx_to_insert = self.vertices.len();
         self.vertices.push(v);
         if let Some(weights) = &mut self.weights {
             weights.push(weight.unwrap_or(0.0));
         }
+        self.update_origin(idx_to_insert, None);
 
-        let near_to_idx: usize;
-
-        if let Some(near_to) = near_to {
-            near_to_idx = near_to;
-        } else if let Some(last_inserted_triangle) = self.last_inserted_triangle {
-            near_to_idx = last_inserted_triangle;
-        } else {
-            near_to_idx = self.tds().num_tris() + self.tds().num_deleted_tris - 1;
-        }
+        let near_to_idx = near_to
+            .map(|handle| handle.idx())
+            .filter(|&tri_idx| self.is_live_tri(tri_idx))
+            .unwrap_or_else(|| self.walk_start(v));
 
         self.insert_v_helper(idx_to_insert, near_to_idx)?;
 
@@ -348,991 +870,5630 @@ impl Triangulation {
         HowOk(())
     }
 
+    /// Insert a vertex into the triangulation, like [`Self::insert_vertex`], and return the triangles it changed.
+    ///
+    /// Flips reuse the slots of the triangles they destroy, see [`SimplexChanges`]. The indices of all other triangles
+    /// are unchanged, so per-triangle data of them stays valid. A vertex that is not inserted, e.g. a redundant one,
+    /// changes no triangles.
+    ///
+    /// ## Errors
+    /// Returns an error if `self` does not have any triangles in it, or its capacity is exceeded.
+    pub fn insert_vertex_tracked(
+        &mut self,
+        v: [f64; 2],
+        weight: Option<f64>,
+        near_to: Option<TriHandle>,
+    ) -> HowResult<SimplexChanges> {
+        self.tracked_changes = Some(SimplexChanges::default());
+        let result = self.insert_vertex(v, weight, near_to);
+        let changes = self.tracked_changes.take().unwrap_or_default();
+
+        result.map(|()| changes)
+    }
+
+    /// Register an observer, that is called on each insertion and flip from now on, replacing the one before.
+    pub fn set_observer(&mut self, observer: impl InsertObserver + 'static) {
+        self.observer.set(Box::new(observer));
+    }
+
+    /// Unregister the observer, returning it.
+    pub fn take_observer(&mut self) -> Option<Box<dyn InsertObserver>> {
+        self.observer.take()
+    }
+
+    /// Start recording the insertions and flips into a [`TriangulationTrace`], replacing the recording before.
+    #[cfg(feature = "trace")]
+    pub fn start_trace(&mut self) {
+        self.trace = Some(TriangulationTrace {
+            vertices: Vec::new(),
+            initial: TriangulationTrace::slots(&self.tds),
+            steps: Vec::new(),
+        });
+    }
+
+    /// Stop recording, returning the trace since [`Self::start_trace`], if any.
+    #[cfg(feature = "trace")]
+    pub fn take_trace(&mut self) -> Option<TriangulationTrace> {
+        let mut trace = self.trace.take()?;
+        trace.vertices.clone_from(&self.vertices);
+
+        Some(trace)
+    }
+
+    /// Insert a set of vertices into the triangulation, in the [`InsertOrder`] of its [config](Self::with_config).
+    ///
+    /// For the classical Delaunay triangulation, don't set weights.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn insert(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        self.insert_vertices_ordered(vertices, weights, self.insert_order)
+    }
+
     /// Insert a set of vertices into the triangulation.
     ///
     /// For the classical Delaunay triangulation, don't set weights.
+    /// With `spatial_sorting` the vertices are sorted spatially, `true` along a Hilbert curve,
+    /// or in any [`InsertOrder`], like [`insert_vertices_ordered`](Self::insert_vertices_ordered).
+    ///
+    /// The vertices can be arrays, or points of other crates behind their features, see [`IntoVertex2`].
+    ///
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
     pub fn insert_vertices(
+        &mut self,
+        vertices: impl IntoIterator<Item = impl IntoVertex2>,
+        weights: Option<Vec<f64>>,
+        spatial_sorting: impl Into<InsertOrder>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        let vertices: Vec<Vertex2> = vertices
+            .into_iter()
+            .map(IntoVertex2::into_vertex2)
+            .collect();
+        self.insert_vertices_ordered(&vertices, weights, spatial_sorting.into())
+    }
+
+    /// Insert a set of vertices into the triangulation, in the given order.
+    ///
+    /// The construction is deterministic, also for [`InsertOrder::Random`] given the same seed.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn insert_vertices_ordered(
         &mut self,
         vertices: &[Vertex2],
         weights: Option<Vec<f64>>,
-        spatial_sorting: bool,
-    ) -> HowResult<()> {
-        #[cfg(feature = "wasm")]
-        if weights.is_some() {
-            return Err(anyhow::Error::msg(
-                "Weighted Delaunay is not supported in wasm (robust predicates are unweighted). Use weights: None.",
-            ));
-        }
+        order: InsertOrder,
+    ) -> HowResult<Vec<InsertStatus>> {
+        let first_new_idx = self.vertices.len();
+        let idxs_to_insert = self.prepare_insertion(vertices, weights, order, None, false)?;
 
-        let mut idxs_to_insert = Vec::new();
+        self.insert_prepared(first_new_idx, idxs_to_insert, |_| ControlFlow::Continue(()))
+    }
 
-        for v in vertices {
-            idxs_to_insert.push(self.vertices.len());
-            self.vertices.push(*v);
+    /// Insert a set of vertices like [`Self::insert_vertices_ordered`], returning their [`Chain`] if all of them are
+    /// collinear, instead of the error [`RitaError::AllPointsCollinear`].
+    ///
+    /// The indices of the chain are into `vertices`, the triangulation stays without triangles then.
+    ///
+    /// ## Errors
+    /// Returns the errors of [`Self::insert_vertices_ordered`], except for collinear vertices.
+    pub fn insert_vertices_or_degenerate(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+    ) -> HowResult<Insertion<Chain>> {
+        match self.insert_vertices_ordered(vertices, weights, order) {
+            Ok(statuses) => HowOk(Insertion::Inserted(statuses)),
+            Err(err) if err.downcast_ref() == Some(&RitaError::AllPointsCollinear) => {
+                HowOk(Insertion::Degenerate(Chain::new(vertices)))
+            }
+            Err(err) => Err(err),
         }
+    }
 
-        self.weights = weights;
+    /// Insert a set of vertices into the triangulation in the given order, reporting the progress to `progress`.
+    ///
+    /// The callback can cancel the insertion by returning [`ControlFlow::Break`], e.g. for huge inputs in a GUI.
+    /// The vertices processed before stay inserted and the triangulation stays sound, see [`InsertProgress`].
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`, [`InsertStatus::Cancelled`] for the
+    /// ones not processed.
+    pub fn insert_vertices_with_progress(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+        progress: impl FnMut(InsertProgress) -> ControlFlow<()>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        let first_new_idx = self.vertices.len();
+        let idxs_to_insert = self.prepare_insertion(vertices, weights, order, None, false)?;
 
-        if self.vertices().len() < 3 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 3 vertices to compute a 2D Triangulation!",
-            ));
-        }
+        self.insert_prepared(first_new_idx, idxs_to_insert, progress)
+    }
 
-        if spatial_sorting {
-            #[cfg(feature = "log_timing")]
-            let now = std::time::Instant::now();
+    /// Insert a set of vertices, that are already in a spatial order, e.g. along a Hilbert or Morton curve.
+    ///
+    /// Skips the spatial sort and the scans over all vertices for their bounds, e.g. for huge inputs that come from
+    /// a prior tiling step. The local origin is derived from the bounding box `bbox`, i.e. `[min, max]` of the vertices.
+    /// Wrong bounds do not affect the correctness, only the accuracy of the origin.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn insert_vertices_presorted(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        bbox: [Vertex2; 2],
+    ) -> HowResult<Vec<InsertStatus>> {
+        let first_new_idx = self.vertices.len();
+        let idxs_to_insert =
+            self.prepare_insertion(vertices, weights, InsertOrder::Input, Some(bbox), false)?;
 
-            idxs_to_insert = sort_along_hilbert_curve_2d(&self.vertices, &idxs_to_insert);
+        self.insert_prepared(first_new_idx, idxs_to_insert, |_| ControlFlow::Continue(()))
+    }
 
-            #[cfg(feature = "log_timing")]
-            log::trace!(
-                "Spatial sorting (hilbert curve) computed in {:.4} µs",
-                now.elapsed().as_micros()
-            );
-        }
+    /// Insert a set of vertices in parallel, by triangulating spatial chunks of them on multiple threads and merging the
+    /// partial triangulations.
+    ///
+    /// The vertices are sorted along a Hilbert curve and split into a chunk per thread, each triangulated on its own.
+    /// The triangles of a chunk, whose power circle contains no other vertex, are part of the result as they are.
+    /// The gaps along the borders of the chunks are filled by triangulating the vertices around them.
+    /// The result is a regular triangulation of the same vertices. In general position, it is the one of
+    /// [`Self::insert_vertices`] up to the order of the triangles, while cocircular vertices may be split differently.
+    /// Of several vertices at the same position, the first one in `vertices` is kept.
+    ///
+    /// Falls back to [`Self::insert_vertices`] along a Hilbert curve, where the merge does not apply: for a triangulation
+    /// that is not empty, bounded, observed or traced, has an epsilon or non-default predicates or duplicate handling,
+    /// for non-finite vertices or too few to split, and if the partial triangulations can not be merged into a regular
+    /// triangulation, e.g. for thin triangles whose power circles are off by rounding.
+    /// Returns the [`InsertStatus`] of each vertex, in the order of `vertices`.
+    pub fn par_insert_vertices(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        // the insertions borrow `self` mutably, so the pool is set aside meanwhile
+        let thread_pool = core::mem::take(&mut self.thread_pool);
+        let result = thread_pool.install(self.max_threads, || {
+            let num_chunks =
+                parallel::current_num_threads().min(vertices.len() / PAR_MIN_CHUNK_SIZE);
+            if num_chunks < 2 || !self.supports_par_insertion(vertices, weights.as_deref()) {
+                return self.insert_vertices_ordered(vertices, weights, InsertOrder::Hilbert);
+            }
 
-        if self.tds.num_tris() == 0 {
-            self.insert_init_tri(&mut idxs_to_insert)?;
-        }
+            self.vertices = vertices.to_vec();
+            self.weights = weights;
+            self.update_origin(0, None);
 
-        #[cfg(feature = "logging")]
-        log::debug!("Inserting {} vertices", idxs_to_insert.len());
+            match self.merge_chunks(num_chunks) {
+                Some(statuses) => HowOk(statuses),
+                None => {
+                    #[cfg(feature = "logging")]
+                    log::warn!(
+                        "Merging the partial triangulations failed, inserting the vertices sequentially"
+                    );
 
-        while let Some(v_idx) = idxs_to_insert.pop() {
-            let near_to_idx = self
-                .last_inserted_triangle
-                .unwrap_or(self.tds().num_tris() + self.tds().num_deleted_tris - 1);
+                    let weights = self.weights.take();
+                    self.vertices.clear();
+                    self.insert_vertices_ordered(vertices, weights, InsertOrder::Hilbert)
+                }
+            }
+        });
+        self.thread_pool = thread_pool;
 
-            self.insert_v_helper(v_idx, near_to_idx)?;
-        }
+        result
+    }
 
-        self.log_time();
+    /// Whether [`Self::par_insert_vertices`] can merge partial triangulations of the vertices into this triangulation.
+    fn supports_par_insertion(&self, vertices: &[Vertex2], weights: Option<&[f64]>) -> bool {
+        #[cfg(feature = "trace")]
+        if self.trace.is_some() {
+            return false;
+        }
+        #[cfg(feature = "wasm")]
+        if weights.is_some() {
+            return false;
+        }
 
-        HowOk(())
+        self.vertices.is_empty()
+            && self.epsilon.is_none()
+            && self.max_vertices.is_none()
+            && !self.observer.is_registered()
+            && self.predicate_mode.is_exact()
+            && self.dedup == DedupPolicy::Exact
+            && self.duplicate_policy == DuplicatePolicy::SkipAndMap
+            && weights.is_none_or(|weights| weights.len() == vertices.len())
+            && vertices.iter().flatten().all(|x| x.is_finite())
     }
 
-    pub fn insert_v_helper(&mut self, v_idx: usize, near_to: usize) -> HowResult<()> {
-        // Perform locate and measure time
-        #[cfg(feature = "timing")]
-        let now = std::time::Instant::now();
-        let containing_tri_idx = self.locate_vis_walk(v_idx, near_to)?; // the possibly invalid triangle
+    /// Triangulate chunks of the vertices in parallel and merge them, `None` if they can not be merged.
+    ///
+    /// The vertices, weights and origin are set already, all other state is set on success only, i.e. if the merged
+    /// triangulation is regular.
+    fn merge_chunks(&mut self, num_chunks: usize) -> Option<Vec<InsertStatus>> {
+        // the first of the vertices at a position is kept, in the order of the input
+        let mut by_position: Vec<VertexIdx> = (0..self.vertices.len()).collect();
+        by_position.par_sort_unstable_by(|&a, &b| {
+            let [a_x, a_y] = self.vertices[a];
+            let [b_x, b_y] = self.vertices[b];
+            a_x.total_cmp(&b_x)
+                .then(a_y.total_cmp(&b_y))
+                .then(a.cmp(&b))
+        });
+        let mut unique = Vec::with_capacity(by_position.len());
+        let mut duplicate_of = BTreeMap::new();
+        for &v_idx in &by_position {
+            match unique.last() {
+                Some(&first) if self.vertices[first] == self.vertices[v_idx] => {
+                    duplicate_of.insert(v_idx, first);
+                }
+                _ => unique.push(v_idx),
+            }
+        }
 
-        #[cfg(feature = "timing")]
-        {
-            self.time_walking += now.elapsed().as_micros();
+        let order = sort_along_hilbert_curve_2d(&self.vertices, &unique);
+        let chunk_size = order.len().div_ceil(num_chunks);
+        let chunks = order
+            .par_chunks(chunk_size)
+            .map(|chunk| self.triangulate_chunk(chunk))
+            .collect::<HowResult<Vec<_>>>()
+            .ok()?;
+
+        let grid = PointGrid::new(&self.vertices, &unique);
+        let max_weight = self.weights.as_ref().map_or(0.0, |weights| {
+            unique
+                .iter()
+                .map(|&v_idx| weights[v_idx])
+                .fold(0.0, f64::max)
+        });
+
+        // the vertices of a triangle that is not final, or on the hull of a chunk, may have triangles that are missing
+        let (final_tris, borders): (Vec<_>, Vec<_>) = chunks
+            .into_par_iter()
+            .map(|(chunk_tris, chunk_hull)| {
+                let (final_tris, other_tris): (Vec<_>, Vec<_>) = chunk_tris
+                    .into_iter()
+                    .partition(|&tri| self.is_final_tri(tri, &grid, max_weight, false));
+                let mut border = chunk_hull;
+                border.extend(other_tris.into_iter().flatten());
+                (final_tris, border)
+            })
+            .unzip();
+        let mut tris: Vec<_> = final_tris.into_iter().flatten().collect();
+        let mut border: Vec<_> = borders.into_iter().flatten().collect();
+
+        border.sort_unstable();
+        border.dedup();
+        if border.len() >= 3 {
+            let (border_tris, _) = self.triangulate_chunk(&border).ok()?;
+            tris.par_extend(
+                border_tris
+                    .into_par_iter()
+                    .filter(|&tri| self.is_final_tri(tri, &grid, max_weight, true)),
+            );
         }
 
-        // Skip vertices that are not in power circle by epsilon (i.e. above the hyperplane)
-        // but only if the containing triangle is casual (for now), i.e. the vertex is inside the current convex hull
-        if self.epsilon.is_some()
-            && self.tds().get_tri(containing_tri_idx)?.is_casual()
-            && !self.is_v_in_eps_powercircle(v_idx, containing_tri_idx)?
-        {
-            self.ignored_vertices.push(v_idx);
-            return HowOk(());
+        // a triangle may be final in its chunk and in the triangulation of the border, and is kept once
+        let mut keyed: Vec<([VertexIdx; 3], [VertexIdx; 3])> = tris
+            .into_iter()
+            .map(|tri| {
+                let mut key = tri;
+                key.sort_unstable();
+                (key, tri)
+            })
+            .collect();
+        keyed.par_sort_unstable();
+        keyed.dedup_by_key(|(key, _)| *key);
+        let tris: Vec<[VertexIdx; 3]> = keyed.into_iter().map(|(_, tri)| tri).collect();
+
+        let tds = TriDataStructure::from_tris(&tris).ok()?;
+        let mut used_vertices: Vec<VertexIdx> = tris.iter().flatten().copied().collect();
+        used_vertices.par_sort_unstable();
+        used_vertices.dedup();
+        if self.weights.is_none() && used_vertices.len() != unique.len() {
+            return None;
         }
 
-        // Perform insert and measure time
-        // Note in the weighted case we can check directly if the vertex is in the power circle of the triangle, cause it might already be redundant
-        // if yes we can skip it, avoid flips and directly go to the next one
-        if self.weighted() && !self.is_v_in_powercircle(v_idx, containing_tri_idx)? {
-            self.redundant_vertices.push(v_idx);
-            return HowOk(());
+        let redundant_vertices = unique
+            .into_iter()
+            .filter(|v_idx| used_vertices.binary_search(v_idx).is_err())
+            .collect();
+
+        // the power circles above are rounded, so the merged triangles are checked, locally regular ones are regular
+        let previous = (
+            core::mem::replace(&mut self.tds, tds),
+            core::mem::replace(&mut self.used_vertices, used_vertices),
+            core::mem::replace(&mut self.redundant_vertices, redundant_vertices),
+            self.last_inserted_triangle.replace(0),
+        );
+        if !self
+            .check_regularity(RegularityCheck::Local, false)
+            .is_regular()
+        {
+            (
+                self.tds,
+                self.used_vertices,
+                self.redundant_vertices,
+                self.last_inserted_triangle,
+            ) = previous;
+            return None;
         }
-        self.used_vertices.push(v_idx);
-
-        #[cfg(feature = "timing")]
-        let now = std::time::Instant::now();
 
-        let mut hedges_to_verify = Vec::new();
-        let [hedge0, hedge1, hedge2] = self.tds().get_tri(containing_tri_idx)?.hedges();
-        hedges_to_verify.push(hedge0.twin().idx);
-        hedges_to_verify.push(hedge1.twin().idx);
-        hedges_to_verify.push(hedge2.twin().idx);
-
-        let [t0, _, _] = self.tds.flip_1_to_3(containing_tri_idx, v_idx)?;
-        self.last_inserted_triangle = Some(t0.idx);
+        self.duplicate_vertices = duplicate_of.keys().copied().collect();
+        self.duplicate_of = duplicate_of;
+        self.generation += 1;
+        if self.use_locate_grid {
+            let tri_idx = 0;
+            let v = self.vertices[tris[tri_idx][0]];
+            self.update_locate_grid(v, tri_idx).ok()?;
+        }
 
-        #[cfg(feature = "timing")]
-        {
-            self.time_inserting += now.elapsed().as_micros();
-        };
+        Some(self.statuses(0))
+    }
 
-        // Perform flips and measure time
-        #[cfg(feature = "timing")]
-        let now = std::time::Instant::now();
-        while let Some(hedge_idx) = hedges_to_verify.pop() {
-            if let Some(flip) = self.should_flip_hedge(hedge_idx)? {
-                match flip {
-                    Flip::TwoToTwo => {
-                        let hedge = self.tds().get_hedge(hedge_idx)?;
+    /// Triangulate the vertices `idxs` on their own, with the local origin of this triangulation.
+    ///
+    /// Returns the casual triangles and the vertices on the hull, both as indices of [`Self::vertices`].
+    fn triangulate_chunk(
+        &self,
+        idxs: &[VertexIdx],
+    ) -> HowResult<(Vec<[VertexIdx; 3]>, Vec<VertexIdx>)> {
+        let mut chunk = Self::new(None);
+        chunk.vertices = idxs.iter().map(|&v_idx| self.vertices[v_idx]).collect();
+        chunk.weights = self
+            .weights
+            .as_ref()
+            .map(|weights| idxs.iter().map(|&v_idx| weights[v_idx]).collect());
+        chunk.origin = self.origin;
+
+        let mut idxs_to_insert =
+            sort_along_hilbert_curve_2d(&chunk.vertices, &(0..idxs.len()).collect::<Vec<_>>());
+        chunk.insert_init_tri(&mut idxs_to_insert)?;
+        chunk.insert_prepared(0, idxs_to_insert, |_| ControlFlow::Continue(()))?;
+
+        let tris = chunk
+            .tri_indices()
+            .into_iter()
+            .map(|tri| tri.map(|v_idx| idxs[v_idx]))
+            .collect();
+        let hull = chunk
+            .hull_edges()
+            .into_iter()
+            .map(|v_idx| idxs[v_idx])
+            .collect();
+
+        HowOk((tris, hull))
+    }
 
-                        // Push the hedges before perofming the flip, because the flip might shift indices
-                        //
-                        // We only need to push 2 new hedges on the stack, as follows
-                        // Denote the inserted vertex v, the hedge to test ab and the opposing point o, that shares ab with v
-                        // The flip makes vab and abo become vao and vbo respectively
-                        // Now the hedges to test are the ones not connected to v in any way, i.e. ao and bo
-                        hedges_to_verify.push(hedge.prev().twin().idx);
-                        hedges_to_verify.push(hedge.next().twin().idx);
+    /// Whether no vertex but its own lies in the power circle of the triangle, i.e. it is part of the triangulation
+    /// of all vertices.
+    ///
+    /// The symbolic perturbation decides vertices on the power circle differently for each triangulation, so these
+    /// count as outside for a triangle `of_border`, and as inside otherwise. A triangle not `of_border` is
+    /// conservatively `false` as well, if its power circle is too large to check quickly, e.g. for a thin triangle.
+    fn is_final_tri(
+        &self,
+        tri: [VertexIdx; 3],
+        grid: &PointGrid,
+        max_weight: f64,
+        of_border: bool,
+    ) -> bool {
+        let weight = |v_idx: VertexIdx| self.weights.as_ref().map_or(0.0, |weights| weights[v_idx]);
+        let [a, b, c] = tri.map(|v_idx| self.vertices[v_idx]);
+        let [h_a, h_b, h_c] = tri.map(|v_idx| self.height(v_idx));
+
+        // a vertex `p` is in the power circle, if its distance to the center is below `r^2 + w_p`
+        let center = orthocenter([a, b, c], tri.map(weight));
+        let power = (a[0] - center[0]).powi(2) + (a[1] - center[1]).powi(2) - weight(tri[0]);
+        let radius = (power + max_weight).sqrt() * (1.0 + PAR_RADIUS_SLACK);
+        let max_cells = if of_border { usize::MAX } else { PAR_MAX_CELLS };
+
+        grid.any_near(center, radius, max_cells, |v_idx| {
+            if tri.contains(&v_idx) {
+                return false;
+            }
 
-                        let [t0, _] = self.tds_mut().flip_2_to_2(hedge_idx)?;
-                        self.last_inserted_triangle = Some(t0.idx);
-                    }
-                    Flip::ThreeToOne((third_tri_idx, relfex_node_idx)) => {
-                        let hedge = self.tds().get_hedge(hedge_idx)?;
+            let p = self.vertices[v_idx];
+            let h_p = self.height(v_idx);
+            let sign = PredicateMode::Exact.orient_2dlifted(&a, &b, &c, &p, h_a, h_b, h_c, h_p);
+            #[cfg(feature = "wasm")]
+            let on_circle = sign == 0.0;
+            #[cfg(not(feature = "wasm"))]
+            let on_circle = {
+                let [a, b, c, p] =
+                    [(a, h_a), (b, h_b), (c, h_c), (p, h_p)].map(|(v, h)| [v[0], v[1], h]);
+                predicates::orient_3d(&a, &b, &c, &p) == 0.0
+            };
 
-                        // get the two incident triangles to the hedge, the third tri idx is in the flip
-                        let tri_idx_abd = hedge.tri().idx;
-                        let tri_idx_bcd = hedge.twin().tri().idx;
+            if on_circle { !of_border } else { sign > 0.0 }
+        }) == Some(false)
+    }
 
-                        let t0 = self.tds.flip_3_to_1(
-                            [tri_idx_abd, tri_idx_bcd, third_tri_idx],
-                            relfex_node_idx,
-                            &self.vertices,
-                        )?;
-                        self.last_inserted_triangle = Some(t0.idx);
+    /// Insert the vertices prepared by [`Self::prepare_insertion`], and report their status.
+    ///
+    /// Stops early if `progress` breaks, the remaining vertices are cancelled then.
+    fn insert_prepared(
+        &mut self,
+        first_new_idx: VertexIdx,
+        mut idxs_to_insert: Vec<VertexIdx>,
+        mut progress: impl FnMut(InsertProgress) -> ControlFlow<()>,
+    ) -> HowResult<Vec<InsertStatus>> {
+        #[cfg(feature = "logging")]
+        log::debug!("Inserting {} vertices", idxs_to_insert.len());
 
-                        // push the new hedges on the stack, these are the three edges of the newly created triangle
-                        // since in the flip 3 to 1, we overwrite the data structure, such that the new triangle now lives at tri_idx_abd
+        let num_total = self.vertices.len() - first_new_idx;
+        while let Some(v_idx) = idxs_to_insert.pop() {
+            let near_to_idx = self.walk_start(self.vertices[v_idx]);
 
-                        let [hedge0, hedge1, hedge2] = self.tds().get_tri(tri_idx_abd)?.hedges();
+            self.insert_v_helper(v_idx, near_to_idx)?;
 
-                        hedges_to_verify.push(hedge0.twin().idx);
-                        hedges_to_verify.push(hedge1.twin().idx);
-                        hedges_to_verify.push(hedge2.twin().idx);
-                    }
-                    _ => {
-                        #[cfg(feature = "logging")]
-                        log::error!("Unexpected flip type!");
-                    }
-                }
+            let num_processed = num_total - idxs_to_insert.len();
+            if InsertProgress::is_due(num_processed, idxs_to_insert.len())
+                && progress(InsertProgress {
+                    num_processed,
+                    num_total,
+                })
+                .is_break()
+            {
+                #[cfg(feature = "logging")]
+                log::info!("Insertion cancelled after {num_processed} of {num_total} vertices");
+                break;
             }
         }
-        #[cfg(feature = "timing")]
-        {
-            self.time_flipping += now.elapsed().as_micros();
-        }
-        HowOk(())
-    }
 
-    /// Check if a triangle is flat, i.e. exists of three co-linear points.
-    pub fn is_tri_flat(&self, tri_idx: usize) -> HowResult<bool> {
-        let tri = self.get_tri_type(tri_idx)?;
+        self.log_time();
 
-        let is_flat = match tri {
-            TriangleExtended::Triangle(tri_idxs) => {
-                predicates::orient_2d(&tri_idxs[0], &tri_idxs[1], &tri_idxs[2]) == 0.0
-            }
-            TriangleExtended::ConceptualTriangle(_) => false, // the conceptual triangle can't be flat
-        };
+        let mut statuses = self.statuses(first_new_idx);
+        for v_idx in idxs_to_insert {
+            statuses[v_idx - first_new_idx] = InsertStatus::Cancelled;
+        }
 
-        HowOk(is_flat)
+        HowOk(statuses)
     }
 
-    /// Check for a vertex, if it lies inside the power circle of a triangle.
-    pub fn is_v_in_powercircle(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
-        let p = self.vertices()[v_idx];
-        let h_p = self.height(v_idx);
-
-        let tri = self.get_tri_type(tri_idx)?;
-
-        let in_circle = match tri {
-            TriangleExtended::Triangle([a, b, c]) => {
-                let [h_a, h_b, h_c] = self
-                    .tds()
-                    .get_tri(tri_idx)?
-                    .nodes()
-                    .map(|n| self.height(n.idx().unwrap()));
-
-                predicates::orient_2dlifted_SOS(&a, &b, &c, &p, h_a, h_b, h_c, h_p)
-            }
-            // if the triangle is a line segment, then the power circle is a circle with infinite radius and we can use an orientation test
-            TriangleExtended::ConceptualTriangle(tri_idxs) => {
-                predicates::orient_2d(&tri_idxs[0], &tri_idxs[1], &p)
-            }
-        };
-
-        HowOk(in_circle > 0.0)
+    /// The status of each vertex from `first_new_idx` on.
+    fn statuses(&self, first_new_idx: VertexIdx) -> Vec<InsertStatus> {
+        insert_statuses(
+            first_new_idx,
+            self.vertices.len(),
+            [
+                // used vertices that are not part of the triangulation anymore, have been flipped out as redundant
+                (&self.used_vertices, InsertStatus::Redundant),
+                (&self.redundant_vertices, InsertStatus::Redundant),
+                (&self.ignored_vertices, InsertStatus::IgnoredByEpsilon),
+                (&self.duplicate_vertices, InsertStatus::Duplicate),
+            ],
+            self.tds.hedge_starting_nodes.iter(),
+        )
     }
 
-    /// Panics if `self.epsilon` is not set.
-    /// When `wasm` feature is on, returns an error (epsilon power circle requires weighted predicates).
-    pub(crate) fn is_v_in_eps_powercircle(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
-        #[cfg(feature = "wasm")]
-        let _ = (v_idx, tri_idx);
-        #[cfg(feature = "wasm")]
-        return Err(anyhow::Error::msg(
-            "Epsilon power circle is not supported in wasm (robust predicates are unweighted).",
-        ));
-
-        #[cfg(not(feature = "wasm"))]
-        {
-            let p = self.vertices()[v_idx];
-
-            let h_p = if let Some(epsilon) = self.epsilon {
-                self.height(v_idx) + epsilon
-            } else {
-                panic!("Epsilon not set!");
-            };
-
-            let tri = self.get_tri_type(tri_idx)?;
+    /// Insert a set of vertices into the triangulation, skipping vertices that fail to insert.
+    ///
+    /// Instead of aborting on the first failing vertex, e.g. due to non-finite coordinates or a failed locate,
+    /// the vertex is skipped and recorded as an [`InsertIssue`], and the insertion continues with the next one.
+    /// Useful when a nearly complete triangulation is more valuable than none, e.g. for visualization.
+    ///
+    /// A vertex that fails after it changed the triangulation, e.g. in a flip after the 1->3 split of its containing
+    /// triangle, is taken out again by rebuilding the triangulation without it, see [`Self::remove_vertices`].
+    /// So the remaining vertices are always inserted into a regular triangulation.
+    ///
+    /// ## Errors
+    /// Returns an error if no initial triangle can be built, the capacity is exceeded, or the triangulation can not
+    /// be rebuilt without a partially inserted vertex.
+    pub fn insert_vertices_lenient(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+    ) -> HowResult<Vec<InsertIssue>> {
+        let first_new_idx = self.vertices.len();
+        let mut idxs_to_insert = self.prepare_insertion(vertices, weights, order, None, true)?;
+
+        let mut issues: Vec<InsertIssue> = (first_new_idx..self.vertices.len())
+            .filter(|&v_idx| !self.is_finite(v_idx))
+            .map(|v_idx| InsertIssue {
+                v_idx,
+                error: anyhow::Error::new(RitaError::NonFinite),
+            })
+            .collect();
 
-            match tri {
-                TriangleExtended::Triangle([a, b, c]) => {
-                    let [h_a, h_b, h_c] = self
-                        .tds()
-                        .get_tri(tri_idx)?
-                        .nodes()
-                        .map(|n| self.height(n.idx().unwrap()));
+        while let Some(v_idx) = idxs_to_insert.pop() {
+            let near_to_idx = self.walk_start(self.vertices[v_idx]);
 
-                    let in_eps_circle =
-                        predicates::orient_2dlifted_SOS(&a, &b, &c, &p, h_a, h_b, h_c, h_p);
+            if let Err(error) = self.insert_v_helper(v_idx, near_to_idx) {
+                #[cfg(feature = "logging")]
+                log::warn!("Skipping vertex {v_idx}: {error}");
 
-                    HowOk(in_eps_circle > 0.0)
+                // the vertex is used once its insertion started to change the triangulation
+                if self.used_vertices.last() == Some(&v_idx) {
+                    self.take_out_failed_vertex(v_idx)?;
                 }
-                TriangleExtended::ConceptualTriangle(_) => Err(anyhow::Error::msg(
-                    "Epsilon power circle test not allowed for conceptual triangles yet!",
-                )),
+                issues.push(InsertIssue { v_idx, error });
             }
         }
+
+        self.log_time();
+
+        HowOk(issues)
     }
 
-    /// Check if the triangulation is regular w.r.t. the empty power-sphere property.
+    /// Insert a stream of vertices with optional weights, consuming it lazily chunk by chunk.
     ///
-    /// Returns if the validation is valid and to what degree.
-    pub fn is_regular(&self) -> HowResult<(bool, f64)> {
-        let mut regular = true;
-        let mut num_violated_triangles = 0;
+    /// Each chunk of up to 65536 vertices is sorted along a Hilbert curve on its own, so only one chunk is buffered
+    /// besides the triangulation itself, e.g. for datasets read from disk that don't fit in a single slice.
+    ///
+    /// The triangulation is weighted if it already is, or if it is empty and the first vertex has a weight.
+    /// Like for [`Self::insert_vertex`], a missing weight defaults to zero in a weighted triangulation,
+    /// and weights are ignored in an unweighted one.
+    ///
+    /// Returns the number of vertices consumed from the stream, their status is counted e.g. by [`Self::num_used_vertices`].
+    ///
+    /// ## Errors
+    /// Returns an error if the first chunk of an empty triangulation has no initial triangle, e.g. with fewer than
+    /// 3 vertices, or the capacity is exceeded. The vertices of the chunks before stay inserted.
+    pub fn insert_stream(
+        &mut self,
+        stream: impl IntoIterator<Item = (Vertex2, Option<f64>)>,
+    ) -> HowResult<usize> {
+        self.insert_stream_chunked(stream, 1 << 16)
+    }
 
-        for tri_idx in 0..self.tds().num_tris() + self.tds().num_deleted_tris {
-            // Skip triangles that have been deleted by 3->1 flips
-            if self
-                .tds()
-                .get_tri(tri_idx)?
-                .nodes()
-                .contains(&VertexNode::Deleted)
-            {
-                continue;
-            }
+    /// Insert a stream of vertices in chunks of `chunk_len`, see [`Self::insert_stream`].
+    fn insert_stream_chunked(
+        &mut self,
+        stream: impl IntoIterator<Item = (Vertex2, Option<f64>)>,
+        chunk_len: usize,
+    ) -> HowResult<usize> {
+        let mut stream = stream.into_iter();
+        let mut chunk = Vec::with_capacity(chunk_len);
+        let mut chunk_weights = Vec::with_capacity(chunk_len);
+        let mut num_consumed = 0;
 
-            if self.is_tri_flat(tri_idx)? {
-                #[cfg(feature = "logging")]
-                error!("Flat triangle: {}", self.tds().get_tri(tri_idx)?);
-                regular = false;
-                num_violated_triangles += 1;
+        loop {
+            chunk.clear();
+            chunk_weights.clear();
+            for (v, weight) in stream.by_ref().take(chunk_len) {
+                chunk.push(v);
+                chunk_weights.push(weight);
             }
-
-            // Check the redundant vertices, for this any computed triangulation should always be regular
-            for &v_idx in &self.redundant_vertices {
-                // skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
-                if self
-                    .tds()
-                    .get_tri(tri_idx)?
-                    .nodes()
-                    .contains(&VertexNode::Casual(v_idx))
-                {
-                    continue;
-                }
-
-                if self.is_v_in_powercircle(v_idx, tri_idx)? {
-                    // #[cfg(feature = "logging")]
-                    // log::error!("Vertex in power circle: {}", self.tds().get_tri(tri_idx)?);
-                    regular = false;
-                    num_violated_triangles += 1; // s. the break below
-                    break;
-                }
+            if chunk.is_empty() {
+                break;
             }
 
-            // Check the used vertices, for this any computed triangulation should always be regular
-            for &v_idx in &self.used_vertices {
-                // skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
-                if self
-                    .tds()
-                    .get_tri(tri_idx)?
-                    .nodes()
-                    .contains(&VertexNode::Casual(v_idx))
-                {
-                    continue;
-                }
-
-                if self.is_v_in_powercircle(v_idx, tri_idx)? {
-                    // #[cfg(feature = "logging")]
-                    // log::error!("Vertex in power circle: {}", self.tds().get_tri(tri_idx)?);
-                    regular = false;
-                    num_violated_triangles += 1; // s. the break below
-                    break;
-                }
+            // checked before the weights are taken, which are only put back by a successful preparation
+            self.check_capacity(chunk.len())?;
+
+            // the weights of the insertion are those of all vertices
+            let is_weighted =
+                self.weights.is_some() || (self.vertices.is_empty() && chunk_weights[0].is_some());
+            let weights = is_weighted.then(|| {
+                let mut weights = self.weights.take().unwrap_or_default();
+                weights.extend(chunk_weights.iter().map(|weight| weight.unwrap_or(0.0)));
+                weights
+            });
+
+            let mut idxs_to_insert =
+                self.prepare_insertion(&chunk, weights, InsertOrder::Hilbert, None, false)?;
+            while let Some(v_idx) = idxs_to_insert.pop() {
+                let near_to_idx = self.walk_start(self.vertices[v_idx]);
+                self.insert_v_helper(v_idx, near_to_idx)?;
             }
+            num_consumed += chunk.len();
         }
 
-        HowOk((
-            regular,
-            1.0 - num_violated_triangles as f64 / self.tds().num_tris() as f64,
-        ))
+        self.log_time();
+
+        HowOk(num_consumed)
     }
 
-    /// Checks regularity in a parallel manner using `rayon`s `par_iter()`.
+    /// Remove vertices from the triangulation, by rebuilding it from the remaining vertices.
     ///
-    /// This can significantly reduce the runtime of this predicate.
-    #[must_use]
-    pub fn par_is_regular(&self, with_ignored_vertices: bool) -> f64 {
-        let num_tris = self.tds().num_tris();
-        let num_deleted_tris = self.tds().num_deleted_tris;
-
-        let num_violated_tris: f64 = (0..num_tris + num_deleted_tris)
-            .into_par_iter()
-            .map(|tri_idx| {
-                // Skip triangles that have been deleted by 3->1 flips
-                if self
-                    .tds()
-                    .get_tri(tri_idx)
-                    .unwrap()
-                    .nodes()
-                    .contains(&VertexNode::Deleted)
-                {
-                    0.0
-                } else if self.is_tri_flat(tri_idx).unwrap() {
-                    1.0
-                } else {
-                    // Check the used vertices, for this any computed tetrahedralization should always be regular
-                    let used_violation = self.used_vertices.iter().find(|&&v_idx| {
-                        // Skip vertices that are part of the current tetrahedron
-                        if self
-                            .tds()
-                            .get_tri(tri_idx)
-                            .unwrap()
-                            .nodes()
-                            .contains(&VertexNode::Casual(v_idx))
-                        {
-                            return false;
-                        }
-
-                        self.is_v_in_powercircle(v_idx, tri_idx).unwrap()
-                    });
+    /// The vertices keep their indices, vertices that are not part of the triangulation are skipped.
+    /// As the regular triangulation of the remaining vertices is unique, this is the same as removing them one by one.
+    /// The observer and the trace stay registered, the insertions of the rebuild are not reported to them.
+    ///
+    /// ## Errors
+    /// Returns an error if an index is out of bounds, or no triangle can be built from the remaining vertices.
+    /// `self` is left unchanged then.
+    pub fn remove_vertices(&mut self, v_idxs: &[VertexIdx]) -> HowResult<()> {
+        let mut rebuilt = self.rebuild_without(v_idxs)?;
+        rebuilt.observer = core::mem::take(&mut self.observer);
+        #[cfg(feature = "trace")]
+        {
+            rebuilt.trace = self.trace.take();
+        }
+        *self = rebuilt;
 
-                    if used_violation.is_some() {
-                        return 1.0;
-                    }
+        HowOk(())
+    }
 
-                    // Check the redundant vertices
-                    let redundant_violation = self.redundant_vertices.iter().find(|&&v_idx| {
-                        // Skip vertices that are part of the current tetrahedron
-                        if self
-                            .tds()
-                            .get_tri(tri_idx)
-                            .unwrap()
-                            .nodes()
-                            .contains(&VertexNode::Casual(v_idx))
-                        {
-                            return false;
-                        }
+    /// A copy of the triangulation, rebuilt from its vertices without `v_idxs`, see [`Self::remove_vertices`].
+    pub(crate) fn rebuild_without(&self, v_idxs: &[VertexIdx]) -> HowResult<Self> {
+        let mut is_removed = vec![false; self.vertices.len()];
+        for &v_idx in v_idxs {
+            *is_removed
+                .get_mut(v_idx)
+                .ok_or(RitaError::IndexOutOfBounds {
+                    kind: "vertex",
+                    idx: v_idx,
+                })? = true;
+        }
 
-                        self.is_v_in_powercircle(v_idx, tri_idx).unwrap()
-                    });
+        let mut is_used = vec![false; self.vertices.len()];
+        for node in self.tds.hedge_starting_nodes.iter() {
+            if let VertexNode::Casual(v_idx) = node {
+                is_used[v_idx] = true;
+            }
+        }
 
-                    if redundant_violation.is_some() {
-                        return 1.0;
-                    }
+        let remaining: Vec<VertexIdx> = (0..self.vertices.len())
+            .filter(|&v_idx| is_used[v_idx] && !is_removed[v_idx])
+            .collect();
+
+        // a bounded triangulation is rebuilt into buffers of the same capacity, which are filled via `clone_from`
+        let mut rebuilt = Self {
+            origin: self.origin,
+            predicate_mode: self.predicate_mode,
+            eps_in_flips: self.eps_in_flips,
+            insertion_strategy: self.insertion_strategy,
+            max_threads: self.max_threads,
+            thread_pool: self.thread_pool.clone(),
+            // the grid is built again by the insertions below
+            use_locate_grid: self.use_locate_grid,
+            insert_order: self.insert_order,
+            dedup: self.dedup,
+            duplicate_policy: self.duplicate_policy,
+            generation: self.generation + 1,
+            ..self.max_vertices.map_or_else(
+                || Self::new(None),
+                |max_vertices| Self::with_bounded_capacity(None, max_vertices),
+            )
+        };
+        rebuilt.vertices.clone_from(&self.vertices);
+        rebuilt.weights.clone_from(&self.weights);
+        if let (Some(max_vertices), Some(weights)) = (self.max_vertices, &mut rebuilt.weights) {
+            weights.reserve_exact(max_vertices.saturating_sub(weights.len()));
+        }
 
-                    // Check the ignored vertices, here we can account for the degree of irregularity the epsilon filter introduced
-                    if with_ignored_vertices {
-                        let ignored_violation = self
-                            .ignored_vertices
-                            .iter()
-                            .find(|&&v_idx| self.is_v_in_powercircle(v_idx, tri_idx).unwrap());
+        // without epsilon, such that none of the remaining vertices is ignored
+        let mut idxs_to_insert = sort_along_hilbert_curve_2d(&rebuilt.vertices, &remaining);
+        rebuilt.insert_init_tri(&mut idxs_to_insert)?;
+        while let Some(v_idx) = idxs_to_insert.pop() {
+            let near_to_idx = rebuilt.walk_start(rebuilt.vertices[v_idx]);
+            rebuilt.insert_v_helper(v_idx, near_to_idx)?;
+        }
 
-                        if ignored_violation.is_some() {
-                            return 1.0;
-                        }
-                    }
+        rebuilt.epsilon = self.epsilon;
+        rebuilt
+            .redundant_vertices
+            .clone_from(&self.redundant_vertices);
+        rebuilt.ignored_vertices.clone_from(&self.ignored_vertices);
+        rebuilt
+            .duplicate_vertices
+            .clone_from(&self.duplicate_vertices);
+        rebuilt.duplicate_of.clone_from(&self.duplicate_of);
+        rebuilt.removed_vertices.clone_from(&self.removed_vertices);
+        rebuilt.removed_vertices.extend(
+            v_idxs
+                .iter()
+                .filter(|&&v_idx| is_used[v_idx] && is_removed[v_idx]),
+        );
+        #[cfg(feature = "metrics")]
+        {
+            rebuilt.stats = self.stats;
+        }
 
-                    0.0
-                }
-            })
-            .sum();
+        HowOk(rebuilt)
+    }
 
-        1.0 - num_violated_tris / self.tds().num_tris() as f64
+    /// Coarsen the triangulation to `target_vertex_count` vertices, by removing the least important vertices first.
+    ///
+    /// The importance of a vertex is the error its removal introduces on the values of the `error_metric`,
+    /// see [`decimation`](crate::decimation). Vertices on the convex hull are never removed, so the covered area is preserved,
+    /// and the decimation stops early, if only those are left.
+    /// Returns the removed vertices, and the largest error of any of them w.r.t. the decimated triangulation.
+    ///
+    /// ## Errors
+    /// Returns an error if there are fewer attribute values than vertices, or the triangulation can not be rebuilt.
+    pub fn decimate(
+        &mut self,
+        target_vertex_count: usize,
+        error_metric: ErrorMetric,
+    ) -> HowResult<Decimation> {
+        decimate(self, target_vertex_count, error_metric)
     }
 
-    pub fn is_regular_for_point_set(
+    /// Build a chain of nested levels of detail, by decimating the triangulation to each of the vertex counts in `levels` in turn.
+    ///
+    /// The first level is a copy of the triangulation, followed by one level per entry of `levels`, see [`Self::decimate`].
+    /// The vertices removed in a level are mapped to the triangles of that level which contain them, such that renderers
+    /// can blend between levels, see [`decimation`](crate::decimation). The triangulation itself is left unchanged.
+    ///
+    /// ## Errors
+    /// Returns an error if there are fewer attribute values than vertices, or a level can not be built.
+    pub fn build_lod_chain(
         &self,
-        vertices: &[[f64; 2]],
-        weights: Option<Vec<f64>>,
-    ) -> HowResult<(bool, f64)> {
-        let mut regular = true;
-        let mut num_violated_triangles = 0;
+        levels: &[usize],
+        error_metric: ErrorMetric,
+    ) -> HowResult<LodChain> {
+        build_lod_chain(self, levels, error_metric)
+    }
 
-        let weights = if let Some(weights) = weights {
-            weights
-        } else {
-            vec![0.0; vertices.len()]
-        };
-
-        for tri_idx in 0..self.tds().num_tris() + self.tds().num_deleted_tris {
-            // Skip triangles that have been deleted by 3->1 flips
-            if self
-                .tds()
-                .get_tri(tri_idx)?
-                .nodes()
-                .contains(&VertexNode::Deleted)
-            {
-                continue;
-            }
-
-            if self.is_tri_flat(tri_idx)? {
-                #[cfg(feature = "logging")]
-                error!("Flat triangle: {}", self.tds().get_tri(tri_idx)?);
-                regular = false;
-                num_violated_triangles += 1;
-            }
-
-            // Check the used vertices, for this any computed triangulation should always be regular
-            for (idx, v) in vertices.iter().enumerate() {
-                // TODO: skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
-
-                let h_v = v[0].powi(2) + v[1].powi(2) - weights[idx];
+    /// Compute one step of Lloyd's relaxation, i.e. move each vertex to the centroid of its cell clipped to `bbox`.
+    ///
+    /// The cells are the power cells, i.e. the Voronoi cells for unweighted triangulations, see [`voronoi`](crate::voronoi).
+    /// `bbox` is `[min, max]` of the box, that bounds the cells of the vertices on the convex hull.
+    /// Returns the relaxed position of each vertex, in the same order as [`Self::vertices`].
+    /// Vertices that are not part of the triangulation, or whose cell does not overlap the box, keep their position.
+    ///
+    /// Repeatedly triangulating the relaxed positions converges to a centroidal Voronoi tessellation.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation has no triangles.
+    pub fn lloyd_step(&self, bbox: [Vertex2; 2]) -> HowResult<Vec<Vertex2>> {
+        lloyd_step(self, bbox)
+    }
 
-                let tri = self.get_tri_type(tri_idx)?;
+    /// Refine the triangulation into a quality mesh, by inserting Steiner points, see [`refinement`](crate::refinement).
+    ///
+    /// Afterwards no triangle has an angle smaller than `min_angle` (in degrees, at most [`MAX_MIN_ANGLE`](crate::refinement::MAX_MIN_ANGLE)),
+    /// or an area larger than `max_area`, if given. Angles of the hull corners, and slivers along hull edges due to
+    /// rounding, can not be improved, they are kept.
+    /// The convex hull, and so the covered area, is preserved. Returns the number of inserted vertices.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation is weighted or has no triangles.
+    pub fn refine(&mut self, max_area: Option<f64>, min_angle: f64) -> HowResult<usize> {
+        refine(self, max_area, min_angle)
+    }
 
-                let in_circle = match tri {
-                    TriangleExtended::Triangle([a, b, c]) => {
-                        let [h_a, h_b, h_c] = self
-                            .tds()
-                            .get_tri(tri_idx)?
-                            .nodes()
-                            .map(|n| self.height(n.idx().unwrap()));
+    /// Re-insert the vertices ignored due to epsilon, with `new_epsilon` instead, e.g. a smaller one.
+    ///
+    /// The vertices are inserted into the current triangulation, i.e. without rebuilding it, such that calling this with
+    /// decreasing epsilons gives progressively finer levels of detail. `None` re-inserts all of them.
+    /// The vertices that are still ignored under `new_epsilon` remain in [`Self::ignored_vertices`].
+    /// Returns the number of vertices that are part of the triangulation now.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation has no triangles, or a re-insertion fails.
+    /// The vertices not re-inserted by then remain in [`Self::ignored_vertices`].
+    pub fn refine_epsilon(&mut self, new_epsilon: Option<f64>) -> HowResult<usize> {
+        if self.tds.num_tris() == 0 {
+            return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+        }
 
-                        predicates::orient_2dlifted_SOS(&a, &b, &c, v, h_a, h_b, h_c, h_v)
-                    }
-                    // if the triangle is a line segment, then the power circle is a circle with infinite radius and we can use an orientation test
-                    TriangleExtended::ConceptualTriangle(tri_idxs) => {
-                        predicates::orient_2d(&tri_idxs[0], &tri_idxs[1], v)
-                    }
-                };
+        self.epsilon = new_epsilon;
+        let num_used = self.used_vertices.len();
 
-                if in_circle > 0.0 {
-                    regular = false;
-                    num_violated_triangles += 1;
-                    break; // each triangle can be violated once
+        let ignored = core::mem::take(&mut self.ignored_vertices);
+        let mut idxs_to_insert = sort_along_hilbert_curve_2d(&self.vertices, &ignored);
+        while let Some(v_idx) = idxs_to_insert.pop() {
+            let near_to_idx = self.walk_start(self.vertices[v_idx]);
+            let num_listed = self.num_listed_vertices();
+            if let Err(err) = self.insert_v_helper(v_idx, near_to_idx) {
+                // the vertices not processed stay ignored, as does the failing one, unless it is listed already
+                if self.num_listed_vertices() == num_listed {
+                    idxs_to_insert.push(v_idx);
                 }
+                self.ignored_vertices.extend(idxs_to_insert);
+                return Err(err);
             }
         }
 
-        HowOk((
-            regular,
-            1.0 - num_violated_triangles as f64 / self.tds().num_tris() as f64,
-        ))
-    }
-
-    pub fn is_sound(&self) -> HowResult<bool> {
-        if self.tds().is_sound() {
-            HowOk(true)
-        } else {
-            #[cfg(feature = "logging")]
-            error!("Triangulation is not sound!");
-            HowOk(false)
-        }
-    }
-
-    pub fn num_ignored_vertices(&self) -> usize {
-        self.ignored_vertices.len()
-    }
-
-    /// The number of all `tris` in the triangulation, `casual` and `conceptual`.
-    pub const fn num_tris(&self) -> usize {
-        self.tds().num_tris()
-    }
-
-    /// The number of `casual` `tris`, i.e. without the ones that have an connection to the dummy point.
-    #[must_use]
-    pub fn num_casual_tris(&self) -> usize {
-        self.tds().num_casual_tris()
+        HowOk(self.used_vertices.len() - num_used)
     }
 
-    /// The number of total tris, i.e. `casual`, `conceptual` and `deleted` tris.
-    #[must_use]
-    pub const fn num_all_tris(&self) -> usize {
-        self.tds().num_tris() + self.tds().num_deleted_tris
+    /// The number of vertices that are used, redundant or ignored.
+    fn num_listed_vertices(&self) -> usize {
+        self.used_vertices.len() + self.redundant_vertices.len() + self.ignored_vertices.len()
     }
 
-    pub fn num_redundant_vertices(&self) -> usize {
-        self.redundant_vertices.len()
+    /// Check if the coordinates and the weight of a vertex are finite.
+    fn is_finite(&self, v_idx: VertexIdx) -> bool {
+        self.vertices[v_idx].iter().all(|x| x.is_finite())
+            && self
+                .weights
+                .as_ref()
+                .is_none_or(|weights| weights[v_idx].is_finite())
     }
 
-    pub fn num_used_vertices(&self) -> usize {
-        self.used_vertices.len()
+    /// Check if a vertex is skipped as a duplicate of an inserted one, according to the [`DedupPolicy`].
+    fn is_duplicate(&self, v: Vertex2, inserted: Vertex2) -> bool {
+        match self.dedup {
+            DedupPolicy::Exact => v == inserted,
+            DedupPolicy::Within(distance) => {
+                let [dx, dy] = [v[0] - inserted[0], v[1] - inserted[1]];
+                v == inserted || dx * dx + dy * dy <= distance * distance
+            }
+        }
     }
 
-    pub(crate) fn should_flip_hedge(&mut self, hedge_idx: usize) -> HowResult<Option<Flip>> {
-        let hedge = self.tds().get_hedge(hedge_idx)?;
-
-        // Skip hedges that have been deleted by 3->1 flips
-        if hedge.starting_node() == VertexNode::Deleted || hedge.end_node() == VertexNode::Deleted {
-            return HowOk(None);
+    /// Push the new vertices and weights, and order them for insertion.
+    ///
+    /// Builds the initial triangle if there is none yet. With `skip_non_finite`, vertices with
+    /// non-finite coordinates or weights are left out of the returned indices.
+    fn prepare_insertion(
+        &mut self,
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        order: InsertOrder,
+        bbox: Option<[Vertex2; 2]>,
+        skip_non_finite: bool,
+    ) -> HowResult<Vec<VertexIdx>> {
+        #[cfg(feature = "wasm")]
+        if weights.is_some() {
+            return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+        }
+        self.check_capacity(vertices.len())?;
+        // the weights are those of all vertices, the inserted ones and the ones before
+        let num_vertices = self.vertices.len() + vertices.len();
+        if let Some(num_weights) = weights
+            .as_ref()
+            .map(Vec::len)
+            .filter(|&n| n != num_vertices)
+        {
+            return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+                num_weights,
+                num_vertices,
+            }));
         }
 
-        let tri_idx_abd = hedge.tri().idx;
-        let node_a = hedge.prev().starting_node();
-        let node_b = hedge.starting_node();
+        let mut idxs_to_insert = Vec::new();
+        let first_new_idx = self.vertices.len();
 
-        let tri_idx_bcd = hedge.twin().tri().idx;
-        let node_c = hedge.twin().prev().starting_node();
-        let node_d = hedge.twin().starting_node();
+        for v in vertices {
+            idxs_to_insert.push(self.vertices.len());
+            self.vertices.push(*v);
+        }
 
-        // Skip edges that have been deleted by 3->1 flips
-        if node_a == VertexNode::Deleted
-            || node_b == VertexNode::Deleted
-            || node_c == VertexNode::Deleted
-            || node_d == VertexNode::Deleted
+        self.weights = weights;
+        if let (Some(max_vertices), Some(weights)) = (self.max_vertices, &mut self.weights) {
+            // such that single insertions later on do not re-allocate
+            weights.reserve_exact(max_vertices.saturating_sub(weights.len()));
+        }
+        if skip_non_finite {
+            idxs_to_insert.retain(|&v_idx| self.is_finite(v_idx));
+        }
+        if let (DuplicatePolicy::MergeWeights, Some(weights)) =
+            (self.duplicate_policy, &mut self.weights)
         {
-            return HowOk(None);
+            merge_duplicate_weights(&self.vertices, weights, &idxs_to_insert);
         }
+        self.update_origin(first_new_idx, bbox);
 
-        match (node_a, node_b, node_c, node_d) {
-            (
-                VertexNode::Casual(idx_node_a),
-                VertexNode::Casual(idx_node_b), // from the hedge
-                VertexNode::Casual(idx_node_c),
-                VertexNode::Casual(idx_node_d), // from the hedge
-            ) => {
-                let mut flip = Some(Flip::TwoToTwo);
+        if self.vertices().len() < 3 {
+            return Err(anyhow::Error::new(RitaError::NotEnoughVertices {
+                required: 3,
+            }));
+        }
 
-                if self.weighted() {
-                    // this can make flipe a 3->1, None or stay a 2->2
-                    flip = self.is_flippable(
-                        [idx_node_b, idx_node_d],
-                        [idx_node_a, idx_node_c],
-                        hedge_idx,
-                    );
+        match order {
+            InsertOrder::Input => (),
+            InsertOrder::Hilbert | InsertOrder::WeightedHilbert => {
+                #[cfg(feature = "timing")]
+                let now = std::time::Instant::now();
 
-                    if flip.is_none() {
-                        return HowOk(None); // edge is not flippable (i.e. a 3 to 1 flip, that cant be made due to internal structure of the triangulation)
-                    }
-                }
+                idxs_to_insert = sort_along_hilbert_curve_2d(&self.vertices, &idxs_to_insert);
 
-                // TODO we should be able to do only one check, if we keep track of the currently inserted vertex here, then the check is clear
-                if self.is_v_in_powercircle(idx_node_c, tri_idx_abd)?
-                    || self.is_v_in_powercircle(idx_node_a, tri_idx_bcd)?
+                #[cfg(feature = "timing")]
                 {
-                    HowOk(flip) // flip necessary, not regular
-                } else {
-                    HowOk(None) // no flip necessary, already regular
+                    self.stats.time_hilbert += now.elapsed().as_micros();
                 }
+                #[cfg(feature = "log_timing")]
+                log::trace!(
+                    "Spatial sorting (hilbert curve) computed in {:.4} µs",
+                    now.elapsed().as_micros()
+                );
             }
-            (
-                VertexNode::Conceptual,
-                VertexNode::Casual(_),
-                VertexNode::Casual(_),
-                VertexNode::Casual(_),
-            ) => HowOk(None),
-            (
-                VertexNode::Casual(idx_node_a),
-                VertexNode::Conceptual,
-                VertexNode::Casual(idx_node_c),
-                VertexNode::Casual(idx_node_d),
-            ) => {
-                if is_convex(
-                    self.vertices()[idx_node_c],
-                    self.vertices()[idx_node_d],
-                    self.vertices()[idx_node_a],
-                ) {
-                    HowOk(Some(Flip::TwoToTwo))
-                } else {
-                    HowOk(None)
-                }
+            InsertOrder::Random(seed) => shuffle(&mut idxs_to_insert, seed),
+            InsertOrder::Morton => {
+                idxs_to_insert = sort_along_morton_curve_2d(&self.vertices, &idxs_to_insert);
             }
-            (
-                VertexNode::Casual(idx_node_a),
-                VertexNode::Casual(_),
-                VertexNode::Conceptual,
-                VertexNode::Casual(_),
-            ) => {
-                if self.is_v_in_powercircle(idx_node_a, tri_idx_bcd)?
-                    || self.is_tri_flat(tri_idx_abd)?
-                {
-                    HowOk(Some(Flip::TwoToTwo))
-                } else {
-                    HowOk(None)
-                }
+            InsertOrder::Brio(seed) => {
+                // popped from the end, such that the first round is inserted first
+                idxs_to_insert = brio_order_2d(&self.vertices, &idxs_to_insert, seed);
+                idxs_to_insert.reverse();
             }
-            (
-                VertexNode::Casual(idx_node_a),
-                VertexNode::Casual(idx_node_b),
-                VertexNode::Casual(idx_node_c),
-                VertexNode::Conceptual,
-            ) => {
-                if is_convex(
-                    self.vertices()[idx_node_a],
-                    self.vertices()[idx_node_b],
-                    self.vertices()[idx_node_c],
-                ) {
-                    HowOk(Some(Flip::TwoToTwo))
-                } else {
-                    HowOk(None)
-                }
+        }
+
+        if order == InsertOrder::WeightedHilbert {
+            if let Some(weights) = &self.weights {
+                sort_buckets_by_weight(&mut idxs_to_insert, weights);
             }
-            (_, _, _, _) => Err(anyhow::Error::msg(
-                "Unexpected node configuration to decide flip for!",
-            )),
         }
-    }
 
-    /// Get the triangulation data structure, as reference.
-    #[must_use]
-    pub const fn tds(&self) -> &TriDataStructure {
-        &self.tds
-    }
+        if self.tds.num_tris() == 0 {
+            self.insert_init_tri(&mut idxs_to_insert)?;
+        }
 
-    /// Get the triangulation data structure, as mutable reference.
-    #[must_use]
-    pub const fn tds_mut(&mut self) -> &mut TriDataStructure {
-        &mut self.tds
+        HowOk(idxs_to_insert)
     }
 
-    /// Get the triangles of the triangulation as `Triangle2`, i.e `[[f64; 2]; 3]`.
-    ///
-    /// Does not include conceptual triangles, i.e. the convex hull edges
-    /// connected to the point at infinity.
-    pub fn tris(&self) -> Vec<Triangle2> {
-        // todo: handle the results gracefully, instead of unwrapping (which is safe here though)
-        (0..self.tds().num_tris() + self.tds().num_deleted_tris)
-            .filter_map(|tri_idx| {
-                let tri = self.tds().get_tri(tri_idx).ok()?;
+    pub fn insert_v_helper(&mut self, v_idx: usize, near_to: usize) -> HowResult<()> {
+        // the power tests of queries since the last insertion are not counted
+        #[cfg(feature = "metrics")]
+        self.filter_counts.take();
 
-                if tri.is_conceptual() || tri.is_deleted() {
-                    return None;
-                }
+        let result = self.insert_v_helper_inner(v_idx, near_to);
 
-                let [node0, node1, node2] = tri.nodes();
+        #[cfg(feature = "metrics")]
+        self.stats.record_filter_counts(self.filter_counts.take());
 
-                Some([
-                    self.vertices[node0.idx().unwrap()],
-                    self.vertices[node1.idx().unwrap()],
-                    self.vertices[node2.idx().unwrap()],
-                ])
-            })
-            .collect()
+        // a full scratch buffer of a bounded triangulation may stop the insertion after the split of the containing
+        // triangle, the vertex is taken out again so the triangulation stays regular
+        if let Err(err) = &result {
+            if matches!(err.downcast_ref(), Some(RitaError::BufferFull { .. }))
+                && self.used_vertices.last() == Some(&v_idx)
+            {
+                self.take_out_failed_vertex(v_idx)?;
+            }
+        }
+        result
     }
 
-    /// Get the used vertices.
-    #[must_use]
-    pub const fn used_vertices(&self) -> &Vec<usize> {
-        &self.used_vertices
-    }
+    /// Take out a vertex, whose insertion failed after it started to change the triangulation, by rebuilding the
+    /// triangulation without it, see [`Self::remove_vertices`]. It is neither used nor removed afterwards.
+    fn take_out_failed_vertex(&mut self, v_idx: VertexIdx) -> HowResult<()> {
+        self.remove_vertices(&[v_idx])?;
+        // it is reported as failed, not as removed
+        self.removed_vertices.retain(|&idx| idx != v_idx);
 
-    /// Get the vertices.
-    #[must_use]
-    pub const fn vertices(&self) -> &Vec<[f64; 2]> {
-        &self.vertices
+        HowOk(())
     }
 
-    /// Get the weights.
-    #[must_use]
-    pub const fn weights(&self) -> &Option<Vec<f64>> {
-        &self.weights
-    }
+    fn insert_v_helper_inner(&mut self, v_idx: usize, near_to: usize) -> HowResult<()> {
+        self.generation += 1;
 
-    /// Locate the triangle that contains a point by using the visibility walk.
-    pub fn locate_vis_walk(&self, v_idx: usize, tri_idx_start: usize) -> HowResult<usize> {
-        let v = self.vertices()[v_idx];
+        // Perform locate and measure time
+        #[cfg(feature = "timing")]
+        let now = std::time::Instant::now();
+        let containing_tri_idx = match self.locate_beyond_hull(v_idx) {
+            Some(tri_idx) => tri_idx,
+            None => {
+                // the possibly invalid triangle
+                let (tri_idx, num_steps, is_bisector_case) =
+                    self.walk_to_point(self.vertices[v_idx], near_to)?;
+                self.walk_stats.record(num_steps, false);
+                if is_bisector_case {
+                    self.walk_stats.num_bisector_cases += 1;
+                }
+                #[cfg(feature = "metrics")]
+                self.stats.record_walk(num_steps);
+                tri_idx
+            }
+        };
+        let is_beyond_hull = self.tds().get_tri(containing_tri_idx)?.is_conceptual();
 
-        let mut tri_idx = tri_idx_start; // variable to store the current triangle index
+        #[cfg(feature = "timing")]
+        {
+            self.stats.time_walking += now.elapsed().as_micros();
+        }
 
-        // start with all hedges of the starting triangle
-        let mut v_hedges = self.tds().get_tri(tri_idx)?.hedges().to_vec();
+        // Skip vertices at the position of a vertex of the containing triangle, the first one inserted is kept
+        let v = self.vertices[v_idx];
+        let canonical = self
+            .tds()
+            .get_tri(containing_tri_idx)?
+            .nodes()
+            .iter()
+            .filter_map(VertexNode::idx)
+            .find(|&idx| self.is_duplicate(v, self.vertices[idx]));
+        if let Some(canonical) = canonical {
+            self.duplicate_policy
+                .check(v_idx, canonical, self.weights.as_deref())?;
+            self.duplicate_vertices.push(v_idx);
+            self.duplicate_of.insert(v_idx, canonical);
+            return HowOk(());
+        }
 
-        let mut side = true; // TODO or false?
+        // Skip vertices that are not in power circle by epsilon (i.e. above the hyperplane) inside the current convex hull,
+        // and vertices that are closer than epsilon to the lifted hull beyond it
+        if self.epsilon.is_some() {
+            let is_ignored = if self.tds().get_tri(containing_tri_idx)?.is_casual() {
+                !self.is_v_in_eps_powercircle(v_idx, containing_tri_idx)?
+            } else {
+                self.is_v_near_lifted_hull(v_idx, containing_tri_idx)?
+            };
+            if is_ignored {
+                self.ignored_vertices.push(v_idx);
+                return HowOk(());
+            }
+        }
 
-        loop {
-            // choose one of the two (three) hedges of the triangle
-            if let Some(hedge) = self.choose_hedge(&v_hedges, &v) {
-                let hedge_twin = hedge.twin();
-                tri_idx = hedge_twin.tri().idx; // the triangle in question is the one incident to the twin hedge
-                v_hedges.clear(); // delete the old hedges, to only look at hedges for the current tri
+        // Perform insert and measure time
+        // Note in the weighted case we can check directly if the vertex is in the power circle of the triangle, cause it might already be redundant
+        // if yes we can skip it, avoid flips and directly go to the next one
+        if self.weighted()
+            && !self.is_v_in_powercircle_with(v_idx, containing_tri_idx, self.predicate_mode)?
+        {
+            self.redundant_vertices.push(v_idx);
+            return HowOk(());
+        }
+        self.used_vertices.push(v_idx);
+        self.observer.on_insert(v_idx, containing_tri_idx);
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.record(
+                &self.tds,
+                TraceEvent::Insert {
+                    v_idx,
+                    containing_idx: containing_tri_idx,
+                },
+                &[],
+                &[],
+            );
+        }
 
-                assert_eq!(
-                    hedge_twin.prev().starting_node(),
-                    hedge_twin.next().end_node()
-                );
-                // if during traversal we travel outside the chull of the current trianuglation
-                // we now have two conceptula triangles in question
-                // they each have an casual edge on the c-hull, and are connected to the conceptual point
-                // they also share a common point
-                // we use the bisector to determine where the point lies in
-                // TODO: refactor this special case
-                if self.weighted()
-                    && hedge_twin.prev().twin().tri().is_conceptual()
-                    && hedge_twin.next().twin().tri().is_conceptual()
-                    && !hedge_twin.prev().starting_node().is_conceptual()
-                {
-                    // first we check for orientation with both edges to see if we are actually already in the tri
+        let new_tri_idx = match self.insertion_strategy {
+            InsertionStrategy::Flips => self.insert_flips(v_idx, containing_tri_idx)?,
+            InsertionStrategy::BowyerWatson => self.insert_bw(v_idx, containing_tri_idx)?,
+        };
 
-                    let o = self.vertices[hedge_twin.prev().starting_node().idx().unwrap()];
-                    let a = self.vertices[hedge_twin.prev().end_node().idx().unwrap()];
-                    let a_tri_idx = hedge_twin.prev().twin().tri().idx;
-                    let b = self.vertices[hedge_twin.next().starting_node().idx().unwrap()];
-                    let b_tri_idx = hedge_twin.next().twin().tri().idx;
+        if is_beyond_hull {
+            self.hull_triangle = self.tri_around(v_idx, new_tri_idx, true);
+        }
+        if self.use_locate_grid {
+            self.update_locate_grid(v, new_tri_idx)?;
+        }
+        HowOk(())
+    }
+
+    /// Record the triangle incident to the inserted vertex `v` in the grid, or build the grid again if it is too coarse.
+    fn update_locate_grid(&mut self, v: Vertex2, tri_idx: usize) -> HowResult<()> {
+        if !self.locate_grid.needs_rebuild(self.used_vertices.len()) {
+            self.locate_grid.record(&v, tri_idx);
+            return HowOk(());
+        }
+
+        let mut anchors = Vec::with_capacity(self.tds.num_tris());
+        for tri_idx in 0..self.tds.num_tris() + self.tds.num_deleted_tris {
+            let tri = self.tds().get_tri(tri_idx)?;
+            if tri.is_deleted() || tri.is_conceptual() {
+                continue;
+            }
+            let corners = tri
+                .nodes()
+                .map(|node| node.idx().map(|idx| self.vertices[idx]));
+            if let [Some(a), Some(b), Some(c)] = corners {
+                let centroid = core::array::from_fn(|i| (a[i] + b[i] + c[i]) / 3.0);
+                anchors.push((centroid, tri_idx));
+            }
+        }
+        self.locate_grid.rebuild(self.used_vertices.len(), &anchors);
+
+        HowOk(())
+    }
+
+    /// Insert a vertex into its containing triangle via a 1->3 flip, and flip the edges around it until they are regular.
+    ///
+    /// Returns a triangle incident to the vertex.
+    fn insert_flips(&mut self, v_idx: usize, containing_tri_idx: usize) -> HowResult<usize> {
+        // the stack is taken out of `self`, to be passed along to the flips
+        let mut hedges_to_verify = core::mem::take(&mut self.hedges_to_verify);
+        hedges_to_verify.clear();
+        let result = self.insert_flips_with(v_idx, containing_tri_idx, &mut hedges_to_verify);
+        self.hedges_to_verify = hedges_to_verify;
+
+        result
+    }
 
-                    // take the point in the middle of hedge and check if v is on the same side than this point
-                    let a_help = self.vertices[hedge.starting_node().idx().unwrap()];
-                    let b_help = self.vertices[hedge.end_node().idx().unwrap()];
-                    let p_help = [(a_help[0] + b_help[0]) / 2.0, (a_help[1] + b_help[1]) / 2.0];
+    /// Push a hedge to check for a flip, which fails instead of growing the stack of a bounded triangulation.
+    fn push_hedge(&self, hedges_to_verify: &mut Vec<usize>, hedge_idx: usize) -> HowResult<()> {
+        push_bounded(
+            hedges_to_verify,
+            hedge_idx,
+            self.max_vertices.is_some(),
+            "flip stack",
+        )
+    }
 
-                    let side_p_help_a = predicates::orient_2d(&o, &a, &p_help);
-                    let side_p_help_b = predicates::orient_2d(&o, &b, &p_help);
-                    let side_v_a = predicates::orient_2d(&o, &a, &v);
-                    let side_v_b = predicates::orient_2d(&o, &b, &v);
+    /// Insert a vertex via flips, see [`Self::insert_flips`], with the empty stack of hedges to check.
+    fn insert_flips_with(
+        &mut self,
+        v_idx: usize,
+        containing_tri_idx: usize,
+        hedges_to_verify: &mut Vec<usize>,
+    ) -> HowResult<usize> {
+        #[cfg(feature = "timing")]
+        let now = std::time::Instant::now();
 
-                    if side_p_help_a == side_v_a && side_p_help_b == side_v_b {
-                        return HowOk(hedge.twin().tri().idx);
-                    }
+        for hedge in self.tds().get_tri(containing_tri_idx)?.hedges() {
+            self.push_hedge(hedges_to_verify, hedge.twin().idx)?;
+        }
 
-                    let o_vec = nalgebra::Vector2::new(o[0], o[1]);
-                    let a_vec = nalgebra::Vector2::new(a[0], a[1]);
-                    let b_vec = nalgebra::Vector2::new(b[0], b[1]);
+        let [t0, t1, t2] = self.tds.flip_1_to_3(containing_tri_idx, v_idx)?;
+        let new_tri_idx = t0.idx;
+        #[cfg(feature = "metrics")]
+        {
+            self.stats.num_flips_1_3 += 1;
+            self.stats.record_changes(1, 3);
+        }
+        if let Some(changes) = &mut self.tracked_changes {
+            changes.destroy(containing_tri_idx);
+            for tri_idx in [t0.idx, t1.idx, t2.idx] {
+                changes.create(tri_idx);
+            }
+        }
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            let tri_idxs = [new_tri_idx, t1.idx, t2.idx];
+            trace.record(&self.tds, TraceEvent::Flip13 { tri_idxs }, &[], &tri_idxs);
+        }
+        self.last_inserted_triangle = Some(new_tri_idx);
+
+        #[cfg(feature = "timing")]
+        {
+            self.stats.time_inserting += now.elapsed().as_micros();
+        };
 
-                    let ao = (a_vec - o_vec).normalize();
-                    let bo = (b_vec - o_vec).normalize();
-                    let oc = (ao + bo).normalize();
+        // Perform flips and measure time
+        #[cfg(feature = "timing")]
+        let now = std::time::Instant::now();
+        while let Some(hedge_idx) = hedges_to_verify.pop() {
+            #[cfg(feature = "metrics")]
+            BuildStats::record(&mut self.stats.peak_hedge_stack, hedges_to_verify.len() + 1);
 
-                    let c_vec = o_vec + oc;
-                    let c = [c_vec[0], c_vec[1]];
+            if let Some(flip) = self.should_flip_hedge(hedge_idx)? {
+                match flip {
+                    Flip::TwoToTwo => {
+                        let hedge = self.tds().get_hedge(hedge_idx)?;
 
-                    if predicates::orient_2d(&o, &c, &v) == predicates::orient_2d(&o, &c, &a) {
-                        return HowOk(a_tri_idx);
-                    } else if predicates::orient_2d(&o, &c, &v) == predicates::orient_2d(&o, &c, &b)
-                    {
-                        return HowOk(b_tri_idx);
-                    } else {
-                        panic!("Vertex is not on either side of the bisector");
+                        // Push the hedges before perofming the flip, because the flip might shift indices
+                        //
+                        // We only need to push 2 new hedges on the stack, as follows
+                        // Denote the inserted vertex v, the hedge to test ab and the opposing point o, that shares ab with v
+                        // The flip makes vab and abo become vao and vbo respectively
+                        // Now the hedges to test are the ones not connected to v in any way, i.e. ao and bo
+                        let [prev_twin_idx, next_twin_idx] =
+                            [hedge.prev().twin().idx, hedge.next().twin().idx];
+                        self.push_hedge(hedges_to_verify, prev_twin_idx)?;
+                        self.push_hedge(hedges_to_verify, next_twin_idx)?;
+
+                        let tri_idxs = self.tds_mut().flip_2_to_2(hedge_idx)?.map(|tri| tri.idx);
+                        self.last_inserted_triangle = Some(tri_idxs[0]);
+                        self.observer.on_flip_2_2(tri_idxs);
+                        #[cfg(feature = "metrics")]
+                        {
+                            self.stats.num_flips_2_2 += 1;
+                            self.stats.record_changes(2, 2);
+                        }
+                        #[cfg(feature = "trace")]
+                        if let Some(trace) = &mut self.trace {
+                            trace.record(
+                                &self.tds,
+                                TraceEvent::Flip22 { tri_idxs },
+                                &[],
+                                &tri_idxs,
+                            );
+                        }
+                        if let Some(changes) = &mut self.tracked_changes {
+                            for tri_idx in tri_idxs {
+                                changes.destroy(tri_idx);
+                            }
+                            for tri_idx in tri_idxs {
+                                changes.create(tri_idx);
+                            }
+                        }
                     }
-                } else if side {
-                    v_hedges.push(hedge_twin.next());
-                    v_hedges.push(hedge_twin.prev());
+                    Flip::ThreeToOne((third_tri_idx, relfex_node_idx)) => {
+                        self.flip_out_node(
+                            hedge_idx,
+                            third_tri_idx,
+                            relfex_node_idx,
+                            hedges_to_verify,
+                        )?;
+                    }
+                    _ => {
+                        #[cfg(feature = "logging")]
+                        log::error!("Unexpected flip type!");
+                    }
+                }
+            } else if self.eps_in_flips && self.epsilon.is_some() && !self.weighted() {
+                // The edge is regular, but one of its vertices might be skipped by the epsilon filter now,
+                // i.e. its neighbors changed since its insertion, so that it is less than epsilon below their lifted triangle
+                if let Some((third_tri_idx, node_idx)) = self.eps_removable_node(hedge_idx)? {
+                    self.flip_out_node(hedge_idx, third_tri_idx, node_idx, hedges_to_verify)?;
+
+                    if let Some(pos) = self.used_vertices.iter().rposition(|&idx| idx == node_idx) {
+                        self.used_vertices.remove(pos);
+                    }
+                    self.ignored_vertices.push(node_idx);
+                }
+            }
+        }
+        #[cfg(feature = "timing")]
+        {
+            self.stats.time_flipping += now.elapsed().as_micros();
+        }
+
+        HowOk(new_tri_idx)
+    }
+
+    /// Insert a vertex via Bowyer Watson, i.e. replace the triangles in conflict with it by a star around it.
+    ///
+    /// Returns a triangle incident to the vertex.
+    fn insert_bw(&mut self, v_idx: usize, containing_tri_idx: usize) -> HowResult<usize> {
+        #[cfg(feature = "timing")]
+        let now = std::time::Instant::now();
+
+        self.tds.bw_start(containing_tri_idx)?;
+
+        loop {
+            while let Some(tri_idx) = self.tds.bw_tris_to_check() {
+                if self.is_v_in_conflict(v_idx, tri_idx)? {
+                    self.tds.bw_rem_tri(tri_idx)?;
                 } else {
-                    v_hedges.push(hedge_twin.prev());
-                    v_hedges.push(hedge_twin.next());
+                    self.tds.bw_keep_tri(tri_idx)?;
                 }
+            }
 
-                side = !side;
-            } else {
-                return HowOk(tri_idx);
+            if self.predicate_mode.is_exact() {
+                break;
             }
+
+            // inexact power circle tests may give a cavity, that is not star-shaped, i.e. some new triangles would be
+            // flat or inverted, so the triangles behind the edges the vertex does not see are removed as well,
+            // one at a time, such that its neighbors are checked before the boundary is checked again
+            let Some(tri_idx) = self.hidden_cavity_tris(v_idx).next() else {
+                break;
+            };
+            self.tds.bw_grow(tri_idx)?;
         }
-    }
 
-    const fn log_time(&self) {
-        #[cfg(feature = "log_timing")]
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tri_idx in self.tds.bw_removed_tris() {
+                changes.destroy(tri_idx);
+            }
+        }
+
+        self.tds.bw_insert_node(VertexNode::Casual(v_idx))?;
+        self.observer
+            .on_bw_cavity(v_idx, self.tds.bw_removed_tris(), self.tds.bw_added_tris());
+        #[cfg(feature = "metrics")]
         {
-            log::debug!("-------------------------------------------");
-            log::debug!("Time elapsed:");
-            log::debug!("Inserts computed in {} μs", self.time_inserting);
-            log::debug!("Walks computed in {} μs", self.time_walking);
-            log::debug!("Flips computed in {} μs", self.time_flipping);
+            self.stats.num_bw_cavities += 1;
+            self.stats.record_changes(
+                self.tds.bw_removed_tris().len(),
+                self.tds.bw_added_tris().len(),
+            );
+        }
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.record(
+                &self.tds,
+                TraceEvent::BwCavity { v_idx },
+                self.tds.bw_removed_tris(),
+                self.tds.bw_added_tris(),
+            );
+        }
+
+        if let Some(changes) = &mut self.tracked_changes {
+            for &tri_idx in self.tds.bw_added_tris() {
+                changes.create(tri_idx);
+            }
+        }
+        let new_tri_idx = self.tds.bw_added_tris()[0];
+        self.last_inserted_triangle = Some(new_tri_idx);
+
+        #[cfg(feature = "timing")]
+        {
+            self.stats.time_inserting += now.elapsed().as_micros();
         }
+
+        HowOk(new_tri_idx)
     }
 
-    fn is_flippable(
-        &self,
-        vertices_from_edge: [usize; 2],
-        vertices_from_incident_tris: [usize; 2],
+    /// Check if a vertex is in conflict with a triangle, i.e. the triangle is part of the cavity of its insertion.
+    ///
+    /// For a conceptual triangle, that is the case if the vertex is beyond its hull edge, or on the hull edge
+    /// within the power circle of the casual triangle on the other side.
+    fn is_v_in_conflict(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
+        let tri = self.tds().get_tri(tri_idx)?;
+        if tri.is_casual() {
+            return self.is_v_in_powercircle_with(v_idx, tri_idx, self.predicate_mode);
+        }
+
+        let hull_hedge = tri
+            .hedges()
+            .into_iter()
+            .find(|hedge| {
+                !hedge.starting_node().is_conceptual() && !hedge.end_node().is_conceptual()
+            })
+            .ok_or(RitaError::InvalidState(
+                "conceptual triangle without hull edge",
+            ))?;
+        let [a, b] = [hull_hedge.starting_node(), hull_hedge.end_node()]
+            .map(|node| self.vertices[node.idx().unwrap()]);
+
+        let orientation = predicates::orient_2d(&a, &b, &self.vertices[v_idx]);
+        if orientation == 0.0 {
+            // on the line of the hull edge, the power circle degenerates to the one of the casual triangle
+            return self.is_v_in_powercircle_with(
+                v_idx,
+                hull_hedge.twin().tri().idx,
+                self.predicate_mode,
+            );
+        }
+
+        HowOk(orientation > 0.0)
+    }
+
+    /// The kept triangles, whose casual edge on the boundary of the cavity the vertex does not see strictly from inside.
+    fn hidden_cavity_tris(&self, v_idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let p = self.vertices[v_idx];
+        self.tds
+            .bw_cavity_boundary()
+            .filter(
+                move |hedge| match (hedge.starting_node(), hedge.end_node()) {
+                    (VertexNode::Casual(v_idx0), VertexNode::Casual(v_idx1)) => {
+                        predicates::orient_2d(&self.vertices[v_idx0], &self.vertices[v_idx1], &p)
+                            <= 0.0
+                    }
+                    _ => false,
+                },
+            )
+            .map(|hedge| hedge.twin().tri().idx)
+    }
+
+    /// Remove the vertex `node_idx` of degree 3 via a 3->1 flip of its triangles, the ones at the hedge and `third_tri_idx`.
+    ///
+    /// Pushes the hedges of the new triangle on `hedges_to_verify`.
+    fn flip_out_node(
+        &mut self,
         hedge_idx: usize,
-    ) -> Option<Flip> {
-        // Simplified procedure described in Incremental Topological Flipping Works for Regular Triangulations (p. 46,47)
-        // Given an edge cd incident to two triangles acd and bcd
-        let mut num_reflex_points = 0;
-        let mut c_reflex = false;
-        let mut d_reflex = false;
+        third_tri_idx: usize,
+        node_idx: VertexIdx,
+        hedges_to_verify: &mut Vec<usize>,
+    ) -> HowResult<()> {
+        let hedge = self.tds().get_hedge(hedge_idx)?;
 
-        // helper vertices, i.e. that form a triangle each with the vertices in question
-        let a = vertices_from_incident_tris[0];
-        let b = vertices_from_incident_tris[1];
+        // get the two incident triangles to the hedge, the third tri idx is in the flip
+        let tri_idx_abd = hedge.tri().idx;
+        let tri_idx_bcd = hedge.twin().tri().idx;
 
-        // vertices in question
-        let c = vertices_from_edge[0];
-        let d = vertices_from_edge[1];
+        let t0 = self.tds.flip_3_to_1(
+            [tri_idx_abd, tri_idx_bcd, third_tri_idx],
+            node_idx,
+            &self.vertices,
+        )?;
+        self.last_inserted_triangle = Some(t0.idx);
+        self.observer
+            .on_flip_3_1([tri_idx_abd, tri_idx_bcd, third_tri_idx], node_idx);
+        #[cfg(feature = "metrics")]
+        {
+            self.stats.num_flips_3_1 += 1;
+            self.stats.record_changes(3, 1);
+        }
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.record(
+                &self.tds,
+                TraceEvent::Flip31 {
+                    tri_idxs: [tri_idx_abd, tri_idx_bcd, third_tri_idx],
+                    v_idx: node_idx,
+                },
+                &[tri_idx_abd, tri_idx_bcd, third_tri_idx],
+                &[tri_idx_abd],
+            );
+        }
+        if let Some(changes) = &mut self.tracked_changes {
+            for tri_idx in [tri_idx_abd, tri_idx_bcd, third_tri_idx] {
+                changes.destroy(tri_idx);
+            }
+            changes.create(tri_idx_abd);
+        }
 
-        // 1) check p = {c, d} to be reflex or convex this can be done as follows, p is the point to check and q the other point of the edge
-        //     - choose at random a "base point" of a, b, WLOG we choose a here (as in the paper)
-        //     - draw a line through p,a
-        //     - if q, b are on different side of the line, then p is reflex, else convex
-        // check if side for d,b for line ca, i.e. c reflex
-        let side_d = predicates::orient_2d(&self.vertices[c], &self.vertices[a], &self.vertices[d]);
-        let side_b = predicates::orient_2d(&self.vertices[c], &self.vertices[a], &self.vertices[b]);
-        if side_d != side_b {
-            num_reflex_points += 1;
-            c_reflex = true;
+        // push the new hedges on the stack, these are the three edges of the newly created triangle
+        // since in the flip 3 to 1, we overwrite the data structure, such that the new triangle now lives at tri_idx_abd
+        for hedge in self.tds().get_tri(tri_idx_abd)?.hedges() {
+            self.push_hedge(hedges_to_verify, hedge.twin().idx)?;
         }
 
-        // check side for c,b for line da, i.e. d reflex
-        // TODO only do this check if c is not reflex, i.e. since only one point can be reflex -> would remove 2 orientation tests in some cases
-        let side_c = predicates::orient_2d(&self.vertices[d], &self.vertices[a], &self.vertices[c]);
-        let side_b = predicates::orient_2d(&self.vertices[d], &self.vertices[a], &self.vertices[b]);
-        if side_c != side_b {
-            num_reflex_points += 1;
-            d_reflex = true;
+        HowOk(())
+    }
+
+    /// Find a vertex of the edge of `hedge_idx`, that is skipped by the epsilon filter.
+    ///
+    /// That is a vertex of degree 3, which is not in the epsilon power circle of the triangle of its three neighbors.
+    /// Returns the third triangle of its star, along with the vertex, s.t. it can be removed via a 3->1 flip.
+    fn eps_removable_node(&self, hedge_idx: usize) -> HowResult<Option<(usize, VertexIdx)>> {
+        let hedge = self.tds().get_hedge(hedge_idx)?;
+
+        // Skip hedges that have been deleted by 3->1 flips
+        if hedge.starting_node() == VertexNode::Deleted || hedge.end_node() == VertexNode::Deleted {
+            return HowOk(None);
         }
 
-        // Early out: iff there are no reflex points, the edge is flippable via 2->2
-        if num_reflex_points == 0 {
-            return Some(Flip::TwoToTwo);
-        } else if num_reflex_points > 1 {
-            panic!("There cannot be more than 1 reflex point.");
+        let nodes = [
+            hedge.prev().starting_node(),
+            hedge.starting_node(),
+            hedge.twin().prev().starting_node(),
+            hedge.twin().starting_node(),
+        ];
+        let [Some(a), Some(b), Some(c), Some(d)] = nodes.map(|node| node.idx()) else {
+            return HowOk(None);
+        };
+        if nodes.contains(&VertexNode::Deleted) {
+            return HowOk(None);
         }
 
-        // 2) For the (hopefully) only marked as reflex, check their degree, if for all the points marked the degree is 3, the the edge is flippable, let again p be the point to check
-        //    - for p to have degree 3, the triangle pab must be in the triangulation (we can PROBABLY check this with hede iterations)
-        let hedge = self.tds().get_hedge(hedge_idx).unwrap();
+        // a vertex of the edge with degree 3, is reflex in the quadrilateral of the two triangles at the edge
+        let Some(Flip::ThreeToOne((third_tri_idx, node_idx))) =
+            self.is_flippable([b, d], [a, c], hedge_idx)?
+        else {
+            return HowOk(None);
+        };
 
-        if c_reflex {
-            // this triangle should contain the vertex nodes abc
-            let possible_third_tri: TriIterator = if VertexNode::Casual(c) == hedge.starting_node()
-            {
-                hedge.prev().twin().tri()
-            } else {
-                // c is the end node of the hedge
-                hedge.next().twin().tri()
-            };
+        let other_idx = if node_idx == b { d } else { b };
+        let mut neighbors = [a, other_idx, c];
+        if predicates::orient_2d(
+            &self.vertices[a],
+            &self.vertices[other_idx],
+            &self.vertices[c],
+        ) < 0.0
+        {
+            neighbors.swap(0, 2);
+        }
 
-            if possible_third_tri.is_conceptual() {
-                return None;
-            }
+        HowOk(
+            (!self.is_v_in_eps_powercircle_of(node_idx, neighbors)?)
+                .then_some((third_tri_idx, node_idx)),
+        )
+    }
 
-            let mut idxs = [a, b, c];
-            let mut tri_idxs = possible_third_tri.nodes().map(|n| n.idx().unwrap());
-            idxs.sort_unstable();
-            tri_idxs.sort_unstable();
+    /// Fast path to locate a vertex beyond the hull, e.g. for inputs sorted along an axis.
+    ///
+    /// Checks whether the vertex sees the hull edge of the last hull triangle or one of its two neighbors along the hull,
+    /// if so, it is inserted there directly instead of walking through the triangulation.
+    fn locate_beyond_hull(&self, v_idx: VertexIdx) -> Option<usize> {
+        let tri = self.tds().get_tri(self.hull_triangle?).ok()?;
+        if !tri.is_conceptual() {
+            return None;
+        }
 
-            match idxs == tri_idxs {
-                // if the possible third tri is the tri abc it fills the reflex wedge and we can flip
-                true => Some(Flip::ThreeToOne((possible_third_tri.idx, c))),
-                false => None,
+        let v = self.vertices[v_idx];
+        let neighbors = tri
+            .hedges()
+            .into_iter()
+            .filter(HedgeIterator::is_conceptual)
+            .map(|hedge| hedge.twin().tri());
+
+        core::iter::once(tri).chain(neighbors).find_map(|tri| {
+            let hull_hedge = tri
+                .hedges()
+                .into_iter()
+                .find(|hedge| !hedge.is_conceptual())?;
+            let a = self.vertices[hull_hedge.starting_node().idx()?];
+            let b = self.vertices[hull_hedge.end_node().idx()?];
+
+            (predicates::orient_2d(&a, &b, &v) > 0.0).then_some(tri.idx)
+        })
+    }
+
+    /// Find a conceptual, or else casual, triangle incident to a vertex, by rotating around it starting at `tri_idx`.
+    fn tri_around(&self, v_idx: VertexIdx, tri_idx: usize, conceptual: bool) -> Option<usize> {
+        let start = self
+            .tds()
+            .get_tri(tri_idx)
+            .ok()?
+            .hedges()
+            .into_iter()
+            .find(|hedge| hedge.starting_node() == VertexNode::Casual(v_idx))?;
+
+        let mut hedge = start.clone();
+        loop {
+            if hedge.tri().is_conceptual() == conceptual {
+                return Some(hedge.tri().idx);
             }
-        } else if d_reflex {
-            // this triangle should contain the vertex nodes abc
-            let possible_third_tri: TriIterator = if VertexNode::Casual(d) == hedge.starting_node()
-            {
-                hedge.prev().twin().tri()
-            } else {
-                // d is the end node of the hedge
-                hedge.next().twin().tri()
-            };
 
-            if possible_third_tri.is_conceptual() {
+            hedge = hedge.prev().twin();
+            if hedge.idx == start.idx {
                 return None;
             }
+        }
+    }
+
+    /// Check if a triangle is flat, i.e. exists of three co-linear points.
+    pub fn is_tri_flat(&self, tri_idx: usize) -> HowResult<bool> {
+        let tri = self.get_tri_type(tri_idx)?;
+
+        let is_flat = match tri {
+            TriangleExtended::Triangle(tri_idxs) => {
+                predicates::orient_2d(&tri_idxs[0], &tri_idxs[1], &tri_idxs[2]) == 0.0
+            }
+            TriangleExtended::ConceptualTriangle(_) => false, // the conceptual triangle can't be flat
+        };
+
+        HowOk(is_flat)
+    }
+
+    /// Check for a vertex, if it lies inside the power circle of a triangle.
+    ///
+    /// Always uses exact predicates, also if the insertion does not, see [`Self::set_predicate_mode`].
+    pub fn is_v_in_powercircle(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
+        self.is_v_in_powercircle_with(v_idx, tri_idx, PredicateMode::Exact)
+    }
+
+    /// Check for a vertex, if it lies inside the power circle of a triangle, decided with the given predicates.
+    fn is_v_in_powercircle_with(
+        &self,
+        v_idx: usize,
+        tri_idx: usize,
+        predicate_mode: PredicateMode,
+    ) -> HowResult<bool> {
+        let p = self.vertices()[v_idx];
+        let h_p = self.height(v_idx);
+
+        let tri = self.get_tri_type(tri_idx)?;
+
+        let in_circle = match tri {
+            TriangleExtended::Triangle([a, b, c]) => {
+                let [h_a, h_b, h_c] = self
+                    .tds()
+                    .get_tri(tri_idx)?
+                    .nodes()
+                    .map(|n| self.height(n.idx().unwrap()));
+
+                let (sign, _decided) =
+                    predicate_mode.orient_2dlifted_decided(&a, &b, &c, &p, h_a, h_b, h_c, h_p);
+                #[cfg(feature = "metrics")]
+                self.filter_counts.record(_decided);
+                sign
+            }
+            // if the triangle is a line segment, then the power circle is a circle with infinite radius and we can use an orientation test
+            TriangleExtended::ConceptualTriangle(tri_idxs) => {
+                predicates::orient_2d(&tri_idxs[0], &tri_idxs[1], &p)
+            }
+        };
+
+        HowOk(in_circle > 0.0)
+    }
+
+    /// Returns an error if `self.epsilon` is not set.
+    /// When `wasm` feature is on, returns an error (epsilon power circle requires weighted predicates).
+    pub(crate) fn is_v_in_eps_powercircle(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
+        match self.get_tri_type(tri_idx)? {
+            TriangleExtended::Triangle(_) => {
+                let tri_idxs = self
+                    .tds()
+                    .get_tri(tri_idx)?
+                    .nodes()
+                    .map(|n| n.idx().unwrap());
+
+                self.is_v_in_eps_powercircle_of(v_idx, tri_idxs)
+            }
+            TriangleExtended::ConceptualTriangle(_) => {
+                Err(anyhow::Error::new(RitaError::ConceptualSimplex))
+            }
+        }
+    }
+
+    /// Check if a vertex is in the epsilon power circle of the counter-clockwise triangle of the vertices `tri_idxs`,
+    /// which does not need to be part of the triangulation.
+    ///
+    /// Returns an error if `self.epsilon` is not set.
+    /// When `wasm` feature is on, returns an error (epsilon power circle requires weighted predicates).
+    fn is_v_in_eps_powercircle_of(
+        &self,
+        v_idx: usize,
+        tri_idxs: [VertexIdx; 3],
+    ) -> HowResult<bool> {
+        #[cfg(feature = "wasm")]
+        let _ = (v_idx, tri_idxs);
+        #[cfg(feature = "wasm")]
+        return Err(anyhow::Error::new(RitaError::WeightsNotSupported));
+
+        #[cfg(not(feature = "wasm"))]
+        {
+            let p = self.vertices()[v_idx];
+
+            let epsilon = self.epsilon.ok_or(RitaError::EpsilonNotSet { v_idx })?;
+            let h_p = self.height(v_idx) + epsilon;
+
+            let [a, b, c] = tri_idxs.map(|idx| self.vertices[idx]);
+            let [h_a, h_b, h_c] = tri_idxs.map(|idx| self.height(idx));
+
+            let in_eps_circle = predicates::orient_2dlifted_SOS(&a, &b, &c, &p, h_a, h_b, h_c, h_p);
+
+            HowOk(in_eps_circle > 0.0)
+        }
+    }
+
+    /// Check if a vertex beyond the hull is closer than `self.epsilon` to the lifted triangulation, the counterpart of
+    /// [`Self::is_v_in_eps_powercircle`] for the conceptual triangle `tri_idx`.
+    ///
+    /// The lifted hull continues upwards from the lifted hull edge of the triangle, towards the point at infinity.
+    /// So the distance is measured from the lifted vertex to the point of the lifted edge next to it, only horizontally if
+    /// the vertex is above that point. Like inside the hull, this skips vertices close to a hull vertex, but not the ones
+    /// close to the middle of a long edge, as the lifted edge is far above them.
+    /// Returns an error if `self.epsilon` is not set.
+    fn is_v_near_lifted_hull(&self, v_idx: usize, tri_idx: usize) -> HowResult<bool> {
+        let epsilon = self.epsilon.ok_or(RitaError::EpsilonNotSet { v_idx })?;
+
+        let hull_hedge = self
+            .tds()
+            .get_tri(tri_idx)?
+            .hedges()
+            .into_iter()
+            .find(|hedge| !hedge.is_conceptual())
+            .ok_or(RitaError::InvalidState(
+                "Conceptual triangle without hull edge",
+            ))?;
+        let edge = [hull_hedge.starting_node(), hull_hedge.end_node()]
+            .map(|node| node.idx().ok_or(RitaError::ConceptualSimplex));
+        let [a_idx, b_idx] = [edge[0]?, edge[1]?];
+
+        let p = self.vertices[v_idx];
+        let lambdas = closest_on_segment([self.vertices[a_idx], self.vertices[b_idx]], p);
+        let q: [f64; 2] = core::array::from_fn(|i| {
+            lambdas[0] * self.vertices[a_idx][i] + lambdas[1] * self.vertices[b_idx][i]
+        });
+        let h_q = lambdas[0] * self.height(a_idx) + lambdas[1] * self.height(b_idx);
+
+        let below = (h_q - self.height(v_idx)).max(0.0);
+        let dist_sq = (p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2) + below * below;
+
+        HowOk(dist_sq < epsilon * epsilon)
+    }
+
+    /// Check if the triangulation is regular w.r.t. the empty power-sphere property.
+    ///
+    /// Returns if the validation is valid and to what degree.
+    pub fn is_regular(&self) -> HowResult<(bool, f64)> {
+        let mut regular = true;
+        let mut num_violated_triangles = 0;
+
+        for tri_idx in 0..self.tds().num_tris() + self.tds().num_deleted_tris {
+            // Skip triangles that have been deleted by 3->1 flips
+            if self
+                .tds()
+                .get_tri(tri_idx)?
+                .nodes()
+                .contains(&VertexNode::Deleted)
+            {
+                continue;
+            }
+
+            if self.is_tri_flat(tri_idx)? {
+                #[cfg(feature = "logging")]
+                error!("Flat triangle: {}", self.describe_tri(tri_idx)?);
+                regular = false;
+                num_violated_triangles += 1;
+            }
+
+            // Check the redundant vertices, for this any computed triangulation should always be regular
+            for &v_idx in &self.redundant_vertices {
+                // skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
+                if self
+                    .tds()
+                    .get_tri(tri_idx)?
+                    .nodes()
+                    .contains(&VertexNode::Casual(v_idx))
+                {
+                    continue;
+                }
+
+                if self.is_v_in_powercircle(v_idx, tri_idx)? {
+                    // #[cfg(feature = "logging")]
+                    // log::error!("Vertex in power circle: {}", self.tds().get_tri(tri_idx)?);
+                    regular = false;
+                    num_violated_triangles += 1; // s. the break below
+                    break;
+                }
+            }
+
+            // Check the used vertices, for this any computed triangulation should always be regular
+            for &v_idx in &self.used_vertices {
+                // skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
+                if self
+                    .tds()
+                    .get_tri(tri_idx)?
+                    .nodes()
+                    .contains(&VertexNode::Casual(v_idx))
+                {
+                    continue;
+                }
+
+                if self.is_v_in_powercircle(v_idx, tri_idx)? {
+                    // #[cfg(feature = "logging")]
+                    // log::error!("Vertex in power circle: {}", self.tds().get_tri(tri_idx)?);
+                    regular = false;
+                    num_violated_triangles += 1; // s. the break below
+                    break;
+                }
+            }
+        }
+
+        HowOk((
+            regular,
+            1.0 - num_violated_triangles as f64 / self.tds().num_tris() as f64,
+        ))
+    }
+
+    /// Checks regularity in a parallel manner using `rayon`s `par_iter()`, on a single thread without the `parallel`
+    /// feature.
+    ///
+    /// This can significantly reduce the runtime of this predicate.
+    /// Returns the share of triangles without violation, see [`RegularityCheck::Full`].
+    #[must_use]
+    pub fn par_is_regular(&self, with_ignored_vertices: bool) -> f64 {
+        self.check_regularity(RegularityCheck::Full, with_ignored_vertices)
+            .degree()
+    }
+
+    /// Former name of [`Self::par_is_regular`].
+    #[deprecated(since = "0.3.0", note = "use `par_is_regular` instead")]
+    #[must_use]
+    pub fn is_regular_p(&self, with_ignored_vertices: bool) -> f64 {
+        self.par_is_regular(with_ignored_vertices)
+    }
+
+    /// Check in parallel, how far the triangulation is regular w.r.t. the empty power circle property.
+    ///
+    /// The used and redundant vertices are checked, and the ignored vertices as well if `with_ignored_vertices`.
+    /// A triangle or vertex, whose check fails, e.g. for a broken data structure, counts as a violation.
+    #[must_use]
+    pub fn check_regularity(
+        &self,
+        check: RegularityCheck,
+        with_ignored_vertices: bool,
+    ) -> RegularityReport {
+        let mut tri_idxs: Vec<usize> = (0..self.tds().num_tris() + self.tds().num_deleted_tris)
+            // Skip triangles that have been deleted by 3->1 flips
+            .filter(|&tri_idx| {
+                self.tds()
+                    .get_tri(tri_idx)
+                    .is_ok_and(|tri| !tri.nodes().contains(&VertexNode::Deleted))
+            })
+            .collect();
+        if let RegularityCheck::Sampled { num_samples, seed } = check {
+            shuffle(&mut tri_idxs, seed);
+            tri_idxs.truncate(num_samples);
+        }
+        let num_checked = tri_idxs.len();
+
+        self.thread_pool.install(self.max_threads, || match check {
+            RegularityCheck::Full | RegularityCheck::Sampled { .. } => RegularityReport {
+                num_checked,
+                num_violated: tri_idxs
+                    .into_par_iter()
+                    .filter(|&tri_idx| {
+                        self.is_tri_violated(tri_idx, with_ignored_vertices)
+                            .unwrap_or(true)
+                    })
+                    .count(),
+            },
+            RegularityCheck::EarlyExit => RegularityReport {
+                num_checked,
+                num_violated: usize::from(tri_idxs.into_par_iter().any(|tri_idx| {
+                    self.is_tri_violated(tri_idx, with_ignored_vertices)
+                        .unwrap_or(true)
+                })),
+            },
+            RegularityCheck::Local => {
+                let mut v_idxs = self.redundant_vertices.clone();
+                if with_ignored_vertices {
+                    v_idxs.extend_from_slice(&self.ignored_vertices);
+                }
+
+                RegularityReport {
+                    num_checked: num_checked + v_idxs.len(),
+                    num_violated: tri_idxs
+                        .into_par_iter()
+                        .filter(|&tri_idx| self.is_tri_locally_violated(tri_idx).unwrap_or(true))
+                        .count()
+                        + v_idxs
+                            .into_par_iter()
+                            .filter(|&v_idx| {
+                                self.is_v_in_containing_powercircle(v_idx).unwrap_or(true)
+                            })
+                            .count(),
+                }
+            }
+        })
+    }
+
+    /// Whether a triangle is flat, or one of the checked vertices lies in its power circle.
+    fn is_tri_violated(&self, tri_idx: usize, with_ignored_vertices: bool) -> HowResult<bool> {
+        if self.is_tri_flat(tri_idx)? {
+            return HowOk(true);
+        }
+
+        let nodes = self.tds().get_tri(tri_idx)?.nodes();
+        let ignored_vertices = if with_ignored_vertices {
+            self.ignored_vertices.as_slice()
+        } else {
+            &[]
+        };
+        for &v_idx in self
+            .used_vertices
+            .iter()
+            .chain(&self.redundant_vertices)
+            .chain(ignored_vertices)
+        {
+            // Skip vertices that are part of the current triangle
+            if !nodes.contains(&VertexNode::Casual(v_idx))
+                && self.is_v_in_powercircle(v_idx, tri_idx)?
+            {
+                return HowOk(true);
+            }
+        }
+
+        HowOk(false)
+    }
+
+    /// Whether a triangle is flat, or the vertex opposite to one of its edges in the neighbor lies in its power circle.
+    fn is_tri_locally_violated(&self, tri_idx: usize) -> HowResult<bool> {
+        if self.is_tri_flat(tri_idx)? {
+            return HowOk(true);
+        }
+
+        for hedge in self.tds().get_tri(tri_idx)?.hedges() {
+            // the neighbor beyond a hull edge has no opposite vertex
+            let Some(v_idx) = hedge.twin().next().end_node().idx() else {
+                continue;
+            };
+            if self.is_v_in_powercircle(v_idx, tri_idx)? {
+                return HowOk(true);
+            }
+        }
+
+        HowOk(false)
+    }
+
+    /// Whether a vertex lies in the power circle of the triangle containing it, i.e. below the lifted triangulation.
+    fn is_v_in_containing_powercircle(&self, v_idx: usize) -> HowResult<bool> {
+        let v = self.vertices[v_idx];
+        let tri_idx = self.locate_point_vis_walk(v, self.walk_start(v))?;
+
+        self.is_v_in_powercircle(v_idx, tri_idx)
+    }
+
+    pub fn is_regular_for_point_set(
+        &self,
+        vertices: &[[f64; 2]],
+        weights: Option<Vec<f64>>,
+    ) -> HowResult<(bool, f64)> {
+        let mut regular = true;
+        let mut num_violated_triangles = 0;
+
+        let weights = if let Some(weights) = weights {
+            weights
+        } else {
+            vec![0.0; vertices.len()]
+        };
+
+        for tri_idx in 0..self.tds().num_tris() + self.tds().num_deleted_tris {
+            // Skip triangles that have been deleted by 3->1 flips
+            if self
+                .tds()
+                .get_tri(tri_idx)?
+                .nodes()
+                .contains(&VertexNode::Deleted)
+            {
+                continue;
+            }
+
+            if self.is_tri_flat(tri_idx)? {
+                #[cfg(feature = "logging")]
+                error!("Flat triangle: {}", self.describe_tri(tri_idx)?);
+                regular = false;
+                num_violated_triangles += 1;
+            }
+
+            if self.is_point_in_powercircle(tri_idx, vertices, &weights)? {
+                regular = false;
+                num_violated_triangles += 1; // each triangle can be violated once
+            }
+        }
+
+        HowOk((
+            regular,
+            1.0 - num_violated_triangles as f64 / self.tds().num_tris() as f64,
+        ))
+    }
+
+    /// Checks regularity exactly, with rational coordinates and without SOS, see [`ExactVertices`].
+    ///
+    /// Cocircular vertices do not count as a violation, so on degenerate inputs e.g. grids, this validates that the
+    /// triangulation of the fast path is one of the regular ones. Checks each triangle against all used vertices,
+    /// so this is only feasible for small inputs.
+    ///
+    /// ## Errors
+    /// Returns an error if a vertex or weight is not finite.
+    #[cfg(feature = "exact")]
+    pub fn is_regular_exact(&self) -> HowResult<bool> {
+        let exact = ExactVertices::new(&self.vertices, self.weights.as_deref())?;
+        let tris: Vec<Vec<VertexIdx>> = self.tri_indices().into_iter().map(Vec::from).collect();
+
+        HowOk(
+            tris.iter()
+                .all(|tri| exact.orient(tri) == core::cmp::Ordering::Greater)
+                && exact.is_regular(&tris, &self.used_vertices),
+        )
+    }
+
+    /// Checks regularity against a point set in parallel, like [`Self::par_is_regular`] does for the own vertices.
+    ///
+    /// Returns the share of triangles that are not flat, and have none of the points in their power circle.
+    /// A triangle, whose check fails, e.g. for a broken data structure, counts as violated.
+    ///
+    /// ## Errors
+    /// Returns [`RitaError::WeightsMismatch`], if the number of weights does not match the number of vertices.
+    pub fn par_is_regular_for_point_set(
+        &self,
+        vertices: &[[f64; 2]],
+        weights: Option<Vec<f64>>,
+    ) -> HowResult<f64> {
+        let weights = weights.unwrap_or_else(|| vec![0.0; vertices.len()]);
+        if weights.len() != vertices.len() {
+            return Err(anyhow::Error::new(RitaError::WeightsMismatch {
+                num_weights: weights.len(),
+                num_vertices: vertices.len(),
+            }));
+        }
+
+        let num_violated_tris = self.thread_pool.install(self.max_threads, || {
+            (0..self.tds().num_tris() + self.tds().num_deleted_tris)
+                .into_par_iter()
+                .filter(|&tri_idx| {
+                    let violated = || -> HowResult<bool> {
+                        let tri = self.tds().get_tri(tri_idx)?;
+                        // Skip triangles that have been deleted by 3->1 flips
+                        if tri.nodes().contains(&VertexNode::Deleted) {
+                            return HowOk(false);
+                        }
+
+                        HowOk(
+                            self.is_tri_flat(tri_idx)?
+                                || self.is_point_in_powercircle(tri_idx, vertices, &weights)?,
+                        )
+                    };
+
+                    violated().unwrap_or(true)
+                })
+                .count()
+        });
+
+        HowOk(1.0 - num_violated_tris as f64 / self.tds().num_tris() as f64)
+    }
+
+    /// Whether one of the weighted points lies in the power circle of a triangle.
+    fn is_point_in_powercircle(
+        &self,
+        tri_idx: usize,
+        vertices: &[[f64; 2]],
+        weights: &[f64],
+    ) -> HowResult<bool> {
+        // TODO: skip vertices, that are part of the current triangle. Geogram predicates avoid return 0.0 (in favor of SOS) so a vertex exactly on the circle, might be considered inside
+        let mut points = vertices.iter().zip(weights);
+
+        HowOk(match self.get_tri_type(tri_idx)? {
+            TriangleExtended::Triangle([a, b, c]) => {
+                let [h_a, h_b, h_c] = self
+                    .tds()
+                    .get_tri(tri_idx)?
+                    .nodes()
+                    .map(|n| self.height(n.idx().unwrap()));
+
+                points.any(|(v, weight)| {
+                    let [x, y] = to_local(v, &self.origin);
+                    let h_v = x.powi(2) + y.powi(2) - weight;
+                    predicates::orient_2dlifted_SOS(&a, &b, &c, v, h_a, h_b, h_c, h_v) > 0.0
+                })
+            }
+            // if the triangle is a line segment, then the power circle is a circle with infinite radius and we can use an orientation test
+            TriangleExtended::ConceptualTriangle([a, b]) => {
+                points.any(|(v, _)| predicates::orient_2d(&a, &b, v) > 0.0)
+            }
+        })
+    }
+
+    pub fn is_sound(&self) -> HowResult<bool> {
+        if self.tds().is_sound() {
+            HowOk(true)
+        } else {
+            #[cfg(feature = "logging")]
+            error!("Triangulation is not sound!");
+            HowOk(false)
+        }
+    }
+
+    /// Check the data structure for broken links, listing each of them instead of a single `bool` like [`Self::is_sound`].
+    pub fn soundness_report(&self) -> SoundnessReport {
+        self.tds().soundness_report()
+    }
+
+    /// Try to repair recoverable inconsistencies of the data structure, e.g. stale links after an interrupted insertion.
+    ///
+    /// Returns the issues that could not be repaired, i.e. the structure is consistent if the report is sound.
+    pub fn try_repair(&mut self) -> SoundnessReport {
+        self.generation += 1;
+        self.tds.try_repair()
+    }
+
+    pub fn num_ignored_vertices(&self) -> usize {
+        self.ignored_vertices.len()
+    }
+
+    /// The number of all `tris` in the triangulation, `casual` and `conceptual`.
+    pub const fn num_tris(&self) -> usize {
+        self.tds().num_tris()
+    }
+
+    /// The number of `casual` `tris`, i.e. without the ones that have an connection to the dummy point.
+    #[must_use]
+    pub const fn num_casual_tris(&self) -> usize {
+        self.tds().num_casual_tris()
+    }
+
+    /// The number of `conceptual` `tris`, i.e. the ones that have a connection to the dummy point.
+    #[must_use]
+    pub const fn num_conceptual_tris(&self) -> usize {
+        self.tds().num_conceptual_tris()
+    }
+
+    /// The number of edges on the convex hull, each of them is part of exactly one conceptual tri.
+    #[must_use]
+    pub const fn num_hull_facets(&self) -> usize {
+        self.num_conceptual_tris()
+    }
+
+    /// The number of total tris, i.e. `casual`, `conceptual` and `deleted` tris.
+    #[must_use]
+    pub const fn num_all_tris(&self) -> usize {
+        self.tds().num_tris() + self.tds().num_deleted_tris
+    }
+
+    /// The number of vertices that have been removed, e.g. by [`decimate`](Self::decimate).
+    pub fn num_removed_vertices(&self) -> usize {
+        self.removed_vertices.len()
+    }
+
+    pub fn num_redundant_vertices(&self) -> usize {
+        self.redundant_vertices.len()
+    }
+
+    pub fn num_used_vertices(&self) -> usize {
+        self.used_vertices.len()
+    }
+
+    pub(crate) fn should_flip_hedge(&mut self, hedge_idx: usize) -> HowResult<Option<Flip>> {
+        let hedge = self.tds().get_hedge(hedge_idx)?;
+
+        // Skip hedges that have been deleted by 3->1 flips
+        if hedge.starting_node() == VertexNode::Deleted || hedge.end_node() == VertexNode::Deleted {
+            return HowOk(None);
+        }
+
+        let tri_idx_abd = hedge.tri().idx;
+        let node_a = hedge.prev().starting_node();
+        let node_b = hedge.starting_node();
+
+        let tri_idx_bcd = hedge.twin().tri().idx;
+        let node_c = hedge.twin().prev().starting_node();
+        let node_d = hedge.twin().starting_node();
+
+        // Skip edges that have been deleted by 3->1 flips
+        if node_a == VertexNode::Deleted
+            || node_b == VertexNode::Deleted
+            || node_c == VertexNode::Deleted
+            || node_d == VertexNode::Deleted
+        {
+            return HowOk(None);
+        }
+
+        match (node_a, node_b, node_c, node_d) {
+            (
+                VertexNode::Casual(idx_node_a),
+                VertexNode::Casual(idx_node_b), // from the hedge
+                VertexNode::Casual(idx_node_c),
+                VertexNode::Casual(idx_node_d), // from the hedge
+            ) => {
+                let mut flip = Some(Flip::TwoToTwo);
+
+                if self.weighted() {
+                    // this can make flipe a 3->1, None or stay a 2->2
+                    flip = self.is_flippable(
+                        [idx_node_b, idx_node_d],
+                        [idx_node_a, idx_node_c],
+                        hedge_idx,
+                    )?;
+
+                    if flip.is_none() {
+                        return HowOk(None); // edge is not flippable (i.e. a 3 to 1 flip, that cant be made due to internal structure of the triangulation)
+                    }
+                }
+
+                // TODO we should be able to do only one check, if we keep track of the currently inserted vertex here, then the check is clear
+                if self.is_v_in_powercircle_with(idx_node_c, tri_idx_abd, self.predicate_mode)?
+                    || self.is_v_in_powercircle_with(
+                        idx_node_a,
+                        tri_idx_bcd,
+                        self.predicate_mode,
+                    )?
+                {
+                    HowOk(flip) // flip necessary, not regular
+                } else {
+                    HowOk(None) // no flip necessary, already regular
+                }
+            }
+            (
+                VertexNode::Conceptual,
+                VertexNode::Casual(_),
+                VertexNode::Casual(_),
+                VertexNode::Casual(_),
+            ) => {
+                // a vertex inserted on the hull edge leaves a flat triangle, flip it to the outside
+                if self.is_tri_flat(tri_idx_bcd)? {
+                    HowOk(Some(Flip::TwoToTwo))
+                } else {
+                    HowOk(None)
+                }
+            }
+            (
+                VertexNode::Casual(idx_node_a),
+                VertexNode::Conceptual,
+                VertexNode::Casual(idx_node_c),
+                VertexNode::Casual(idx_node_d),
+            ) => {
+                if is_convex(
+                    self.vertices()[idx_node_c],
+                    self.vertices()[idx_node_d],
+                    self.vertices()[idx_node_a],
+                ) {
+                    HowOk(Some(Flip::TwoToTwo))
+                } else {
+                    HowOk(None)
+                }
+            }
+            (
+                VertexNode::Casual(idx_node_a),
+                VertexNode::Casual(_),
+                VertexNode::Conceptual,
+                VertexNode::Casual(_),
+            ) => {
+                if self.is_v_in_powercircle_with(idx_node_a, tri_idx_bcd, self.predicate_mode)?
+                    || self.is_tri_flat(tri_idx_abd)?
+                {
+                    HowOk(Some(Flip::TwoToTwo))
+                } else {
+                    HowOk(None)
+                }
+            }
+            (
+                VertexNode::Casual(idx_node_a),
+                VertexNode::Casual(idx_node_b),
+                VertexNode::Casual(idx_node_c),
+                VertexNode::Conceptual,
+            ) => {
+                if is_convex(
+                    self.vertices()[idx_node_a],
+                    self.vertices()[idx_node_b],
+                    self.vertices()[idx_node_c],
+                ) {
+                    HowOk(Some(Flip::TwoToTwo))
+                } else {
+                    HowOk(None)
+                }
+            }
+            (_, _, _, _) => Err(anyhow::Error::new(RitaError::InvalidState(
+                "unexpected node configuration to decide flip for",
+            ))),
+        }
+    }
+
+    /// Get the triangulation data structure, as reference.
+    #[must_use]
+    pub const fn tds(&self) -> &TriDataStructure {
+        &self.tds
+    }
+
+    /// Get the triangulation data structure, as mutable reference.
+    #[must_use]
+    pub const fn tds_mut(&mut self) -> &mut TriDataStructure {
+        &mut self.tds
+    }
+
+    /// Encode the triangulation as a compact binary snapshot, e.g. to checkpoint a long build.
+    ///
+    /// The snapshot can only be loaded by a build of rita with the same version and features, see [`load_snapshot`](Self::load_snapshot).
+    ///
+    /// ## Errors
+    /// Returns an error if the encoding fails.
+    #[cfg(feature = "snapshot")]
+    pub fn save_snapshot(&self) -> HowResult<Vec<u8>> {
+        snapshot::save(SnapshotKind::Triangulation, self)
+    }
+
+    /// Decode a triangulation from a snapshot of [`save_snapshot`](Self::save_snapshot).
+    ///
+    /// The decoded structure is not validated, a snapshot of a sound structure stays sound.
+    ///
+    /// ## Errors
+    /// Returns an error if the bytes are not a snapshot of a triangulation, or it can not be decoded.
+    #[cfg(feature = "snapshot")]
+    pub fn load_snapshot(bytes: &[u8]) -> HowResult<Self> {
+        snapshot::load(SnapshotKind::Triangulation, bytes)
+    }
+
+    /// Get the triangles of the triangulation as `Triangle2`, i.e `[[f64; 2]; 3]`.
+    ///
+    /// Does not include conceptual triangles, i.e. the convex hull edges
+    /// connected to the point at infinity.
+    pub fn tris(&self) -> Vec<Triangle2> {
+        // todo: handle the results gracefully, instead of unwrapping (which is safe here though)
+        (0..self.tds().num_tris() + self.tds().num_deleted_tris)
+            .filter_map(|tri_idx| {
+                let tri = self.tds().get_tri(tri_idx).ok()?;
+
+                if tri.is_conceptual() || tri.is_deleted() {
+                    return None;
+                }
+
+                let [node0, node1, node2] = tri.nodes();
+
+                Some([
+                    self.vertices[node0.idx().unwrap()],
+                    self.vertices[node1.idx().unwrap()],
+                    self.vertices[node2.idx().unwrap()],
+                ])
+            })
+            .collect()
+    }
+
+    /// Write the casual triangles as an OBJ mesh, with the z coordinate of the vertices given by `z`, see [`io`](crate::io).
+    ///
+    /// Only the vertices that are part of the triangulation are written.
+    ///
+    /// ## Errors
+    /// Returns an error if the writer fails.
+    pub fn write_obj(&self, writer: &mut impl core::fmt::Write, z: ObjZ) -> HowResult<()> {
+        let position = |v_idx: VertexIdx| {
+            let [x, y] = self.vertices[v_idx];
+            let z = match z {
+                ObjZ::Zero => 0.0,
+                ObjZ::Height => self.height(v_idx),
+                ObjZ::Weight => self.weights.as_ref().map_or(0.0, |weights| weights[v_idx]),
+            };
+            [x, y, z]
+        };
+
+        io::write_obj(writer, self.vertices.len(), position, &self.tri_indices())
+    }
+
+    /// Get all vertices at `z = 0` with their weights, and the casual triangles, to be written as a PLY file.
+    ///
+    /// See [`io::write_ply`], the vertices keep their indices, so they can be inserted again with their weights.
+    pub fn to_ply(&self) -> PlyData {
+        PlyData {
+            vertices: self.vertices.iter().map(|&[x, y]| [x, y, 0.0]).collect(),
+            weights: self.weights.clone(),
+            faces: self.tri_indices(),
+        }
+    }
+
+    /// Get all vertices with their weights, and the casual triangles, to be written as the `.node` and `.ele` files of Triangle.
+    ///
+    /// See [`io::write_node_ele`], the vertices keep their indices.
+    pub fn to_node_ele(&self) -> NodeEleData<2, 3> {
+        NodeEleData {
+            vertices: self.vertices.clone(),
+            weights: self.weights.clone(),
+            elements: self.tri_indices(),
+        }
+    }
+
+    /// Get all vertices with their weights, and the convex hull as segments, to be written as a `.poly` file of Triangle.
+    ///
+    /// See [`io::write_poly`], Triangle then triangulates the same domain.
+    pub fn to_poly(&self) -> PolyData {
+        let hull = self.hull_edges();
+        PolyData {
+            vertices: self.vertices.clone(),
+            weights: self.weights.clone(),
+            segments: (0..hull.len())
+                .map(|i| [hull[i], hull[(i + 1) % hull.len()]])
+                .collect(),
+            holes: Vec::new(),
+        }
+    }
+
+    /// Render the triangulation as an SVG figure, see [`SvgOptions`] for what is drawn.
+    ///
+    /// ```
+    /// use rita::{Triangulation, io::SvgOptions};
+    ///
+    /// let mut triangulation = Triangulation::new(None);
+    /// triangulation.insert_vertices([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]], None, false).unwrap();
+    ///
+    /// let options = SvgOptions { voronoi: true, ..SvgOptions::default() };
+    /// let svg = triangulation.to_svg(&options);
+    /// assert!(svg.starts_with("<svg") && svg.contains("class=\"voronoi\""));
+    /// ```
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        io::write_svg(self, options)
+    }
+
+    /// Stream the casual triangles and their vertices into a [`MeshSink`].
+    ///
+    /// Vertices are added lazily right before the first triangle referencing them,
+    /// so only vertices that are part of the triangulation end up in the sink.
+    pub fn emit_into(&self, sink: &mut impl MeshSink) {
+        const NOT_EMITTED: usize = usize::MAX;
+        let mut emitted_idxs = vec![NOT_EMITTED; self.vertices.len()];
+        let mut num_emitted = 0;
+
+        sink.reserve(self.used_vertices.len(), self.tds().num_tris());
+
+        for tri_idx in 0..self.tds().num_tris() + self.tds().num_deleted_tris {
+            let tri = TriIterator::new(self.tds(), tri_idx);
+
+            if tri.is_conceptual() || tri.is_deleted() {
+                continue;
+            }
+
+            let tri = tri.nodes().map(|node| {
+                let v_idx = node.idx().unwrap();
+
+                if emitted_idxs[v_idx] == NOT_EMITTED {
+                    sink.add_vertex(self.vertices[v_idx]);
+                    emitted_idxs[v_idx] = num_emitted;
+                    num_emitted += 1;
+                }
+
+                emitted_idxs[v_idx]
+            });
+
+            sink.add_triangle(tri);
+        }
+    }
+
+    /// Get the triangles of the triangulation as vertex indices.
+    ///
+    /// Same order as [`tris`](Self::tris), i.e. conceptual and deleted triangles are skipped.
+    pub fn tri_indices(&self) -> Vec<[VertexIdx; 3]> {
+        (0..self.tds().num_tris() + self.tds().num_deleted_tris)
+            .filter_map(|tri_idx| {
+                let tri = self.tds().get_tri(tri_idx).ok()?;
+                if tri.is_conceptual() || tri.is_deleted() {
+                    return None;
+                }
+                Some(tri.nodes().map(|node| node.idx().unwrap()))
+            })
+            .collect()
+    }
+
+    /// Get the edges of the casual triangles as vertex indices, each undirected edge once with the smaller index first.
+    pub fn edges(&self) -> impl Iterator<Item = (VertexIdx, VertexIdx)> + '_ {
+        // each edge has a hedge in both directions, only the one towards the larger index is kept
+        (0..self.tds().hedge_starting_nodes.len()).filter_map(|hedge_idx| {
+            let hedge = self.tds().get_hedge(hedge_idx).ok()?;
+            match (hedge.starting_node(), hedge.end_node()) {
+                (VertexNode::Casual(v0), VertexNode::Casual(v1)) if v0 < v1 => Some((v0, v1)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Get the adjacency graph of the triangulation, with the [`edges`](Self::edges) weighted by their length.
+    ///
+    /// There is a node per vertex, at its index and with its position as weight,
+    /// so vertices that are not part of the triangulation are isolated nodes.
+    #[cfg(feature = "petgraph")]
+    pub fn to_graph(&self) -> petgraph::graph::UnGraph<Vertex2, f64> {
+        use petgraph::graph::{NodeIndex, UnGraph};
+
+        let mut graph = UnGraph::with_capacity(self.vertices.len(), 0);
+        for &v in &self.vertices {
+            graph.add_node(v);
+        }
+
+        for (v0, v1) in self.edges() {
+            let [a, b] = [self.vertices[v0], self.vertices[v1]];
+            let length = (a[0] - b[0]).hypot(a[1] - b[1]);
+            graph.add_edge(NodeIndex::new(v0), NodeIndex::new(v1), length);
+        }
+
+        graph
+    }
+
+    /// Export the casual triangles as a GeoJSON feature collection of polygons, e.g. for GIS tools.
+    ///
+    /// Each feature has the indices of its vertices as its `vertices` property.
+    #[cfg(feature = "geojson")]
+    pub fn to_geojson(&self) -> geojson::FeatureCollection {
+        self.tri_indices()
+            .into_iter()
+            .map(|tri| {
+                let mut properties = geojson::JsonObject::new();
+                properties.insert("vertices".into(), tri.to_vec().into());
+                io::geojson_polygon(&tri.map(|v_idx| self.vertices[v_idx]), properties)
+            })
+            .collect()
+    }
+
+    /// Export the power cells, i.e. the Voronoi cells if unweighted, clipped to the box `[min, max]`
+    /// as a GeoJSON feature collection of polygons, see [`clipped_power_cells`](crate::voronoi::clipped_power_cells).
+    ///
+    /// Each feature has the index of its vertex as its `vertex` property, and its `weight` if weighted.
+    /// Empty cells are skipped.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation has no triangles.
+    #[cfg(feature = "geojson")]
+    pub fn cells_to_geojson(&self, bbox: [Vertex2; 2]) -> HowResult<geojson::FeatureCollection> {
+        let cells = crate::voronoi::clipped_power_cells(self, bbox)?;
+
+        HowOk(
+            cells
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(|(v_idx, cell)| {
+                    let mut properties = geojson::JsonObject::new();
+                    properties.insert("vertex".into(), v_idx.into());
+                    if let Some(weights) = &self.weights {
+                        properties.insert("weight".into(), weights[v_idx].into());
+                    }
+                    io::geojson_polygon(cell, properties)
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the convex hull as the vertex indices of a closed, counter-clockwise polygon.
+    ///
+    /// Walks along the conceptual triangles, each of which contributes one hull edge. Empty without triangles.
+    pub fn hull_edges(&self) -> Vec<VertexIdx> {
+        let Some(start) =
+            (0..self.tds().num_tris() + self.tds().num_deleted_tris).find_map(|tri_idx| {
+                let tri = self.tds().get_tri(tri_idx).ok()?;
+                if !tri.is_conceptual() || tri.is_deleted() {
+                    return None;
+                }
+                tri.hedges()
+                    .into_iter()
+                    .find(|hedge| !hedge.is_conceptual())
+            })
+        else {
+            return Vec::new();
+        };
+
+        // the hull hedges of the conceptual triangles run clockwise, so walk them backwards
+        let mut hull = Vec::with_capacity(self.num_hull_facets());
+        let mut hedge = start.clone();
+        loop {
+            hull.push(hedge.end_node().idx().unwrap());
+
+            hedge = hedge.prev().twin().prev();
+            if hedge.idx == start.idx {
+                break;
+            }
+        }
+
+        hull
+    }
+
+    /// Get the casual triangles as a simplicial complex, together with its boundary operators.
+    pub fn boundary_matrices(&self) -> BoundaryMatrices {
+        BoundaryMatrices::from_tris(self.tri_indices())
+    }
+
+    /// Get the primal and dual (power diagram) measures of the simplicial complex, for discrete exterior calculus.
+    ///
+    /// The arrays are aligned with the simplices of [`boundary_matrices`](Self::boundary_matrices).
+    pub fn dec_measures(&self) -> DecMeasures {
+        DecMeasures::from_tris(&self.boundary_matrices(), &self.vertices, |v_idx| {
+            self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
+        })
+    }
+
+    /// Get the quality metrics of the casual triangles, in the same order as [`tris`](Self::tris).
+    pub fn tri_qualities(&self) -> Vec<TriQuality> {
+        self.tris().into_iter().map(tri_quality).collect()
+    }
+
+    /// Get the histograms of the quality metrics of the casual triangles, each with `num_bins` bins.
+    pub fn quality_histograms(&self, num_bins: usize) -> QualityHistograms {
+        QualityHistograms::from_tris(&self.tri_qualities(), num_bins)
+    }
+
+    /// Get the used vertices.
+    #[must_use]
+    pub const fn used_vertices(&self) -> &Vec<usize> {
+        &self.used_vertices
+    }
+
+    /// Get the vertices skipped due to their weight, i.e. not in the power circle of their containing triangle.
+    #[must_use]
+    pub fn redundant_vertices(&self) -> &[usize] {
+        &self.redundant_vertices
+    }
+
+    /// Get the vertices skipped due to epsilon, e.g. to re-insert them with a smaller one.
+    #[must_use]
+    pub fn ignored_vertices(&self) -> &[usize] {
+        &self.ignored_vertices
+    }
+
+    /// Get the vertices.
+    #[must_use]
+    pub const fn vertices(&self) -> &Vec<[f64; 2]> {
+        &self.vertices
+    }
+
+    /// Get the weights.
+    #[must_use]
+    pub const fn weights(&self) -> &Option<Vec<f64>> {
+        &self.weights
+    }
+
+    /// Get the local origin, that heights are computed relative to.
+    ///
+    /// The vertices themselves are kept in their input coordinates.
+    #[must_use]
+    pub const fn origin(&self) -> Vertex2 {
+        self.origin
+    }
+
+    /// Get how the power circle tests of the insertion are decided.
+    #[must_use]
+    pub const fn predicate_mode(&self) -> PredicateMode {
+        self.predicate_mode
+    }
+
+    /// Set how the power circle tests of the insertion are decided.
+    ///
+    /// With [`PredicateMode::FastInexact`], the triangulation built afterwards is not guaranteed to be regular.
+    pub const fn set_predicate_mode(&mut self, predicate_mode: PredicateMode) {
+        self.predicate_mode = predicate_mode;
+    }
+
+    /// Get whether vertices are also skipped by epsilon after their insertion, see [`Self::set_eps_in_flips`].
+    #[must_use]
+    pub const fn eps_in_flips(&self) -> bool {
+        self.eps_in_flips
+    }
+
+    /// Set whether vertices are also skipped by epsilon after their insertion, which simplifies the triangulation further.
+    ///
+    /// Inserting a vertex changes the neighbors of the vertices around it, so an inserted vertex of degree 3 might end up
+    /// less than epsilon below the lifted triangle of its neighbors. If enabled, such vertices are removed via a 3->1 flip
+    /// during the flips of later insertions, and are added to [`Self::ignored_vertices`].
+    /// So [`Self::refine_epsilon`] does not keep all vertices used before anymore.
+    ///
+    /// Only applies to unweighted triangulations, as a removed vertex might hide redundant vertices,
+    /// and to [`InsertionStrategy::Flips`].
+    pub const fn set_eps_in_flips(&mut self, eps_in_flips: bool) {
+        self.eps_in_flips = eps_in_flips;
+    }
+
+    /// Get how the vertices are inserted.
+    #[must_use]
+    pub const fn insertion_strategy(&self) -> InsertionStrategy {
+        self.insertion_strategy
+    }
+
+    /// Get the order of the vertices inserted by [`Self::insert`].
+    #[must_use]
+    pub const fn insert_order(&self) -> InsertOrder {
+        self.insert_order
+    }
+
+    /// Set the order of the vertices inserted by [`Self::insert`].
+    pub const fn set_insert_order(&mut self, insert_order: InsertOrder) {
+        self.insert_order = insert_order;
+    }
+
+    /// Get how vertices at the position of an inserted vertex are skipped.
+    #[must_use]
+    pub const fn dedup(&self) -> DedupPolicy {
+        self.dedup
+    }
+
+    /// Set how vertices at the position of an inserted vertex are skipped, only affects later insertions.
+    pub const fn set_dedup(&mut self, dedup: DedupPolicy) {
+        self.dedup = dedup;
+    }
+
+    /// Get what happens to vertices at the position of an inserted vertex.
+    #[must_use]
+    pub const fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Set what happens to vertices at the position of an inserted vertex, only affects later insertions.
+    pub const fn set_duplicate_policy(&mut self, duplicate_policy: DuplicatePolicy) {
+        self.duplicate_policy = duplicate_policy;
+    }
+
+    /// Get the canonical vertex of each vertex, i.e. the vertex itself,
+    /// or for a duplicate the vertex at its position that was inserted before.
+    ///
+    /// The canonical vertex might not be part of the triangulation, e.g. when it becomes redundant later.
+    #[must_use]
+    pub fn canonical_vertices(&self) -> Vec<VertexIdx> {
+        canonical_vertices(self.vertices.len(), &self.duplicate_of)
+    }
+
+    /// Get a casual triangle incident to the vertex, `None` if the vertex is not part of the triangulation.
+    ///
+    /// Vertices keep their input index, also when they are spatially sorted for insertion.
+    /// An incident triangle of each vertex is maintained during insertion, such that this does not scan the triangles.
+    #[must_use]
+    pub fn vertex_to_triangle(&self, v_idx: VertexIdx) -> Option<usize> {
+        let tri_idx = self.tds.vertex_tri(v_idx)?;
+        self.tri_around(v_idx, tri_idx, false)
+    }
+
+    /// Get the casual triangles incident to the vertex, i.e. its star, in counter-clockwise order.
+    ///
+    /// Rotates around the vertex starting at its incident triangle, empty if the vertex is not part of the triangulation.
+    #[must_use]
+    pub fn incident_tris(&self, v_idx: VertexIdx) -> Vec<usize> {
+        self.hedges_from(v_idx)
+            .into_iter()
+            .filter(|hedge| hedge.tri().is_casual())
+            .map(|hedge| hedge.tri().idx)
+            .collect()
+    }
+
+    /// The hedges starting at the vertex in counter-clockwise order, by rotating around it from its incident triangle.
+    fn hedges_from(&self, v_idx: VertexIdx) -> Vec<HedgeIterator<'_>> {
+        let Some(start) = self.tds.vertex_tri(v_idx).and_then(|tri_idx| {
+            self.tds
+                .get_tri(tri_idx)
+                .ok()?
+                .hedges()
+                .into_iter()
+                .find(|hedge| hedge.starting_node() == VertexNode::Casual(v_idx))
+        }) else {
+            return Vec::new();
+        };
+
+        let mut hedges = Vec::new();
+        let mut hedge = start.clone();
+        loop {
+            hedge = hedge.prev().twin();
+            hedges.push(hedge.clone());
+            if hedge.idx == start.idx {
+                return hedges;
+            }
+        }
+    }
+
+    /// Get the vertex of the triangulation closest to the point, `None` if there are no triangles.
+    ///
+    /// Locates the point and descends to closer neighbors from there, as in a Delaunay triangulation every vertex
+    /// but the closest one has a closer neighbor. Weighted triangulations are not Delaunay, so all of their
+    /// vertices are scanned instead, see [`Self::nearest_power_vertex`] for the query they answer by descending.
+    #[must_use]
+    pub fn nearest_vertex(&self, point: Vertex2) -> Option<VertexIdx> {
+        let dist_sq = |v_idx: VertexIdx| {
+            let v = self.vertices[v_idx];
+            (v[0] - point[0]).powi(2) + (v[1] - point[1]).powi(2)
+        };
+
+        if self.weighted() {
+            return self
+                .tds
+                .hedge_starting_nodes
+                .iter()
+                .filter_map(|node| node.idx())
+                .min_by(|&a, &b| dist_sq(a).total_cmp(&dist_sq(b)));
+        }
+
+        self.descend_to_nearest(point, dist_sq)
+    }
+
+    /// Get the vertex, whose power cell contains the point, `None` if there are no triangles.
+    ///
+    /// That is the vertex with the smallest power distance `|p - v|² - w` to the point,
+    /// found by locating the point and descending to neighbors of smaller power distance.
+    /// Without weights, this is the [`nearest_vertex`](Self::nearest_vertex).
+    #[must_use]
+    pub fn nearest_power_vertex(&self, point: Vertex2) -> Option<VertexIdx> {
+        self.descend_to_nearest(point, |v_idx| {
+            let v = self.vertices[v_idx];
+            (v[0] - point[0]).powi(2) + (v[1] - point[1]).powi(2)
+                - self.weights.as_ref().map_or(0.0, |weights| weights[v_idx])
+        })
+    }
+
+    /// Descend from a vertex of the triangle containing the point to neighbors of smaller distance.
+    fn descend_to_nearest(
+        &self,
+        point: Vertex2,
+        dist: impl Fn(VertexIdx) -> f64,
+    ) -> Option<VertexIdx> {
+        if self.tds.num_tris() == 0 {
+            return None;
+        }
+
+        let closest = |v_idxs: &mut dyn Iterator<Item = VertexIdx>| {
+            v_idxs.min_by(|&a, &b| dist(a).total_cmp(&dist(b)))
+        };
+        let start_tri = self
+            .locate_point_vis_walk(point, self.walk_start(point))
+            .ok()
+            .filter(|&tri_idx| self.is_live_tri(tri_idx))
+            .or_else(|| {
+                (0..self.tds.hedge_starting_nodes.len() / 3)
+                    .find(|&tri_idx| self.is_live_tri(tri_idx))
+            })?;
+        let mut v_idx = closest(
+            &mut self
+                .tds
+                .get_tri(start_tri)
+                .ok()?
+                .nodes()
+                .into_iter()
+                .filter_map(|node| node.idx()),
+        )?;
+
+        loop {
+            let neighbor = closest(
+                &mut self
+                    .hedges_from(v_idx)
+                    .into_iter()
+                    .filter_map(|hedge| hedge.end_node().idx()),
+            )?;
+            if dist(neighbor) >= dist(v_idx) {
+                return Some(v_idx);
+            }
+            v_idx = neighbor;
+        }
+    }
+
+    /// Get the most threads of the parallel methods, see [`Self::set_max_threads`].
+    #[must_use]
+    pub const fn max_threads(&self) -> Option<usize> {
+        self.max_threads
+    }
+
+    /// Set the most threads of the parallel methods, e.g. [`Self::par_is_regular`], to bound their CPU usage.
+    ///
+    /// With `Some`, the calls run on a thread pool with that many threads, built on the first call and kept until the
+    /// limit is set again. With `None`, the default, they run on the global pool of [rayon], or on the pool of a
+    /// surrounding `ThreadPool::install`, to share one pool.
+    /// Without the `parallel` feature, they run on the calling thread either way.
+    #[cfg_attr(not(feature = "parallel"), allow(clippy::missing_const_for_fn))]
+    pub fn set_max_threads(&mut self, max_threads: Option<usize>) {
+        self.max_threads = max_threads;
+        self.thread_pool = parallel::ThreadPool::new();
+    }
+
+    /// Get whether the visibility walks start from a uniform grid of recently inserted triangles.
+    #[must_use]
+    pub const fn uses_locate_grid(&self) -> bool {
+        self.use_locate_grid
+    }
+
+    /// Set whether the visibility walks start from a uniform grid of recently inserted triangles.
+    ///
+    /// The grid remembers the triangle last inserted in each of its cells, so walks start close to their target even
+    /// without a spatially sorted insertion order, e.g. for single insertions in random order or many [`Self::locate`]
+    /// queries. It has about a cell per four vertices and is built again whenever the number of vertices doubled.
+    /// A triangulation of [`Self::with_bounded_capacity`] does not use the grid.
+    pub fn set_locate_grid(&mut self, use_locate_grid: bool) {
+        self.use_locate_grid = use_locate_grid && self.max_vertices.is_none();
+        if !use_locate_grid {
+            self.locate_grid.clear();
+        }
+    }
+
+    /// Set how the vertices are inserted, both give the same regular triangulation.
+    ///
+    /// [`InsertionStrategy::BowyerWatson`] removes all triangles in conflict with a vertex at once, instead of flipping
+    /// them one by one, which often behaves better for heavily weighted input, where many vertices become redundant.
+    pub const fn set_insertion_strategy(&mut self, insertion_strategy: InsertionStrategy) {
+        self.insertion_strategy = insertion_strategy;
+    }
+
+    /// Get the statistics of the build, e.g. peak buffer sizes and counts of flips, recorded since construction, see [`BuildStats`].
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub const fn stats(&self) -> BuildStats {
+        self.stats
+    }
+
+    /// Get the statistics of the visibility walks, recorded since construction.
+    #[must_use]
+    pub const fn walk_stats(&self) -> &WalkStats {
+        &self.walk_stats
+    }
+
+    /// The triangle to start a visibility walk towards the point from.
+    ///
+    /// The triangle of the [`LocateGrid`] cell of the point, if it is used and the triangle still exists,
+    /// otherwise the last inserted triangle if there is any.
+    pub(crate) fn walk_start(&self, point: Vertex2) -> usize {
+        self.locate_grid
+            .hint(&point)
+            .filter(|&tri_idx| self.is_live_tri(tri_idx))
+            .or(self.last_inserted_triangle)
+            .unwrap_or(self.tds().num_tris() + self.tds().num_deleted_tris - 1)
+    }
+
+    /// Locate the triangle that contains a point by using the visibility walk.
+    pub fn locate_vis_walk(&self, v_idx: usize, tri_idx_start: usize) -> HowResult<usize> {
+        self.locate_point_vis_walk(self.vertices()[v_idx], tri_idx_start)
+    }
+
+    /// The current generation of the triangulation, advanced on every change of its triangles.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Get a handle to the triangle with this index, e.g. as a hint for [`Self::insert_vertex`].
+    ///
+    /// ## Errors
+    /// Returns an error if there is no such triangle, i.e. the index is out of bounds or the triangle is deleted.
+    pub fn tri_handle(&self, tri_idx: usize) -> HowResult<TriHandle> {
+        if !self.is_live_tri(tri_idx) {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "triangle",
+                idx: tri_idx,
+            }));
+        }
+
+        HowOk(self.handle(tri_idx))
+    }
+
+    /// Get the casual triangles adjacent to a triangle, `None` across the hull.
+    ///
+    /// The neighbor `i` shares the hedge `i` of the triangle, i.e. the edge from its node `i` to its node `i + 1`.
+    ///
+    /// ## Errors
+    /// Returns an error if there is no such triangle, i.e. the index is out of bounds or the triangle is deleted.
+    pub fn tri_neighbors(&self, tri_idx: usize) -> HowResult<[Option<usize>; 3]> {
+        if !self.is_live_tri(tri_idx) {
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "triangle",
+                idx: tri_idx,
+            }));
+        }
+
+        HowOk(self.tds.get_tri(tri_idx)?.hedges().map(|hedge| {
+            let neighbor = hedge.twin().tri();
+            neighbor.is_casual().then_some(neighbor.idx)
+        }))
+    }
+
+    /// Get the casual triangles a segment passes through, in order from `a` to `b`.
+    ///
+    /// Walks from the triangle containing `a` across the edges the segment crosses, until reaching the one containing `b`.
+    /// Where the segment passes through a vertex or along an edge, the walk steps around the vertex,
+    /// so triangles that the segment only touches are included as well.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation has no triangles, or [`RitaError::SegmentOutsideHull`] if `a` or `b` is
+    /// outside the convex hull.
+    pub fn tris_intersecting_segment(&self, a: Vertex2, b: Vertex2) -> HowResult<Vec<usize>> {
+        let start_tris = match self.locate(a)? {
+            Location::InsideTriangle(handle) => vec![handle.idx()],
+            Location::OnEdge(hedge_idx) => {
+                vec![
+                    hedge_idx / 3,
+                    self.tds.get_hedge(hedge_idx)?.twin().tri().idx,
+                ]
+            }
+            Location::OnVertex(v_idx) => self.incident_tris(v_idx),
+            Location::OutsideHull(_) => {
+                return Err(anyhow::Error::new(RitaError::SegmentOutsideHull));
+            }
+        };
+        if matches!(self.locate(b)?, Location::OutsideHull(_)) {
+            return Err(anyhow::Error::new(RitaError::SegmentOutsideHull));
+        }
+
+        let ends = |hedge: &HedgeIterator| {
+            [hedge.starting_node(), hedge.end_node()].map(|node| self.vertices[node.idx().unwrap()])
+        };
+
+        // the segment enters the triangle, if `b` is not behind an edge through `a`
+        let mut tri_idx = start_tris
+            .into_iter()
+            .find(|&tri_idx| {
+                self.tds.get_tri(tri_idx).is_ok_and(|tri| {
+                    tri.is_casual()
+                        && tri.hedges().iter().all(|hedge| {
+                            let [u, v] = ends(hedge);
+                            predicates::orient_2d(&u, &v, &a) != 0.0
+                                || predicates::orient_2d(&u, &v, &b) >= 0.0
+                        })
+                })
+            })
+            .ok_or(RitaError::LocateFailed)?;
+
+        let mut tris = vec![tri_idx];
+        for _ in 0..self.num_all_tris() {
+            // leave across the edge, that the line from `a` to `b` crosses from left to right, with `b` beyond it
+            let exit = self
+                .tds
+                .get_tri(tri_idx)?
+                .hedges()
+                .into_iter()
+                .find(|hedge| {
+                    let [u, v] = ends(hedge);
+                    predicates::orient_2d(&u, &v, &b) < 0.0
+                        && predicates::orient_2d(&a, &b, &u) <= 0.0
+                        && predicates::orient_2d(&a, &b, &v) >= 0.0
+                });
+            let Some(exit) = exit else {
+                return HowOk(tris);
+            };
+
+            tri_idx = exit.twin().tri().idx;
+            tris.push(tri_idx);
+        }
+
+        Err(anyhow::Error::new(RitaError::LocateFailed))
+    }
+
+    /// The index of the triangle of a handle, checked to be taken from the current generation.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangulation changed since the handle was taken.
+    pub fn resolve_tri(&self, handle: TriHandle) -> HowResult<usize> {
+        if handle.generation() != self.generation {
+            return Err(anyhow::Error::new(RitaError::StaleHandle {
+                handle: handle.generation(),
+                current: self.generation,
+            }));
+        }
+
+        HowOk(handle.idx())
+    }
+
+    /// A handle to an existing triangle, of the current generation.
+    const fn handle(&self, tri_idx: usize) -> TriHandle {
+        TriHandle::new(tri_idx, self.generation)
+    }
+
+    /// Check if the index refers to an existing, i.e. not deleted, triangle.
+    fn is_live_tri(&self, tri_idx: usize) -> bool {
+        self.tds()
+            .get_tri(tri_idx)
+            .is_ok_and(|tri| !tri.is_deleted())
+    }
+
+    /// Locate an arbitrary point, starting the visibility walk from the last inserted triangle.
+    ///
+    /// Classifies whether the point is inside a triangle, on an edge, on a vertex or outside the convex hull.
+    pub fn locate(&self, point: Vertex2) -> HowResult<Location> {
+        if self.tds().num_tris() == 0 {
+            return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+        }
+
+        let tri_idx = self.locate_point_vis_walk(point, self.walk_start(point))?;
+        let tri = self.tds().get_tri(tri_idx)?;
+        if tri.is_conceptual() {
+            return HowOk(Location::OutsideHull(self.handle(tri_idx)));
+        }
+
+        if let Some(v_idx) = tri
+            .nodes()
+            .into_iter()
+            .filter_map(|node| node.idx())
+            .find(|&v_idx| self.vertices[v_idx] == point)
+        {
+            return HowOk(Location::OnVertex(v_idx));
+        }
+
+        let on_hedge = tri.hedges().into_iter().find(|hedge| {
+            let [v0, v1] = [hedge.starting_node(), hedge.end_node()]
+                .map(|node| self.vertices[node.idx().unwrap()]);
+            predicates::orient_2d(&v0, &v1, &point) == 0.0
+        });
+
+        HowOk(
+            on_hedge.map_or(Location::InsideTriangle(self.handle(tri_idx)), |hedge| {
+                Location::OnEdge(hedge.idx)
+            }),
+        )
+    }
+
+    /// Interpolate per vertex `values` at an arbitrary point, via its barycentric coordinates in the containing triangle.
+    ///
+    /// The `values` are indexed like the vertices.
+    /// Returns `None` for points outside the convex hull, or if a vertex of the containing triangle has no value.
+    pub fn interpolate(&self, point: Vertex2, values: &[f64]) -> Option<f64> {
+        let (v_idxs, lambdas) = self.barycentric_coordinates(point)?;
+        // a point on a vertex takes its value, even if it is not finite
+        let [v_idx0, v_idx1, v_idx2] = v_idxs;
+        if v_idx0 == v_idx1 && v_idx0 == v_idx2 {
+            return values.get(v_idx0).copied();
+        }
+
+        v_idxs
+            .into_iter()
+            .zip(lambdas)
+            .map(|(v_idx, lambda)| values.get(v_idx).map(|value| lambda * value))
+            .sum()
+    }
+
+    /// Freeze the triangulation into a read-only [`CompactTriangulation`], optimized for queries.
+    ///
+    /// The vertices are copied and keep their indices, the triangles are renumbered, see [`crate::frozen`].
+    #[must_use]
+    pub fn freeze(&self) -> CompactTriangulation {
+        CompactTriangulation::new(self.vertices.clone(), &self.tri_indices())
+    }
+
+    /// A read-only view of the current state, to share with threads that query or render it while this one keeps
+    /// inserting.
+    ///
+    /// The view is frozen by [`Self::freeze`] once per state: until the triangles or vertices change, further calls
+    /// return the same [`Arc`] without copying anything.
+    pub fn snapshot(&mut self) -> Arc<CompactTriangulation> {
+        let state = (self.generation, self.vertices.len());
+        match &self.last_snapshot {
+            Some((generation, num_vertices, snapshot)) if (*generation, *num_vertices) == state => {
+                Arc::clone(snapshot)
+            }
+            _ => {
+                let snapshot = Arc::new(self.freeze());
+                self.last_snapshot = Some((state.0, state.1, Arc::clone(&snapshot)));
+                snapshot
+            }
+        }
+    }
+
+    /// The vertices of the triangle that contains an arbitrary point, and the barycentric coordinates of the point in it.
+    ///
+    /// A point on a vertex gets the coordinate `1.0` for the first of three copies of the vertex.
+    /// Returns `None` for points outside the convex hull.
+    pub(crate) fn barycentric_coordinates(
+        &self,
+        point: Vertex2,
+    ) -> Option<([VertexIdx; 3], [f64; 3])> {
+        let tri_idx = match self.locate(point).ok()? {
+            Location::InsideTriangle(handle) => handle.idx(),
+            Location::OnEdge(hedge_idx) => hedge_idx / 3,
+            Location::OnVertex(v_idx) => return Some(([v_idx; 3], [1.0, 0.0, 0.0])),
+            Location::OutsideHull(_) => return None,
+        };
+
+        let [n0, n1, n2] = self.tds().get_tri(tri_idx).ok()?.nodes();
+        let v_idxs = [n0.idx()?, n1.idx()?, n2.idx()?];
+        let lambdas = barycentric_2d(v_idxs.map(|v_idx| self.vertices[v_idx]), point);
+
+        Some((v_idxs, lambdas))
+    }
+
+    /// Locate the triangle that contains an arbitrary point by using the visibility walk.
+    pub(crate) fn locate_point_vis_walk(
+        &self,
+        v: Vertex2,
+        tri_idx_start: usize,
+    ) -> HowResult<usize> {
+        self.walk_to_point(v, tri_idx_start)
+            .map(|(tri_idx, _, _)| tri_idx)
+    }
+
+    /// Walks from the starting triangle towards the point.
+    ///
+    /// Returns the reached triangle, the number of steps and whether the walk ended in the special case beyond a hull vertex.
+    fn walk_to_point(&self, v: Vertex2, tri_idx_start: usize) -> HowResult<(usize, usize, bool)> {
+        let mut tri_idx = tri_idx_start; // variable to store the current triangle index
+        let mut num_steps = 0;
+
+        // start with all hedges of the starting triangle, then the two of each next one besides the one walked over
+        let mut v_hedges = self.tds().get_tri(tri_idx)?.hedges();
+        let mut num_hedges = 3;
+
+        let mut side = true; // TODO or false?
+
+        loop {
+            // choose one of the two (three) hedges of the triangle
+            if let Some(hedge) = self.choose_hedge(&v_hedges[..num_hedges], &v) {
+                let hedge_twin = hedge.twin();
+                tri_idx = hedge_twin.tri().idx; // the triangle in question is the one incident to the twin hedge
+                num_steps += 1;
+
+                assert_eq!(
+                    hedge_twin.prev().starting_node(),
+                    hedge_twin.next().end_node()
+                );
+                // if during traversal we travel outside the chull of the current trianuglation
+                // we now have two conceptula triangles in question
+                // they each have an casual edge on the c-hull, and are connected to the conceptual point
+                // they also share a common point
+                // we use orientation tests against their hull edges to determine where the point lies in
+                // TODO: refactor this special case
+                if self.weighted()
+                    && hedge_twin.prev().twin().tri().is_conceptual()
+                    && hedge_twin.next().twin().tri().is_conceptual()
+                    && !hedge_twin.prev().starting_node().is_conceptual()
+                {
+                    // the triangle oab has two hull edges, oa and ob, each shared with a conceptual triangle
+                    let o = self.vertices[hedge_twin.prev().starting_node().idx().unwrap()];
+                    let a = self.vertices[hedge_twin.prev().end_node().idx().unwrap()];
+                    let a_tri_idx = hedge_twin.prev().twin().tri().idx;
+                    let b = self.vertices[hedge_twin.next().starting_node().idx().unwrap()];
+                    let b_tri_idx = hedge_twin.next().twin().tri().idx;
+
+                    // v is beyond a hull edge, if it is on the opposite side of the third vertex of the triangle
+                    let beyond_oa =
+                        predicates::orient_2d(&o, &a, &v) * predicates::orient_2d(&o, &a, &b) < 0.0;
+                    let beyond_ob =
+                        predicates::orient_2d(&o, &b, &v) * predicates::orient_2d(&o, &b, &a) < 0.0;
+
+                    // if v sees both hull edges, either conceptual triangle is a valid choice
+                    return match (beyond_oa, beyond_ob) {
+                        (false, false) => HowOk((tri_idx, num_steps, true)),
+                        (true, _) => HowOk((a_tri_idx, num_steps, true)),
+                        (false, true) => HowOk((b_tri_idx, num_steps, true)),
+                    };
+                } else if side {
+                    v_hedges[..2].clone_from_slice(&[hedge_twin.next(), hedge_twin.prev()]);
+                } else {
+                    v_hedges[..2].clone_from_slice(&[hedge_twin.prev(), hedge_twin.next()]);
+                }
+                // only look at the hedges of the current triangle
+                num_hedges = 2;
+
+                side = !side;
+            } else {
+                return HowOk((tri_idx, num_steps, false));
+            }
+        }
+    }
+
+    /// Log the times recorded so far, with the `log_timing` feature.
+    #[cfg_attr(not(feature = "log_timing"), allow(clippy::missing_const_for_fn))]
+    fn log_time(&self) {
+        #[cfg(feature = "log_timing")]
+        {
+            log::debug!("-------------------------------------------");
+            log::debug!("Time elapsed:");
+            log::debug!("Inserts computed in {} μs", self.stats.time_inserting);
+            log::debug!("Walks computed in {} μs", self.stats.time_walking);
+            log::debug!("Flips computed in {} μs", self.stats.time_flipping);
+        }
+    }
+
+    fn is_flippable(
+        &self,
+        vertices_from_edge: [usize; 2],
+        vertices_from_incident_tris: [usize; 2],
+        hedge_idx: usize,
+    ) -> HowResult<Option<Flip>> {
+        // Simplified procedure described in Incremental Topological Flipping Works for Regular Triangulations (p. 46,47)
+        // Given an edge cd incident to two triangles acd and bcd
+        let mut num_reflex_points = 0;
+        let mut c_reflex = false;
+        let mut d_reflex = false;
+
+        // helper vertices, i.e. that form a triangle each with the vertices in question
+        let a = vertices_from_incident_tris[0];
+        let b = vertices_from_incident_tris[1];
+
+        // vertices in question
+        let c = vertices_from_edge[0];
+        let d = vertices_from_edge[1];
+
+        // 1) check p = {c, d} to be reflex or convex this can be done as follows, p is the point to check and q the other point of the edge
+        //     - choose at random a "base point" of a, b, WLOG we choose a here (as in the paper)
+        //     - draw a line through p,a
+        //     - if q, b are on different side of the line, then p is reflex, else convex
+        // check if side for d,b for line ca, i.e. c reflex
+        let side_d = predicates::orient_2d(&self.vertices[c], &self.vertices[a], &self.vertices[d]);
+        let side_b = predicates::orient_2d(&self.vertices[c], &self.vertices[a], &self.vertices[b]);
+        if side_d != side_b {
+            num_reflex_points += 1;
+            c_reflex = true;
+        }
+
+        // check side for c,b for line da, i.e. d reflex
+        // TODO only do this check if c is not reflex, i.e. since only one point can be reflex -> would remove 2 orientation tests in some cases
+        let side_c = predicates::orient_2d(&self.vertices[d], &self.vertices[a], &self.vertices[c]);
+        let side_b = predicates::orient_2d(&self.vertices[d], &self.vertices[a], &self.vertices[b]);
+        if side_c != side_b {
+            num_reflex_points += 1;
+            d_reflex = true;
+        }
+
+        // Early out: iff there are no reflex points, the edge is flippable via 2->2
+        if num_reflex_points == 0 {
+            return HowOk(Some(Flip::TwoToTwo));
+        } else if num_reflex_points > 1 {
+            return Err(anyhow::Error::new(RitaError::MultipleReflexVertices {
+                edge: [c, d],
+            }));
+        }
+
+        // 2) For the (hopefully) only marked as reflex, check their degree, if for all the points marked the degree is 3, the the edge is flippable, let again p be the point to check
+        //    - for p to have degree 3, the triangle pab must be in the triangulation (we can PROBABLY check this with hede iterations)
+        let hedge = self.tds().get_hedge(hedge_idx)?;
+
+        if c_reflex {
+            // this triangle should contain the vertex nodes abc
+            let possible_third_tri: TriIterator = if VertexNode::Casual(c) == hedge.starting_node()
+            {
+                hedge.prev().twin().tri()
+            } else {
+                // c is the end node of the hedge
+                hedge.next().twin().tri()
+            };
+
+            if possible_third_tri.is_conceptual() {
+                return HowOk(None);
+            }
+
+            let mut idxs = [a, b, c];
+            let mut tri_idxs = possible_third_tri.nodes().map(|n| n.idx().unwrap());
+            idxs.sort_unstable();
+            tri_idxs.sort_unstable();
+
+            HowOk(match idxs == tri_idxs {
+                // if the possible third tri is the tri abc it fills the reflex wedge and we can flip
+                true => Some(Flip::ThreeToOne((possible_third_tri.idx, c))),
+                false => None,
+            })
+        } else if d_reflex {
+            // this triangle should contain the vertex nodes abc
+            let possible_third_tri: TriIterator = if VertexNode::Casual(d) == hedge.starting_node()
+            {
+                hedge.prev().twin().tri()
+            } else {
+                // d is the end node of the hedge
+                hedge.next().twin().tri()
+            };
+
+            if possible_third_tri.is_conceptual() {
+                return HowOk(None);
+            }
+
+            let mut idxs = [a, b, d];
+            let mut tri_idxs = possible_third_tri.nodes().map(|n| n.idx().unwrap());
+            idxs.sort_unstable();
+            tri_idxs.sort_unstable();
+
+            HowOk(match idxs == tri_idxs {
+                // if the possible third tri is the tri abc it fills the reflex wedge and we can flip
+                true => Some(Flip::ThreeToOne((possible_third_tri.idx, d))),
+                false => None,
+            })
+        } else {
+            Err(anyhow::Error::new(RitaError::InvalidState(
+                "No reflex vertex found, but there should be one",
+            )))
+        }
+    }
+}
+
+// Note: this is for cg lab
+impl PartialEq for Triangulation {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+    }
+}
+
+impl Eq for Triangulation {}
+
+#[cfg(all(test, feature = "logging"))]
+mod pre_test {
+    #[test]
+    fn logging_enabled() {
+        // When logging is enabled, this test passes; used to remind to use --features logging for full test output
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::audit::SoundnessIssue;
+    use crate::decimation::ErrorMetric;
+    use rita_test_utils::sample_vertices_2d;
+    #[cfg(not(feature = "wasm"))]
+    use rita_test_utils::sample_weights;
+
+    fn verify_triangulation(triangulation: &Triangulation) {
+        let regularity = triangulation.par_is_regular(false);
+        let sound = triangulation.is_sound().unwrap();
+        assert_eq!(regularity, 1.0);
+        assert!(sound);
+        assert!(
+            triangulation
+                .check_regularity(RegularityCheck::Local, false)
+                .is_regular()
+        );
+
+        // the maintained counters match a full scan
+        let num_conceptual_tris = (0..triangulation.num_all_tris())
+            .map(|tri_idx| triangulation.tds().get_tri(tri_idx).unwrap())
+            .filter(|tri| tri.is_conceptual() && !tri.is_deleted())
+            .count();
+        assert_eq!(triangulation.num_conceptual_tris(), num_conceptual_tris);
+        assert_eq!(triangulation.num_casual_tris(), triangulation.tris().len());
+    }
+
+    const NUM_VERTICES_LIST: [usize; 7] = [3, 5, 10, 50, 100, 500, 1000];
+
+    const EXAMPLE_VERTICES: [[f64; 2]; 10] = [
+        [0.0, 0.0],
+        [-0.5, 1.0],
+        [0.0, 2.5],
+        [2.0, 3.0],
+        [4.0, 2.5],
+        [5.0, 1.5],
+        [4.5, 0.5],
+        [2.5, -0.5],
+        [1.5, 1.5],
+        [3.0, 1.0],
+    ];
+    #[cfg(not(feature = "wasm"))]
+    const EXAMPLE_WEIGHTS: [f64; 10] = [
+        0.681, 0.579, 0.5625, 0.86225, 10.0, 0.472, 0.5865, 0.59625, 0.51225, 7.0,
+    ];
+
+    fn run_delaunay_2d_test() {
+        for n in NUM_VERTICES_LIST {
+            let vertices = sample_vertices_2d(n, None);
+
+            let mut triangulation = Triangulation::new(None);
+            let result = triangulation.insert_vertices(&vertices, None, true);
+
+            assert!(
+                result.is_ok(),
+                "insert_vertices failed: {}",
+                result.unwrap_err()
+            );
+
+            verify_triangulation(&triangulation);
+        }
+    }
+
+    #[test]
+    fn test_get_tris() {
+        // Test unweighted case (runs with both geogram and wasm/robust)
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let tris = triangulation.tris();
+        let num_tris = tris.len();
+
+        assert!(tris.len() == 10, "Expected 10 triangles, got {num_tris}");
+
+        // Test weighted case (geogram only; wasm rejects weights)
+        #[cfg(not(feature = "wasm"))]
+        {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices(EXAMPLE_VERTICES, Some(EXAMPLE_WEIGHTS.to_vec()), true)
+                .unwrap();
+
+            let tris = triangulation.tris();
+            let num_tris = tris.len();
+
+            assert!(tris.len() == 8, "Expected 8 triangles, got {num_tris}");
+        }
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_large_coordinates() {
+        // UTM-like coordinates, where the absolute heights would lose the weights to rounding
+        let offset = [500_000.0, 5_700_000.0];
+        let vertices = EXAMPLE_VERTICES.map(|v| [v[0] + offset[0], v[1] + offset[1]]);
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, Some(EXAMPLE_WEIGHTS.to_vec()), true)
+            .unwrap();
+
+        assert_eq!(triangulation.tris().len(), 8);
+        assert_eq!(triangulation.vertices(), &vertices.to_vec());
+        assert!((triangulation.origin()[0] - offset[0]).abs() < 10.0);
+        assert!((triangulation.origin()[1] - offset[1]).abs() < 10.0);
+        verify_triangulation(&triangulation);
+    }
+
+    #[test]
+    fn test_from_projected_3d() {
+        // the example vertices on the tilted plane z = x + y
+        let points = EXAMPLE_VERTICES.map(|v| [v[0], v[1], v[0] + v[1]]);
+
+        let triangulation = Triangulation::from_projected_3d(&points, None).unwrap();
+        let tri_indices = triangulation.tri_indices();
+
+        assert_eq!(tri_indices.len(), 10);
+        assert_eq!(
+            tri_indices
+                .iter()
+                .map(|tri| tri.map(|v_idx| triangulation.vertices()[v_idx]))
+                .collect::<Vec<_>>(),
+            triangulation.tris()
+        );
+
+        // with a given plane, e.g. the xy-plane
+        let plane = Plane::new([0.0; 3], [0.0, 0.0, 1.0]).unwrap();
+        let triangulation = Triangulation::from_projected_3d(&points, Some(plane)).unwrap();
+        assert_eq!(triangulation.tri_indices().len(), 10);
+    }
+
+    #[test]
+    fn test_bounded_capacity() {
+        fn capacities(triangulation: &Triangulation) -> [usize; 10] {
+            let tds = triangulation.tds();
+            let [free, check, del, keep, added, boundary] = tds.scratch_capacities();
+            [
+                triangulation.vertices().capacity(),
+                tds.hedge_starting_nodes.capacity(),
+                tds.hedge_twins.capacity(),
+                triangulation.hedges_to_verify.capacity(),
+                free,
+                check,
+                del,
+                keep,
+                added,
+                boundary,
+            ]
+        }
+
+        for strategy in [InsertionStrategy::Flips, InsertionStrategy::BowyerWatson] {
+            let mut triangulation =
+                Triangulation::with_bounded_capacity(None, EXAMPLE_VERTICES.len());
+            triangulation.set_insertion_strategy(strategy);
+            triangulation.set_locate_grid(true);
+            assert!(!triangulation.uses_locate_grid());
+            let initial = capacities(&triangulation);
+            assert_eq!(initial, [10, 54, 54, 30, 18, 54, 18, 18, 18, 18]);
+
+            triangulation
+                .insert_vertices(&EXAMPLE_VERTICES[..9], None, true)
+                .unwrap();
+            triangulation
+                .insert_vertex(EXAMPLE_VERTICES[9], None, None)
+                .unwrap();
+            assert_eq!(capacities(&triangulation), initial);
+            verify_triangulation(&triangulation);
+
+            triangulation.remove_vertices(&[3]).unwrap();
+            assert_eq!(capacities(&triangulation), initial);
+            verify_triangulation(&triangulation);
+        }
+
+        // a full flip stack stops the insertion after the split, the vertex is taken out again
+        let mut triangulation = Triangulation::with_bounded_capacity(None, EXAMPLE_VERTICES.len());
+        triangulation
+            .insert_vertices(&EXAMPLE_VERTICES[..9], None, true)
+            .unwrap();
+        let expected = sorted_tris(&triangulation);
+        triangulation.hedges_to_verify = Vec::with_capacity(2);
+        let err = triangulation
+            .insert_vertex(EXAMPLE_VERTICES[9], None, None)
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<RitaError>(),
+            Some(RitaError::BufferFull {
+                buffer: "flip stack",
+                ..
+            })
+        ));
+        assert!(!triangulation.used_vertices().contains(&9));
+        assert_eq!(triangulation.num_removed_vertices(), 0);
+        assert_eq!(sorted_tris(&triangulation), expected);
+        verify_triangulation(&triangulation);
+
+        let mut triangulation = Triangulation::with_bounded_capacity(None, EXAMPLE_VERTICES.len());
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let err = triangulation
+            .insert_vertex([1.0, 1.0], None, None)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RitaError>(),
+            Some(&RitaError::CapacityExceeded {
+                max_vertices: 10,
+                requested: 11
+            })
+        );
+        assert_eq!(triangulation.vertices().len(), 10);
+        assert!(
+            triangulation
+                .insert_vertices([[1.0, 1.0]], None, true)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_insert_order() {
+        let vertices = sample_vertices_2d(500, None);
+
+        for order in [
+            InsertOrder::Input,
+            InsertOrder::Hilbert,
+            InsertOrder::Random(42),
+            InsertOrder::WeightedHilbert,
+            InsertOrder::Morton,
+            InsertOrder::Brio(42),
+        ] {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices_ordered(&vertices, None, order)
+                .unwrap();
+            verify_triangulation(&triangulation);
+        }
+
+        // the same seed results in the same construction
+        let tris = [7, 7].map(|seed| {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices_ordered(&vertices, None, InsertOrder::Random(seed))
+                .unwrap();
+            triangulation.tri_indices()
+        });
+        assert_eq!(tris[0], tris[1]);
+
+        // the insert orders are accepted in place of the toggle, the BRIO is a permutation of the vertices
+        let idxs: Vec<usize> = (0..vertices.len()).collect();
+        let mut brio = brio_order_2d(&vertices, &idxs, 3);
+        assert_ne!(brio, sort_along_hilbert_curve_2d(&vertices, &idxs));
+        brio.sort_unstable();
+        assert_eq!(brio, idxs);
+        for insert_order in [
+            InsertOrder::Input,
+            InsertOrder::Morton,
+            InsertOrder::Brio(3),
+        ] {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices(&vertices, None, insert_order)
+                .unwrap();
+            assert_eq!(triangulation.num_used_vertices(), vertices.len());
+        }
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, InsertOrder::Random(3))
+            .unwrap();
+        assert_eq!(triangulation.num_used_vertices(), vertices.len());
+    }
+
+    #[test]
+    fn test_collinear_hull_chains() {
+        // a rasterized rectangle outline, i.e. every vertex is on one of four collinear hull chains
+        let mut outline = Vec::new();
+        for x in 0..40 {
+            outline.push([x as f64, 0.0]);
+            outline.push([x as f64, 19.0]);
+        }
+        for y in 1..19 {
+            outline.push([0.0, y as f64]);
+            outline.push([39.0, y as f64]);
+        }
+        let mut filled = outline.clone();
+        filled.extend(
+            sample_vertices_2d(100, Some(1.0..=18.0))
+                .iter()
+                .map(|v| [v[0] * 2.0, v[1]]),
+        );
+
+        // a collinear chain, that is popped first and splits around the first edge
+        let mut chain: Vec<Vertex2> = (0..100).map(|i| [i as f64, 2.0 * i as f64]).collect();
+        chain.swap(10, 99);
+        chain.insert(0, [50.0, 0.0]);
+
+        for (vertices, num_hull_vertices) in [
+            (&outline, outline.len()),
+            (&filled, outline.len()),
+            (&chain, chain.len()),
+        ] {
+            for order in [
+                InsertOrder::Input,
+                InsertOrder::Hilbert,
+                InsertOrder::Random(42),
+            ] {
+                let mut triangulation = Triangulation::new(None);
+                triangulation
+                    .insert_vertices_ordered(vertices, None, order)
+                    .unwrap();
+                verify_triangulation(&triangulation);
+
+                // every vertex of a chain is on the hull, i.e. the hull edges link consecutive ones
+                assert_eq!(triangulation.used_vertices().len(), vertices.len());
+                assert_eq!(triangulation.num_hull_facets(), num_hull_vertices);
+
+                for tri_idx in 0..triangulation.num_all_tris() {
+                    let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+                    if tri.is_casual() && !tri.is_deleted() {
+                        assert!(!triangulation.is_tri_flat(tri_idx).unwrap());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorted_insertion() {
+        // sorted along x, such that every vertex is inserted beyond the current hull
+        let mut vertices = sample_vertices_2d(1000, None);
+        vertices.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, false)
+            .unwrap();
+
+        assert_eq!(triangulation.num_used_vertices(), 1000);
+        verify_triangulation(&triangulation);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        // a linear function is reproduced exactly, up to rounding
+        let f = |[x, y]: Vertex2| 2.0 * x - y + 1.0;
+        let values: Vec<f64> = EXAMPLE_VERTICES.iter().map(|&v| f(v)).collect();
+
+        let [a, b] = [EXAMPLE_VERTICES[0], EXAMPLE_VERTICES[7]];
+        for point in [a, [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0], [1.0, 2.0]] {
+            let value = triangulation.interpolate(point, &values).unwrap();
+            assert!((value - f(point)).abs() < 1e-9);
+        }
+
+        assert_eq!(triangulation.interpolate([100.0, 100.0], &values), None);
+        assert_eq!(triangulation.interpolate([1.0, 2.0], &[]), None);
+    }
+
+    #[test]
+    fn test_remove_vertices() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_max_threads(Some(2));
+        triangulation.set_locate_grid(true);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        triangulation.remove_vertices(&[8, 9]).unwrap();
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), 8);
+        assert_eq!(triangulation.num_removed_vertices(), 2);
+        // the settings carry over to the rebuilt triangulation
+        assert_eq!(triangulation.max_threads(), Some(2));
+        assert!(triangulation.uses_locate_grid());
+        assert!(triangulation.locate_grid.hint(&[0.5, 0.5]).is_some());
+        assert!(
+            triangulation
+                .tri_indices()
+                .iter()
+                .flatten()
+                .all(|&v_idx| v_idx < 8)
+        );
+
+        // errors leave the triangulation unchanged
+        assert!(triangulation.remove_vertices(&[10]).is_err());
+        assert!(triangulation.remove_vertices(&[0, 1, 2, 3, 4, 5]).is_err());
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), 8);
+    }
+
+    #[test]
+    fn test_decimate() {
+        let vertices = sample_vertices_2d(500, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let hull = triangulation.hull_edges();
+
+        // a linear field is reproduced exactly, up to rounding
+        let plane: Vec<f64> = vertices.iter().map(|[x, y]| 2.0 * x - y).collect();
+        let decimation = triangulation
+            .decimate(100, ErrorMetric::Attribute(&plane))
+            .unwrap();
+        verify_triangulation(&triangulation);
+        assert!(decimation.max_error < 1e-9);
+        assert_eq!(triangulation.num_used_vertices(), 100.max(hull.len()));
+        assert_eq!(
+            decimation.removed_vertices.len(),
+            triangulation.num_removed_vertices()
+        );
+
+        // the hull, and so the covered area, is preserved
+        let mut decimated_hull = triangulation.hull_edges();
+        let mut hull = hull;
+        decimated_hull.sort_unstable();
+        hull.sort_unstable();
+        assert_eq!(decimated_hull, hull);
+
+        // a bump is kept around its peak
+        let bump: Vec<f64> = vertices
+            .iter()
+            .map(|[x, y]| (-(x * x + y * y) * 50.0).exp())
+            .collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let decimation = triangulation
+            .decimate(0, ErrorMetric::Attribute(&bump))
+            .unwrap();
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), hull.len());
+        assert!(decimation.max_error > 0.0 && decimation.max_error <= 1.0);
+
+        assert!(
+            triangulation
+                .decimate(0, ErrorMetric::Attribute(&[]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_build_lod_chain() {
+        let vertices = sample_vertices_2d(300, None);
+        let bump: Vec<f64> = vertices
+            .iter()
+            .map(|[x, y]| (-(x * x + y * y) * 50.0).exp())
+            .collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let chain = triangulation
+            .build_lod_chain(&[150, 50], ErrorMetric::Attribute(&bump))
+            .unwrap();
+        assert_eq!(triangulation.num_used_vertices(), 300);
+        assert_eq!(chain.levels.len(), 3);
+        assert!(chain.levels[0].parents.is_empty() && chain.levels[0].max_error == 0.0);
+
+        for (level, pair) in (1..).zip(chain.levels.windows(2)) {
+            let [finer, coarser] = [&pair[0], &pair[1]];
+            verify_triangulation(&coarser.triangulation);
+            let num_coarser = coarser.triangulation.num_used_vertices();
+            assert_eq!(
+                num_coarser,
+                [150, 50][level - 1].max(triangulation.hull_edges().len())
+            );
+            assert_eq!(
+                finer.triangulation.num_used_vertices(),
+                num_coarser + coarser.parents.len()
+            );
+
+            // the removed vertices are reproduced by their barycentric coordinates in the coarser level
+            for parent in &coarser.parents {
+                assert_eq!(chain.removed_in_level[parent.v_idx], Some(level));
+                assert!(finer.triangulation.used_vertices().contains(&parent.v_idx));
+                let v = vertices[parent.v_idx];
+                for i in 0..2 {
+                    let interpolated: f64 = (0..3)
+                        .map(|j| parent.lambdas[j] * vertices[parent.tri[j]][i])
+                        .sum();
+                    assert!((interpolated - v[i]).abs() < 1e-9);
+                }
+                for v_idx in parent.tri {
+                    assert!(coarser.triangulation.used_vertices().contains(&v_idx));
+                }
+            }
+        }
+        assert!(chain.levels[2].max_error > 0.0);
+
+        let num_kept = chain
+            .removed_in_level
+            .iter()
+            .filter(|r| r.is_none())
+            .count();
+        assert_eq!(num_kept, chain.levels[2].triangulation.num_used_vertices());
+    }
+
+    #[test]
+    fn test_refine() {
+        let mut vertices = vec![[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]];
+        vertices.extend(sample_vertices_2d(50, Some(-0.4..=0.4)));
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let num_inserted = triangulation.refine(Some(0.01), 20.0).unwrap();
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), 54 + num_inserted);
+
+        // the square has no small corner angles, so all triangles are good
+        let qualities = triangulation.tri_qualities();
+        assert!(
+            qualities
+                .iter()
+                .all(|q| q.min_angle >= 20.0 && q.area <= 0.01)
+        );
+        let area: f64 = qualities.iter().map(|q| q.area).sum();
+        assert!((area - 1.0).abs() < 1e-9);
+
+        // a sharp corner is kept, but does not prevent refining by area
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices([[0.0, 0.0], [1.0, 0.0], [1.0, 0.1]], None, false)
+            .unwrap();
+        triangulation.refine(Some(0.001), 20.0).unwrap();
+        verify_triangulation(&triangulation);
+        assert!(
+            triangulation
+                .tri_qualities()
+                .iter()
+                .all(|q| q.area <= 0.001)
+        );
+
+        assert!(Triangulation::new(None).refine(None, 20.0).is_err());
+    }
+
+    #[test]
+    fn test_locate() {
+        let location_idx = |triangulation: &Triangulation, location: Location| match location {
+            Location::InsideTriangle(handle) | Location::OutsideHull(handle) => {
+                triangulation.resolve_tri(handle).unwrap()
+            }
+            Location::OnEdge(idx) | Location::OnVertex(idx) => idx,
+        };
+
+        let mut triangulation = Triangulation::new(None);
+        assert!(triangulation.locate([0.0, 0.0]).is_err());
+
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        for (v_idx, &v) in EXAMPLE_VERTICES.iter().enumerate() {
+            assert_eq!(triangulation.locate(v).unwrap(), Location::OnVertex(v_idx));
+        }
+
+        // the midpoint of the hull edge from the vertex 0 to 7
+        let location = triangulation.locate([1.25, -0.25]).unwrap();
+        assert!(matches!(location, Location::OnEdge(_)));
+        let hedge = triangulation
+            .tds()
+            .get_hedge(location_idx(&triangulation, location))
+            .unwrap();
+        let mut edge = [hedge.starting_node(), hedge.end_node()].map(|node| node.idx().unwrap());
+        edge.sort_unstable();
+        assert_eq!(edge, [0, 7]);
+
+        let location = triangulation.locate([1.0, 0.4]).unwrap();
+        assert!(matches!(location, Location::InsideTriangle(_)));
+        let tri = triangulation
+            .tds()
+            .get_tri(location_idx(&triangulation, location))
+            .unwrap();
+        assert!(tri.is_casual());
+
+        let location = triangulation.locate([10.0, 10.0]).unwrap();
+        assert!(matches!(location, Location::OutsideHull(_)));
+        let tri = triangulation
+            .tds()
+            .get_tri(location_idx(&triangulation, location))
+            .unwrap();
+        assert!(tri.is_conceptual());
+    }
+
+    #[test]
+    fn test_tri_handles() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+        assert!(triangulation.tri_handle(usize::MAX).is_err());
+
+        let Location::InsideTriangle(handle) = triangulation.locate([1.0, 0.4]).unwrap() else {
+            unreachable!("expected the point inside a triangle");
+        };
+        assert_eq!(handle.generation(), triangulation.generation());
+        let tri_idx = triangulation.resolve_tri(handle).unwrap();
+        assert_eq!(triangulation.tri_handle(tri_idx).unwrap(), handle);
+
+        // a handle is stale after an insertion, but still works as a hint
+        triangulation
+            .insert_vertex([1.0, 0.45], None, Some(handle))
+            .unwrap();
+        assert!(handle.generation() < triangulation.generation());
+        let error = triangulation.resolve_tri(handle).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RitaError>(),
+            Some(RitaError::StaleHandle { .. })
+        ));
+        triangulation
+            .insert_vertex([1.0, 0.35], None, Some(handle))
+            .unwrap();
+
+        // a hint to a triangle that does not exist falls back to the default walk
+        triangulation
+            .insert_vertex([1.05, 0.4], None, Some(TriHandle::new(usize::MAX, 0)))
+            .unwrap();
+        assert_eq!(
+            triangulation.num_used_vertices(),
+            EXAMPLE_VERTICES.len() + 3
+        );
+        verify_triangulation(&triangulation);
+    }
+
+    /// The triangles of a triangulation by their vertex indices, each starting at its smallest index, sorted.
+    fn sorted_tris(triangulation: &Triangulation) -> Vec<[VertexIdx; 3]> {
+        let mut tris = triangulation.tri_indices();
+        for tri in &mut tris {
+            let min = (0..3).min_by_key(|&i| tri[i]).unwrap();
+            tri.rotate_left(min);
+        }
+        tris.sort_unstable();
+        tris
+    }
+
+    /// Merge `num_chunks` partial triangulations of the vertices, as by [`Triangulation::par_insert_vertices`].
+    fn merged(
+        vertices: &[Vertex2],
+        weights: Option<Vec<f64>>,
+        num_chunks: usize,
+    ) -> (Triangulation, Vec<InsertStatus>) {
+        let mut triangulation = Triangulation::new(None);
+        triangulation.vertices = vertices.to_vec();
+        triangulation.weights = weights;
+        triangulation.update_origin(0, None);
+        let statuses = triangulation.merge_chunks(num_chunks).unwrap();
+
+        (triangulation, statuses)
+    }
+
+    #[test]
+    fn test_par_insert_vertices() {
+        let vertices = sample_vertices_2d(3000, None);
+        let mut sequential = Triangulation::new(None);
+        let expected = sequential.insert_vertices(&vertices, None, true).unwrap();
+
+        for num_chunks in [2, 3, 8] {
+            let (triangulation, statuses) = merged(&vertices, None, num_chunks);
+            verify_triangulation(&triangulation);
+            assert_eq!(sorted_tris(&triangulation), sorted_tris(&sequential));
+            assert_eq!(statuses, expected);
+
+            // the merged triangulation is built further as usual
+            let mut triangulation = triangulation;
+            triangulation.insert_vertex([0.5, 0.5], None, None).unwrap();
+            verify_triangulation(&triangulation);
+        }
+
+        // of duplicates, the first vertex at their position is kept
+        let mut with_duplicates = vertices.clone();
+        with_duplicates.extend_from_slice(&[vertices[7], vertices[11]]);
+        let (triangulation, statuses) = merged(&with_duplicates, None, 4);
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&sequential));
+        assert_eq!(statuses[3000..], [InsertStatus::Duplicate; 2]);
+        assert_eq!(triangulation.canonical_vertices()[3000..], [7, 11]);
+
+        // the cocircular vertices of a grid are left to the triangulation of the border
+        let grid: Vec<Vertex2> = (0..900)
+            .map(|i| [i % 30, i / 30].map(|x| f64::from(x) * 0.1))
+            .collect();
+        let (triangulation, _) = merged(&grid, None, 4);
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), grid.len());
+
+        // thin triangles between two flat arcs, whose power circles are the hardest to round
+        let arc = |offset: f64, sign: f64| {
+            (0..1000).map(move |i| {
+                let x = (f64::from(i) + offset) / 1000.0;
+                [x, sign * 1e-9 * x * (1.0 - x)]
+            })
+        };
+        let thin: Vec<Vertex2> = arc(0.0, 1.0).chain(arc(0.5, -1.0)).collect();
+        let (triangulation, _) = merged(&thin, None, 4);
+        let mut sequential_thin = Triangulation::new(None);
+        sequential_thin.insert_vertices(&thin, None, true).unwrap();
+        assert!(
+            triangulation
+                .check_regularity(RegularityCheck::Full, false)
+                .is_regular()
+        );
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&sequential_thin));
+
+        // few vertices are inserted sequentially
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation.par_insert_vertices(&vertices, None).unwrap();
+        assert_eq!(statuses, expected);
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&sequential));
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_par_insert_vertices_weighted() {
+        let vertices = sample_vertices_2d(2000, None);
+        let weights = sample_weights(2000, None);
+
+        let mut sequential = Triangulation::new(None);
+        let expected = sequential
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+        assert!(expected.contains(&InsertStatus::Redundant));
+
+        let (triangulation, statuses) = merged(&vertices, Some(weights), 4);
+        verify_triangulation(&triangulation);
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&sequential));
+        assert_eq!(statuses, expected);
+    }
+
+    #[test]
+    fn test_check_regularity() {
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let num_tris = triangulation.num_tris();
+
+        for check in [
+            RegularityCheck::Full,
+            RegularityCheck::EarlyExit,
+            RegularityCheck::Local,
+        ] {
+            let report = triangulation.check_regularity(check, false);
+            assert!(report.is_regular());
+            assert_eq!(report.num_checked, num_tris);
+        }
+        let check = RegularityCheck::Sampled {
+            num_samples: 20,
+            seed: 7,
+        };
+        assert_eq!(triangulation.check_regularity(check, false).num_checked, 20);
+
+        // flipping an interior edge with a convex quad breaks the regularity of its two triangles
+        let tds = triangulation.tds();
+        let hedge_idx = (0..3 * tds.num_tris())
+            .find(|&hedge_idx| {
+                let hedge = tds.get_hedge(hedge_idx).unwrap();
+                let twin = hedge.twin();
+                if ![hedge.tri(), twin.tri()].iter().all(|tri| tri.is_casual()) {
+                    return false;
+                }
+                let [a, b, c, d] = [
+                    hedge.starting_node(),
+                    hedge.end_node(),
+                    hedge.next().end_node(),
+                    twin.next().end_node(),
+                ]
+                .map(|node| triangulation.vertices()[node.idx().unwrap()]);
+                predicates::orient_2d(&c, &d, &a) * predicates::orient_2d(&c, &d, &b) < 0.0
+            })
+            .unwrap();
+        triangulation.tds.flip_2_to_2(hedge_idx).unwrap();
+
+        let full = triangulation.check_regularity(RegularityCheck::Full, false);
+        assert!(!full.is_regular());
+        assert!(full.num_violated >= 2);
+        assert_eq!(full.degree(), triangulation.par_is_regular(false));
+        let early_exit = triangulation.check_regularity(RegularityCheck::EarlyExit, false);
+        assert_eq!(early_exit.num_violated, 1);
+        let local = triangulation.check_regularity(RegularityCheck::Local, false);
+        assert_eq!(local.num_violated, 2);
+        let check = RegularityCheck::Sampled {
+            num_samples: num_tris,
+            seed: 7,
+        };
+        assert_eq!(triangulation.check_regularity(check, false), full);
+    }
+
+    #[test]
+    fn test_par_is_regular_for_point_set() {
+        let vertices = sample_vertices_2d(200, None);
+        let (inserted, others) = vertices.split_at(100);
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(inserted, None, true).unwrap();
+
+        assert_eq!(
+            triangulation
+                .par_is_regular_for_point_set(&[], None)
+                .unwrap(),
+            1.0
+        );
+
+        let (_, degree) = triangulation
+            .is_regular_for_point_set(others, None)
+            .unwrap();
+        assert!(degree < 1.0);
+        assert_eq!(
+            triangulation
+                .par_is_regular_for_point_set(others, None)
+                .unwrap(),
+            degree
+        );
+
+        assert_eq!(
+            triangulation
+                .par_is_regular_for_point_set(others, Some(vec![0.0; 3]))
+                .unwrap_err()
+                .downcast_ref::<RitaError>(),
+            Some(&RitaError::WeightsMismatch {
+                num_weights: 3,
+                num_vertices: others.len(),
+            })
+        );
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_eps_powercircle_without_epsilon() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]], None, true)
+            .unwrap();
+
+        for tri_idx in 0..triangulation.tds().num_tris() {
+            let err = if triangulation
+                .tds()
+                .get_tri(tri_idx)
+                .unwrap()
+                .is_conceptual()
+            {
+                triangulation.is_v_near_lifted_hull(3, tri_idx)
+            } else {
+                triangulation.is_v_in_eps_powercircle(3, tri_idx)
+            }
+            .unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<RitaError>(),
+                Some(&RitaError::EpsilonNotSet { v_idx: 3 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_threads() {
+        let vertices = sample_vertices_2d(300, None);
+        let mut triangulation =
+            Triangulation::with_config(TriangulationConfig::new().max_threads(2));
+        assert_eq!(triangulation.max_threads(), Some(2));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert_eq!(triangulation.par_is_regular(false), 1.0);
+
+        #[cfg(feature = "parallel")]
+        {
+            assert_eq!(
+                triangulation
+                    .thread_pool
+                    .install(Some(2), parallel::current_num_threads),
+                2
+            );
+            // the pool is built once, and shared by the rebuilt triangulation
+            let pool = Arc::clone(triangulation.thread_pool.get().unwrap());
+            triangulation.remove_vertices(&[0]).unwrap();
+            assert!(Arc::ptr_eq(&pool, triangulation.thread_pool.get().unwrap()));
+        }
+        assert_eq!(
+            parallel::ThreadPool::new().install(None, parallel::current_num_threads),
+            parallel::current_num_threads()
+        );
+
+        triangulation.set_max_threads(None);
+        assert_eq!(triangulation.max_threads(), None);
+        assert_eq!(triangulation.par_is_regular(false), 1.0);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let vertices = sample_vertices_2d(500, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices[..100], None, true)
+            .unwrap();
+
+        let snapshot = triangulation.snapshot();
+        assert!(Arc::ptr_eq(&snapshot, &triangulation.snapshot()));
+        assert_eq!(snapshot.num_tris(), triangulation.num_casual_tris());
+
+        // a reader sees consistent states, while the vertices are inserted
+        let (sender, receiver) = std::sync::mpsc::channel::<Arc<CompactTriangulation>>();
+        let reader = std::thread::spawn(move || {
+            let mut num_snapshots = 0;
+            for snapshot in receiver {
+                for tri_idx in 0..snapshot.num_tris() {
+                    let tri = snapshot.tri(tri_idx).unwrap();
+                    assert!(tri.iter().all(|&v_idx| v_idx < snapshot.vertices().len()));
+                }
+                num_snapshots += 1;
+            }
+            num_snapshots
+        });
+        for chunk in vertices[100..].chunks(100) {
+            triangulation.insert_vertices(chunk, None, true).unwrap();
+            sender.send(triangulation.snapshot()).unwrap();
+        }
+        drop(sender);
+        assert_eq!(reader.join().unwrap(), 4);
+
+        // the earlier snapshot is not affected by the insertions
+        assert_eq!(snapshot.vertices().len(), 100);
+        assert!(!Arc::ptr_eq(&snapshot, &triangulation.snapshot()));
+        assert_eq!(
+            triangulation.snapshot().num_tris(),
+            triangulation.num_casual_tris()
+        );
+    }
+
+    #[test]
+    fn test_insert_stream() {
+        // the same triangulation as inserting all vertices at once, also across chunks
+        let vertices = sample_vertices_2d(1000, None);
+        let mut expected = Triangulation::new(None);
+        expected.insert_vertices(&vertices, None, true).unwrap();
+
+        for chunk_len in [7, 100, 1 << 16] {
+            let mut triangulation = Triangulation::new(None);
+            let num_consumed = triangulation
+                .insert_stream_chunked(vertices.iter().map(|&v| (v, None)), chunk_len)
+                .unwrap();
+            assert_eq!(num_consumed, vertices.len());
+            assert_eq!(sorted_tris(&triangulation), sorted_tris(&expected));
+            assert!(triangulation.weights().is_none());
+            verify_triangulation(&triangulation);
+        }
+
+        // an existing triangulation is extended
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices[..500], None, true)
+            .unwrap();
+        let stream = vertices[500..].iter().map(|&v| (v, None));
+        assert_eq!(triangulation.insert_stream(stream).unwrap(), 500);
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&expected));
+
+        assert!(
+            Triangulation::new(None)
+                .insert_stream([([0.0, 0.0], None), ([1.0, 0.0], None)])
+                .is_err()
+        );
+        assert_eq!(Triangulation::new(None).insert_stream([]).unwrap(), 0);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_insert_stream_weighted() {
+        let vertices = sample_vertices_2d(500, None);
+        let weights = sample_weights(500, None);
+        let mut expected = Triangulation::new(None);
+        expected
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+
+        let mut triangulation = Triangulation::new(None);
+        let stream = vertices.iter().zip(&weights).map(|(&v, &w)| (v, Some(w)));
+        triangulation.insert_stream_chunked(stream, 64).unwrap();
+        assert_eq!(triangulation.weights().as_ref(), Some(&weights));
+        assert_eq!(sorted_tris(&triangulation), sorted_tris(&expected));
+        assert!(triangulation.is_sound().unwrap());
+
+        // a chunk beyond the capacity fails, the chunks before stay inserted with their weights
+        let mut triangulation = Triangulation::with_bounded_capacity(None, 100);
+        let stream = vertices.iter().zip(&weights).map(|(&v, &w)| (v, Some(w)));
+        let error = triangulation.insert_stream_chunked(stream, 64).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RitaError>(),
+            Some(RitaError::CapacityExceeded { .. })
+        ));
+        assert_eq!(triangulation.vertices().len(), 64);
+        assert_eq!(triangulation.weights().as_deref(), Some(&weights[..64]));
+        assert!(triangulation.is_sound().unwrap());
+    }
+
+    #[test]
+    fn test_bowyer_watson() {
+        // random, beyond the hull one by one, and on a grid with collinear and co-circular vertices
+        let grid: Vec<Vertex2> = (0..100)
+            .map(|i| [(i % 10) as f64, (i / 10) as f64])
+            .collect();
+        let mut along_x = sample_vertices_2d(200, None);
+        along_x.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+        for (vertices, spatial_sorting) in [
+            (sample_vertices_2d(1000, None), true),
+            (along_x, false),
+            (grid, false),
+        ] {
+            let mut triangulation_flips = Triangulation::new(None);
+            let statuses_flips = triangulation_flips
+                .insert_vertices(&vertices, None, spatial_sorting)
+                .unwrap();
+
+            let mut triangulation = Triangulation::new(None);
+            triangulation.set_insertion_strategy(InsertionStrategy::BowyerWatson);
+            let statuses = triangulation
+                .insert_vertices(&vertices, None, spatial_sorting)
+                .unwrap();
+            verify_triangulation(&triangulation);
+
+            assert_eq!(statuses, statuses_flips);
+            assert_eq!(
+                sorted_tris(&triangulation),
+                sorted_tris(&triangulation_flips)
+            );
+        }
+    }
+
+    #[test]
+    fn test_config() {
+        let vertices = sample_vertices_2d(200, None);
+        // every fourth vertex has a noisy copy at the end
+        let mut noisy = vertices.clone();
+        noisy.extend(vertices.iter().step_by(4).map(|&[x, y]| [x + 1e-9, y]));
+
+        let config = TriangulationConfig::new()
+            .insert_order(InsertOrder::Random(3))
+            .dedup(DedupPolicy::Within(1e-6))
+            .insertion_strategy(InsertionStrategy::BowyerWatson)
+            .capacity(noisy.len());
+        assert_eq!(TriangulationConfig::default(), TriangulationConfig::new());
+
+        let mut triangulation = Triangulation::with_config(config);
+        assert!(triangulation.vertices().capacity() >= noisy.len());
+        assert_eq!(triangulation.insert_order(), InsertOrder::Random(3));
+        assert_eq!(
+            triangulation.insertion_strategy(),
+            InsertionStrategy::BowyerWatson
+        );
+        let statuses = triangulation.insert(&noisy, None).unwrap();
+        verify_triangulation(&triangulation);
+
+        // either of the two close vertices is kept, depending on the order
+        let num_duplicates = statuses
+            .iter()
+            .filter(|&&status| status == InsertStatus::Duplicate)
+            .count();
+        assert_eq!(num_duplicates, noisy.len() - vertices.len());
+        assert_eq!(triangulation.num_used_vertices(), vertices.len());
+
+        // with exact deduplication the noisy copies are inserted
+        triangulation = Triangulation::with_config(config.dedup(DedupPolicy::Exact));
+        triangulation.insert(&noisy, None).unwrap();
+        assert_eq!(triangulation.num_used_vertices(), noisy.len());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_bowyer_watson_weighted() {
+        let vertices = sample_vertices_2d(1000, None);
+        let weights = sample_weights(1000, None);
+
+        let mut triangulation_flips = Triangulation::new(None);
+        triangulation_flips
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_insertion_strategy(InsertionStrategy::BowyerWatson);
+        triangulation
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+        verify_triangulation(&triangulation);
+        assert!(triangulation.num_redundant_vertices() > 0);
+        assert_eq!(
+            sorted_tris(&triangulation),
+            sorted_tris(&triangulation_flips)
+        );
+
+        // the cavity of a heavy vertex contains the light ones around it
+        let light = sample_vertices_2d(100, Some(-0.1..=0.1));
+        let mut triangulations =
+            [InsertionStrategy::Flips, InsertionStrategy::BowyerWatson].map(|insertion_strategy| {
+                let mut triangulation = Triangulation::new(None);
+                triangulation.set_insertion_strategy(insertion_strategy);
+                triangulation
+                    .insert_vertices(&light, Some(vec![0.0; 100]), true)
+                    .unwrap();
+                triangulation
+            });
+        let [_, changes] = triangulations.each_mut().map(|triangulation| {
+            triangulation
+                .insert_vertex_tracked([0.0, 0.0], Some(1.0), None)
+                .unwrap()
+        });
+        verify_triangulation(&triangulations[1]);
+        assert!(changes.destroyed.len() > changes.created.len());
+        assert_eq!(
+            sorted_tris(&triangulations[1]),
+            sorted_tris(&triangulations[0])
+        );
+
+        // inexact power circle tests still give a valid structure
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_insertion_strategy(InsertionStrategy::BowyerWatson);
+        triangulation.set_predicate_mode(PredicateMode::FastInexact { tolerance: 1e-12 });
+        triangulation
+            .insert_vertices(&vertices, Some(sample_weights(1000, None)), true)
+            .unwrap();
+        assert!(triangulation.is_sound().unwrap());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_insert_vertex_tracked() {
+        // the sorted vertices of each live triangle, by index
+        fn tri_keys(triangulation: &Triangulation) -> Vec<Option<Vec<Option<usize>>>> {
+            (0..triangulation.num_all_tris())
+                .map(|tri_idx| {
+                    let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+                    (!tri.is_deleted()).then(|| {
+                        let mut key = tri.nodes().map(|node| node.idx()).to_vec();
+                        key.sort_unstable();
+                        key
+                    })
+                })
+                .collect()
+        }
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(
+                sample_vertices_2d(100, None),
+                Some(sample_weights(100, None)),
+                true,
+            )
+            .unwrap();
+
+        // also beyond the hull, and redundant ones
+        let vertices = sample_vertices_2d(100, Some(-0.7..=0.7));
+        let weights = sample_weights(100, None);
+        for (&v, &weight) in vertices.iter().zip(&weights) {
+            let before = tri_keys(&triangulation);
+            let num_used = triangulation.num_used_vertices();
+            let changes = triangulation
+                .insert_vertex_tracked(v, Some(weight), None)
+                .unwrap();
+            let after = tri_keys(&triangulation);
+
+            if triangulation.num_used_vertices() == num_used {
+                assert_eq!(changes, SimplexChanges::default());
+            } else {
+                assert!(!changes.destroyed.is_empty() && !changes.created.is_empty());
+            }
+            for tri_idx in 0..after.len() {
+                let was_destroyed = changes.destroyed.contains(&tri_idx);
+                let is_created = changes.created.contains(&tri_idx);
+                assert_eq!(
+                    was_destroyed,
+                    before
+                        .get(tri_idx)
+                        .is_some_and(|key| key.is_some() && !after.contains(key))
+                );
+                assert_eq!(
+                    is_created,
+                    after[tri_idx].is_some() && !before.contains(&after[tri_idx])
+                );
+                if !was_destroyed && !is_created {
+                    assert_eq!(before.get(tri_idx).cloned().flatten(), after[tri_idx]);
+                }
+            }
+        }
+        verify_triangulation(&triangulation);
+        assert!(triangulation.num_redundant_vertices() > 0);
+    }
+
+    #[test]
+    fn test_soundness_report() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+        assert!(triangulation.soundness_report().is_sound());
+
+        // the twin of a hedge is its successor in the same triangle
+        let hedge_idx = (0..triangulation.tds().hedge_starting_nodes.len())
+            .find(|&idx| triangulation.tds().hedge_starting_nodes.get(idx) != VertexNode::Deleted)
+            .unwrap();
+        triangulation
+            .tds_mut()
+            .hedge_twins
+            .set(hedge_idx, hedge_idx + 1);
+
+        let report = triangulation.soundness_report();
+        assert!(!report.is_sound());
+        assert!(
+            report
+                .issues
+                .contains(&SoundnessIssue::WrongTwin(hedge_idx))
+        );
+        assert!(!triangulation.is_sound().unwrap());
+    }
+
+    #[test]
+    fn test_try_repair() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+        assert!(triangulation.try_repair().is_sound());
+
+        let num_hedges = triangulation.tds().hedge_starting_nodes.len();
+        let mut hedge_idxs = (0..num_hedges).filter(|&idx| {
+            triangulation.tds().hedge_starting_nodes.get(idx) != VertexNode::Deleted
+        });
+        let (hedge_idx0, hedge_idx1) = (hedge_idxs.next().unwrap(), hedge_idxs.next().unwrap());
+        triangulation
+            .tds_mut()
+            .hedge_twins
+            .set(hedge_idx0, hedge_idx1);
+        triangulation
+            .tds_mut()
+            .hedge_twins
+            .set(hedge_idx1, num_hedges);
+        assert!(!triangulation.soundness_report().is_sound());
+
+        assert!(triangulation.try_repair().is_sound());
+        verify_triangulation(&triangulation);
+    }
+
+    #[test]
+    fn test_describe_tri() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let tri_idx = (0..triangulation.tds().num_tris())
+            .find(|&tri_idx| triangulation.tds().get_tri(tri_idx).unwrap().is_casual())
+            .unwrap();
+        let description = triangulation.describe_tri(tri_idx).unwrap();
+        assert!(description.starts_with(&format!("Triangle {tri_idx} (casual)")));
+        assert_eq!(description.matches(" at [").count(), 3);
+        assert!(description.contains("neighbors: "));
+        assert!(!description.contains("weight"));
+
+        assert!(triangulation.describe_tri(usize::MAX / 4).is_err());
+    }
+
+    #[test]
+    fn test_insert_vertices_presorted() {
+        let vertices = sample_vertices_2d(100, Some(10.0..=20.0));
+        let order =
+            sort_along_hilbert_curve_2d(&vertices, &(0..vertices.len()).collect::<Vec<_>>());
+        let sorted: Vec<Vertex2> = order.iter().map(|&v_idx| vertices[v_idx]).collect();
+
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation
+            .insert_vertices_presorted(&sorted, None, [[10.0, 10.0], [20.0, 20.0]])
+            .unwrap();
+        verify_triangulation(&triangulation);
+        assert!(
+            statuses
+                .iter()
+                .all(|&status| status == InsertStatus::Inserted)
+        );
+        assert_eq!(triangulation.origin(), [15.0, 15.0]);
+
+        let mut reference = Triangulation::new(None);
+        reference.insert_vertices(&vertices, None, true).unwrap();
+        assert_eq!(triangulation.num_casual_tris(), reference.num_casual_tris());
+    }
+
+    #[test]
+    fn test_insert_vertices_with_progress() {
+        let vertices = sample_vertices_2d(3000, None);
+
+        let mut reports = Vec::new();
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation
+            .insert_vertices_with_progress(&vertices, None, InsertOrder::Hilbert, |progress| {
+                reports.push(progress);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        assert!(statuses.iter().all(|&s| s == InsertStatus::Inserted));
+        let num_processed: Vec<usize> = reports.iter().map(|p| p.num_processed).collect();
+        assert_eq!(num_processed, [1024, 2048, 3000]);
+        assert!(reports.iter().all(|p| p.num_total == 3000));
+        assert_eq!(reports.last().unwrap().fraction(), 1.0);
+
+        // cancelling keeps the vertices processed before, and the triangulation can be extended further
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation
+            .insert_vertices_with_progress(&vertices, None, InsertOrder::Hilbert, |_| {
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        let num_cancelled = statuses
+            .iter()
+            .filter(|&&s| s == InsertStatus::Cancelled)
+            .count();
+        assert_eq!(num_cancelled, 3000 - 1024);
+        assert_eq!(triangulation.num_used_vertices(), 1024);
+        assert!(triangulation.is_sound().unwrap());
+        verify_triangulation(&triangulation);
+
+        triangulation
+            .insert_vertices(sample_vertices_2d(100, Some(0.2..=0.8)), None, true)
+            .unwrap();
+        assert!(triangulation.is_sound().unwrap());
+    }
+
+    #[test]
+    fn test_insert_statuses() {
+        let mut vertices = EXAMPLE_VERTICES.to_vec();
+        vertices.push(EXAMPLE_VERTICES[3]);
+
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        // aligned with the input, even though the vertices are sorted
+        assert_eq!(statuses.len(), 11);
+        let mut duplicates = [statuses[3], statuses[10]];
+        duplicates.sort_by_key(|&s| s == InsertStatus::Duplicate);
+        assert_eq!(
+            duplicates,
+            [InsertStatus::Inserted, InsertStatus::Duplicate]
+        );
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|&&s| s == InsertStatus::Inserted)
+                .count(),
+            10
+        );
+        verify_triangulation(&triangulation);
+
+        // the statuses only cover the vertices of the current call
+        let statuses = triangulation
+            .insert_vertices([[1.0, 2.0], [3.5, 2.0]], None, true)
+            .unwrap();
+        assert_eq!(statuses, vec![InsertStatus::Inserted; 2]);
+    }
+
+    #[test]
+    fn test_vertex_to_triangle() {
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        triangulation.insert_vertex([0.5, 0.5], None, None).unwrap();
+
+        for v_idx in 0..triangulation.vertices().len() {
+            let tri = triangulation
+                .tds()
+                .get_tri(triangulation.vertex_to_triangle(v_idx).unwrap())
+                .unwrap();
+            assert!(tri.is_casual());
+            assert!(tri.nodes().contains(&VertexNode::Casual(v_idx)));
+        }
+        assert_eq!(triangulation.vertex_to_triangle(201), None);
+    }
+
+    #[test]
+    fn test_incident_tris() {
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        for v_idx in 0..vertices.len() {
+            let mut incident = triangulation.incident_tris(v_idx);
+            incident.sort_unstable();
+
+            let node = VertexNode::Casual(v_idx);
+            let num_tris = triangulation.tds().num_tris() + triangulation.tds().num_deleted_tris;
+            let expected: Vec<usize> = (0..num_tris)
+                .filter(|&tri_idx| {
+                    let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+                    tri.is_casual() && tri.nodes().contains(&node)
+                })
+                .collect();
+            assert_eq!(incident, expected);
+        }
+        assert!(triangulation.incident_tris(200).is_empty());
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(sample_vertices_2d(100, None), None, true)
+            .unwrap();
+
+        let mut edges: Vec<[VertexIdx; 2]> =
+            triangulation.edges().map(|(v0, v1)| [v0, v1]).collect();
+        edges.sort_unstable();
+        assert_eq!(edges, triangulation.boundary_matrices().edges);
+
+        // Euler's formula, without the outer face
+        assert_eq!(edges.len(), 100 + triangulation.num_casual_tris() - 1);
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn test_to_geojson() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        // the ring of a polygon feature, empty for other geometries
+        let ring = |feature: &geojson::Feature| -> Vec<Vertex2> {
+            match feature.geometry.as_ref().map(|geometry| &geometry.value) {
+                Some(geojson::Value::Polygon(rings)) => {
+                    rings[0].iter().map(|p| [p[0], p[1]]).collect()
+                }
+                _ => Vec::new(),
+            }
+        };
+
+        let collection = triangulation.to_geojson();
+        assert_eq!(collection.features.len(), 4);
+        for (feature, tri) in collection.features.iter().zip(triangulation.tri_indices()) {
+            let ring = ring(feature);
+            assert_eq!(ring.len(), 4);
+            assert_eq!(ring[..3], tri.map(|v_idx| vertices[v_idx]));
+            assert_eq!(ring[0], ring[3]);
+            assert_eq!(feature.property("vertices"), Some(&tri.to_vec().into()));
+        }
+
+        // the center has a diamond as its cell, the cells of the corners are clipped by the box
+        let collection = triangulation
+            .cells_to_geojson([[0.0, 0.0], [2.0, 2.0]])
+            .unwrap();
+        assert_eq!(collection.features.len(), 5);
+        let center = &collection.features[4];
+        assert_eq!(center.property("vertex"), Some(&4.into()));
+        assert!(!center.contains_property("weight"));
+        assert_eq!(ring(center).len(), 5);
+
+        // valid GeoJSON
+        let json = collection.to_string();
+        assert!(json.parse::<geojson::GeoJson>().is_ok());
+
+        assert!(
+            Triangulation::new(None)
+                .cells_to_geojson([[0.0, 0.0], [1.0, 1.0]])
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_to_graph() {
+        let vertices = sample_vertices_2d(100, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let graph = triangulation.to_graph();
+        assert_eq!(graph.node_count(), vertices.len());
+        assert_eq!(graph.edge_count(), triangulation.edges().count());
+        assert_eq!(petgraph::algo::connected_components(&graph), 1);
+
+        let [from, to] = [0, 1].map(petgraph::graph::NodeIndex::new);
+        let path_len = petgraph::algo::dijkstra(&graph, from, Some(to), |edge| *edge.weight())[&to];
+        let [a, b] = [vertices[0], vertices[1]];
+        assert!(path_len >= (a[0] - b[0]).hypot(a[1] - b[1]));
+    }
+
+    #[test]
+    fn test_nearest_vertex() {
+        let vertices = sample_vertices_2d(300, None);
+        let mut triangulation = Triangulation::new(None);
+        assert_eq!(triangulation.nearest_vertex([0.0, 0.0]), None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        // also beyond the hull
+        for query in sample_vertices_2d(100, Some(-1.0..=1.0)) {
+            let dist_sq = |v: Vertex2| (v[0] - query[0]).powi(2) + (v[1] - query[1]).powi(2);
+            let expected = vertices
+                .iter()
+                .map(|&v| dist_sq(v))
+                .fold(f64::INFINITY, f64::min);
+
+            let nearest = triangulation.nearest_vertex(query).unwrap();
+            assert_eq!(dist_sq(vertices[nearest]), expected);
+            assert_eq!(triangulation.nearest_power_vertex(query), Some(nearest));
+        }
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_nearest_power_vertex() {
+        let vertices = sample_vertices_2d(300, None);
+        let weights = sample_weights(300, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+        assert!(!triangulation.redundant_vertices().is_empty());
+
+        let inserted: Vec<VertexIdx> = (0..vertices.len())
+            .filter(|&v_idx| triangulation.vertex_to_triangle(v_idx).is_some())
+            .collect();
+        for query in sample_vertices_2d(100, Some(-1.0..=1.0)) {
+            let dist_sq = |v_idx: VertexIdx| {
+                (vertices[v_idx][0] - query[0]).powi(2) + (vertices[v_idx][1] - query[1]).powi(2)
+            };
+            let power = |v_idx: VertexIdx| dist_sq(v_idx) - weights[v_idx];
+
+            // the power cells of redundant vertices are empty
+            let expected_power = (0..vertices.len()).map(power).fold(f64::INFINITY, f64::min);
+            let nearest = triangulation.nearest_power_vertex(query).unwrap();
+            assert_eq!(power(nearest), expected_power);
+
+            let expected_dist = inserted
+                .iter()
+                .map(|&v_idx| dist_sq(v_idx))
+                .fold(f64::INFINITY, f64::min);
+            assert_eq!(
+                dist_sq(triangulation.nearest_vertex(query).unwrap()),
+                expected_dist
+            );
+        }
+    }
+
+    #[test]
+    fn test_tri_neighbors() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(sample_vertices_2d(100, None), None, true)
+            .unwrap();
+
+        let mut num_across_hull = 0;
+        for tri_idx in 0..triangulation.tds().num_tris() + triangulation.tds().num_deleted_tris {
+            let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+            if tri.is_deleted() || tri.is_conceptual() {
+                continue;
+            }
+            let neighbors = triangulation.tri_neighbors(tri_idx).unwrap();
+            for (hedge, neighbor) in tri.hedges().iter().zip(neighbors) {
+                match neighbor {
+                    Some(neighbor_idx) => {
+                        assert!(
+                            triangulation
+                                .tri_neighbors(neighbor_idx)
+                                .unwrap()
+                                .contains(&Some(tri_idx))
+                        );
+                        let nodes = triangulation.tds().get_tri(neighbor_idx).unwrap().nodes();
+                        assert!(
+                            nodes.contains(&hedge.starting_node())
+                                && nodes.contains(&hedge.end_node())
+                        );
+                    }
+                    None => num_across_hull += 1,
+                }
+            }
+        }
+
+        // one casual triangle per hull edge, which is adjacent to a conceptual triangle
+        assert_eq!(num_across_hull, triangulation.tds().num_conceptual_tris());
+        assert!(triangulation.tri_neighbors(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_tris_intersecting_segment() {
+        let check_segment = |triangulation: &Triangulation, a: Vertex2, b: Vertex2| {
+            let tris = triangulation.tris_intersecting_segment(a, b).unwrap();
+            let corners = |tri_idx: usize| {
+                let nodes = triangulation.tds().get_tri(tri_idx).unwrap().nodes();
+                nodes.map(|node| triangulation.vertices()[node.idx().unwrap()])
+            };
+            // the sides of the points to each edge, and of the corners to the segment
+            let sides = |tri_idx: usize, p: &Vertex2| {
+                let [u, v, w] = corners(tri_idx);
+                [[u, v], [v, w], [w, u]].map(|[u, v]| predicates::orient_2d(&u, &v, p))
+            };
+            let segment_sides =
+                |tri_idx: usize| corners(tri_idx).map(|c| predicates::orient_2d(&a, &b, &c));
+
+            assert!(sides(tris[0], &a).iter().all(|&side| side >= 0.0));
+            assert!(
+                sides(*tris.last().unwrap(), &b)
+                    .iter()
+                    .all(|&side| side >= 0.0)
+            );
+            for pair in tris.windows(2) {
+                assert!(
+                    triangulation
+                        .tri_neighbors(pair[0])
+                        .unwrap()
+                        .contains(&Some(pair[1]))
+                );
+            }
+
+            for tri_idx in 0..triangulation.num_all_tris() {
+                let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+                if tri.is_deleted() || tri.is_conceptual() {
+                    continue;
+                }
+                let [sides_a, sides_b] = [sides(tri_idx, &a), sides(tri_idx, &b)];
+                let segment_sides = segment_sides(tri_idx);
+
+                // every triangle the segment passes through the interior of is traversed
+                let is_crossed = (0..3).all(|i| sides_a[i] > 0.0 || sides_b[i] > 0.0)
+                    && segment_sides.iter().any(|&side| side > 0.0)
+                    && segment_sides.iter().any(|&side| side < 0.0);
+                if is_crossed {
+                    assert!(tris.contains(&tri_idx));
+                }
+
+                // and every traversed one touches the segment
+                let is_touched = (0..3).all(|i| sides_a[i] >= 0.0 || sides_b[i] >= 0.0)
+                    && segment_sides.iter().any(|&side| side >= 0.0)
+                    && segment_sides.iter().any(|&side| side <= 0.0);
+                if tris.contains(&tri_idx) {
+                    assert!(is_touched);
+                }
+            }
+        };
+
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let queries = sample_vertices_2d(40, Some(-0.3..=0.3));
+        for pair in queries.chunks(2) {
+            check_segment(&triangulation, pair[0], pair[1]);
+        }
+        check_segment(&triangulation, vertices[0], vertices[1]);
+        check_segment(&triangulation, queries[0], queries[0]);
+
+        // through vertices and along edges of a grid
+        let grid: Vec<Vertex2> = (0..25).map(|i| [(i % 5) as f64, (i / 5) as f64]).collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(&grid, None, true).unwrap();
+        for (a, b) in [
+            ([0.0, 0.0], [4.0, 4.0]),
+            ([0.0, 2.0], [4.0, 2.0]),
+            ([0.0, 0.0], [4.0, 0.0]),
+            ([4.0, 3.5], [0.5, 0.0]),
+            ([1.0, 1.0], [2.0, 4.0]),
+        ] {
+            check_segment(&triangulation, a, b);
+        }
+
+        for (a, b) in [([0.0, 0.0], [5.0, 0.0]), ([5.0, 0.0], [0.0, 0.0])] {
+            assert_eq!(
+                triangulation
+                    .tris_intersecting_segment(a, b)
+                    .unwrap_err()
+                    .downcast_ref::<RitaError>(),
+                Some(&RitaError::SegmentOutsideHull)
+            );
+        }
+        assert_eq!(
+            Triangulation::new(None)
+                .tris_intersecting_segment([0.0, 0.0], [1.0, 0.0])
+                .unwrap_err()
+                .downcast_ref::<RitaError>(),
+            Some(&RitaError::EmptyTriangulation)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        let mut vertices = EXAMPLE_VERTICES.to_vec();
+        vertices.push(EXAMPLE_VERTICES[3]);
+
+        let mut triangulation = Triangulation::new(None);
+        assert_eq!(
+            triangulation.duplicate_policy(),
+            DuplicatePolicy::SkipAndMap
+        );
+        let statuses = triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        // the duplicate maps to the one of both inserted first, all other vertices to themselves
+        let (kept, skipped) = if statuses[3] == InsertStatus::Inserted {
+            (3, 10)
+        } else {
+            (10, 3)
+        };
+        let mut expected: Vec<VertexIdx> = (0..vertices.len()).collect();
+        expected[skipped] = kept;
+        assert_eq!(triangulation.canonical_vertices(), expected);
+
+        let mut triangulation = Triangulation::with_config(
+            TriangulationConfig::new().duplicate_policy(DuplicatePolicy::Error),
+        );
+        let err = triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RitaError>(),
+            Some(&RitaError::DuplicateVertex {
+                v_idx: skipped,
+                canonical: kept
+            })
+        );
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_merge_duplicate_weights() {
+        let mut vertices = EXAMPLE_VERTICES.to_vec();
+        vertices.push(EXAMPLE_VERTICES[3]);
+        let mut weights = vec![0.0; vertices.len()];
+        weights[10] = 0.5;
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_duplicate_policy(DuplicatePolicy::MergeWeights);
+        let statuses = triangulation
+            .insert_vertices(&vertices, Some(weights), true)
+            .unwrap();
+        verify_triangulation(&triangulation);
+
+        // the kept vertex has the larger weight, whichever of both is inserted first
+        let kept = if statuses[3] == InsertStatus::Inserted {
+            3
+        } else {
+            10
+        };
+        assert_eq!(triangulation.weights.as_ref().unwrap()[kept], 0.5);
+
+        // a heavier duplicate of a vertex inserted by an earlier call fails, a lighter one is mapped
+        let err = triangulation
+            .insert_vertex(EXAMPLE_VERTICES[3], Some(1.0), None)
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<RitaError>(),
+            Some(RitaError::DuplicateVertex { .. })
+        ));
+        triangulation
+            .insert_vertex(EXAMPLE_VERTICES[3], Some(0.25), None)
+            .unwrap();
+        assert_eq!(triangulation.canonical_vertices()[12], kept);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_insert_statuses_weighted() {
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0], [0.4, 0.3]];
+        let weights = vec![0.0, 0.0, 0.0, 0.0, -1.0];
+
+        let mut triangulation = Triangulation::new(None);
+        let statuses = triangulation
+            .insert_vertices(vertices, Some(weights), false)
+            .unwrap();
+
+        assert_eq!(statuses[4], InsertStatus::Redundant);
+        assert!(statuses[..4].iter().all(|&s| s == InsertStatus::Inserted));
+    }
+
+    #[test]
+    fn test_fast_inexact() {
+        let vertices = sample_vertices_2d(1000, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_predicate_mode(PredicateMode::FastInexact { tolerance: 1e-12 });
+        assert!(!triangulation.predicate_mode().is_exact());
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        // the tolerance is far above the rounding errors, so the result is regular nonetheless
+        verify_triangulation(&triangulation);
+
+        // without a tolerance, the cocircular vertices of a grid are decided by rounding errors, the structure stays valid
+        let grid: Vec<Vertex2> = (0..400)
+            .map(|i| [i % 20, i / 20].map(|x| f64::from(x) * 0.1))
+            .collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.set_predicate_mode(PredicateMode::FastInexact { tolerance: 0.0 });
+        triangulation.insert_vertices(&grid, None, true).unwrap();
+        assert!(triangulation.is_sound().unwrap());
+        assert_eq!(triangulation.num_used_vertices(), grid.len());
+    }
+
+    #[test]
+    fn test_hull_edges() {
+        let mut triangulation = Triangulation::new(None);
+        assert!(triangulation.hull_edges().is_empty());
+
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let hull = triangulation.hull_edges();
+        assert_eq!(hull.len(), triangulation.num_hull_facets());
+
+        // every vertex is on the left of, or on, every hull edge
+        for (i, &v_idx) in hull.iter().enumerate() {
+            let a = EXAMPLE_VERTICES[v_idx];
+            let b = EXAMPLE_VERTICES[hull[(i + 1) % hull.len()]];
+            assert!(
+                EXAMPLE_VERTICES
+                    .iter()
+                    .all(|v| predicates::orient_2d(&a, &b, v) >= 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_boundary_matrices() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let complex = triangulation.boundary_matrices();
+
+        // Euler characteristic of a disk
+        assert_eq!(
+            complex.vertices.len() + complex.tris.len() - complex.edges.len(),
+            1
+        );
+        assert_eq!(complex.tris.len(), 10);
+        assert_eq!(complex.d2.num_cols, 10);
+
+        // interior edges cancel out, only the hull edges remain on the boundary of the whole disk
+        let mut edge_sums = vec![0; complex.edges.len()];
+        for &(row, _, value) in &complex.d2.entries {
+            edge_sums[row] += value;
+        }
+        assert_eq!(
+            edge_sums.iter().filter(|&&sum| sum != 0).count(),
+            triangulation.num_hull_facets()
+        );
+    }
+
+    #[test]
+    fn test_quality_histograms() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(sample_vertices_2d(100, None), None, true)
+            .unwrap();
+
+        let qualities = triangulation.tri_qualities();
+        assert_eq!(qualities.len(), triangulation.tris().len());
+        assert!(
+            qualities
+                .iter()
+                .all(|q| q.min_angle > 0.0 && q.min_angle <= 60.0)
+        );
+        assert!(qualities.iter().all(|q| q.aspect_ratio >= 1.0 - 1e-9));
+
+        let histograms = triangulation.quality_histograms(8);
+        assert_eq!(histograms.min_angle.counts.len(), 8);
+        assert_eq!(histograms.size.num_values(), qualities.len());
+        let area: f64 = qualities.iter().map(|q| q.area).sum();
+        let hull_area: f64 = triangulation.dec_measures().tri_areas.iter().sum();
+        assert!((area - hull_area).abs() < 1e-9 * area);
+    }
+
+    #[test]
+    fn test_dec_measures() {
+        let vertices = sample_vertices_2d(100, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let complex = triangulation.boundary_matrices();
+        let measures = triangulation.dec_measures();
+        assert_eq!(measures.edge_lengths.len(), complex.edges.len());
+        assert_eq!(measures.dual_edge_lengths.len(), complex.edges.len());
+        assert_eq!(measures.tri_areas.len(), complex.tris.len());
+        assert_eq!(measures.dual_areas.len(), complex.vertices.len());
+
+        // the dual cells partition the triangulated domain
+        let area: f64 = measures.tri_areas.iter().sum();
+        let dual_area: f64 = measures.dual_areas.iter().sum();
+        assert!(measures.tri_areas.iter().all(|&area| area > 0.0));
+        assert!((area - dual_area).abs() < 1e-9 * area);
+
+        // the dual edges of a Delaunay triangulation have non-negative length in the interior
+        let mut num_incident = vec![0; complex.edges.len()];
+        for &(row, _, _) in &complex.d2.entries {
+            num_incident[row] += 1;
+        }
+        for (length, num_incident) in measures.dual_edge_lengths.iter().zip(num_incident) {
+            if num_incident == 2 {
+                assert!(*length >= -1e-9);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let json = serde_json::to_string(&triangulation).unwrap();
+        let mut reloaded: Triangulation = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.tri_indices(), triangulation.tri_indices());
+
+        // the reloaded triangulation can be extended further
+        reloaded.insert_vertex([2.0, 2.0], None, None).unwrap();
+        assert_eq!(reloaded.num_casual_tris(), 12);
+        verify_triangulation(&reloaded);
+    }
+
+    #[test]
+    fn test_insert_vertices_lenient() {
+        let mut vertices = EXAMPLE_VERTICES.to_vec();
+        vertices.insert(3, [f64::NAN, 1.0]);
+        vertices.push([2.0, f64::INFINITY]);
+
+        let mut triangulation = Triangulation::new(None);
+        let issues = triangulation
+            .insert_vertices_lenient(&vertices, None, InsertOrder::Hilbert)
+            .unwrap();
+
+        assert_eq!(
+            issues.iter().map(|issue| issue.v_idx).collect::<Vec<_>>(),
+            vec![3, 11]
+        );
+        assert_eq!(triangulation.num_casual_tris(), 10);
+        assert_eq!(triangulation.num_used_vertices(), 10);
+        assert_eq!(triangulation.origin(), local_origin(&EXAMPLE_VERTICES));
+        verify_triangulation(&triangulation);
+
+        // failures that affect the whole triangulation are still errors
+        assert!(
+            Triangulation::new(None)
+                .insert_vertices_lenient(&[[0.0, 0.0], [1.0, f64::NAN]], None, InsertOrder::Input)
+                .is_err()
+        );
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_insert_vertices_lenient_failed_flip() {
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, Some(vec![0.0; 10]), true)
+            .unwrap();
+        triangulation.set_locate_grid(false);
+
+        // the vertex is inserted into the last triangle, where the walk starts, and the node of its neighbor opposite
+        // the shared edge is made the vertex itself, so the split succeeds, but both nodes of the edge are reflex
+        let tri_idx = triangulation.last_inserted_triangle.unwrap();
+        let tri = triangulation.tds().get_tri(tri_idx).unwrap();
+        let twin = tri
+            .hedges()
+            .into_iter()
+            .map(|hedge| hedge.twin())
+            .find(|twin| twin.tri().is_casual())
+            .unwrap();
+        let opposite_idx = twin.prev().idx;
+        let [a, b, c] = tri
+            .nodes()
+            .map(|node| triangulation.vertices()[node.idx().unwrap()]);
+        let centroid = core::array::from_fn(|i| (a[i] + b[i] + c[i]) / 3.0);
+        triangulation
+            .tds_mut()
+            .hedge_starting_nodes
+            .set(opposite_idx, VertexNode::Casual(10));
+
+        let issues = triangulation
+            .insert_vertices_lenient(&[centroid], Some(vec![0.0; 11]), InsertOrder::Input)
+            .unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].v_idx, 10);
+        assert!(matches!(
+            issues[0].error.downcast_ref::<RitaError>(),
+            Some(RitaError::MultipleReflexVertices { .. })
+        ));
+        assert_eq!(triangulation.statuses(10), vec![InsertStatus::Failed]);
+        assert_eq!(triangulation.num_removed_vertices(), 0);
+        assert_eq!(triangulation.num_used_vertices(), 10);
+        assert!(triangulation.is_sound().unwrap());
+        verify_triangulation(&triangulation);
+    }
+
+    #[test]
+    fn test_errors() {
+        fn reason<T: core::fmt::Debug>(result: HowResult<T>) -> RitaError {
+            *result.unwrap_err().downcast_ref::<RitaError>().unwrap()
+        }
+
+        assert_eq!(
+            reason(Triangulation::new(None).insert_vertices(&EXAMPLE_VERTICES[..2], None, true)),
+            RitaError::NotEnoughVertices { required: 3 }
+        );
+        assert_eq!(
+            reason(Triangulation::new(None).insert_vertices(
+                [[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]],
+                None,
+                true
+            )),
+            RitaError::AllPointsCollinear
+        );
+        assert_eq!(
+            reason(Triangulation::new(None).insert_vertex([0.0, 0.0], None, None)),
+            RitaError::EmptyTriangulation
+        );
+        #[cfg(not(feature = "wasm"))]
+        assert_eq!(
+            reason(Triangulation::new(None).insert_vertices(
+                &EXAMPLE_VERTICES[..4],
+                Some(vec![0.0; 2]),
+                true
+            )),
+            RitaError::WeightsMismatch {
+                num_weights: 2,
+                num_vertices: 4
+            }
+        );
 
-            let mut idxs = [a, b, d];
-            let mut tri_idxs = possible_third_tri.nodes().map(|n| n.idx().unwrap());
-            idxs.sort_unstable();
-            tri_idxs.sort_unstable();
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+        assert_eq!(
+            triangulation
+                .tds()
+                .get_tri(100)
+                .err()
+                .unwrap()
+                .downcast_ref::<RitaError>(),
+            Some(&RitaError::IndexOutOfBounds {
+                kind: "triangle",
+                idx: 100
+            })
+        );
+    }
 
-            match idxs == tri_idxs {
-                // if the possible third tri is the tri abc it fills the reflex wedge and we can flip
-                true => Some(Flip::ThreeToOne((possible_third_tri.idx, d))),
-                false => None,
-            }
-        } else {
-            panic!("No reflex point found, but we should have found one!");
+    #[cfg(feature = "exact")]
+    #[test]
+    fn test_is_regular_exact_on_grid() {
+        // a grid, where every four vertices of a cell are cocircular
+        let grid: Vec<Vertex2> = (0..64).map(|i| [(i % 8) as f64, (i / 8) as f64]).collect();
+
+        let mut num_tris = None;
+        for order in [
+            InsertOrder::Input,
+            InsertOrder::Hilbert,
+            InsertOrder::Random(7),
+            InsertOrder::Brio(7),
+        ] {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices_ordered(&grid, None, order)
+                .unwrap();
+
+            assert!(triangulation.is_regular_exact().unwrap());
+            assert_eq!(
+                *num_tris.get_or_insert(triangulation.tris().len()),
+                triangulation.tris().len()
+            );
         }
-    }
-}
+        assert_eq!(num_tris, Some(2 * 7 * 7));
 
-// Note: this is for cg lab
-impl PartialEq for Triangulation {
-    fn eq(&self, other: &Self) -> bool {
-        self.vertices == other.vertices
+        #[cfg(not(feature = "wasm"))]
+        {
+            let weights = sample_weights(64, None);
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices(&grid, Some(weights), true)
+                .unwrap();
+            assert!(triangulation.is_regular_exact().unwrap());
+        }
     }
-}
-
-impl Eq for Triangulation {}
 
-#[cfg(all(test, feature = "logging"))]
-mod pre_test {
     #[test]
-    fn logging_enabled() {
-        // When logging is enabled, this test passes; used to remind to use --features logging for full test output
-    }
-}
+    fn test_insert_vertices_or_degenerate() {
+        let collinear = [[1.0, 1.0], [0.0, 0.0], [2.0, 2.0], [1.0, 1.0]];
+        let Insertion::Degenerate(chain) = Triangulation::new(None)
+            .insert_vertices_or_degenerate(&collinear, None, InsertOrder::Hilbert)
+            .unwrap()
+        else {
+            panic!("expected collinear vertices");
+        };
+        assert_eq!(chain.v_idxs, vec![1, 0, 2]);
 
-#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
-mod tests {
-    use super::*;
-    use rita_test_utils::sample_vertices_2d;
-    #[cfg(not(feature = "wasm"))]
-    use rita_test_utils::sample_weights;
+        let Insertion::Inserted(statuses) = Triangulation::new(None)
+            .insert_vertices_or_degenerate(&EXAMPLE_VERTICES, None, InsertOrder::Hilbert)
+            .unwrap()
+        else {
+            panic!("expected a triangulation");
+        };
+        assert_eq!(statuses.len(), EXAMPLE_VERTICES.len());
 
-    fn verify_triangulation(triangulation: &Triangulation) {
-        let regularity = triangulation.par_is_regular(false);
-        let sound = triangulation.is_sound().unwrap();
-        assert_eq!(regularity, 1.0);
-        assert!(sound);
+        assert!(
+            Triangulation::new(None)
+                .insert_vertices_or_degenerate(&collinear[..2], None, InsertOrder::Hilbert)
+                .is_err()
+        );
     }
 
-    const NUM_VERTICES_LIST: [usize; 7] = [3, 5, 10, 50, 100, 500, 1000];
+    #[test]
+    fn test_reuse_deleted_tris() {
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.2, 0.2]];
+        let mut tds = TriDataStructure::new();
+        tds.add_init_tri([0, 1, 2]).unwrap();
+        let split = tds.flip_1_to_3(0, 3).unwrap().map(|tri| tri.idx);
+        assert_eq!(split, [0, 4, 5]);
+
+        // the 3->1 flip deletes two slots, that the next 1->3 flip reuses instead of appending
+        tds.flip_3_to_1(split, 3, &vertices).unwrap();
+        assert_eq!((tds.num_tris(), tds.num_deleted_tris), (4, 2));
+        let mut resplit = tds.flip_1_to_3(0, 3).unwrap().map(|tri| tri.idx);
+        resplit.sort_unstable();
+        assert_eq!(resplit, split);
+        assert_eq!((tds.num_tris(), tds.num_deleted_tris), (6, 0));
+        assert_eq!(tds.hedge_starting_nodes.len(), 18);
+        assert!(tds.is_sound());
+    }
 
-    const EXAMPLE_VERTICES: [[f64; 2]; 10] = [
-        [0.0, 0.0],
-        [-0.5, 1.0],
-        [0.0, 2.5],
-        [2.0, 3.0],
-        [4.0, 2.5],
-        [5.0, 1.5],
-        [4.5, 0.5],
-        [2.5, -0.5],
-        [1.5, 1.5],
-        [3.0, 1.0],
-    ];
-    #[cfg(not(feature = "wasm"))]
-    const EXAMPLE_WEIGHTS: [f64; 10] = [
-        0.681, 0.579, 0.5625, 0.86225, 10.0, 0.472, 0.5865, 0.59625, 0.51225, 7.0,
-    ];
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_stats() {
+        let mut triangulation = Triangulation::new(None);
+        assert_eq!(triangulation.stats(), BuildStats::default());
 
-    fn run_delaunay_2d_test() {
-        for n in NUM_VERTICES_LIST {
-            let vertices = sample_vertices_2d(n, None);
+        triangulation
+            .insert_vertices(sample_vertices_2d(100, None), None, true)
+            .unwrap();
 
-            let mut triangulation = Triangulation::new(None);
-            let result = triangulation.insert_vertices(&vertices, None, true);
+        let stats = triangulation.stats();
+        // each insertion starts with the three hedges of the containing triangle
+        assert!(stats.peak_hedge_stack >= 3);
+        assert_eq!(stats.peak_bw_tets_to_check, 0);
 
-            assert!(
-                result.is_ok(),
-                "insert_vertices failed: {}",
-                result.unwrap_err()
-            );
+        // all but the vertices of the initial triangle are inserted by a 1->3 flip, each adding two triangles
+        assert_eq!(stats.num_flips_1_3, 97);
+        assert!(stats.num_flips_2_2 > 0);
+        assert_eq!((stats.num_flips_3_1, stats.num_bw_cavities), (0, 0));
+        assert_eq!(
+            stats.num_simplices_created - stats.num_simplices_deleted,
+            2 * 97
+        );
+        assert!(stats.num_walks > 0 && stats.num_walks <= 97);
+        assert!(stats.mean_walk_steps() <= stats.peak_walk_steps as f64);
+        #[cfg(feature = "timing")]
+        assert!(stats.time_walking + stats.time_inserting + stats.time_flipping > 0);
 
-            verify_triangulation(&triangulation);
+        // random vertices are well separated, while the cocircular vertices of a grid need the exact predicates
+        #[cfg(feature = "geogram")]
+        {
+            assert!(stats.num_filtered_predicates > 0);
+            let grid: Vec<Vertex2> = (0..64).map(|i| [(i % 8) as f64, (i / 8) as f64]).collect();
+            let mut triangulation = Triangulation::new(None);
+            triangulation.insert_vertices(&grid, None, true).unwrap();
+            assert!(triangulation.stats().num_filter_failures > 0);
+            assert!(triangulation.stats().filter_failure_rate() > 0.0);
         }
     }
 
+    #[cfg(all(feature = "metrics", feature = "geogram"))]
     #[test]
-    fn test_get_tris() {
-        // Test unweighted case (runs with both geogram and wasm/robust)
+    fn test_filter_counts_concurrent() {
+        let random = sample_vertices_2d(500, None);
+        let grid: Vec<Vertex2> = (0..400)
+            .map(|i| [(i % 20) as f64, (i / 20) as f64])
+            .collect();
+
+        let filter_counts = |vertices: &[Vertex2]| {
+            let mut triangulation = Triangulation::new(None);
+            triangulation.insert_vertices(vertices, None, true).unwrap();
+            let stats = triangulation.stats();
+            (stats.num_filtered_predicates, stats.num_filter_failures)
+        };
+        let solo = [filter_counts(&random), filter_counts(&grid)];
+        assert_eq!(solo[0].1, 0);
+        assert!(solo[1].1 > 0);
+
+        // the failures of the grid do not leak into the stats of the random vertices built at the same time
+        std::thread::scope(|scope| {
+            let builds = [&random, &grid].map(|vertices| {
+                scope.spawn(|| (0..10).map(|_| filter_counts(vertices)).collect::<Vec<_>>())
+            });
+            for (build, solo) in builds.into_iter().zip(solo) {
+                assert!(
+                    build
+                        .join()
+                        .unwrap()
+                        .into_iter()
+                        .all(|counts| counts == solo)
+                );
+            }
+        });
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_walk_stats() {
+        let vertices = sample_vertices_2d(200, None);
+
         let mut triangulation = Triangulation::new(None);
+        assert_eq!(triangulation.walk_stats().num_walks(), 0);
         triangulation
-            .insert_vertices(&EXAMPLE_VERTICES, None, true)
+            .insert_vertices(&vertices, None, true)
             .unwrap();
 
-        let tris = triangulation.tris();
-        let num_tris = tris.len();
+        let stats = triangulation.walk_stats();
+        assert!(stats.num_walks() > 0 && stats.num_walks() <= 197);
+        assert!(stats.mean_len() <= stats.max_len as f64);
+        // the fallbacks and the bisector cases are specific to 3D and weighted triangulations
+        assert_eq!(
+            (
+                stats.num_capped,
+                stats.num_fallbacks,
+                stats.num_bisector_cases
+            ),
+            (0, 0, 0)
+        );
 
-        assert!(tris.len() == 10, "Expected 10 triangles, got {num_tris}");
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, Some(sample_weights(200, None)), true)
+            .unwrap();
+        let stats = triangulation.walk_stats();
+        assert!(stats.num_bisector_cases <= stats.num_walks());
+    }
 
-        // Test weighted case (geogram only; wasm rejects weights)
-        #[cfg(not(feature = "wasm"))]
-        {
-            let mut triangulation = Triangulation::new(None);
-            triangulation
-                .insert_vertices(&EXAMPLE_VERTICES, Some(EXAMPLE_WEIGHTS.to_vec()), true)
-                .unwrap();
+    #[test]
+    fn test_locate_grid() {
+        let vertices = sample_vertices_2d(2000, None);
 
-            let tris = triangulation.tris();
-            let num_tris = tris.len();
+        let mut plain = Triangulation::new(None);
+        plain
+            .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+            .unwrap();
+        let mut gridded = Triangulation::with_config(TriangulationConfig::new().locate_grid(true));
+        assert!(gridded.uses_locate_grid());
+        gridded
+            .insert_vertices_ordered(&vertices, None, InsertOrder::Random(7))
+            .unwrap();
 
-            assert!(tris.len() == 8, "Expected 8 triangles, got {num_tris}");
+        // the same triangulation, with shorter walks in a random order
+        assert_eq!(sorted_tris(&gridded), sorted_tris(&plain));
+        assert!(gridded.walk_stats().mean_len() < plain.walk_stats().mean_len());
+        for v in &vertices[..100] {
+            assert!(matches!(gridded.locate(*v).unwrap(), Location::OnVertex(_)));
+        }
+
+        gridded.set_locate_grid(false);
+        assert!(!gridded.uses_locate_grid());
+        gridded.insert_vertex([0.5, 0.5], None, None).unwrap();
+        assert!(gridded.is_sound().unwrap());
+    }
+
+    #[test]
+    fn test_emit_into() {
+        #[derive(Default)]
+        struct Collector {
+            vertices: Vec<Vertex2>,
+            tris: Vec<[usize; 3]>,
+        }
+
+        impl MeshSink for Collector {
+            fn add_vertex(&mut self, position: Vertex2) {
+                self.vertices.push(position);
+            }
+
+            fn add_triangle(&mut self, tri: [usize; 3]) {
+                self.tris.push(tri);
+            }
         }
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(EXAMPLE_VERTICES, None, true)
+            .unwrap();
+
+        let mut collector = Collector::default();
+        triangulation.emit_into(&mut collector);
+
+        assert_eq!(collector.vertices.len(), EXAMPLE_VERTICES.len());
+        assert_eq!(
+            collector
+                .tris
+                .iter()
+                .map(|tri| tri.map(|v_idx| collector.vertices[v_idx]))
+                .collect::<Vec<_>>(),
+            triangulation.tris()
+        );
     }
 
     #[test]
@@ -1375,6 +6536,41 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_weighted_insert_order() {
+        let vertices = sample_vertices_2d(1000, None);
+        let weights = sample_weights(1000, Some((0.0, 0.02)));
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices_ordered(&vertices, Some(weights), InsertOrder::WeightedHilbert)
+            .unwrap();
+
+        verify_triangulation(&triangulation);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_weighted_locate_outside_hull() {
+        // a spiral, inserted from the inside out, such that every vertex is located outside the current hull
+        let mut vertices: Vec<Vertex2> = (0..200)
+            .map(|i| {
+                let (r, angle) = (0.1 + 0.01 * i as f64, 2.4 * i as f64);
+                [r * angle.cos(), r * angle.sin()]
+            })
+            .collect();
+        vertices.reverse();
+        let weights = sample_weights(200, None);
+
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, Some(weights), false)
+            .unwrap();
+
+        verify_triangulation(&triangulation);
+    }
+
     /// Epsilon power circle is not supported in wasm (robust predicates are unweighted).
     #[cfg(not(feature = "wasm"))]
     #[test]
@@ -1402,6 +6598,206 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_eps_beyond_hull() {
+        let mut triangulation = Triangulation::new(Some(1e-3));
+        triangulation
+            .insert_vertices(
+                [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+                None,
+                false,
+            )
+            .unwrap();
+
+        // close to hull vertices, also in the lifted space
+        for v in [[-5e-4, -2e-4], [1.0 + 5e-4, 1.0 + 5e-4]] {
+            triangulation.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(triangulation.ignored_vertices(), &[4, 5]);
+
+        // the lifted vertex is far below the lifted hull edge, like inside the hull next to the edge
+        for v in [[0.5, -1e-4], [0.25, 1e-4], [2.0, 2.0]] {
+            triangulation.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(triangulation.num_ignored_vertices(), 2);
+        assert_eq!(triangulation.num_used_vertices(), 7);
+        verify_triangulation(&triangulation);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_eps_removal_in_flips() {
+        // the center is far below the large triangle, but only 0.01 below the small one around it
+        let vertices = [
+            [-10.0, -10.0],
+            [10.0, -10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+            [0.1, 0.0],
+            [-0.05, 0.0866],
+            [-0.05, -0.0866],
+        ];
+
+        let mut triangulation = Triangulation::new(Some(0.05));
+        triangulation.set_eps_in_flips(true);
+        triangulation
+            .insert_vertices(&vertices[..4], None, false)
+            .unwrap();
+        assert_eq!(triangulation.num_used_vertices(), 4);
+
+        // once it has degree 3 inside the small triangle, it is skipped
+        for &v in &vertices[4..] {
+            triangulation.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(triangulation.ignored_vertices(), &[3]);
+        assert_eq!(triangulation.num_used_vertices(), 6);
+        assert!(
+            triangulation
+                .tri_indices()
+                .iter()
+                .all(|tri| !tri.contains(&3))
+        );
+        verify_triangulation(&triangulation);
+
+        // only checked at its insertion, it is kept
+        let mut triangulation = Triangulation::new(Some(0.05));
+        triangulation
+            .insert_vertices(&vertices[..4], None, false)
+            .unwrap();
+        for &v in &vertices[4..] {
+            triangulation.insert_vertex(v, None, None).unwrap();
+        }
+        assert_eq!(triangulation.num_ignored_vertices(), 0);
+
+        // more vertices are skipped for random inputs
+        let vertices = sample_vertices_2d(1000, None);
+        let mut triangulation = Triangulation::new(Some(1e-3));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let num_ignored = triangulation.num_ignored_vertices();
+
+        let mut triangulation = Triangulation::new(Some(1e-3));
+        triangulation.set_eps_in_flips(true);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert!(triangulation.num_ignored_vertices() > num_ignored);
+        assert_eq!(
+            triangulation.num_used_vertices() + triangulation.num_ignored_vertices(),
+            vertices.len()
+        );
+        verify_triangulation(&triangulation);
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_skipped_vertices() {
+        let vertices = sample_vertices_2d(1000, None);
+
+        let mut triangulation = Triangulation::new(Some(1e-3));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        assert!(triangulation.redundant_vertices().is_empty());
+        assert!(!triangulation.ignored_vertices().is_empty());
+
+        // the used and ignored vertices partition the input
+        let mut v_idxs = [
+            triangulation.used_vertices().as_slice(),
+            triangulation.ignored_vertices(),
+        ]
+        .concat();
+        v_idxs.sort_unstable();
+        assert_eq!(v_idxs, (0..vertices.len()).collect::<Vec<_>>());
+
+        // re-inserting the ignored vertices with a smaller epsilon
+        let used = triangulation
+            .used_vertices()
+            .iter()
+            .map(|&v_idx| vertices[v_idx])
+            .collect::<Vec<_>>();
+        let ignored = triangulation
+            .ignored_vertices()
+            .iter()
+            .map(|&v_idx| vertices[v_idx])
+            .collect::<Vec<_>>();
+        let mut triangulation = Triangulation::new(Some(1e-9));
+        triangulation.insert_vertices(&used, None, true).unwrap();
+        triangulation.insert_vertices(&ignored, None, true).unwrap();
+        verify_triangulation(&triangulation);
+        assert_eq!(triangulation.num_used_vertices(), vertices.len());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_refine_epsilon() {
+        let n = 1000;
+        let vertices = sample_vertices_2d(n, None);
+
+        let mut triangulation = Triangulation::new(Some(1e-2));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let mut num_used = triangulation.num_used_vertices();
+        assert!(triangulation.num_ignored_vertices() > 0);
+
+        // each level is a superset of the coarser one
+        for new_epsilon in [Some(1e-3), Some(1e-4), None] {
+            let used = triangulation.used_vertices().clone();
+            let num_reinserted = triangulation.refine_epsilon(new_epsilon).unwrap();
+            verify_triangulation(&triangulation);
+
+            assert_eq!(triangulation.num_used_vertices(), num_used + num_reinserted);
+            assert_eq!(
+                triangulation.num_used_vertices() + triangulation.num_ignored_vertices(),
+                n
+            );
+            assert!(
+                used.iter()
+                    .all(|v_idx| triangulation.used_vertices().contains(v_idx))
+            );
+            num_used = triangulation.num_used_vertices();
+        }
+        assert_eq!(num_used, n);
+
+        // without epsilon, the unique delaunay triangulation is reached
+        let mut triangulation_all = Triangulation::new(None);
+        triangulation_all
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let mut tris = triangulation.tri_indices();
+        let mut tris_all = triangulation_all.tri_indices();
+        for tris in [&mut tris, &mut tris_all] {
+            for tri in tris.iter_mut() {
+                let min = (0..3).min_by_key(|&i| tri[i]).unwrap();
+                tri.rotate_left(min);
+            }
+            tris.sort_unstable();
+        }
+        assert_eq!(tris, tris_all);
+
+        assert!(Triangulation::new(None).refine_epsilon(None).is_err());
+
+        // a failing re-insertion keeps the vertices not processed ignored
+        let mut triangulation = Triangulation::new(Some(1e-2));
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let num_ignored = triangulation.num_ignored_vertices();
+        let v_idx = triangulation.ignored_vertices()[0];
+        triangulation.vertices[v_idx] = vertices[triangulation.used_vertices()[0]];
+        triangulation.set_duplicate_policy(DuplicatePolicy::Error);
+        assert!(triangulation.refine_epsilon(None).is_err());
+        assert!(triangulation.ignored_vertices().contains(&v_idx));
+        assert_eq!(
+            triangulation.num_used_vertices() + triangulation.num_ignored_vertices(),
+            n
+        );
+        assert!(triangulation.num_ignored_vertices() <= num_ignored);
+    }
+
     #[cfg(not(feature = "wasm"))]
     #[test]
     fn test_eps_weighted_delaunay_2d() {
@@ -1470,17 +6866,17 @@ mod tests {
             triangulation!(vertices).tris(),
             vec![
                 [[6.0, -3.46], [3.5, 6.1], [-49.31, 2.4]],
-                [[4.7, 91.5], [4.9, 31.9], [44.2, -0.05]],
-                [[3.5, 6.1], [7.7, 9.1], [4.9, 31.9]],
+                [[-49.31, 2.4], [-3.7, -40.3], [6.0, -3.46]],
                 [[3.5, 6.1], [6.0, -3.46], [6.7, 3.6]],
                 [[-3.7, -40.3], [98.5, -6.9], [44.2, -0.05]],
                 [[3.5, 6.1], [6.7, 3.6], [7.7, 9.1]],
                 [[44.2, -0.05], [6.0, -3.46], [-3.7, -40.3]],
-                [[-49.31, 2.4], [-3.7, -40.3], [6.0, -3.46]],
+                [[3.5, 6.1], [7.7, 9.1], [4.9, 31.9]],
+                [[44.2, -0.05], [98.5, -6.9], [4.7, 91.5]],
                 [[-49.31, 2.4], [3.5, 6.1], [4.9, 31.9]],
                 [[4.9, 31.9], [7.7, 9.1], [44.2, -0.05]],
                 [[4.9, 31.9], [4.7, 91.5], [-49.31, 2.4]],
-                [[44.2, -0.05], [98.5, -6.9], [4.7, 91.5]],
+                [[4.7, 91.5], [4.9, 31.9], [44.2, -0.05]],
                 [[7.7, 9.1], [6.7, 3.6], [44.2, -0.05]],
                 [[44.2, -0.05], [6.7, 3.6], [6.0, -3.46]]
             ]
@@ -1533,14 +6929,14 @@ mod tests {
                     [0.37042241707160173, 0.18423333136526698]
                 ],
                 [
-                    [0.37042241707160173, 0.18423333136526698],
                     [0.36490258549176935, 0.1365021615193457],
-                    [0.44217013845102393, -0.055915696282054284]
+                    [0.24723377358550735, 0.2100464123915723],
+                    [0.3504827256051506, -0.19027659995331642]
                 ],
                 [
+                    [0.37042241707160173, 0.18423333136526698],
                     [0.36490258549176935, 0.1365021615193457],
-                    [0.24723377358550735, 0.2100464123915723],
-                    [0.3504827256051506, -0.19027659995331642]
+                    [0.44217013845102393, -0.055915696282054284]
                 ],
                 [
                     [0.44217013845102393, -0.055915696282054284],
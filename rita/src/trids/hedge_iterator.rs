@@ -1,10 +1,9 @@
+use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::fmt;
-#[cfg(feature = "logging")]
-use log::error;
 
 use super::{tri_data_structure::TriDataStructure, tri_iterator::TriIterator};
-use crate::{VertexNode, utils::types::HedgeIteratorIdx};
+use crate::{VertexNode, audit::SoundnessIssue, utils::types::HedgeIteratorIdx};
 
 /// An iterator over the half-edges of a triangulation data structure.
 #[derive(Clone)]
@@ -21,7 +20,7 @@ impl<'a> HedgeIterator<'a> {
 
     /// Retrieve the node this hedge originates from.
     pub fn starting_node(&self) -> VertexNode {
-        self.tds.hedge_starting_nodes[self.idx]
+        self.tds.hedge_starting_nodes.get(self.idx)
     }
 
     /// Check if the hedge is conceptual, i.e. one of the nodes is the infinite node
@@ -31,35 +30,36 @@ impl<'a> HedgeIterator<'a> {
 
     /// Check if the hedge is sound, i.e. `next`, `prev` and `twin` are pointing to correct nodes.
     pub fn is_sound(&self) -> bool {
-        let mut sound = true;
+        let mut issues = Vec::new();
+        self.check_soundness(&mut issues);
+        issues.is_empty()
+    }
 
+    /// Collect the broken links of this hedge, see [`Self::is_sound`].
+    pub(crate) fn check_soundness(&self, issues: &mut Vec<SoundnessIssue>) {
         let starting_node = self.starting_node();
         let end_node = self.end_node();
 
-        #[allow(unused)]
-        let mut check = |condition: bool, error_msg: &str| {
-            if !condition {
-                #[cfg(feature = "logging")]
-                error!("{self}: {error_msg}");
-                sound = false;
-            }
-        };
-
-        check(self.next().starting_node() == end_node, "Wrong next hedge");
-        check(self.prev().end_node() == starting_node, "Wrong prev hedge");
-        check(
-            self.twin().starting_node() == end_node && self.twin().end_node() == starting_node,
-            "Wrong twin hedge",
-        );
-
-        sound
+        if self.next().starting_node() != end_node {
+            issues.push(SoundnessIssue::WrongNext(self.idx));
+        }
+        if self.prev().end_node() != starting_node {
+            issues.push(SoundnessIssue::WrongPrev(self.idx));
+        }
+        // a stale link may point beyond the hedges, e.g. after an interrupted flip
+        if self.tds.hedge_twins.get(self.idx) >= self.tds.hedge_starting_nodes.len()
+            || self.twin().starting_node() != end_node
+            || self.twin().end_node() != starting_node
+        {
+            issues.push(SoundnessIssue::WrongTwin(self.idx));
+        }
     }
 
     /// Retrieve the node this hedge ends at.
     pub fn end_node(&self) -> VertexNode {
         match (self.idx % 3).cmp(&2) {
-            Ordering::Equal => self.tds.hedge_starting_nodes[self.idx - 2],
-            Ordering::Greater | Ordering::Less => self.tds.hedge_starting_nodes[self.idx + 1], // TODO: can this be greater, x % 3 is always 0, 1 or 2
+            Ordering::Equal => self.tds.hedge_starting_nodes.get(self.idx - 2),
+            Ordering::Greater | Ordering::Less => self.tds.hedge_starting_nodes.get(self.idx + 1), // TODO: can this be greater, x % 3 is always 0, 1 or 2
         }
     }
 
@@ -77,7 +77,7 @@ impl<'a> HedgeIterator<'a> {
     ///
     /// i.e. `self.starting_node() == self.twin().end_node()` and the other way around.
     pub fn twin(&self) -> HedgeIterator<'a> {
-        Self::new(self.tds, self.tds.hedge_twins[self.idx])
+        Self::new(self.tds, self.tds.hedge_twins.get(self.idx))
     }
 
     /// Retrieve the `previous` half-edge belonging to the same triangle.
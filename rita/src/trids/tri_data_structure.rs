@@ -1,8 +1,21 @@
 use super::{hedge_iterator::HedgeIterator, tri_iterator::TriIterator};
-use crate::{VertexNode, utils::types::HedgeIteratorIdx};
+use crate::{
+    RitaError, VertexNode,
+    audit::SoundnessReport,
+    utils::{
+        bounded::push_bounded,
+        compact::{IdxVec, NodeVec},
+        types::{HedgeIteratorIdx, VertexIdx},
+    },
+};
 
 use crate::predicates;
-use alloc::vec::Vec;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{self, SnapshotKind};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use anyhow::{Ok as HowOk, Result as HowResult};
 
 const INACTIVE: usize = usize::MAX;
@@ -27,15 +40,55 @@ const INACTIVE: usize = usize::MAX;
 // `hedge1 = next(he3)`
 #[derive(Debug)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TriDataStructure {
     /// The first node is stored, the last can be obtained via `% 3`
-    pub(crate) hedge_starting_nodes: Vec<VertexNode>,
-    pub(crate) hedge_twins: Vec<HedgeIteratorIdx>,
+    pub(crate) hedge_starting_nodes: NodeVec,
+    pub(crate) hedge_twins: IdxVec,
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub num_tris: usize,
     /// The number of deleted triangles.
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub num_deleted_tris: usize, // we also need to track the number of deleted to index into the existing one correctly (otherwise we would have to shift all indices, which is tedious)
+    /// The number of triangles connected to the conceptual node, maintained on every mutation.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    num_conceptual_tris: usize,
+    /// A triangle incident to each vertex, set whenever a triangle with the vertex is written.
+    ///
+    /// Stale for vertices that are not part of the triangulation anymore, see [`Self::vertex_tri`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    vertex_tris: Vec<usize>,
+    /// The slots of deleted triangles, reused by [`Self::add_tri`] before new ones are appended.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    free_tris: Vec<usize>,
+
+    // scratch buffers of the Bowyer Watson insertion, reused such that inserting a vertex does not allocate
+    /// The triangles to check, whether they are part of the cavity.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_tris_to_check: Vec<usize>,
+    /// The triangles of the cavity, i.e. the ones to remove.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_tris_to_del: Vec<usize>,
+    /// The triangles next to the cavity, that are kept.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_tris_to_keep: Vec<usize>,
+    /// The triangles created by the last insertion, one per boundary edge of the cavity.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_added_tris: Vec<usize>,
+    /// The boundary edges of the cavity, along with their twin outside of it.
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bw_boundary: Vec<(VertexNode, VertexNode, HedgeIteratorIdx)>,
+    /// Whether the scratch buffers are allocated once, see [`Self::with_bounded_capacity`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bounded: bool,
 }
 
 impl Default for TriDataStructure {
@@ -47,23 +100,119 @@ impl Default for TriDataStructure {
 impl TriDataStructure {
     pub const fn new() -> Self {
         Self {
-            hedge_starting_nodes: Vec::new(),
-            hedge_twins: Vec::new(),
+            hedge_starting_nodes: NodeVec::new(),
+            hedge_twins: IdxVec::new(),
+            num_tris: 0,
+            num_deleted_tris: 0,
+            num_conceptual_tris: 0,
+            vertex_tris: Vec::new(),
+            free_tris: Vec::new(),
+            bw_tris_to_check: Vec::new(),
+            bw_tris_to_del: Vec::new(),
+            bw_tris_to_keep: Vec::new(),
+            bw_added_tris: Vec::new(),
+            bw_boundary: Vec::new(),
+            bounded: false,
+        }
+    }
+
+    /// Encode the data structure as a compact binary snapshot, e.g. to checkpoint a long build.
+    ///
+    /// The snapshot can only be loaded by a build of rita with the same version and features, see [`load_snapshot`](Self::load_snapshot).
+    ///
+    /// ## Errors
+    /// Returns an error if the encoding fails.
+    #[cfg(feature = "snapshot")]
+    pub fn save_snapshot(&self) -> HowResult<Vec<u8>> {
+        snapshot::save(SnapshotKind::TriDataStructure, self)
+    }
+
+    /// Decode a data structure of triangles from a snapshot of [`save_snapshot`](Self::save_snapshot).
+    ///
+    /// The decoded structure is not validated, a snapshot of a sound structure stays sound.
+    ///
+    /// ## Errors
+    /// Returns an error if the bytes are not a snapshot of a data structure of triangles, or it can not be decoded.
+    #[cfg(feature = "snapshot")]
+    pub fn load_snapshot(bytes: &[u8]) -> HowResult<Self> {
+        snapshot::load(SnapshotKind::TriDataStructure, bytes)
+    }
+
+    /// Create a new data structure with pre-allocated space for `num_tris` triangles.
+    pub fn with_capacity(num_tris: usize) -> Self {
+        Self {
+            hedge_starting_nodes: NodeVec::with_capacity(3 * num_tris),
+            hedge_twins: IdxVec::with_capacity(3 * num_tris),
             num_tris: 0,
             num_deleted_tris: 0,
+            num_conceptual_tris: 0,
+            // each vertex creates at most two triangles
+            vertex_tris: Vec::with_capacity(num_tris / 2 + 1),
+            free_tris: Vec::new(),
+            bw_tris_to_check: Vec::new(),
+            bw_tris_to_del: Vec::new(),
+            bw_tris_to_keep: Vec::new(),
+            bw_added_tris: Vec::new(),
+            bw_boundary: Vec::new(),
+            bounded: false,
         }
     }
 
+    /// Create a new data structure for at most `num_tris` triangles, whose buffers never grow.
+    ///
+    /// Next to the triangles, the scratch buffers of the insertions are allocated once for `num_tris` triangles.
+    /// Pushing onto a full scratch buffer fails with [`RitaError::BufferFull`] instead of re-allocating.
+    pub fn with_bounded_capacity(num_tris: usize) -> Self {
+        Self {
+            free_tris: Vec::with_capacity(num_tris),
+            // each removed triangle adds its three neighbors
+            bw_tris_to_check: Vec::with_capacity(3 * num_tris),
+            bw_tris_to_del: Vec::with_capacity(num_tris),
+            bw_tris_to_keep: Vec::with_capacity(num_tris),
+            bw_added_tris: Vec::with_capacity(num_tris),
+            bw_boundary: Vec::with_capacity(num_tris),
+            bounded: true,
+            ..Self::with_capacity(num_tris)
+        }
+    }
+
+    /// The capacities of the free slots and the scratch buffers of Bowyer Watson, to check that they do not grow.
+    #[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+    pub(crate) fn scratch_capacities(&self) -> [usize; 6] {
+        [
+            self.free_tris.capacity(),
+            self.bw_tris_to_check.capacity(),
+            self.bw_tris_to_del.capacity(),
+            self.bw_tris_to_keep.capacity(),
+            self.bw_added_tris.capacity(),
+            self.bw_boundary.capacity(),
+        ]
+    }
+
     /// Add a triangle to the triangulation and retrieve the hedge indices.
+    ///
+    /// The slot of a deleted triangle is reused if there is one, otherwise the triangle is appended.
+    /// The twins of the new hedges are inactive, until they are set by the caller.
     pub fn add_tri(
         &mut self,
         vertex_nodes: [VertexNode; 3],
     ) -> (HedgeIteratorIdx, HedgeIteratorIdx, HedgeIteratorIdx) {
-        let hedge_idx0 = self.hedge_starting_nodes.len();
-
-        self.hedge_starting_nodes.extend_from_slice(&vertex_nodes); // Add the three nodes to the node list
+        let hedge_idx0 = if let Some(tri_idx) = self.free_tris.pop() {
+            self.hedge_starting_nodes
+                .set_all(3 * tri_idx, &vertex_nodes);
+            self.num_deleted_tris -= 1;
+            3 * tri_idx
+        } else {
+            self.hedge_starting_nodes.extend(vertex_nodes);
+            self.hedge_twins.extend([INACTIVE; 3]);
+            self.hedge_starting_nodes.len() - 3
+        };
+        self.set_vertex_tris(&vertex_nodes, hedge_idx0 / 3);
 
         self.num_tris += 1;
+        if vertex_nodes.iter().any(VertexNode::is_conceptual) {
+            self.num_conceptual_tris += 1;
+        }
 
         (hedge_idx0, hedge_idx0 + 1, hedge_idx0 + 2)
     }
@@ -71,9 +220,7 @@ impl TriDataStructure {
     /// Insert an initial triangle into the triangulation.
     pub fn add_init_tri(&mut self, v_idxs: [usize; 3]) -> HowResult<[TriIterator<'_>; 4]> {
         if self.num_tris() > 0 {
-            return Err(anyhow::Error::msg(
-                "Triangulation already contains triangles!",
-            ));
+            return Err(anyhow::Error::new(RitaError::AlreadyInitialized));
         }
         // Create nodes for the first triangle and an infinity node; also save the first triangles index
         let a = VertexNode::Casual(v_idxs[0]);
@@ -89,30 +236,91 @@ impl TriDataStructure {
         let (hedge2i, hedgei0, hedge02) = self.add_tri([c, n_inf, a]);
         let (hedge10, hedge0i, hedgei1) = self.add_tri([b, a, n_inf]);
 
-        // Add all halfe edges to the opposite list
+        // Link all half edges to their opposite
         // E.g. the opposite of hedge01 is hedge10, i.e the reverse direction of creation above
-        self.hedge_twins.push(hedge10);
-        self.hedge_twins.push(hedge21);
-        self.hedge_twins.push(hedge02);
-        self.hedge_twins.push(hedge2i);
-        self.hedge_twins.push(hedge12);
-        self.hedge_twins.push(hedgei1);
-        self.hedge_twins.push(hedgei2);
-        self.hedge_twins.push(hedge0i);
-        self.hedge_twins.push(hedge20);
-        self.hedge_twins.push(hedge01);
-        self.hedge_twins.push(hedgei0);
-        self.hedge_twins.push(hedge1i);
+        self.set_twins(hedge01, hedge10);
+        self.set_twins(hedge12, hedge21);
+        self.set_twins(hedge20, hedge02);
+        self.set_twins(hedgei2, hedge2i);
+        self.set_twins(hedge1i, hedgei1);
+        self.set_twins(hedgei0, hedge0i);
 
         // Return the four new triangle iterators
         HowOk([
-            TriIterator::new(self, 0),
-            TriIterator::new(self, 1),
-            TriIterator::new(self, 2),
-            TriIterator::new(self, 3),
+            TriIterator::new(self, hedge01 / 3),
+            TriIterator::new(self, hedgei2 / 3),
+            TriIterator::new(self, hedge2i / 3),
+            TriIterator::new(self, hedge10 / 3),
         ])
     }
 
+    /// Create a data structure from the casual triangles of a triangulation of a convex region, each counter-clockwise.
+    ///
+    /// The hull edges are closed by conceptual triangles, like by the insertion.
+    ///
+    /// ## Errors
+    /// Returns an error if the triangles do not triangulate a disk, e.g. if an edge is in more than two of them,
+    /// or there is a hole.
+    pub(crate) fn from_tris(tris: &[[VertexIdx; 3]]) -> HowResult<Self> {
+        let invalid =
+            || anyhow::Error::new(RitaError::InvalidState("triangles do not form a disk"));
+        let key = |node: VertexNode| node.idx().unwrap_or(usize::MAX);
+
+        let mut tds = Self::with_capacity(tris.len());
+        for tri in tris {
+            tds.add_tri(tri.map(VertexNode::Casual));
+        }
+
+        let mut hedges = BTreeMap::new();
+        for hedge_idx in 0..tds.hedge_starting_nodes.len() {
+            let hedge = HedgeIterator::new(&tds, hedge_idx);
+            let edge = [key(hedge.starting_node()), key(hedge.end_node())];
+            if hedges.insert(edge, hedge_idx).is_some() {
+                return Err(invalid());
+            }
+        }
+
+        // each hedge without a twin is on the hull, its twin is in a conceptual triangle
+        let hull: Vec<[usize; 2]> = hedges
+            .keys()
+            .filter(|&&[start, end]| !hedges.contains_key(&[end, start]))
+            .copied()
+            .collect();
+        for [start, end] in hull {
+            let (hedge_ba, hedge_ai, hedge_ib) = tds.add_tri([
+                VertexNode::Casual(end),
+                VertexNode::Casual(start),
+                VertexNode::Conceptual,
+            ]);
+            for (edge, hedge_idx) in [
+                ([end, start], hedge_ba),
+                ([start, usize::MAX], hedge_ai),
+                ([usize::MAX, end], hedge_ib),
+            ] {
+                if hedges.insert(edge, hedge_idx).is_some() {
+                    return Err(invalid());
+                }
+            }
+        }
+
+        for (&[start, end], &hedge_idx) in &hedges {
+            let twin_idx = *hedges.get(&[end, start]).ok_or_else(invalid)?;
+            tds.hedge_twins.set(hedge_idx, twin_idx);
+        }
+
+        // a disk closed by the conceptual node is a sphere, with the Euler characteristic `V - E + F = 2`
+        let num_nodes = hedges
+            .keys()
+            .map(|&[start, _]| start)
+            .collect::<BTreeSet<_>>()
+            .len();
+        if num_nodes + tds.num_tris() != hedges.len() / 2 + 2 {
+            return Err(invalid());
+        }
+
+        HowOk(tds)
+    }
+
     /// Insert a vertex `d` into an existing triangle `abc`; called the `1 -> 3 flip`, as it deletes the triangle and creates three new ones.
     pub fn flip_1_to_3(
         &mut self,
@@ -120,50 +328,51 @@ impl TriDataStructure {
         v_idx: usize,
     ) -> HowResult<[TriIterator<'_>; 3]> {
         if idx_to_remove > self.num_tris() + self.num_deleted_tris {
-            return Err(anyhow::Error::msg("Triangle index out of bounds!"));
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "triangle",
+                idx: idx_to_remove,
+            }));
         }
 
         let hedge_ab = idx_to_remove * 3;
         let hedge_bc = hedge_ab + 1;
         let hedge_ca = hedge_ab + 2;
 
-        let a = self.hedge_starting_nodes[hedge_ab];
-        let b = self.hedge_starting_nodes[hedge_bc];
-        let c = self.hedge_starting_nodes[hedge_ca];
+        let a = self.hedge_starting_nodes.get(hedge_ab);
+        let b = self.hedge_starting_nodes.get(hedge_bc);
+        let c = self.hedge_starting_nodes.get(hedge_ca);
 
         let d = VertexNode::Casual(v_idx);
 
-        let hedge_ba = self.hedge_twins[hedge_ab];
-        let hedge_cb = self.hedge_twins[hedge_bc];
-        let hedge_ac = self.hedge_twins[hedge_ca];
+        let hedge_ba = self.hedge_twins.get(hedge_ab);
+        let hedge_cb = self.hedge_twins.get(hedge_bc);
+        let hedge_ac = self.hedge_twins.get(hedge_ca);
 
         let (hedge_ab, hedge_bd, hedge_da) = self.replace_tri(idx_to_remove, a, b, d);
         let (hedge_bc, hedge_cd, hedge_db) = self.add_tri([b, c, d]);
         let (hedge_ca, hedge_ad, hedge_dc) = self.add_tri([c, a, d]);
 
-        self.hedge_twins[hedge_ba] = hedge_ab;
-        self.hedge_twins[hedge_cb] = hedge_bc;
-        self.hedge_twins[hedge_ac] = hedge_ca;
-        self.hedge_twins[hedge_ab] = hedge_ba;
-        self.hedge_twins[hedge_bd] = hedge_db;
-        self.hedge_twins[hedge_da] = hedge_ad;
-        self.hedge_twins.push(hedge_cb);
-        self.hedge_twins.push(hedge_dc);
-        self.hedge_twins.push(hedge_bd);
-        self.hedge_twins.push(hedge_ac);
-        self.hedge_twins.push(hedge_da);
-        self.hedge_twins.push(hedge_cd);
+        self.set_twins(hedge_ab, hedge_ba);
+        self.set_twins(hedge_bc, hedge_cb);
+        self.set_twins(hedge_ca, hedge_ac);
+        self.set_twins(hedge_bd, hedge_db);
+        self.set_twins(hedge_cd, hedge_dc);
+        self.set_twins(hedge_da, hedge_ad);
 
+        #[cfg(debug_assertions)]
+        self.debug_check_tris("1->3 flip", &[idx_to_remove, hedge_bc / 3, hedge_ca / 3]);
+
+        // the new triangles are in reused slots or appended after the deleted ones, i.e. not at `num_tris`
         HowOk([
             TriIterator::new(self, idx_to_remove),
-            TriIterator::new(self, self.num_tris() - 2),
-            TriIterator::new(self, self.num_tris() - 1),
+            TriIterator::new(self, hedge_bc / 3),
+            TriIterator::new(self, hedge_ca / 3),
         ])
     }
 
     /// Flips an edge that internally connects two triangles to an edge that connects the other two triangles.
     pub fn flip_2_to_2(&mut self, idx: usize) -> HowResult<[TriIterator<'_>; 2]> {
-        let hedge_twin_idx = self.hedge_twins[idx];
+        let hedge_twin_idx = self.hedge_twins.get(idx);
 
         let tri1_idx = idx / 3;
         let tri2_idx = hedge_twin_idx / 3;
@@ -193,31 +402,34 @@ impl TriDataStructure {
             (hedge01_twin, hedge12_twin)
         };
 
-        let na = self.hedge_starting_nodes[hedge_ab];
-        let nb = self.hedge_starting_nodes[hedge_bc];
-        let nc = self.hedge_starting_nodes[hedge_cd];
-        let nd = self.hedge_starting_nodes[hedge_da];
+        let na = self.hedge_starting_nodes.get(hedge_ab);
+        let nb = self.hedge_starting_nodes.get(hedge_bc);
+        let nc = self.hedge_starting_nodes.get(hedge_cd);
+        let nd = self.hedge_starting_nodes.get(hedge_da);
 
-        let hedge_ba = self.hedge_twins[hedge_ab];
-        let hedge_cb = self.hedge_twins[hedge_bc];
-        let hedge_dc = self.hedge_twins[hedge_cd];
-        let hedge_ad = self.hedge_twins[hedge_da];
+        let hedge_ba = self.hedge_twins.get(hedge_ab);
+        let hedge_cb = self.hedge_twins.get(hedge_bc);
+        let hedge_dc = self.hedge_twins.get(hedge_cd);
+        let hedge_ad = self.hedge_twins.get(hedge_da);
 
         let (hedge_bc, hedge_cd, hedge_db) = self.replace_tri(tri1_idx, nb, nc, nd);
         let (hedge_da, hedge_ab, hedge_bd) = self.replace_tri(tri2_idx, nd, na, nb);
 
-        self.hedge_twins[hedge_ab] = hedge_ba;
-        self.hedge_twins[hedge_da] = hedge_ad;
-        self.hedge_twins[hedge_bc] = hedge_cb;
-        self.hedge_twins[hedge_cd] = hedge_dc;
+        self.hedge_twins.set(hedge_ab, hedge_ba);
+        self.hedge_twins.set(hedge_da, hedge_ad);
+        self.hedge_twins.set(hedge_bc, hedge_cb);
+        self.hedge_twins.set(hedge_cd, hedge_dc);
+
+        self.hedge_twins.set(hedge_bd, hedge_db);
+        self.hedge_twins.set(hedge_db, hedge_bd);
 
-        self.hedge_twins[hedge_bd] = hedge_db;
-        self.hedge_twins[hedge_db] = hedge_bd;
+        self.hedge_twins.set(hedge_ba, hedge_ab);
+        self.hedge_twins.set(hedge_ad, hedge_da);
+        self.hedge_twins.set(hedge_cb, hedge_bc);
+        self.hedge_twins.set(hedge_dc, hedge_cd);
 
-        self.hedge_twins[hedge_ba] = hedge_ab;
-        self.hedge_twins[hedge_ad] = hedge_da;
-        self.hedge_twins[hedge_cb] = hedge_bc;
-        self.hedge_twins[hedge_dc] = hedge_cd;
+        #[cfg(debug_assertions)]
+        self.debug_check_tris("2->2 flip", &[tri1_idx, tri2_idx]);
 
         HowOk([
             TriIterator::new(self, tri1_idx),
@@ -231,7 +443,7 @@ impl TriDataStructure {
     ///
     /// Assumption: `abd`, `bcd` and `cad` are arranged in a way that they form a larger triangle, i.e. the vertex `d` lies in the "middle" and is redundant.
     ///
-    /// Note: only appears in weighted Delaunay triangulations, where the weights are not zero.
+    /// Note: only appears in weighted Delaunay triangulations, where the weights are not zero, or to remove vertices skipped by epsilon.
     pub fn flip_3_to_1(
         &mut self,
         idxs_to_flip: [usize; 3],
@@ -244,6 +456,7 @@ impl TriDataStructure {
         // with that information we can update the first (WLOG) of the three triangles to become the new triangle
         let tri0 = self.get_tri(idxs_to_flip[0]).unwrap();
         let tri0_idx = tri0.idx;
+        let tri0_was_conceptual = tri0.is_conceptual();
         let hedges0 = tri0.hedges();
 
         // 0. Get the indices where the new triangle will be stored (these are the idxs of the first triangle to be removed from the trgltn)
@@ -309,53 +522,243 @@ impl TriDataStructure {
         }
 
         // 2.2 First new edge
-        self.hedge_starting_nodes[h_idx0] = starting_node0;
-        self.hedge_twins[h_idx0] = twin_idx0;
-        self.hedge_twins[twin_idx0] = h_idx0;
+        self.hedge_starting_nodes.set(h_idx0, starting_node0);
+        self.hedge_twins.set(h_idx0, twin_idx0);
+        self.hedge_twins.set(twin_idx0, h_idx0);
         // 2.3 Second new edge
-        self.hedge_starting_nodes[h_idx1] = starting_node1;
-        self.hedge_twins[h_idx1] = twin_idx1;
-        self.hedge_twins[twin_idx1] = h_idx1;
+        self.hedge_starting_nodes.set(h_idx1, starting_node1);
+        self.hedge_twins.set(h_idx1, twin_idx1);
+        self.hedge_twins.set(twin_idx1, h_idx1);
         // 2.4 Third new edge
-        self.hedge_starting_nodes[h_idx2] = starting_node2;
-        self.hedge_twins[h_idx2] = twin_idx2;
-        self.hedge_twins[twin_idx2] = h_idx2;
+        self.hedge_starting_nodes.set(h_idx2, starting_node2);
+        self.hedge_twins.set(h_idx2, twin_idx2);
+        self.hedge_twins.set(twin_idx2, h_idx2);
+        self.set_vertex_tris(&[starting_node0, starting_node1, starting_node2], tri0_idx);
+
+        if tri0_was_conceptual {
+            self.num_conceptual_tris -= 1;
+        }
+        if TriIterator::new(self, tri0_idx).is_conceptual() {
+            self.num_conceptual_tris += 1;
+        }
 
         // 3. Set the other two triangles to deleted and their twins to inactive
-        self.set_tri_inactive(idxs_to_flip[1]);
-        self.set_tri_inactive(idxs_to_flip[2]);
+        self.set_tri_inactive(idxs_to_flip[1])?;
+        self.set_tri_inactive(idxs_to_flip[2])?;
 
         // 4. Update number of triangles and deleted triangles
         self.num_tris -= 2;
         self.num_deleted_tris += 2;
 
+        #[cfg(debug_assertions)]
+        self.debug_check_tris("3->1 flip", &[tri0_idx]);
+
         HowOk(TriIterator::new(self, tri0_idx))
     }
 
+    /// Link two hedges as twins of each other.
+    fn set_twins(&mut self, hedge_idx0: HedgeIteratorIdx, hedge_idx1: HedgeIteratorIdx) {
+        self.hedge_twins.set(hedge_idx0, hedge_idx1);
+        self.hedge_twins.set(hedge_idx1, hedge_idx0);
+    }
+
     /// Helper function for 3->1 flip. Sets a triangle to inactive.
     ///
     /// Called twice by the 3->1 flip, once for each triangle that is set to inactive.
-    ///
-    /// Easier for now, than to re-arrange the indices in the array.
-    fn set_tri_inactive(&mut self, triangle_idx: usize) {
-        let hedges = self.get_tri(triangle_idx).unwrap().hedges();
+    /// The slot is reused by the next [`Self::add_tri`], instead of re-arranging the indices in the array.
+    fn set_tri_inactive(&mut self, triangle_idx: usize) -> HowResult<()> {
+        let tri = self.get_tri(triangle_idx).unwrap();
+        let was_conceptual = tri.is_conceptual();
+        let hedges = tri.hedges();
         let idx_del0 = hedges[0].idx;
         let idx_del1 = hedges[1].idx;
         let idx_del2 = hedges[2].idx;
 
-        self.hedge_starting_nodes[idx_del0] = VertexNode::Deleted;
-        self.hedge_starting_nodes[idx_del1] = VertexNode::Deleted;
-        self.hedge_starting_nodes[idx_del2] = VertexNode::Deleted;
+        if was_conceptual {
+            self.num_conceptual_tris -= 1;
+        }
+
+        self.hedge_starting_nodes.set(idx_del0, VertexNode::Deleted);
+        self.hedge_starting_nodes.set(idx_del1, VertexNode::Deleted);
+        self.hedge_starting_nodes.set(idx_del2, VertexNode::Deleted);
+
+        self.hedge_twins.set(idx_del0, INACTIVE);
+        self.hedge_twins.set(idx_del1, INACTIVE);
+        self.hedge_twins.set(idx_del2, INACTIVE);
+
+        push_bounded(
+            &mut self.free_tris,
+            triangle_idx,
+            self.bounded,
+            "free triangles",
+        )
+    }
+
+    /// Starts a Bowyer Watson insertion, setting a first triangle to remove.
+    pub fn bw_start(&mut self, first_tri_idx: usize) -> HowResult<()> {
+        if !self.bw_tris_to_check.is_empty() || !self.bw_tris_to_keep.is_empty() {
+            return Err(anyhow::Error::new(RitaError::InvalidState(
+                "Bowyer Watson algorithm already started",
+            )));
+        }
+        self.bw_tris_to_del.clear();
+
+        self.bw_rem_tri(first_tri_idx)
+    }
+
+    /// Gets the next triangle to check, that is neither removed nor kept yet.
+    ///
+    /// The cavities are small in general, so the membership is checked on the lists themselves.
+    pub fn bw_tris_to_check(&mut self) -> Option<usize> {
+        while let Some(tri_idx) = self.bw_tris_to_check.pop() {
+            if !self.bw_tris_to_del.contains(&tri_idx) && !self.bw_tris_to_keep.contains(&tri_idx) {
+                return Some(tri_idx);
+            }
+        }
+        None
+    }
+
+    /// Sets a triangle to remove, and its neighbors to check.
+    pub fn bw_rem_tri(&mut self, tri_idx: usize) -> HowResult<()> {
+        for hedge_idx in 3 * tri_idx..3 * tri_idx + 3 {
+            push_bounded(
+                &mut self.bw_tris_to_check,
+                self.hedge_twins.get(hedge_idx) / 3,
+                self.bounded,
+                "triangles to check",
+            )?;
+        }
+
+        push_bounded(&mut self.bw_tris_to_del, tri_idx, self.bounded, "cavity")
+    }
+
+    /// Sets a triangle to keep.
+    pub fn bw_keep_tri(&mut self, tri_idx: usize) -> HowResult<()> {
+        push_bounded(
+            &mut self.bw_tris_to_keep,
+            tri_idx,
+            self.bounded,
+            "triangles to keep",
+        )
+    }
+
+    /// Sets a kept triangle to remove after all, e.g. to grow the cavity.
+    pub fn bw_grow(&mut self, tri_idx: usize) -> HowResult<()> {
+        if self.bw_tris_to_del.contains(&tri_idx) {
+            return HowOk(());
+        }
+
+        self.bw_tris_to_keep.retain(|&idx| idx != tri_idx);
+        self.bw_rem_tri(tri_idx)
+    }
+
+    /// The hedges of the removed triangles, whose twin is in a kept one, i.e. the boundary of the cavity.
+    pub fn bw_cavity_boundary(&self) -> impl Iterator<Item = HedgeIterator<'_>> {
+        self.bw_tris_to_del
+            .iter()
+            .flat_map(|&tri_idx| TriIterator::new(self, tri_idx).hedges())
+            .filter(|hedge| !self.bw_tris_to_del.contains(&hedge.twin().tri().idx))
+    }
+
+    /// The triangles removed by the current insertion, i.e. the cavity.
+    pub(crate) fn bw_removed_tris(&self) -> &[usize] {
+        &self.bw_tris_to_del
+    }
+
+    /// The triangles created by the last [`Self::bw_insert_node`].
+    pub(crate) fn bw_added_tris(&self) -> &[usize] {
+        &self.bw_added_tris
+    }
+
+    /// Replace the cavity by the triangles connecting its boundary edges to `node`.
+    ///
+    /// The slots of the removed triangles are reused, further triangles are appended. If the cavity contains vertices,
+    /// e.g. redundant ones of a weighted triangulation, there are fewer new triangles, and the remaining slots are deleted.
+    pub fn bw_insert_node(&mut self, node: VertexNode) -> HowResult<()> {
+        if !self.bw_tris_to_check.is_empty() {
+            return Err(anyhow::Error::new(RitaError::InvalidState(
+                "cannot insert node if all triangles are not checked",
+            )));
+        }
+
+        // the boundary is taken out of `self`, to be filled while iterating the cavity
+        let mut boundary = core::mem::take(&mut self.bw_boundary);
+        boundary.clear();
+        let result = self.bw_replace_cavity(node, &mut boundary);
+        self.bw_boundary = boundary;
 
-        self.hedge_twins[idx_del0] = INACTIVE;
-        self.hedge_twins[idx_del1] = INACTIVE;
-        self.hedge_twins[idx_del2] = INACTIVE;
+        result
+    }
+
+    /// Replace the cavity by the triangles connecting its boundary edges to `node`, see [`Self::bw_insert_node`].
+    fn bw_replace_cavity(
+        &mut self,
+        node: VertexNode,
+        boundary: &mut Vec<(VertexNode, VertexNode, HedgeIteratorIdx)>,
+    ) -> HowResult<()> {
+        // 1. the boundary edges `ab`, along with the twin outside of the cavity
+        for hedge in self.bw_cavity_boundary() {
+            push_bounded(
+                boundary,
+                (hedge.starting_node(), hedge.end_node(), hedge.twin().idx),
+                self.bounded,
+                "cavity boundary",
+            )?;
+        }
+
+        // 2. a triangle `abn` per boundary edge, in the slots of the cavity first
+        self.bw_added_tris.clear();
+        for (i, &(a, b, twin_ab)) in boundary.iter().enumerate() {
+            let (hedge_ab, _, _) = if let Some(&tri_idx) = self.bw_tris_to_del.get(i) {
+                self.replace_tri(tri_idx, a, b, node)
+            } else {
+                self.add_tri([a, b, node])
+            };
+
+            self.hedge_twins.set(hedge_ab, twin_ab);
+            self.hedge_twins.set(twin_ab, hedge_ab);
+            push_bounded(
+                &mut self.bw_added_tris,
+                hedge_ab / 3,
+                self.bounded,
+                "added triangles",
+            )?;
+        }
+
+        // 3. connect the new triangles around the node, `bn` is the twin of `nb` in the triangle starting at `b`
+        for (i, &(_, b, _)) in boundary.iter().enumerate() {
+            let next = boundary
+                .iter()
+                .position(|&(a, _, _)| a == b)
+                .ok_or(RitaError::InvalidState("cavity boundary is not closed"))?;
+
+            let hedge_bn = 3 * self.bw_added_tris[i] + 1;
+            let hedge_nb = 3 * self.bw_added_tris[next] + 2;
+            self.hedge_twins.set(hedge_bn, hedge_nb);
+            self.hedge_twins.set(hedge_nb, hedge_bn);
+        }
+
+        // 4. delete the remaining slots of the cavity
+        for i in boundary.len()..self.bw_tris_to_del.len() {
+            self.set_tri_inactive(self.bw_tris_to_del[i])?;
+            self.num_tris -= 1;
+            self.num_deleted_tris += 1;
+        }
+        self.bw_tris_to_keep.clear();
+
+        #[cfg(debug_assertions)]
+        self.debug_check_tris("Bowyer Watson insertion", &self.bw_added_tris);
+
+        HowOk(())
     }
 
     /// Retrieve a half-edge iterator by index.
     pub fn get_hedge(&self, idx: usize) -> HowResult<HedgeIterator<'_>> {
         if idx >= self.hedge_starting_nodes.len() {
-            return Err(anyhow::Error::msg("Hedge index out of bounds"));
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "hedge",
+                idx,
+            }));
         }
 
         HowOk(HedgeIterator::new(self, idx))
@@ -365,7 +768,10 @@ impl TriDataStructure {
     pub fn get_tri(&self, idx: usize) -> HowResult<TriIterator<'_>> {
         if idx >= self.num_tris() + self.num_deleted_tris {
             // - num_deleted_tris because we have to account for the deleted, that basically clog up array indices
-            return Err(anyhow::Error::msg("Tri index out of bounds!"));
+            return Err(anyhow::Error::new(RitaError::IndexOutOfBounds {
+                kind: "triangle",
+                idx,
+            }));
         }
 
         HowOk(TriIterator::new(self, idx))
@@ -377,37 +783,133 @@ impl TriDataStructure {
     }
 
     /// Get the number of triangles in the triangulation, without the ones connected to the dummy point.
-    pub fn num_casual_tris(&self) -> usize {
-        let mut num_casual_tris = 0;
-        for i in 0..self.num_tris() + self.num_deleted_tris {
-            let tri = self.get_tri(i).unwrap();
-            let [n0, n1, n2] = tri.nodes();
+    pub const fn num_casual_tris(&self) -> usize {
+        self.num_tris - self.num_conceptual_tris
+    }
 
-            if !tri.is_conceptual()
-                && !tri.is_deleted()
-                && n0.idx().is_some()
-                && n1.idx().is_some()
-                && n2.idx().is_some()
-            {
-                num_casual_tris += 1;
-            }
-        }
-        num_casual_tris
+    /// Get the number of triangles connected to the dummy point, i.e. one per convex hull edge.
+    pub const fn num_conceptual_tris(&self) -> usize {
+        self.num_conceptual_tris
     }
 
     /// Check if the data structure is sound, i.e. hedges point to correct next and previous nodes.
     pub fn is_sound(&self) -> bool {
-        let mut sound = true;
+        let report = self.soundness_report();
+
+        #[cfg(feature = "logging")]
+        for issue in &report.issues {
+            log::error!("{issue}");
+        }
+
+        report.is_sound()
+    }
+
+    /// Check the `next`, `prev` and `twin` links of all hedges, that are not deleted.
+    pub fn soundness_report(&self) -> SoundnessReport {
+        let mut report = SoundnessReport::default();
 
         for hedge_idx in 0..self.hedge_starting_nodes.len() {
-            if self.hedge_starting_nodes[hedge_idx] == VertexNode::Deleted {
+            if self.hedge_starting_nodes.get(hedge_idx) == VertexNode::Deleted {
                 continue;
             }
-            let hedge = self.get_hedge(hedge_idx).unwrap();
-            sound = sound && hedge.is_sound();
+            HedgeIterator::new(self, hedge_idx).check_soundness(&mut report.issues);
         }
 
-        sound
+        report
+    }
+
+    /// Try to repair recoverable inconsistencies, e.g. after an interrupted flip, and report the remaining issues.
+    ///
+    /// Every hedge, whose twin is deleted or does not run in reverse, is re-linked to the unique hedge that does.
+    pub fn try_repair(&mut self) -> SoundnessReport {
+        let key = |node: VertexNode| node.idx().unwrap_or(usize::MAX);
+        let num_hedges = self.hedge_starting_nodes.len();
+
+        let mut hedges: BTreeMap<[usize; 2], Vec<HedgeIteratorIdx>> = BTreeMap::new();
+        for hedge_idx in 0..num_hedges {
+            let hedge = HedgeIterator::new(self, hedge_idx);
+            if !hedge.starting_node().is_deleted() {
+                hedges
+                    .entry([key(hedge.starting_node()), key(hedge.end_node())])
+                    .or_default()
+                    .push(hedge_idx);
+            }
+        }
+
+        for hedge_idx in 0..num_hedges {
+            let hedge = HedgeIterator::new(self, hedge_idx);
+            let [start, end] = [hedge.starting_node(), hedge.end_node()];
+            let twin_idx = self.hedge_twins.get(hedge_idx);
+
+            let synced = twin_idx < num_hedges
+                && self.hedge_twins.get(twin_idx) == hedge_idx
+                && HedgeIterator::new(self, twin_idx).starting_node() == end
+                && HedgeIterator::new(self, twin_idx).end_node() == start;
+            if start.is_deleted() || synced {
+                continue;
+            }
+
+            if let Some(&[twin_idx]) = hedges.get(&[key(end), key(start)]).map(Vec::as_slice) {
+                self.hedge_twins.set(hedge_idx, twin_idx);
+                self.hedge_twins.set(twin_idx, hedge_idx);
+            }
+        }
+
+        self.soundness_report()
+    }
+
+    /// Check the structural invariants of the given triangles, right after they were modified.
+    ///
+    /// Catches corruption at the mutation site, instead of much later in [`Self::is_sound`].
+    /// Panics with the offending indices, only compiled with `debug_assertions`.
+    #[cfg(debug_assertions)]
+    fn debug_check_tris(&self, operation: &str, tri_idxs: &[usize]) {
+        for &tri_idx in tri_idxs {
+            let tri = TriIterator::new(self, tri_idx);
+            let [n0, n1, n2] = tri.nodes();
+
+            assert!(
+                !n0.is_deleted() && !n1.is_deleted() && !n2.is_deleted(),
+                "{operation}: triangle {tri_idx} has deleted nodes [{n0}, {n1}, {n2}]"
+            );
+            assert!(
+                n0 != n1 && n1 != n2 && n2 != n0,
+                "{operation}: triangle {tri_idx} has duplicate nodes [{n0}, {n1}, {n2}]"
+            );
+
+            for hedge in tri.hedges() {
+                let hedge_idx = hedge.idx;
+
+                assert!(
+                    hedge.next().next().next().idx == hedge_idx
+                        && hedge.next().prev().idx == hedge_idx,
+                    "{operation}: next/prev cycle of hedge {hedge_idx} in triangle {tri_idx} is broken"
+                );
+
+                let twin_idx = self.hedge_twins.get(hedge_idx);
+                assert!(
+                    twin_idx < self.hedge_twins.len(),
+                    "{operation}: hedge {hedge_idx} in triangle {tri_idx} has no twin ({twin_idx})"
+                );
+                assert_eq!(
+                    self.hedge_twins.get(twin_idx),
+                    hedge_idx,
+                    "{operation}: twin of the twin {twin_idx} of hedge {hedge_idx} in triangle {tri_idx} is not the hedge itself"
+                );
+                assert_ne!(
+                    twin_idx / 3,
+                    tri_idx,
+                    "{operation}: hedge {hedge_idx} and its twin {twin_idx} are in the same triangle {tri_idx}"
+                );
+
+                let twin = hedge.twin();
+                assert!(
+                    twin.starting_node() == hedge.end_node()
+                        && twin.end_node() == hedge.starting_node(),
+                    "{operation}: nodes of hedge {hedge} and its twin {twin} in triangle {tri_idx} do not match"
+                );
+            }
+        }
     }
 
     /// Replace a triangle in the triangulation and retrieve the hedge indices.
@@ -420,10 +922,51 @@ impl TriDataStructure {
     ) -> (usize, usize, usize) {
         let idx0 = idx_to_remove * 3;
 
-        self.hedge_starting_nodes[idx0] = v0;
-        self.hedge_starting_nodes[idx0 + 1] = v1;
-        self.hedge_starting_nodes[idx0 + 2] = v2;
+        if self
+            .hedge_starting_nodes
+            .contains(idx0..idx0 + 3, VertexNode::Conceptual)
+        {
+            self.num_conceptual_tris -= 1;
+        }
+        if [v0, v1, v2].iter().any(VertexNode::is_conceptual) {
+            self.num_conceptual_tris += 1;
+        }
+
+        self.hedge_starting_nodes.set(idx0, v0);
+        self.hedge_starting_nodes.set(idx0 + 1, v1);
+        self.hedge_starting_nodes.set(idx0 + 2, v2);
+        self.set_vertex_tris(&[v0, v1, v2], idx_to_remove);
 
         (idx0, idx0 + 1, idx0 + 2)
     }
+
+    /// Record the triangle as the incident triangle of its casual nodes.
+    fn set_vertex_tris(&mut self, nodes: &[VertexNode], tri_idx: usize) {
+        for v_idx in nodes.iter().filter_map(VertexNode::idx) {
+            if v_idx >= self.vertex_tris.len() {
+                self.vertex_tris.resize(v_idx + 1, usize::MAX);
+            }
+            self.vertex_tris[v_idx] = tri_idx;
+        }
+    }
+
+    /// A triangle incident to the vertex, `None` if the vertex is in no triangle.
+    ///
+    /// Falls back to scanning all triangles, if the recorded one does not contain the vertex,
+    /// e.g. for a data structure deserialized without incident triangles.
+    pub fn vertex_tri(&self, v_idx: VertexIdx) -> Option<usize> {
+        let num_tris = self.hedge_starting_nodes.len() / 3;
+        let contains_v = |tri_idx: &usize| {
+            *tri_idx < num_tris
+                && self
+                    .hedge_starting_nodes
+                    .contains(3 * tri_idx..3 * tri_idx + 3, VertexNode::Casual(v_idx))
+        };
+
+        self.vertex_tris
+            .get(v_idx)
+            .copied()
+            .filter(contains_v)
+            .or_else(|| (0..num_tris).find(contains_v))
+    }
 }
@@ -55,9 +55,9 @@ impl<'a> TriIterator<'a> {
     // the indices of the nodes can be retrieved by multiplying the triangle index by 3
     pub fn nodes(&self) -> [VertexNode; 3] {
         [
-            self.tds.hedge_starting_nodes[self.idx * 3],
-            self.tds.hedge_starting_nodes[self.idx * 3 + 1],
-            self.tds.hedge_starting_nodes[self.idx * 3 + 2],
+            self.tds.hedge_starting_nodes.get(self.idx * 3),
+            self.tds.hedge_starting_nodes.get(self.idx * 3 + 1),
+            self.tds.hedge_starting_nodes.get(self.idx * 3 + 2),
         ]
     }
 }
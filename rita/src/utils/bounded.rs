@@ -0,0 +1,25 @@
+//! Scratch buffers of bounded data structures, that are allocated once and never grow.
+
+use alloc::vec::Vec;
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+use crate::RitaError;
+
+/// Push `item` onto the scratch buffer `name`, which fails with [`RitaError::BufferFull`] instead of re-allocating
+/// if the buffer is `bounded` and full.
+pub(crate) fn push_bounded<T>(
+    buffer: &mut Vec<T>,
+    item: T,
+    bounded: bool,
+    name: &'static str,
+) -> HowResult<()> {
+    if bounded && buffer.len() == buffer.capacity() {
+        return Err(anyhow::Error::new(RitaError::BufferFull {
+            buffer: name,
+            capacity: buffer.capacity(),
+        }));
+    }
+    buffer.push(item);
+
+    HowOk(())
+}
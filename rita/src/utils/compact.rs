@@ -0,0 +1,238 @@
+//! Compact storage of the indices and nodes of the data structures.
+//!
+//! Indices are stored as [`StoredIdx`], which is `u32` with the `u32-indices` feature, roughly halving the memory of
+//! the data structures. Nodes are stored as a single index, with the two largest ones for the conceptual and deleted
+//! node. With `u32` indices, the vertices, hedges and half triangles are limited to `u32::MAX - 1`, storing a larger
+//! index panics.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::VertexNode;
+
+/// The type that indices are stored as, `u32` with the `u32-indices` feature, otherwise `usize`.
+#[cfg(feature = "u32-indices")]
+pub type StoredIdx = u32;
+/// The type that indices are stored as, `u32` with the `u32-indices` feature, otherwise `usize`.
+#[cfg(not(feature = "u32-indices"))]
+pub type StoredIdx = usize;
+
+/// The stored [`usize::MAX`], e.g. of an inactive twin, and the stored [`VertexNode::Deleted`].
+const MAX: StoredIdx = StoredIdx::MAX;
+/// The stored [`VertexNode::Conceptual`].
+const CONCEPTUAL: StoredIdx = StoredIdx::MAX - 1;
+
+#[cfg(feature = "u32-indices")]
+const fn store(idx: usize) -> StoredIdx {
+    if idx == usize::MAX {
+        return MAX;
+    }
+    assert!(
+        idx < CONCEPTUAL as usize,
+        "The index exceeds the u32 indices!"
+    );
+    idx as StoredIdx
+}
+
+#[cfg(feature = "u32-indices")]
+const fn load(stored: StoredIdx) -> usize {
+    if stored == MAX {
+        usize::MAX
+    } else {
+        stored as usize
+    }
+}
+
+#[cfg(not(feature = "u32-indices"))]
+const fn store(idx: usize) -> StoredIdx {
+    idx
+}
+
+#[cfg(not(feature = "u32-indices"))]
+const fn load(stored: StoredIdx) -> usize {
+    stored
+}
+
+const fn store_node(node: VertexNode) -> StoredIdx {
+    match node {
+        VertexNode::Casual(v_idx) => store(v_idx),
+        VertexNode::Conceptual => CONCEPTUAL,
+        VertexNode::Deleted => MAX,
+    }
+}
+
+const fn load_node(stored: StoredIdx) -> VertexNode {
+    match stored {
+        MAX => VertexNode::Deleted,
+        CONCEPTUAL => VertexNode::Conceptual,
+        v_idx => VertexNode::Casual(load(v_idx)),
+    }
+}
+
+/// A vector of indices, e.g. the twins of the hedges, stored as [`StoredIdx`].
+///
+/// Indexing out of bounds panics, like for a `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct IdxVec(Vec<StoredIdx>);
+
+impl IdxVec {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// The index at position `pos`.
+    pub fn get(&self, pos: usize) -> usize {
+        load(self.0[pos])
+    }
+
+    /// Set the index at position `pos`.
+    pub fn set(&mut self, pos: usize, idx: usize) {
+        self.0[pos] = store(idx);
+    }
+
+    pub fn push(&mut self, idx: usize) {
+        self.0.push(store(idx));
+    }
+
+    pub fn pop(&mut self) -> Option<usize> {
+        self.0.pop().map(load)
+    }
+
+    pub fn extend(&mut self, idxs: impl IntoIterator<Item = usize>) {
+        self.0.extend(idxs.into_iter().map(store));
+    }
+}
+
+/// A vector of nodes, e.g. the nodes of the tetrahedra, each stored as a single [`StoredIdx`].
+///
+/// Indexing out of bounds panics, like for a `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct NodeVec(Vec<StoredIdx>);
+
+impl NodeVec {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// The node at position `pos`.
+    pub fn get(&self, pos: usize) -> VertexNode {
+        load_node(self.0[pos])
+    }
+
+    /// Set the node at position `pos`.
+    pub fn set(&mut self, pos: usize, node: VertexNode) {
+        self.0[pos] = store_node(node);
+    }
+
+    /// Set the nodes from position `pos` on.
+    pub fn set_all(&mut self, pos: usize, nodes: &[VertexNode]) {
+        for (i, &node) in nodes.iter().enumerate() {
+            self.set(pos + i, node);
+        }
+    }
+
+    pub fn push(&mut self, node: VertexNode) {
+        self.0.push(store_node(node));
+    }
+
+    pub fn pop(&mut self) -> Option<VertexNode> {
+        self.0.pop().map(load_node)
+    }
+
+    pub fn extend(&mut self, nodes: impl IntoIterator<Item = VertexNode>) {
+        self.0.extend(nodes.into_iter().map(store_node));
+    }
+
+    /// Whether the node is at one of the positions `range`.
+    pub fn contains(&self, range: Range<usize>, node: VertexNode) -> bool {
+        self.0[range].contains(&store_node(node))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = VertexNode> + '_ {
+        self.0.iter().map(|&stored| load_node(stored))
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact() {
+        let mut idxs = IdxVec::new();
+        idxs.extend([0, 7, usize::MAX]);
+        assert_eq!((idxs.get(1), idxs.get(2)), (7, usize::MAX));
+        idxs.set(0, 3);
+        assert_eq!(idxs.pop(), Some(usize::MAX));
+        assert_eq!((idxs.len(), idxs.get(0)), (2, 3));
+
+        let mut nodes = NodeVec::new();
+        nodes.extend([
+            VertexNode::Casual(0),
+            VertexNode::Conceptual,
+            VertexNode::Deleted,
+        ]);
+        assert_eq!(
+            nodes.iter().collect::<Vec<_>>(),
+            [
+                VertexNode::Casual(0),
+                VertexNode::Conceptual,
+                VertexNode::Deleted
+            ]
+        );
+        nodes.set(2, VertexNode::Casual(5));
+        assert!(nodes.contains(1..3, VertexNode::Casual(5)));
+        assert!(!nodes.contains(0..2, VertexNode::Casual(5)));
+    }
+
+    #[cfg(feature = "u32-indices")]
+    #[test]
+    #[should_panic(expected = "exceeds the u32 indices")]
+    fn test_compact_overflow() {
+        IdxVec::new().push(u32::MAX as usize);
+    }
+}
@@ -2,7 +2,7 @@ use super::types::Vertex2;
 use crate::predicates;
 
 /// Checks if ang(v1--v0, v1--v2) is convex, flat, or concave
-pub(crate) fn is_convex(v0: Vertex2, v1: Vertex2, v2: Vertex2) -> bool {
+pub fn is_convex(v0: Vertex2, v1: Vertex2, v2: Vertex2) -> bool {
     // true <-> used to return 1
     let sign = predicates::orient_2d(&v0, &v1, &v2);
 
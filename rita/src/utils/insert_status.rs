@@ -0,0 +1,194 @@
+use super::types::VertexIdx;
+use crate::{RitaError, VertexNode};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use anyhow::Result as HowResult;
+
+/// What became of a vertex passed to `insert_vertices`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertStatus {
+    /// The vertex is part of the triangulation.
+    Inserted,
+    /// The vertex is not part of the triangulation, due to its weight.
+    ///
+    /// This includes vertices that were inserted, but removed again when a heavier vertex was inserted later.
+    Redundant,
+    /// The vertex was skipped, as it is not in any power circle (or sphere) by epsilon.
+    IgnoredByEpsilon,
+    /// The vertex was skipped, as it coincides with a vertex inserted before.
+    Duplicate,
+    /// The vertex could not be inserted, e.g. a non-finite vertex.
+    Failed,
+    /// The vertex was not processed, as the insertion was cancelled before, see [`InsertProgress`].
+    Cancelled,
+}
+
+/// The progress of a bulk insertion, passed to its callback, e.g. to display a progress bar.
+///
+/// The callback is called every [`InsertProgress::INTERVAL`] vertices and once all vertices are processed.
+/// Returning [`ControlFlow::Break`](core::ops::ControlFlow::Break) cancels the insertion cleanly, i.e. the vertices
+/// processed so far stay inserted and the triangulation stays sound, the others get [`InsertStatus::Cancelled`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertProgress {
+    /// The number of vertices processed so far, inserted or skipped.
+    pub num_processed: usize,
+    /// The number of vertices passed to the insertion.
+    pub num_total: usize,
+}
+
+impl InsertProgress {
+    /// The number of processed vertices between two calls of the callback.
+    pub const INTERVAL: usize = 1024;
+
+    /// The processed fraction of the vertices, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.num_total == 0 {
+            1.0
+        } else {
+            self.num_processed as f64 / self.num_total as f64
+        }
+    }
+
+    /// Whether the callback is due after processing a vertex, with `num_remaining` vertices left.
+    pub(crate) const fn is_due(num_processed: usize, num_remaining: usize) -> bool {
+        num_processed % Self::INTERVAL == 0 || num_remaining == 0
+    }
+}
+
+/// The status of each vertex from `first_new_idx` on.
+///
+/// Vertices start out as failed, then the `skipped` lists are applied in order, later ones taking precedence.
+/// Last, all vertices that are `nodes` of the data structure are inserted.
+pub(crate) fn insert_statuses(
+    first_new_idx: VertexIdx,
+    num_vertices: usize,
+    skipped: [(&[VertexIdx], InsertStatus); 4],
+    nodes: impl IntoIterator<Item = VertexNode>,
+) -> Vec<InsertStatus> {
+    let mut statuses = vec![InsertStatus::Failed; num_vertices - first_new_idx];
+
+    for (v_idxs, status) in skipped {
+        for &v_idx in v_idxs {
+            if v_idx >= first_new_idx {
+                statuses[v_idx - first_new_idx] = status;
+            }
+        }
+    }
+
+    for node in nodes {
+        if let VertexNode::Casual(v_idx) = node {
+            if v_idx >= first_new_idx {
+                statuses[v_idx - first_new_idx] = InsertStatus::Inserted;
+            }
+        }
+    }
+
+    statuses
+}
+
+/// What happens to a vertex at the position of a vertex inserted before.
+///
+/// Which positions coincide is decided exactly, or in 2D within the distance of a [`DedupPolicy`](crate::DedupPolicy).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicatePolicy {
+    /// Skip the duplicate and map it to the vertex inserted before.
+    #[default]
+    SkipAndMap,
+    /// Fail with [`RitaError::DuplicateVertex`].
+    Error,
+    /// Skip the duplicate and map it to the vertex inserted before, which gets the largest weight of both.
+    ///
+    /// Weights are merged among the vertices of one call, a duplicate heavier than a vertex
+    /// inserted by an earlier call fails with [`RitaError::DuplicateVertex`].
+    MergeWeights,
+}
+
+impl DuplicatePolicy {
+    /// Decide on the duplicate `v_idx` of the inserted vertex `canonical`.
+    pub(crate) fn check(
+        self,
+        v_idx: VertexIdx,
+        canonical: VertexIdx,
+        weights: Option<&[f64]>,
+    ) -> HowResult<()> {
+        let is_heavier = || weights.is_some_and(|weights| weights[v_idx] > weights[canonical]);
+        match self {
+            Self::SkipAndMap => Ok(()),
+            Self::MergeWeights if !is_heavier() => Ok(()),
+            Self::Error | Self::MergeWeights => {
+                Err(anyhow::Error::new(RitaError::DuplicateVertex {
+                    v_idx,
+                    canonical,
+                }))
+            }
+        }
+    }
+}
+
+/// Set the weights of vertices with exactly the same position to the largest weight among them.
+pub(crate) fn merge_duplicate_weights<const D: usize>(
+    vertices: &[[f64; D]],
+    weights: &mut [f64],
+    v_idxs: &[VertexIdx],
+) {
+    let key = |v_idx: VertexIdx| vertices[v_idx].map(|x| (x + 0.0).to_bits());
+    let mut max_weights: BTreeMap<[u64; D], f64> = BTreeMap::new();
+
+    for &v_idx in v_idxs {
+        let max_weight = max_weights.entry(key(v_idx)).or_insert(weights[v_idx]);
+        *max_weight = max_weight.max(weights[v_idx]);
+    }
+
+    for &v_idx in v_idxs {
+        weights[v_idx] = max_weights[&key(v_idx)];
+    }
+}
+
+/// The canonical vertex of each vertex, i.e. itself or for a duplicate the vertex inserted before.
+pub(crate) fn canonical_vertices(
+    num_vertices: usize,
+    duplicate_of: &BTreeMap<VertexIdx, VertexIdx>,
+) -> Vec<VertexIdx> {
+    (0..num_vertices)
+        .map(|v_idx| duplicate_of.get(&v_idx).copied().unwrap_or(v_idx))
+        .collect()
+}
+
+/// The simplices that a single insertion destroyed and created, e.g. to invalidate per-simplex caches precisely.
+///
+/// The slots of destroyed simplices are reused, so an index can be in both lists, it refers to the created simplex then.
+/// Simplices that are created and destroyed again within the insertion, e.g. by consecutive flips, are in neither.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimplexChanges {
+    /// The indices of the simplices before the insertion, that do not exist anymore.
+    pub destroyed: Vec<usize>,
+    /// The indices of the simplices after the insertion, that did not exist before.
+    pub created: Vec<usize>,
+}
+
+impl SimplexChanges {
+    pub(crate) fn destroy(&mut self, idx: usize) {
+        if let Some(pos) = self.created.iter().position(|&created| created == idx) {
+            self.created.swap_remove(pos);
+        } else if !self.destroyed.contains(&idx) {
+            self.destroyed.push(idx);
+        }
+    }
+
+    pub(crate) fn create(&mut self, idx: usize) {
+        if !self.created.contains(&idx) {
+            self.created.push(idx);
+        }
+    }
+
+    /// The simplex at `from` was moved to `to`, e.g. when compacting the data structure.
+    pub(crate) fn relocate(&mut self, from: usize, to: usize) {
+        if let Some(created) = self.created.iter_mut().find(|created| **created == from) {
+            *created = to;
+        } else {
+            self.destroy(from);
+            self.create(to);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversions of point types from other crates into vertices, e.g. for [`Triangulation::insert_vertices`](crate::Triangulation::insert_vertices).
+//!
+//! Arrays are always supported, the point types of [nalgebra], [glam] and [geo-types](geo_types) behind the features
+//! of the same name.
+
+use crate::utils::types::{Vertex2, Vertex3};
+
+/// A 2D point, that can be inserted as a vertex of a [`Triangulation`](crate::Triangulation).
+pub trait IntoVertex2 {
+    /// The coordinates of the point.
+    fn into_vertex2(self) -> Vertex2;
+}
+
+/// A 3D point, that can be inserted as a vertex of a [`Tetrahedralization`](crate::Tetrahedralization).
+pub trait IntoVertex3 {
+    /// The coordinates of the point.
+    fn into_vertex3(self) -> Vertex3;
+}
+
+impl IntoVertex2 for Vertex2 {
+    fn into_vertex2(self) -> Vertex2 {
+        self
+    }
+}
+
+impl IntoVertex3 for Vertex3 {
+    fn into_vertex3(self) -> Vertex3 {
+        self
+    }
+}
+
+impl<T: IntoVertex2 + Copy> IntoVertex2 for &T {
+    fn into_vertex2(self) -> Vertex2 {
+        (*self).into_vertex2()
+    }
+}
+
+impl<T: IntoVertex3 + Copy> IntoVertex3 for &T {
+    fn into_vertex3(self) -> Vertex3 {
+        (*self).into_vertex3()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl IntoVertex2 for nalgebra::Point2<f64> {
+    fn into_vertex2(self) -> Vertex2 {
+        [self.x, self.y]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl IntoVertex3 for nalgebra::Point3<f64> {
+    fn into_vertex3(self) -> Vertex3 {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "glam")]
+impl IntoVertex2 for glam::DVec2 {
+    fn into_vertex2(self) -> Vertex2 {
+        self.to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl IntoVertex3 for glam::DVec3 {
+    fn into_vertex3(self) -> Vertex3 {
+        self.to_array()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl IntoVertex2 for geo_types::Coord<f64> {
+    fn into_vertex2(self) -> Vertex2 {
+        [self.x, self.y]
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl IntoVertex2 for geo_types::Point<f64> {
+    fn into_vertex2(self) -> Vertex2 {
+        self.0.into_vertex2()
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::{Tetrahedralization, Triangulation};
+
+    const SQUARE: [Vertex2; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+    #[test]
+    fn test_insert_iterators() {
+        // by value, by reference and from an iterator
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(SQUARE, None, false).unwrap();
+        assert_eq!(triangulation.vertices(), &SQUARE.to_vec());
+
+        let vertices = SQUARE.to_vec();
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, false)
+            .unwrap();
+        assert_eq!(triangulation.vertices(), &vertices);
+
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(
+                (0..8).map(|i| [i & 1, (i >> 1) & 1, i >> 2].map(f64::from)),
+                None,
+                false,
+            )
+            .unwrap();
+        assert_eq!(tetrahedralization.vertices().len(), 8);
+        assert!(tetrahedralization.num_casual_tets() >= 5);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        let points: Vec<nalgebra::Point2<f64>> = SQUARE.iter().map(|&v| v.into()).collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(&points, None, false).unwrap();
+        assert_eq!(triangulation.vertices(), &SQUARE.to_vec());
+
+        assert_eq!(
+            nalgebra::Point3::new(1.0, 2.0, 3.0).into_vertex3(),
+            [1.0, 2.0, 3.0]
+        );
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam() {
+        let points = SQUARE.map(glam::DVec2::from_array);
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(points, None, false).unwrap();
+        assert_eq!(triangulation.vertices(), &SQUARE.to_vec());
+
+        assert_eq!(
+            glam::DVec3::new(1.0, 2.0, 3.0).into_vertex3(),
+            [1.0, 2.0, 3.0]
+        );
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn test_geo_types() {
+        let coords = SQUARE.map(|[x, y]| geo_types::Coord { x, y });
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(coords, None, false).unwrap();
+        assert_eq!(triangulation.vertices(), &SQUARE.to_vec());
+
+        assert_eq!(geo_types::Point::new(1.0, 2.0).into_vertex2(), [1.0, 2.0]);
+    }
+}
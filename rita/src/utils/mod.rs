@@ -1,3 +1,10 @@
+pub(crate) mod bounded;
+pub(crate) mod compact;
 pub(crate) mod convexity;
+pub(crate) mod insert_status;
+pub(crate) mod into_vertex;
+pub(crate) mod parallel;
+pub(crate) mod point_grid;
 pub(crate) mod point_order;
+pub(crate) mod translation;
 pub mod types;
@@ -0,0 +1,134 @@
+//! The parallel iterators of [rayon] with the `parallel` feature, and sequential stand-ins of the same name without it.
+//!
+//! Import `prelude::*` in place of `rayon::prelude::*`, the code using it runs on a single thread then.
+
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::{current_num_threads, prelude};
+
+/// The number of threads available, a single one without the `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+pub(crate) const fn current_num_threads() -> usize {
+    1
+}
+
+/// The thread pool of the parallel methods of a structure, built on the first call for its most threads.
+///
+/// Clones share the pool, e.g. the one of a triangulation rebuilt without some vertices.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ThreadPool(std::sync::OnceLock<Option<alloc::sync::Arc<rayon::ThreadPool>>>);
+
+#[cfg(feature = "parallel")]
+impl ThreadPool {
+    pub(crate) const fn new() -> Self {
+        Self(std::sync::OnceLock::new())
+    }
+
+    /// Run `op` on a pool of at most `max_threads` threads, or on the current pool for `None`.
+    ///
+    /// The current pool is the global one, or the one of a surrounding [`rayon::ThreadPool::install`].
+    /// Falls back to it as well, if the threads can not be spawned. `max_threads` is the one of the first call,
+    /// set a new `ThreadPool` to change it.
+    pub(crate) fn install<R: Send>(
+        &self,
+        max_threads: Option<usize>,
+        op: impl FnOnce() -> R + Send,
+    ) -> R {
+        let pool = self.0.get_or_init(|| {
+            max_threads.and_then(|max_threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(max_threads.max(1))
+                    .build()
+                    .ok()
+                    .map(alloc::sync::Arc::new)
+            })
+        });
+
+        match pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+
+    /// The pool, if it is built and bounds the threads.
+    #[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+    pub(crate) fn get(&self) -> Option<&alloc::sync::Arc<rayon::ThreadPool>> {
+        self.0.get().and_then(Option::as_ref)
+    }
+}
+
+/// A stand-in without the `parallel` feature, that runs on the current thread.
+#[cfg(not(feature = "parallel"))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ThreadPool;
+
+#[cfg(not(feature = "parallel"))]
+impl ThreadPool {
+    pub(crate) const fn new() -> Self {
+        Self
+    }
+
+    /// Run `op` on the current thread.
+    pub(crate) fn install<R: Send>(
+        &self,
+        _max_threads: Option<usize>,
+        op: impl FnOnce() -> R + Send,
+    ) -> R {
+        op()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) mod prelude {
+    use alloc::vec::Vec;
+    use core::{cmp::Ordering, slice::Chunks};
+
+    pub(crate) trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<T: IntoIterator> IntoParallelIterator for T {}
+
+    pub(crate) trait ParallelSlice<T> {
+        fn par_chunks(&self, chunk_size: usize) -> Chunks<'_, T>;
+    }
+
+    impl<T> ParallelSlice<T> for [T] {
+        fn par_chunks(&self, chunk_size: usize) -> Chunks<'_, T> {
+            self.chunks(chunk_size)
+        }
+    }
+
+    pub(crate) trait ParallelSliceMut<T> {
+        fn par_sort_unstable(&mut self)
+        where
+            T: Ord;
+
+        fn par_sort_unstable_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering);
+    }
+
+    impl<T> ParallelSliceMut<T> for [T] {
+        fn par_sort_unstable(&mut self)
+        where
+            T: Ord,
+        {
+            self.sort_unstable();
+        }
+
+        fn par_sort_unstable_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+            self.sort_unstable_by(compare);
+        }
+    }
+
+    pub(crate) trait ParallelExtend<T> {
+        fn par_extend(&mut self, items: impl IntoIterator<Item = T>);
+    }
+
+    impl<T> ParallelExtend<T> for Vec<T> {
+        fn par_extend(&mut self, items: impl IntoIterator<Item = T>) {
+            self.extend(items);
+        }
+    }
+}
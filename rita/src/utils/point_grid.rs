@@ -0,0 +1,161 @@
+//! A uniform grid over a set of points, to find the points near a region without scanning all of them.
+
+use alloc::{vec, vec::Vec};
+
+use super::types::{Vertex2, VertexIdx};
+
+/// The mean number of points per cell the grid is built for.
+const POINTS_PER_CELL: usize = 2;
+
+/// A uniform grid of square cells over the bounding box of points, with the points of each cell.
+///
+/// The points of cell `i` are `points[offsets[i]..offsets[i + 1]]`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PointGrid {
+    /// The lower corner of the grid.
+    min: Vertex2,
+    /// The inverse size of a cell.
+    inv_cell_size: f64,
+    /// The number of cells along each axis.
+    resolution: [usize; 2],
+    offsets: Vec<usize>,
+    points: Vec<VertexIdx>,
+}
+
+impl PointGrid {
+    /// Build the grid over the vertices `idxs`, which have to be finite.
+    pub(crate) fn new(vertices: &[Vertex2], idxs: &[VertexIdx]) -> Self {
+        let mut min = [f64::INFINITY; 2];
+        let mut max = [f64::NEG_INFINITY; 2];
+        for &v_idx in idxs {
+            for i in 0..2 {
+                min[i] = min[i].min(vertices[v_idx][i]);
+                max[i] = max[i].max(vertices[v_idx][i]);
+            }
+        }
+
+        let extent = [max[0] - min[0], max[1] - min[1]];
+        let area = extent[0] * extent[1];
+        let num_cells = idxs.len().div_ceil(POINTS_PER_CELL).max(1);
+        let cell_size = if area > 0.0 {
+            (area / num_cells as f64).sqrt()
+        } else {
+            // all points are on a line, so the grid is a row of cells along it
+            extent[0].max(extent[1]) / num_cells as f64
+        };
+        let inv_cell_size = if cell_size > 0.0 {
+            1.0 / cell_size
+        } else {
+            0.0
+        };
+        let resolution =
+            extent.map(|extent| ((extent * inv_cell_size).ceil() as usize).clamp(1, num_cells));
+
+        let mut grid = Self {
+            min,
+            inv_cell_size,
+            resolution,
+            offsets: vec![0; resolution[0] * resolution[1] + 1],
+            points: vec![0; idxs.len()],
+        };
+
+        let cells: Vec<usize> = idxs
+            .iter()
+            .map(|&v_idx| grid.cell_of(vertices[v_idx]))
+            .collect();
+        for &cell in &cells {
+            grid.offsets[cell + 1] += 1;
+        }
+        for cell in 0..grid.offsets.len() - 1 {
+            grid.offsets[cell + 1] += grid.offsets[cell];
+        }
+        let mut next = grid.offsets.clone();
+        for (&v_idx, &cell) in idxs.iter().zip(&cells) {
+            grid.points[next[cell]] = v_idx;
+            next[cell] += 1;
+        }
+
+        grid
+    }
+
+    /// The coordinates of the cell of a point, points beyond the grid use the closest cell.
+    fn coords_of(&self, point: Vertex2) -> [usize; 2] {
+        // the float to int cast saturates, i.e. points below the grid end up in the first cell
+        core::array::from_fn(|i| {
+            (((point[i] - self.min[i]) * self.inv_cell_size) as usize).min(self.resolution[i] - 1)
+        })
+    }
+
+    fn cell_of(&self, point: Vertex2) -> usize {
+        let [x, y] = self.coords_of(point);
+        y * self.resolution[0] + x
+    }
+
+    /// Whether one of the points within `radius` of `center` fulfills `pred`, some points beyond may be checked as well.
+    ///
+    /// Returns `None` if the disk overlaps more than `max_cells` cells, or its radius is not finite.
+    pub(crate) fn any_near(
+        &self,
+        center: Vertex2,
+        radius: f64,
+        max_cells: usize,
+        mut pred: impl FnMut(VertexIdx) -> bool,
+    ) -> Option<bool> {
+        if !radius.is_finite() || !center.iter().all(|x| x.is_finite()) {
+            return None;
+        }
+
+        let lower = self.coords_of([center[0] - radius, center[1] - radius]);
+        let upper = self.coords_of([center[0] + radius, center[1] + radius]);
+        if (upper[0] - lower[0] + 1).saturating_mul(upper[1] - lower[1] + 1) > max_cells {
+            return None;
+        }
+
+        for y in lower[1]..=upper[1] {
+            let row = y * self.resolution[0];
+            let cells = self.offsets[row + lower[0]]..self.offsets[row + upper[0] + 1];
+            if self.points[cells].iter().any(|&v_idx| pred(v_idx)) {
+                return Some(true);
+            }
+        }
+
+        Some(false)
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_grid() {
+        let vertices: Vec<Vertex2> = (0..100)
+            .map(|i| [f64::from(i % 10), f64::from(i / 10)])
+            .collect();
+        let idxs: Vec<VertexIdx> = (0..100).collect();
+        let grid = PointGrid::new(&vertices, &idxs);
+
+        let near = |center, radius| {
+            let mut found = Vec::new();
+            let result = grid.any_near(center, radius, 4, |v_idx| {
+                found.push(v_idx);
+                false
+            });
+            result.map(|_| found)
+        };
+        let found = near([0.1, 0.1], 0.5).unwrap();
+        assert!(found.contains(&0) && !found.contains(&99));
+        assert!(near([9.0, 9.0], 0.5).unwrap().contains(&99));
+        assert_eq!(near([4.5, 4.5], 10.0), None);
+        assert_eq!(near([4.5, 4.5], f64::NAN), None);
+
+        assert_eq!(
+            grid.any_near([3.0, 2.0], 0.1, 4, |v_idx| v_idx == 23),
+            Some(true)
+        );
+        assert_eq!(
+            grid.any_near([3.0, 2.0], 0.1, 4, |v_idx| v_idx == 99),
+            Some(false)
+        );
+    }
+}
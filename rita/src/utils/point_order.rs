@@ -1,9 +1,189 @@
 use super::types::{Vertex2, Vertex3};
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+
+/// The order in which a batch of vertices is inserted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertOrder {
+    /// The order of the input.
+    Input,
+    /// Along a Hilbert curve, which keeps the point location walks short.
+    #[default]
+    Hilbert,
+    /// A random permutation, which is reproducible given the seed.
+    ///
+    /// Useful for structured inputs in an adversarial order, where spatial sorting does not help.
+    Random(u64),
+    /// Along a Hilbert curve, but the heaviest vertices of each small stretch of the curve first.
+    ///
+    /// Dominant vertices then tend to be in place before the lighter vertices around them,
+    /// which are rejected as redundant right away, instead of being inserted and flipped out again.
+    /// The same as [`InsertOrder::Hilbert`] for unweighted vertices.
+    WeightedHilbert,
+    /// Along a Morton curve, i.e. in Z-order, which is cheaper to compute, but jumps between the quadrants.
+    Morton,
+    /// A biased randomized insertion order (BRIO): random rounds of doubling size, each along a Hilbert curve.
+    ///
+    /// Keeps the walks short like the curve, while the random rounds avoid the worst cases of a fixed order,
+    /// e.g. on large structured inputs. Reproducible given the seed.
+    Brio(u64),
+}
+
+/// The toggle of spatial sorting, `true` for [`InsertOrder::Hilbert`] and `false` for [`InsertOrder::Input`].
+impl From<bool> for InsertOrder {
+    fn from(spatial_sorting: bool) -> Self {
+        if spatial_sorting {
+            Self::Hilbert
+        } else {
+            Self::Input
+        }
+    }
+}
+
+/// The number of consecutive vertices along the Hilbert curve, that are reordered by weight.
+const WEIGHT_BUCKET_SIZE: usize = 64;
+
+/// Reorders buckets of consecutive indices by ascending weight.
+///
+/// Vertices are popped from the end for insertion, so the heaviest vertex of each bucket is inserted first.
+pub(crate) fn sort_buckets_by_weight(indices: &mut [usize], weights: &[f64]) {
+    for bucket in indices.chunks_mut(WEIGHT_BUCKET_SIZE) {
+        bucket.sort_by(|&a, &b| weights[a].total_cmp(&weights[b]));
+    }
+}
+
+/// A small, seedable pseudo random number generator (SplitMix64).
+///
+/// Keeps any randomized construction reproducible, without an external dependency.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..n`, for `n > 0`.
+    pub(crate) const fn below(&mut self, n: usize) -> usize {
+        // multiply-shift instead of modulo, to avoid the bias for large n
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+/// Shuffles indices with a Fisher-Yates shuffle, seeded for reproducibility.
+pub(crate) fn shuffle(indices: &mut [usize], seed: u64) {
+    let mut rng = SplitMix64::new(seed);
+    for i in (1..indices.len()).rev() {
+        indices.swap(i, rng.below(i + 1));
+    }
+}
+
+/// The first round of an [`InsertOrder::Brio`] has at least about `2^MIN_BRIO_ROUND_BITS` vertices.
+const MIN_BRIO_ROUND_BITS: u32 = 6;
+
+/// Distributes indices to random rounds, each round twice as large as the one before, and sorts each round.
+///
+/// The rounds are in the order of their insertion, i.e. the smallest one first.
+fn brio(indices: &[usize], seed: u64, sort_round: impl Fn(Vec<usize>) -> Vec<usize>) -> Vec<usize> {
+    let num_rounds =
+        (usize::BITS - indices.len().leading_zeros()).saturating_sub(MIN_BRIO_ROUND_BITS);
+
+    // a vertex is in the round `k` with probability `2^-(k + 1)`, the rounds after the first one have half the vertices
+    let mut rounds = vec![Vec::new(); num_rounds as usize + 1];
+    let mut rng = SplitMix64::new(seed);
+    for &idx in indices {
+        let round = rng.next_u64().trailing_zeros().min(num_rounds);
+        rounds[round as usize].push(idx);
+    }
+
+    rounds.into_iter().rev().flat_map(sort_round).collect()
+}
+
+/// Orders vertices in a biased randomized insertion order, with each round along a 2D Hilbert curve.
+///
+/// Unlike the curves, the order has a direction: insert the vertices from the front, the first round is the smallest.
+pub fn brio_order_2d(vertices: &[Vertex2], indices_to_add: &[usize], seed: u64) -> Vec<usize> {
+    brio(indices_to_add, seed, |round| {
+        sort_along_hilbert_curve_2d(vertices, &round)
+    })
+}
+
+/// Orders vertices in a biased randomized insertion order, with each round along a 3D Hilbert curve.
+///
+/// Unlike the curves, the order has a direction: insert the vertices from the front, the first round is the smallest.
+pub fn brio_order_3d(vertices: &[Vertex3], indices_to_add: &[usize], seed: u64) -> Vec<usize> {
+    brio(indices_to_add, seed, |round| {
+        sort_along_hilbert_curve_3d(vertices, round)
+    })
+}
+
+/// Sorts indices by the Morton code of their vertex, i.e. by interleaving the bits of the quantized coordinates.
+fn sort_along_morton_curve<const D: usize>(
+    vertices: &[[f64; D]],
+    indices_to_add: &[usize],
+) -> Vec<usize> {
+    let Some(&first) = indices_to_add.first() else {
+        return Vec::new();
+    };
+
+    let (mut v_min, mut v_max) = (vertices[first], vertices[first]);
+    for &ind in indices_to_add {
+        for d in 0..D {
+            v_min[d] = v_min[d].min(vertices[ind][d]);
+            v_max[d] = v_max[d].max(vertices[ind][d]);
+        }
+    }
+
+    let bits = u64::BITS / D as u32;
+    let scale = ((1_u64 << bits) - 1) as f64;
+    let code = |ind: usize| {
+        let quantized: [u64; D] = core::array::from_fn(|d| {
+            let extent = v_max[d] - v_min[d];
+            if extent > 0.0 {
+                ((vertices[ind][d] - v_min[d]) / extent * scale) as u64
+            } else {
+                0
+            }
+        });
+
+        let mut code = 0_u64;
+        for bit in (0..bits).rev() {
+            for q in quantized {
+                code = (code << 1) | ((q >> bit) & 1);
+            }
+        }
+        code
+    };
+
+    let mut coded: Vec<(u64, usize)> = indices_to_add.iter().map(|&ind| (code(ind), ind)).collect();
+    coded.sort_unstable();
+    coded.into_iter().map(|(_, ind)| ind).collect()
+}
+
+/// Sorts vertices along 2D Morton curve
+pub fn sort_along_morton_curve_2d(vertices: &[Vertex2], indices_to_add: &[usize]) -> Vec<usize> {
+    sort_along_morton_curve(vertices, indices_to_add)
+}
+
+/// Sorts vertices along 3D Morton curve
+pub fn sort_along_morton_curve_3d(vertices: &[Vertex3], indices_to_add: &[usize]) -> Vec<usize> {
+    sort_along_morton_curve(vertices, indices_to_add)
+}
 
 /// Sorts vertices along 2D Hilbert curve
 pub fn sort_along_hilbert_curve_2d(vertices: &[Vertex2], indices_to_add: &[usize]) -> Vec<usize> {
     let mut curve_order = Vec::new();
+    if indices_to_add.is_empty() {
+        return curve_order;
+    }
 
     let (v_min, v_max) = find_min_max_2d(vertices, indices_to_add);
 
@@ -13,6 +193,10 @@ pub fn sort_along_hilbert_curve_2d(vertices: &[Vertex2], indices_to_add: &[usize
 
     while let Some((rot, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
         match indices_to_add.len().cmp(&1) {
+            // duplicates can not be separated by subdividing
+            core::cmp::Ordering::Greater if coincide(vertices, &indices_to_add) => {
+                curve_order.extend(indices_to_add);
+            }
             core::cmp::Ordering::Greater => {
                 let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
                 let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
@@ -99,6 +283,13 @@ pub fn sort_along_hilbert_curve_2d(vertices: &[Vertex2], indices_to_add: &[usize
     curve_order
 }
 
+/// Whether all vertices are at the same position
+fn coincide<const D: usize>(vertices: &[[f64; D]], indices: &[usize]) -> bool {
+    indices
+        .iter()
+        .all(|&ind| vertices[ind] == vertices[indices[0]])
+}
+
 // Finds the minimum and maximum x and y values of the vertices
 fn find_min_max_2d(vertices: &[Vertex2], indices_to_add: &[usize]) -> (Vertex2, Vertex2) {
     let mut v_min = vertices[indices_to_add[0]];
@@ -125,6 +316,9 @@ fn find_min_max_2d(vertices: &[Vertex2], indices_to_add: &[usize]) -> (Vertex2,
 /// Sorts vertices along 3D Hilbert curve
 pub fn sort_along_hilbert_curve_3d(vertices: &[Vertex3], indices_to_add: Vec<usize>) -> Vec<usize> {
     let mut curve_order = Vec::new();
+    if indices_to_add.is_empty() {
+        return curve_order;
+    }
 
     let mut pt_min = vertices[indices_to_add[0]];
     let mut pt_max = vertices[indices_to_add[0]];
@@ -155,6 +349,10 @@ pub fn sort_along_hilbert_curve_3d(vertices: &[Vertex3], indices_to_add: Vec<usi
 
     while let Some((start, dir, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
         match indices_to_add.len().cmp(&1) {
+            // duplicates can not be separated by subdividing
+            core::cmp::Ordering::Greater if coincide(vertices, &indices_to_add) => {
+                curve_order.extend(indices_to_add);
+            }
             core::cmp::Ordering::Greater => {
                 let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
                 let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
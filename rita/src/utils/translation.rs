@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+/// Check if `x - origin` can be computed without rounding, via the error term of Knuth's two-sum.
+fn is_exact_difference(x: f64, origin: f64) -> bool {
+    let b = -origin;
+    let s = x + b;
+    let b_virtual = s - x;
+    let a_virtual = s - b_virtual;
+
+    s.is_finite() && (x - a_virtual) + (b - b_virtual) == 0.0
+}
+
+/// Check if a vertex can be translated exactly, i.e. every coordinate of `v - origin` is representable.
+///
+/// Non-finite vertices are never part of a triangulation, so they count as exact.
+pub(crate) fn is_exact_translation<const D: usize>(v: &[f64; D], origin: &[f64; D]) -> bool {
+    !v.iter().all(|x| x.is_finite())
+        || v.iter()
+            .zip(origin)
+            .all(|(&x, &o)| is_exact_difference(x, o))
+}
+
+/// Translate a vertex into the local frame of `origin`.
+#[inline]
+pub(crate) fn to_local<const D: usize>(v: &[f64; D], origin: &[f64; D]) -> [f64; D] {
+    core::array::from_fn(|i| v[i] - origin[i])
+}
+
+/// The distance between two adjacent doubles at the magnitude of `x`, i.e. the unit in the last place.
+fn ulp(x: f64) -> f64 {
+    let exponent_bits = x.abs().to_bits() & (0x7ff << 52);
+    f64::from_bits(exponent_bits) * f64::EPSILON
+}
+
+/// Snap `center` to the grid spanned by the unit in the last place of `max_abs`, if it is usable.
+fn snap_to_grid(center: f64, max_abs: f64) -> Option<f64> {
+    let grid = ulp(max_abs);
+    if grid == 0.0 || !grid.is_normal() {
+        return None;
+    }
+
+    let snapped = (center / grid).round() * grid;
+    snapped.is_finite().then_some(snapped)
+}
+
+/// Compute an origin close to the centroid of `vertices`, such that all vertices can be translated exactly.
+///
+/// The centroid is snapped to the grid spanned by the unit in the last place of the largest coordinate,
+/// on which every coordinate lies. Any axis for which this does not yield an exact translation
+/// for all vertices keeps its origin at zero. Non-finite vertices are not considered.
+pub(crate) fn local_origin<const D: usize>(vertices: &[[f64; D]]) -> [f64; D] {
+    let mut origin = [0.0; D];
+
+    let vertices: Vec<&[f64; D]> = vertices
+        .iter()
+        .filter(|v| v.iter().all(|x| x.is_finite()))
+        .collect();
+    if vertices.is_empty() {
+        return origin;
+    }
+
+    for (axis, o) in origin.iter_mut().enumerate() {
+        let mut sum = 0.0;
+        let mut max_abs: f64 = 0.0;
+        for v in &vertices {
+            sum += v[axis];
+            max_abs = max_abs.max(v[axis].abs());
+        }
+
+        let centroid = sum / vertices.len() as f64;
+        if let Some(snapped) = snap_to_grid(centroid, max_abs) {
+            if vertices
+                .iter()
+                .all(|v| is_exact_difference(v[axis], snapped))
+            {
+                *o = snapped;
+            }
+        }
+    }
+
+    origin
+}
+
+/// Compute an origin close to the center of the bounding box `[min, max]` of `vertices`, see [`local_origin`].
+///
+/// Skips the scan for the centroid and the largest coordinate, but still checks every vertex for an exact translation,
+/// so a box not containing all vertices only moves the origin.
+pub(crate) fn bbox_origin<const D: usize>(
+    vertices: &[[f64; D]],
+    [min, max]: [[f64; D]; 2],
+) -> [f64; D] {
+    let mut origin = [0.0; D];
+
+    for (axis, o) in origin.iter_mut().enumerate() {
+        let center = min[axis] / 2.0 + max[axis] / 2.0;
+        let max_abs = min[axis].abs().max(max[axis].abs());
+
+        if let Some(snapped) = snap_to_grid(center, max_abs) {
+            if vertices
+                .iter()
+                .all(|v| !v[axis].is_finite() || is_exact_difference(v[axis], snapped))
+            {
+                *o = snapped;
+            }
+        }
+    }
+
+    origin
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_origin() {
+        let vertices = [
+            [500_000.123, 5_700_000.5],
+            [500_010.987, 5_700_003.25],
+            [500_004.5, 5_700_011.0],
+        ];
+
+        let origin = local_origin(&vertices);
+
+        assert!((origin[0] - 500_005.2).abs() < 1.0);
+        assert!((origin[1] - 5_700_004.9).abs() < 1.0);
+
+        for v in &vertices {
+            assert!(is_exact_translation(v, &origin));
+            let local = to_local(v, &origin);
+            assert_eq!(local[0] + origin[0], v[0]);
+            assert_eq!(local[1] + origin[1], v[1]);
+        }
+    }
+
+    #[test]
+    fn test_bbox_origin() {
+        let vertices = [
+            [500_000.123, 5_700_000.5],
+            [500_010.987, 5_700_003.25],
+            [500_004.5, 5_700_011.0],
+        ];
+
+        let origin = bbox_origin(
+            &vertices,
+            [[500_000.123, 5_700_000.5], [500_010.987, 5_700_011.0]],
+        );
+
+        assert!((origin[0] - 500_005.555).abs() < 1.0);
+        assert!((origin[1] - 5_700_005.75).abs() < 1.0);
+        assert!(vertices.iter().all(|v| is_exact_translation(v, &origin)));
+
+        // a box far from the vertices still yields an exact translation, or none
+        let origin = bbox_origin(&[[1e-300, 0.0]], [[-1e10, 0.0], [3e10, 1.0]]);
+        assert_eq!(origin[0], 0.0);
+    }
+
+    #[test]
+    fn test_local_origin_falls_back_to_zero() {
+        // a tiny coordinate next to a huge one can not be translated exactly
+        let vertices = [[1e-300, 0.0], [1e10, 1.0]];
+
+        assert_eq!(local_origin(&vertices)[0], 0.0);
+        assert_eq!(local_origin::<2>(&[]), [0.0, 0.0]);
+    }
+}
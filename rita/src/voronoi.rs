@@ -0,0 +1,872 @@
+//! Extraction of the Voronoi and power diagram, i.e. the dual of a 2D (weighted) triangulation.
+//!
+//! Every casual triangle corresponds to a Voronoi vertex at its circumcenter, or to a power vertex at its
+//! orthocenter, every vertex of the triangulation to a cell. Cells of vertices on the convex hull are unbounded.
+//!
+//! Clipped to a bounding box, see [`clipped_power_cells`], the centroids of the cells give a step of Lloyd's relaxation,
+//! see [`Triangulation::lloyd_step`].
+//!
+//! The Voronoi diagram also gives the largest empty circle centered inside the convex hull, see [`largest_empty_circle`],
+//! and the analogous sphere of a [`Tetrahedralization`], see [`largest_empty_sphere`].
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    RitaError, Tetrahedralization, Triangulation,
+    balls::clip_polygon,
+    decimation::vertex_neighbors,
+    geometry::{cross, dot, polygon_area, sub},
+    tetrahedralization::Location3,
+    triangulation::Location,
+    trids::hedge_iterator::HedgeIterator,
+    utils::types::{Vertex2, Vertex3, VertexIdx},
+};
+use anyhow::{Ok as HowOk, Result as HowResult};
+
+/// The Voronoi or power cell of a single vertex.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VoronoiCell {
+    /// The finite corners of the cell, in counter-clockwise order.
+    pub vertices: Vec<Vertex2>,
+    /// For unbounded cells, the unit directions of the two infinite rays.
+    ///
+    /// The first ray ends at the first vertex, the second one starts at the last vertex,
+    /// i.e. walking from infinity along `-rays[0]`, then along `vertices` and back to infinity along `rays[1]`
+    /// traverses the boundary counter-clockwise.
+    pub rays: Option<[Vertex2; 2]>,
+}
+
+impl VoronoiCell {
+    /// Check if the cell is empty, i.e. the vertex is not part of the triangulation.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Check if the cell is bounded, i.e. the vertex is not on the convex hull.
+    pub const fn is_bounded(&self) -> bool {
+        self.rays.is_none()
+    }
+}
+
+/// The point with equal power distance to the three weighted vertices of a triangle.
+///
+/// Without weights, this is the circumcenter.
+pub(crate) fn orthocenter([a, b, c]: [Vertex2; 3], [w_a, w_b, w_c]: [f64; 3]) -> Vertex2 {
+    // translate to `a` to reduce cancellation
+    let [bx, by] = [b[0] - a[0], b[1] - a[1]];
+    let [cx, cy] = [c[0] - a[0], c[1] - a[1]];
+
+    let d = 2.0 * (bx * cy - by * cx);
+    let b_sq = bx * bx + by * by - w_b + w_a;
+    let c_sq = cx * cx + cy * cy - w_c + w_a;
+
+    [
+        a[0] + (cy * b_sq - by * c_sq) / d,
+        a[1] + (bx * c_sq - cx * b_sq) / d,
+    ]
+}
+
+/// The outward unit normal of the hull edge `from -> to`, with the triangulation on the given side.
+fn hull_normal(from: Vertex2, to: Vertex2, inside_is_left: bool) -> Vertex2 {
+    let [dx, dy] = [to[0] - from[0], to[1] - from[1]];
+    let len = dx.hypot(dy);
+
+    if inside_is_left {
+        [dy / len, -dx / len]
+    } else {
+        [-dy / len, dx / len]
+    }
+}
+
+/// Compute the Voronoi diagram of a triangulation.
+///
+/// Returns one cell per vertex of the triangulation, in the same order as [`Triangulation::vertices`].
+/// Vertices that are not part of the triangulation, i.e. ignored or redundant ones, have empty cells.
+///
+/// For weighted triangulations the circumcenters do not form a valid diagram,
+/// the dual of those is the power diagram, see [`power_cells`].
+///
+/// ## Errors
+/// Returns an error if the triangulation has no triangles.
+pub fn voronoi_cells(triangulation: &Triangulation) -> HowResult<Vec<VoronoiCell>> {
+    let vertices = triangulation.vertices();
+    dual_cells(triangulation, |tri| {
+        orthocenter(tri.map(|v_idx| vertices[v_idx]), [0.0; 3])
+    })
+}
+
+/// Compute the power diagram, i.e. the weighted Voronoi diagram, of a triangulation.
+///
+/// Returns one cell per vertex of the triangulation, in the same order as [`Triangulation::vertices`].
+/// Redundant vertices, i.e. those whose power cell vanishes due to their weight, and ignored vertices have empty cells.
+/// For unweighted triangulations this is the same as [`voronoi_cells`].
+///
+/// ## Errors
+/// Returns an error if the triangulation has no triangles.
+pub fn power_cells(triangulation: &Triangulation) -> HowResult<Vec<VoronoiCell>> {
+    let vertices = triangulation.vertices();
+    let weight = |v_idx: VertexIdx| {
+        triangulation
+            .weights()
+            .as_ref()
+            .map_or(0.0, |weights| weights[v_idx])
+    };
+    dual_cells(triangulation, |tri| {
+        orthocenter(tri.map(|v_idx| vertices[v_idx]), tri.map(weight))
+    })
+}
+
+/// Collect the cells around each vertex, with the given dual vertex per casual triangle.
+fn dual_cells(
+    triangulation: &Triangulation,
+    center: impl Fn([VertexIdx; 3]) -> Vertex2,
+) -> HowResult<Vec<VoronoiCell>> {
+    if triangulation.num_tris() == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    let tds = triangulation.tds();
+    let vertices = triangulation.vertices();
+
+    // one hedge starting at each vertex and the dual vertex of each casual triangle
+    let mut v_hedges = vec![None; vertices.len()];
+    let mut centers = vec![None; triangulation.num_all_tris()];
+    for (tri_idx, dual) in centers.iter_mut().enumerate() {
+        let tri = tds.get_tri(tri_idx)?;
+        if tri.is_deleted() {
+            continue;
+        }
+
+        for hedge in tri.hedges() {
+            if let Some(v_idx) = hedge.starting_node().idx() {
+                v_hedges[v_idx] = Some(hedge.idx);
+            }
+        }
+
+        if tri.is_casual() {
+            *dual = Some(center(tri.nodes().map(|node| node.idx().unwrap())));
+        }
+    }
+
+    let mut cells = vec![VoronoiCell::default(); vertices.len()];
+    for (v_idx, cell) in cells.iter_mut().enumerate() {
+        let Some(hedge_idx) = v_hedges[v_idx] else {
+            continue;
+        };
+
+        // all hedges starting at the vertex, in counter-clockwise order
+        let mut fan: Vec<HedgeIterator> = Vec::new();
+        let mut hedge = tds.get_hedge(hedge_idx)?;
+        loop {
+            fan.push(hedge.clone());
+            hedge = hedge.prev().twin();
+            if hedge.idx == hedge_idx {
+                break;
+            }
+        }
+
+        // for hull vertices, start right after the conceptual triangles and stop at the next one
+        let is_conceptual = |hedge: &HedgeIterator| hedge.tri().is_conceptual();
+        if fan.iter().any(is_conceptual) {
+            let start = (0..fan.len())
+                .find(|&i| {
+                    !is_conceptual(&fan[i]) && is_conceptual(&fan[(i + fan.len() - 1) % fan.len()])
+                })
+                .unwrap_or(0);
+            fan.rotate_left(start);
+            let end = fan.iter().position(is_conceptual).unwrap_or(fan.len());
+            fan.truncate(end);
+
+            let (Some(first), Some(last)) = (fan.first(), fan.last()) else {
+                continue;
+            };
+
+            let v = vertices[v_idx];
+            let first_to = vertices[first.end_node().idx().unwrap()];
+            let last_to = vertices[last.prev().starting_node().idx().unwrap()];
+            cell.rays = Some([
+                hull_normal(v, first_to, true),
+                hull_normal(v, last_to, false),
+            ]);
+        }
+
+        cell.vertices = fan
+            .iter()
+            .filter_map(|hedge| centers[hedge.tri().idx])
+            .collect();
+    }
+
+    HowOk(cells)
+}
+
+/// Clip a convex polygon to the half-plane `normal · p <= offset`.
+fn clip_half_plane(polygon: &[Vertex2], normal: Vertex2, offset: f64) -> Vec<Vertex2> {
+    let side = |p: Vertex2| normal[0] * p[0] + normal[1] * p[1] - offset;
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &p) in polygon.iter().enumerate() {
+        let q = polygon[(i + 1) % polygon.len()];
+        let (side_p, side_q) = (side(p), side(q));
+
+        if side_p <= 0.0 {
+            clipped.push(p);
+        }
+        if (side_p < 0.0 && side_q > 0.0) || (side_p > 0.0 && side_q < 0.0) {
+            let t = side_p / (side_p - side_q);
+            clipped.push([p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1])]);
+        }
+    }
+
+    clipped
+}
+
+/// The centroid of a counter-clockwise polygon, `None` if it has no area.
+fn polygon_centroid(polygon: &[Vertex2]) -> Option<Vertex2> {
+    let area = polygon_area(polygon);
+    if area <= 0.0 {
+        return None;
+    }
+
+    // translate to the first vertex to reduce cancellation
+    let o = polygon[0];
+    let mut centroid = [0.0; 2];
+    for i in 1..polygon.len() - 1 {
+        let a = [polygon[i][0] - o[0], polygon[i][1] - o[1]];
+        let b = [polygon[i + 1][0] - o[0], polygon[i + 1][1] - o[1]];
+        let cross = a[0] * b[1] - a[1] * b[0];
+        centroid[0] += (a[0] + b[0]) * cross;
+        centroid[1] += (a[1] + b[1]) * cross;
+    }
+
+    Some([
+        o[0] + centroid[0] / (6.0 * area),
+        o[1] + centroid[1] / (6.0 * area),
+    ])
+}
+
+/// The power cells clipped to the box, in coordinates relative to their vertex.
+///
+/// Vertices that are not part of the triangulation, or whose cell misses the box, have empty cells.
+fn clip_cells_relative(triangulation: &Triangulation, bbox: [Vertex2; 2]) -> Vec<Vec<Vertex2>> {
+    let vertices = triangulation.vertices();
+    let weight = |v_idx: VertexIdx| {
+        triangulation
+            .weights()
+            .as_ref()
+            .map_or(0.0, |weights| weights[v_idx])
+    };
+    let [min, max] = bbox;
+
+    let neighbors = vertex_neighbors(triangulation);
+    vertices
+        .iter()
+        .zip(&neighbors)
+        .enumerate()
+        .map(|(v_idx, (&v, ring))| {
+            if ring.is_empty() {
+                return Vec::new();
+            }
+
+            // the box, in coordinates relative to the vertex
+            let mut cell = vec![
+                [min[0] - v[0], min[1] - v[1]],
+                [max[0] - v[0], min[1] - v[1]],
+                [max[0] - v[0], max[1] - v[1]],
+                [min[0] - v[0], max[1] - v[1]],
+            ];
+
+            // the cell is bounded by the power bisectors to its neighbors in the triangulation,
+            // i.e. `|p|² - w_v <= |p - d|² - w_u` for the neighbor `u` at `d` relative to `v`
+            for &u_idx in ring {
+                let d = [vertices[u_idx][0] - v[0], vertices[u_idx][1] - v[1]];
+                let offset = (d[0] * d[0] + d[1] * d[1] - weight(u_idx) + weight(v_idx)) / 2.0;
+                cell = clip_half_plane(&cell, d, offset);
+                if cell.is_empty() {
+                    break;
+                }
+            }
+
+            cell
+        })
+        .collect()
+}
+
+/// Compute the power diagram of a triangulation clipped to an axis-aligned box `[min, max]`.
+///
+/// Returns one counter-clockwise polygon per vertex, in the same order as [`Triangulation::vertices`].
+/// Unlike [`power_cells`] all cells are bounded, cells of vertices that are not part of the triangulation
+/// or that miss the box are empty.
+///
+/// ## Errors
+/// Returns an error if the triangulation has no triangles.
+pub fn clipped_power_cells(
+    triangulation: &Triangulation,
+    bbox: [Vertex2; 2],
+) -> HowResult<Vec<Vec<Vertex2>>> {
+    if triangulation.num_casual_tris() == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    let cells = clip_cells_relative(triangulation, bbox)
+        .into_iter()
+        .zip(triangulation.vertices())
+        .map(|(cell, v)| {
+            cell.into_iter()
+                .map(|c| [v[0] + c[0], v[1] + c[1]])
+                .collect()
+        })
+        .collect();
+
+    HowOk(cells)
+}
+
+/// Compute one step of Lloyd's relaxation, see [`Triangulation::lloyd_step`].
+pub(crate) fn lloyd_step(
+    triangulation: &Triangulation,
+    bbox: [Vertex2; 2],
+) -> HowResult<Vec<Vertex2>> {
+    if triangulation.num_casual_tris() == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    let relaxed = triangulation
+        .vertices()
+        .iter()
+        .zip(clip_cells_relative(triangulation, bbox))
+        .map(|(&v, cell)| polygon_centroid(&cell).map_or(v, |c| [v[0] + c[0], v[1] + c[1]]))
+        .collect();
+
+    HowOk(relaxed)
+}
+
+/// The circumcenter of a tetrahedron.
+fn circumcenter_3d([a, b, c, d]: [Vertex3; 4]) -> Vertex3 {
+    // translate to `a` to reduce cancellation
+    let [b, c, d] = [sub(b, a), sub(c, a), sub(d, a)];
+    let [cd, db, bc] = [cross(c, d), cross(d, b), cross(b, c)];
+    let [b_sq, c_sq, d_sq] = [dot(b, b), dot(c, c), dot(d, d)];
+    let denom = 2.0 * dot(b, cd);
+
+    core::array::from_fn(|i| a[i] + (b_sq * cd[i] + c_sq * db[i] + d_sq * bc[i]) / denom)
+}
+
+/// The empty balls are defined by the Voronoi diagram only, not by a power diagram.
+fn check_empty_ball_args(is_weighted: bool, num_casual_simplices: usize) -> HowResult<()> {
+    if is_weighted {
+        return Err(anyhow::Error::new(RitaError::UnweightedRequired));
+    }
+    if num_casual_simplices == 0 {
+        return Err(anyhow::Error::new(RitaError::EmptyTriangulation));
+    }
+
+    HowOk(())
+}
+
+/// Find the largest circle centered inside the convex hull, that contains no vertex of the triangulation.
+///
+/// The distance to the nearest vertex is maximal either at a Voronoi vertex inside the hull,
+/// or where a Voronoi edge crosses a hull edge. The latter are found by walking along each hull edge
+/// through the cells it crosses.
+///
+/// Returns the center and the radius of the circle, the nearest vertices lie on it.
+///
+/// ## Errors
+/// Returns an error if the triangulation is weighted or has no casual triangles.
+pub fn largest_empty_circle(triangulation: &Triangulation) -> HowResult<(Vertex2, f64)> {
+    check_empty_ball_args(
+        triangulation.weights().is_some(),
+        triangulation.num_casual_tris(),
+    )?;
+
+    let vertices = triangulation.vertices();
+    let dist = |p: Vertex2, v_idx: VertexIdx| {
+        let d = sub(p, vertices[v_idx]);
+        d[0].hypot(d[1])
+    };
+    let tris = triangulation.tri_indices();
+    let mut largest = (vertices[tris[0][0]], 0.0);
+    let mut update = |center: Vertex2, radius: f64| {
+        if radius > largest.1 {
+            largest = (center, radius);
+        }
+    };
+
+    // the Voronoi vertices inside the hull
+    for tri in tris {
+        let center = orthocenter(tri.map(|v_idx| vertices[v_idx]), [0.0; 3]);
+        if center.iter().all(|x| x.is_finite())
+            && !matches!(triangulation.locate(center)?, Location::OutsideHull(_))
+        {
+            update(center, dist(center, tri[0]));
+        }
+    }
+
+    // the Voronoi edges crossing the hull edges
+    let neighbors = vertex_neighbors(triangulation);
+    let hull = triangulation.hull_edges();
+    for (i, &p_idx) in hull.iter().enumerate() {
+        let q_idx = hull[(i + 1) % hull.len()];
+        let p = vertices[p_idx];
+        let pq = sub(vertices[q_idx], p);
+
+        // walk from the cell of `p` to the one of `q`, the edge leaves each cell at the first bisector it crosses
+        let mut s_idx = p_idx;
+        for _ in 0..vertices.len() {
+            if s_idx == q_idx {
+                break;
+            }
+
+            let sp = sub(p, vertices[s_idx]);
+            let Some((t, next_idx)) = neighbors[s_idx]
+                .iter()
+                .filter_map(|&u_idx| {
+                    // `d · (sp + t * pq) <= |d|² / 2` for the neighbor `u` at `d` relative to `s`
+                    let d = sub(vertices[u_idx], vertices[s_idx]);
+                    let along = d[0] * pq[0] + d[1] * pq[1];
+                    (along > 0.0).then(|| {
+                        let offset =
+                            (d[0] * d[0] + d[1] * d[1]) / 2.0 - (d[0] * sp[0] + d[1] * sp[1]);
+                        (offset / along, u_idx)
+                    })
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+            else {
+                break;
+            };
+
+            let crossing = [p[0] + t * pq[0], p[1] + t * pq[1]];
+            update(crossing, dist(crossing, s_idx));
+            s_idx = next_idx;
+        }
+    }
+
+    HowOk(largest)
+}
+
+/// Find the largest sphere centered inside the convex hull, that contains no vertex of the tetrahedralization.
+///
+/// The distance to the nearest vertex is maximal either at a Voronoi vertex inside the hull, where a Voronoi edge
+/// crosses a hull facet, or where a Voronoi face crosses a hull edge. The latter two are the corners of the cells
+/// clipped to each hull facet, which are found by searching the cells across the facet.
+///
+/// Returns the center and the radius of the sphere, the nearest vertices lie on it.
+///
+/// ## Errors
+/// Returns an error if the tetrahedralization is weighted or has no casual tetrahedra.
+pub fn largest_empty_sphere(tetrahedralization: &Tetrahedralization) -> HowResult<(Vertex3, f64)> {
+    check_empty_ball_args(
+        tetrahedralization.weighted(),
+        tetrahedralization.num_casual_tets(),
+    )?;
+
+    let vertices = tetrahedralization.vertices();
+    let tets = tetrahedralization.tet_indices();
+    let mut largest = (vertices[tets[0][0]], 0.0);
+    let mut update = |center: Vertex3, radius: f64| {
+        if radius > largest.1 {
+            largest = (center, radius);
+        }
+    };
+
+    // the Voronoi vertices inside the hull
+    for tet in tets {
+        let center = circumcenter_3d(tet.map(|v_idx| vertices[v_idx]));
+        if center.iter().all(|x| x.is_finite())
+            && !matches!(
+                tetrahedralization.locate(center)?,
+                Location3::OutsideHull(_)
+            )
+        {
+            let d = sub(center, vertices[tet[0]]);
+            update(center, dot(d, d).sqrt());
+        }
+    }
+
+    let mut neighbors = vec![Vec::new(); vertices.len()];
+    for (v0, v1) in tetrahedralization.edges() {
+        neighbors[v0].push(v1);
+        neighbors[v1].push(v0);
+    }
+
+    // the corners of the cells clipped to each hull facet, the cells across the facet are connected via their edges
+    let mut visited = vec![false; vertices.len()];
+    for facet in tetrahedralization.hull_facets() {
+        let mut to_visit = vec![facet[0]];
+        let mut seen = vec![facet[0]];
+        visited[facet[0]] = true;
+        while let Some(s_idx) = to_visit.pop() {
+            let s = vertices[s_idx];
+
+            // the facet, in coordinates relative to `s`
+            let mut polygon: Vec<(Vertex3, Option<usize>)> = facet
+                .iter()
+                .map(|&v_idx| (sub(vertices[v_idx], s), None))
+                .collect();
+            for &u_idx in &neighbors[s_idx] {
+                let d = sub(vertices[u_idx], s);
+                polygon = clip_polygon(&polygon, d, dot(d, d) / 2.0, Some(u_idx));
+                if polygon.is_empty() {
+                    break;
+                }
+            }
+
+            for &(p, label) in &polygon {
+                update(core::array::from_fn(|i| s[i] + p[i]), dot(p, p).sqrt());
+                if let Some(u_idx) = label {
+                    if !visited[u_idx] {
+                        visited[u_idx] = true;
+                        seen.push(u_idx);
+                        to_visit.push(u_idx);
+                    }
+                }
+            }
+        }
+
+        for v_idx in seen {
+            visited[v_idx] = false;
+        }
+    }
+
+    HowOk(largest)
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::geometry::polygon_area;
+    use rita_test_utils::sample_vertices_2d;
+    #[cfg(not(feature = "wasm"))]
+    use rita_test_utils::sample_vertices_3d;
+    #[cfg(not(feature = "wasm"))]
+    use rita_test_utils::sample_weights;
+
+    fn assert_close(a: Vertex2, b: Vertex2) {
+        assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_voronoi_cells() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        let cells = voronoi_cells(&triangulation).unwrap();
+        assert_eq!(cells.len(), 5);
+
+        // the center vertex has a bounded diamond as its cell
+        let center = &cells[4];
+        assert!(center.is_bounded());
+        assert_eq!(center.vertices.len(), 4);
+        assert!((polygon_area(&center.vertices) - 2.0).abs() < 1e-12);
+
+        // the corners are unbounded, with rays perpendicular to the hull edges
+        let corner = &cells[0];
+        assert!(!corner.is_bounded());
+        assert_eq!(corner.vertices.len(), 2);
+        assert_close(corner.vertices[0], [1.0, 0.0]);
+        assert_close(corner.vertices[1], [0.0, 1.0]);
+        let [ray0, ray1] = corner.rays.unwrap();
+        assert_close(ray0, [0.0, -1.0]);
+        assert_close(ray1, [-1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_voronoi_cells_random() {
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let cells = voronoi_cells(&triangulation).unwrap();
+
+        assert_eq!(
+            cells.iter().filter(|cell| !cell.is_bounded()).count(),
+            triangulation.num_hull_facets()
+        );
+        assert_eq!(
+            cells.iter().filter(|cell| !cell.is_empty()).count(),
+            triangulation.num_used_vertices()
+        );
+
+        for cell in cells.iter().filter(|cell| cell.is_bounded()) {
+            assert!(cell.vertices.len() >= 3);
+            assert!(polygon_area(&cell.vertices) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_lloyd_step() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, None, false)
+            .unwrap();
+
+        // the center is the centroid of its diamond, the corners move to the centroids of their clipped triangles
+        let relaxed = triangulation.lloyd_step([[0.0, 0.0], [2.0, 2.0]]).unwrap();
+        assert_close(relaxed[4], [1.0, 1.0]);
+        assert_close(relaxed[0], [1.0 / 3.0, 1.0 / 3.0]);
+        assert_close(relaxed[2], [5.0 / 3.0, 5.0 / 3.0]);
+
+        // cell centered grid points are a fixed point
+        let grid: Vec<Vertex2> = (0..16)
+            .map(|i| [(i % 4) as f64 + 0.5, (i / 4) as f64 + 0.5])
+            .collect();
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(&grid, None, true).unwrap();
+        let relaxed = triangulation.lloyd_step([[0.0, 0.0], [4.0, 4.0]]).unwrap();
+        for (&v, r) in grid.iter().zip(relaxed) {
+            assert_close(v, r);
+        }
+
+        // random vertices stay inside the box, and spread out
+        let bbox = [[-0.5, -0.5], [0.5, 0.5]];
+        let mut vertices = sample_vertices_2d(100, None);
+        let min_distance = |vertices: &[Vertex2]| {
+            let mut min = f64::INFINITY;
+            for (i, a) in vertices.iter().enumerate() {
+                for b in &vertices[i + 1..] {
+                    min = min.min((a[0] - b[0]).hypot(a[1] - b[1]));
+                }
+            }
+            min
+        };
+        let initial_min_distance = min_distance(&vertices);
+        for _ in 0..20 {
+            let mut triangulation = Triangulation::new(None);
+            triangulation
+                .insert_vertices(&vertices, None, true)
+                .unwrap();
+            vertices = triangulation.lloyd_step(bbox).unwrap();
+        }
+        assert!(
+            vertices
+                .iter()
+                .all(|v| v.iter().all(|x| (-0.5..=0.5).contains(x)))
+        );
+        assert!(min_distance(&vertices) > initial_min_distance);
+
+        assert!(Triangulation::new(None).lloyd_step(bbox).is_err());
+    }
+
+    #[test]
+    fn test_clipped_power_cells() {
+        // the cells partition the box
+        let bbox = [[-0.25, -0.5], [0.5, 0.25]];
+        let vertices = sample_vertices_2d(100, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+
+        let cells = clipped_power_cells(&triangulation, bbox).unwrap();
+        assert_eq!(cells.len(), vertices.len());
+        let area: f64 = cells.iter().map(|cell| polygon_area(cell)).sum();
+        assert!((area - 0.75 * 0.75).abs() < 1e-12);
+        for cell in &cells {
+            assert!(polygon_area(cell) >= 0.0);
+            assert!(cell.iter().all(|&[x, y]| {
+                (-0.25 - 1e-12..=0.5 + 1e-12).contains(&x)
+                    && (-0.5 - 1e-12..=0.25 + 1e-12).contains(&y)
+            }));
+        }
+
+        assert!(clipped_power_cells(&Triangulation::new(None), bbox).is_err());
+    }
+
+    #[test]
+    fn test_voronoi_cells_empty() {
+        assert!(voronoi_cells(&Triangulation::new(None)).is_err());
+        assert!(power_cells(&Triangulation::new(None)).is_err());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_power_cells() {
+        let vertices = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]];
+
+        // equal weights result in the Voronoi diagram
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, Some(vec![0.5; 5]), false)
+            .unwrap();
+        assert_eq!(
+            power_cells(&triangulation).unwrap(),
+            voronoi_cells(&triangulation).unwrap()
+        );
+
+        // a heavier center vertex pushes its cell boundary outwards,
+        // i.e. the diamond of area 2 grows, but stays within the square
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, Some(vec![0.0, 0.0, 0.0, 0.0, 0.5]), false)
+            .unwrap();
+        let cells = power_cells(&triangulation).unwrap();
+        let area = polygon_area(&cells[4].vertices);
+        assert!(area > 2.0 && area < 4.0);
+
+        // a redundant vertex has an empty cell, the center is inserted last here
+        let mut vertices = vertices;
+        vertices.rotate_right(1);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(vertices, Some(vec![-2.0, 1.0, 1.0, 1.0, 1.0]), false)
+            .unwrap();
+        assert_eq!(triangulation.num_redundant_vertices(), 1);
+        let cells = power_cells(&triangulation).unwrap();
+        assert!(cells[0].is_empty());
+        assert!(cells[1..].iter().all(|cell| !cell.is_empty()));
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_power_cells_random() {
+        let vertices = sample_vertices_2d(200, None);
+        let weights = sample_weights(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, Some(weights.clone()), true)
+            .unwrap();
+
+        let cells = power_cells(&triangulation).unwrap();
+
+        // exactly the vertices of the triangulation have a cell
+        let mut in_tris = vec![false; vertices.len()];
+        for v_idx in triangulation.tri_indices().into_iter().flatten() {
+            in_tris[v_idx] = true;
+        }
+        for (cell, in_tris) in cells.iter().zip(in_tris) {
+            assert_eq!(cell.is_empty(), !in_tris);
+        }
+
+        // no vertex, including the redundant ones, has a smaller power distance to a corner of a cell
+        // than the vertex of that cell
+        let power = |p: Vertex2, v_idx: usize| {
+            (p[0] - vertices[v_idx][0]).powi(2) + (p[1] - vertices[v_idx][1]).powi(2)
+                - weights[v_idx]
+        };
+        for (v_idx, cell) in cells.iter().enumerate() {
+            for &p in &cell.vertices {
+                let own = power(p, v_idx);
+                assert!((0..vertices.len()).all(|other| power(p, other) >= own - 1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn test_largest_empty_circle() {
+        // the unit square, the circle through all corners
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let mut triangulation = Triangulation::new(None);
+        triangulation.insert_vertices(square, None, false).unwrap();
+        let (center, radius) = largest_empty_circle(&triangulation).unwrap();
+        assert_close(center, [0.5, 0.5]);
+        assert!((radius - 0.5_f64.sqrt()).abs() < 1e-12);
+
+        // with a vertex in its center, the largest circles are centered on the hull
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(
+                [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]],
+                None,
+                false,
+            )
+            .unwrap();
+        let (_, radius) = largest_empty_circle(&triangulation).unwrap();
+        assert!((radius - 1.0).abs() < 1e-12);
+
+        let vertices = sample_vertices_2d(200, None);
+        let mut triangulation = Triangulation::new(None);
+        triangulation
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let (center, radius) = largest_empty_circle(&triangulation).unwrap();
+        let nearest = |p: Vertex2| {
+            vertices
+                .iter()
+                .map(|v| (p[0] - v[0]).hypot(p[1] - v[1]))
+                .fold(f64::INFINITY, f64::min)
+        };
+
+        // inside or on the counter-clockwise hull, up to rounding
+        let hull = triangulation.hull_edges();
+        let is_inside = |p: Vertex2| {
+            (0..hull.len()).all(|i| {
+                let [a, b] = [vertices[hull[i]], vertices[hull[(i + 1) % hull.len()]]];
+                (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]) > -1e-12
+            })
+        };
+
+        // the circle is empty and centered inside the hull
+        assert!((nearest(center) - radius).abs() < 1e-9);
+        assert!(is_inside(center));
+
+        // no sampled center inside the hull has a larger empty circle
+        for i in 0..=100 {
+            for j in 0..=100 {
+                let p = [i as f64 / 100.0 - 0.5, j as f64 / 100.0 - 0.5];
+                if is_inside(p) {
+                    assert!(nearest(p) <= radius + 1e-9);
+                }
+            }
+        }
+
+        assert!(largest_empty_circle(&Triangulation::new(None)).is_err());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_largest_empty_sphere() {
+        // the unit cube, the sphere through all corners
+        let cube: Vec<Vertex3> = (0..8)
+            .map(|i| [(i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64])
+            .collect();
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&cube, None, false)
+            .unwrap();
+        let (center, radius) = largest_empty_sphere(&tetrahedralization).unwrap();
+        assert!(center.iter().all(|x| (x - 0.5).abs() < 1e-12));
+        assert!((radius - 0.75_f64.sqrt()).abs() < 1e-12);
+
+        let vertices = sample_vertices_3d(100, None);
+        let mut tetrahedralization = Tetrahedralization::new(None);
+        tetrahedralization
+            .insert_vertices(&vertices, None, true)
+            .unwrap();
+        let (center, radius) = largest_empty_sphere(&tetrahedralization).unwrap();
+        let nearest = |p: Vertex3| {
+            vertices
+                .iter()
+                .map(|&v| dot(sub(p, v), sub(p, v)).sqrt())
+                .fold(f64::INFINITY, f64::min)
+        };
+        // inside or on the hull, whose facets have the inside on their positive side, up to rounding
+        let hull = tetrahedralization.hull_facets();
+        let is_inside = |p: Vertex3| {
+            hull.iter().all(|facet| {
+                let [a, b, c] = facet.map(|v_idx| vertices[v_idx]);
+                dot(cross(sub(b, a), sub(c, a)), sub(p, a)) > -1e-12
+            })
+        };
+
+        // the sphere is empty and centered inside the hull
+        assert!((nearest(center) - radius).abs() < 1e-9);
+        assert!(is_inside(center));
+
+        // no sampled center inside the hull has a larger empty sphere
+        for i in 0..=20 {
+            for j in 0..=20 {
+                for k in 0..=20 {
+                    let p = [i, j, k].map(|x| x as f64 / 20.0 - 0.5);
+                    if is_inside(p) {
+                        assert!(nearest(p) <= radius + 1e-9);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! The visibility walk, that locates a vertex by walking through the triangulation or tetrahedralization towards it.
+
+/// The number of buckets of the [`WalkStats::histogram`], walks that are longer end up in the last bucket.
+pub const NUM_WALK_BUCKETS: usize = 32;
+
+/// The walks visit at least this many tetrahedra with [`WalkCap::Auto`], before falling back to a linear scan.
+const MIN_AUTO_CAP: usize = 64;
+
+/// The maximum number of tetrahedra a visibility walk visits, before it falls back, see [`WalkFallback`].
+///
+/// The walk only fails on degenerate inputs, the cap keeps it from cycling,
+/// but a cap too small for the domain causes unnecessary O(n) fallbacks, e.g. on elongated domains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WalkCap {
+    /// A quarter of the current number of tetrahedra.
+    #[default]
+    Quarter,
+    /// A fixed number of tetrahedra.
+    Fixed(usize),
+    /// Tuned from the walks observed during the build.
+    ///
+    /// Twice the longest walk so far, but at least a quarter of the tetrahedra.
+    /// Since a walk that hits the cap counts as a walk of that length, every fallback doubles the cap.
+    Auto,
+}
+
+impl WalkCap {
+    /// The number of tetrahedra a walk may visit, given the current `num_tets` and the walks so far.
+    pub const fn max_visits(self, num_tets: usize, stats: &WalkStats) -> usize {
+        match self {
+            Self::Quarter => num_tets >> 2,
+            Self::Fixed(cap) => cap,
+            Self::Auto => {
+                let quarter = num_tets >> 2;
+                let tuned = stats.max_len.saturating_mul(2);
+                let cap = if tuned > quarter { tuned } else { quarter };
+                if cap > MIN_AUTO_CAP {
+                    cap
+                } else {
+                    MIN_AUTO_CAP
+                }
+            }
+        }
+    }
+}
+
+/// What a locate does, when the visibility walk hits the [`WalkCap`] or fails.
+///
+/// The linear scan always finds the tetrahedron, so it is the last resort of every fallback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WalkFallback {
+    /// Check all tetrahedra, one after another.
+    #[default]
+    LinearScan,
+    /// Walk again from random tetrahedra, up to `num_restarts` times, before checking all tetrahedra.
+    ///
+    /// Each walk starts from the tetrahedron nearest to the vertex among `num_samples` random ones,
+    /// which escapes cycles on degenerate inputs and brings the start close to the vertex after a poor hint.
+    /// The random choices are seeded by the vertex, so the tetrahedralization is reproducible.
+    Restarts {
+        num_restarts: usize,
+        num_samples: usize,
+    },
+}
+
+/// Statistics of the visibility walks of a triangulation or tetrahedralization, recorded since construction.
+///
+/// Reveals pathological inputs, e.g. long walks on elongated domains or many fallbacks on degenerate ones,
+/// and helps to tune the [`WalkCap`] or the hints of single insertions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalkStats {
+    /// The number of walks by length, i.e. by the number of simplices visited.
+    ///
+    /// The first bucket counts walks of length zero, the `i`-th bucket walks of length in `[2^(i-1), 2^i)`.
+    pub histogram: [usize; NUM_WALK_BUCKETS],
+    /// 3D: the number of walks, that hit the cap and fell back to a linear scan.
+    pub num_capped: usize,
+    /// The length of the longest walk.
+    pub max_len: usize,
+    /// The summed length of all walks, along with [`Self::num_walks`] the mean length of a walk.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub total_len: usize,
+    /// 3D: the number of locates, that fell back to checking all tetrahedra, as the walk hit the cap or failed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_fallbacks: usize,
+    /// 3D: the number of walks restarted from a random tetrahedron, see [`WalkFallback::Restarts`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_restarts: usize,
+    /// 2D: the number of walks, that ended beyond a hull vertex between two conceptual triangles in a weighted
+    /// triangulation, where the side is decided by the bisecting orientation tests of both hull edges.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_bisector_cases: usize,
+}
+
+impl WalkStats {
+    pub(crate) const fn new() -> Self {
+        Self {
+            histogram: [0; NUM_WALK_BUCKETS],
+            num_capped: 0,
+            max_len: 0,
+            total_len: 0,
+            num_fallbacks: 0,
+            num_restarts: 0,
+            num_bisector_cases: 0,
+        }
+    }
+
+    /// The histogram bucket of a walk of length `len`.
+    pub const fn bucket(len: usize) -> usize {
+        let bucket = (usize::BITS - len.leading_zeros()) as usize;
+        if bucket < NUM_WALK_BUCKETS {
+            bucket
+        } else {
+            NUM_WALK_BUCKETS - 1
+        }
+    }
+
+    /// The number of walks recorded.
+    pub fn num_walks(&self) -> usize {
+        self.histogram.iter().sum()
+    }
+
+    /// The mean length of the walks, zero without walks.
+    pub fn mean_len(&self) -> f64 {
+        match self.num_walks() {
+            0 => 0.0,
+            num_walks => self.total_len as f64 / num_walks as f64,
+        }
+    }
+
+    /// Record a walk of length `len`, `capped` if it hit the cap.
+    pub(crate) const fn record(&mut self, len: usize, capped: bool) {
+        self.histogram[Self::bucket(len)] += 1;
+        self.total_len += len;
+        if capped {
+            self.num_capped += 1;
+        }
+        if len > self.max_len {
+            self.max_len = len;
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "logging", feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets() {
+        assert_eq!(WalkStats::bucket(0), 0);
+        assert_eq!(WalkStats::bucket(1), 1);
+        assert_eq!(WalkStats::bucket(3), 2);
+        assert_eq!(WalkStats::bucket(4), 3);
+        assert_eq!(WalkStats::bucket(usize::MAX), NUM_WALK_BUCKETS - 1);
+    }
+
+    #[test]
+    fn test_auto_cap() {
+        let mut stats = WalkStats::new();
+        assert_eq!(WalkCap::Auto.max_visits(1000, &stats), 250);
+        assert_eq!(WalkCap::Auto.max_visits(10, &stats), MIN_AUTO_CAP);
+
+        // every walk hitting the cap doubles it
+        let cap = WalkCap::Auto.max_visits(1000, &stats);
+        stats.record(cap + 1, true);
+        assert_eq!(WalkCap::Auto.max_visits(1000, &stats), 2 * (cap + 1));
+        assert_eq!(stats.num_walks(), 1);
+        assert_eq!(stats.num_capped, 1);
+        stats.record(1, false);
+        assert_eq!(stats.mean_len(), (cap + 2) as f64 / 2.0);
+
+        assert_eq!(WalkCap::Quarter.max_visits(1000, &stats), 250);
+        assert_eq!(WalkCap::Fixed(7).max_visits(1000, &stats), 7);
+    }
+}
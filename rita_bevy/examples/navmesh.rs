@@ -0,0 +1,89 @@
+//! Navmesh-style example: left-click to insert points into a running triangulation.
+//!
+//! The triangulation is updated incrementally with `insert_vertex`
+//! and the render mesh is rebuilt from it afterwards.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use rita::Triangulation;
+use rita_bevy::triangulation_to_mesh;
+
+#[derive(Resource)]
+struct NavMesh(Triangulation);
+
+#[derive(Component)]
+struct NavMeshView;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (insert_on_click, draw_edges))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // start with the corners of the walkable area
+    let mut triangulation = Triangulation::new(None);
+    triangulation
+        .insert_vertices(
+            &[
+                [-400.0, -300.0],
+                [400.0, -300.0],
+                [400.0, 300.0],
+                [-400.0, 300.0],
+            ],
+            None,
+            true,
+        )
+        .expect("the corners are not aligned");
+
+    commands.spawn(Camera2d);
+    commands.spawn((
+        NavMeshView,
+        Mesh2d(meshes.add(triangulation_to_mesh(&triangulation))),
+        MeshMaterial2d(materials.add(Color::srgb(0.2, 0.5, 0.3))),
+    ));
+    commands.insert_resource(NavMesh(triangulation));
+}
+
+fn insert_on_click(
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    view: Single<&Mesh2d, With<NavMeshView>>,
+    mut nav_mesh: ResMut<NavMesh>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Some(position) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    let point = [f64::from(position.x), f64::from(position.y)];
+    if let Err(e) = nav_mesh.0.insert_vertex(point, None, None) {
+        warn!("Could not insert {point:?}: {e}");
+        return;
+    }
+
+    if let Some(mesh) = meshes.get_mut(&view.0) {
+        *mesh = triangulation_to_mesh(&nav_mesh.0);
+    }
+}
+
+fn draw_edges(nav_mesh: Res<NavMesh>, mut gizmos: Gizmos) {
+    for [a, b, c] in nav_mesh.0.tris() {
+        let [a, b, c] = [a, b, c].map(|v| Vec2::new(v[0] as f32, v[1] as f32));
+        gizmos.linestrip_2d([a, b, c, a], Color::WHITE);
+    }
+}
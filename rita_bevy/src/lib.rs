@@ -0,0 +1,58 @@
+//! Bevy integration for rita.
+//!
+//! Converts a [`Triangulation`] into a bevy [`Mesh`] via [`rita::mesh::MeshSink`],
+//! without collecting the triangle coordinates first.
+#![forbid(unsafe_code)]
+#![deny(unused)]
+#![warn(clippy::all, clippy::missing_const_for_fn)]
+
+use bevy::{
+    asset::RenderAssetUsages,
+    render::mesh::{Indices, Mesh, PrimitiveTopology},
+};
+use rita::{Triangulation, mesh::MeshSink};
+
+/// Collects the output of [`Triangulation::emit_into`] into buffers laid out for a bevy [`Mesh`].
+#[derive(Default)]
+pub struct BevyMeshSink {
+    positions: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+}
+
+impl MeshSink for BevyMeshSink {
+    fn reserve(&mut self, num_vertices: usize, num_triangles: usize) {
+        self.positions.reserve(num_vertices);
+        self.indices.reserve(3 * num_triangles);
+    }
+
+    fn add_vertex(&mut self, position: [f64; 2]) {
+        self.positions
+            .push([position[0] as f32, position[1] as f32, 0.0]);
+    }
+
+    fn add_triangle(&mut self, tri: [usize; 3]) {
+        self.indices.extend(tri.map(|v_idx| v_idx as u32));
+    }
+}
+
+impl BevyMeshSink {
+    /// Turn the collected buffers into a triangle list [`Mesh`] in the `z = 0` plane.
+    pub fn into_mesh(self) -> Mesh {
+        let normals = vec![[0.0, 0.0, 1.0]; self.positions.len()];
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+/// Build a bevy [`Mesh`] from the casual triangles of a [`Triangulation`].
+pub fn triangulation_to_mesh(triangulation: &Triangulation) -> Mesh {
+    let mut sink = BevyMeshSink::default();
+    triangulation.emit_into(&mut sink);
+    sink.into_mesh()
+}
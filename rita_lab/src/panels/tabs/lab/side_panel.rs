@@ -2,7 +2,7 @@ use std::ops::RangeInclusive;
 
 use egui::{Context, Ui};
 use log::info;
-use rita::Triangulation;
+use rita::{Triangulation, io::points::parse_points};
 use vertex_clustering::VertexClusterer2;
 
 use crate::{
@@ -94,7 +94,7 @@ fn vertex_generator(
             if triangulation_data.vertex_generator == VertexGenerator::FromFile
                 && ui
                     .button("📂 Open csv file")
-                    .on_hover_text("Read a csv file with the format:\nx,y\n1.0,5.0\n...")
+                    .on_hover_text("Read a csv file with the format:\nx,y[,w]\n1.0,5.0\n...")
                     .clicked()
             {
                 {
@@ -144,11 +144,17 @@ fn vertex_generator(
                             triangulation_data.vertices = utils::get_example_vertices();
                             triangulation_data.weights = Some(get_example_weights());
                         }
-                        VertexGenerator::FromFile => {
-                            triangulation_data.vertices =
-                                utils::read_vertices_from_string(&file_handler.text.clone());
-                            triangulation_data.weights = None;
-                        }
+                        VertexGenerator::FromFile => match parse_points(&file_handler.text) {
+                            Ok(points) => {
+                                triangulation_data.vertices = points.vertices;
+                                triangulation_data.weights = points.weights;
+                            }
+                            Err(error) => {
+                                log::error!("Could not read the vertices: {error}");
+                                triangulation_data.vertices.clear();
+                                triangulation_data.weights = None;
+                            }
+                        },
                     }
 
                     // Reset triangulation data, when generating new vertices
@@ -56,22 +56,6 @@ pub fn get_example_weights() -> Vec<f64> {
     ]
 }
 
-/// Read vertices from a string in CSV format
-///
-/// The string should be in the following format:
-///
-/// ```csv
-/// x,y
-/// 1.0,2.0
-/// 3.0,4.0
-/// ```
-pub fn read_vertices_from_string(input: &str) -> Vec<Vertex2> {
-    let mut reader = csv::Reader::from_reader(input.as_bytes());
-    let vertices = reader.deserialize().map(|record| record.unwrap()).collect();
-
-    vertices
-}
-
 pub fn bbox_2d(vertices: &[Vertex2]) -> (Vertex2, Vertex2) {
     let mut min = [f64::INFINITY, f64::INFINITY];
     let mut max = [f64::NEG_INFINITY, f64::NEG_INFINITY];